@@ -64,6 +64,8 @@ pub(crate) struct AppConfig {
     pub(crate) chat_images_s3: Option<S3Config>,
     /// Путь к внешнему JSON service account для FCM HTTP v1.
     pub(crate) fcm_service_account_path: Option<String>,
+    /// Хранилище файлов записей голосовых комнат.
+    pub(crate) voice_recordings: VoiceRecordingStorageConfig,
 }
 
 /// Конфигурация S3-совместимого объектного хранилища.
@@ -73,7 +75,7 @@ pub(crate) struct S3Config {
     pub(crate) endpoint: String,
     /// Регион подписи S3.
     pub(crate) region: String,
-    /// S3 bucket для хранения объектов изображений чата.
+    /// S3 bucket для хранения объектов.
     pub(crate) bucket: String,
     /// ID ключа доступа.
     pub(crate) access_key_id: String,
@@ -83,6 +85,17 @@ pub(crate) struct S3Config {
     pub(crate) force_path_style: bool,
 }
 
+/// Конфигурация хранилища записей голосовых комнат.
+#[derive(Debug, Clone)]
+pub(crate) enum VoiceRecordingStorageConfig {
+    /// Запись голосовых комнат выключена.
+    Disabled,
+    /// Файлы записей сохраняются в локальный каталог.
+    Directory(String),
+    /// Файлы записей сохраняются в S3-совместимое хранилище.
+    S3(S3Config),
+}
+
 /// Конфигурация бэкенда хранения аутентификации.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum AuthStoreConfig {
@@ -144,10 +157,11 @@ impl AppConfig {
                 "WEBTRANSPORT_TLS_RELOAD_INTERVAL_SECONDS",
                 5,
             )?,
-            chat_images_s3: optional_s3_config()?,
+            chat_images_s3: optional_s3_config("CHAT_IMAGES_S3", "chat image")?,
             fcm_service_account_path: env::var("FCM_SERVICE_ACCOUNT_PATH")
                 .ok()
                 .filter(|value| !value.trim().is_empty()),
+            voice_recordings: voice_recording_storage_config()?,
         })
     }
 
@@ -237,13 +251,13 @@ fn auth_store_config(value: &str) -> anyhow::Result<AuthStoreConfig> {
     }
 }
 
-fn optional_s3_config() -> anyhow::Result<Option<S3Config>> {
+fn optional_s3_config(prefix: &str, label: &str) -> anyhow::Result<Option<S3Config>> {
     let keys = [
-        "CHAT_IMAGES_S3_ENDPOINT",
-        "CHAT_IMAGES_S3_REGION",
-        "CHAT_IMAGES_S3_BUCKET",
-        "CHAT_IMAGES_S3_ACCESS_KEY_ID",
-        "CHAT_IMAGES_S3_SECRET_ACCESS_KEY",
+        format!("{prefix}_ENDPOINT"),
+        format!("{prefix}_REGION"),
+        format!("{prefix}_BUCKET"),
+        format!("{prefix}_ACCESS_KEY_ID"),
+        format!("{prefix}_SECRET_ACCESS_KEY"),
     ];
     let present = keys
         .iter()
@@ -258,21 +272,36 @@ fn optional_s3_config() -> anyhow::Result<Option<S3Config>> {
     }
     if present != keys.len() {
         return Err(anyhow!(
-            "chat image S3 storage is partially configured; set all of {}",
+            "{label} S3 storage is partially configured; set all of {}",
             keys.join(", ")
         ));
     }
 
     Ok(Some(S3Config {
-        endpoint: required("CHAT_IMAGES_S3_ENDPOINT")?,
-        region: required("CHAT_IMAGES_S3_REGION")?,
-        bucket: required("CHAT_IMAGES_S3_BUCKET")?,
-        access_key_id: required("CHAT_IMAGES_S3_ACCESS_KEY_ID")?,
-        secret_access_key: required("CHAT_IMAGES_S3_SECRET_ACCESS_KEY")?,
-        force_path_style: optional_bool("CHAT_IMAGES_S3_FORCE_PATH_STYLE", true)?,
+        endpoint: required(&keys[0])?,
+        region: required(&keys[1])?,
+        bucket: required(&keys[2])?,
+        access_key_id: required(&keys[3])?,
+        secret_access_key: required(&keys[4])?,
+        force_path_style: optional_bool(&format!("{prefix}_FORCE_PATH_STYLE"), true)?,
     }))
 }
 
+fn voice_recording_storage_config() -> anyhow::Result<VoiceRecordingStorageConfig> {
+    let directory = env::var("VOICE_RECORDINGS_DIR")
+        .ok()
+        .filter(|value| !value.trim().is_empty());
+    let s3 = optional_s3_config("VOICE_RECORDINGS_S3", "voice recording")?;
+    match (directory, s3) {
+        (Some(_), Some(_)) => Err(anyhow!(
+            "set either VOICE_RECORDINGS_DIR or VOICE_RECORDINGS_S3_*, not both"
+        )),
+        (Some(directory), None) => Ok(VoiceRecordingStorageConfig::Directory(directory)),
+        (None, Some(s3)) => Ok(VoiceRecordingStorageConfig::S3(s3)),
+        (None, None) => Ok(VoiceRecordingStorageConfig::Disabled),
+    }
+}
+
 fn optional_bool(key: &str, default: bool) -> anyhow::Result<bool> {
     let value = env::var(key).unwrap_or_else(|_| default.to_string());
     match value.trim().to_lowercase().as_str() {
@@ -439,6 +439,12 @@ pub(super) fn state_with_mailer() -> (AppState, Arc<TestAuthMailer>) {
         image_store: Arc::new(
             crate::features::images::infrastructure::InMemoryImageStore::default(),
         ),
+        link_preview_store: Arc::new(
+            crate::features::link_previews::infrastructure::InMemoryLinkPreviewStore::default(),
+        ),
+        link_preview_fetcher: Arc::new(
+            crate::features::link_previews::LinkPreviewFetcher::public_only(),
+        ),
         push_notifications: Arc::new(
             crate::features::push_notifications::application::PushNotifications::disabled(
                 Arc::new(InMemoryAuthStore::default()),
@@ -451,6 +457,9 @@ pub(super) fn state_with_mailer() -> (AppState, Arc<TestAuthMailer>) {
         direct_call_store: Arc::new(
             crate::features::voice_chat::infrastructure::InMemoryDirectCallStore::default(),
         ),
+        voice_recording_archive: Arc::new(
+            crate::features::voice_chat::infrastructure::DisabledVoiceRecordingArchive,
+        ),
         realtime_hub: Arc::new(RealtimeHub::default()),
         auth_keys: AuthKeys::generate_for_tests(),
         access_token_lifetime_minutes: 15,
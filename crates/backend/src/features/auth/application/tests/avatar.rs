@@ -9,6 +9,7 @@ use uuid::Uuid;
 use crate::features::auth::application::{register, update_current_user_avatar};
 use crate::features::images::application::public_image;
 use crate::features::voice_chat::infrastructure::{VoicePresence, VoicePresenceTargetKind};
+use crate::features::voice_chat::test_builders::voice_presence;
 
 use super::state;
 
@@ -79,15 +80,8 @@ async fn avatar_upload_updates_active_voice_presence() {
     state
         .voice_presence_store
         .join(VoicePresence {
-            realtime_stream_id: Uuid::new_v4(),
-            session_id: Uuid::new_v4(),
-            target_kind: VoicePresenceTargetKind::Server,
-            server_id,
-            room_id,
-            user_id,
             nickname: auth.user.nickname.clone(),
-            avatar_url: None,
-            joined_at: chrono::Utc::now(),
+            ..voice_presence(server_id, room_id, user_id)
         })
         .await;
 
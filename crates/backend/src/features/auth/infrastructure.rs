@@ -118,6 +118,13 @@ pub(crate) trait AuthStore: Send + Sync {
         limit: u64,
     ) -> anyhow::Result<Vec<UserAccount>>;
 
+    /// Находит пользователей, чей никнейм без учета регистра совпадает с одним
+    /// из переданных никнеймов в нижнем регистре.
+    async fn find_users_by_nicknames(
+        &self,
+        nicknames: &[String],
+    ) -> anyhow::Result<Vec<UserAccount>>;
+
     /// Обновляет публичный никнейм пользователя.
     async fn update_user_nickname(
         &self,
@@ -126,6 +126,13 @@ impl AuthStore for InMemoryAuthStore {
         Ok(users)
     }
 
+    async fn find_users_by_nicknames(
+        &self,
+        nicknames: &[String],
+    ) -> anyhow::Result<Vec<UserAccount>> {
+        super::in_memory_profile::find_users_by_nicknames(&self.state, nicknames)
+    }
+
     async fn update_user_nickname(
         &self,
         user_id: &Uuid,
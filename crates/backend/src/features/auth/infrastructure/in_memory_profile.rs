@@ -11,6 +11,20 @@ use crate::features::auth::infrastructure::{
     UpdateUserNicknameError, UserConflict, in_memory::poisoned,
 };
 
+/// Finds users whose lowercased nickname is one of `nicknames`.
+pub(super) fn find_users_by_nicknames(
+    state: &Mutex<InMemoryState>,
+    nicknames: &[String],
+) -> anyhow::Result<Vec<UserAccount>> {
+    let state = state.lock().map_err(|_| poisoned())?;
+    Ok(state
+        .users
+        .iter()
+        .filter(|user| nicknames.contains(&user.account.nickname.to_lowercase()))
+        .map(|user| user.account.clone())
+        .collect())
+}
+
 /// Updates a user's public nickname.
 pub(super) fn update_user_nickname(
     state: &Mutex<InMemoryState>,
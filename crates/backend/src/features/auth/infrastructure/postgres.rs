@@ -96,6 +96,13 @@ impl AuthStore for PostgresAuthStore {
             .collect())
     }
 
+    async fn find_users_by_nicknames(
+        &self,
+        nicknames: &[String],
+    ) -> anyhow::Result<Vec<UserAccount>> {
+        super::postgres_profile::find_users_by_nicknames(&self.database, nicknames).await
+    }
+
     async fn update_user_nickname(
         &self,
         user_id: &Uuid,
@@ -1,6 +1,7 @@
 //! Postgres user profile update helpers.
 
 use chrono::{DateTime, Duration, Utc};
+use sea_orm::sea_query::{Expr, Func};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set,
     TransactionTrait,
@@ -13,6 +14,30 @@ use crate::features::auth::infrastructure::entities::{
 };
 use crate::features::auth::infrastructure::{UpdateUserNicknameError, UserConflict};
 
+/// Находит пользователей, чей никнейм в нижнем регистре входит в `nicknames`.
+pub(super) async fn find_users_by_nicknames(
+    database: &DatabaseConnection,
+    nicknames: &[String],
+) -> anyhow::Result<Vec<UserAccount>> {
+    if nicknames.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(users::Entity::find()
+        .filter(
+            Expr::expr(Func::lower(Expr::col((
+                users::Entity,
+                users::Column::Nickname,
+            ))))
+            .is_in(nicknames.iter().cloned()),
+        )
+        .all(database)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
 /// Обновляет публичный никнейм пользователя.
 pub(super) async fn update_user_nickname(
     database: &DatabaseConnection,
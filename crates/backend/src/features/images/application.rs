@@ -14,10 +14,13 @@ use crate::state::AppState;
 
 const USER_AVATAR_KIND: &str = "user_avatar";
 const SERVER_AVATAR_KIND: &str = "server_avatar";
+const LINK_PREVIEW_KIND: &str = "link_preview";
 const PNG_CONTENT_TYPE: &str = "image/png";
 const DATABASE_STORAGE_BACKEND: &str = "database";
 const AVATAR_SIZE_PX: u32 = 512;
 const MAX_AVATAR_UPLOAD_BYTES: usize = 8 * 1024 * 1024;
+/// Максимальная сторона сохраняемой копии изображения карточки ссылки.
+const LINK_PREVIEW_MAX_SIDE_PX: u32 = 640;
 /// Максимальная сторона изображения, которую мы готовы декодировать.
 const MAX_DECODE_DIMENSION: u32 = 8192;
 /// Жесткий потолок аллокации памяти при декодировании.
@@ -144,6 +147,52 @@ pub(crate) async fn process_server_avatar(
     processed
 }
 
+/// Уменьшает изображение карточки ссылки и готовит его к сохранению.
+///
+/// Обработка идет через ту же глобальную очередь, что и аватары, а исходный файл
+/// всегда перекодируется в PNG, поэтому наружу отдаются только проверенные байты.
+pub(crate) async fn process_link_preview_image(
+    state: &AppState,
+    id: Uuid,
+    owner_user_id: Uuid,
+    bytes: &[u8],
+) -> anyhow::Result<NewStoredImage> {
+    let _permit = state.image_processing_queue.clone().acquire_owned().await?;
+    let decoded = decode_image_limited(bytes)?;
+    let (width, height) = decoded.dimensions();
+    if width == 0 || height == 0 {
+        anyhow::bail!("link preview image is empty");
+    }
+    let resized = if width > LINK_PREVIEW_MAX_SIDE_PX || height > LINK_PREVIEW_MAX_SIDE_PX {
+        decoded.thumbnail(LINK_PREVIEW_MAX_SIDE_PX, LINK_PREVIEW_MAX_SIDE_PX)
+    } else {
+        decoded
+    }
+    .to_rgba8();
+    let mut data = Vec::new();
+    PngEncoder::new_with_quality(&mut data, CompressionType::Best, PngFilterType::Adaptive)
+        .write_image(
+            resized.as_raw(),
+            resized.width(),
+            resized.height(),
+            image::ExtendedColorType::Rgba8,
+        )?;
+
+    Ok(NewStoredImage {
+        id,
+        owner_user_id,
+        kind: LINK_PREVIEW_KIND.to_owned(),
+        content_type: PNG_CONTENT_TYPE.to_owned(),
+        width: i32::try_from(resized.width()).unwrap_or(i32::MAX),
+        height: i32::try_from(resized.height()).unwrap_or(i32::MAX),
+        byte_size: i64::try_from(data.len()).unwrap_or(i64::MAX),
+        sha256: sha256_hex(&data),
+        storage_backend: DATABASE_STORAGE_BACKEND.to_owned(),
+        storage_key: None,
+        data: Some(data),
+    })
+}
+
 /// Загружает публичное изображение по идентификатору.
 pub(crate) async fn public_image(
     state: &AppState,
@@ -157,8 +206,10 @@ pub(crate) async fn public_image(
     else {
         return Err(AuthError::BadRequest("Изображение не найдено.".to_owned()));
     };
-    if !matches!(image.kind.as_str(), USER_AVATAR_KIND | SERVER_AVATAR_KIND)
-        || image.content_type != PNG_CONTENT_TYPE
+    if !matches!(
+        image.kind.as_str(),
+        USER_AVATAR_KIND | SERVER_AVATAR_KIND | LINK_PREVIEW_KIND
+    ) || image.content_type != PNG_CONTENT_TYPE
     {
         return Err(AuthError::BadRequest("Изображение не найдено.".to_owned()));
     }
@@ -168,6 +219,11 @@ pub(crate) async fn public_image(
 
 /// Строит публичный URL аватара по идентификатору изображения.
 pub(crate) fn avatar_url(state: &AppState, image_id: &Uuid) -> String {
+    public_image_url(state, image_id)
+}
+
+/// Строит публичный URL изображения по идентификатору.
+pub(crate) fn public_image_url(state: &AppState, image_id: &Uuid) -> String {
     format!(
         "{}/images/{}",
         state.cheenhub_api_base_url.trim_end_matches('/'),
@@ -46,6 +46,8 @@ pub(crate) struct StoredImage {
     pub(crate) height: i32,
     /// Сохраненный размер в байтах.
     pub(crate) byte_size: i64,
+    /// SHA-256 сохраненных байтов в hex.
+    pub(crate) sha256: String,
     /// Имя бэкенда хранения.
     pub(crate) storage_backend: String,
     /// Сохраненные байты изображения при хранении в базе данных.
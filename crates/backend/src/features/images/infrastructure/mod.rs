@@ -19,6 +19,9 @@ pub(crate) trait ImageStore: Send + Sync {
 
     /// Находит сохраненное изображение по идентификатору.
     async fn find_image(&self, image_id: &Uuid) -> anyhow::Result<Option<StoredImage>>;
+
+    /// Удаляет изображение; отсутствующее изображение ошибкой не считается.
+    async fn delete_image(&self, image_id: &Uuid) -> anyhow::Result<()>;
 }
 
 /// Хранилище изображений на базе Postgres.
@@ -64,6 +67,14 @@ impl ImageStore for PostgresImageStore {
             .await?
             .map(Into::into))
     }
+
+    async fn delete_image(&self, image_id: &Uuid) -> anyhow::Result<()> {
+        entities::images::Entity::delete_by_id(*image_id)
+            .exec(&self.database)
+            .await?;
+
+        Ok(())
+    }
 }
 
 /// In-memory-хранилище изображений для тестов и локальной разработки.
@@ -86,6 +97,7 @@ impl ImageStore for InMemoryImageStore {
                 width: image.width,
                 height: image.height,
                 byte_size: image.byte_size,
+                sha256: image.sha256,
                 storage_backend: image.storage_backend,
                 data: image.data,
             });
@@ -102,6 +114,15 @@ impl ImageStore for InMemoryImageStore {
             .find(|image| image.id == *image_id)
             .cloned())
     }
+
+    async fn delete_image(&self, image_id: &Uuid) -> anyhow::Result<()> {
+        self.images
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory image store lock poisoned"))?
+            .retain(|image| image.id != *image_id);
+
+        Ok(())
+    }
 }
 
 impl From<entities::images::Model> for StoredImage {
@@ -114,6 +135,7 @@ impl From<entities::images::Model> for StoredImage {
             width: row.width,
             height: row.height,
             byte_size: row.byte_size,
+            sha256: row.sha256,
             storage_backend: row.storage_backend,
             data: row.data,
         }
@@ -0,0 +1,280 @@
+//! Сценарии получения и кеширования карточек ссылок.
+
+use std::time::Duration as StdDuration;
+
+use cheenhub_contracts::rest::LinkPreviewSummary;
+use chrono::{DateTime, Duration, Utc};
+use url::Url;
+use uuid::Uuid;
+
+use crate::features::images::application as image_application;
+use crate::features::link_previews::domain::LinkPreview;
+use crate::features::link_previews::metadata::parse_page_metadata;
+use crate::state::AppState;
+
+/// Сколько ссылок одного сообщения получают карточки.
+const MAX_LINKS_PER_MESSAGE: usize = 3;
+const MAX_URL_LENGTH: usize = 2048;
+const RESOLVED_CACHE_HOURS: i64 = 24;
+const FAILED_CACHE_HOURS: i64 = 1;
+/// Общий потолок времени на страницу и ее изображение.
+const UNFURL_TIMEOUT: StdDuration = StdDuration::from_secs(15);
+
+/// Находит в тексте сообщения http(s)-ссылки для карточек в порядке появления.
+pub(crate) fn message_links(body: &str) -> Vec<String> {
+    let mut links = Vec::new();
+    for word in body.split_whitespace() {
+        let Some(start) = word.find("https://").or_else(|| word.find("http://")) else {
+            continue;
+        };
+        let link = trim_link(&word[start..]);
+        if link.len() > MAX_URL_LENGTH || links.iter().any(|saved| saved == link) {
+            continue;
+        }
+        let Ok(url) = Url::parse(link) else {
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            continue;
+        }
+        links.push(link.to_owned());
+        if links.len() == MAX_LINKS_PER_MESSAGE {
+            break;
+        }
+    }
+    links
+}
+
+/// Возвращает уже полученные карточки ссылок для каждого текста.
+///
+/// Сеть не используется: ссылки без готовой карточки просто пропускаются.
+pub(crate) async fn known_link_previews(
+    state: &AppState,
+    bodies: &[&str],
+) -> anyhow::Result<Vec<Vec<LinkPreviewSummary>>> {
+    let links = bodies
+        .iter()
+        .map(|body| message_links(body))
+        .collect::<Vec<_>>();
+    let mut all_links = links.iter().flatten().cloned().collect::<Vec<_>>();
+    all_links.sort_unstable();
+    all_links.dedup();
+    if all_links.is_empty() {
+        return Ok(vec![Vec::new(); bodies.len()]);
+    }
+    let previews = state
+        .link_preview_store
+        .find_link_previews(&all_links)
+        .await?;
+
+    Ok(links
+        .iter()
+        .map(|links| {
+            links
+                .iter()
+                .filter_map(|link| previews.iter().find(|preview| preview.url == *link))
+                .filter_map(|preview| preview_summary(state, preview))
+                .collect()
+        })
+        .collect())
+}
+
+/// Получает карточки ссылок сообщения: из кеша или загружая недостающие.
+///
+/// Возвращает только удавшиеся карточки в порядке ссылок в тексте.
+pub(crate) async fn resolve_link_previews(
+    state: &AppState,
+    owner_user_id: Uuid,
+    body: &str,
+) -> anyhow::Result<Vec<LinkPreviewSummary>> {
+    let links = message_links(body);
+    if links.is_empty() {
+        return Ok(Vec::new());
+    }
+    let cached = state.link_preview_store.find_link_previews(&links).await?;
+    let now = Utc::now();
+    let mut summaries = Vec::new();
+    for link in links {
+        let preview = match cached.iter().find(|preview| preview.url == link) {
+            Some(preview) if is_fresh(preview, now) => preview.clone(),
+            previous => {
+                let previous_image_id = previous.and_then(|preview| preview.image_id);
+                let preview =
+                    fetch_link_preview(state, owner_user_id, &link, previous_image_id).await;
+                state
+                    .link_preview_store
+                    .save_link_preview(preview.clone())
+                    .await?;
+                if let Some(previous_image_id) = previous_image_id
+                    && preview.image_id != Some(previous_image_id)
+                    && let Err(error) = state.image_store.delete_image(&previous_image_id).await
+                {
+                    tracing::warn!(
+                        url = link,
+                        image_id = %previous_image_id,
+                        %error,
+                        "failed to delete replaced link preview image"
+                    );
+                }
+                preview
+            }
+        };
+        summaries.extend(preview_summary(state, &preview));
+    }
+
+    Ok(summaries)
+}
+
+fn trim_link(link: &str) -> &str {
+    let mut link = link.trim_end_matches(['.', ',', ';', ':', '!', '?', '"', '\'', '>', ']']);
+    // Закрывающую скобку оставляем, только если она парная, как в ссылках Википедии.
+    while link.ends_with(')') && link.matches(')').count() > link.matches('(').count() {
+        link = link[..link.len() - 1].trim_end_matches(['.', ',', ';', ':', '!', '?']);
+    }
+    link
+}
+
+fn is_fresh(preview: &LinkPreview, now: DateTime<Utc>) -> bool {
+    let lifetime = if preview.resolved {
+        Duration::hours(RESOLVED_CACHE_HOURS)
+    } else {
+        Duration::hours(FAILED_CACHE_HOURS)
+    };
+    now - preview.fetched_at < lifetime
+}
+
+fn preview_summary(state: &AppState, preview: &LinkPreview) -> Option<LinkPreviewSummary> {
+    preview.resolved.then(|| LinkPreviewSummary {
+        url: preview.url.clone(),
+        title: preview.title.clone(),
+        description: preview.description.clone(),
+        site_name: preview.site_name.clone(),
+        image_url: preview
+            .image_id
+            .map(|image_id| image_application::public_image_url(state, &image_id)),
+    })
+}
+
+async fn fetch_link_preview(
+    state: &AppState,
+    owner_user_id: Uuid,
+    link: &str,
+    previous_image_id: Option<Uuid>,
+) -> LinkPreview {
+    match tokio::time::timeout(
+        UNFURL_TIMEOUT,
+        unfurl(state, owner_user_id, link, previous_image_id),
+    )
+    .await
+    {
+        Ok(Ok(preview)) => preview,
+        Ok(Err(error)) => {
+            tracing::debug!(url = link, %error, "link preview fetch failed");
+            LinkPreview::failed(link, Utc::now())
+        }
+        Err(_) => {
+            tracing::debug!(url = link, "link preview fetch timed out");
+            LinkPreview::failed(link, Utc::now())
+        }
+    }
+}
+
+async fn unfurl(
+    state: &AppState,
+    owner_user_id: Uuid,
+    link: &str,
+    previous_image_id: Option<Uuid>,
+) -> anyhow::Result<LinkPreview> {
+    let page = state
+        .link_preview_fetcher
+        .fetch_page(Url::parse(link)?)
+        .await?;
+    let metadata = parse_page_metadata(&String::from_utf8_lossy(&page.body));
+    if !metadata.is_useful() {
+        anyhow::bail!("link preview page has no title or description");
+    }
+    let image_id = match metadata
+        .image
+        .as_deref()
+        .and_then(|image| page.url.join(image).ok())
+    {
+        Some(image_url) => {
+            match store_preview_image(state, owner_user_id, image_url, previous_image_id).await {
+                Ok(image_id) => Some(image_id),
+                Err(error) => {
+                    tracing::debug!(url = link, %error, "link preview image was skipped");
+                    None
+                }
+            }
+        }
+        None => None,
+    };
+
+    Ok(LinkPreview {
+        url: link.to_owned(),
+        title: metadata.title,
+        description: metadata.description,
+        site_name: metadata
+            .site_name
+            .or_else(|| page.url.host_str().map(str::to_owned)),
+        image_id,
+        resolved: true,
+        fetched_at: Utc::now(),
+    })
+}
+
+/// Сохраняет изображение карточки.
+///
+/// Если обновленная карточка указывает на те же байты, переиспользуется
+/// прежнее изображение, чтобы обновление кеша не копило копии.
+async fn store_preview_image(
+    state: &AppState,
+    owner_user_id: Uuid,
+    url: Url,
+    previous_image_id: Option<Uuid>,
+) -> anyhow::Result<Uuid> {
+    let image = state.link_preview_fetcher.fetch_image(url).await?;
+    let image_id = Uuid::new_v4();
+    let stored =
+        image_application::process_link_preview_image(state, image_id, owner_user_id, &image.body)
+            .await?;
+    if let Some(previous_image_id) = previous_image_id
+        && state
+            .image_store
+            .find_image(&previous_image_id)
+            .await?
+            .is_some_and(|previous| previous.sha256 == stored.sha256)
+    {
+        return Ok(previous_image_id);
+    }
+    state.image_store.insert_image(stored).await?;
+    Ok(image_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::message_links;
+
+    #[test]
+    fn message_links_are_unique_trimmed_and_limited() {
+        let links = message_links(
+            "смотри https://example.com/a, (https://example.com/b) и https://example.com/a \
+             https://en.wikipedia.org/wiki/Rust_(language). ftp://example.com \
+             https://example.com/c https://example.com/d",
+        );
+
+        assert_eq!(
+            links,
+            [
+                "https://example.com/a",
+                "https://example.com/b",
+                "https://en.wikipedia.org/wiki/Rust_(language)",
+            ]
+        );
+    }
+
+    #[test]
+    fn text_without_links_has_no_links() {
+        assert!(message_links("просто текст http:// и https").is_empty());
+    }
+}
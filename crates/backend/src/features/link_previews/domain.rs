@@ -0,0 +1,41 @@
+//! Доменные модели карточек ссылок.
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// Сохраненный результат получения карточки ссылки.
+///
+/// Неудачные попытки тоже кешируются (`resolved == false`), чтобы недоступные
+/// страницы не запрашивались заново на каждое сообщение.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LinkPreview {
+    /// Ссылка в том виде, в котором она встречается в тексте сообщения.
+    pub(crate) url: String,
+    /// Заголовок страницы.
+    pub(crate) title: Option<String>,
+    /// Краткое описание страницы.
+    pub(crate) description: Option<String>,
+    /// Название сайта.
+    pub(crate) site_name: Option<String>,
+    /// Сохраненная копия изображения страницы.
+    pub(crate) image_id: Option<Uuid>,
+    /// Удалось ли получить метаданные страницы.
+    pub(crate) resolved: bool,
+    /// Время последней попытки получения.
+    pub(crate) fetched_at: DateTime<Utc>,
+}
+
+impl LinkPreview {
+    /// Создает запись о неудачной попытке получить карточку.
+    pub(crate) fn failed(url: &str, fetched_at: DateTime<Utc>) -> Self {
+        Self {
+            url: url.to_owned(),
+            title: None,
+            description: None,
+            site_name: None,
+            image_id: None,
+            resolved: false,
+            fetched_at,
+        }
+    }
+}
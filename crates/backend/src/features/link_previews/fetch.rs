@@ -0,0 +1,301 @@
+//! Загрузка страниц и изображений для карточек ссылок с защитой от SSRF.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{Context, bail};
+use reqwest::header::{ACCEPT, CONTENT_TYPE, LOCATION};
+use reqwest::redirect::Policy;
+use tokio::sync::Semaphore;
+use url::{Host, Url};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+const MAX_REDIRECTS: usize = 3;
+const MAX_CONCURRENT_FETCHES: usize = 4;
+/// Сколько байт страницы читается в поисках метаданных; остаток отбрасывается.
+const MAX_PAGE_BYTES: usize = 512 * 1024;
+const MAX_IMAGE_BYTES: usize = 5 * 1024 * 1024;
+const USER_AGENT: &str = "CheenHubBot/1.0 (link preview)";
+const PAGE_ACCEPT: &str = "text/html,application/xhtml+xml;q=0.9,*/*;q=0.1";
+const IMAGE_ACCEPT: &str = "image/png,image/jpeg,image/webp,image/gif;q=0.9";
+
+/// Загрузчик внешних ресурсов для карточек ссылок.
+///
+/// Каждый переход, включая редиректы, заново резолвится и проверяется: запросы
+/// к loopback, частным, link-local и прочим непубличным адресам отклоняются, а
+/// соединение закрепляется за проверенным адресом, чтобы повторный DNS-ответ
+/// не увел запрос во внутреннюю сеть.
+pub(crate) struct LinkPreviewFetcher {
+    allow_private_networks: bool,
+    permits: Arc<Semaphore>,
+}
+
+/// Загруженный ресурс.
+pub(super) struct FetchedResource {
+    /// Итоговый адрес после редиректов.
+    pub(super) url: Url,
+    /// MIME-тип из ответа в нижнем регистре.
+    pub(super) content_type: String,
+    /// Прочитанные байты тела ответа.
+    pub(super) body: Vec<u8>,
+}
+
+#[derive(Clone, Copy)]
+enum BodyLimit {
+    /// Отбросить байты сверх лимита.
+    Truncate(usize),
+    /// Считать ответ сверх лимита ошибкой.
+    Reject(usize),
+}
+
+impl LinkPreviewFetcher {
+    /// Создает загрузчик, которому доступны только публичные адреса.
+    pub(crate) fn public_only() -> Self {
+        Self {
+            allow_private_networks: false,
+            permits: Arc::new(Semaphore::new(MAX_CONCURRENT_FETCHES)),
+        }
+    }
+
+    /// Создает загрузчик без проверки адресов для тестов с локальным stub-сервером.
+    #[cfg(test)]
+    pub(crate) fn allowing_private_networks() -> Self {
+        Self {
+            allow_private_networks: true,
+            ..Self::public_only()
+        }
+    }
+
+    /// Загружает начало HTML-страницы.
+    pub(super) async fn fetch_page(&self, url: Url) -> anyhow::Result<FetchedResource> {
+        let page = self
+            .fetch(url, PAGE_ACCEPT, BodyLimit::Truncate(MAX_PAGE_BYTES))
+            .await?;
+        if !page.content_type.contains("html") {
+            bail!("link preview page has content type {}", page.content_type);
+        }
+        Ok(page)
+    }
+
+    /// Загружает изображение страницы целиком, если оно не больше лимита.
+    pub(super) async fn fetch_image(&self, url: Url) -> anyhow::Result<FetchedResource> {
+        let image = self
+            .fetch(url, IMAGE_ACCEPT, BodyLimit::Reject(MAX_IMAGE_BYTES))
+            .await?;
+        if !image.content_type.starts_with("image/") {
+            bail!("link preview image has content type {}", image.content_type);
+        }
+        Ok(image)
+    }
+
+    async fn fetch(
+        &self,
+        mut url: Url,
+        accept: &str,
+        limit: BodyLimit,
+    ) -> anyhow::Result<FetchedResource> {
+        let _permit = self.permits.clone().acquire_owned().await?;
+        for _ in 0..=MAX_REDIRECTS {
+            let address = self.resolve(&url).await?;
+            let host = url.host_str().context("link preview url has no host")?;
+            let client = reqwest::Client::builder()
+                .redirect(Policy::none())
+                .no_proxy()
+                .timeout(REQUEST_TIMEOUT)
+                .connect_timeout(CONNECT_TIMEOUT)
+                .user_agent(USER_AGENT)
+                .resolve(host, address)
+                .build()?;
+            let mut response = client
+                .get(url.clone())
+                .header(ACCEPT, accept)
+                .send()
+                .await?;
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get(LOCATION)
+                    .and_then(|value| value.to_str().ok())
+                    .context("link preview redirect has no location")?;
+                url = url.join(location)?;
+                continue;
+            }
+            if !response.status().is_success() {
+                bail!("link preview fetch returned {}", response.status());
+            }
+            if let BodyLimit::Reject(max_bytes) = limit
+                && response
+                    .content_length()
+                    .is_some_and(|length| length > max_bytes as u64)
+            {
+                bail!("link preview resource is larger than {max_bytes} bytes");
+            }
+            let content_type = response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let body = read_limited(&mut response, limit).await?;
+
+            return Ok(FetchedResource {
+                url,
+                content_type,
+                body,
+            });
+        }
+
+        bail!("link preview fetch exceeded {MAX_REDIRECTS} redirects")
+    }
+
+    /// Резолвит адрес ссылки и отклоняет непубличные адреса.
+    async fn resolve(&self, url: &Url) -> anyhow::Result<SocketAddr> {
+        if !matches!(url.scheme(), "http" | "https") {
+            bail!("link preview url has unsupported scheme {}", url.scheme());
+        }
+        let port = url
+            .port_or_known_default()
+            .context("link preview url has no port")?;
+        let addresses = match url.host() {
+            Some(Host::Ipv4(address)) => vec![SocketAddr::new(address.into(), port)],
+            Some(Host::Ipv6(address)) => vec![SocketAddr::new(address.into(), port)],
+            Some(Host::Domain(domain)) => tokio::net::lookup_host((domain, port))
+                .await?
+                .collect::<Vec<_>>(),
+            None => bail!("link preview url has no host"),
+        };
+        if !self.allow_private_networks
+            && addresses
+                .iter()
+                .any(|address| !is_public_address(address.ip()))
+        {
+            bail!("link preview host resolves to a non-public address");
+        }
+
+        addresses
+            .into_iter()
+            .next()
+            .context("link preview host has no addresses")
+    }
+}
+
+async fn read_limited(
+    response: &mut reqwest::Response,
+    limit: BodyLimit,
+) -> anyhow::Result<Vec<u8>> {
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        body.extend_from_slice(&chunk);
+        match limit {
+            BodyLimit::Truncate(max_bytes) if body.len() >= max_bytes => {
+                body.truncate(max_bytes);
+                break;
+            }
+            BodyLimit::Reject(max_bytes) if body.len() > max_bytes => {
+                bail!("link preview resource is larger than {max_bytes} bytes");
+            }
+            _ => {}
+        }
+    }
+    Ok(body)
+}
+
+/// Можно ли обращаться к адресу из загрузчика карточек.
+fn is_public_address(address: IpAddr) -> bool {
+    match address {
+        IpAddr::V4(address) => is_public_ipv4(address),
+        IpAddr::V6(address) => match address.to_ipv4_mapped() {
+            Some(mapped) => is_public_ipv4(mapped),
+            None => is_public_ipv6(address),
+        },
+    }
+}
+
+fn is_public_ipv4(address: Ipv4Addr) -> bool {
+    let [first, second, third, _] = address.octets();
+    !(address.is_private()
+        || address.is_loopback()
+        || address.is_link_local()
+        || address.is_unspecified()
+        || address.is_broadcast()
+        || address.is_multicast()
+        || address.is_documentation()
+        || first == 0
+        // 100.64.0.0/10: carrier-grade NAT.
+        || (first == 100 && (64..128).contains(&second))
+        // 192.0.0.0/24: IETF protocol assignments.
+        || (first == 192 && second == 0 && third == 0)
+        // 198.18.0.0/15: benchmarking.
+        || (first == 198 && (18..20).contains(&second))
+        || first >= 240)
+}
+
+fn is_public_ipv6(address: Ipv6Addr) -> bool {
+    let segments = address.segments();
+    !(address.is_loopback()
+        || address.is_unspecified()
+        || address.is_multicast()
+        // ::a.b.c.d: устаревшие IPv4-совместимые адреса.
+        || address.to_ipv4().is_some()
+        // fc00::/7: unique local.
+        || (segments[0] & 0xfe00) == 0xfc00
+        // fe80::/10: link-local.
+        || (segments[0] & 0xffc0) == 0xfe80
+        // 2001:db8::/32: documentation.
+        || (segments[0] == 0x2001 && segments[1] == 0x0db8)
+        // 64:ff9b::/96: NAT64 может вести во внутреннюю IPv4-сеть.
+        || (segments[0] == 0x64 && segments[1] == 0xff9b))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::IpAddr;
+
+    use url::Url;
+
+    use super::{LinkPreviewFetcher, is_public_address};
+
+    #[test]
+    fn private_and_special_addresses_are_not_public() {
+        for address in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+        ] {
+            let address = address.parse::<IpAddr>().expect("address should parse");
+            assert!(!is_public_address(address), "{address} should be rejected");
+        }
+        for address in ["93.184.216.34", "1.1.1.1", "2606:4700:4700::1111"] {
+            let address = address.parse::<IpAddr>().expect("address should parse");
+            assert!(is_public_address(address), "{address} should be allowed");
+        }
+    }
+
+    #[tokio::test]
+    async fn public_only_fetcher_rejects_loopback_targets() {
+        let fetcher = LinkPreviewFetcher::public_only();
+
+        for url in [
+            "http://127.0.0.1:9/",
+            "http://[::1]:9/",
+            "http://localhost:9/",
+            "file:///etc/passwd",
+        ] {
+            let url = Url::parse(url).expect("url should parse");
+            assert!(fetcher.fetch_page(url).await.is_err());
+        }
+    }
+}
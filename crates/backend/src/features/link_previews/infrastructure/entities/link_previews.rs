@@ -0,0 +1,30 @@
+//! Сущность кешированной карточки ссылки.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных карточки ссылки.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "link_previews")]
+pub struct Model {
+    /// Ссылка из текста сообщения.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub url: String,
+    /// Заголовок страницы.
+    pub title: Option<String>,
+    /// Краткое описание страницы.
+    pub description: Option<String>,
+    /// Название сайта.
+    pub site_name: Option<String>,
+    /// Сохраненная копия изображения страницы.
+    pub image_id: Option<Uuid>,
+    /// Удалось ли получить метаданные страницы.
+    pub resolved: bool,
+    /// Время последней попытки получения.
+    pub fetched_at: DateTimeUtc,
+}
+
+/// Связи карточки ссылки.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
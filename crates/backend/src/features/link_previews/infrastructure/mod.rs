@@ -0,0 +1,126 @@
+//! Инфраструктурный слой карточек ссылок.
+
+use async_trait::async_trait;
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use std::sync::Mutex;
+
+use crate::features::link_previews::domain::LinkPreview;
+
+mod entities {
+    pub(crate) mod link_previews;
+}
+
+/// Граница кеша карточек ссылок.
+#[async_trait]
+pub(crate) trait LinkPreviewStore: Send + Sync {
+    /// Находит сохраненные карточки для указанных ссылок.
+    async fn find_link_previews(&self, urls: &[String]) -> anyhow::Result<Vec<LinkPreview>>;
+
+    /// Сохраняет карточку ссылки, заменяя прежний результат.
+    async fn save_link_preview(&self, preview: LinkPreview) -> anyhow::Result<()>;
+}
+
+/// Кеш карточек ссылок на базе Postgres.
+pub(crate) struct PostgresLinkPreviewStore {
+    database: DatabaseConnection,
+}
+
+impl PostgresLinkPreviewStore {
+    /// Создает кеш карточек ссылок на базе Postgres.
+    pub(crate) fn new(database: DatabaseConnection) -> Self {
+        Self { database }
+    }
+}
+
+#[async_trait]
+impl LinkPreviewStore for PostgresLinkPreviewStore {
+    async fn find_link_previews(&self, urls: &[String]) -> anyhow::Result<Vec<LinkPreview>> {
+        if urls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(entities::link_previews::Entity::find()
+            .filter(entities::link_previews::Column::Url.is_in(urls.iter().cloned()))
+            .all(&self.database)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
+    async fn save_link_preview(&self, preview: LinkPreview) -> anyhow::Result<()> {
+        use entities::link_previews::Column;
+
+        entities::link_previews::Entity::insert(entities::link_previews::ActiveModel {
+            url: Set(preview.url),
+            title: Set(preview.title),
+            description: Set(preview.description),
+            site_name: Set(preview.site_name),
+            image_id: Set(preview.image_id),
+            resolved: Set(preview.resolved),
+            fetched_at: Set(preview.fetched_at),
+        })
+        .on_conflict(
+            OnConflict::column(Column::Url)
+                .update_columns([
+                    Column::Title,
+                    Column::Description,
+                    Column::SiteName,
+                    Column::ImageId,
+                    Column::Resolved,
+                    Column::FetchedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(&self.database)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// In-memory-кеш карточек ссылок для тестов и локальной разработки.
+#[derive(Default)]
+pub(crate) struct InMemoryLinkPreviewStore {
+    previews: Mutex<Vec<LinkPreview>>,
+}
+
+#[async_trait]
+impl LinkPreviewStore for InMemoryLinkPreviewStore {
+    async fn find_link_previews(&self, urls: &[String]) -> anyhow::Result<Vec<LinkPreview>> {
+        Ok(self
+            .previews
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory link preview store lock poisoned"))?
+            .iter()
+            .filter(|preview| urls.contains(&preview.url))
+            .cloned()
+            .collect())
+    }
+
+    async fn save_link_preview(&self, preview: LinkPreview) -> anyhow::Result<()> {
+        let mut previews = self
+            .previews
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory link preview store lock poisoned"))?;
+        previews.retain(|saved| saved.url != preview.url);
+        previews.push(preview);
+
+        Ok(())
+    }
+}
+
+impl From<entities::link_previews::Model> for LinkPreview {
+    fn from(row: entities::link_previews::Model) -> Self {
+        Self {
+            url: row.url,
+            title: row.title,
+            description: row.description,
+            site_name: row.site_name,
+            image_id: row.image_id,
+            resolved: row.resolved,
+            fetched_at: row.fetched_at,
+        }
+    }
+}
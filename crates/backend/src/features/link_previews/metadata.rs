@@ -0,0 +1,290 @@
+//! Разбор OpenGraph- и Twitter-метаданных HTML-страницы.
+
+use std::collections::HashMap;
+
+const MAX_TITLE_CHARS: usize = 300;
+const MAX_DESCRIPTION_CHARS: usize = 500;
+const MAX_SITE_NAME_CHARS: usize = 100;
+const MAX_IMAGE_URL_CHARS: usize = 2048;
+
+/// Метаданные страницы, найденные в `<head>`.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct PageMetadata {
+    /// Заголовок страницы.
+    pub(super) title: Option<String>,
+    /// Краткое описание страницы.
+    pub(super) description: Option<String>,
+    /// Название сайта.
+    pub(super) site_name: Option<String>,
+    /// Ссылка на изображение страницы, возможно относительная.
+    pub(super) image: Option<String>,
+}
+
+impl PageMetadata {
+    /// Есть ли в метаданных текст для карточки.
+    pub(super) fn is_useful(&self) -> bool {
+        self.title.is_some() || self.description.is_some()
+    }
+}
+
+/// Находит заголовок, описание, название сайта и изображение страницы.
+///
+/// OpenGraph приоритетнее Twitter-карточек, а те — обычных `<title>` и
+/// `<meta name="description">`. Разбор останавливается на `<body>`.
+pub(super) fn parse_page_metadata(html: &str) -> PageMetadata {
+    // ASCII-перевод в нижний регистр сохраняет байтовые смещения исходного текста.
+    let lower = html.to_ascii_lowercase();
+    let mut meta = HashMap::<String, String>::new();
+    let mut title = None;
+    let mut cursor = 0;
+
+    while let Some(offset) = lower[cursor..].find('<') {
+        let start = cursor + offset;
+        if lower[start..].starts_with("<!--") {
+            let Some(close) = lower[start..].find("-->") else {
+                break;
+            };
+            cursor = start + close + 3;
+            continue;
+        }
+        let Some(end) = tag_end(&lower, start) else {
+            break;
+        };
+        let name_end = lower[start + 1..end]
+            .find(|character: char| character.is_ascii_whitespace() || character == '/')
+            .map_or(end, |position| start + 1 + position);
+        cursor = end + 1;
+        match &lower[start + 1..name_end] {
+            "meta" => {
+                let attributes = attributes(&html[name_end..end]);
+                let key = attributes
+                    .get("property")
+                    .or_else(|| attributes.get("name"))
+                    .map(|key| key.to_ascii_lowercase());
+                if let (Some(key), Some(content)) = (key, attributes.get("content")) {
+                    meta.entry(key).or_insert_with(|| content.clone());
+                }
+            }
+            "title" if title.is_none() => {
+                let close = lower[cursor..]
+                    .find("</title")
+                    .map_or(lower.len(), |position| cursor + position);
+                title = Some(decode_entities(&html[cursor..close]));
+                cursor = close;
+            }
+            tag @ ("script" | "style") => {
+                let close = format!("</{tag}");
+                cursor = lower[cursor..]
+                    .find(&close)
+                    .map_or(lower.len(), |position| cursor + position);
+            }
+            "body" | "/head" => break,
+            _ => {}
+        }
+    }
+
+    let first = |keys: &[&str]| keys.iter().find_map(|key| meta.get(*key).cloned());
+    PageMetadata {
+        title: first(&["og:title", "twitter:title"])
+            .or(title)
+            .and_then(|value| clean_text(&value, MAX_TITLE_CHARS)),
+        description: first(&["og:description", "twitter:description", "description"])
+            .and_then(|value| clean_text(&value, MAX_DESCRIPTION_CHARS)),
+        site_name: first(&["og:site_name"])
+            .and_then(|value| clean_text(&value, MAX_SITE_NAME_CHARS)),
+        image: first(&[
+            "og:image:secure_url",
+            "og:image:url",
+            "og:image",
+            "twitter:image",
+            "twitter:image:src",
+        ])
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty() && value.len() <= MAX_IMAGE_URL_CHARS),
+    }
+}
+
+/// Находит `>`, закрывающий тег, пропуская кавычки в значениях атрибутов.
+fn tag_end(html: &str, start: usize) -> Option<usize> {
+    let mut quote = None;
+    for (offset, &byte) in html.as_bytes()[start + 1..].iter().enumerate() {
+        match (quote, byte) {
+            (Some(open), _) if byte == open => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(byte),
+            (None, b'>') => return Some(start + 1 + offset),
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// Разбирает атрибуты тега; имена приводятся к нижнему регистру, значения декодируются.
+fn attributes(source: &str) -> HashMap<String, String> {
+    let bytes = source.as_bytes();
+    let mut attributes = HashMap::new();
+    let mut index = 0;
+    let skip_whitespace = |index: &mut usize| {
+        while *index < bytes.len() && (bytes[*index].is_ascii_whitespace() || bytes[*index] == b'/')
+        {
+            *index += 1;
+        }
+    };
+
+    loop {
+        skip_whitespace(&mut index);
+        if index >= bytes.len() {
+            break;
+        }
+        let name_start = index;
+        while index < bytes.len()
+            && !bytes[index].is_ascii_whitespace()
+            && !matches!(bytes[index], b'=' | b'/')
+        {
+            index += 1;
+        }
+        let name = source[name_start..index].to_ascii_lowercase();
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+        let mut value = "";
+        if index < bytes.len() && bytes[index] == b'=' {
+            index += 1;
+            while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+                index += 1;
+            }
+            if index < bytes.len() && matches!(bytes[index], b'"' | b'\'') {
+                let quote = bytes[index];
+                let value_start = index + 1;
+                let value_end = source[value_start..]
+                    .bytes()
+                    .position(|byte| byte == quote)
+                    .map_or(bytes.len(), |position| value_start + position);
+                value = &source[value_start..value_end];
+                index = (value_end + 1).min(bytes.len());
+            } else {
+                let value_start = index;
+                while index < bytes.len() && !bytes[index].is_ascii_whitespace() {
+                    index += 1;
+                }
+                value = &source[value_start..index];
+            }
+        }
+        if !name.is_empty() {
+            attributes
+                .entry(name)
+                .or_insert_with(|| decode_entities(value));
+        }
+    }
+
+    attributes
+}
+
+/// Декодирует именованные и числовые HTML-сущности, встречающиеся в метаданных.
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(ampersand) = rest.find('&') {
+        decoded.push_str(&rest[..ampersand]);
+        rest = &rest[ampersand..];
+        let entity = rest
+            .char_indices()
+            .take(12)
+            .find(|(_, character)| *character == ';')
+            .and_then(|(semicolon, _)| Some((entity_char(&rest[1..semicolon])?, semicolon)));
+        match entity {
+            Some((character, semicolon)) => {
+                decoded.push(character);
+                rest = &rest[semicolon + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn entity_char(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let number = name.strip_prefix('#')?;
+            let code = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => number.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+/// Схлопывает пробелы и обрезает текст до `max_chars` символов.
+fn clean_text(value: &str, max_chars: usize) -> Option<String> {
+    let text = value.split_whitespace().collect::<Vec<_>>().join(" ");
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= max_chars {
+        return Some(text);
+    }
+    let mut truncated = text
+        .chars()
+        .take(max_chars.saturating_sub(1))
+        .collect::<String>();
+    truncated.push('…');
+    Some(truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_page_metadata;
+
+    #[test]
+    fn prefers_open_graph_over_twitter_and_title() {
+        let metadata = parse_page_metadata(
+            r#"<!doctype html><html><head>
+            <title>Plain title</title>
+            <meta name="twitter:title" content="Twitter title">
+            <meta property="og:title" content="OG &amp; title">
+            <META NAME="description" CONTENT='Short   description'>
+            <meta property="og:site_name" content="Example">
+            <meta property="og:image" content="/cover.png" />
+            </head><body><meta property="og:title" content="Ignored"></body></html>"#,
+        );
+
+        assert_eq!(metadata.title.as_deref(), Some("OG & title"));
+        assert_eq!(metadata.description.as_deref(), Some("Short description"));
+        assert_eq!(metadata.site_name.as_deref(), Some("Example"));
+        assert_eq!(metadata.image.as_deref(), Some("/cover.png"));
+    }
+
+    #[test]
+    fn falls_back_to_title_tag_and_skips_comments() {
+        let metadata = parse_page_metadata(
+            "<head><!-- <meta property=\"og:title\" content=\"Hidden\"> -->\
+             <title>\n  Привет &#8212; мир &#x21;\n</title></head>",
+        );
+
+        assert_eq!(metadata.title.as_deref(), Some("Привет — мир !"));
+        assert!(metadata.description.is_none());
+        assert!(metadata.is_useful());
+    }
+
+    #[test]
+    fn long_text_is_truncated_and_empty_page_is_not_useful() {
+        let long = "а".repeat(400);
+        let metadata =
+            parse_page_metadata(&format!("<meta property=\"og:title\" content=\"{long}\">"));
+
+        assert_eq!(metadata.title.map(|title| title.chars().count()), Some(300));
+        assert!(!parse_page_metadata("<html><body>text</body></html>").is_useful());
+    }
+}
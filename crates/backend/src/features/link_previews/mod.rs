@@ -0,0 +1,9 @@
+//! Функция карточек ссылок (предпросмотра) для сообщений чата.
+
+pub(crate) mod application;
+pub(crate) mod domain;
+mod fetch;
+pub(crate) mod infrastructure;
+mod metadata;
+
+pub(crate) use fetch::LinkPreviewFetcher;
@@ -2,6 +2,7 @@
 
 pub(crate) mod auth;
 pub(crate) mod images;
+pub(crate) mod link_previews;
 pub(crate) mod push_notifications;
 pub(crate) mod servers;
 pub(crate) mod social;
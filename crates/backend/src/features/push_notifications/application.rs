@@ -12,7 +12,7 @@ use crate::features::auth::application::require_current_user;
 use crate::features::auth::error::AuthError;
 use crate::features::auth::infrastructure::AuthStore;
 use crate::features::push_notifications::domain::{
-    DirectMessagePush, FriendRequestPush, PushPayload,
+    DirectMessagePush, FriendRequestPush, MentionPush, PushPayload,
 };
 use crate::features::push_notifications::error::PushError;
 use crate::features::push_notifications::fcm::{FcmClient, FcmSendError};
@@ -78,6 +78,16 @@ impl PushNotifications {
             .await
     }
 
+    /// Ставит уведомление об упоминании в очередь активных auth-сессий адресата.
+    pub(crate) async fn enqueue_mention(
+        &self,
+        recipient_user_id: Uuid,
+        payload: MentionPush,
+    ) -> anyhow::Result<usize> {
+        self.enqueue(recipient_user_id, PushPayload::Mention(payload))
+            .await
+    }
+
     async fn enqueue(
         &self,
         recipient_user_id: Uuid,
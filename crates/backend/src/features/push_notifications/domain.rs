@@ -99,6 +99,54 @@ impl FriendRequestPush {
     }
 }
 
+/// Содержимое push-уведомления об упоминании в сообщении комнаты.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct MentionPush {
+    /// Версия схемы data payload.
+    pub(crate) schema_version: String,
+    /// Машиночитаемый вид события.
+    pub(crate) kind: String,
+    /// Идентификатор сообщения для дедупликации.
+    pub(crate) message_id: String,
+    /// Идентификатор сервера.
+    pub(crate) server_id: String,
+    /// Идентификатор текстовой комнаты.
+    pub(crate) room_id: String,
+    /// Идентификатор автора сообщения.
+    pub(crate) sender_user_id: String,
+    /// Отображаемое имя автора сообщения.
+    pub(crate) sender_nickname: String,
+    /// Безопасно ограниченный текст для системного уведомления.
+    pub(crate) body_preview: String,
+    /// RFC 3339 время создания сообщения.
+    pub(crate) created_at: String,
+}
+
+impl MentionPush {
+    /// Собирает payload уведомления об упоминании.
+    pub(crate) fn new(
+        message_id: Uuid,
+        server_id: Uuid,
+        room_id: Uuid,
+        sender_user_id: Uuid,
+        sender_nickname: &str,
+        body_preview: &str,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            schema_version: "1".to_owned(),
+            kind: "mention".to_owned(),
+            message_id: message_id.to_string(),
+            server_id: server_id.to_string(),
+            room_id: room_id.to_string(),
+            sender_user_id: sender_user_id.to_string(),
+            sender_nickname: sender_nickname.chars().take(100).collect(),
+            body_preview: body_preview.chars().take(500).collect(),
+            created_at: created_at.to_rfc3339(),
+        }
+    }
+}
+
 /// Обратно совместимое содержимое задания push-очереди.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -107,6 +155,8 @@ pub(crate) enum PushPayload {
     DirectMessage(DirectMessagePush),
     /// Новая заявка в друзья.
     FriendRequest(FriendRequestPush),
+    /// Упоминание в сообщении комнаты.
+    Mention(MentionPush),
 }
 
 impl PushPayload {
@@ -115,6 +165,7 @@ impl PushPayload {
         match self {
             Self::DirectMessage(payload) => &payload.message_id,
             Self::FriendRequest(payload) => &payload.request_id,
+            Self::Mention(payload) => &payload.message_id,
         }
     }
 
@@ -123,6 +174,7 @@ impl PushPayload {
         match self {
             Self::DirectMessage(payload) => &payload.kind,
             Self::FriendRequest(payload) => &payload.kind,
+            Self::Mention(payload) => &payload.kind,
         }
     }
 }
@@ -155,7 +207,9 @@ pub(crate) struct PendingDelivery {
 
 #[cfg(test)]
 mod tests {
-    use super::{DirectMessagePush, FriendRequestPush, PushPayload, direct_message_preview};
+    use super::{
+        DirectMessagePush, FriendRequestPush, MentionPush, PushPayload, direct_message_preview,
+    };
     use chrono::{TimeZone, Utc};
     use serde_json::json;
     use uuid::Uuid;
@@ -220,6 +274,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn mention_payload_matches_android_data_contract() {
+        let message_id = Uuid::new_v4();
+        let server_id = Uuid::new_v4();
+        let room_id = Uuid::new_v4();
+        let sender_user_id = Uuid::new_v4();
+        let created_at = Utc
+            .with_ymd_and_hms(2026, 10, 16, 10, 20, 30)
+            .single()
+            .expect("test timestamp should be valid");
+        let payload = MentionPush::new(
+            message_id,
+            server_id,
+            room_id,
+            sender_user_id,
+            "Alice",
+            "@bob привет",
+            created_at,
+        );
+
+        let value =
+            serde_json::to_value(PushPayload::Mention(payload)).expect("payload should serialize");
+        assert_eq!(
+            value,
+            json!({
+                "schema_version": "1",
+                "kind": "mention",
+                "message_id": message_id.to_string(),
+                "server_id": server_id.to_string(),
+                "room_id": room_id.to_string(),
+                "sender_user_id": sender_user_id.to_string(),
+                "sender_nickname": "Alice",
+                "body_preview": "@bob привет",
+                "created_at": created_at.to_rfc3339(),
+            })
+        );
+        assert!(matches!(
+            serde_json::from_value::<PushPayload>(value)
+                .expect("mention payload should deserialize"),
+            PushPayload::Mention(_)
+        ));
+    }
+
     #[test]
     fn queued_payload_deserializes_both_supported_event_kinds() {
         let direct_message = json!({
@@ -5,7 +5,7 @@ use cheenhub_contracts::rest::{
     CreateServerInviteRequest, CreateServerInviteResponse, CreateServerRequest,
     CreateServerResponse, CreateServerRoomRequest, CreateServerRoomResponse,
     ListServerRoomsResponse, ListServersResponse, ServerInviteInfoResponse, ServerInviteSummary,
-    ServerRoomKind, UpdateServerRoomRequest, UpdateServerRoomResponse,
+    ServerRoomKind, ServerRoomSummary, UpdateServerRoomRequest, UpdateServerRoomResponse,
 };
 use chrono::{Duration, Utc};
 use uuid::Uuid;
@@ -13,6 +13,7 @@ use uuid::Uuid;
 use crate::features::auth::application as auth_application;
 use crate::features::servers::error::ServerError;
 use crate::features::servers::validation;
+use crate::features::text_chat::application as text_chat_application;
 use crate::state::AppState;
 
 use self::support::{
@@ -22,6 +23,7 @@ use self::support::{
 
 mod accept_invite;
 mod invite_settings;
+mod media_settings;
 mod members_settings;
 mod profile;
 mod role_settings;
@@ -31,6 +33,7 @@ pub(crate) use accept_invite::accept_invite;
 pub(crate) use invite_settings::{
     kick_server_invite_member, list_server_invites, revoke_server_invite,
 };
+pub(crate) use media_settings::{load_server_media_settings, save_server_media_settings};
 pub(crate) use members_settings::{
     assign_server_member_role, kick_server_member, list_server_members, revoke_server_member_role,
 };
@@ -289,9 +292,28 @@ pub(crate) async fn list_rooms(
         .list_server_rooms(&server.id)
         .await
         .map_err(ServerError::Internal)?;
+    let text_room_ids = rooms
+        .iter()
+        .filter(|room| room.kind != ServerRoomKind::Voice)
+        .map(|room| room.id)
+        .collect::<Vec<_>>();
+    let mut unread_counts =
+        text_chat_application::room_unread_counts(state, &user_id, &server.id, &text_room_ids)
+            .await
+            .map_err(ServerError::Internal)?;
 
     Ok(ListServerRoomsResponse {
-        rooms: rooms.iter().map(room_summary).collect(),
+        rooms: rooms
+            .iter()
+            .map(|room| {
+                let counts = unread_counts.remove(&room.id).unwrap_or_default();
+                ServerRoomSummary {
+                    unread_count: text_chat_application::saturating_u32(counts.unread_count),
+                    mention_count: text_chat_application::saturating_u32(counts.mention_count),
+                    ..room_summary(room)
+                }
+            })
+            .collect(),
     })
 }
 
@@ -8,6 +8,7 @@ use super::support::{map_auth_error, server_summary};
 use crate::features::auth::application as auth_application;
 use crate::features::servers::error::ServerError;
 use crate::features::servers::infrastructure::AcceptInviteOutcome;
+use crate::features::text_chat::application as text_chat_application;
 use crate::state::AppState;
 
 /// Принимает приглашение сервера для текущего пользователя.
@@ -99,6 +100,16 @@ pub(crate) async fn accept_invite(
                 user_id = %user_id,
                 "atomically accepted server invite"
             );
+            if let Err(error) =
+                text_chat_application::seed_server_read_states(state, &user_id, &server.id).await
+            {
+                tracing::warn!(
+                    server_id = %server.id,
+                    user_id = %user_id,
+                    %error,
+                    "failed to seed text room read markers for new server member"
+                );
+            }
             false
         }
         AcceptInviteOutcome::AlreadyMember => true,
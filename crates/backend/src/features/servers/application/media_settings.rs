@@ -0,0 +1,106 @@
+//! Server voice and video settings application flows.
+
+use cheenhub_contracts::realtime::{
+    LoadServerMediaSettings, SaveServerMediaSettings,
+    ServerMediaSettings as ServerMediaSettingsDto, ServerRolePermission,
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::features::servers::domain::ServerMediaSettings;
+use crate::features::servers::error::ServerError;
+use crate::features::servers::validation;
+use crate::features::voice_chat::application as voice_chat_application;
+use crate::state::AppState;
+
+use super::support::{parse_server_id, server_for_member_or_owner, user_has_server_permission};
+
+/// Loads voice and video limits of a server the current user belongs to.
+pub(crate) async fn load_server_media_settings(
+    state: &AppState,
+    user_id: &Uuid,
+    request: LoadServerMediaSettings,
+) -> Result<ServerMediaSettingsDto, ServerError> {
+    let server_id = parse_server_id(request.server_id)?;
+    let server = server_for_member_or_owner(state, &server_id, user_id).await?;
+    let settings = state
+        .server_store
+        .find_server_media_settings(&server.id)
+        .await
+        .map_err(ServerError::Internal)?
+        .unwrap_or_else(|| ServerMediaSettings::defaults(server.id));
+
+    tracing::debug!(
+        server_id = %server.id,
+        user_id = %user_id,
+        "loaded server media settings"
+    );
+
+    Ok(settings_dto(settings))
+}
+
+/// Saves voice and video limits and applies them to active voice rooms of the server.
+pub(crate) async fn save_server_media_settings(
+    state: &AppState,
+    user_id: &Uuid,
+    request: SaveServerMediaSettings,
+) -> Result<ServerMediaSettingsDto, ServerError> {
+    let server_id = parse_server_id(request.server_id)?;
+    let server = server_for_member_or_owner(state, &server_id, user_id).await?;
+    if !user_has_server_permission(
+        state,
+        &server,
+        user_id,
+        ServerRolePermission::ManageMediaSettings,
+    )
+    .await?
+    {
+        tracing::warn!(
+            server_id = %server.id,
+            user_id = %user_id,
+            "rejected server media settings update without permission"
+        );
+        return Err(ServerError::Unauthorized(
+            "Нет права менять настройки голоса и видео.".to_owned(),
+        ));
+    }
+    let valid = validation::server_media_settings(
+        &request.allowed_video_presets,
+        request.audio_bitrate_bps,
+        request.max_video_publishers,
+    )
+    .map_err(|message| ServerError::BadRequest(message.to_owned()))?;
+    let saved = state
+        .server_store
+        .save_server_media_settings(ServerMediaSettings {
+            server_id: server.id,
+            allowed_video_presets: valid.allowed_video_presets,
+            audio_bitrate_bps: valid.audio_bitrate_bps,
+            max_video_publishers: valid.max_video_publishers,
+            updated_by_user_id: Some(*user_id),
+            updated_at: Utc::now(),
+        })
+        .await
+        .map_err(ServerError::Internal)?;
+    voice_chat_application::apply_server_media_settings(state, &saved).await;
+
+    tracing::info!(
+        server_id = %server.id,
+        user_id = %user_id,
+        allowed_video_presets = ?saved.allowed_video_presets,
+        audio_bitrate_bps = saved.audio_bitrate_bps,
+        max_video_publishers = saved.max_video_publishers,
+        "saved server media settings"
+    );
+
+    Ok(settings_dto(saved))
+}
+
+fn settings_dto(settings: ServerMediaSettings) -> ServerMediaSettingsDto {
+    ServerMediaSettingsDto {
+        server_id: settings.server_id.to_string(),
+        allowed_video_presets: settings.allowed_video_presets,
+        audio_bitrate_bps: settings.audio_bitrate_bps,
+        max_video_publishers: settings.max_video_publishers,
+    }
+}
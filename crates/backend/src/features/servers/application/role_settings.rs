@@ -363,5 +363,9 @@ fn all_permissions() -> Vec<ServerRolePermission> {
         ServerRolePermission::ManageRoles,
         ServerRolePermission::KickVoiceMembers,
         ServerRolePermission::DeleteMessages,
+        ServerRolePermission::PinMessages,
+        ServerRolePermission::ManageMediaSettings,
+        ServerRolePermission::RecordVoiceRoom,
+        ServerRolePermission::ModerateVoiceMembers,
     ]
 }
@@ -66,6 +66,8 @@ pub(super) fn room_summary(room: &ServerRoom) -> ServerRoomSummary {
         name: room.name.clone(),
         kind: room.kind,
         position: room.position,
+        unread_count: 0,
+        mention_count: 0,
     }
 }
 
@@ -53,6 +53,12 @@ fn state_with_store(server_store: Arc<InMemoryServerStore>) -> AppState {
         image_store: Arc::new(
             crate::features::images::infrastructure::InMemoryImageStore::default(),
         ),
+        link_preview_store: Arc::new(
+            crate::features::link_previews::infrastructure::InMemoryLinkPreviewStore::default(),
+        ),
+        link_preview_fetcher: Arc::new(
+            crate::features::link_previews::LinkPreviewFetcher::public_only(),
+        ),
         push_notifications: Arc::new(
             crate::features::push_notifications::application::PushNotifications::disabled(
                 Arc::new(InMemoryAuthStore::default()),
@@ -65,6 +71,9 @@ fn state_with_store(server_store: Arc<InMemoryServerStore>) -> AppState {
         direct_call_store: Arc::new(
             crate::features::voice_chat::infrastructure::InMemoryDirectCallStore::default(),
         ),
+        voice_recording_archive: Arc::new(
+            crate::features::voice_chat::infrastructure::DisabledVoiceRecordingArchive,
+        ),
         realtime_hub: Arc::new(RealtimeHub::default()),
         auth_keys: AuthKeys::generate_for_tests(),
         access_token_lifetime_minutes: 15,
@@ -1,7 +1,10 @@
 //! Доменные модели сервера.
 
-use cheenhub_contracts::realtime::{ServerRoleKind, ServerRolePermission};
+use cheenhub_contracts::realtime::{
+    DEFAULT_AUDIO_BITRATE_BPS, DEFAULT_MAX_VIDEO_PUBLISHERS, ServerRoleKind, ServerRolePermission,
+};
 use cheenhub_contracts::rest::ServerRoomKind;
+use cheenhub_contracts::video_presets::{ALL_VIDEO_PRESETS, VideoPresetId};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
@@ -78,6 +81,39 @@ pub(crate) struct ServerRole {
     pub(crate) updated_at: DateTime<Utc>,
 }
 
+/// Сохраненные ограничения голоса и видео в голосовых комнатах сервера.
+#[derive(Debug, Clone)]
+pub(crate) struct ServerMediaSettings {
+    /// Server the settings belong to.
+    pub(crate) server_id: Uuid,
+    /// Video presets publishers may use.
+    pub(crate) allowed_video_presets: Vec<VideoPresetId>,
+    /// Microphone bitrate cap in bits per second.
+    pub(crate) audio_bitrate_bps: u32,
+    /// Maximum number of users publishing video in one room at the same time.
+    pub(crate) max_video_publishers: u32,
+    /// User that saved the settings last.
+    #[allow(dead_code)]
+    pub(crate) updated_by_user_id: Option<Uuid>,
+    /// Last settings update timestamp.
+    #[allow(dead_code)]
+    pub(crate) updated_at: DateTime<Utc>,
+}
+
+impl ServerMediaSettings {
+    /// Returns the limits of a server that never saved its own settings.
+    pub(crate) fn defaults(server_id: Uuid) -> Self {
+        Self {
+            server_id,
+            allowed_video_presets: ALL_VIDEO_PRESETS.to_vec(),
+            audio_bitrate_bps: DEFAULT_AUDIO_BITRATE_BPS,
+            max_video_publishers: DEFAULT_MAX_VIDEO_PUBLISHERS,
+            updated_by_user_id: None,
+            updated_at: Utc::now(),
+        }
+    }
+}
+
 /// Данные приглашения сервера, используемые в потоках сервера.
 #[derive(Debug, Clone)]
 pub(crate) struct ServerInvite {
@@ -2,6 +2,7 @@
 
 pub(crate) mod server_invite_uses;
 pub(crate) mod server_invites;
+pub(crate) mod server_media_settings;
 pub(crate) mod server_member_exclusions;
 pub(crate) mod server_member_roles;
 pub(crate) mod server_members;
@@ -0,0 +1,28 @@
+//! Сущность настроек голоса и видео сервера.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных настроек голоса и видео сервера.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel)]
+#[sea_orm(table_name = "server_media_settings")]
+pub struct Model {
+    /// Сервер, которому принадлежат настройки.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub server_id: Uuid,
+    /// Разрешенные пресеты видео в виде JSON-массива идентификаторов.
+    pub allowed_video_presets: Json,
+    /// Верхняя граница битрейта микрофона в битах в секунду.
+    pub audio_bitrate_bps: i32,
+    /// Максимум одновременных видеопубликаций в одной комнате.
+    pub max_video_publishers: i32,
+    /// Пользователь, последним сохранивший настройки.
+    pub updated_by_user_id: Option<Uuid>,
+    /// Временная метка последнего сохранения.
+    pub updated_at: DateTimeUtc,
+}
+
+/// Связи настроек голоса и видео сервера.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
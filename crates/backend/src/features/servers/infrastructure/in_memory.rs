@@ -7,8 +7,8 @@ use std::sync::Mutex;
 use uuid::Uuid;
 
 use crate::features::servers::domain::{
-    Server, ServerAccess, ServerInvite, ServerInviteUse, ServerMember, ServerMemberExclusion,
-    ServerRole, ServerRoom,
+    Server, ServerAccess, ServerInvite, ServerInviteUse, ServerMediaSettings, ServerMember,
+    ServerMemberExclusion, ServerRole, ServerRoom,
 };
 use crate::features::servers::infrastructure::{AcceptInviteOutcome, ServerStore};
 /// In-memory-хранилище серверов для локального запуска и тестов.
@@ -28,6 +28,7 @@ pub(super) struct InMemoryState {
     pub(super) roles: Vec<ServerRole>,
     /// (server_id, user_id, role_id, granted_by_user_id)
     pub(super) member_roles: Vec<(Uuid, Uuid, Uuid, Uuid)>,
+    pub(super) media_settings: Vec<ServerMediaSettings>,
 }
 
 #[async_trait]
@@ -433,6 +434,20 @@ impl ServerStore for InMemoryServerStore {
     ) -> anyhow::Result<()> {
         super::in_memory_roles::revoke_server_member_role(&self.state, server_id, user_id, role_id)
     }
+
+    async fn find_server_media_settings(
+        &self,
+        server_id: &Uuid,
+    ) -> anyhow::Result<Option<ServerMediaSettings>> {
+        super::in_memory_media_settings::find_server_media_settings(&self.state, server_id)
+    }
+
+    async fn save_server_media_settings(
+        &self,
+        settings: ServerMediaSettings,
+    ) -> anyhow::Result<ServerMediaSettings> {
+        super::in_memory_media_settings::save_server_media_settings(&self.state, settings)
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,38 @@
+//! In-memory server voice and video settings storage helpers.
+
+use std::sync::Mutex;
+
+use uuid::Uuid;
+
+use crate::features::servers::domain::ServerMediaSettings;
+use crate::features::servers::infrastructure::in_memory::InMemoryState;
+
+pub(super) fn find_server_media_settings(
+    state: &Mutex<InMemoryState>,
+    server_id: &Uuid,
+) -> anyhow::Result<Option<ServerMediaSettings>> {
+    let state = state.lock().map_err(|_| poisoned())?;
+
+    Ok(state
+        .media_settings
+        .iter()
+        .find(|settings| settings.server_id == *server_id)
+        .cloned())
+}
+
+pub(super) fn save_server_media_settings(
+    state: &Mutex<InMemoryState>,
+    settings: ServerMediaSettings,
+) -> anyhow::Result<ServerMediaSettings> {
+    let mut state = state.lock().map_err(|_| poisoned())?;
+    state
+        .media_settings
+        .retain(|existing| existing.server_id != settings.server_id);
+    state.media_settings.push(settings.clone());
+
+    Ok(settings)
+}
+
+fn poisoned() -> anyhow::Error {
+    anyhow::anyhow!("in-memory server store lock poisoned")
+}
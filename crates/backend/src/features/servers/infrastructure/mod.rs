@@ -3,11 +3,13 @@
 mod entities;
 mod in_memory;
 mod in_memory_invites;
+mod in_memory_media_settings;
 mod in_memory_roles;
 mod in_memory_rooms;
 mod postgres;
 mod postgres_conversions;
 mod postgres_invites;
+mod postgres_media_settings;
 mod postgres_roles;
 mod postgres_rooms;
 
@@ -18,9 +20,11 @@ use uuid::Uuid;
 
 use crate::features::servers::domain::{
     Server, ServerAccess, ServerInvite, ServerInviteUse, ServerMember, ServerMemberExclusion,
-    ServerRole, ServerRoom,
+    ServerRoom,
 };
 
+pub(crate) use crate::features::servers::domain::{ServerMediaSettings, ServerRole};
+
 pub(crate) use in_memory::InMemoryServerStore;
 pub(crate) use postgres::PostgresServerStore;
 
@@ -218,4 +222,16 @@ pub(crate) trait ServerStore: Send + Sync {
         user_id: &Uuid,
         role_id: &Uuid,
     ) -> anyhow::Result<()>;
+
+    /// Находит сохраненные настройки голоса и видео сервера.
+    async fn find_server_media_settings(
+        &self,
+        server_id: &Uuid,
+    ) -> anyhow::Result<Option<ServerMediaSettings>>;
+
+    /// Создает или заменяет настройки голоса и видео сервера.
+    async fn save_server_media_settings(
+        &self,
+        settings: ServerMediaSettings,
+    ) -> anyhow::Result<ServerMediaSettings>;
 }
@@ -11,12 +11,13 @@ use sea_orm::{
 use uuid::Uuid;
 
 use crate::features::servers::domain::{
-    Server, ServerAccess, ServerInvite, ServerInviteUse, ServerMember, ServerMemberExclusion,
-    ServerRole, ServerRoom,
+    Server, ServerAccess, ServerInvite, ServerInviteUse, ServerMediaSettings, ServerMember,
+    ServerMemberExclusion, ServerRole, ServerRoom,
 };
 use crate::features::servers::infrastructure::entities::{
     server_invite_uses, server_invites, server_member_exclusions, server_members, servers,
 };
+use crate::features::servers::infrastructure::postgres_media_settings;
 use crate::features::servers::infrastructure::postgres_roles;
 use crate::features::servers::infrastructure::postgres_rooms;
 use crate::features::servers::infrastructure::{AcceptInviteOutcome, ServerStore};
@@ -438,4 +439,18 @@ impl ServerStore for PostgresServerStore {
     ) -> anyhow::Result<()> {
         postgres_roles::revoke_server_member_role(&self.database, server_id, user_id, role_id).await
     }
+
+    async fn find_server_media_settings(
+        &self,
+        server_id: &Uuid,
+    ) -> anyhow::Result<Option<ServerMediaSettings>> {
+        postgres_media_settings::find_server_media_settings(&self.database, server_id).await
+    }
+
+    async fn save_server_media_settings(
+        &self,
+        settings: ServerMediaSettings,
+    ) -> anyhow::Result<ServerMediaSettings> {
+        postgres_media_settings::save_server_media_settings(&self.database, settings).await
+    }
 }
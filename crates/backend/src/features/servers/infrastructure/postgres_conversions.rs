@@ -76,6 +76,14 @@ pub(super) fn role_permission_as_str(
             "kick_voice_members"
         }
         cheenhub_contracts::realtime::ServerRolePermission::DeleteMessages => "delete_messages",
+        cheenhub_contracts::realtime::ServerRolePermission::PinMessages => "pin_messages",
+        cheenhub_contracts::realtime::ServerRolePermission::ManageMediaSettings => {
+            "manage_media_settings"
+        }
+        cheenhub_contracts::realtime::ServerRolePermission::RecordVoiceRoom => "record_voice_room",
+        cheenhub_contracts::realtime::ServerRolePermission::ModerateVoiceMembers => {
+            "moderate_voice_members"
+        }
     }
 }
 
@@ -112,6 +120,16 @@ pub(super) fn role_permission_from_str(
             Ok(cheenhub_contracts::realtime::ServerRolePermission::KickVoiceMembers)
         }
         "delete_messages" => Ok(cheenhub_contracts::realtime::ServerRolePermission::DeleteMessages),
+        "pin_messages" => Ok(cheenhub_contracts::realtime::ServerRolePermission::PinMessages),
+        "manage_media_settings" => {
+            Ok(cheenhub_contracts::realtime::ServerRolePermission::ManageMediaSettings)
+        }
+        "record_voice_room" => {
+            Ok(cheenhub_contracts::realtime::ServerRolePermission::RecordVoiceRoom)
+        }
+        "moderate_voice_members" => {
+            Ok(cheenhub_contracts::realtime::ServerRolePermission::ModerateVoiceMembers)
+        }
         other => Err(anyhow::anyhow!("unknown server role permission: {other}")),
     }
 }
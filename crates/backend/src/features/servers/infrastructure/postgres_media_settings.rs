@@ -0,0 +1,60 @@
+//! Postgres storage helpers for server voice and video settings.
+
+use sea_orm::sea_query::OnConflict;
+use sea_orm::{DatabaseConnection, EntityTrait, Set};
+use uuid::Uuid;
+
+use crate::features::servers::domain::ServerMediaSettings;
+use crate::features::servers::infrastructure::entities::server_media_settings;
+
+pub(super) async fn find_server_media_settings(
+    database: &DatabaseConnection,
+    server_id: &Uuid,
+) -> anyhow::Result<Option<ServerMediaSettings>> {
+    server_media_settings::Entity::find_by_id(*server_id)
+        .one(database)
+        .await?
+        .map(settings_from_model)
+        .transpose()
+}
+
+pub(super) async fn save_server_media_settings(
+    database: &DatabaseConnection,
+    settings: ServerMediaSettings,
+) -> anyhow::Result<ServerMediaSettings> {
+    let model = server_media_settings::ActiveModel {
+        server_id: Set(settings.server_id),
+        allowed_video_presets: Set(serde_json::to_value(&settings.allowed_video_presets)?),
+        audio_bitrate_bps: Set(i32::try_from(settings.audio_bitrate_bps)?),
+        max_video_publishers: Set(i32::try_from(settings.max_video_publishers)?),
+        updated_by_user_id: Set(settings.updated_by_user_id),
+        updated_at: Set(settings.updated_at),
+    };
+    server_media_settings::Entity::insert(model)
+        .on_conflict(
+            OnConflict::column(server_media_settings::Column::ServerId)
+                .update_columns([
+                    server_media_settings::Column::AllowedVideoPresets,
+                    server_media_settings::Column::AudioBitrateBps,
+                    server_media_settings::Column::MaxVideoPublishers,
+                    server_media_settings::Column::UpdatedByUserId,
+                    server_media_settings::Column::UpdatedAt,
+                ])
+                .to_owned(),
+        )
+        .exec(database)
+        .await?;
+
+    Ok(settings)
+}
+
+fn settings_from_model(row: server_media_settings::Model) -> anyhow::Result<ServerMediaSettings> {
+    Ok(ServerMediaSettings {
+        server_id: row.server_id,
+        allowed_video_presets: serde_json::from_value(row.allowed_video_presets)?,
+        audio_bitrate_bps: u32::try_from(row.audio_bitrate_bps)?,
+        max_video_publishers: u32::try_from(row.max_video_publishers)?,
+        updated_by_user_id: row.updated_by_user_id,
+        updated_at: row.updated_at,
+    })
+}
@@ -2,8 +2,9 @@
 
 use cheenhub_contracts::realtime::{
     AssignServerMemberRole, KickServerInviteMember, KickServerMember, ListServerInvites,
-    ListServerMembers, ListServerRoles, RealtimeEnvelope, RealtimeKind, RealtimeModule,
-    RejectionCode, RevokeServerInvite, RevokeServerMemberRole, SaveServerRoles, ServerKind,
+    ListServerMembers, ListServerRoles, LoadServerMediaSettings, RealtimeEnvelope, RealtimeKind,
+    RealtimeModule, RejectionCode, RevokeServerInvite, RevokeServerMemberRole,
+    SaveServerMediaSettings, SaveServerRoles, ServerKind,
 };
 use uuid::Uuid;
 
@@ -176,6 +177,40 @@ pub(crate) async fn handle(
                 Err(error) => reject_server_error(send, Some(request_id), error).await,
             }
         }
+        RealtimeKind::Server(ServerKind::LoadServerMediaSettings) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: LoadServerMediaSettings = decode_payload(&envelope)?;
+            match application::load_server_media_settings(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::Server,
+                        RealtimeKind::Server(ServerKind::ServerMediaSettings),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_server_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::Server(ServerKind::SaveServerMediaSettings) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: SaveServerMediaSettings = decode_payload(&envelope)?;
+            match application::save_server_media_settings(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::Server,
+                        RealtimeKind::Server(ServerKind::ServerMediaSettingsSaved),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_server_error(send, Some(request_id), error).await,
+            }
+        }
         RealtimeKind::Server(_) => {
             send_rejection(
                 send,
@@ -1,5 +1,10 @@
 //! Валидация входных данных сервера.
 
+use cheenhub_contracts::realtime::{
+    MAX_AUDIO_BITRATE_BPS, MAX_VIDEO_PUBLISHERS, MIN_AUDIO_BITRATE_BPS,
+};
+use cheenhub_contracts::video_presets::{ALL_VIDEO_PRESETS, VideoPresetId};
+
 /// Нормализованный ввод для создания сервера.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct ValidCreateServer {
@@ -66,9 +71,47 @@ pub(crate) fn create_server_invite(
     })
 }
 
+/// Нормализованный ввод настроек голоса и видео сервера.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ValidServerMediaSettings {
+    /// Разрешенные пресеты без повторов в каноническом порядке.
+    pub(crate) allowed_video_presets: Vec<VideoPresetId>,
+    /// Верхняя граница битрейта микрофона в битах в секунду.
+    pub(crate) audio_bitrate_bps: u32,
+    /// Максимум одновременных видеопубликаций в одной комнате.
+    pub(crate) max_video_publishers: u32,
+}
+
+/// Проверяет ввод настроек голоса и видео сервера.
+pub(crate) fn server_media_settings(
+    allowed_video_presets: &[VideoPresetId],
+    audio_bitrate_bps: u32,
+    max_video_publishers: u32,
+) -> Result<ValidServerMediaSettings, &'static str> {
+    if !(MIN_AUDIO_BITRATE_BPS..=MAX_AUDIO_BITRATE_BPS).contains(&audio_bitrate_bps) {
+        return Err("Битрейт микрофона должен быть от 16 до 64 кбит/с.");
+    }
+
+    if !(1..=MAX_VIDEO_PUBLISHERS).contains(&max_video_publishers) {
+        return Err("Лимит видео в комнате должен быть от 1 до 25 участников.");
+    }
+
+    Ok(ValidServerMediaSettings {
+        allowed_video_presets: ALL_VIDEO_PRESETS
+            .iter()
+            .copied()
+            .filter(|preset| allowed_video_presets.contains(preset))
+            .collect(),
+        audio_bitrate_bps,
+        max_video_publishers,
+    })
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{create_server, server_room};
+    use cheenhub_contracts::video_presets::VideoPresetId;
+
+    use super::{create_server, server_media_settings, server_room};
 
     #[test]
     fn trims_valid_server_name() {
@@ -129,4 +172,31 @@ mod tests {
         assert!(super::create_server_invite(None, Some(0)).is_err());
         assert!(super::create_server_invite(None, Some(366)).is_err());
     }
+
+    #[test]
+    fn media_settings_deduplicate_presets_in_canonical_order() {
+        let valid = server_media_settings(
+            &[
+                VideoPresetId::Screen1080p15,
+                VideoPresetId::Camera720p24,
+                VideoPresetId::Screen1080p15,
+            ],
+            48_000,
+            4,
+        )
+        .expect("media settings should be valid");
+
+        assert_eq!(
+            valid.allowed_video_presets,
+            vec![VideoPresetId::Camera720p24, VideoPresetId::Screen1080p15]
+        );
+    }
+
+    #[test]
+    fn rejects_media_settings_out_of_bounds() {
+        assert!(server_media_settings(&[], 8_000, 4).is_err());
+        assert!(server_media_settings(&[], 128_000, 4).is_err());
+        assert!(server_media_settings(&[], 32_000, 0).is_err());
+        assert!(server_media_settings(&[], 32_000, 26).is_err());
+    }
 }
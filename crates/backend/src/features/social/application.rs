@@ -2,6 +2,9 @@
 
 mod attachments;
 mod direct_messages;
+mod link_previews;
+mod reactions;
+mod search;
 
 use cheenhub_contracts::realtime::SocialChangeReason;
 use cheenhub_contracts::rest::{
@@ -21,11 +24,15 @@ use crate::features::social::support::{
 };
 use crate::state::AppState;
 
-pub(crate) use attachments::{attachment_summary, dm_image, upload_dm_image};
+pub(crate) use attachments::{
+    attachment_summary, dm_attachment, dm_image, image_summary, upload_dm_attachment,
+};
 pub(crate) use direct_messages::{
     list_dm_conversations, list_dm_messages, mark_dm_conversation_read, open_dm_conversation,
     send_dm_message,
 };
+pub(crate) use reactions::{add_dm_reaction, remove_dm_reaction};
+pub(crate) use search::search_dm_messages;
 
 const USER_SEARCH_LIMIT: u64 = 20;
 
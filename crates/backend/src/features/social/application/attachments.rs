@@ -1,44 +1,163 @@
-//! Изображения личных сообщений.
+//! Вложения личных сообщений.
 
-use axum::body::Bytes;
-use cheenhub_contracts::rest::{DmImageAttachmentSummary, UploadDmImageResponse};
-use image::GenericImageView;
-use sha2::{Digest, Sha256};
+use cheenhub_contracts::realtime::TextChatAttachment;
+use cheenhub_contracts::rest::{DmImageAttachmentSummary, UploadDmAttachmentResponse};
+use chrono::Utc;
 use uuid::Uuid;
 
 use crate::features::auth::application::require_current_user;
-use crate::features::images::domain::{NewStoredImage, StoredImage};
+use crate::features::images::domain::StoredImage;
+use crate::features::social::domain::DmAttachment;
 use crate::features::social::error::SocialError;
 use crate::features::social::support::{load_user_conversation, map_auth_error, parse_id};
+use crate::features::text_chat::application::{
+    TextChatApplicationError, clean_filename, contract_kind, sha256_hex, sniff_attachment,
+};
 use crate::state::AppState;
 
 const DM_IMAGE_KIND_PREFIX: &str = "direct_message_image:";
-const MAX_DM_IMAGE_BYTES: usize = 8 * 1024 * 1024;
+const DM_ATTACHMENT_OBJECT_KEY_PREFIX: &str = "dm-attachments";
 
-/// Загружает изображение, которое затем можно прикрепить к личному сообщению.
-pub(crate) async fn upload_dm_image(
+/// Загружает вложение, которое затем можно прикрепить к личному сообщению.
+///
+/// Вид и MIME-тип определяются по содержимому, лимит размера зависит от вида,
+/// как и у вложений комнат.
+pub(crate) async fn upload_dm_attachment(
     state: &AppState,
     access_token: &str,
     conversation_id: String,
-    bytes: Bytes,
-) -> Result<UploadDmImageResponse, SocialError> {
+    original_filename: Option<String>,
+    bytes: &[u8],
+) -> Result<UploadDmAttachmentResponse, SocialError> {
     let (user, _) = require_current_user(state, access_token)
         .await
         .map_err(map_auth_error)?;
     let conversation_id = parse_id(&conversation_id, "Диалог не найден.")?;
     load_user_conversation(state, &conversation_id, &user.id).await?;
-    let image = validate_image(user.id, conversation_id, bytes)?;
-    let summary = summary(&image);
+
+    let sniffed = sniff_attachment(bytes).map_err(map_sniff_error)?;
+    let attachment_id = Uuid::new_v4();
+    let object_key = format!(
+        "{DM_ATTACHMENT_OBJECT_KEY_PREFIX}/{conversation_id}/{attachment_id}.{}",
+        sniffed.extension
+    );
+    let Some(bucket) = state.chat_attachment_object_store.bucket() else {
+        tracing::warn!(
+            conversation_id = %conversation_id,
+            user_id = %user.id,
+            "rejected direct message attachment upload because S3 storage is not configured"
+        );
+        return Err(SocialError::Internal(anyhow::anyhow!(
+            "chat attachment object storage is not configured"
+        )));
+    };
+    let bucket = bucket.to_owned();
+
     state
-        .image_store
-        .insert_image(image)
+        .chat_attachment_object_store
+        .put_object(&object_key, sniffed.content_type, bytes.to_vec())
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                attachment_id = %attachment_id,
+                conversation_id = %conversation_id,
+                user_id = %user.id,
+                object_key = %object_key,
+                error = ?error,
+                "failed to upload direct message attachment to object storage"
+            );
+            SocialError::Internal(error)
+        })?;
+
+    let attachment = DmAttachment {
+        id: attachment_id,
+        conversation_id,
+        uploader_user_id: user.id,
+        bucket,
+        object_key,
+        kind: sniffed.kind,
+        content_type: sniffed.content_type.to_owned(),
+        byte_size: i64::try_from(bytes.len()).unwrap_or(i64::MAX),
+        width: sniffed
+            .width
+            .map(|width| i32::try_from(width).unwrap_or(i32::MAX)),
+        height: sniffed
+            .height
+            .map(|height| i32::try_from(height).unwrap_or(i32::MAX)),
+        sha256: sha256_hex(bytes),
+        original_filename: original_filename.and_then(clean_filename),
+        created_at: Utc::now(),
+    };
+    state
+        .social_store
+        .insert_dm_attachment(attachment.clone())
         .await
         .map_err(SocialError::Internal)?;
-    tracing::info!(conversation_id = %conversation_id, user_id = %user.id, image_id = %summary.id, "uploaded direct message image");
-    Ok(UploadDmImageResponse { image: summary })
+    tracing::info!(
+        conversation_id = %conversation_id,
+        user_id = %user.id,
+        attachment_id = %attachment_id,
+        byte_size = bytes.len(),
+        kind = attachment.kind.as_str(),
+        content_type = sniffed.content_type,
+        "uploaded direct message attachment"
+    );
+    Ok(UploadDmAttachmentResponse {
+        attachment: summary(&attachment),
+    })
 }
 
-/// Загружает байты изображения для участника диалога.
+/// Загружает байты прикреплённого вложения для участника диалога.
+pub(crate) async fn dm_attachment(
+    state: &AppState,
+    access_token: &str,
+    conversation_id: String,
+    attachment_id: String,
+) -> Result<(DmAttachment, Vec<u8>), SocialError> {
+    let (user, _) = require_current_user(state, access_token)
+        .await
+        .map_err(map_auth_error)?;
+    let conversation_id = parse_id(&conversation_id, "Диалог не найден.")?;
+    let conversation = load_user_conversation(state, &conversation_id, &user.id).await?;
+    let attachment_id = parse_id(&attachment_id, "Вложение не найдено.")?;
+    if state
+        .social_store
+        .dm_message_by_attachment_id(&conversation.id, &attachment_id)
+        .await
+        .map_err(SocialError::Internal)?
+        .is_none()
+    {
+        tracing::warn!(conversation_id = %conversation.id, user_id = %user.id, %attachment_id, "rejected unattached direct message attachment read");
+        return Err(SocialError::NotFound("Вложение не найдено.".to_owned()));
+    }
+    let attachment = state
+        .social_store
+        .find_dm_attachment(&attachment_id)
+        .await
+        .map_err(SocialError::Internal)?
+        .filter(|attachment| attachment.conversation_id == conversation.id)
+        .ok_or_else(|| SocialError::NotFound("Вложение не найдено.".to_owned()))?;
+    let object = state
+        .chat_attachment_object_store
+        .get_object(&attachment.object_key)
+        .await
+        .map_err(|error| {
+            tracing::error!(
+                attachment_id = %attachment.id,
+                conversation_id = %conversation.id,
+                user_id = %user.id,
+                object_key = %attachment.object_key,
+                error = ?error,
+                "failed to read direct message attachment from object storage"
+            );
+            SocialError::Internal(error)
+        })?;
+    Ok((attachment, object.bytes))
+}
+
+/// Загружает байты изображения старого формата для участника диалога.
+///
+/// Новые изображения загружаются как вложения; этот путь нужен для истории.
 pub(crate) async fn dm_image(
     state: &AppState,
     access_token: &str,
@@ -75,7 +194,8 @@ pub(crate) async fn dm_image(
     Ok(image)
 }
 
-pub(crate) async fn attachment_summary(
+/// Собирает метаданные изображения старого формата для сообщения.
+pub(crate) async fn image_summary(
     state: &AppState,
     conversation_id: Uuid,
     image_id: Option<Uuid>,
@@ -92,96 +212,82 @@ pub(crate) async fn attachment_summary(
         .map(|image| summary_stored(&image)))
 }
 
+/// Собирает метаданные вложения для сообщения.
+pub(crate) async fn attachment_summary(
+    state: &AppState,
+    conversation_id: Uuid,
+    attachment_id: Option<Uuid>,
+) -> Result<Option<TextChatAttachment>, SocialError> {
+    let Some(attachment_id) = attachment_id else {
+        return Ok(None);
+    };
+    Ok(state
+        .social_store
+        .find_dm_attachment(&attachment_id)
+        .await
+        .map_err(SocialError::Internal)?
+        .filter(|attachment| attachment.conversation_id == conversation_id)
+        .map(|attachment| summary(&attachment)))
+}
+
 pub(super) async fn validate_attachment_owner(
     state: &AppState,
     conversation_id: Uuid,
-    image_id: Uuid,
+    attachment_id: Uuid,
     user_id: Uuid,
 ) -> Result<(), SocialError> {
     if state
         .social_store
-        .dm_message_by_image_id(&conversation_id, &image_id)
+        .dm_message_by_attachment_id(&conversation_id, &attachment_id)
         .await
         .map_err(SocialError::Internal)?
         .is_some()
     {
-        tracing::warn!(%conversation_id, %image_id, %user_id, "rejected reused direct message image");
+        tracing::warn!(%conversation_id, %attachment_id, %user_id, "rejected reused direct message attachment");
         return Err(SocialError::BadRequest(
-            "Изображение уже прикреплено к сообщению.".to_owned(),
+            "Вложение уже прикреплено к сообщению.".to_owned(),
         ));
     }
     let valid = state
-        .image_store
-        .find_image(&image_id)
+        .social_store
+        .find_dm_attachment(&attachment_id)
         .await
         .map_err(SocialError::Internal)?
-        .is_some_and(|image| {
-            image.kind == dm_image_kind(conversation_id) && image.owner_user_id == user_id
+        .is_some_and(|attachment| {
+            attachment.conversation_id == conversation_id && attachment.uploader_user_id == user_id
         });
     if valid {
         Ok(())
     } else {
-        Err(SocialError::BadRequest(
-            "Изображение недоступно.".to_owned(),
-        ))
-    }
-}
-
-fn validate_image(
-    owner_user_id: Uuid,
-    conversation_id: Uuid,
-    bytes: Bytes,
-) -> Result<NewStoredImage, SocialError> {
-    if bytes.is_empty() || bytes.len() > MAX_DM_IMAGE_BYTES {
-        tracing::warn!(user_id = %owner_user_id, byte_size = bytes.len(), "rejected direct message image by size");
-        return Err(SocialError::BadRequest(
-            "Изображение должно быть не больше 8 МБ.".to_owned(),
-        ));
+        Err(SocialError::BadRequest("Вложение недоступно.".to_owned()))
     }
-    let format = image::guess_format(&bytes).map_err(|_| {
-        SocialError::BadRequest("Выберите изображение PNG, JPEG, GIF или WebP.".to_owned())
-    })?;
-    let content_type = match format {
-        image::ImageFormat::Png => "image/png",
-        image::ImageFormat::Jpeg => "image/jpeg",
-        image::ImageFormat::Gif => "image/gif",
-        image::ImageFormat::WebP => "image/webp",
-        _ => {
-            return Err(SocialError::BadRequest(
-                "Выберите изображение PNG, JPEG, GIF или WebP.".to_owned(),
-            ));
-        }
-    };
-    let decoded = crate::features::images::application::decode_image_limited(&bytes)
-        .map_err(|_| SocialError::BadRequest("Не удалось прочитать изображение.".to_owned()))?;
-    let (width, height) = decoded.dimensions();
-    let id = Uuid::new_v4();
-    let digest = format!("{:x}", Sha256::digest(&bytes));
-    Ok(NewStoredImage {
-        id,
-        owner_user_id,
-        kind: dm_image_kind(conversation_id),
-        content_type: content_type.to_owned(),
-        width: width as i32,
-        height: height as i32,
-        byte_size: bytes.len() as i64,
-        sha256: digest,
-        storage_backend: "database".to_owned(),
-        storage_key: None,
-        data: Some(bytes.to_vec()),
-    })
 }
 
 fn dm_image_kind(conversation_id: Uuid) -> String {
     format!("{DM_IMAGE_KIND_PREFIX}{conversation_id}")
 }
 
-fn summary(image: &NewStoredImage) -> DmImageAttachmentSummary {
-    DmImageAttachmentSummary {
-        id: image.id.to_string(),
-        content_type: image.content_type.clone(),
-        width: image.width,
-        height: image.height,
+fn summary(attachment: &DmAttachment) -> TextChatAttachment {
+    TextChatAttachment {
+        id: attachment.id.to_string(),
+        kind: contract_kind(attachment.kind),
+        content_type: attachment.content_type.clone(),
+        byte_size: attachment.byte_size,
+        width: attachment.width,
+        height: attachment.height,
+        original_filename: attachment.original_filename.clone(),
+    }
+}
+
+fn map_sniff_error(error: TextChatApplicationError) -> SocialError {
+    match error {
+        TextChatApplicationError::BadRequest(message) => SocialError::BadRequest(message),
+        TextChatApplicationError::NotFound(message) => SocialError::NotFound(message),
+        TextChatApplicationError::Unauthorized(message) => SocialError::Unauthorized(message),
+        TextChatApplicationError::Misconfigured { message, .. } => {
+            SocialError::Internal(anyhow::anyhow!(message))
+        }
+        TextChatApplicationError::Internal(error) => SocialError::Internal(error),
     }
 }
 
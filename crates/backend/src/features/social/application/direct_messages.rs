@@ -10,6 +10,7 @@ use chrono::Utc;
 use uuid::Uuid;
 
 use super::attachments::validate_attachment_owner;
+use super::link_previews::schedule_link_previews;
 use crate::features::auth::application::require_current_user;
 use crate::features::push_notifications::{DirectMessagePush, direct_message_preview};
 use crate::features::social::domain::{DmMessage, FriendshipStatus};
@@ -217,15 +218,15 @@ pub(crate) async fn send_dm_message(
     let conversation = load_user_conversation(state, &conversation_id, &current_user.id).await?;
     let friend_user_id = other_user_id(&conversation, &current_user.id);
     ensure_friendship(state, &current_user.id, &friend_user_id).await?;
-    let image_id = request
-        .image_id
+    let attachment_id = request
+        .attachment_id
         .as_deref()
-        .map(|value| parse_id(value, "Изображение недоступно."))
+        .map(|value| parse_id(value, "Вложение недоступно."))
         .transpose()?;
-    if let Some(image_id) = image_id {
-        validate_attachment_owner(state, conversation_id, image_id, current_user.id).await?;
+    if let Some(attachment_id) = attachment_id {
+        validate_attachment_owner(state, conversation_id, attachment_id, current_user.id).await?;
     }
-    let body = if request.body.trim().is_empty() && image_id.is_some() {
+    let body = if request.body.trim().is_empty() && attachment_id.is_some() {
         String::new()
     } else {
         message_body(request.body)?
@@ -239,7 +240,8 @@ pub(crate) async fn send_dm_message(
             seq: 0,
             sender_user_id: current_user.id,
             body,
-            image_id,
+            image_id: None,
+            attachment_id,
             created_at: now,
             updated_at: now,
             deleted_at: None,
@@ -253,7 +255,7 @@ pub(crate) async fn send_dm_message(
         seq = message.seq,
         "sent direct message"
     );
-    let message_preview = direct_message_preview(&message.body, message.image_id.is_some());
+    let message_preview = direct_message_preview(&message.body, message.attachment_id.is_some());
     let push_payload = DirectMessagePush::new(
         message.id,
         message.conversation_id,
@@ -302,6 +304,7 @@ pub(crate) async fn send_dm_message(
         Some(conversation_id),
     )
     .await;
+    schedule_link_previews(state, &message, [current_user.id, friend_user_id]);
     Ok(SendDmMessageResponse {
         message: message_summary(
             state,
@@ -313,7 +316,7 @@ pub(crate) async fn send_dm_message(
     })
 }
 
-async fn ensure_friendship(
+pub(super) async fn ensure_friendship(
     state: &AppState,
     current_user_id: &Uuid,
     friend_user_id: &Uuid,
@@ -333,7 +336,7 @@ async fn ensure_friendship(
     }
 }
 
-async fn recipient_last_read_seq(
+pub(super) async fn recipient_last_read_seq(
     state: &AppState,
     conversation_id: &Uuid,
     recipient_user_id: &Uuid,
@@ -0,0 +1,47 @@
+//! Карточки ссылок в личных сообщениях.
+
+use cheenhub_contracts::realtime::DirectMessagePreviewReady;
+use uuid::Uuid;
+
+use crate::features::link_previews::application as link_preview_application;
+use crate::features::social::domain::DmMessage;
+use crate::features::social::realtime::notify_direct_message_preview_ready;
+use crate::state::AppState;
+
+/// Запускает фоновое получение карточек ссылок и отправляет их обоим участникам.
+pub(super) fn schedule_link_previews(state: &AppState, message: &DmMessage, user_ids: [Uuid; 2]) {
+    if link_preview_application::message_links(&message.body).is_empty() {
+        return;
+    }
+    let state = state.clone();
+    let message = message.clone();
+    tokio::spawn(async move {
+        match link_preview_application::resolve_link_previews(
+            &state,
+            message.sender_user_id,
+            &message.body,
+        )
+        .await
+        {
+            Ok(previews) if previews.is_empty() => {}
+            Ok(previews) => {
+                notify_direct_message_preview_ready(
+                    &state,
+                    &user_ids,
+                    DirectMessagePreviewReady {
+                        conversation_id: message.conversation_id.to_string(),
+                        message_id: message.id.to_string(),
+                        previews,
+                    },
+                )
+                .await;
+            }
+            Err(error) => tracing::error!(
+                message_id = %message.id,
+                conversation_id = %message.conversation_id,
+                %error,
+                "failed to resolve direct message link previews"
+            ),
+        }
+    });
+}
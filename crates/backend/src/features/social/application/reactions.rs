@@ -0,0 +1,116 @@
+//! Реакции эмодзи на личные сообщения.
+
+use cheenhub_contracts::realtime::{
+    AddDirectMessageReaction, DirectMessageReactionsChanged, RemoveDirectMessageReaction,
+};
+use uuid::Uuid;
+
+use super::direct_messages::ensure_friendship;
+use crate::features::social::error::SocialError;
+use crate::features::social::realtime::notify_direct_message_reactions_changed;
+use crate::features::social::support::{load_user_conversation, other_user_id, parse_id};
+use crate::features::text_chat::application::{ensure_reaction_slot, reaction_summaries};
+use crate::features::text_chat::validation::reaction_emoji;
+use crate::state::AppState;
+
+/// Ставит реакцию текущего пользователя на личное сообщение.
+pub(crate) async fn add_dm_reaction(
+    state: &AppState,
+    user_id: &Uuid,
+    request: AddDirectMessageReaction,
+) -> Result<DirectMessageReactionsChanged, SocialError> {
+    change_reaction(
+        state,
+        user_id,
+        &request.conversation_id,
+        &request.message_id,
+        request.emoji,
+        true,
+    )
+    .await
+}
+
+/// Снимает реакцию текущего пользователя с личного сообщения.
+pub(crate) async fn remove_dm_reaction(
+    state: &AppState,
+    user_id: &Uuid,
+    request: RemoveDirectMessageReaction,
+) -> Result<DirectMessageReactionsChanged, SocialError> {
+    change_reaction(
+        state,
+        user_id,
+        &request.conversation_id,
+        &request.message_id,
+        request.emoji,
+        false,
+    )
+    .await
+}
+
+async fn change_reaction(
+    state: &AppState,
+    user_id: &Uuid,
+    conversation_id: &str,
+    message_id: &str,
+    emoji: String,
+    added: bool,
+) -> Result<DirectMessageReactionsChanged, SocialError> {
+    let conversation_id = parse_id(conversation_id, "Диалог не найден.")?;
+    let message_id = parse_id(message_id, "Сообщение не найдено.")?;
+    let emoji =
+        reaction_emoji(emoji).map_err(|message| SocialError::BadRequest(message.to_owned()))?;
+    let conversation = load_user_conversation(state, &conversation_id, user_id).await?;
+    let friend_user_id = other_user_id(&conversation, user_id);
+    ensure_friendship(state, user_id, &friend_user_id).await?;
+    state
+        .social_store
+        .dm_message_by_id(&conversation_id, &message_id)
+        .await
+        .map_err(SocialError::Internal)?
+        .filter(|message| message.deleted_at.is_none())
+        .ok_or_else(|| SocialError::NotFound("Сообщение не найдено.".to_owned()))?;
+
+    let changed = if added {
+        let current = state
+            .social_store
+            .dm_message_reactions(&[message_id])
+            .await
+            .map_err(SocialError::Internal)?;
+        ensure_reaction_slot(&current, &emoji)
+            .map_err(|message| SocialError::BadRequest(message.to_owned()))?;
+        state
+            .social_store
+            .add_dm_reaction(&message_id, user_id, &emoji)
+            .await
+    } else {
+        state
+            .social_store
+            .remove_dm_reaction(&message_id, user_id, &emoji)
+            .await
+    }
+    .map_err(SocialError::Internal)?;
+
+    let reactions = state
+        .social_store
+        .dm_message_reactions(&[message_id])
+        .await
+        .map_err(SocialError::Internal)?;
+    let mut payload = DirectMessageReactionsChanged {
+        conversation_id: conversation_id.to_string(),
+        message_id: message_id.to_string(),
+        user_id: user_id.to_string(),
+        emoji,
+        added,
+        reactions: reaction_summaries(&reactions, None)
+            .remove(&message_id)
+            .unwrap_or_default(),
+    };
+    if changed {
+        notify_direct_message_reactions_changed(state, &[*user_id, friend_user_id], &payload).await;
+    }
+
+    payload.reactions = reaction_summaries(&reactions, Some(user_id))
+        .remove(&message_id)
+        .unwrap_or_default();
+    Ok(payload)
+}
@@ -0,0 +1,96 @@
+//! Поиск сообщений по личным диалогам пользователя.
+
+use std::collections::HashMap;
+
+use cheenhub_contracts::rest::SearchDmMessagesResponse;
+use uuid::Uuid;
+
+use super::direct_messages::recipient_last_read_seq;
+use crate::features::auth::application::require_current_user;
+use crate::features::social::error::SocialError;
+use crate::features::social::support::{
+    load_user_conversation, map_auth_error, message_summaries, other_user_id, parse_id,
+};
+use crate::features::text_chat::validation;
+use crate::state::AppState;
+
+/// Ищет неудаленные сообщения в личных диалогах текущего пользователя.
+pub(crate) async fn search_dm_messages(
+    state: &AppState,
+    access_token: &str,
+    query: Option<String>,
+    conversation_id: Option<String>,
+    before_message_id: Option<String>,
+) -> Result<SearchDmMessagesResponse, SocialError> {
+    let (current_user, _) = require_current_user(state, access_token)
+        .await
+        .map_err(map_auth_error)?;
+    let query = validation::search_query(query.as_deref().unwrap_or_default())
+        .map_err(|message| SocialError::BadRequest(message.to_owned()))?;
+    let before_message_id = before_message_id
+        .as_deref()
+        .map(|value| parse_id(value, "Результаты поиска недоступны."))
+        .transpose()?;
+    let conversations = match conversation_id {
+        Some(conversation_id) => {
+            let conversation_id = parse_id(&conversation_id, "Диалог не найден.")?;
+            vec![load_user_conversation(state, &conversation_id, &current_user.id).await?]
+        }
+        None => state
+            .social_store
+            .conversations_for_user(&current_user.id)
+            .await
+            .map_err(SocialError::Internal)?,
+    };
+    let conversation_ids = conversations
+        .iter()
+        .map(|conversation| conversation.id)
+        .collect::<Vec<_>>();
+
+    let page = state
+        .social_store
+        .search_dm_messages(&conversation_ids, &query, before_message_id.as_ref())
+        .await
+        .map_err(|error| {
+            if before_message_id.is_some() {
+                SocialError::BadRequest("Результаты поиска недоступны.".to_owned())
+            } else {
+                SocialError::Internal(error)
+            }
+        })?;
+
+    // Статус доставки зависит от read-state собеседника, поэтому сообщения
+    // собираются по диалогам, а затем возвращаются в исходном порядке.
+    let order = page
+        .messages
+        .iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    let mut by_conversation = HashMap::<Uuid, Vec<_>>::new();
+    for message in page.messages {
+        by_conversation
+            .entry(message.conversation_id)
+            .or_default()
+            .push(message);
+    }
+    let mut summaries = HashMap::new();
+    for conversation in &conversations {
+        let Some(messages) = by_conversation.remove(&conversation.id) else {
+            continue;
+        };
+        let friend_user_id = other_user_id(conversation, &current_user.id);
+        let last_read_seq =
+            recipient_last_read_seq(state, &conversation.id, &friend_user_id).await?;
+        for summary in message_summaries(state, &current_user.id, last_read_seq, messages).await? {
+            summaries.insert(summary.id.clone(), summary);
+        }
+    }
+
+    Ok(SearchDmMessagesResponse {
+        messages: order
+            .iter()
+            .filter_map(|message_id| summaries.remove(&message_id.to_string()))
+            .collect(),
+        has_more: page.has_more,
+    })
+}
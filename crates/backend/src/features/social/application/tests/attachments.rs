@@ -0,0 +1,182 @@
+use cheenhub_contracts::realtime::TextChatAttachmentKind;
+use cheenhub_contracts::rest::{
+    OpenDmConversationRequest, SendDmMessageRequest, SendFriendRequestRequest,
+};
+
+use super::super::{
+    accept_friend_request, dm_attachment, open_dm_conversation, send_dm_message,
+    send_friend_request, upload_dm_attachment,
+};
+use super::{registered_user, setup_pair, test_png};
+use crate::features::social::SocialError;
+
+#[tokio::test]
+async fn direct_message_attachment_upload_send_and_load_is_scoped_and_single_use() {
+    let setup = setup_pair().await;
+    let bytes = b"%PDF-1.7\nnot really a document".to_vec();
+    let uploaded = upload_dm_attachment(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id.clone(),
+        Some("отчет.pdf".to_owned()),
+        &bytes,
+    )
+    .await
+    .expect("attachment should upload")
+    .attachment;
+    assert_eq!(uploaded.kind, TextChatAttachmentKind::File);
+    assert_eq!(uploaded.original_filename.as_deref(), Some("отчет.pdf"));
+
+    let orphan_error = dm_attachment(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id.clone(),
+        uploaded.id.clone(),
+    )
+    .await
+    .expect_err("unattached attachment should not be readable");
+    assert!(matches!(orphan_error, SocialError::NotFound(_)));
+
+    let sent = send_dm_message(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id.clone(),
+        SendDmMessageRequest {
+            body: String::new(),
+            attachment_id: Some(uploaded.id.clone()),
+        },
+    )
+    .await
+    .expect("attachment message should send")
+    .message;
+    assert!(sent.body.is_empty());
+    assert!(sent.image.is_none());
+    assert_eq!(sent.attachment.as_ref(), Some(&uploaded));
+
+    let (loaded, loaded_bytes) = dm_attachment(
+        &setup.state,
+        &setup.bob_access_token,
+        setup.conversation_id.clone(),
+        uploaded.id.clone(),
+    )
+    .await
+    .expect("conversation participant should load attached file");
+    assert_eq!(loaded_bytes, bytes);
+    assert_eq!(loaded.content_type, uploaded.content_type);
+
+    let reused = send_dm_message(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id,
+        SendDmMessageRequest {
+            body: String::new(),
+            attachment_id: Some(uploaded.id),
+        },
+    )
+    .await
+    .expect_err("attached file must not be reusable");
+    assert!(matches!(reused, SocialError::BadRequest(_)));
+}
+
+#[tokio::test]
+async fn direct_message_image_attachment_is_sniffed_by_content() {
+    let setup = setup_pair().await;
+
+    let uploaded = upload_dm_attachment(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id.clone(),
+        Some("photo.txt".to_owned()),
+        &test_png(),
+    )
+    .await
+    .expect("image should upload")
+    .attachment;
+    assert_eq!(uploaded.kind, TextChatAttachmentKind::Image);
+    assert_eq!(uploaded.content_type, "image/png");
+    assert_eq!((uploaded.width, uploaded.height), (Some(2), Some(2)));
+
+    let empty = upload_dm_attachment(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id,
+        None,
+        &[],
+    )
+    .await
+    .expect_err("empty upload should be rejected");
+    assert!(matches!(empty, SocialError::BadRequest(_)));
+}
+
+#[tokio::test]
+async fn direct_message_attachment_is_hidden_from_unrelated_conversation_member() {
+    let setup = setup_pair().await;
+    let charlie = registered_user(&setup.state, "charlie_dm", "charlie-dm@example.com").await;
+    let dave = registered_user(&setup.state, "dave_dm", "dave-dm@example.com").await;
+    let request = send_friend_request(
+        &setup.state,
+        &charlie.access_token,
+        SendFriendRequestRequest {
+            recipient_user_id: dave.user.id.clone(),
+        },
+    )
+    .await
+    .expect("second pair request should send");
+    accept_friend_request(&setup.state, &dave.access_token, request.request.id)
+        .await
+        .expect("second pair request should accept");
+    let foreign_conversation = open_dm_conversation(
+        &setup.state,
+        &charlie.access_token,
+        OpenDmConversationRequest {
+            friend_user_id: dave.user.id.clone(),
+        },
+    )
+    .await
+    .expect("second pair conversation should open")
+    .conversation;
+    let uploaded = upload_dm_attachment(
+        &setup.state,
+        &charlie.access_token,
+        foreign_conversation.id.clone(),
+        None,
+        &test_png(),
+    )
+    .await
+    .expect("foreign attachment should upload")
+    .attachment;
+    send_dm_message(
+        &setup.state,
+        &charlie.access_token,
+        foreign_conversation.id.clone(),
+        SendDmMessageRequest {
+            body: String::new(),
+            attachment_id: Some(uploaded.id.clone()),
+        },
+    )
+    .await
+    .expect("foreign attachment should attach");
+
+    let denied = dm_attachment(
+        &setup.state,
+        &setup.alice_access_token,
+        foreign_conversation.id.clone(),
+        uploaded.id.clone(),
+    )
+    .await
+    .expect_err("unrelated user must not load attachment");
+    assert!(matches!(denied, SocialError::NotFound(_)));
+
+    let smuggled = send_dm_message(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id,
+        SendDmMessageRequest {
+            body: String::new(),
+            attachment_id: Some(uploaded.id),
+        },
+    )
+    .await
+    .expect_err("attachment from another conversation must not attach");
+    assert!(matches!(smuggled, SocialError::BadRequest(_)));
+}
@@ -0,0 +1,106 @@
+use cheenhub_contracts::realtime::{AddDirectMessageReaction, RemoveDirectMessageReaction};
+use cheenhub_contracts::rest::SendDmMessageRequest;
+use uuid::Uuid;
+
+use super::super::{add_dm_reaction, list_dm_messages, remove_dm_reaction, send_dm_message};
+use super::setup_pair;
+use crate::features::social::SocialError;
+
+#[tokio::test]
+async fn direct_message_reactions_aggregate_per_emoji_and_toggle() {
+    let setup = setup_pair().await;
+    let message = send_dm_message(
+        &setup.state,
+        &setup.bob_access_token,
+        setup.conversation_id.clone(),
+        SendDmMessageRequest {
+            attachment_id: None,
+            body: "Реагируй".to_owned(),
+        },
+    )
+    .await
+    .expect("message should send")
+    .message;
+    let alice_user_id = Uuid::parse_str(&setup.alice_user_id).expect("user id should be uuid");
+    let bob_user_id = Uuid::parse_str(&message.sender_user_id).expect("user id should be uuid");
+    let add = |emoji: &str| AddDirectMessageReaction {
+        conversation_id: setup.conversation_id.clone(),
+        message_id: message.id.clone(),
+        emoji: emoji.to_owned(),
+    };
+
+    add_dm_reaction(&setup.state, &alice_user_id, add("👍"))
+        .await
+        .expect("reaction should be added");
+    let repeated = add_dm_reaction(&setup.state, &alice_user_id, add("👍"))
+        .await
+        .expect("repeated reaction should be accepted");
+    assert_eq!(repeated.reactions.len(), 1);
+    assert_eq!(repeated.reactions[0].count, 1);
+    let changed = add_dm_reaction(&setup.state, &bob_user_id, add("👍"))
+        .await
+        .expect("friend reaction should be added");
+    assert_eq!(changed.reactions[0].count, 2);
+    assert!(changed.reactions[0].reacted);
+
+    let history = list_dm_messages(
+        &setup.state,
+        &setup.alice_access_token,
+        setup.conversation_id.clone(),
+        None,
+    )
+    .await
+    .expect("history should load");
+    assert_eq!(history.messages[0].reactions.len(), 1);
+    assert_eq!(history.messages[0].reactions[0].emoji, "👍");
+    assert!(history.messages[0].reactions[0].reacted);
+
+    let removed = remove_dm_reaction(
+        &setup.state,
+        &alice_user_id,
+        RemoveDirectMessageReaction {
+            conversation_id: setup.conversation_id.clone(),
+            message_id: message.id.clone(),
+            emoji: "👍".to_owned(),
+        },
+    )
+    .await
+    .expect("reaction should be removed");
+    assert_eq!(removed.reactions[0].count, 1);
+    assert!(!removed.reactions[0].reacted);
+
+    let invalid = add_dm_reaction(&setup.state, &alice_user_id, add("ok"))
+        .await
+        .expect_err("plain text must not become a reaction");
+    assert!(matches!(invalid, SocialError::BadRequest(_)));
+}
+
+#[tokio::test]
+async fn direct_message_reaction_requires_conversation_membership() {
+    let setup = setup_pair().await;
+    let message = send_dm_message(
+        &setup.state,
+        &setup.bob_access_token,
+        setup.conversation_id.clone(),
+        SendDmMessageRequest {
+            attachment_id: None,
+            body: "Только для нас".to_owned(),
+        },
+    )
+    .await
+    .expect("message should send")
+    .message;
+
+    let denied = add_dm_reaction(
+        &setup.state,
+        &Uuid::new_v4(),
+        AddDirectMessageReaction {
+            conversation_id: setup.conversation_id,
+            message_id: message.id,
+            emoji: "🔥".to_owned(),
+        },
+    )
+    .await
+    .expect_err("outsider must not react");
+    assert!(matches!(denied, SocialError::NotFound(_)));
+}
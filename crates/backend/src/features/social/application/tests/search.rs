@@ -0,0 +1,56 @@
+use cheenhub_contracts::rest::SendDmMessageRequest;
+
+use super::super::{search_dm_messages, send_dm_message};
+use super::setup_pair;
+use crate::features::social::SocialError;
+
+#[tokio::test]
+async fn direct_message_search_matches_bodies_across_own_conversations() {
+    let setup = setup_pair().await;
+    for (token, body) in [
+        (&setup.alice_access_token, "Встречаемся у МЕТРО в семь"),
+        (&setup.bob_access_token, "ок"),
+    ] {
+        send_dm_message(
+            &setup.state,
+            token,
+            setup.conversation_id.clone(),
+            SendDmMessageRequest {
+                attachment_id: None,
+                body: body.to_owned(),
+            },
+        )
+        .await
+        .expect("message should send");
+    }
+
+    let found = search_dm_messages(
+        &setup.state,
+        &setup.bob_access_token,
+        Some("метро".to_owned()),
+        None,
+        None,
+    )
+    .await
+    .expect("search should succeed");
+    assert_eq!(found.messages.len(), 1);
+    assert_eq!(found.messages[0].conversation_id, setup.conversation_id);
+    assert!(!found.has_more);
+
+    let scoped = search_dm_messages(
+        &setup.state,
+        &setup.alice_access_token,
+        Some("метро".to_owned()),
+        Some(setup.conversation_id.clone()),
+        None,
+    )
+    .await
+    .expect("scoped search should succeed");
+    assert_eq!(scoped.messages.len(), 1);
+    assert_eq!(scoped.messages[0].id, found.messages[0].id);
+
+    let error = search_dm_messages(&setup.state, &setup.alice_access_token, None, None, None)
+        .await
+        .expect_err("empty query should be rejected");
+    assert!(matches!(error, SocialError::BadRequest(_)));
+}
@@ -3,6 +3,8 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use crate::features::text_chat::domain::ChatAttachmentKind;
+
 /// Статус записи дружбы.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum FriendshipStatus {
@@ -86,8 +88,10 @@ pub(crate) struct DmMessage {
     pub(crate) sender_user_id: Uuid,
     /// Текст сообщения.
     pub(crate) body: String,
-    /// Идентификатор прикреплённого изображения.
+    /// Идентификатор изображения, прикреплённого в старом формате.
     pub(crate) image_id: Option<Uuid>,
+    /// Идентификатор прикреплённого типизированного вложения.
+    pub(crate) attachment_id: Option<Uuid>,
     /// Время создания.
     pub(crate) created_at: DateTime<Utc>,
     /// Время последнего обновления.
@@ -96,6 +100,37 @@ pub(crate) struct DmMessage {
     pub(crate) deleted_at: Option<DateTime<Utc>>,
 }
 
+/// Метаданные вложения личного диалога.
+#[derive(Debug, Clone)]
+pub(crate) struct DmAttachment {
+    /// Стабильный идентификатор вложения.
+    pub(crate) id: Uuid,
+    /// Диалог, которому принадлежит вложение.
+    pub(crate) conversation_id: Uuid,
+    /// Пользователь, загрузивший вложение.
+    pub(crate) uploader_user_id: Uuid,
+    /// S3 bucket, в котором хранится объект.
+    pub(crate) bucket: String,
+    /// Ключ S3-объекта.
+    pub(crate) object_key: String,
+    /// Вид вложения, определенный по содержимому.
+    pub(crate) kind: ChatAttachmentKind,
+    /// MIME-тип, определенный по содержимому.
+    pub(crate) content_type: String,
+    /// Исходный размер загрузки в байтах.
+    pub(crate) byte_size: i64,
+    /// Ширина изображения в пикселях; у остальных видов не задается.
+    pub(crate) width: Option<i32>,
+    /// Высота изображения в пикселях; у остальных видов не задается.
+    pub(crate) height: Option<i32>,
+    /// SHA-256-хэш загруженных байтов.
+    pub(crate) sha256: String,
+    /// Необязательное исходное имя файла из запроса загрузки.
+    pub(crate) original_filename: Option<String>,
+    /// Временная метка создания.
+    pub(crate) created_at: DateTime<Utc>,
+}
+
 /// Текущее состояние прочтения участника личного диалога.
 #[derive(Debug, Clone)]
 pub(crate) struct ConversationMemberState {
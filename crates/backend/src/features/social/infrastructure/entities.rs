@@ -78,8 +78,10 @@ pub mod dm_messages {
         pub sender_user_id: Uuid,
         /// Текст сообщения.
         pub body: String,
-        /// Прикреплённое изображение.
+        /// Прикреплённое изображение старого формата.
         pub image_id: Option<Uuid>,
+        /// Прикреплённое типизированное вложение.
+        pub attachment_id: Option<Uuid>,
         /// Время создания.
         pub created_at: DateTimeUtc,
         /// Время последнего изменения.
@@ -95,6 +97,80 @@ pub mod dm_messages {
     impl ActiveModelBehavior for ActiveModel {}
 }
 
+/// SeaORM-сущность вложений личных диалогов.
+pub mod dm_attachments {
+    use sea_orm::entity::prelude::*;
+
+    /// Строка вложения личного диалога.
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "dm_attachments")]
+    pub struct Model {
+        /// Стабильный идентификатор вложения.
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        /// Диалог, которому принадлежит вложение.
+        pub conversation_id: Uuid,
+        /// Пользователь, загрузивший вложение.
+        pub uploader_user_id: Uuid,
+        /// S3 bucket, в котором хранится объект.
+        pub bucket: String,
+        /// Ключ S3-объекта.
+        pub object_key: String,
+        /// Вид вложения: `image`, `video`, `audio` или `file`.
+        pub kind: String,
+        /// MIME-тип, определенный по содержимому.
+        pub content_type: String,
+        /// Исходный размер загрузки в байтах.
+        pub byte_size: i64,
+        /// Ширина изображения в пикселях.
+        pub width: Option<i32>,
+        /// Высота изображения в пикселях.
+        pub height: Option<i32>,
+        /// SHA-256-хэш загруженных байтов.
+        pub sha256: String,
+        /// Необязательное исходное имя файла из запроса загрузки.
+        pub original_filename: Option<String>,
+        /// Время создания.
+        pub created_at: DateTimeUtc,
+    }
+
+    /// Отношения вложения личного диалога.
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
+/// SeaORM-сущность реакций на сообщения; social использует строки личных сообщений.
+pub mod message_reactions {
+    use sea_orm::entity::prelude::*;
+
+    /// Строка реакции на сообщение.
+    #[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+    #[sea_orm(table_name = "message_reactions")]
+    pub struct Model {
+        /// Стабильный идентификатор реакции.
+        #[sea_orm(primary_key, auto_increment = false)]
+        pub id: Uuid,
+        /// Сообщение комнаты; для реакций в личных диалогах не задано.
+        pub text_message_id: Option<Uuid>,
+        /// Личное сообщение.
+        pub dm_message_id: Option<Uuid>,
+        /// Пользователь, поставивший реакцию.
+        pub user_id: Uuid,
+        /// Эмодзи реакции.
+        pub emoji: String,
+        /// Время установки реакции.
+        pub created_at: DateTimeUtc,
+    }
+
+    /// Отношения реакции на сообщение.
+    #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+    pub enum Relation {}
+
+    impl ActiveModelBehavior for ActiveModel {}
+}
+
 /// SeaORM-сущность текущего read-state участника личного диалога.
 pub mod conversation_member_states {
     use sea_orm::entity::prelude::*;
@@ -8,12 +8,20 @@ use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::features::social::domain::{
-    ConversationMemberState, ConversationReadCheckpoint, ConversationReadUpdate, DmConversation,
-    DmMessage, Friendship, FriendshipStatus, ordered_pair,
+    ConversationMemberState, ConversationReadCheckpoint, ConversationReadUpdate, DmAttachment,
+    DmConversation, DmMessage, Friendship, FriendshipStatus, ordered_pair,
 };
 use crate::features::social::infrastructure::{
     DM_HISTORY_LIMIT, DmMessagePage, SocialStore, normalize_unread_count, unread_count_after_read,
 };
+use crate::features::text_chat::domain::MessageReaction;
+
+mod attachments;
+mod messages;
+mod read_state;
+
+use messages::page_before;
+use read_state::default_member_state;
 
 /// In-memory-хранилище социальных данных для локального режима и тестов.
 #[derive(Default)]
@@ -21,8 +29,10 @@ pub(crate) struct InMemorySocialStore {
     friendships: Mutex<Vec<Friendship>>,
     conversations: Mutex<Vec<DmConversation>>,
     messages: Mutex<Vec<DmMessage>>,
+    attachments: Mutex<Vec<DmAttachment>>,
     member_states: Mutex<Vec<ConversationMemberState>>,
     read_checkpoints: Mutex<Vec<ConversationReadCheckpoint>>,
+    reactions: Mutex<Vec<MessageReaction>>,
 }
 
 #[async_trait]
@@ -204,30 +214,24 @@ impl SocialStore for InMemorySocialStore {
         conversation_id: &Uuid,
         before_message_id: Option<&Uuid>,
     ) -> anyhow::Result<DmMessagePage> {
-        let mut messages = self
+        let messages = self
             .messages
             .lock()
             .map_err(|_| poisoned())?
             .iter()
             .filter(|row| row.conversation_id == *conversation_id && row.deleted_at.is_none())
             .cloned()
-            .collect::<Vec<_>>();
-        messages.sort_by_key(|row| (row.created_at, row.id));
-        if let Some(before_message_id) = before_message_id {
-            let Some(cursor_index) = messages.iter().position(|row| row.id == *before_message_id)
-            else {
-                return Err(anyhow!("dm message history cursor was not found"));
-            };
-            messages.truncate(cursor_index);
-        }
-        let start = messages
-            .len()
-            .saturating_sub(usize::try_from(DM_HISTORY_LIMIT).unwrap_or(50));
-        let has_more = start > 0;
-        Ok(DmMessagePage {
-            messages: messages.split_off(start),
-            has_more,
-        })
+            .collect();
+        page_before(messages, before_message_id, DM_HISTORY_LIMIT)
+    }
+
+    async fn search_dm_messages(
+        &self,
+        conversation_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<DmMessagePage> {
+        self.search_messages(conversation_ids, query, before_message_id)
     }
 
     async fn dm_message_by_id(
@@ -249,17 +253,28 @@ impl SocialStore for InMemorySocialStore {
         conversation_id: &Uuid,
         image_id: &Uuid,
     ) -> anyhow::Result<Option<DmMessage>> {
-        Ok(self
-            .messages
-            .lock()
-            .map_err(|_| poisoned())?
-            .iter()
-            .find(|row| {
-                row.conversation_id == *conversation_id
-                    && row.image_id == Some(*image_id)
-                    && row.deleted_at.is_none()
-            })
-            .cloned())
+        self.message_with(conversation_id, |row| row.image_id == Some(*image_id))
+    }
+
+    async fn dm_message_by_attachment_id(
+        &self,
+        conversation_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmMessage>> {
+        self.message_with(conversation_id, |row| {
+            row.attachment_id == Some(*attachment_id)
+        })
+    }
+
+    async fn insert_dm_attachment(&self, attachment: DmAttachment) -> anyhow::Result<()> {
+        self.push_attachment(attachment)
+    }
+
+    async fn find_dm_attachment(
+        &self,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmAttachment>> {
+        self.attachment_by_id(attachment_id)
     }
 
     async fn conversation_member_state(
@@ -380,16 +395,7 @@ impl SocialStore for InMemorySocialStore {
     }
 
     async fn insert_dm_message(&self, message: DmMessage) -> anyhow::Result<DmMessage> {
-        if let Some(image_id) = message.image_id
-            && self
-                .messages
-                .lock()
-                .map_err(|_| poisoned())?
-                .iter()
-                .any(|row| row.image_id == Some(image_id))
-        {
-            return Err(anyhow::anyhow!("direct message image is already attached"));
-        }
+        self.ensure_attachments_unused(&message)?;
         let next_seq = self
             .messages
             .lock()
@@ -429,60 +435,58 @@ impl SocialStore for InMemorySocialStore {
             .push(message.clone());
         Ok(message)
     }
-}
 
-impl InMemorySocialStore {
-    fn ensure_member_state(
+    async fn add_dm_reaction(
         &self,
-        conversation_id: &Uuid,
+        message_id: &Uuid,
         user_id: &Uuid,
-        now: DateTime<Utc>,
-    ) -> anyhow::Result<()> {
-        let mut member_states = self.member_states.lock().map_err(|_| poisoned())?;
-        if !member_states
-            .iter()
-            .any(|row| row.conversation_id == *conversation_id && row.user_id == *user_id)
-        {
-            member_states.push(default_member_state(conversation_id, user_id, now));
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        let mut reactions = self.reactions.lock().map_err(|_| poisoned())?;
+        if reactions.iter().any(|reaction| {
+            reaction.message_id == *message_id
+                && reaction.user_id == *user_id
+                && reaction.emoji == emoji
+        }) {
+            return Ok(false);
         }
-        Ok(())
+        reactions.push(MessageReaction {
+            message_id: *message_id,
+            user_id: *user_id,
+            emoji: emoji.to_owned(),
+            created_at: Utc::now(),
+        });
+        Ok(true)
     }
 
-    fn increment_unread(
+    async fn remove_dm_reaction(
         &self,
-        conversation_id: &Uuid,
+        message_id: &Uuid,
         user_id: &Uuid,
-        now: DateTime<Utc>,
-    ) -> anyhow::Result<()> {
-        let mut member_states = self.member_states.lock().map_err(|_| poisoned())?;
-        if let Some(row) = member_states
-            .iter_mut()
-            .find(|row| row.conversation_id == *conversation_id && row.user_id == *user_id)
-        {
-            row.unread_count = normalize_unread_count(row.unread_count) + 1;
-            row.updated_at = now;
-        } else {
-            let mut state = default_member_state(conversation_id, user_id, now);
-            state.unread_count = 1;
-            member_states.push(state);
-        }
-        Ok(())
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        let mut reactions = self.reactions.lock().map_err(|_| poisoned())?;
+        let before = reactions.len();
+        reactions.retain(|reaction| {
+            reaction.message_id != *message_id
+                || reaction.user_id != *user_id
+                || reaction.emoji != emoji
+        });
+        Ok(reactions.len() != before)
     }
-}
 
-fn default_member_state(
-    conversation_id: &Uuid,
-    user_id: &Uuid,
-    now: DateTime<Utc>,
-) -> ConversationMemberState {
-    ConversationMemberState {
-        conversation_id: *conversation_id,
-        user_id: *user_id,
-        last_read_message_id: None,
-        last_read_seq: 0,
-        last_read_at: None,
-        unread_count: 0,
-        updated_at: now,
+    async fn dm_message_reactions(
+        &self,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<MessageReaction>> {
+        Ok(self
+            .reactions
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|reaction| message_ids.contains(&reaction.message_id))
+            .cloned()
+            .collect())
     }
 }
 
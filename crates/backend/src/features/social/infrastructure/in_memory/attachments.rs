@@ -0,0 +1,65 @@
+//! Вложения in-memory-хранилища личных сообщений.
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use super::{InMemorySocialStore, poisoned};
+use crate::features::social::domain::{DmAttachment, DmMessage};
+
+impl InMemorySocialStore {
+    /// Находит неудаленное сообщение диалога, подходящее под условие.
+    pub(super) fn message_with(
+        &self,
+        conversation_id: &Uuid,
+        matches: impl Fn(&DmMessage) -> bool,
+    ) -> anyhow::Result<Option<DmMessage>> {
+        Ok(self
+            .messages
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .find(|row| {
+                row.conversation_id == *conversation_id && row.deleted_at.is_none() && matches(row)
+            })
+            .cloned())
+    }
+
+    /// Повторяет уникальные индексы `image_id` и `attachment_id` из Postgres.
+    pub(super) fn ensure_attachments_unused(&self, message: &DmMessage) -> anyhow::Result<()> {
+        let messages = self.messages.lock().map_err(|_| poisoned())?;
+        if let Some(image_id) = message.image_id
+            && messages.iter().any(|row| row.image_id == Some(image_id))
+        {
+            return Err(anyhow!("direct message image is already attached"));
+        }
+        if let Some(attachment_id) = message.attachment_id
+            && messages
+                .iter()
+                .any(|row| row.attachment_id == Some(attachment_id))
+        {
+            return Err(anyhow!("direct message attachment is already attached"));
+        }
+        Ok(())
+    }
+
+    pub(super) fn push_attachment(&self, attachment: DmAttachment) -> anyhow::Result<()> {
+        self.attachments
+            .lock()
+            .map_err(|_| poisoned())?
+            .push(attachment);
+        Ok(())
+    }
+
+    pub(super) fn attachment_by_id(
+        &self,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmAttachment>> {
+        Ok(self
+            .attachments
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .find(|row| row.id == *attachment_id)
+            .cloned())
+    }
+}
@@ -0,0 +1,57 @@
+//! Страницы и поиск сообщений in-memory-хранилища личных сообщений.
+
+use anyhow::anyhow;
+use uuid::Uuid;
+
+use super::{InMemorySocialStore, poisoned};
+use crate::features::social::domain::DmMessage;
+use crate::features::social::infrastructure::{DM_SEARCH_LIMIT, DmMessagePage};
+
+impl InMemorySocialStore {
+    /// Ищет подстроку в неудаленных сообщениях диалогов без учета регистра.
+    pub(super) fn search_messages(
+        &self,
+        conversation_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<DmMessagePage> {
+        let query = query.to_lowercase();
+        let messages = self
+            .messages
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|row| {
+                conversation_ids.contains(&row.conversation_id)
+                    && row.deleted_at.is_none()
+                    && row.body.to_lowercase().contains(&query)
+            })
+            .cloned()
+            .collect();
+        page_before(messages, before_message_id, DM_SEARCH_LIMIT)
+    }
+}
+
+/// Возвращает последние `limit` сообщений перед курсором в порядке от старых к новым.
+pub(super) fn page_before(
+    mut messages: Vec<DmMessage>,
+    before_message_id: Option<&Uuid>,
+    limit: u64,
+) -> anyhow::Result<DmMessagePage> {
+    messages.sort_by_key(|row| (row.created_at, row.id));
+    if let Some(before_message_id) = before_message_id {
+        let Some(cursor_index) = messages.iter().position(|row| row.id == *before_message_id)
+        else {
+            return Err(anyhow!("dm message history cursor was not found"));
+        };
+        messages.truncate(cursor_index);
+    }
+    let start = messages
+        .len()
+        .saturating_sub(usize::try_from(limit).unwrap_or(50));
+    let has_more = start > 0;
+    Ok(DmMessagePage {
+        messages: messages.split_off(start),
+        has_more,
+    })
+}
@@ -3,22 +3,27 @@
 mod entities;
 mod in_memory;
 mod postgres;
+mod postgres_attachments;
 mod postgres_conversions;
+mod postgres_reactions;
 mod postgres_read_state;
+mod postgres_search;
 
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
 use crate::features::social::domain::{
-    ConversationMemberState, ConversationReadUpdate, DmConversation, DmMessage, Friendship,
-    FriendshipStatus,
+    ConversationMemberState, ConversationReadUpdate, DmAttachment, DmConversation, DmMessage,
+    Friendship, FriendshipStatus,
 };
+use crate::features::text_chat::domain::MessageReaction;
 
 pub(crate) use in_memory::InMemorySocialStore;
 pub(crate) use postgres::PostgresSocialStore;
 
 pub(crate) const DM_HISTORY_LIMIT: u64 = 50;
+pub(crate) const DM_SEARCH_LIMIT: u64 = 25;
 
 /// Приводит счетчик непрочитанных к допустимому диапазону.
 pub(crate) fn normalize_unread_count(unread_count: i64) -> i64 {
@@ -104,6 +109,17 @@ pub(crate) trait SocialStore: Send + Sync {
         before_message_id: Option<&Uuid>,
     ) -> anyhow::Result<DmMessagePage>;
 
+    /// Ищет неудаленные сообщения перечисленных диалогов по тексту.
+    ///
+    /// Возвращает страницу совпадений в порядке от старых к новым. Курсор
+    /// `before_message_id` должен быть одним из ранее найденных сообщений.
+    async fn search_dm_messages(
+        &self,
+        conversation_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<DmMessagePage>;
+
     /// Возвращает одно сообщение личного диалога.
     async fn dm_message_by_id(
         &self,
@@ -118,6 +134,22 @@ pub(crate) trait SocialStore: Send + Sync {
         image_id: &Uuid,
     ) -> anyhow::Result<Option<DmMessage>>;
 
+    /// Находит сообщение диалога, к которому уже прикреплено вложение.
+    async fn dm_message_by_attachment_id(
+        &self,
+        conversation_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmMessage>>;
+
+    /// Сохраняет метаданные загруженного вложения личного диалога.
+    async fn insert_dm_attachment(&self, attachment: DmAttachment) -> anyhow::Result<()>;
+
+    /// Находит вложение личного диалога по идентификатору.
+    async fn find_dm_attachment(
+        &self,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmAttachment>>;
+
     /// Возвращает read-state участника диалога.
     async fn conversation_member_state(
         &self,
@@ -148,6 +180,32 @@ pub(crate) trait SocialStore: Send + Sync {
 
     /// Вставляет личное сообщение и обновляет время диалога.
     async fn insert_dm_message(&self, message: DmMessage) -> anyhow::Result<DmMessage>;
+
+    /// Ставит реакцию пользователя на личное сообщение.
+    ///
+    /// Возвращает `false`, если такая реакция пользователя уже существует.
+    async fn add_dm_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool>;
+
+    /// Снимает реакцию пользователя с личного сообщения.
+    ///
+    /// Возвращает `false`, если такой реакции не было.
+    async fn remove_dm_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool>;
+
+    /// Загружает реакции на личные сообщения в порядке их установки.
+    async fn dm_message_reactions(
+        &self,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<MessageReaction>>;
 }
 
 #[cfg(test)]
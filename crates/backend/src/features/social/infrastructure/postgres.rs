@@ -4,25 +4,28 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sea_orm::{
     ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, IntoActiveModel,
-    PaginatorTrait, QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait,
-    sea_query::LockType,
+    QueryFilter, QueryOrder, QuerySelect, Set, TransactionTrait, sea_query::LockType,
 };
 use uuid::Uuid;
 
 use crate::features::social::domain::{
-    ConversationMemberState, ConversationReadUpdate, DmConversation, DmMessage, Friendship,
-    FriendshipStatus, ordered_pair,
+    ConversationMemberState, ConversationReadUpdate, DmAttachment, DmConversation, DmMessage,
+    Friendship, FriendshipStatus, ordered_pair,
 };
 use crate::features::social::infrastructure::entities::{
-    self as friendships, conversation_member_states, conversation_read_checkpoints,
-    dm_conversations, dm_messages,
+    self as friendships, conversation_member_states, dm_conversations, dm_messages,
 };
+use crate::features::social::infrastructure::postgres_attachments;
+use crate::features::social::infrastructure::postgres_conversions::try_friendship;
+use crate::features::social::infrastructure::postgres_reactions;
 use crate::features::social::infrastructure::postgres_read_state::{
-    ensure_member_state, increment_unread,
+    self, ensure_member_state, increment_unread,
 };
+use crate::features::social::infrastructure::postgres_search::{self, older_than};
 use crate::features::social::infrastructure::{
-    DM_HISTORY_LIMIT, DmMessagePage, SocialStore, normalize_unread_count, unread_count_after_read,
+    DM_HISTORY_LIMIT, DmMessagePage, SocialStore, normalize_unread_count,
 };
+use crate::features::text_chat::domain::MessageReaction;
 
 /// Postgres-хранилище социальных данных.
 pub(crate) struct PostgresSocialStore {
@@ -230,15 +233,7 @@ impl SocialStore for PostgresSocialStore {
             .add(dm_messages::Column::ConversationId.eq(*conversation_id))
             .add(dm_messages::Column::DeletedAt.is_null());
         if let Some(message) = before_message {
-            filter = filter.add(
-                Condition::any()
-                    .add(dm_messages::Column::CreatedAt.lt(message.created_at))
-                    .add(
-                        Condition::all()
-                            .add(dm_messages::Column::CreatedAt.eq(message.created_at))
-                            .add(dm_messages::Column::Id.lt(message.id)),
-                    ),
-            );
+            filter = filter.add(older_than(&message));
         }
 
         let mut messages = dm_messages::Entity::find()
@@ -259,6 +254,21 @@ impl SocialStore for PostgresSocialStore {
         Ok(DmMessagePage { messages, has_more })
     }
 
+    async fn search_dm_messages(
+        &self,
+        conversation_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<DmMessagePage> {
+        postgres_search::search_dm_messages(
+            &self.database,
+            conversation_ids,
+            query,
+            before_message_id,
+        )
+        .await
+    }
+
     async fn dm_message_by_id(
         &self,
         conversation_id: &Uuid,
@@ -277,13 +287,31 @@ impl SocialStore for PostgresSocialStore {
         conversation_id: &Uuid,
         image_id: &Uuid,
     ) -> anyhow::Result<Option<DmMessage>> {
-        Ok(dm_messages::Entity::find()
-            .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
-            .filter(dm_messages::Column::ImageId.eq(*image_id))
-            .filter(dm_messages::Column::DeletedAt.is_null())
-            .one(&self.database)
-            .await?
-            .map(Into::into))
+        postgres_attachments::message_by_image_id(&self.database, conversation_id, image_id).await
+    }
+
+    async fn dm_message_by_attachment_id(
+        &self,
+        conversation_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmMessage>> {
+        postgres_attachments::message_by_attachment_id(
+            &self.database,
+            conversation_id,
+            attachment_id,
+        )
+        .await
+    }
+
+    async fn insert_dm_attachment(&self, attachment: DmAttachment) -> anyhow::Result<()> {
+        postgres_attachments::insert_attachment(&self.database, attachment).await
+    }
+
+    async fn find_dm_attachment(
+        &self,
+        attachment_id: &Uuid,
+    ) -> anyhow::Result<Option<DmAttachment>> {
+        postgres_attachments::find_attachment(&self.database, attachment_id).await
     }
 
     async fn conversation_member_state(
@@ -316,61 +344,14 @@ impl SocialStore for PostgresSocialStore {
         last_read_message_id: &Uuid,
         now: DateTime<Utc>,
     ) -> anyhow::Result<ConversationReadUpdate> {
-        let message = dm_messages::Entity::find()
-            .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
-            .filter(dm_messages::Column::Id.eq(*last_read_message_id))
-            .one(&self.database)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("dm read message was not found in conversation"))?;
-        let transaction = self.database.begin().await?;
-        ensure_member_state(&transaction, conversation_id, user_id, now).await?;
-        let state = conversation_member_states::Entity::find()
-            .filter(conversation_member_states::Column::ConversationId.eq(*conversation_id))
-            .filter(conversation_member_states::Column::UserId.eq(*user_id))
-            .lock(LockType::Update)
-            .one(&transaction)
-            .await?
-            .ok_or_else(|| anyhow::anyhow!("dm read state was not found after ensure"))?;
-        if message.seq <= state.last_read_seq {
-            transaction.commit().await?;
-            return Ok(ConversationReadUpdate {
-                state: state.into(),
-                checkpoint: None,
-            });
-        }
-
-        let incoming_read = dm_messages::Entity::find()
-            .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
-            .filter(dm_messages::Column::DeletedAt.is_null())
-            .filter(dm_messages::Column::SenderUserId.ne(*user_id))
-            .filter(dm_messages::Column::Seq.gt(state.last_read_seq))
-            .filter(dm_messages::Column::Seq.lte(message.seq))
-            .count(&transaction)
-            .await? as i64;
-        let next_unread_count = unread_count_after_read(state.unread_count, incoming_read);
-        let mut active = state.into_active_model();
-        active.last_read_message_id = Set(Some(*last_read_message_id));
-        active.last_read_seq = Set(message.seq);
-        active.last_read_at = Set(Some(now));
-        active.unread_count = Set(next_unread_count);
-        active.updated_at = Set(now);
-        let state = active.update(&transaction).await?;
-        let checkpoint = conversation_read_checkpoints::ActiveModel {
-            id: Set(Uuid::new_v4()),
-            conversation_id: Set(*conversation_id),
-            user_id: Set(*user_id),
-            last_read_message_id: Set(*last_read_message_id),
-            last_read_seq: Set(message.seq),
-            read_at: Set(now),
-            created_at: Set(now),
-        }
-        .insert(&transaction)
-        .await?;
-        transaction.commit().await?;
-        Ok(ConversationReadUpdate {
-            state: state.into(),
-            checkpoint: Some(checkpoint.into()),
-        })
+        postgres_read_state::mark_conversation_read(
+            &self.database,
+            conversation_id,
+            user_id,
+            last_read_message_id,
+            now,
+        )
+        .await
     }
 
     #[cfg(test)]
@@ -380,15 +361,8 @@ impl SocialStore for PostgresSocialStore {
         user_id: &Uuid,
         message_seq: i64,
     ) -> anyhow::Result<Option<DateTime<Utc>>> {
-        Ok(conversation_read_checkpoints::Entity::find()
-            .filter(conversation_read_checkpoints::Column::ConversationId.eq(*conversation_id))
-            .filter(conversation_read_checkpoints::Column::UserId.eq(*user_id))
-            .filter(conversation_read_checkpoints::Column::LastReadSeq.gte(message_seq))
-            .order_by_asc(conversation_read_checkpoints::Column::LastReadSeq)
-            .order_by_asc(conversation_read_checkpoints::Column::ReadAt)
-            .one(&self.database)
-            .await?
-            .map(|row| row.read_at))
+        postgres_read_state::message_read_at(&self.database, conversation_id, user_id, message_seq)
+            .await
     }
 
     async fn insert_dm_message(&self, message: DmMessage) -> anyhow::Result<DmMessage> {
@@ -412,6 +386,7 @@ impl SocialStore for PostgresSocialStore {
             sender_user_id: Set(message.sender_user_id),
             body: Set(message.body),
             image_id: Set(message.image_id),
+            attachment_id: Set(message.attachment_id),
             created_at: Set(message.created_at),
             updated_at: Set(message.updated_at),
             deleted_at: Set(message.deleted_at),
@@ -439,6 +414,31 @@ impl SocialStore for PostgresSocialStore {
         transaction.commit().await?;
         Ok(inserted)
     }
+
+    async fn add_dm_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        postgres_reactions::add_reaction(&self.database, message_id, user_id, emoji).await
+    }
+
+    async fn remove_dm_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        postgres_reactions::remove_reaction(&self.database, message_id, user_id, emoji).await
+    }
+
+    async fn dm_message_reactions(
+        &self,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<MessageReaction>> {
+        postgres_reactions::message_reactions(&self.database, message_ids).await
+    }
 }
 
 async fn request_rows(
@@ -459,18 +459,3 @@ async fn request_rows(
 fn rows_to_friendships(rows: Vec<friendships::Model>) -> anyhow::Result<Vec<Friendship>> {
     rows.into_iter().map(try_friendship).collect()
 }
-
-fn try_friendship(row: friendships::Model) -> anyhow::Result<Friendship> {
-    let status = FriendshipStatus::from_str(&row.status)
-        .ok_or_else(|| anyhow::anyhow!("unknown friendship status {}", row.status))?;
-    Ok(Friendship {
-        id: row.id,
-        requester_user_id: row.requester_user_id,
-        recipient_user_id: row.recipient_user_id,
-        user_low_id: row.user_low_id,
-        user_high_id: row.user_high_id,
-        status,
-        created_at: row.created_at,
-        updated_at: row.updated_at,
-    })
-}
@@ -0,0 +1,73 @@
+//! Postgres-операции с вложениями личных сообщений.
+
+use sea_orm::{ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set};
+use uuid::Uuid;
+
+use crate::features::social::domain::{DmAttachment, DmMessage};
+use crate::features::social::infrastructure::entities::{dm_attachments, dm_messages};
+
+/// Находит неудаленное сообщение диалога с изображением старого формата.
+pub(super) async fn message_by_image_id(
+    database: &DatabaseConnection,
+    conversation_id: &Uuid,
+    image_id: &Uuid,
+) -> anyhow::Result<Option<DmMessage>> {
+    Ok(dm_messages::Entity::find()
+        .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
+        .filter(dm_messages::Column::ImageId.eq(*image_id))
+        .filter(dm_messages::Column::DeletedAt.is_null())
+        .one(database)
+        .await?
+        .map(Into::into))
+}
+
+/// Находит неудаленное сообщение диалога с указанным вложением.
+pub(super) async fn message_by_attachment_id(
+    database: &DatabaseConnection,
+    conversation_id: &Uuid,
+    attachment_id: &Uuid,
+) -> anyhow::Result<Option<DmMessage>> {
+    Ok(dm_messages::Entity::find()
+        .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
+        .filter(dm_messages::Column::AttachmentId.eq(*attachment_id))
+        .filter(dm_messages::Column::DeletedAt.is_null())
+        .one(database)
+        .await?
+        .map(Into::into))
+}
+
+/// Сохраняет метаданные загруженного вложения.
+pub(super) async fn insert_attachment(
+    database: &DatabaseConnection,
+    attachment: DmAttachment,
+) -> anyhow::Result<()> {
+    dm_attachments::ActiveModel {
+        id: Set(attachment.id),
+        conversation_id: Set(attachment.conversation_id),
+        uploader_user_id: Set(attachment.uploader_user_id),
+        bucket: Set(attachment.bucket),
+        object_key: Set(attachment.object_key),
+        kind: Set(attachment.kind.as_str().to_owned()),
+        content_type: Set(attachment.content_type),
+        byte_size: Set(attachment.byte_size),
+        width: Set(attachment.width),
+        height: Set(attachment.height),
+        sha256: Set(attachment.sha256),
+        original_filename: Set(attachment.original_filename),
+        created_at: Set(attachment.created_at),
+    }
+    .insert(database)
+    .await?;
+    Ok(())
+}
+
+/// Находит вложение по идентификатору.
+pub(super) async fn find_attachment(
+    database: &DatabaseConnection,
+    attachment_id: &Uuid,
+) -> anyhow::Result<Option<DmAttachment>> {
+    Ok(dm_attachments::Entity::find_by_id(*attachment_id)
+        .one(database)
+        .await?
+        .map(Into::into))
+}
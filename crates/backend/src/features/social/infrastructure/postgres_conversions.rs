@@ -1,12 +1,15 @@
 //! Преобразования строк Postgres social-хранилища в доменные структуры.
 
 use crate::features::social::domain::{
-    ConversationMemberState, ConversationReadCheckpoint, DmConversation, DmMessage,
+    ConversationMemberState, ConversationReadCheckpoint, DmAttachment, DmConversation, DmMessage,
+    Friendship, FriendshipStatus,
 };
 use crate::features::social::infrastructure::entities::{
-    conversation_member_states, conversation_read_checkpoints, dm_conversations, dm_messages,
+    self as friendships, conversation_member_states, conversation_read_checkpoints, dm_attachments,
+    dm_conversations, dm_messages,
 };
 use crate::features::social::infrastructure::normalize_unread_count;
+use crate::features::text_chat::domain::ChatAttachmentKind;
 
 impl From<dm_conversations::Model> for DmConversation {
     fn from(row: dm_conversations::Model) -> Self {
@@ -28,6 +31,7 @@ impl From<dm_messages::Model> for DmMessage {
             sender_user_id: row.sender_user_id,
             body: row.body,
             image_id: row.image_id,
+            attachment_id: row.attachment_id,
             created_at: row.created_at,
             updated_at: row.updated_at,
             deleted_at: row.deleted_at,
@@ -35,6 +39,27 @@ impl From<dm_messages::Model> for DmMessage {
     }
 }
 
+impl From<dm_attachments::Model> for DmAttachment {
+    fn from(row: dm_attachments::Model) -> Self {
+        Self {
+            id: row.id,
+            conversation_id: row.conversation_id,
+            uploader_user_id: row.uploader_user_id,
+            bucket: row.bucket,
+            object_key: row.object_key,
+            // Неизвестный вид безопаснее всего отдавать как обычный файл.
+            kind: ChatAttachmentKind::from_str(&row.kind).unwrap_or(ChatAttachmentKind::File),
+            content_type: row.content_type,
+            byte_size: row.byte_size,
+            width: row.width,
+            height: row.height,
+            sha256: row.sha256,
+            original_filename: row.original_filename,
+            created_at: row.created_at,
+        }
+    }
+}
+
 impl From<conversation_member_states::Model> for ConversationMemberState {
     fn from(row: conversation_member_states::Model) -> Self {
         Self {
@@ -62,3 +87,19 @@ impl From<conversation_read_checkpoints::Model> for ConversationReadCheckpoint {
         }
     }
 }
+
+/// Преобразует строку дружбы, проверяя сохраненный статус.
+pub(super) fn try_friendship(row: friendships::Model) -> anyhow::Result<Friendship> {
+    let status = FriendshipStatus::from_str(&row.status)
+        .ok_or_else(|| anyhow::anyhow!("unknown friendship status {}", row.status))?;
+    Ok(Friendship {
+        id: row.id,
+        requester_user_id: row.requester_user_id,
+        recipient_user_id: row.recipient_user_id,
+        user_low_id: row.user_low_id,
+        user_high_id: row.user_high_id,
+        status,
+        created_at: row.created_at,
+        updated_at: row.updated_at,
+    })
+}
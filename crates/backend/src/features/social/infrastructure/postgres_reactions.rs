@@ -0,0 +1,82 @@
+//! Postgres-операции с реакциями на личные сообщения.
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TryInsertResult,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::features::social::infrastructure::entities::message_reactions;
+use crate::features::text_chat::domain::MessageReaction;
+
+/// Вставляет реакцию, если у пользователя ее еще нет.
+pub(super) async fn add_reaction(
+    database: &DatabaseConnection,
+    message_id: &Uuid,
+    user_id: &Uuid,
+    emoji: &str,
+) -> anyhow::Result<bool> {
+    let result = message_reactions::Entity::insert(message_reactions::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        text_message_id: Set(None),
+        dm_message_id: Set(Some(*message_id)),
+        user_id: Set(*user_id),
+        emoji: Set(emoji.to_owned()),
+        created_at: Set(Utc::now()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            message_reactions::Column::DmMessageId,
+            message_reactions::Column::UserId,
+            message_reactions::Column::Emoji,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .do_nothing()
+    .exec(database)
+    .await?;
+    Ok(matches!(result, TryInsertResult::Inserted(_)))
+}
+
+/// Удаляет реакцию пользователя.
+pub(super) async fn remove_reaction(
+    database: &DatabaseConnection,
+    message_id: &Uuid,
+    user_id: &Uuid,
+    emoji: &str,
+) -> anyhow::Result<bool> {
+    let result = message_reactions::Entity::delete_many()
+        .filter(message_reactions::Column::DmMessageId.eq(*message_id))
+        .filter(message_reactions::Column::UserId.eq(*user_id))
+        .filter(message_reactions::Column::Emoji.eq(emoji))
+        .exec(database)
+        .await?;
+    Ok(result.rows_affected > 0)
+}
+
+/// Загружает реакции на личные сообщения в порядке установки.
+pub(super) async fn message_reactions(
+    database: &DatabaseConnection,
+    message_ids: &[Uuid],
+) -> anyhow::Result<Vec<MessageReaction>> {
+    if message_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(message_reactions::Entity::find()
+        .filter(message_reactions::Column::DmMessageId.is_in(message_ids.iter().copied()))
+        .order_by_asc(message_reactions::Column::CreatedAt)
+        .all(database)
+        .await?
+        .into_iter()
+        .filter_map(|row| {
+            Some(MessageReaction {
+                message_id: row.dm_message_id?,
+                user_id: row.user_id,
+                emoji: row.emoji,
+                created_at: row.created_at,
+            })
+        })
+        .collect())
+}
@@ -2,12 +2,17 @@
 
 use chrono::{DateTime, Utc};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set,
-    sea_query::{Expr, ExprTrait, Func},
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait,
+    IntoActiveModel, PaginatorTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
+    sea_query::{Expr, ExprTrait, Func, LockType},
 };
 use uuid::Uuid;
 
-use crate::features::social::infrastructure::entities::conversation_member_states;
+use crate::features::social::domain::ConversationReadUpdate;
+use crate::features::social::infrastructure::entities::{
+    conversation_member_states, conversation_read_checkpoints, dm_messages,
+};
+use crate::features::social::infrastructure::unread_count_after_read;
 
 /// Возвращает существующий read-state участника или создает пустой.
 pub(super) async fn ensure_member_state<C>(
@@ -70,3 +75,89 @@ where
         .await?;
     Ok(())
 }
+
+/// Продвигает read-state участника до сообщения и записывает checkpoint.
+pub(super) async fn mark_conversation_read(
+    database: &DatabaseConnection,
+    conversation_id: &Uuid,
+    user_id: &Uuid,
+    last_read_message_id: &Uuid,
+    now: DateTime<Utc>,
+) -> anyhow::Result<ConversationReadUpdate> {
+    let message = dm_messages::Entity::find()
+        .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
+        .filter(dm_messages::Column::Id.eq(*last_read_message_id))
+        .one(database)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("dm read message was not found in conversation"))?;
+    let transaction = database.begin().await?;
+    ensure_member_state(&transaction, conversation_id, user_id, now).await?;
+    let state = conversation_member_states::Entity::find()
+        .filter(conversation_member_states::Column::ConversationId.eq(*conversation_id))
+        .filter(conversation_member_states::Column::UserId.eq(*user_id))
+        .lock(LockType::Update)
+        .one(&transaction)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("dm read state was not found after ensure"))?;
+    if message.seq <= state.last_read_seq {
+        transaction.commit().await?;
+        return Ok(ConversationReadUpdate {
+            state: state.into(),
+            checkpoint: None,
+        });
+    }
+
+    let incoming_read = dm_messages::Entity::find()
+        .filter(dm_messages::Column::ConversationId.eq(*conversation_id))
+        .filter(dm_messages::Column::DeletedAt.is_null())
+        .filter(dm_messages::Column::SenderUserId.ne(*user_id))
+        .filter(dm_messages::Column::Seq.gt(state.last_read_seq))
+        .filter(dm_messages::Column::Seq.lte(message.seq))
+        .count(&transaction)
+        .await? as i64;
+    let next_unread_count = unread_count_after_read(state.unread_count, incoming_read);
+    let mut active = state.into_active_model();
+    active.last_read_message_id = Set(Some(*last_read_message_id));
+    active.last_read_seq = Set(message.seq);
+    active.last_read_at = Set(Some(now));
+    active.unread_count = Set(next_unread_count);
+    active.updated_at = Set(now);
+    let state = active.update(&transaction).await?;
+    let checkpoint = conversation_read_checkpoints::ActiveModel {
+        id: Set(Uuid::new_v4()),
+        conversation_id: Set(*conversation_id),
+        user_id: Set(*user_id),
+        last_read_message_id: Set(*last_read_message_id),
+        last_read_seq: Set(message.seq),
+        read_at: Set(now),
+        created_at: Set(now),
+    }
+    .insert(&transaction)
+    .await?;
+    transaction.commit().await?;
+    Ok(ConversationReadUpdate {
+        state: state.into(),
+        checkpoint: Some(checkpoint.into()),
+    })
+}
+
+/// Возвращает время прочтения сообщения по первому подходящему checkpoint.
+#[cfg(test)]
+pub(super) async fn message_read_at(
+    database: &DatabaseConnection,
+    conversation_id: &Uuid,
+    user_id: &Uuid,
+    message_seq: i64,
+) -> anyhow::Result<Option<DateTime<Utc>>> {
+    use sea_orm::QueryOrder;
+
+    Ok(conversation_read_checkpoints::Entity::find()
+        .filter(conversation_read_checkpoints::Column::ConversationId.eq(*conversation_id))
+        .filter(conversation_read_checkpoints::Column::UserId.eq(*user_id))
+        .filter(conversation_read_checkpoints::Column::LastReadSeq.gte(message_seq))
+        .order_by_asc(conversation_read_checkpoints::Column::LastReadSeq)
+        .order_by_asc(conversation_read_checkpoints::Column::ReadAt)
+        .one(database)
+        .await?
+        .map(|row| row.read_at))
+}
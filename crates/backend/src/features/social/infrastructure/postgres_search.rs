@@ -0,0 +1,72 @@
+//! Полнотекстовый поиск и keyset-пагинация Postgres личных сообщений.
+
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::features::social::domain::DmMessage;
+use crate::features::social::infrastructure::entities::dm_messages;
+use crate::features::social::infrastructure::{DM_SEARCH_LIMIT, DmMessagePage};
+
+/// Ищет неудаленные сообщения диалогов через индекс `idx_dm_messages_body_search`.
+pub(super) async fn search_dm_messages(
+    database: &DatabaseConnection,
+    conversation_ids: &[Uuid],
+    query: &str,
+    before_message_id: Option<&Uuid>,
+) -> anyhow::Result<DmMessagePage> {
+    if conversation_ids.is_empty() {
+        return Ok(DmMessagePage {
+            messages: Vec::new(),
+            has_more: false,
+        });
+    }
+
+    // Выражение должно совпадать с выражением индекса, иначе Postgres его не использует.
+    let mut filter = Condition::all()
+        .add(dm_messages::Column::ConversationId.is_in(conversation_ids.iter().copied()))
+        .add(dm_messages::Column::DeletedAt.is_null())
+        .add(Expr::cust_with_values(
+            "to_tsvector('simple', body) @@ plainto_tsquery('simple', $1)",
+            [query],
+        ));
+    if let Some(message_id) = before_message_id {
+        let message = dm_messages::Entity::find()
+            .filter(dm_messages::Column::ConversationId.is_in(conversation_ids.iter().copied()))
+            .filter(dm_messages::Column::Id.eq(*message_id))
+            .one(database)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("dm message search cursor was not found"))?;
+        filter = filter.add(older_than(&message));
+    }
+
+    let mut messages = dm_messages::Entity::find()
+        .filter(filter)
+        .order_by_desc(dm_messages::Column::CreatedAt)
+        .order_by_desc(dm_messages::Column::Id)
+        .limit(DM_SEARCH_LIMIT + 1)
+        .all(database)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<_>>();
+    let has_more = messages.len() > usize::try_from(DM_SEARCH_LIMIT).unwrap_or(25);
+    if has_more {
+        messages.truncate(usize::try_from(DM_SEARCH_LIMIT).unwrap_or(25));
+    }
+    messages.sort_by_key(|message: &DmMessage| (message.created_at, message.id));
+    Ok(DmMessagePage { messages, has_more })
+}
+
+/// Собирает keyset-условие для сообщений строго старше курсора.
+pub(super) fn older_than(message: &dm_messages::Model) -> Condition {
+    Condition::any()
+        .add(dm_messages::Column::CreatedAt.lt(message.created_at))
+        .add(
+            Condition::all()
+                .add(dm_messages::Column::CreatedAt.eq(message.created_at))
+                .add(dm_messages::Column::Id.lt(message.id)),
+        )
+}
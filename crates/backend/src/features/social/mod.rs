@@ -14,6 +14,7 @@ use axum::{
     routing::{delete, get, post},
 };
 
+use crate::features::text_chat::application::MAX_ATTACHMENT_UPLOAD_BYTES;
 use crate::state::AppState;
 
 pub(crate) use application::{
@@ -55,13 +56,19 @@ pub(crate) fn dm_routes() -> Router<AppState> {
             "/conversations",
             get(transport::list_dm_conversations).post(transport::open_dm_conversation),
         )
+        .route("/search", get(transport::search_dm_messages))
         .route(
             "/conversations/{conversation_id}/messages",
             get(transport::list_dm_messages).post(transport::send_dm_message),
         )
         .route(
-            "/conversations/{conversation_id}/images",
-            post(transport::upload_dm_image).layer(DefaultBodyLimit::max(8 * 1024 * 1024)),
+            "/conversations/{conversation_id}/attachments",
+            post(transport::upload_dm_attachment)
+                .layer(DefaultBodyLimit::max(MAX_ATTACHMENT_UPLOAD_BYTES)),
+        )
+        .route(
+            "/conversations/{conversation_id}/attachments/{attachment_id}",
+            get(transport::dm_attachment),
         )
         .route(
             "/conversations/{conversation_id}/images/{image_id}",
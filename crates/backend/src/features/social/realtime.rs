@@ -1,20 +1,25 @@
 //! Realtime-адаптер друзей и личных сообщений.
 
 use cheenhub_contracts::realtime::{
-    ConversationReadCheckpoint as ReadCheckpointPayload, DirectMessageCreated, RealtimeEnvelope,
-    RealtimeKind, RealtimeModule, RejectionCode, SocialChangeReason, SocialChanged, SocialKind,
-    SocialReady,
+    AddDirectMessageReaction, ConversationReadCheckpoint as ReadCheckpointPayload,
+    DirectMessageCreated, DirectMessagePreviewReady, DirectMessageReactionsChanged,
+    RealtimeEnvelope, RealtimeKind, RealtimeModule, RejectionCode, RemoveDirectMessageReaction,
+    SocialChangeReason, SocialChanged, SocialKind, SocialReady,
 };
 use uuid::Uuid;
 
+use crate::features::social::application;
 use crate::features::social::domain::ConversationReadCheckpoint;
+use crate::features::social::error::SocialError;
 use crate::realtime::EnvelopeSink;
-use crate::realtime::protocol::{require_request_id, send_rejection, write_envelope};
+use crate::realtime::protocol::{
+    decode_payload, require_request_id, send_rejection, write_envelope,
+};
 use crate::state::AppState;
 
 /// Обрабатывает realtime-сообщения social-модуля.
 pub(crate) async fn handle(
-    _state: &AppState,
+    state: &AppState,
     user_id: &Uuid,
     send: &EnvelopeSink,
     envelope: RealtimeEnvelope,
@@ -32,6 +37,40 @@ pub(crate) async fn handle(
             )
             .await
         }
+        RealtimeKind::Social(SocialKind::AddDirectMessageReaction) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: AddDirectMessageReaction = decode_payload(&envelope)?;
+            match application::add_dm_reaction(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::Social,
+                        RealtimeKind::Social(SocialKind::DirectMessageReactionsChanged),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_social_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::Social(SocialKind::RemoveDirectMessageReaction) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: RemoveDirectMessageReaction = decode_payload(&envelope)?;
+            match application::remove_dm_reaction(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::Social,
+                        RealtimeKind::Social(SocialKind::DirectMessageReactionsChanged),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_social_error(send, Some(request_id), error).await,
+            }
+        }
         RealtimeKind::Social(_) => {
             send_rejection(
                 send,
@@ -53,6 +92,33 @@ pub(crate) async fn handle(
     }
 }
 
+async fn reject_social_error(
+    send: &EnvelopeSink,
+    request_id: Option<Uuid>,
+    error: SocialError,
+) -> anyhow::Result<()> {
+    match error {
+        SocialError::BadRequest(message)
+        | SocialError::NotFound(message)
+        | SocialError::Conflict(message) => {
+            send_rejection(send, request_id, RejectionCode::BadRequest, &message).await
+        }
+        SocialError::Unauthorized(message) => {
+            send_rejection(send, request_id, RejectionCode::Unauthorized, &message).await
+        }
+        SocialError::Internal(error) => {
+            tracing::error!(%error, "social realtime request failed");
+            send_rejection(
+                send,
+                request_id,
+                RejectionCode::InternalError,
+                "Не удалось выполнить действие.",
+            )
+            .await
+        }
+    }
+}
+
 /// Отправляет получателю точные данные нового личного сообщения.
 pub(crate) async fn notify_direct_message_created(
     state: &AppState,
@@ -154,3 +220,50 @@ pub(crate) async fn notify_conversation_read_checkpoint(
         )
         .await;
 }
+
+/// Отправляет участникам диалога актуальные реакции на личное сообщение.
+pub(crate) async fn notify_direct_message_reactions_changed(
+    state: &AppState,
+    user_ids: &[Uuid],
+    payload: &DirectMessageReactionsChanged,
+) {
+    tracing::debug!(
+        conversation_id = %payload.conversation_id,
+        message_id = %payload.message_id,
+        user_id = %payload.user_id,
+        added = payload.added,
+        "fanning out direct message reactions change"
+    );
+    state
+        .realtime_hub
+        .fanout_to_user_streams(
+            RealtimeModule::Social,
+            RealtimeKind::Social(SocialKind::DirectMessageReactionsChanged),
+            user_ids,
+            payload.clone(),
+        )
+        .await;
+}
+
+/// Отправляет участникам диалога готовые карточки ссылок личного сообщения.
+pub(crate) async fn notify_direct_message_preview_ready(
+    state: &AppState,
+    user_ids: &[Uuid],
+    payload: DirectMessagePreviewReady,
+) {
+    tracing::debug!(
+        conversation_id = %payload.conversation_id,
+        message_id = %payload.message_id,
+        preview_count = payload.previews.len(),
+        "fanning out direct message link previews"
+    );
+    state
+        .realtime_hub
+        .fanout_to_user_streams(
+            RealtimeModule::Social,
+            RealtimeKind::Social(SocialKind::DirectMessagePreviewReady),
+            user_ids,
+            payload,
+        )
+        .await;
+}
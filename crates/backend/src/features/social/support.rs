@@ -10,11 +10,13 @@ use uuid::Uuid;
 use crate::features::auth::application::auth_user;
 use crate::features::auth::domain::UserAccount;
 use crate::features::auth::error::AuthError;
+use crate::features::link_previews::application::known_link_previews;
 use crate::features::social::domain::{
     ConversationMemberState, DmConversation, DmMessage, Friendship, FriendshipStatus,
 };
 use crate::features::social::error::SocialError;
 use crate::features::social::infrastructure::normalize_unread_count;
+use crate::features::text_chat::application::reaction_summaries;
 use crate::state::AppState;
 
 pub(super) async fn request_response(
@@ -131,10 +133,23 @@ pub(super) async fn message_summaries(
     recipient_last_read_seq: i64,
     messages: Vec<DmMessage>,
 ) -> Result<Vec<DmMessageSummary>, SocialError> {
+    let message_ids = messages
+        .iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    let reactions = state
+        .social_store
+        .dm_message_reactions(&message_ids)
+        .await
+        .map_err(SocialError::Internal)?;
+    let mut reactions = reaction_summaries(&reactions, Some(current_user_id));
     let mut summaries = Vec::new();
     for message in messages {
-        summaries
-            .push(message_summary(state, current_user_id, recipient_last_read_seq, message).await?);
+        let message_id = message.id;
+        let mut summary =
+            message_summary(state, current_user_id, recipient_last_read_seq, message).await?;
+        summary.reactions = reactions.remove(&message_id).unwrap_or_default();
+        summaries.push(summary);
     }
     Ok(summaries)
 }
@@ -150,8 +165,18 @@ pub(super) async fn message_summary(
         &ensure_user_exists(state, &message.sender_user_id).await?,
     );
     let image =
-        super::application::attachment_summary(state, message.conversation_id, message.image_id)
-            .await?;
+        super::application::image_summary(state, message.conversation_id, message.image_id).await?;
+    let attachment = super::application::attachment_summary(
+        state,
+        message.conversation_id,
+        message.attachment_id,
+    )
+    .await?;
+    let link_previews = known_link_previews(state, &[message.body.as_str()])
+        .await
+        .map_err(SocialError::Internal)?
+        .pop()
+        .unwrap_or_default();
     Ok(DmMessageSummary {
         id: message.id.to_string(),
         conversation_id: message.conversation_id.to_string(),
@@ -162,7 +187,10 @@ pub(super) async fn message_summary(
         delivery_status: delivery_status(&message, current_user_id, recipient_last_read_seq),
         body: message.body,
         image,
+        attachment,
         created_at: message.created_at.to_rfc3339(),
+        reactions: Vec::new(),
+        link_previews,
     })
 }
 
@@ -10,14 +10,16 @@ use axum::{
 use cheenhub_contracts::rest::{
     ApiError, ListDmConversationsResponse, ListDmMessagesResponse, ListFriendRequestsResponse,
     ListFriendsResponse, MarkDmConversationReadRequest, MarkDmConversationReadResponse,
-    OpenDmConversationRequest, OpenDmConversationResponse, SearchUsersResponse,
-    SendDmMessageRequest, SendDmMessageResponse, SendFriendRequestRequest,
-    SendFriendRequestResponse, UploadDmImageResponse,
+    OpenDmConversationRequest, OpenDmConversationResponse, SearchDmMessagesResponse,
+    SearchUsersResponse, SendDmMessageRequest, SendDmMessageResponse, SendFriendRequestRequest,
+    SendFriendRequestResponse, UploadDmAttachmentResponse,
 };
 use serde::Deserialize;
 
 use crate::features::social::application;
 use crate::features::social::error::SocialError;
+use crate::features::text_chat::attachment_response;
+use crate::features::text_chat::domain::ChatAttachmentKind;
 use crate::state::AppState;
 
 /// Query-параметры поиска пользователей.
@@ -34,6 +36,24 @@ pub(crate) struct DmMessagesQuery {
     before_message_id: Option<String>,
 }
 
+/// Query-параметры загрузки вложения личного сообщения.
+#[derive(Deserialize)]
+pub(crate) struct UploadDmAttachmentQuery {
+    /// Исходное имя файла.
+    filename: Option<String>,
+}
+
+/// Query-параметры поиска по личным сообщениям.
+#[derive(Deserialize)]
+pub(crate) struct SearchDmMessagesQuery {
+    /// Строка поиска по тексту сообщений.
+    q: Option<String>,
+    /// Диалог, которым нужно ограничить поиск.
+    conversation_id: Option<String>,
+    /// Найденное ранее сообщение, перед которым нужно вернуть более старые совпадения.
+    before_message_id: Option<String>,
+}
+
 /// Ищет пользователей по никнейму.
 pub(crate) async fn search_users(
     State(state): State<AppState>,
@@ -172,6 +192,24 @@ pub(crate) async fn list_dm_messages(
         .map(Json)
 }
 
+/// Ищет сообщения в личных диалогах текущего пользователя.
+pub(crate) async fn search_dm_messages(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<SearchDmMessagesQuery>,
+) -> Result<Json<SearchDmMessagesResponse>, SocialError> {
+    let token = bearer_token(&headers)?;
+    application::search_dm_messages(
+        &state,
+        token,
+        query.q,
+        query.conversation_id,
+        query.before_message_id,
+    )
+    .await
+    .map(Json)
+}
+
 /// Отправляет личное сообщение.
 pub(crate) async fn send_dm_message(
     State(state): State<AppState>,
@@ -185,20 +223,47 @@ pub(crate) async fn send_dm_message(
         .map(Json)
 }
 
-/// Загружает изображение для личного сообщения.
-pub(crate) async fn upload_dm_image(
+/// Загружает вложение для личного сообщения.
+pub(crate) async fn upload_dm_attachment(
     State(state): State<AppState>,
     headers: HeaderMap,
     Path(conversation_id): Path<String>,
+    Query(query): Query<UploadDmAttachmentQuery>,
     bytes: Bytes,
-) -> Result<Json<UploadDmImageResponse>, SocialError> {
+) -> Result<Json<UploadDmAttachmentResponse>, SocialError> {
     let token = bearer_token(&headers)?;
-    application::upload_dm_image(&state, token, conversation_id, bytes)
+    application::upload_dm_attachment(&state, token, conversation_id, query.filename, &bytes)
         .await
         .map(Json)
 }
 
-/// Возвращает изображение участнику личного диалога.
+/// Отдает вложение участнику личного диалога.
+///
+/// Заголовки те же, что у вложений комнат: тип из проверки содержимого,
+/// `nosniff` и sandbox-CSP.
+pub(crate) async fn dm_attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((conversation_id, attachment_id)): Path<(String, String)>,
+) -> Result<Response, SocialError> {
+    let token = bearer_token(&headers)?;
+    let (attachment, bytes) =
+        application::dm_attachment(&state, token, conversation_id, attachment_id).await?;
+    let extension = attachment
+        .object_key
+        .rsplit_once('.')
+        .map_or("bin", |(_, extension)| extension);
+    Ok(attachment_response(
+        attachment.kind,
+        attachment.id,
+        extension,
+        attachment.original_filename.as_deref(),
+        attachment.content_type,
+        bytes,
+    ))
+}
+
+/// Возвращает изображение старого формата участнику личного диалога.
 pub(crate) async fn dm_image(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -209,11 +274,18 @@ pub(crate) async fn dm_image(
     let data = image
         .data
         .ok_or_else(|| SocialError::NotFound("Изображение не найдено.".to_owned()))?;
-    Ok((
-        [(axum::http::header::CONTENT_TYPE, image.content_type)],
+    let extension = image
+        .content_type
+        .rsplit_once('/')
+        .map_or("bin", |(_, subtype)| subtype);
+    Ok(attachment_response(
+        ChatAttachmentKind::Image,
+        image.id,
+        extension,
+        None,
+        image.content_type.clone(),
         data,
-    )
-        .into_response())
+    ))
 }
 
 /// Помечает личный диалог прочитанным до указанного сообщения.
@@ -1,27 +1,54 @@
 //! Потоки приложения текстового чата.
 
+use std::collections::HashMap;
+
 use cheenhub_contracts::realtime::{
-    DeleteMessage, DeleteMessageAccepted, LoadRoomHistory, MessageDeletedPayload, RealtimeKind,
-    RealtimeModule, RoomHistory, SendMessage, SendMessageAccepted, TextChatImageAttachment,
-    TextChatKind, TextChatMessage,
+    DeleteMessage, DeleteMessageAccepted, LoadRoomHistory, MessageDeletedPayload, RoomHistory,
+    SendMessage, SendMessageAccepted, TextChatMessage, TextChatReplySnapshot,
 };
 use cheenhub_contracts::rest::AuthUser;
-use cheenhub_contracts::rest::ServerRoomKind;
+use cheenhub_contracts::rest::{MessageReactionSummary, ServerRoomKind};
 use chrono::Utc;
 use tracing::error;
 use uuid::Uuid;
 
 use crate::features::images::application as image_application;
 use crate::features::text_chat::domain::TextMessage;
+use crate::features::text_chat::infrastructure::HistoryAnchor;
 use crate::features::text_chat::policy;
 use crate::features::text_chat::validation;
 use crate::state::AppState;
 
+mod attachment_content;
 mod attachments;
+mod editing;
+mod fanout;
+mod link_previews;
+mod mentions;
+mod pins;
+mod reactions;
+mod read_state;
+mod replies;
+mod search;
+
+pub(crate) use attachment_content::{MAX_ATTACHMENT_UPLOAD_BYTES, sniff_attachment};
+pub(crate) use attachments::{
+    chat_attachment, chat_image, clean_filename, contract_kind, sha256_hex, upload_chat_attachment,
+};
+pub(crate) use editing::edit_message;
+pub(crate) use pins::{list_pinned_messages, pin_message, unpin_message};
+pub(crate) use reactions::{
+    add_reaction, ensure_reaction_slot, reaction_summaries, remove_reaction,
+};
+pub(crate) use read_state::{
+    mark_room_read, room_unread_counts, saturating_u32, seed_server_read_states,
+};
+pub(crate) use search::{SearchServerMessages, search_server_messages};
 
-pub(crate) use attachments::{chat_image, upload_chat_image};
-
-/// Загружает последние текстовые сообщения комнаты.
+/// Загружает страницу текстовых сообщений комнаты.
+///
+/// Без курсоров возвращает последние сообщения; `before_message_id`,
+/// `after_message_id` и `around_message_id` взаимоисключающие.
 pub(crate) async fn load_room_history(
     state: &AppState,
     user_id: &Uuid,
@@ -29,46 +56,60 @@ pub(crate) async fn load_room_history(
 ) -> Result<RoomHistory, TextChatApplicationError> {
     let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
     let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
-    let before_message_id = request
-        .before_message_id
-        .as_deref()
-        .map(|value| parse_id(value, "История сообщений недоступна."))
-        .transpose()?;
+    let anchor = history_anchor(&request)?;
     ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
 
     let page = state
         .text_chat_store
-        .room_message_page(&room_id, before_message_id.as_ref())
+        .room_message_page(&room_id, anchor)
         .await
         .map_err(|error| {
-            if before_message_id.is_some() {
-                TextChatApplicationError::BadRequest("История сообщений недоступна.".to_owned())
-            } else {
+            if anchor == HistoryAnchor::Latest {
                 TextChatApplicationError::Internal(error)
+            } else {
+                TextChatApplicationError::BadRequest("История сообщений недоступна.".to_owned())
             }
         })?;
 
-    let avatar_urls = image_application::avatar_urls_by_user_ids(
-        state,
-        page.messages.iter().map(|message| message.author_user_id),
-    )
-    .await
-    .map_err(TextChatApplicationError::Internal)?;
+    let messages = message_summaries(state, user_id, &page.messages)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    let last_read_message_id = read_state::last_read_message_id(state, user_id, &room_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
 
     Ok(RoomHistory {
         server_id: server_id.to_string(),
         room_id: room_id.to_string(),
-        messages: page
-            .messages
-            .iter()
-            .map(|message| {
-                message_summary(message, avatar_urls.get(&message.author_user_id).cloned())
-            })
-            .collect(),
-        has_more: page.has_more,
+        messages,
+        has_more_before: page.has_more_before,
+        has_more_after: page.has_more_after,
+        last_read_message_id: last_read_message_id.map(|message_id| message_id.to_string()),
     })
 }
 
+fn history_anchor(request: &LoadRoomHistory) -> Result<HistoryAnchor, TextChatApplicationError> {
+    let parse_cursor = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(|value| parse_id(value, "История сообщений недоступна."))
+            .transpose()
+    };
+    match (
+        parse_cursor(&request.before_message_id)?,
+        parse_cursor(&request.after_message_id)?,
+        parse_cursor(&request.around_message_id)?,
+    ) {
+        (None, None, None) => Ok(HistoryAnchor::Latest),
+        (Some(message_id), None, None) => Ok(HistoryAnchor::Before(message_id)),
+        (None, Some(message_id), None) => Ok(HistoryAnchor::After(message_id)),
+        (None, None, Some(message_id)) => Ok(HistoryAnchor::Around(message_id)),
+        _ => Err(TextChatApplicationError::BadRequest(
+            "Укажи только одно положение истории.".to_owned(),
+        )),
+    }
+}
+
 /// Принимает сообщение, запускает рассылку и сохранение и сразу возвращает ответ.
 pub(crate) async fn send_message(
     state: &AppState,
@@ -79,6 +120,8 @@ pub(crate) async fn send_message(
     let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
     let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
     ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
+    let reply_parent =
+        replies::reply_parent(state, &room_id, request.reply_to_message_id.as_deref()).await?;
     let attachments = load_message_attachments(
         state,
         user_id,
@@ -87,13 +130,16 @@ pub(crate) async fn send_message(
         &request.attachment_ids,
     )
     .await?;
-    let body = if request.body.trim().is_empty() && !attachments.is_empty() {
-        String::new()
+    let (body, mention_names) = if request.body.trim().is_empty() && !attachments.is_empty() {
+        (String::new(), Vec::new())
     } else {
-        validation::message_body(request.body)
-            .map_err(|message| TextChatApplicationError::BadRequest(message.to_owned()))?
-            .body
+        let valid = validation::message_body(request.body)
+            .map_err(|message| TextChatApplicationError::BadRequest(message.to_owned()))?;
+        (valid.body, valid.mention_names)
     };
+    let mentions = mentions::resolve_mentions(state, &server_id, &room_id, &mention_names)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
     let message = TextMessage {
         id: Uuid::new_v4(),
         server_id,
@@ -105,12 +151,23 @@ pub(crate) async fn send_message(
         created_at: Utc::now(),
         deleted_at: None,
         deleted_by_user_id: None,
+        edited_at: None,
+        reply_to_message_id: reply_parent.as_ref().map(|parent| parent.id),
+        mentions,
     };
-    let payload = message_summary(&message, user.avatar_url.clone());
+    let payload = message_summary(
+        &message,
+        user.avatar_url.clone(),
+        reply_parent.as_ref().map(replies::reply_snapshot),
+        Vec::new(),
+    );
     let state_for_insert = state.clone();
     let message_for_insert = message.clone();
+    let state_for_mentions = state.clone();
+    let message_for_mentions = message.clone();
+    let payload_for_mentions = payload.clone();
 
-    if let Err(error) = fanout_message_created(state, payload.clone()).await {
+    if let Err(error) = fanout::fanout_message_created(state, payload.clone()).await {
         error!(
             message_id = %message.id,
             server_id = %message.server_id,
@@ -121,6 +178,27 @@ pub(crate) async fn send_message(
         );
     }
 
+    if !message.mentions.is_empty() {
+        tokio::spawn(async move {
+            if let Err(error) = mentions::notify_mentioned(
+                &state_for_mentions,
+                &message_for_mentions,
+                payload_for_mentions,
+            )
+            .await
+            {
+                error!(
+                    message_id = %message_for_mentions.id,
+                    user_id = %message_for_mentions.author_user_id,
+                    %error,
+                    "failed to notify mentioned text chat members"
+                );
+            }
+        });
+    }
+
+    link_previews::schedule_link_previews(state, &message);
+
     tokio::spawn(async move {
         if let Err(error) = state_for_insert
             .text_chat_store
@@ -188,7 +266,7 @@ pub(crate) async fn delete_message(
         message_id: message.id.to_string(),
     };
 
-    if let Err(error) = fanout_message_deleted(state, deleted_payload).await {
+    if let Err(error) = fanout::fanout_message_deleted(state, deleted_payload).await {
         error!(
             message_id = %message.id,
             user_id = %user_id,
@@ -257,87 +335,6 @@ pub(super) async fn ensure_room_text_available(
     }
 }
 
-async fn fanout_message_created(state: &AppState, message: TextChatMessage) -> anyhow::Result<()> {
-    let server_id = Uuid::parse_str(&message.server_id)?;
-    let room_id = Uuid::parse_str(&message.room_id)?;
-    let candidates = state
-        .realtime_hub
-        .recipients(state, RealtimeModule::TextChat, &server_id)
-        .await;
-    let mut stream_ids = Vec::new();
-
-    for candidate in candidates {
-        match policy::can_receive_room_event(state, &candidate.user_id, &server_id, &room_id).await
-        {
-            Ok(true) => stream_ids.push(candidate.stream_id),
-            Ok(false) => {}
-            Err(error) => {
-                tracing::warn!(
-                    stream_id = %candidate.stream_id,
-                    user_id = %candidate.user_id,
-                    %error,
-                    "failed to evaluate text chat fanout policy"
-                );
-            }
-        }
-    }
-
-    state
-        .realtime_hub
-        .fanout_to_streams(
-            RealtimeModule::TextChat,
-            &server_id,
-            RealtimeKind::TextChat(TextChatKind::MessageCreated),
-            &stream_ids,
-            message,
-        )
-        .await;
-
-    Ok(())
-}
-
-async fn fanout_message_deleted(
-    state: &AppState,
-    message: MessageDeletedPayload,
-) -> anyhow::Result<()> {
-    let server_id = Uuid::parse_str(&message.server_id)?;
-    let room_id = Uuid::parse_str(&message.room_id)?;
-    let candidates = state
-        .realtime_hub
-        .recipients(state, RealtimeModule::TextChat, &server_id)
-        .await;
-    let mut stream_ids = Vec::new();
-
-    for candidate in candidates {
-        match policy::can_receive_room_event(state, &candidate.user_id, &server_id, &room_id).await
-        {
-            Ok(true) => stream_ids.push(candidate.stream_id),
-            Ok(false) => {}
-            Err(error) => {
-                tracing::warn!(
-                    stream_id = %candidate.stream_id,
-                    user_id = %candidate.user_id,
-                    %error,
-                    "failed to evaluate text chat delete fanout policy"
-                );
-            }
-        }
-    }
-
-    state
-        .realtime_hub
-        .fanout_to_streams(
-            RealtimeModule::TextChat,
-            &server_id,
-            RealtimeKind::TextChat(TextChatKind::MessageDeleted),
-            &stream_ids,
-            message,
-        )
-        .await;
-
-    Ok(())
-}
-
 pub(super) fn parse_id(value: &str, message: &str) -> Result<Uuid, TextChatApplicationError> {
     Uuid::parse_str(value).map_err(|_| TextChatApplicationError::BadRequest(message.to_owned()))
 }
@@ -351,20 +348,20 @@ async fn load_message_attachments(
 ) -> Result<Vec<crate::features::text_chat::domain::ChatAttachment>, TextChatApplicationError> {
     if attachment_ids.len() > 1 {
         return Err(TextChatApplicationError::BadRequest(
-            "К сообщению можно прикрепить только одно изображение.".to_owned(),
+            "К сообщению можно прикрепить только одно вложение.".to_owned(),
         ));
     }
 
     let mut attachments = Vec::new();
     for attachment_id in attachment_ids {
-        let attachment_id = parse_id(attachment_id, "Изображение не найдено.")?;
+        let attachment_id = parse_id(attachment_id, "Вложение не найдено.")?;
         let attachment = state
             .text_chat_store
             .find_chat_attachment(&attachment_id)
             .await
             .map_err(TextChatApplicationError::Internal)?
             .ok_or_else(|| {
-                TextChatApplicationError::BadRequest("Изображение не найдено.".to_owned())
+                TextChatApplicationError::BadRequest("Вложение не найдено.".to_owned())
             })?;
         if attachment.server_id != *server_id
             || attachment.room_id != *room_id
@@ -372,7 +369,7 @@ async fn load_message_attachments(
             || attachment.message_id.is_some()
         {
             return Err(TextChatApplicationError::BadRequest(
-                "Изображение недоступно для этого сообщения.".to_owned(),
+                "Вложение недоступно для этого сообщения.".to_owned(),
             ));
         }
         attachments.push(attachment);
@@ -381,7 +378,57 @@ async fn load_message_attachments(
     Ok(attachments)
 }
 
-fn message_summary(message: &TextMessage, author_avatar_url: Option<String>) -> TextChatMessage {
+/// Собирает сообщения страницы вместе с цитатами, реакциями, аватарами авторов
+/// и уже полученными карточками ссылок.
+///
+/// Страница может содержать сообщения нескольких комнат, поэтому снимки
+/// цитируемых сообщений загружаются отдельно для каждой комнаты.
+pub(super) async fn message_summaries(
+    state: &AppState,
+    user_id: &Uuid,
+    messages: &[TextMessage],
+) -> anyhow::Result<Vec<TextChatMessage>> {
+    let mut room_ids = messages
+        .iter()
+        .map(|message| message.room_id)
+        .collect::<Vec<_>>();
+    room_ids.sort_unstable();
+    room_ids.dedup();
+    let mut reply_snapshots = HashMap::new();
+    for room_id in room_ids {
+        reply_snapshots.extend(replies::reply_snapshots(state, &room_id, messages).await?);
+    }
+    let mut reactions = reactions::message_reactions(state, user_id, messages).await?;
+    let avatar_urls = image_application::avatar_urls_by_user_ids(
+        state,
+        messages.iter().map(|message| message.author_user_id),
+    )
+    .await?;
+
+    let mut summaries = messages
+        .iter()
+        .map(|message| {
+            message_summary(
+                message,
+                avatar_urls.get(&message.author_user_id).cloned(),
+                message
+                    .reply_to_message_id
+                    .and_then(|parent_id| reply_snapshots.get(&parent_id).cloned()),
+                reactions.remove(&message.id).unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>();
+    link_previews::attach_known_previews(state, &mut summaries).await?;
+
+    Ok(summaries)
+}
+
+pub(super) fn message_summary(
+    message: &TextMessage,
+    author_avatar_url: Option<String>,
+    reply_to: Option<TextChatReplySnapshot>,
+    reactions: Vec<MessageReactionSummary>,
+) -> TextChatMessage {
     TextChatMessage {
         id: message.id.to_string(),
         server_id: message.server_id.to_string(),
@@ -393,16 +440,18 @@ fn message_summary(message: &TextMessage, author_avatar_url: Option<String>) ->
         attachments: message
             .attachments
             .iter()
-            .map(|attachment| TextChatImageAttachment {
-                id: attachment.id.to_string(),
-                content_type: attachment.content_type.clone(),
-                byte_size: attachment.byte_size,
-                width: attachment.width,
-                height: attachment.height,
-            })
+            .map(attachments::attachment_summary)
             .collect(),
         delivery_status: None,
         created_at: message.created_at.to_rfc3339(),
+        edited_at: message.edited_at.map(|edited_at| edited_at.to_rfc3339()),
+        reply_to_message_id: message
+            .reply_to_message_id
+            .map(|parent_id| parent_id.to_string()),
+        reply_to,
+        reactions,
+        mentions: mentions::mention_summaries(&message.mentions),
+        link_previews: Vec::new(),
     }
 }
 
@@ -0,0 +1,284 @@
+//! Определение вида вложения по содержимому и лимиты размера для каждого вида.
+
+use image::GenericImageView;
+
+use super::TextChatApplicationError;
+use crate::features::text_chat::domain::ChatAttachmentKind;
+
+const MIB: usize = 1024 * 1024;
+const MAX_IMAGE_BYTES: usize = 10 * MIB;
+const MAX_AUDIO_BYTES: usize = 25 * MIB;
+const MAX_VIDEO_BYTES: usize = 50 * MIB;
+const MAX_FILE_BYTES: usize = 25 * MIB;
+/// Самый большой из лимитов; используется как предел тела HTTP-загрузки.
+pub(crate) const MAX_ATTACHMENT_UPLOAD_BYTES: usize = MAX_VIDEO_BYTES;
+
+/// Вид и MIME-тип вложения, определенные по сигнатуре содержимого.
+///
+/// Заявленные клиентом тип и расширение не учитываются: браузер получит
+/// только тот тип, который подтверждается байтами файла.
+pub(crate) struct SniffedAttachment {
+    pub(crate) kind: ChatAttachmentKind,
+    pub(crate) content_type: &'static str,
+    pub(crate) extension: &'static str,
+    pub(crate) width: Option<u32>,
+    pub(crate) height: Option<u32>,
+}
+
+/// Определяет вид вложения и проверяет лимит размера для этого вида.
+pub(crate) fn sniff_attachment(
+    bytes: &[u8],
+) -> Result<SniffedAttachment, TextChatApplicationError> {
+    if bytes.is_empty() {
+        tracing::warn!("rejected empty chat attachment upload");
+        return Err(TextChatApplicationError::BadRequest(
+            "Выбери файл для отправки.".to_owned(),
+        ));
+    }
+    if bytes.len() > MAX_ATTACHMENT_UPLOAD_BYTES {
+        return Err(oversized(bytes.len(), MAX_ATTACHMENT_UPLOAD_BYTES));
+    }
+
+    let sniffed = match supported_image(bytes) {
+        Some((content_type, extension)) => {
+            if bytes.len() > MAX_IMAGE_BYTES {
+                return Err(oversized(bytes.len(), MAX_IMAGE_BYTES));
+            }
+            let (width, height) = image_dimensions(bytes)?;
+            SniffedAttachment {
+                kind: ChatAttachmentKind::Image,
+                content_type,
+                extension,
+                width: Some(width),
+                height: Some(height),
+            }
+        }
+        None => {
+            let (kind, content_type, extension) = sniff_media_or_file(bytes);
+            SniffedAttachment {
+                kind,
+                content_type,
+                extension,
+                width: None,
+                height: None,
+            }
+        }
+    };
+    let limit = match sniffed.kind {
+        ChatAttachmentKind::Image => MAX_IMAGE_BYTES,
+        ChatAttachmentKind::Audio => MAX_AUDIO_BYTES,
+        ChatAttachmentKind::Video => MAX_VIDEO_BYTES,
+        ChatAttachmentKind::File => MAX_FILE_BYTES,
+    };
+    if bytes.len() > limit {
+        return Err(oversized(bytes.len(), limit));
+    }
+
+    Ok(sniffed)
+}
+
+fn oversized(input_bytes: usize, limit: usize) -> TextChatApplicationError {
+    tracing::warn!(
+        input_bytes,
+        limit,
+        "rejected oversized chat attachment upload"
+    );
+    TextChatApplicationError::BadRequest(format!(
+        "Файл слишком большой. Загрузи файл до {} МБ.",
+        limit / MIB
+    ))
+}
+
+/// Изображения, которые лента показывает сама; прочие форматы считаются файлами.
+fn supported_image(bytes: &[u8]) -> Option<(&'static str, &'static str)> {
+    match image::guess_format(bytes).ok()? {
+        image::ImageFormat::Jpeg => Some(("image/jpeg", "jpg")),
+        image::ImageFormat::Png => Some(("image/png", "png")),
+        image::ImageFormat::WebP => Some(("image/webp", "webp")),
+        image::ImageFormat::Gif => Some(("image/gif", "gif")),
+        _ => None,
+    }
+}
+
+fn image_dimensions(bytes: &[u8]) -> Result<(u32, u32), TextChatApplicationError> {
+    let decoded =
+        crate::features::images::application::decode_image_limited(bytes).map_err(|error| {
+            tracing::warn!(%error, input_bytes = bytes.len(), "rejected invalid chat image upload");
+            TextChatApplicationError::BadRequest("Не удалось прочитать изображение.".to_owned())
+        })?;
+    let (width, height) = decoded.dimensions();
+    if width == 0 || height == 0 {
+        tracing::warn!(width, height, "rejected empty-dimension chat image");
+        return Err(TextChatApplicationError::BadRequest(
+            "Изображение пустое.".to_owned(),
+        ));
+    }
+    Ok((width, height))
+}
+
+fn sniff_media_or_file(bytes: &[u8]) -> (ChatAttachmentKind, &'static str, &'static str) {
+    use ChatAttachmentKind::{Audio, File, Video};
+
+    let at = |offset: usize, signature: &[u8]| {
+        bytes
+            .get(offset..offset + signature.len())
+            .is_some_and(|window| window == signature)
+    };
+    let head = &bytes[..bytes.len().min(64)];
+    let head_contains = |needle: &[u8]| head.windows(needle.len()).any(|window| window == needle);
+
+    if at(4, b"ftyp") {
+        return match bytes.get(8..12).unwrap_or_default() {
+            b"M4A " | b"M4B " => (Audio, "audio/mp4", "m4a"),
+            b"qt  " => (Video, "video/quicktime", "mov"),
+            // HEIF и AVIF лента не показывает, поэтому это обычные файлы.
+            b"avif" | b"avis" | b"heic" | b"heix" | b"mif1" | b"msf1" => {
+                (File, "application/octet-stream", "bin")
+            }
+            _ => (Video, "video/mp4", "mp4"),
+        };
+    }
+    if at(0, &[0x1A, 0x45, 0xDF, 0xA3]) {
+        return if head_contains(b"webm") {
+            (Video, "video/webm", "webm")
+        } else {
+            (Video, "video/x-matroska", "mkv")
+        };
+    }
+    if at(0, b"RIFF") && at(8, b"AVI ") {
+        return (Video, "video/x-msvideo", "avi");
+    }
+    if at(0, b"RIFF") && at(8, b"WAVE") {
+        return (Audio, "audio/wav", "wav");
+    }
+    if at(0, b"OggS") {
+        return if head_contains(b"theora") {
+            (Video, "video/ogg", "ogv")
+        } else {
+            (Audio, "audio/ogg", "ogg")
+        };
+    }
+    if at(0, b"fLaC") {
+        return (Audio, "audio/flac", "flac");
+    }
+    if at(0, b"ID3") {
+        return (Audio, "audio/mpeg", "mp3");
+    }
+    if let [0xFF, second, ..] = bytes {
+        // ADTS AAC: layer 00; иначе это кадр MPEG audio.
+        if second & 0xF6 == 0xF0 {
+            return (Audio, "audio/aac", "aac");
+        }
+        if second & 0xE0 == 0xE0 {
+            return (Audio, "audio/mpeg", "mp3");
+        }
+    }
+    if at(0, b"%PDF-") {
+        return (File, "application/pdf", "pdf");
+    }
+    if at(0, b"PK\x03\x04") || at(0, b"PK\x05\x06") {
+        return (File, "application/zip", "zip");
+    }
+    if at(0, &[0x1F, 0x8B]) {
+        return (File, "application/gzip", "gz");
+    }
+    if at(0, &[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) {
+        return (File, "application/x-7z-compressed", "7z");
+    }
+    if at(0, b"Rar!\x1A\x07") {
+        return (File, "application/vnd.rar", "rar");
+    }
+    if is_plain_text(bytes) {
+        // SVG, HTML и скрипты тоже попадают сюда и отдаются как текст, а не исполняются.
+        return (File, "text/plain; charset=utf-8", "txt");
+    }
+
+    (File, "application/octet-stream", "bin")
+}
+
+fn is_plain_text(bytes: &[u8]) -> bool {
+    std::str::from_utf8(bytes).is_ok_and(|text| {
+        !text.chars().any(|character| {
+            character.is_control() && !matches!(character, '\t' | '\n' | '\r' | '\x0C' | '\x1B')
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MAX_FILE_BYTES, sniff_attachment, sniff_media_or_file};
+    use crate::features::text_chat::domain::ChatAttachmentKind;
+
+    #[test]
+    fn detects_media_and_archives_by_signature() {
+        let cases: [(&[u8], ChatAttachmentKind, &str); 8] = [
+            (
+                b"\0\0\0\x20ftypisom\0\0\x02\0",
+                ChatAttachmentKind::Video,
+                "video/mp4",
+            ),
+            (
+                b"\0\0\0\x20ftypM4A \0\0\0\0",
+                ChatAttachmentKind::Audio,
+                "audio/mp4",
+            ),
+            (
+                b"\x1A\x45\xDF\xA3\x9F\x42\x86\x81\x01\x42\x82\x84webm",
+                ChatAttachmentKind::Video,
+                "video/webm",
+            ),
+            (
+                b"ID3\x04\0\0\0\0\0\0",
+                ChatAttachmentKind::Audio,
+                "audio/mpeg",
+            ),
+            (
+                b"RIFF\x24\0\0\0WAVEfmt ",
+                ChatAttachmentKind::Audio,
+                "audio/wav",
+            ),
+            (b"%PDF-1.7\n", ChatAttachmentKind::File, "application/pdf"),
+            (
+                b"PK\x03\x04\x14\0",
+                ChatAttachmentKind::File,
+                "application/zip",
+            ),
+            (
+                b"2026-10-17 INFO started\n",
+                ChatAttachmentKind::File,
+                "text/plain; charset=utf-8",
+            ),
+        ];
+
+        for (bytes, kind, content_type) in cases {
+            assert_eq!(sniff_media_or_file(bytes).0, kind);
+            assert_eq!(sniff_media_or_file(bytes).1, content_type);
+        }
+    }
+
+    #[test]
+    fn markup_is_served_as_text_and_binary_as_octet_stream() {
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"><script>alert(1)</script></svg>";
+
+        assert_eq!(sniff_media_or_file(svg).1, "text/plain; charset=utf-8");
+        assert_eq!(
+            sniff_media_or_file(&[0x00, 0x01, 0x02, 0xFE]).1,
+            "application/octet-stream"
+        );
+    }
+
+    #[test]
+    fn applies_size_limit_of_the_detected_kind() {
+        let mut oversized_log = vec![b'a'; MAX_FILE_BYTES + 1];
+        oversized_log[0] = b'#';
+        let mut video = vec![0; MAX_FILE_BYTES + 1];
+        video[4..12].copy_from_slice(b"ftypisom");
+
+        assert!(sniff_attachment(&oversized_log).is_err());
+        assert_eq!(
+            sniff_attachment(&video).map(|sniffed| sniffed.kind).ok(),
+            Some(ChatAttachmentKind::Video)
+        );
+        assert!(sniff_attachment(&[]).is_err());
+    }
+}
@@ -1,41 +1,44 @@
 //! Потоки приложения для вложений текстового чата.
 
-use cheenhub_contracts::realtime::ChatImageUploadResponse;
-use image::GenericImageView;
+use cheenhub_contracts::realtime::{
+    ChatAttachmentUploadResponse, TextChatAttachment, TextChatAttachmentKind,
+};
 use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use super::attachment_content::sniff_attachment;
 use super::{TextChatApplicationError, ensure_room_text_available, parse_id};
-use crate::features::text_chat::domain::{ChatAttachment, NewChatAttachment};
+use crate::features::text_chat::domain::{ChatAttachment, ChatAttachmentKind, NewChatAttachment};
 use crate::state::AppState;
 
-const MAX_CHAT_IMAGE_UPLOAD_BYTES: usize = 10 * 1024 * 1024;
-
-/// Загружает одно вложение-изображение для комнаты текстового чата.
-pub(crate) async fn upload_chat_image(
+/// Загружает одно вложение для комнаты текстового чата.
+///
+/// Вид и MIME-тип определяются по содержимому, лимит размера зависит от вида.
+pub(crate) async fn upload_chat_attachment(
     state: &AppState,
     user_id: &Uuid,
     server_id: String,
     room_id: String,
     original_filename: Option<String>,
     bytes: &[u8],
-) -> Result<ChatImageUploadResponse, TextChatApplicationError> {
+) -> Result<ChatAttachmentUploadResponse, TextChatApplicationError> {
     let server_id = parse_id(&server_id, "Сервер не найден.")?;
     let room_id = parse_id(&room_id, "Комната не найдена.")?;
     ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
 
-    let validated = validate_chat_image(bytes)?;
+    let sniffed = sniff_attachment(bytes)?;
     let attachment_id = Uuid::new_v4();
     let object_key = format!(
-        "chat-images/{server_id}/{room_id}/{attachment_id}.{}",
-        validated.extension
+        "{}/{server_id}/{room_id}/{attachment_id}.{}",
+        object_key_prefix(sniffed.kind),
+        sniffed.extension
     );
     let Some(bucket) = state.chat_attachment_object_store.bucket() else {
         tracing::warn!(
             server_id = %server_id,
             room_id = %room_id,
             user_id = %user_id,
-            "rejected chat image upload because S3 storage is not configured"
+            "rejected chat attachment upload because S3 storage is not configured"
         );
         return Err(TextChatApplicationError::Misconfigured {
             feature: "chat_images_s3",
@@ -46,14 +49,14 @@ pub(crate) async fn upload_chat_image(
                 "CHAT_IMAGES_S3_ACCESS_KEY_ID",
                 "CHAT_IMAGES_S3_SECRET_ACCESS_KEY",
             ],
-            message: "Загрузка изображений пока не настроена.".to_owned(),
+            message: "Загрузка файлов пока не настроена.".to_owned(),
         });
     };
     let bucket = bucket.to_owned();
 
     state
         .chat_attachment_object_store
-        .put_object(&object_key, validated.content_type, bytes.to_vec())
+        .put_object(&object_key, sniffed.content_type, bytes.to_vec())
         .await
         .map_err(|error| {
             tracing::error!(
@@ -63,7 +66,7 @@ pub(crate) async fn upload_chat_image(
                 user_id = %user_id,
                 object_key = %object_key,
                 error = ?error,
-                "failed to upload chat image to object storage"
+                "failed to upload chat attachment to object storage"
             );
             TextChatApplicationError::Internal(error)
         })?;
@@ -76,10 +79,15 @@ pub(crate) async fn upload_chat_image(
         message_id: None,
         bucket,
         object_key: object_key.clone(),
-        content_type: validated.content_type.to_owned(),
+        kind: sniffed.kind,
+        content_type: sniffed.content_type.to_owned(),
         byte_size: i64::try_from(bytes.len()).unwrap_or(i64::MAX),
-        width: i32::try_from(validated.width).unwrap_or(i32::MAX),
-        height: i32::try_from(validated.height).unwrap_or(i32::MAX),
+        width: sniffed
+            .width
+            .map(|width| i32::try_from(width).unwrap_or(i32::MAX)),
+        height: sniffed
+            .height
+            .map(|height| i32::try_from(height).unwrap_or(i32::MAX)),
         sha256: sha256_hex(bytes),
         original_filename: original_filename.and_then(clean_filename),
     };
@@ -96,7 +104,7 @@ pub(crate) async fn upload_chat_image(
                 user_id = %user_id,
                 object_key = %object_key,
                 %error,
-                "failed to persist chat image metadata"
+                "failed to persist chat attachment metadata"
             );
             TextChatApplicationError::Internal(error)
         })?;
@@ -107,36 +115,63 @@ pub(crate) async fn upload_chat_image(
         room_id = %room_id,
         user_id = %user_id,
         byte_size = bytes.len(),
-        content_type = validated.content_type,
-        width = validated.width,
-        height = validated.height,
-        "uploaded chat image"
+        kind = attachment.kind.as_str(),
+        content_type = sniffed.content_type,
+        width = sniffed.width,
+        height = sniffed.height,
+        "uploaded chat attachment"
     );
 
-    Ok(ChatImageUploadResponse {
+    Ok(ChatAttachmentUploadResponse {
         id: attachment_id.to_string(),
         server_id: server_id.to_string(),
         room_id: room_id.to_string(),
-        content_type: validated.content_type.to_owned(),
-        byte_size: i64::try_from(bytes.len()).unwrap_or(i64::MAX),
-        width: i32::try_from(validated.width).unwrap_or(i32::MAX),
-        height: i32::try_from(validated.height).unwrap_or(i32::MAX),
+        kind: contract_kind(attachment.kind),
+        content_type: attachment.content_type,
+        byte_size: attachment.byte_size,
+        width: attachment.width,
+        height: attachment.height,
+        original_filename: attachment.original_filename,
     })
 }
 
 /// Загружает одно вложение-изображение после проверки видимости комнаты.
+///
+/// Остальные виды вложений этим путем не отдаются: они скачиваются по HTTP.
 pub(crate) async fn chat_image(
     state: &AppState,
     user_id: &Uuid,
     attachment_id: String,
 ) -> Result<(ChatAttachment, Vec<u8>), TextChatApplicationError> {
-    let attachment_id = parse_id(&attachment_id, "Изображение не найдено.")?;
+    let (attachment, bytes) = chat_attachment(state, user_id, attachment_id)
+        .await
+        .map_err(|error| match error {
+            TextChatApplicationError::NotFound(_) => {
+                TextChatApplicationError::NotFound("Изображение не найдено.".to_owned())
+            }
+            error => error,
+        })?;
+    if attachment.kind != ChatAttachmentKind::Image {
+        return Err(TextChatApplicationError::NotFound(
+            "Изображение не найдено.".to_owned(),
+        ));
+    }
+    Ok((attachment, bytes))
+}
+
+/// Загружает байты вложения любого вида после проверки видимости комнаты.
+pub(crate) async fn chat_attachment(
+    state: &AppState,
+    user_id: &Uuid,
+    attachment_id: String,
+) -> Result<(ChatAttachment, Vec<u8>), TextChatApplicationError> {
+    let attachment_id = parse_id(&attachment_id, "Вложение не найдено.")?;
     let attachment = state
         .text_chat_store
         .find_chat_attachment(&attachment_id)
         .await
         .map_err(TextChatApplicationError::Internal)?
-        .ok_or_else(|| TextChatApplicationError::NotFound("Изображение не найдено.".to_owned()))?;
+        .ok_or_else(|| TextChatApplicationError::NotFound("Вложение не найдено.".to_owned()))?;
     ensure_room_text_available(state, user_id, &attachment.server_id, &attachment.room_id).await?;
 
     let object = state
@@ -151,7 +186,7 @@ pub(crate) async fn chat_image(
                 user_id = %user_id,
                 object_key = %attachment.object_key,
                 error = ?error,
-                "failed to read chat image from object storage"
+                "failed to read chat attachment from object storage"
             );
             TextChatApplicationError::Internal(error)
         })?;
@@ -163,6 +198,7 @@ pub(crate) async fn chat_image(
         uploader_user_id = %attachment.uploader_user_id,
         user_id = %user_id,
         bucket = %attachment.bucket,
+        kind = attachment.kind.as_str(),
         stored_byte_size = attachment.byte_size,
         width = attachment.width,
         height = attachment.height,
@@ -171,75 +207,44 @@ pub(crate) async fn chat_image(
         created_at = %attachment.created_at,
         byte_size = object.bytes.len(),
         content_type = %object.content_type,
-        "serving chat image through backend proxy"
+        "serving chat attachment through backend proxy"
     );
 
     Ok((attachment, object.bytes))
 }
 
-struct ValidatedChatImage {
-    content_type: &'static str,
-    extension: &'static str,
-    width: u32,
-    height: u32,
+/// Собирает метаданные вложения для сообщения текстового чата.
+pub(super) fn attachment_summary(attachment: &ChatAttachment) -> TextChatAttachment {
+    TextChatAttachment {
+        id: attachment.id.to_string(),
+        kind: contract_kind(attachment.kind),
+        content_type: attachment.content_type.clone(),
+        byte_size: attachment.byte_size,
+        width: attachment.width,
+        height: attachment.height,
+        original_filename: attachment.original_filename.clone(),
+    }
 }
 
-fn validate_chat_image(bytes: &[u8]) -> Result<ValidatedChatImage, TextChatApplicationError> {
-    if bytes.is_empty() {
-        tracing::warn!("rejected empty chat image upload");
-        return Err(TextChatApplicationError::BadRequest(
-            "Выбери изображение для отправки.".to_owned(),
-        ));
-    }
-    if bytes.len() > MAX_CHAT_IMAGE_UPLOAD_BYTES {
-        tracing::warn!(
-            input_bytes = bytes.len(),
-            "rejected oversized chat image upload"
-        );
-        return Err(TextChatApplicationError::BadRequest(
-            "Изображение слишком большое. Загрузи файл до 10 МБ.".to_owned(),
-        ));
+pub(crate) fn contract_kind(kind: ChatAttachmentKind) -> TextChatAttachmentKind {
+    match kind {
+        ChatAttachmentKind::Image => TextChatAttachmentKind::Image,
+        ChatAttachmentKind::Video => TextChatAttachmentKind::Video,
+        ChatAttachmentKind::Audio => TextChatAttachmentKind::Audio,
+        ChatAttachmentKind::File => TextChatAttachmentKind::File,
     }
+}
 
-    let format = image::guess_format(bytes).map_err(|error| {
-        tracing::warn!(%error, input_bytes = bytes.len(), "rejected chat upload with unknown image format");
-        TextChatApplicationError::BadRequest("Не удалось прочитать изображение.".to_owned())
-    })?;
-    let (content_type, extension) = match format {
-        image::ImageFormat::Jpeg => ("image/jpeg", "jpg"),
-        image::ImageFormat::Png => ("image/png", "png"),
-        image::ImageFormat::WebP => ("image/webp", "webp"),
-        image::ImageFormat::Gif => ("image/gif", "gif"),
-        _ => {
-            tracing::warn!(?format, "rejected unsupported chat image format");
-            return Err(TextChatApplicationError::BadRequest(
-                "Поддерживаются только JPEG, PNG, WebP и GIF.".to_owned(),
-            ));
-        }
-    };
-
-    let decoded =
-        crate::features::images::application::decode_image_limited(bytes).map_err(|error| {
-            tracing::warn!(%error, input_bytes = bytes.len(), "rejected invalid chat image upload");
-            TextChatApplicationError::BadRequest("Не удалось прочитать изображение.".to_owned())
-        })?;
-    let (width, height) = decoded.dimensions();
-    if width == 0 || height == 0 {
-        tracing::warn!(width, height, "rejected empty-dimension chat image");
-        return Err(TextChatApplicationError::BadRequest(
-            "Изображение пустое.".to_owned(),
-        ));
+fn object_key_prefix(kind: ChatAttachmentKind) -> &'static str {
+    match kind {
+        ChatAttachmentKind::Image => "chat-images",
+        ChatAttachmentKind::Video => "chat-videos",
+        ChatAttachmentKind::Audio => "chat-audio",
+        ChatAttachmentKind::File => "chat-files",
     }
-
-    Ok(ValidatedChatImage {
-        content_type,
-        extension,
-        width,
-        height,
-    })
 }
 
-fn clean_filename(value: String) -> Option<String> {
+pub(crate) fn clean_filename(value: String) -> Option<String> {
     // Убираем разделители путей, управляющие символы и ведущие/замыкающие точки,
     // чтобы сохраненное имя нельзя было использовать для path traversal или
     // инъекции в заголовок Content-Disposition, если оно когда-нибудь попадет туда.
@@ -257,7 +262,7 @@ fn clean_filename(value: String) -> Option<String> {
     }
 }
 
-fn sha256_hex(bytes: &[u8]) -> String {
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
     Sha256::digest(bytes)
         .iter()
         .map(|byte| format!("{byte:02x}"))
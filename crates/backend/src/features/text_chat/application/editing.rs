@@ -0,0 +1,89 @@
+//! Редактирование текстовых сообщений автором.
+
+use cheenhub_contracts::realtime::{EditMessage, EditMessageAccepted, MessageEditedPayload};
+use cheenhub_contracts::rest::AuthUser;
+use tracing::error;
+use uuid::Uuid;
+
+use super::{
+    TextChatApplicationError, ensure_room_text_available, fanout, link_previews, mentions,
+    message_summary, parse_id, reactions, replies,
+};
+use crate::features::text_chat::validation;
+use crate::state::AppState;
+
+/// Заменяет текст сообщения. Изменять сообщение может только его автор,
+/// права модерации на редактирование не распространяются.
+///
+/// Упоминания разбираются заново и заменяют прежние, но повторных
+/// уведомлений упомянутым участникам не отправляется. Карточки новых ссылок
+/// запрашиваются так же, как при отправке.
+pub(crate) async fn edit_message(
+    state: &AppState,
+    user: &AuthUser,
+    user_id: &Uuid,
+    request: EditMessage,
+) -> Result<EditMessageAccepted, TextChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    let message_id = parse_id(&request.message_id, "Сообщение не найдено.")?;
+    ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
+    let valid = validation::message_body(request.body)
+        .map_err(|message| TextChatApplicationError::BadRequest(message.to_owned()))?;
+    let mentions = mentions::resolve_mentions(state, &server_id, &room_id, &valid.mention_names)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+
+    let message = state
+        .text_chat_store
+        .edit_message(
+            &server_id,
+            &room_id,
+            &message_id,
+            user_id,
+            valid.body,
+            mentions,
+        )
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .ok_or_else(|| {
+            TextChatApplicationError::NotFound("Сообщение не найдено или уже удалено.".to_owned())
+        })?;
+    let edited_at = message.edited_at.unwrap_or(message.created_at).to_rfc3339();
+
+    let edited_payload = MessageEditedPayload {
+        server_id: message.server_id.to_string(),
+        room_id: message.room_id.to_string(),
+        message_id: message.id.to_string(),
+        body: message.body.clone(),
+        edited_at,
+        mentions: mentions::mention_summaries(&message.mentions),
+    };
+    if let Err(error) = fanout::fanout_message_edited(state, edited_payload).await {
+        error!(
+            message_id = %message.id,
+            user_id = %user_id,
+            %error,
+            "failed to schedule text chat edit fanout"
+        );
+    }
+    link_previews::schedule_link_previews(state, &message);
+
+    let reply_to = replies::reply_snapshots(state, &room_id, std::slice::from_ref(&message))
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .into_values()
+        .next();
+    let reactions = reactions::message_reactions(state, user_id, std::slice::from_ref(&message))
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .remove(&message.id)
+        .unwrap_or_default();
+
+    let mut summary = message_summary(&message, user.avatar_url.clone(), reply_to, reactions);
+    link_previews::attach_known_previews(state, std::slice::from_mut(&mut summary))
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+
+    Ok(EditMessageAccepted { message: summary })
+}
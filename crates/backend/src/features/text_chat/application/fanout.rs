@@ -0,0 +1,163 @@
+//! Рассылка событий текстового чата участникам комнаты.
+
+use cheenhub_contracts::realtime::{
+    MessageDeletedPayload, MessageEditedPayload, MessagePinnedPayload, MessagePreviewReadyPayload,
+    ReactionsChangedPayload, RealtimeKind, RealtimeModule, TextChatKind, TextChatMessage,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::features::text_chat::policy;
+use crate::state::AppState;
+
+pub(super) async fn fanout_message_created(
+    state: &AppState,
+    message: TextChatMessage,
+) -> anyhow::Result<()> {
+    let server_id = Uuid::parse_str(&message.server_id)?;
+    let room_id = Uuid::parse_str(&message.room_id)?;
+    fanout_room_event(
+        state,
+        &server_id,
+        &room_id,
+        TextChatKind::MessageCreated,
+        message,
+    )
+    .await;
+
+    Ok(())
+}
+
+pub(super) async fn fanout_message_deleted(
+    state: &AppState,
+    message: MessageDeletedPayload,
+) -> anyhow::Result<()> {
+    let server_id = Uuid::parse_str(&message.server_id)?;
+    let room_id = Uuid::parse_str(&message.room_id)?;
+    fanout_room_event(
+        state,
+        &server_id,
+        &room_id,
+        TextChatKind::MessageDeleted,
+        message,
+    )
+    .await;
+
+    Ok(())
+}
+
+pub(super) async fn fanout_message_edited(
+    state: &AppState,
+    message: MessageEditedPayload,
+) -> anyhow::Result<()> {
+    let server_id = Uuid::parse_str(&message.server_id)?;
+    let room_id = Uuid::parse_str(&message.room_id)?;
+    fanout_room_event(
+        state,
+        &server_id,
+        &room_id,
+        TextChatKind::MessageEdited,
+        message,
+    )
+    .await;
+
+    Ok(())
+}
+
+pub(super) async fn fanout_reactions_changed(
+    state: &AppState,
+    payload: ReactionsChangedPayload,
+) -> anyhow::Result<()> {
+    let server_id = Uuid::parse_str(&payload.server_id)?;
+    let room_id = Uuid::parse_str(&payload.room_id)?;
+    fanout_room_event(
+        state,
+        &server_id,
+        &room_id,
+        TextChatKind::ReactionsChanged,
+        payload,
+    )
+    .await;
+
+    Ok(())
+}
+
+pub(super) async fn fanout_message_pinned(
+    state: &AppState,
+    payload: MessagePinnedPayload,
+) -> anyhow::Result<()> {
+    let server_id = Uuid::parse_str(&payload.server_id)?;
+    let room_id = Uuid::parse_str(&payload.room_id)?;
+    fanout_room_event(
+        state,
+        &server_id,
+        &room_id,
+        TextChatKind::MessagePinned,
+        payload,
+    )
+    .await;
+
+    Ok(())
+}
+
+pub(super) async fn fanout_message_preview_ready(
+    state: &AppState,
+    payload: MessagePreviewReadyPayload,
+) -> anyhow::Result<()> {
+    let server_id = Uuid::parse_str(&payload.server_id)?;
+    let room_id = Uuid::parse_str(&payload.room_id)?;
+    fanout_room_event(
+        state,
+        &server_id,
+        &room_id,
+        TextChatKind::MessagePreviewReady,
+        payload,
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Отправляет событие всем потокам сервера, которым политика разрешает видеть комнату.
+async fn fanout_room_event<P>(
+    state: &AppState,
+    server_id: &Uuid,
+    room_id: &Uuid,
+    kind: TextChatKind,
+    payload: P,
+) where
+    P: Serialize + Clone,
+{
+    let candidates = state
+        .realtime_hub
+        .recipients(state, RealtimeModule::TextChat, server_id)
+        .await;
+    let mut stream_ids = Vec::new();
+
+    for candidate in candidates {
+        match policy::can_receive_room_event(state, &candidate.user_id, server_id, room_id).await {
+            Ok(true) => stream_ids.push(candidate.stream_id),
+            Ok(false) => {}
+            Err(error) => {
+                tracing::warn!(
+                    stream_id = %candidate.stream_id,
+                    user_id = %candidate.user_id,
+                    ?kind,
+                    %error,
+                    "failed to evaluate text chat fanout policy"
+                );
+            }
+        }
+    }
+
+    state
+        .realtime_hub
+        .fanout_to_streams(
+            RealtimeModule::TextChat,
+            server_id,
+            RealtimeKind::TextChat(kind),
+            &stream_ids,
+            payload,
+        )
+        .await;
+}
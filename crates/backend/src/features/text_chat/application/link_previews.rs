@@ -0,0 +1,69 @@
+//! Карточки ссылок в сообщениях комнат.
+
+use cheenhub_contracts::realtime::{MessagePreviewReadyPayload, TextChatMessage};
+use tracing::error;
+
+use super::fanout;
+use crate::features::link_previews::application as link_preview_application;
+use crate::features::text_chat::domain::TextMessage;
+use crate::state::AppState;
+
+/// Запускает фоновое получение карточек ссылок сообщения и рассылает готовые карточки.
+pub(super) fn schedule_link_previews(state: &AppState, message: &TextMessage) {
+    if link_preview_application::message_links(&message.body).is_empty() {
+        return;
+    }
+    let state = state.clone();
+    let message = message.clone();
+    tokio::spawn(async move {
+        let previews = match link_preview_application::resolve_link_previews(
+            &state,
+            message.author_user_id,
+            &message.body,
+        )
+        .await
+        {
+            Ok(previews) if previews.is_empty() => return,
+            Ok(previews) => previews,
+            Err(error) => {
+                error!(
+                    message_id = %message.id,
+                    room_id = %message.room_id,
+                    %error,
+                    "failed to resolve text chat link previews"
+                );
+                return;
+            }
+        };
+        let payload = MessagePreviewReadyPayload {
+            server_id: message.server_id.to_string(),
+            room_id: message.room_id.to_string(),
+            message_id: message.id.to_string(),
+            previews,
+        };
+        if let Err(error) = fanout::fanout_message_preview_ready(&state, payload).await {
+            error!(
+                message_id = %message.id,
+                room_id = %message.room_id,
+                %error,
+                "failed to schedule text chat link preview fanout"
+            );
+        }
+    });
+}
+
+/// Дополняет собранные сообщения уже полученными карточками ссылок.
+pub(super) async fn attach_known_previews(
+    state: &AppState,
+    summaries: &mut [TextChatMessage],
+) -> anyhow::Result<()> {
+    let bodies = summaries
+        .iter()
+        .map(|summary| summary.body.as_str())
+        .collect::<Vec<_>>();
+    let previews = link_preview_application::known_link_previews(state, &bodies).await?;
+    for (summary, previews) in summaries.iter_mut().zip(previews) {
+        summary.link_previews = previews;
+    }
+    Ok(())
+}
@@ -0,0 +1,183 @@
+//! Разрешение `@`-упоминаний и персональные уведомления упомянутых участников.
+
+use std::collections::HashSet;
+
+use cheenhub_contracts::realtime::{
+    MentionReceivedPayload, RealtimeKind, RealtimeModule, ServerRoleKind, TextChatKind,
+    TextChatMention, TextChatMentionKind, TextChatMessage,
+};
+use uuid::Uuid;
+
+use crate::features::push_notifications::{MentionPush, direct_message_preview};
+use crate::features::text_chat::domain::{MentionKind, MessageMention, TextMessage};
+use crate::features::text_chat::policy;
+use crate::state::AppState;
+
+/// Разрешает имена из `@`-токенов в участников и роли сервера.
+///
+/// Никнейм разрешается, только если пользователь видит комнату. Роль участника
+/// по умолчанию не разрешается, чтобы одно сообщение не уведомляло весь сервер.
+/// Имена без совпадений остаются обычным текстом.
+pub(super) async fn resolve_mentions(
+    state: &AppState,
+    server_id: &Uuid,
+    room_id: &Uuid,
+    names: &[String],
+) -> anyhow::Result<Vec<MessageMention>> {
+    if names.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut mentions = Vec::new();
+    for user in state.auth_store.find_users_by_nicknames(names).await? {
+        if policy::can_receive_room_event(state, &user.id, server_id, room_id).await? {
+            mentions.push(MessageMention {
+                kind: MentionKind::User,
+                target_id: user.id,
+                name: user.nickname,
+            });
+        }
+    }
+    let roles = state.server_store.list_server_roles(server_id).await?;
+    for name in names {
+        mentions.extend(
+            roles
+                .iter()
+                .filter(|role| {
+                    role.kind != ServerRoleKind::Member && role.name.to_lowercase() == *name
+                })
+                .map(|role| MessageMention {
+                    kind: MentionKind::Role,
+                    target_id: role.id,
+                    name: role.name.clone(),
+                }),
+        );
+    }
+
+    Ok(mentions)
+}
+
+/// Доставляет персональное событие и push-уведомление каждому упомянутому участнику.
+///
+/// Упоминание роли раскрывается в ее участников; автор сообщения и участники,
+/// потерявшие доступ к комнате, уведомления не получают.
+pub(super) async fn notify_mentioned(
+    state: &AppState,
+    message: &TextMessage,
+    payload: TextChatMessage,
+) -> anyhow::Result<()> {
+    let recipients = mention_recipients(state, message).await?;
+    if recipients.is_empty() {
+        return Ok(());
+    }
+
+    let delivered = state
+        .realtime_hub
+        .fanout_to_user_streams(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::MentionReceived),
+            &recipients,
+            MentionReceivedPayload { message: payload },
+        )
+        .await;
+    tracing::debug!(
+        message_id = %message.id,
+        recipient_count = recipients.len(),
+        stream_count = delivered,
+        "delivered text chat mention events"
+    );
+
+    let preview = direct_message_preview(&message.body, !message.attachments.is_empty());
+    for recipient_user_id in recipients {
+        let push_payload = MentionPush::new(
+            message.id,
+            message.server_id,
+            message.room_id,
+            message.author_user_id,
+            &message.author_nickname,
+            &preview,
+            message.created_at,
+        );
+        if let Err(error) = state
+            .push_notifications
+            .enqueue_mention(recipient_user_id, push_payload)
+            .await
+        {
+            tracing::error!(
+                %error,
+                message_id = %message.id,
+                %recipient_user_id,
+                "failed to queue mention push deliveries"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Преобразует упоминания сообщения в контракт realtime.
+pub(super) fn mention_summaries(mentions: &[MessageMention]) -> Vec<TextChatMention> {
+    mentions
+        .iter()
+        .map(|mention| TextChatMention {
+            kind: match mention.kind {
+                MentionKind::User => TextChatMentionKind::User,
+                MentionKind::Role => TextChatMentionKind::Role,
+            },
+            target_id: mention.target_id.to_string(),
+            name: mention.name.clone(),
+        })
+        .collect()
+}
+
+async fn mention_recipients(state: &AppState, message: &TextMessage) -> anyhow::Result<Vec<Uuid>> {
+    let mut candidates = HashSet::new();
+    let mentioned_role_ids = message
+        .mentions
+        .iter()
+        .filter(|mention| mention.kind == MentionKind::Role)
+        .map(|mention| mention.target_id)
+        .collect::<HashSet<_>>();
+    if !mentioned_role_ids.is_empty() {
+        let roles = state
+            .server_store
+            .list_server_roles(&message.server_id)
+            .await?;
+        let mentions_owner_role = roles.iter().any(|role| {
+            role.kind == ServerRoleKind::Owner && mentioned_role_ids.contains(&role.id)
+        });
+        if mentions_owner_role
+            && let Some(server) = state.server_store.find_server(&message.server_id).await?
+        {
+            candidates.insert(server.owner_user_id);
+        }
+        candidates.extend(
+            state
+                .server_store
+                .list_server_member_roles(&message.server_id)
+                .await?
+                .into_iter()
+                .filter(|(_, role_id)| mentioned_role_ids.contains(role_id))
+                .map(|(user_id, _)| user_id),
+        );
+    }
+    candidates.extend(
+        message
+            .mentions
+            .iter()
+            .filter(|mention| mention.kind == MentionKind::User)
+            .map(|mention| mention.target_id),
+    );
+    candidates.remove(&message.author_user_id);
+
+    let mut recipients = Vec::new();
+    for user_id in candidates {
+        if policy::can_receive_room_event(state, &user_id, &message.server_id, &message.room_id)
+            .await?
+        {
+            recipients.push(user_id);
+        }
+    }
+
+    Ok(recipients)
+}
@@ -0,0 +1,225 @@
+//! Закрепленные сообщения текстовых комнат.
+
+use cheenhub_contracts::realtime::{
+    ListPinnedMessages, MessagePinnedPayload, PinMessage, PinnedMessagesList, TextChatMessage,
+    TextChatPinnedMessage, UnpinMessage,
+};
+use chrono::Utc;
+use tracing::error;
+use uuid::Uuid;
+
+use super::{TextChatApplicationError, ensure_room_text_available, fanout, parse_id};
+use crate::features::text_chat::domain::{PinnedMessage, TextMessage};
+use crate::features::text_chat::policy;
+use crate::state::AppState;
+
+/// Максимальное количество закрепленных сообщений в одной комнате.
+const MAX_PINNED_MESSAGES: usize = 50;
+
+/// Закрепляет сообщение комнаты и сообщает об этом всем участникам.
+pub(crate) async fn pin_message(
+    state: &AppState,
+    user_id: &Uuid,
+    request: PinMessage,
+) -> Result<MessagePinnedPayload, TextChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    let message_id = parse_id(&request.message_id, "Сообщение не найдено.")?;
+    ensure_can_pin(state, user_id, &server_id, &room_id).await?;
+    let message = state
+        .text_chat_store
+        .find_room_messages(&room_id, &[message_id])
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .into_iter()
+        .find(|message| message.server_id == server_id && message.deleted_at.is_none())
+        .ok_or_else(|| {
+            TextChatApplicationError::NotFound("Сообщение не найдено или уже удалено.".to_owned())
+        })?;
+
+    let pins = active_pins(state, &room_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    if let Some((pin, message)) = pins.iter().find(|(pin, _)| pin.message_id == message_id) {
+        return pinned_payload(state, user_id, pin, message)
+            .await
+            .map_err(TextChatApplicationError::Internal);
+    }
+    if pins.len() >= MAX_PINNED_MESSAGES {
+        return Err(TextChatApplicationError::BadRequest(
+            "В комнате закреплено слишком много сообщений.".to_owned(),
+        ));
+    }
+
+    let pin = PinnedMessage {
+        message_id,
+        room_id,
+        pinned_by_user_id: *user_id,
+        pinned_at: Utc::now(),
+    };
+    let changed = state
+        .text_chat_store
+        .pin_message(pin.clone())
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    let payload = pinned_payload(state, user_id, &pin, &message)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    if changed {
+        schedule_pin_fanout(state, payload.clone(), user_id).await;
+    }
+
+    Ok(payload)
+}
+
+/// Открепляет сообщение комнаты и сообщает об этом всем участникам.
+pub(crate) async fn unpin_message(
+    state: &AppState,
+    user_id: &Uuid,
+    request: UnpinMessage,
+) -> Result<MessagePinnedPayload, TextChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    let message_id = parse_id(&request.message_id, "Сообщение не найдено.")?;
+    ensure_can_pin(state, user_id, &server_id, &room_id).await?;
+
+    let changed = state
+        .text_chat_store
+        .unpin_message(&room_id, &message_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    let payload = MessagePinnedPayload {
+        server_id: server_id.to_string(),
+        room_id: room_id.to_string(),
+        message_id: message_id.to_string(),
+        pinned: false,
+        pin: None,
+    };
+    if changed {
+        schedule_pin_fanout(state, payload.clone(), user_id).await;
+    }
+
+    Ok(payload)
+}
+
+/// Загружает закрепленные сообщения комнаты от недавно закрепленных к давно закрепленным.
+///
+/// Закрепления удаленных сообщений не возвращаются.
+pub(crate) async fn list_pinned_messages(
+    state: &AppState,
+    user_id: &Uuid,
+    request: ListPinnedMessages,
+) -> Result<PinnedMessagesList, TextChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
+
+    let (pins, messages): (Vec<_>, Vec<_>) = active_pins(state, &room_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .into_iter()
+        .unzip();
+    let summaries = super::message_summaries(state, user_id, &messages)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+
+    Ok(PinnedMessagesList {
+        server_id: server_id.to_string(),
+        room_id: room_id.to_string(),
+        pins: pins
+            .iter()
+            .zip(summaries)
+            .map(|(pin, message)| pinned_summary(pin, message))
+            .collect(),
+    })
+}
+
+async fn ensure_can_pin(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &Uuid,
+    room_id: &Uuid,
+) -> Result<(), TextChatApplicationError> {
+    ensure_room_text_available(state, user_id, server_id, room_id).await?;
+    if policy::can_pin_messages(state, user_id, server_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+    {
+        Ok(())
+    } else {
+        Err(TextChatApplicationError::Unauthorized(
+            "Нет права закреплять сообщения.".to_owned(),
+        ))
+    }
+}
+
+/// Загружает закрепления комнаты вместе с сообщениями, пропуская удаленные.
+async fn active_pins(
+    state: &AppState,
+    room_id: &Uuid,
+) -> anyhow::Result<Vec<(PinnedMessage, TextMessage)>> {
+    let pins = state.text_chat_store.room_pinned_messages(room_id).await?;
+    let message_ids = pins.iter().map(|pin| pin.message_id).collect::<Vec<_>>();
+    let mut messages = state
+        .text_chat_store
+        .find_room_messages(room_id, &message_ids)
+        .await?;
+    messages.retain(|message| message.deleted_at.is_none());
+
+    Ok(pins
+        .into_iter()
+        .filter_map(|pin| {
+            let message = messages
+                .iter()
+                .find(|message| message.id == pin.message_id)?
+                .clone();
+            Some((pin, message))
+        })
+        .collect())
+}
+
+async fn pinned_payload(
+    state: &AppState,
+    user_id: &Uuid,
+    pin: &PinnedMessage,
+    message: &TextMessage,
+) -> anyhow::Result<MessagePinnedPayload> {
+    let summary = super::message_summaries(state, user_id, std::slice::from_ref(message))
+        .await?
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("pinned message summary is missing"))?;
+
+    Ok(MessagePinnedPayload {
+        server_id: message.server_id.to_string(),
+        room_id: message.room_id.to_string(),
+        message_id: message.id.to_string(),
+        pinned: true,
+        pin: Some(pinned_summary(pin, summary)),
+    })
+}
+
+fn pinned_summary(pin: &PinnedMessage, message: TextChatMessage) -> TextChatPinnedMessage {
+    TextChatPinnedMessage {
+        message,
+        pinned_by_user_id: pin.pinned_by_user_id.to_string(),
+        pinned_at: pin.pinned_at.to_rfc3339(),
+    }
+}
+
+/// Рассылает изменение закрепления без персональных флагов реакций текущего пользователя.
+async fn schedule_pin_fanout(state: &AppState, mut payload: MessagePinnedPayload, user_id: &Uuid) {
+    if let Some(pin) = payload.pin.as_mut() {
+        for reaction in &mut pin.message.reactions {
+            reaction.reacted = false;
+        }
+    }
+    let message_id = payload.message_id.clone();
+    if let Err(error) = fanout::fanout_message_pinned(state, payload).await {
+        error!(
+            %message_id,
+            %user_id,
+            %error,
+            "failed to schedule text chat pin fanout"
+        );
+    }
+}
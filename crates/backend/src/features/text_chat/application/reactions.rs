@@ -0,0 +1,215 @@
+//! Реакции эмодзи на сообщения комнат.
+
+use std::collections::HashMap;
+
+use cheenhub_contracts::realtime::{AddReaction, ReactionsChangedPayload, RemoveReaction};
+use cheenhub_contracts::rest::MessageReactionSummary;
+use tracing::error;
+use uuid::Uuid;
+
+use super::{TextChatApplicationError, ensure_room_text_available, fanout, parse_id};
+use crate::features::text_chat::domain::{MessageReaction, TextMessage};
+use crate::features::text_chat::validation;
+use crate::state::AppState;
+
+/// Максимальное количество разных эмодзи на одном сообщении.
+const MAX_DISTINCT_REACTIONS: usize = 20;
+
+/// Ставит реакцию текущего пользователя на сообщение комнаты.
+pub(crate) async fn add_reaction(
+    state: &AppState,
+    user_id: &Uuid,
+    request: AddReaction,
+) -> Result<ReactionsChangedPayload, TextChatApplicationError> {
+    change_reaction(
+        state,
+        user_id,
+        ReactionChange {
+            server_id: request.server_id,
+            room_id: request.room_id,
+            message_id: request.message_id,
+            emoji: request.emoji,
+            added: true,
+        },
+    )
+    .await
+}
+
+/// Снимает реакцию текущего пользователя с сообщения комнаты.
+pub(crate) async fn remove_reaction(
+    state: &AppState,
+    user_id: &Uuid,
+    request: RemoveReaction,
+) -> Result<ReactionsChangedPayload, TextChatApplicationError> {
+    change_reaction(
+        state,
+        user_id,
+        ReactionChange {
+            server_id: request.server_id,
+            room_id: request.room_id,
+            message_id: request.message_id,
+            emoji: request.emoji,
+            added: false,
+        },
+    )
+    .await
+}
+
+/// Группирует реакции по сообщениям и эмодзи в порядке первой установки.
+///
+/// Флаг `reacted` вычисляется для `current_user_id`; для широковещательных
+/// событий передается `None`, и флаг остается `false`.
+pub(crate) fn reaction_summaries(
+    reactions: &[MessageReaction],
+    current_user_id: Option<&Uuid>,
+) -> HashMap<Uuid, Vec<MessageReactionSummary>> {
+    let mut ordered = reactions.iter().collect::<Vec<_>>();
+    ordered.sort_by_key(|reaction| reaction.created_at);
+
+    let mut by_message: HashMap<Uuid, Vec<MessageReactionSummary>> = HashMap::new();
+    for reaction in ordered {
+        let summaries = by_message.entry(reaction.message_id).or_default();
+        let reacted = current_user_id == Some(&reaction.user_id);
+        if let Some(summary) = summaries
+            .iter_mut()
+            .find(|summary| summary.emoji == reaction.emoji)
+        {
+            summary.count += 1;
+            summary.reacted |= reacted;
+        } else {
+            summaries.push(MessageReactionSummary {
+                emoji: reaction.emoji.clone(),
+                count: 1,
+                reacted,
+            });
+        }
+    }
+
+    by_message
+}
+
+/// Загружает агрегированные реакции для страницы сообщений комнаты.
+pub(super) async fn message_reactions(
+    state: &AppState,
+    user_id: &Uuid,
+    messages: &[TextMessage],
+) -> anyhow::Result<HashMap<Uuid, Vec<MessageReactionSummary>>> {
+    let message_ids = messages
+        .iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    if message_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let reactions = state
+        .text_chat_store
+        .message_reactions(&message_ids)
+        .await?;
+
+    Ok(reaction_summaries(&reactions, Some(user_id)))
+}
+
+struct ReactionChange {
+    server_id: String,
+    room_id: String,
+    message_id: String,
+    emoji: String,
+    added: bool,
+}
+
+async fn change_reaction(
+    state: &AppState,
+    user_id: &Uuid,
+    change: ReactionChange,
+) -> Result<ReactionsChangedPayload, TextChatApplicationError> {
+    let server_id = parse_id(&change.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&change.room_id, "Комната не найдена.")?;
+    let message_id = parse_id(&change.message_id, "Сообщение не найдено.")?;
+    let emoji = validation::reaction_emoji(change.emoji)
+        .map_err(|message| TextChatApplicationError::BadRequest(message.to_owned()))?;
+    ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
+    let message_exists = state
+        .text_chat_store
+        .find_room_messages(&room_id, &[message_id])
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .iter()
+        .any(|message| {
+            message.id == message_id
+                && message.server_id == server_id
+                && message.deleted_at.is_none()
+        });
+    if !message_exists {
+        return Err(TextChatApplicationError::NotFound(
+            "Сообщение не найдено или уже удалено.".to_owned(),
+        ));
+    }
+
+    let changed = if change.added {
+        let current = state
+            .text_chat_store
+            .message_reactions(&[message_id])
+            .await
+            .map_err(TextChatApplicationError::Internal)?;
+        ensure_reaction_slot(&current, &emoji)
+            .map_err(|message| TextChatApplicationError::BadRequest(message.to_owned()))?;
+        state
+            .text_chat_store
+            .add_message_reaction(&message_id, user_id, &emoji)
+            .await
+    } else {
+        state
+            .text_chat_store
+            .remove_message_reaction(&message_id, user_id, &emoji)
+            .await
+    }
+    .map_err(TextChatApplicationError::Internal)?;
+
+    let reactions = state
+        .text_chat_store
+        .message_reactions(&[message_id])
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    let mut payload = ReactionsChangedPayload {
+        server_id: server_id.to_string(),
+        room_id: room_id.to_string(),
+        message_id: message_id.to_string(),
+        user_id: user_id.to_string(),
+        emoji,
+        added: change.added,
+        reactions: reaction_summaries(&reactions, None)
+            .remove(&message_id)
+            .unwrap_or_default(),
+    };
+    if changed && let Err(error) = fanout::fanout_reactions_changed(state, payload.clone()).await {
+        error!(
+            %message_id,
+            %user_id,
+            %error,
+            "failed to schedule text chat reaction fanout"
+        );
+    }
+
+    payload.reactions = reaction_summaries(&reactions, Some(user_id))
+        .remove(&message_id)
+        .unwrap_or_default();
+    Ok(payload)
+}
+
+/// Проверяет, что новая реакция не превысит лимит разных эмодзи на сообщении.
+pub(crate) fn ensure_reaction_slot(
+    current: &[MessageReaction],
+    emoji: &str,
+) -> Result<(), &'static str> {
+    let mut distinct = current
+        .iter()
+        .map(|reaction| reaction.emoji.as_str())
+        .collect::<Vec<_>>();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if distinct.len() >= MAX_DISTINCT_REACTIONS && !distinct.contains(&emoji) {
+        return Err("На сообщении слишком много разных реакций.");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,148 @@
+//! Отметки прочтения и счетчики непрочитанного в текстовых комнатах.
+
+use std::collections::HashMap;
+
+use cheenhub_contracts::realtime::{
+    MarkRoomRead, RealtimeKind, RealtimeModule, RoomReadUpdatedPayload, ServerRoleKind,
+    TextChatKind,
+};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use crate::features::text_chat::application::{
+    TextChatApplicationError, ensure_room_text_available, parse_id,
+};
+use crate::features::text_chat::domain::RoomUnreadCounts;
+use crate::state::AppState;
+
+/// Сдвигает отметку прочтения комнаты и сообщает новые счетчики всем сессиям пользователя.
+///
+/// Отметка только продвигается вперед, поэтому повторная или запоздавшая
+/// отметка более старого сообщения возвращает текущее состояние.
+pub(crate) async fn mark_room_read(
+    state: &AppState,
+    user_id: &Uuid,
+    request: MarkRoomRead,
+) -> Result<RoomReadUpdatedPayload, TextChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    let message_id = parse_id(&request.message_id, "Сообщение не найдено.")?;
+    ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
+
+    let read_state = state
+        .text_chat_store
+        .mark_room_read(&room_id, user_id, &message_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .ok_or_else(|| TextChatApplicationError::NotFound("Сообщение не найдено.".to_owned()))?;
+    let counts = room_unread_counts(state, user_id, &server_id, &[room_id])
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .remove(&room_id)
+        .unwrap_or_default();
+    let payload = RoomReadUpdatedPayload {
+        server_id: server_id.to_string(),
+        room_id: room_id.to_string(),
+        last_read_message_id: Some(read_state.last_read_message_id.to_string()),
+        unread_count: saturating_u32(counts.unread_count),
+        mention_count: saturating_u32(counts.mention_count),
+    };
+
+    state
+        .realtime_hub
+        .fanout_to_user_streams(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::RoomReadUpdated),
+            &[*user_id],
+            payload.clone(),
+        )
+        .await;
+
+    Ok(payload)
+}
+
+/// Возвращает последнее прочитанное пользователем сообщение комнаты.
+pub(super) async fn last_read_message_id(
+    state: &AppState,
+    user_id: &Uuid,
+    room_id: &Uuid,
+) -> anyhow::Result<Option<Uuid>> {
+    Ok(state
+        .text_chat_store
+        .room_read_state(room_id, user_id)
+        .await?
+        .map(|read_state| read_state.last_read_message_id))
+}
+
+/// Отмечает прочитанной историю текстовых комнат сервера для нового участника.
+///
+/// Без отметки непрочитанными считаются все сообщения комнаты, поэтому
+/// история до вступления иначе попала бы в счетчики целиком.
+pub(crate) async fn seed_server_read_states(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &Uuid,
+) -> anyhow::Result<()> {
+    let room_ids = state
+        .server_store
+        .list_server_rooms(server_id)
+        .await?
+        .into_iter()
+        .filter(|room| room.kind != ServerRoomKind::Voice)
+        .map(|room| room.id)
+        .collect::<Vec<_>>();
+    state
+        .text_chat_store
+        .seed_room_read_states(user_id, &room_ids)
+        .await
+}
+
+/// Считает непрочитанное пользователем в комнатах сервера.
+///
+/// Упоминания ролей учитываются для ролей участника, а для владельца сервера —
+/// еще и для роли владельца, как и при доставке уведомлений об упоминаниях.
+pub(crate) async fn room_unread_counts(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &Uuid,
+    room_ids: &[Uuid],
+) -> anyhow::Result<HashMap<Uuid, RoomUnreadCounts>> {
+    if room_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut role_ids = state
+        .server_store
+        .list_server_member_roles(server_id)
+        .await?
+        .into_iter()
+        .filter(|(member_user_id, _)| member_user_id == user_id)
+        .map(|(_, role_id)| role_id)
+        .collect::<Vec<_>>();
+    if state
+        .server_store
+        .find_server(server_id)
+        .await?
+        .is_some_and(|server| server.owner_user_id == *user_id)
+    {
+        role_ids.extend(
+            state
+                .server_store
+                .list_server_roles(server_id)
+                .await?
+                .into_iter()
+                .filter(|role| role.kind == ServerRoleKind::Owner)
+                .map(|role| role.id),
+        );
+    }
+
+    state
+        .text_chat_store
+        .room_unread_counts(user_id, &role_ids, room_ids)
+        .await
+}
+
+/// Приводит счетчик хранилища к размеру поля контракта.
+pub(crate) fn saturating_u32(value: u64) -> u32 {
+    u32::try_from(value).unwrap_or(u32::MAX)
+}
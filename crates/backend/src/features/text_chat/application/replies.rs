@@ -0,0 +1,93 @@
+//! Ответы на сообщения и компактные снимки цитируемых сообщений.
+
+use std::collections::HashMap;
+
+use cheenhub_contracts::realtime::TextChatReplySnapshot;
+use uuid::Uuid;
+
+use super::{TextChatApplicationError, parse_id};
+use crate::features::text_chat::domain::TextMessage;
+use crate::state::AppState;
+
+/// Максимальная длина тела цитируемого сообщения в снимке, в символах.
+const REPLY_SNAPSHOT_BODY_CHARS: usize = 140;
+
+/// Находит сообщение, на которое отвечает новое сообщение, в той же комнате.
+pub(super) async fn reply_parent(
+    state: &AppState,
+    room_id: &Uuid,
+    reply_to_message_id: Option<&str>,
+) -> Result<Option<TextMessage>, TextChatApplicationError> {
+    let Some(reply_to_message_id) = reply_to_message_id else {
+        return Ok(None);
+    };
+    let message_id = parse_id(reply_to_message_id, "Сообщение для ответа не найдено.")?;
+    let parent = state
+        .text_chat_store
+        .find_room_messages(room_id, &[message_id])
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .into_iter()
+        .find(|message| message.id == message_id && message.deleted_at.is_none())
+        .ok_or_else(|| {
+            TextChatApplicationError::BadRequest("Сообщение для ответа не найдено.".to_owned())
+        })?;
+
+    Ok(Some(parent))
+}
+
+/// Загружает снимки цитируемых сообщений для страницы сообщений комнаты.
+pub(super) async fn reply_snapshots(
+    state: &AppState,
+    room_id: &Uuid,
+    messages: &[TextMessage],
+) -> anyhow::Result<HashMap<Uuid, TextChatReplySnapshot>> {
+    let mut parent_ids = messages
+        .iter()
+        .filter_map(|message| message.reply_to_message_id)
+        .collect::<Vec<_>>();
+    parent_ids.sort_unstable();
+    parent_ids.dedup();
+    if parent_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    Ok(state
+        .text_chat_store
+        .find_room_messages(room_id, &parent_ids)
+        .await?
+        .iter()
+        .map(|parent| (parent.id, reply_snapshot(parent)))
+        .collect())
+}
+
+/// Собирает компактный снимок цитируемого сообщения; тело удаленных сообщений скрывается.
+pub(super) fn reply_snapshot(parent: &TextMessage) -> TextChatReplySnapshot {
+    let deleted = parent.deleted_at.is_some();
+
+    TextChatReplySnapshot {
+        message_id: parent.id.to_string(),
+        author_user_id: parent.author_user_id.to_string(),
+        author_nickname: parent.author_nickname.clone(),
+        body: if deleted {
+            String::new()
+        } else {
+            truncate_body(&parent.body)
+        },
+        has_attachments: !deleted && !parent.attachments.is_empty(),
+        deleted,
+    }
+}
+
+fn truncate_body(body: &str) -> String {
+    let mut chars = body.chars();
+    let truncated = chars
+        .by_ref()
+        .take(REPLY_SNAPSHOT_BODY_CHARS)
+        .collect::<String>();
+    if chars.next().is_some() {
+        format!("{}…", truncated.trim_end())
+    } else {
+        truncated
+    }
+}
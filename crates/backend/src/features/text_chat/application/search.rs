@@ -0,0 +1,105 @@
+//! Поиск сообщений по текстовым комнатам сервера.
+
+use cheenhub_contracts::rest::{SearchServerMessagesResponse, ServerRoomKind};
+use uuid::Uuid;
+
+use super::{TextChatApplicationError, ensure_room_text_available, message_summaries, parse_id};
+use crate::features::text_chat::{policy, validation};
+use crate::state::AppState;
+
+/// Параметры поиска сообщений на сервере.
+pub(crate) struct SearchServerMessages {
+    /// Строка поиска.
+    pub(crate) query: String,
+    /// Комната, которой нужно ограничить поиск.
+    pub(crate) room_id: Option<String>,
+    /// Найденное ранее сообщение, перед которым нужно вернуть более старые совпадения.
+    pub(crate) before_message_id: Option<String>,
+}
+
+/// Ищет неудаленные сообщения в текстовых комнатах сервера, доступных пользователю.
+pub(crate) async fn search_server_messages(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: String,
+    request: SearchServerMessages,
+) -> Result<SearchServerMessagesResponse, TextChatApplicationError> {
+    let server_id = parse_id(&server_id, "Сервер не найден.")?;
+    let query = validation::search_query(&request.query)
+        .map_err(|message| TextChatApplicationError::BadRequest(message.to_owned()))?;
+    let before_message_id = request
+        .before_message_id
+        .as_deref()
+        .map(|value| parse_id(value, "Результаты поиска недоступны."))
+        .transpose()?;
+    let room_ids = match request.room_id {
+        Some(room_id) => {
+            let room_id = parse_id(&room_id, "Комната не найдена.")?;
+            ensure_room_text_available(state, user_id, &server_id, &room_id).await?;
+            vec![room_id]
+        }
+        None => searchable_room_ids(state, user_id, &server_id).await?,
+    };
+
+    let page = state
+        .text_chat_store
+        .search_room_messages(&room_ids, &query, before_message_id.as_ref())
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .ok_or_else(|| {
+            TextChatApplicationError::BadRequest("Результаты поиска недоступны.".to_owned())
+        })?;
+    let messages = message_summaries(state, user_id, &page.messages)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+
+    Ok(SearchServerMessagesResponse {
+        messages,
+        has_more: page.has_more_before,
+    })
+}
+
+/// Возвращает текстовые комнаты сервера, события которых может получать пользователь.
+async fn searchable_room_ids(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &Uuid,
+) -> Result<Vec<Uuid>, TextChatApplicationError> {
+    if state
+        .server_store
+        .find_server(server_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?
+        .is_none()
+    {
+        return Err(TextChatApplicationError::NotFound(
+            "Сервер не найден.".to_owned(),
+        ));
+    }
+    let rooms = state
+        .server_store
+        .list_server_rooms(server_id)
+        .await
+        .map_err(TextChatApplicationError::Internal)?;
+    let mut text_room_count = 0;
+    let mut room_ids = Vec::new();
+    for room in rooms
+        .iter()
+        .filter(|room| room.kind != ServerRoomKind::Voice)
+    {
+        text_room_count += 1;
+        if policy::can_receive_room_event(state, user_id, server_id, &room.id)
+            .await
+            .map_err(TextChatApplicationError::Internal)?
+        {
+            room_ids.push(room.id);
+        }
+    }
+    if room_ids.is_empty() && text_room_count > 0 {
+        return Err(TextChatApplicationError::Unauthorized(
+            "Нет доступа к этому серверу.".to_owned(),
+        ));
+    }
+
+    Ok(room_ids)
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use cheenhub_contracts::rest::{RegisterRequest, ServerRoomKind};
+use chrono::Utc;
 use image::ImageEncoder;
 use image::codecs::png::PngEncoder;
 use uuid::Uuid;
@@ -10,14 +11,23 @@ use crate::features::auth::infrastructure::InMemoryAuthStore;
 use crate::features::auth::security::keys::AuthKeys;
 use crate::features::servers::infrastructure::InMemoryServerStore;
 use crate::features::social::infrastructure::InMemorySocialStore;
+use crate::features::text_chat::domain::TextMessage;
 use crate::features::text_chat::infrastructure::InMemoryTextChatStore;
 use crate::realtime::hub::RealtimeHub;
 use crate::state::AppState;
 
 mod attachments;
 mod deletion;
+mod editing;
 mod history;
+mod link_previews;
+mod mentions;
 mod messages;
+mod pins;
+mod reactions;
+mod read_state;
+mod replies;
+mod search;
 
 pub(super) fn state() -> AppState {
     AppState {
@@ -34,6 +44,12 @@ pub(super) fn state() -> AppState {
         image_store: Arc::new(
             crate::features::images::infrastructure::InMemoryImageStore::default(),
         ),
+        link_preview_store: Arc::new(
+            crate::features::link_previews::infrastructure::InMemoryLinkPreviewStore::default(),
+        ),
+        link_preview_fetcher: Arc::new(
+            crate::features::link_previews::LinkPreviewFetcher::allowing_private_networks(),
+        ),
         push_notifications: Arc::new(
             crate::features::push_notifications::application::PushNotifications::disabled(
                 Arc::new(InMemoryAuthStore::default()),
@@ -46,6 +62,9 @@ pub(super) fn state() -> AppState {
         direct_call_store: Arc::new(
             crate::features::voice_chat::infrastructure::InMemoryDirectCallStore::default(),
         ),
+        voice_recording_archive: Arc::new(
+            crate::features::voice_chat::infrastructure::DisabledVoiceRecordingArchive,
+        ),
         realtime_hub: Arc::new(RealtimeHub::default()),
         auth_keys: AuthKeys::generate_for_tests(),
         access_token_lifetime_minutes: 15,
@@ -109,6 +128,40 @@ pub(super) async fn create_server_room(
     (server.id.to_string(), room.id.to_string())
 }
 
+/// Сообщение комнаты без вложений, ответа и упоминаний, отправленное сейчас.
+pub(super) fn text_message(
+    server_id: &str,
+    room_id: &str,
+    author_user_id: Uuid,
+    body: &str,
+) -> TextMessage {
+    TextMessage {
+        id: Uuid::new_v4(),
+        server_id: Uuid::parse_str(server_id).expect("server id should be uuid"),
+        room_id: Uuid::parse_str(room_id).expect("room id should be uuid"),
+        author_user_id,
+        author_nickname: "author".to_owned(),
+        body: body.to_owned(),
+        attachments: Vec::new(),
+        created_at: Utc::now(),
+        deleted_at: None,
+        deleted_by_user_id: None,
+        edited_at: None,
+        reply_to_message_id: None,
+        mentions: Vec::new(),
+    }
+}
+
+pub(super) async fn insert_text_message(state: &AppState, message: TextMessage) -> Uuid {
+    let message_id = message.id;
+    state
+        .text_chat_store
+        .insert_text_message(message)
+        .await
+        .expect("message should insert");
+    message_id
+}
+
 pub(super) fn tiny_png() -> Vec<u8> {
     let mut bytes = Vec::new();
     PngEncoder::new(&mut bytes)
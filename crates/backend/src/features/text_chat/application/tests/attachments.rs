@@ -1,7 +1,10 @@
+use cheenhub_contracts::realtime::{SendMessage, TextChatAttachmentKind};
 use cheenhub_contracts::rest::ServerRoomKind;
 use uuid::Uuid;
 
-use super::super::{chat_image, upload_chat_image};
+use super::super::{
+    TextChatApplicationError, chat_attachment, chat_image, send_message, upload_chat_attachment,
+};
 use super::{create_server_room, registered_user, state, tiny_png};
 
 #[tokio::test]
@@ -19,7 +22,7 @@ async fn chat_image_upload_is_stored_and_served_through_proxy_flow() {
     .await;
     let bytes = tiny_png();
 
-    let uploaded = upload_chat_image(
+    let uploaded = upload_chat_attachment(
         &state,
         &user_id,
         server_id.clone(),
@@ -32,9 +35,10 @@ async fn chat_image_upload_is_stored_and_served_through_proxy_flow() {
 
     assert_eq!(uploaded.server_id, server_id);
     assert_eq!(uploaded.room_id, room_id);
+    assert_eq!(uploaded.kind, TextChatAttachmentKind::Image);
     assert_eq!(uploaded.content_type, "image/png");
-    assert_eq!(uploaded.width, 1);
-    assert_eq!(uploaded.height, 1);
+    assert_eq!(uploaded.width, Some(1));
+    assert_eq!(uploaded.height, Some(1));
 
     let (attachment, served) = chat_image(&state, &user_id, uploaded.id)
         .await
@@ -42,3 +46,98 @@ async fn chat_image_upload_is_stored_and_served_through_proxy_flow() {
     assert_eq!(attachment.content_type, "image/png");
     assert_eq!(served, bytes);
 }
+
+#[tokio::test]
+async fn generic_file_is_typed_by_content_and_attached_to_message() {
+    let state = state();
+    let auth = registered_user(&state, "file_owner", "file-owner@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Files",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let bytes = b"%PDF-1.7\n%fake report\n".to_vec();
+
+    let uploaded = upload_chat_attachment(
+        &state,
+        &user_id,
+        server_id.clone(),
+        room_id.clone(),
+        Some("../report.png".to_owned()),
+        &bytes,
+    )
+    .await
+    .expect("pdf should upload");
+
+    assert_eq!(uploaded.kind, TextChatAttachmentKind::File);
+    assert_eq!(uploaded.content_type, "application/pdf");
+    assert_eq!(uploaded.width, None);
+    assert_eq!(uploaded.original_filename.as_deref(), Some("report.png"));
+    assert!(matches!(
+        chat_image(&state, &user_id, uploaded.id.clone()).await,
+        Err(TextChatApplicationError::NotFound(_))
+    ));
+
+    let accepted = send_message(
+        &state,
+        &auth.user,
+        &user_id,
+        SendMessage {
+            server_id,
+            room_id,
+            body: "отчет".to_owned(),
+            attachment_ids: vec![uploaded.id.clone()],
+            reply_to_message_id: None,
+        },
+    )
+    .await
+    .expect("message with file should send");
+    let attachment = &accepted.message.attachments[0];
+    assert_eq!(attachment.kind, TextChatAttachmentKind::File);
+    assert_eq!(attachment.byte_size, bytes.len() as i64);
+    assert_eq!(attachment.original_filename.as_deref(), Some("report.png"));
+
+    let (stored, served) = chat_attachment(&state, &user_id, uploaded.id)
+        .await
+        .expect("file should serve");
+    assert!(stored.object_key.starts_with("chat-files/"));
+    assert_eq!(served, bytes);
+}
+
+#[tokio::test]
+async fn attachment_download_requires_room_access() {
+    let state = state();
+    let owner = registered_user(&state, "file_keeper", "file-keeper@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let outsider = registered_user(&state, "file_outsider", "file-outsider@example.com").await;
+    let outsider_id = Uuid::parse_str(&outsider.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Private files",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let uploaded = upload_chat_attachment(
+        &state,
+        &owner_id,
+        server_id,
+        room_id,
+        Some("notes.txt".to_owned()),
+        "заметки".as_bytes(),
+    )
+    .await
+    .expect("text file should upload");
+    assert_eq!(uploaded.content_type, "text/plain; charset=utf-8");
+
+    assert!(
+        chat_attachment(&state, &outsider_id, uploaded.id)
+            .await
+            .is_err()
+    );
+}
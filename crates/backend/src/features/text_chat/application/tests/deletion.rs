@@ -1,11 +1,9 @@
 use cheenhub_contracts::realtime::{DeleteMessage, LoadRoomHistory};
 use cheenhub_contracts::rest::ServerRoomKind;
-use chrono::Utc;
 use uuid::Uuid;
 
 use super::super::{TextChatApplicationError, delete_message, load_room_history};
-use super::{create_server_room, registered_user, state};
-use crate::features::text_chat::domain::TextMessage;
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
 
 #[tokio::test]
 async fn cannot_delete_message_from_foreign_server_via_owned_room() {
@@ -34,25 +32,16 @@ async fn cannot_delete_message_from_foreign_server_via_owned_room() {
         ServerRoomKind::TextAndVoice,
     )
     .await;
-    let victim_server = Uuid::parse_str(&victim_server_string).expect("server id should be uuid");
-    let victim_room = Uuid::parse_str(&victim_room_string).expect("room id should be uuid");
-    let foreign_message_id = Uuid::new_v4();
-    state
-        .text_chat_store
-        .insert_text_message(TextMessage {
-            id: foreign_message_id,
-            server_id: victim_server,
-            room_id: victim_room,
-            author_user_id: victim_id,
-            author_nickname: victim.user.nickname.clone(),
-            body: "secret".to_owned(),
-            attachments: Vec::new(),
-            created_at: Utc::now(),
-            deleted_at: None,
-            deleted_by_user_id: None,
-        })
-        .await
-        .expect("message should insert");
+    let foreign_message_id = insert_text_message(
+        &state,
+        text_message(
+            &victim_server_string,
+            &victim_room_string,
+            victim_id,
+            "secret",
+        ),
+    )
+    .await;
 
     // Права атакующего не должны применяться к сообщению из другой комнаты.
     let error = delete_message(
@@ -76,6 +65,8 @@ async fn cannot_delete_message_from_foreign_server_via_owned_room() {
             server_id: victim_server_string,
             room_id: victim_room_string,
             before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
@@ -0,0 +1,175 @@
+use cheenhub_contracts::realtime::{EditMessage, LoadRoomHistory};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::super::{TextChatApplicationError, edit_message, load_room_history};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
+
+#[tokio::test]
+async fn author_can_edit_message_and_history_shows_edit() {
+    let state = state();
+    let auth = registered_user(&state, "editor", "editor@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Edit Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_id =
+        insert_text_message(&state, text_message(&server_id, &room_id, user_id, "draft")).await;
+
+    let accepted = edit_message(
+        &state,
+        &auth.user,
+        &user_id,
+        EditMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            message_id: message_id.to_string(),
+            body: "  final  ".to_owned(),
+        },
+    )
+    .await
+    .expect("author edit should be accepted");
+    assert_eq!(accepted.message.body, "final");
+    assert!(accepted.message.edited_at.is_some());
+
+    let history = load_room_history(
+        &state,
+        &user_id,
+        LoadRoomHistory {
+            server_id,
+            room_id,
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("history should load");
+    assert_eq!(history.messages[0].body, "final");
+    assert_eq!(history.messages[0].edited_at, accepted.message.edited_at);
+}
+
+#[tokio::test]
+async fn member_cannot_edit_someone_elses_message() {
+    let state = state();
+    let owner = registered_user(&state, "edit_owner", "edit-owner@example.com").await;
+    let member = registered_user(&state, "edit_member", "edit-member@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let member_id = Uuid::parse_str(&member.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Edit Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    state
+        .server_store
+        .insert_server_member(
+            &Uuid::parse_str(&server_id).expect("server id should be uuid"),
+            &member_id,
+        )
+        .await
+        .expect("member should insert");
+    let message_id = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "owner text"),
+    )
+    .await;
+
+    // Редактирование не опирается на права модерации: править может только автор.
+    let error = edit_message(
+        &state,
+        &member.user,
+        &member_id,
+        EditMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            message_id: message_id.to_string(),
+            body: "hijacked".to_owned(),
+        },
+    )
+    .await
+    .expect_err("non-author edit must be rejected");
+    assert!(matches!(error, TextChatApplicationError::NotFound(_)));
+
+    let history = load_room_history(
+        &state,
+        &owner_id,
+        LoadRoomHistory {
+            server_id,
+            room_id,
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("history should load");
+    assert_eq!(history.messages[0].body, "owner text");
+    assert!(history.messages[0].edited_at.is_none());
+}
+
+#[tokio::test]
+async fn cannot_edit_message_through_another_room() {
+    let state = state();
+    let auth = registered_user(&state, "room_hopper", "room-hopper@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Edit Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let (other_server_id, other_room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Other Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_id = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, user_id, "original"),
+    )
+    .await;
+
+    let error = edit_message(
+        &state,
+        &auth.user,
+        &user_id,
+        EditMessage {
+            server_id: other_server_id,
+            room_id: other_room_id,
+            message_id: message_id.to_string(),
+            body: "moved".to_owned(),
+        },
+    )
+    .await
+    .expect_err("edit through foreign room must be rejected");
+    assert!(matches!(error, TextChatApplicationError::NotFound(_)));
+
+    let error = edit_message(
+        &state,
+        &auth.user,
+        &user_id,
+        EditMessage {
+            server_id,
+            room_id,
+            message_id: message_id.to_string(),
+            body: "   ".to_owned(),
+        },
+    )
+    .await
+    .expect_err("blank edit must be rejected");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+}
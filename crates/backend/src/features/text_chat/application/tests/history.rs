@@ -4,7 +4,7 @@ use chrono::{Duration, Utc};
 use uuid::Uuid;
 
 use super::super::{TextChatApplicationError, load_room_history};
-use super::{create_server_room, registered_user, state};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
 use crate::features::text_chat::domain::TextMessage;
 
 #[tokio::test]
@@ -20,28 +20,7 @@ async fn room_history_returns_latest_fifty_oldest_to_newest() {
         ServerRoomKind::TextAndVoice,
     )
     .await;
-    let server_id = Uuid::parse_str(&server_id_string).expect("server id should be uuid");
-    let room_id = Uuid::parse_str(&room_id_string).expect("room id should be uuid");
-    let base_time = Utc::now();
-
-    for index in 0..55 {
-        state
-            .text_chat_store
-            .insert_text_message(TextMessage {
-                id: Uuid::new_v4(),
-                server_id,
-                room_id,
-                author_user_id: user_id,
-                author_nickname: auth.user.nickname.clone(),
-                body: format!("message {index}"),
-                attachments: Vec::new(),
-                created_at: base_time + Duration::seconds(index),
-                deleted_at: None,
-                deleted_by_user_id: None,
-            })
-            .await
-            .expect("message should insert");
-    }
+    seed_messages(&state, &user_id, &server_id_string, &room_id_string, 55).await;
 
     let history = load_room_history(
         &state,
@@ -50,13 +29,16 @@ async fn room_history_returns_latest_fifty_oldest_to_newest() {
             server_id: server_id_string,
             room_id: room_id_string,
             before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
     .expect("history should load");
 
     assert_eq!(history.messages.len(), 50);
-    assert!(history.has_more);
+    assert!(history.has_more_before);
+    assert!(!history.has_more_after);
     assert_eq!(history.messages[0].body, "message 5");
     assert_eq!(history.messages[49].body, "message 54");
 }
@@ -74,28 +56,7 @@ async fn room_history_page_before_cursor_returns_older_messages() {
         ServerRoomKind::TextAndVoice,
     )
     .await;
-    let server_id = Uuid::parse_str(&server_id_string).expect("server id should be uuid");
-    let room_id = Uuid::parse_str(&room_id_string).expect("room id should be uuid");
-    let base_time = Utc::now();
-
-    for index in 0..75 {
-        state
-            .text_chat_store
-            .insert_text_message(TextMessage {
-                id: Uuid::new_v4(),
-                server_id,
-                room_id,
-                author_user_id: user_id,
-                author_nickname: auth.user.nickname.clone(),
-                body: format!("message {index}"),
-                attachments: Vec::new(),
-                created_at: base_time + Duration::seconds(index),
-                deleted_at: None,
-                deleted_by_user_id: None,
-            })
-            .await
-            .expect("message should insert");
-    }
+    seed_messages(&state, &user_id, &server_id_string, &room_id_string, 75).await;
 
     let latest = load_room_history(
         &state,
@@ -104,6 +65,8 @@ async fn room_history_page_before_cursor_returns_older_messages() {
             server_id: server_id_string.clone(),
             room_id: room_id_string.clone(),
             before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
@@ -116,6 +79,8 @@ async fn room_history_page_before_cursor_returns_older_messages() {
             server_id: server_id_string,
             room_id: room_id_string,
             before_message_id: Some(cursor),
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
@@ -123,7 +88,8 @@ async fn room_history_page_before_cursor_returns_older_messages() {
 
     assert_eq!(latest.messages[0].body, "message 25");
     assert_eq!(older.messages.len(), 25);
-    assert!(!older.has_more);
+    assert!(!older.has_more_before);
+    assert!(older.has_more_after);
     assert_eq!(older.messages[0].body, "message 0");
     assert_eq!(older.messages[24].body, "message 24");
 }
@@ -149,26 +115,11 @@ async fn foreign_history_cursor_is_rejected() {
         ServerRoomKind::TextAndVoice,
     )
     .await;
-    let server_id = Uuid::parse_str(&server_id_string).expect("server id should be uuid");
-    let other_room_id = Uuid::parse_str(&other_room_id_string).expect("room id should be uuid");
-    let foreign_message_id = Uuid::new_v4();
-
-    state
-        .text_chat_store
-        .insert_text_message(TextMessage {
-            id: foreign_message_id,
-            server_id,
-            room_id: other_room_id,
-            author_user_id: user_id,
-            author_nickname: auth.user.nickname.clone(),
-            body: "foreign".to_owned(),
-            attachments: Vec::new(),
-            created_at: Utc::now(),
-            deleted_at: None,
-            deleted_by_user_id: None,
-        })
-        .await
-        .expect("message should insert");
+    let foreign_message_id = insert_text_message(
+        &state,
+        text_message(&server_id_string, &other_room_id_string, user_id, "foreign"),
+    )
+    .await;
 
     let error = load_room_history(
         &state,
@@ -177,6 +128,8 @@ async fn foreign_history_cursor_is_rejected() {
             server_id: server_id_string,
             room_id: room_id_string,
             before_message_id: Some(foreign_message_id.to_string()),
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
@@ -184,3 +137,130 @@ async fn foreign_history_cursor_is_rejected() {
 
     assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
 }
+
+#[tokio::test]
+async fn room_history_around_cursor_centres_window_on_message() {
+    let state = state();
+    let auth = registered_user(&state, "around_owner", "around-owner@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id_string, room_id_string) = create_server_room(
+        &state,
+        &user_id,
+        "Around History",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_ids =
+        seed_messages(&state, &user_id, &server_id_string, &room_id_string, 120).await;
+
+    let around = load_room_history(
+        &state,
+        &user_id,
+        LoadRoomHistory {
+            server_id: server_id_string,
+            room_id: room_id_string,
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: Some(message_ids[60].to_string()),
+        },
+    )
+    .await
+    .expect("history around message should load");
+
+    assert_eq!(around.messages.len(), 50);
+    assert!(around.has_more_before);
+    assert!(around.has_more_after);
+    assert_eq!(around.messages[0].body, "message 36");
+    assert_eq!(around.messages[24].body, "message 60");
+    assert_eq!(around.messages[49].body, "message 85");
+}
+
+#[tokio::test]
+async fn room_history_after_cursor_returns_newer_messages() {
+    let state = state();
+    let auth = registered_user(&state, "after_owner", "after-owner@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id_string, room_id_string) = create_server_room(
+        &state,
+        &user_id,
+        "After History",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_ids = seed_messages(&state, &user_id, &server_id_string, &room_id_string, 75).await;
+
+    let newer = load_room_history(
+        &state,
+        &user_id,
+        LoadRoomHistory {
+            server_id: server_id_string,
+            room_id: room_id_string,
+            before_message_id: None,
+            after_message_id: Some(message_ids[39].to_string()),
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("newer history should load");
+
+    assert_eq!(newer.messages.len(), 35);
+    assert!(newer.has_more_before);
+    assert!(!newer.has_more_after);
+    assert_eq!(newer.messages[0].body, "message 40");
+    assert_eq!(newer.messages[34].body, "message 74");
+}
+
+#[tokio::test]
+async fn room_history_rejects_multiple_cursors() {
+    let state = state();
+    let auth = registered_user(&state, "cursors_owner", "cursors-owner@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id_string, room_id_string) = create_server_room(
+        &state,
+        &user_id,
+        "Many Cursors",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_ids = seed_messages(&state, &user_id, &server_id_string, &room_id_string, 3).await;
+
+    let error = load_room_history(
+        &state,
+        &user_id,
+        LoadRoomHistory {
+            server_id: server_id_string,
+            room_id: room_id_string,
+            before_message_id: Some(message_ids[2].to_string()),
+            after_message_id: Some(message_ids[0].to_string()),
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect_err("multiple cursors should fail");
+
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+}
+
+async fn seed_messages(
+    state: &crate::state::AppState,
+    user_id: &Uuid,
+    server_id: &str,
+    room_id: &str,
+    count: i64,
+) -> Vec<Uuid> {
+    let base_time = Utc::now();
+    let mut message_ids = Vec::new();
+
+    for index in 0..count {
+        let message = TextMessage {
+            created_at: base_time + Duration::seconds(index),
+            ..text_message(server_id, room_id, *user_id, &format!("message {index}"))
+        };
+        message_ids.push(insert_text_message(state, message).await);
+    }
+
+    message_ids
+}
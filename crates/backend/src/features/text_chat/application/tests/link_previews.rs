@@ -0,0 +1,231 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use axum::Router;
+use axum::http::header::CONTENT_TYPE;
+use axum::routing::get;
+use cheenhub_contracts::realtime::{EditMessage, LoadRoomHistory, SendMessage};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::super::{edit_message, load_room_history, send_message};
+use super::{
+    create_server_room, insert_text_message, registered_user, state, text_message, tiny_png,
+};
+use crate::features::link_previews::LinkPreviewFetcher;
+use crate::features::link_previews::application::resolve_link_previews;
+
+const ARTICLE_HTML: &str = r#"<!doctype html><html><head>
+<title>Fallback</title>
+<meta property="og:title" content="Stub &amp; article">
+<meta property="og:description" content="Описание страницы">
+<meta property="og:site_name" content="Stub site">
+<meta property="og:image" content="/cover.png">
+</head><body>content</body></html>"#;
+
+/// Поднимает локальную страницу с OpenGraph-разметкой и считает обращения к ней.
+async fn stub_site() -> (String, Arc<AtomicUsize>) {
+    let article_hits = Arc::new(AtomicUsize::new(0));
+    let hits = article_hits.clone();
+    let router = Router::new()
+        .route(
+            "/article",
+            get(move || {
+                hits.fetch_add(1, Ordering::SeqCst);
+                async { ([(CONTENT_TYPE, "text/html; charset=utf-8")], ARTICLE_HTML) }
+            }),
+        )
+        .route(
+            "/cover.png",
+            get(|| async { ([(CONTENT_TYPE, "image/png")], tiny_png()) }),
+        );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("stub listener should bind");
+    let address = listener.local_addr().expect("stub address should exist");
+    tokio::spawn(async move {
+        axum::serve(listener, router)
+            .await
+            .expect("stub server should run");
+    });
+
+    (format!("http://{address}"), article_hits)
+}
+
+#[tokio::test]
+async fn room_link_preview_is_fetched_once_and_attached_to_history() {
+    let state = state();
+    let (base_url, article_hits) = stub_site().await;
+    let auth = registered_user(&state, "preview_owner", "preview-owner@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Preview Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let body = format!("смотри {base_url}/article.");
+
+    send_message(
+        &state,
+        &auth.user,
+        &user_id,
+        SendMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            body: body.clone(),
+            attachment_ids: Vec::new(),
+            reply_to_message_id: None,
+        },
+    )
+    .await
+    .expect("send should be accepted");
+
+    let mut previews = Vec::new();
+    for _ in 0..50 {
+        let history = load_room_history(
+            &state,
+            &user_id,
+            LoadRoomHistory {
+                server_id: server_id.clone(),
+                room_id: room_id.clone(),
+                before_message_id: None,
+                after_message_id: None,
+                around_message_id: None,
+            },
+        )
+        .await
+        .expect("history should load");
+        if let Some(message) = history.messages.first()
+            && !message.link_previews.is_empty()
+        {
+            previews = message.link_previews.clone();
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    assert_eq!(previews.len(), 1);
+    let preview = &previews[0];
+    assert_eq!(preview.url, format!("{base_url}/article"));
+    assert_eq!(preview.title.as_deref(), Some("Stub & article"));
+    assert_eq!(preview.description.as_deref(), Some("Описание страницы"));
+    assert_eq!(preview.site_name.as_deref(), Some("Stub site"));
+    let image_url = preview
+        .image_url
+        .as_deref()
+        .expect("image should be stored");
+    let image_id = image_url
+        .rsplit('/')
+        .next()
+        .and_then(|id| Uuid::parse_str(id).ok())
+        .expect("image url should end with id");
+    let image = state
+        .image_store
+        .find_image(&image_id)
+        .await
+        .expect("image should load")
+        .expect("image should exist");
+    assert_eq!(image.kind, "link_preview");
+
+    let cached = resolve_link_previews(&state, user_id, &body)
+        .await
+        .expect("cached previews should resolve");
+    assert_eq!(cached, previews);
+    assert_eq!(article_hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn public_only_fetcher_does_not_reach_local_addresses() {
+    let mut state = state();
+    state.link_preview_fetcher = Arc::new(LinkPreviewFetcher::public_only());
+    let (base_url, article_hits) = stub_site().await;
+    let auth = registered_user(&state, "preview_ssrf", "preview-ssrf@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let body = format!("{base_url}/article");
+
+    let previews = resolve_link_previews(&state, user_id, &body)
+        .await
+        .expect("resolve should not fail");
+
+    assert!(previews.is_empty());
+    assert_eq!(article_hits.load(Ordering::SeqCst), 0);
+    let cached = state
+        .link_preview_store
+        .find_link_previews(&[body])
+        .await
+        .expect("cache should load");
+    assert_eq!(cached.len(), 1);
+    assert!(!cached[0].resolved);
+}
+
+#[tokio::test]
+async fn edited_link_gets_preview_and_edit_response_attaches_known_one() {
+    let state = state();
+    let (base_url, article_hits) = stub_site().await;
+    let auth = registered_user(&state, "preview_editor", "preview-editor@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Preview Edit Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_id = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, user_id, "ссылка будет позже"),
+    )
+    .await;
+    let edit = |body: String| EditMessage {
+        server_id: server_id.clone(),
+        room_id: room_id.clone(),
+        message_id: message_id.to_string(),
+        body,
+    };
+
+    let first = edit_message(
+        &state,
+        &auth.user,
+        &user_id,
+        edit(format!("вот {base_url}/article")),
+    )
+    .await
+    .expect("edit should be accepted");
+    assert!(first.message.link_previews.is_empty());
+
+    let mut resolved = false;
+    for _ in 0..50 {
+        resolved = state
+            .link_preview_store
+            .find_link_previews(&[format!("{base_url}/article")])
+            .await
+            .expect("cache should load")
+            .first()
+            .is_some_and(|preview| preview.resolved);
+        if resolved {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(resolved);
+
+    let second = edit_message(
+        &state,
+        &auth.user,
+        &user_id,
+        edit(format!("всё ещё {base_url}/article")),
+    )
+    .await
+    .expect("edit should be accepted");
+    assert_eq!(second.message.link_previews.len(), 1);
+    assert_eq!(
+        second.message.link_previews[0].title.as_deref(),
+        Some("Stub & article")
+    );
+    assert_eq!(article_hits.load(Ordering::SeqCst), 1);
+}
@@ -0,0 +1,159 @@
+use cheenhub_contracts::realtime::{
+    EditMessage, LoadRoomHistory, SendMessage, ServerRoleKind, TextChatMention, TextChatMentionKind,
+};
+use cheenhub_contracts::rest::ServerRoomKind;
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::super::{edit_message, load_room_history, send_message};
+use super::{create_server_room, registered_user, state};
+use crate::features::servers::infrastructure::ServerRole;
+
+fn send(server_id: &str, room_id: &str, body: &str) -> SendMessage {
+    SendMessage {
+        server_id: server_id.to_owned(),
+        room_id: room_id.to_owned(),
+        body: body.to_owned(),
+        attachment_ids: Vec::new(),
+        reply_to_message_id: None,
+    }
+}
+
+fn role(server_id: Uuid, role_id: Uuid, name: &str, kind: ServerRoleKind) -> ServerRole {
+    ServerRole {
+        id: role_id,
+        server_id,
+        name: name.to_owned(),
+        color: "#38bdf8".to_owned(),
+        kind,
+        position: 0,
+        permissions: Vec::new(),
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn mentions_resolve_members_and_custom_roles_and_persist_in_history() {
+    let state = state();
+    let owner = registered_user(&state, "mention_owner", "mention-owner@example.com").await;
+    let member = registered_user(&state, "Mention_Member", "mention-member@example.com").await;
+    registered_user(&state, "mention_outsider", "mention-outsider@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let member_id = Uuid::parse_str(&member.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Mention Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let server_uuid = Uuid::parse_str(&server_id).expect("server id should be uuid");
+    state
+        .server_store
+        .insert_server_member(&server_uuid, &member_id)
+        .await
+        .expect("member should insert");
+    let role_id = Uuid::new_v4();
+    state
+        .server_store
+        .replace_server_roles(
+            &server_uuid,
+            vec![
+                role(server_uuid, role_id, "Модераторы", ServerRoleKind::Custom),
+                role(
+                    server_uuid,
+                    Uuid::new_v4(),
+                    "Участник",
+                    ServerRoleKind::Member,
+                ),
+            ],
+        )
+        .await
+        .expect("roles should save");
+
+    let accepted = send_message(
+        &state,
+        &owner.user,
+        &owner_id,
+        send(
+            &server_id,
+            &room_id,
+            "@mention_member @модераторы @mention_outsider @участник",
+        ),
+    )
+    .await
+    .expect("send should be accepted");
+
+    let expected = vec![
+        TextChatMention {
+            kind: TextChatMentionKind::User,
+            target_id: member_id.to_string(),
+            name: "Mention_Member".to_owned(),
+        },
+        TextChatMention {
+            kind: TextChatMentionKind::Role,
+            target_id: role_id.to_string(),
+            name: "Модераторы".to_owned(),
+        },
+    ];
+    assert_eq!(accepted.message.mentions, expected);
+
+    tokio::task::yield_now().await;
+    let history = load_room_history(
+        &state,
+        &owner_id,
+        LoadRoomHistory {
+            server_id,
+            room_id,
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("history should load");
+    assert_eq!(history.messages[0].mentions, expected);
+}
+
+#[tokio::test]
+async fn editing_replaces_resolved_mentions() {
+    let state = state();
+    let owner = registered_user(&state, "edit_mentioner", "edit-mentioner@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Mention Edit Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let accepted = send_message(
+        &state,
+        &owner.user,
+        &owner_id,
+        send(&server_id, &room_id, "note to @edit_mentioner"),
+    )
+    .await
+    .expect("send should be accepted");
+    assert_eq!(accepted.message.mentions.len(), 1);
+    tokio::task::yield_now().await;
+
+    let edited = edit_message(
+        &state,
+        &owner.user,
+        &owner_id,
+        EditMessage {
+            server_id,
+            room_id,
+            message_id: accepted.message.id,
+            body: "note to nobody".to_owned(),
+        },
+    )
+    .await
+    .expect("edit should be accepted");
+
+    assert!(edited.message.mentions.is_empty());
+}
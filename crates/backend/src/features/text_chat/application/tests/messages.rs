@@ -28,6 +28,7 @@ async fn owner_can_send_and_load_room_messages() {
             room_id: room_id.clone(),
             body: "  hello wt  ".to_owned(),
             attachment_ids: Vec::new(),
+            reply_to_message_id: None,
         },
     )
     .await
@@ -42,6 +43,8 @@ async fn owner_can_send_and_load_room_messages() {
             server_id,
             room_id,
             before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
@@ -74,6 +77,8 @@ async fn non_member_cannot_load_or_send() {
             server_id: server_id.clone(),
             room_id: room_id.clone(),
             before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
         },
     )
     .await
@@ -87,6 +92,7 @@ async fn non_member_cannot_load_or_send() {
             room_id,
             body: "hello".to_owned(),
             attachment_ids: Vec::new(),
+            reply_to_message_id: None,
         },
     )
     .await
@@ -125,6 +131,7 @@ async fn voice_room_rejects_text_chat() {
             room_id,
             body: "hello".to_owned(),
             attachment_ids: Vec::new(),
+            reply_to_message_id: None,
         },
     )
     .await
@@ -157,6 +164,7 @@ async fn message_body_is_required_and_limited() {
                 room_id: room_id.clone(),
                 body,
                 attachment_ids: Vec::new(),
+                reply_to_message_id: None,
             },
         )
         .await
@@ -0,0 +1,220 @@
+use cheenhub_contracts::realtime::{
+    DeleteMessage, ListPinnedMessages, PinMessage, ServerRoleKind, ServerRolePermission,
+    UnpinMessage,
+};
+use cheenhub_contracts::rest::ServerRoomKind;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use super::super::{
+    TextChatApplicationError, delete_message, list_pinned_messages, pin_message, unpin_message,
+};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
+use crate::features::servers::infrastructure::ServerRole;
+use crate::features::text_chat::domain::TextMessage;
+
+fn pin(server_id: &str, room_id: &str, message_id: Uuid) -> PinMessage {
+    PinMessage {
+        server_id: server_id.to_owned(),
+        room_id: room_id.to_owned(),
+        message_id: message_id.to_string(),
+    }
+}
+
+fn list(server_id: &str, room_id: &str) -> ListPinnedMessages {
+    ListPinnedMessages {
+        server_id: server_id.to_owned(),
+        room_id: room_id.to_owned(),
+    }
+}
+
+fn message(server_id: &str, room_id: &str, author_user_id: Uuid, index: i64) -> TextMessage {
+    TextMessage {
+        created_at: Utc::now() + Duration::seconds(index),
+        ..text_message(
+            server_id,
+            room_id,
+            author_user_id,
+            &format!("message {index}"),
+        )
+    }
+}
+
+fn role(
+    server_id: Uuid,
+    kind: ServerRoleKind,
+    permissions: Vec<ServerRolePermission>,
+) -> ServerRole {
+    ServerRole {
+        id: Uuid::new_v4(),
+        server_id,
+        name: format!("{kind:?}"),
+        color: "#38bdf8".to_owned(),
+        kind,
+        position: 0,
+        permissions,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn owner_pins_and_unpins_messages_most_recent_first() {
+    let state = state();
+    let owner = registered_user(&state, "pin_owner", "pin-owner@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Pin Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let first = insert_text_message(&state, message(&server_id, &room_id, owner_id, 0)).await;
+    let second = insert_text_message(&state, message(&server_id, &room_id, owner_id, 1)).await;
+
+    let pinned = pin_message(&state, &owner_id, pin(&server_id, &room_id, first))
+        .await
+        .expect("message should pin");
+    assert!(pinned.pinned);
+    let pinned_entry = pinned.pin.expect("pin should be returned");
+    assert_eq!(pinned_entry.message.id, first.to_string());
+    assert_eq!(pinned_entry.pinned_by_user_id, owner_id.to_string());
+    pin_message(&state, &owner_id, pin(&server_id, &room_id, second))
+        .await
+        .expect("message should pin");
+    pin_message(&state, &owner_id, pin(&server_id, &room_id, first))
+        .await
+        .expect("repeated pin should be accepted");
+
+    let pins = list_pinned_messages(&state, &owner_id, list(&server_id, &room_id))
+        .await
+        .expect("pins should load");
+    let ids = pins
+        .pins
+        .iter()
+        .map(|pin| pin.message.id.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![second.to_string(), first.to_string()]);
+
+    let unpinned = unpin_message(
+        &state,
+        &owner_id,
+        UnpinMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            message_id: second.to_string(),
+        },
+    )
+    .await
+    .expect("message should unpin");
+    assert!(!unpinned.pinned);
+    assert!(unpinned.pin.is_none());
+
+    let pins = list_pinned_messages(&state, &owner_id, list(&server_id, &room_id))
+        .await
+        .expect("pins should load");
+    assert_eq!(pins.pins.len(), 1);
+    assert_eq!(pins.pins[0].message.id, first.to_string());
+}
+
+#[tokio::test]
+async fn pinning_requires_pin_messages_permission() {
+    let state = state();
+    let owner = registered_user(&state, "pin_perm_owner", "pin-perm-owner@example.com").await;
+    let member = registered_user(&state, "pin_perm_member", "pin-perm-member@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let member_id = Uuid::parse_str(&member.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Pin Permissions",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let server_uuid = Uuid::parse_str(&server_id).expect("server id should be uuid");
+    state
+        .server_store
+        .insert_server_member(&server_uuid, &member_id)
+        .await
+        .expect("member should insert");
+    let message_id = insert_text_message(&state, message(&server_id, &room_id, member_id, 0)).await;
+
+    let error = pin_message(&state, &member_id, pin(&server_id, &room_id, message_id))
+        .await
+        .expect_err("member without permission should not pin");
+    assert!(matches!(error, TextChatApplicationError::Unauthorized(_)));
+    let visible = list_pinned_messages(&state, &member_id, list(&server_id, &room_id))
+        .await
+        .expect("members should still see pins");
+    assert!(visible.pins.is_empty());
+
+    let moderators = role(
+        server_uuid,
+        ServerRoleKind::Custom,
+        vec![ServerRolePermission::PinMessages],
+    );
+    let moderators_id = moderators.id;
+    state
+        .server_store
+        .replace_server_roles(
+            &server_uuid,
+            vec![
+                moderators,
+                role(server_uuid, ServerRoleKind::Member, Vec::new()),
+            ],
+        )
+        .await
+        .expect("roles should save");
+    state
+        .server_store
+        .assign_server_member_role(&server_uuid, &member_id, &moderators_id, &owner_id)
+        .await
+        .expect("role should be assigned");
+
+    pin_message(&state, &member_id, pin(&server_id, &room_id, message_id))
+        .await
+        .expect("member with permission should pin");
+}
+
+#[tokio::test]
+async fn deleted_messages_drop_out_of_pins_and_cannot_be_pinned() {
+    let state = state();
+    let owner = registered_user(&state, "pin_delete_owner", "pin-delete-owner@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Pin Deletion",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_id = insert_text_message(&state, message(&server_id, &room_id, owner_id, 0)).await;
+    pin_message(&state, &owner_id, pin(&server_id, &room_id, message_id))
+        .await
+        .expect("message should pin");
+
+    delete_message(
+        &state,
+        &owner_id,
+        DeleteMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            message_id: message_id.to_string(),
+        },
+    )
+    .await
+    .expect("message should delete");
+
+    let pins = list_pinned_messages(&state, &owner_id, list(&server_id, &room_id))
+        .await
+        .expect("pins should load");
+    assert!(pins.pins.is_empty());
+    let error = pin_message(&state, &owner_id, pin(&server_id, &room_id, message_id))
+        .await
+        .expect_err("deleted message should not pin");
+    assert!(matches!(error, TextChatApplicationError::NotFound(_)));
+}
@@ -0,0 +1,161 @@
+use cheenhub_contracts::realtime::{AddReaction, DeleteMessage, LoadRoomHistory, RemoveReaction};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::super::{
+    TextChatApplicationError, add_reaction, delete_message, load_room_history, remove_reaction,
+};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
+
+fn add(server_id: &str, room_id: &str, message_id: &Uuid, emoji: &str) -> AddReaction {
+    AddReaction {
+        server_id: server_id.to_owned(),
+        room_id: room_id.to_owned(),
+        message_id: message_id.to_string(),
+        emoji: emoji.to_owned(),
+    }
+}
+
+async fn room_with_message(nickname: &str) -> (crate::state::AppState, Uuid, String, String, Uuid) {
+    let state = state();
+    let auth = registered_user(&state, nickname, &format!("{nickname}@example.com")).await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Reaction Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let message_id = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, user_id, "react to me"),
+    )
+    .await;
+    (state, user_id, server_id, room_id, message_id)
+}
+
+#[tokio::test]
+async fn reactions_are_aggregated_in_history_and_can_be_removed() {
+    let (state, user_id, server_id, room_id, message_id) = room_with_message("reactor").await;
+
+    let added = add_reaction(
+        &state,
+        &user_id,
+        add(&server_id, &room_id, &message_id, " 👍 "),
+    )
+    .await
+    .expect("reaction should be added");
+    assert_eq!(added.emoji, "👍");
+    assert_eq!(added.reactions.len(), 1);
+    assert_eq!(added.reactions[0].count, 1);
+    assert!(added.reactions[0].reacted);
+    add_reaction(
+        &state,
+        &user_id,
+        add(&server_id, &room_id, &message_id, "👍"),
+    )
+    .await
+    .expect("repeated reaction should be accepted");
+    add_reaction(
+        &state,
+        &user_id,
+        add(&server_id, &room_id, &message_id, "🎉"),
+    )
+    .await
+    .expect("second emoji should be added");
+
+    let history = load_room_history(
+        &state,
+        &user_id,
+        LoadRoomHistory {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("history should load");
+    let reactions = &history.messages[0].reactions;
+    assert_eq!(
+        reactions
+            .iter()
+            .map(|reaction| (reaction.emoji.as_str(), reaction.count))
+            .collect::<Vec<_>>(),
+        vec![("👍", 1), ("🎉", 1)]
+    );
+
+    let removed = remove_reaction(
+        &state,
+        &user_id,
+        RemoveReaction {
+            server_id,
+            room_id,
+            message_id: message_id.to_string(),
+            emoji: "👍".to_owned(),
+        },
+    )
+    .await
+    .expect("reaction should be removed");
+    assert!(!removed.added);
+    assert_eq!(removed.reactions.len(), 1);
+    assert_eq!(removed.reactions[0].emoji, "🎉");
+}
+
+#[tokio::test]
+async fn reactions_reject_invalid_emoji_overflow_and_deleted_messages() {
+    let (state, user_id, server_id, room_id, message_id) = room_with_message("limits").await;
+
+    let error = add_reaction(
+        &state,
+        &user_id,
+        add(&server_id, &room_id, &message_id, "lol"),
+    )
+    .await
+    .expect_err("plain text must not become a reaction");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+
+    for offset in 0..20 {
+        let emoji = char::from_u32(0x1F600 + offset)
+            .expect("emoji code point should be valid")
+            .to_string();
+        add_reaction(
+            &state,
+            &user_id,
+            add(&server_id, &room_id, &message_id, &emoji),
+        )
+        .await
+        .expect("reaction below the limit should be added");
+    }
+    let error = add_reaction(
+        &state,
+        &user_id,
+        add(&server_id, &room_id, &message_id, "🦀"),
+    )
+    .await
+    .expect_err("distinct reaction limit must be enforced");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+
+    delete_message(
+        &state,
+        &user_id,
+        DeleteMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            message_id: message_id.to_string(),
+        },
+    )
+    .await
+    .expect("author should delete message");
+    let error = add_reaction(
+        &state,
+        &user_id,
+        add(&server_id, &room_id, &message_id, "😀"),
+    )
+    .await
+    .expect_err("deleted message must not accept reactions");
+    assert!(matches!(error, TextChatApplicationError::NotFound(_)));
+}
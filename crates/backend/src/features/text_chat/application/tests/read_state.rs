@@ -0,0 +1,239 @@
+use cheenhub_contracts::realtime::{LoadRoomHistory, MarkRoomRead};
+use cheenhub_contracts::rest::ServerRoomKind;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+use super::super::{
+    TextChatApplicationError, load_room_history, mark_room_read, room_unread_counts,
+    seed_server_read_states,
+};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
+use crate::features::text_chat::domain::{MentionKind, MessageMention, TextMessage};
+
+fn message(
+    server_id: &str,
+    room_id: &str,
+    author_user_id: Uuid,
+    index: i64,
+    mentions: Vec<MessageMention>,
+) -> TextMessage {
+    TextMessage {
+        created_at: Utc::now() + Duration::seconds(index),
+        mentions,
+        ..text_message(
+            server_id,
+            room_id,
+            author_user_id,
+            &format!("message {index}"),
+        )
+    }
+}
+
+fn mark(server_id: &str, room_id: &str, message_id: Uuid) -> MarkRoomRead {
+    MarkRoomRead {
+        server_id: server_id.to_owned(),
+        room_id: room_id.to_owned(),
+        message_id: message_id.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn unread_counts_skip_own_and_deleted_messages_after_marker() {
+    let state = state();
+    let owner = registered_user(&state, "unread_owner", "unread-owner@example.com").await;
+    let member = registered_user(&state, "unread_member", "unread-member@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let member_id = Uuid::parse_str(&member.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Unread Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let server_uuid = Uuid::parse_str(&server_id).expect("server id should be uuid");
+    let room_uuid = Uuid::parse_str(&room_id).expect("room id should be uuid");
+    state
+        .server_store
+        .insert_server_member(&server_uuid, &member_id)
+        .await
+        .expect("member should insert");
+    let owner_mention = MessageMention {
+        kind: MentionKind::User,
+        target_id: owner_id,
+        name: "unread_owner".to_owned(),
+    };
+
+    let first = insert_text_message(
+        &state,
+        message(&server_id, &room_id, member_id, 0, Vec::new()),
+    )
+    .await;
+    insert_text_message(
+        &state,
+        message(&server_id, &room_id, owner_id, 1, Vec::new()),
+    )
+    .await;
+    insert_text_message(
+        &state,
+        message(&server_id, &room_id, member_id, 2, vec![owner_mention]),
+    )
+    .await;
+    let mut deleted = message(&server_id, &room_id, member_id, 3, Vec::new());
+    deleted.deleted_at = Some(Utc::now());
+    insert_text_message(&state, deleted).await;
+
+    let counts = room_unread_counts(&state, &owner_id, &server_uuid, &[room_uuid])
+        .await
+        .expect("counts should load");
+    assert_eq!(counts[&room_uuid].unread_count, 2);
+    assert_eq!(counts[&room_uuid].mention_count, 1);
+
+    let updated = mark_room_read(&state, &owner_id, mark(&server_id, &room_id, first))
+        .await
+        .expect("room should be marked read");
+    assert_eq!(updated.last_read_message_id, Some(first.to_string()));
+    assert_eq!(updated.unread_count, 1);
+    assert_eq!(updated.mention_count, 1);
+
+    let member_counts = room_unread_counts(&state, &member_id, &server_uuid, &[room_uuid])
+        .await
+        .expect("counts should load");
+    assert_eq!(member_counts[&room_uuid].unread_count, 1);
+    assert_eq!(member_counts[&room_uuid].mention_count, 0);
+}
+
+#[tokio::test]
+async fn history_before_joining_is_not_unread() {
+    let state = state();
+    let owner = registered_user(&state, "seed_owner", "seed-owner@example.com").await;
+    let member = registered_user(&state, "seed_member", "seed-member@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let member_id = Uuid::parse_str(&member.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Seed Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let server_uuid = Uuid::parse_str(&server_id).expect("server id should be uuid");
+    let room_uuid = Uuid::parse_str(&room_id).expect("room id should be uuid");
+    for index in 0..3 {
+        insert_text_message(
+            &state,
+            message(&server_id, &room_id, owner_id, index, Vec::new()),
+        )
+        .await;
+    }
+
+    state
+        .server_store
+        .insert_server_member(&server_uuid, &member_id)
+        .await
+        .expect("member should insert");
+    seed_server_read_states(&state, &member_id, &server_uuid)
+        .await
+        .expect("read markers should seed");
+    let counts = room_unread_counts(&state, &member_id, &server_uuid, &[room_uuid])
+        .await
+        .expect("counts should load");
+    assert_eq!(counts[&room_uuid].unread_count, 0);
+
+    insert_text_message(
+        &state,
+        message(&server_id, &room_id, owner_id, 3, Vec::new()),
+    )
+    .await;
+    let counts = room_unread_counts(&state, &member_id, &server_uuid, &[room_uuid])
+        .await
+        .expect("counts should load");
+    assert_eq!(counts[&room_uuid].unread_count, 1);
+}
+
+#[tokio::test]
+async fn read_marker_only_moves_forward_and_is_returned_with_history() {
+    let state = state();
+    let owner = registered_user(&state, "marker_owner", "marker-owner@example.com").await;
+    let member = registered_user(&state, "marker_member", "marker-member@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let member_id = Uuid::parse_str(&member.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Marker Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let older = insert_text_message(
+        &state,
+        message(&server_id, &room_id, member_id, 0, Vec::new()),
+    )
+    .await;
+    let newer = insert_text_message(
+        &state,
+        message(&server_id, &room_id, member_id, 1, Vec::new()),
+    )
+    .await;
+
+    mark_room_read(&state, &owner_id, mark(&server_id, &room_id, newer))
+        .await
+        .expect("newer message should be marked read");
+    let stale = mark_room_read(&state, &owner_id, mark(&server_id, &room_id, older))
+        .await
+        .expect("stale marker should be accepted");
+    assert_eq!(stale.last_read_message_id, Some(newer.to_string()));
+    assert_eq!(stale.unread_count, 0);
+
+    let history = load_room_history(
+        &state,
+        &owner_id,
+        LoadRoomHistory {
+            server_id,
+            room_id,
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("history should load");
+    assert_eq!(history.last_read_message_id, Some(newer.to_string()));
+}
+
+#[tokio::test]
+async fn marking_message_from_another_room_is_rejected() {
+    let state = state();
+    let owner = registered_user(&state, "foreign_reader", "foreign-reader@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Foreign Read",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let (other_server_id, other_room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Other Read",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let foreign = insert_text_message(
+        &state,
+        message(&other_server_id, &other_room_id, owner_id, 0, Vec::new()),
+    )
+    .await;
+
+    let error = mark_room_read(&state, &owner_id, mark(&server_id, &room_id, foreign))
+        .await
+        .expect_err("foreign message should not be marked read");
+
+    assert!(matches!(error, TextChatApplicationError::NotFound(_)));
+}
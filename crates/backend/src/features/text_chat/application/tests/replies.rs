@@ -0,0 +1,132 @@
+use cheenhub_contracts::realtime::{DeleteMessage, LoadRoomHistory, SendMessage};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::super::{TextChatApplicationError, delete_message, load_room_history, send_message};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
+
+#[tokio::test]
+async fn reply_carries_parent_snapshot_through_history_and_deletion() {
+    let state = state();
+    let auth = registered_user(&state, "replier", "replier@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Reply Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let long_body = "a".repeat(300);
+    let parent_id = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, user_id, &long_body),
+    )
+    .await;
+
+    let accepted = send_message(
+        &state,
+        &auth.user,
+        &user_id,
+        SendMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            body: "answer".to_owned(),
+            attachment_ids: Vec::new(),
+            reply_to_message_id: Some(parent_id.to_string()),
+        },
+    )
+    .await
+    .expect("reply should be accepted");
+    let snapshot = accepted
+        .message
+        .reply_to
+        .expect("reply should carry snapshot");
+    assert_eq!(
+        accepted.message.reply_to_message_id,
+        Some(parent_id.to_string())
+    );
+    assert_eq!(snapshot.message_id, parent_id.to_string());
+    assert!(snapshot.body.chars().count() < long_body.len());
+    assert!(snapshot.body.ends_with('…'));
+    assert!(!snapshot.deleted);
+
+    tokio::task::yield_now().await;
+    delete_message(
+        &state,
+        &user_id,
+        DeleteMessage {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            message_id: parent_id.to_string(),
+        },
+    )
+    .await
+    .expect("parent delete should succeed");
+
+    let history = load_room_history(
+        &state,
+        &user_id,
+        LoadRoomHistory {
+            server_id,
+            room_id,
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .await
+    .expect("history should load");
+    assert_eq!(history.messages.len(), 1);
+    let snapshot = history.messages[0]
+        .reply_to
+        .clone()
+        .expect("history should carry parent snapshot");
+    assert!(snapshot.deleted);
+    assert!(snapshot.body.is_empty());
+}
+
+#[tokio::test]
+async fn reply_to_message_from_another_room_is_rejected() {
+    let state = state();
+    let auth = registered_user(&state, "cross_reply", "cross-reply@example.com").await;
+    let user_id = Uuid::parse_str(&auth.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Reply Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let (other_server_id, other_room_id) = create_server_room(
+        &state,
+        &user_id,
+        "Other Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let foreign_parent_id = insert_text_message(
+        &state,
+        text_message(&other_server_id, &other_room_id, user_id, "elsewhere"),
+    )
+    .await;
+
+    let error = send_message(
+        &state,
+        &auth.user,
+        &user_id,
+        SendMessage {
+            server_id,
+            room_id,
+            body: "answer".to_owned(),
+            attachment_ids: Vec::new(),
+            reply_to_message_id: Some(foreign_parent_id.to_string()),
+        },
+    )
+    .await
+    .expect_err("cross-room reply must be rejected");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+}
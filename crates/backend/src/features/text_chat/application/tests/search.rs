@@ -0,0 +1,168 @@
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::super::search::SearchServerMessages;
+use super::super::{TextChatApplicationError, search_server_messages};
+use super::{create_server_room, insert_text_message, registered_user, state, text_message};
+
+fn search(query: &str) -> SearchServerMessages {
+    SearchServerMessages {
+        query: query.to_owned(),
+        room_id: None,
+        before_message_id: None,
+    }
+}
+
+#[tokio::test]
+async fn search_finds_visible_messages_and_skips_deleted_ones() {
+    let state = state();
+    let owner = registered_user(&state, "search_owner", "search-owner@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Search Server",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    let server_uuid = Uuid::parse_str(&server_id).expect("server id should be uuid");
+    let room_uuid = Uuid::parse_str(&room_id).expect("room id should be uuid");
+    let kept = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "Релиз в пятницу"),
+    )
+    .await;
+    let deleted = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "релиз отменён"),
+    )
+    .await;
+    insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "обед в два"),
+    )
+    .await;
+    state
+        .text_chat_store
+        .soft_delete_message(&server_uuid, &room_uuid, &deleted, &owner_id, true)
+        .await
+        .expect("message should delete");
+
+    let found = search_server_messages(&state, &owner_id, server_id.clone(), search("релиз"))
+        .await
+        .expect("search should succeed");
+
+    assert_eq!(found.messages.len(), 1);
+    assert_eq!(found.messages[0].id, kept.to_string());
+    assert!(!found.has_more);
+
+    let older = search_server_messages(
+        &state,
+        &owner_id,
+        server_id.clone(),
+        SearchServerMessages {
+            before_message_id: Some(kept.to_string()),
+            ..search("релиз")
+        },
+    )
+    .await
+    .expect("known cursor should page");
+    assert!(older.messages.is_empty());
+
+    let error = search_server_messages(
+        &state,
+        &owner_id,
+        server_id.clone(),
+        SearchServerMessages {
+            before_message_id: Some(Uuid::new_v4().to_string()),
+            ..search("релиз")
+        },
+    )
+    .await
+    .expect_err("unknown cursor should be rejected");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+
+    let error = search_server_messages(&state, &owner_id, server_id, search(" р "))
+        .await
+        .expect_err("short query should be rejected");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+}
+
+#[tokio::test]
+async fn search_cursor_must_be_a_previous_match() {
+    let state = state();
+    let owner = registered_user(&state, "search_cursor", "search-cursor@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Cursor Search",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "релиз в пятницу"),
+    )
+    .await;
+    let unrelated = insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "обед в два"),
+    )
+    .await;
+
+    let error = search_server_messages(
+        &state,
+        &owner_id,
+        server_id,
+        SearchServerMessages {
+            before_message_id: Some(unrelated.to_string()),
+            ..search("релиз")
+        },
+    )
+    .await
+    .expect_err("cursor outside the matches should be rejected");
+    assert!(matches!(error, TextChatApplicationError::BadRequest(_)));
+}
+
+#[tokio::test]
+async fn search_is_limited_to_servers_the_user_can_read() {
+    let state = state();
+    let owner = registered_user(&state, "search_private", "search-private@example.com").await;
+    let outsider = registered_user(&state, "search_outsider", "search-outsider@example.com").await;
+    let owner_id = Uuid::parse_str(&owner.user.id).expect("user id should be uuid");
+    let outsider_id = Uuid::parse_str(&outsider.user.id).expect("user id should be uuid");
+    let (server_id, room_id) = create_server_room(
+        &state,
+        &owner_id,
+        "Private Search",
+        "general",
+        ServerRoomKind::TextAndVoice,
+    )
+    .await;
+    insert_text_message(
+        &state,
+        text_message(&server_id, &room_id, owner_id, "секретный план"),
+    )
+    .await;
+
+    let error = search_server_messages(&state, &outsider_id, server_id.clone(), search("план"))
+        .await
+        .expect_err("outsider should not search the server");
+    assert!(matches!(error, TextChatApplicationError::Unauthorized(_)));
+
+    let error = search_server_messages(
+        &state,
+        &outsider_id,
+        server_id,
+        SearchServerMessages {
+            room_id: Some(room_id),
+            ..search("план")
+        },
+    )
+    .await
+    .expect_err("outsider should not search the room");
+    assert!(matches!(error, TextChatApplicationError::Unauthorized(_)));
+}
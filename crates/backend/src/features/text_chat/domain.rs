@@ -18,7 +18,7 @@ pub(crate) struct TextMessage {
     pub(crate) author_nickname: String,
     /// Тело сообщения.
     pub(crate) body: String,
-    /// Вложения-изображения, включенные в сообщение.
+    /// Вложения, включенные в сообщение.
     pub(crate) attachments: Vec<ChatAttachment>,
     /// Временная метка создания сообщения.
     pub(crate) created_at: DateTime<Utc>,
@@ -26,9 +26,124 @@ pub(crate) struct TextMessage {
     pub(crate) deleted_at: Option<DateTime<Utc>>,
     /// Пользователь, удаливший сообщение; для модераторских удалений может отличаться от автора.
     pub(crate) deleted_by_user_id: Option<Uuid>,
+    /// Временная метка последнего изменения текста; задается при редактировании автором.
+    pub(crate) edited_at: Option<DateTime<Utc>>,
+    /// Сообщение той же комнаты, на которое отвечает это сообщение.
+    pub(crate) reply_to_message_id: Option<Uuid>,
+    /// Пользователи и роли, упомянутые в теле сообщения.
+    pub(crate) mentions: Vec<MessageMention>,
 }
 
-/// Метаданные вложения-изображения чата.
+/// Вид цели упоминания.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum MentionKind {
+    /// Участник сервера.
+    User,
+    /// Роль сервера.
+    Role,
+}
+
+/// Разрешенное упоминание пользователя или роли в сообщении.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MessageMention {
+    /// Вид цели упоминания.
+    pub(crate) kind: MentionKind,
+    /// Идентификатор пользователя или роли.
+    pub(crate) target_id: Uuid,
+    /// Снимок никнейма или названия роли на момент отправки.
+    pub(crate) name: String,
+}
+
+/// Реакция одного пользователя одним эмодзи на сообщение.
+///
+/// Общая форма строки для сообщений комнат и личных сообщений: `message_id`
+/// указывает на сообщение того хранилища, которое вернуло реакцию.
+#[derive(Debug, Clone)]
+pub(crate) struct MessageReaction {
+    /// Сообщение, на которое поставлена реакция.
+    pub(crate) message_id: Uuid,
+    /// Пользователь, поставивший реакцию.
+    pub(crate) user_id: Uuid,
+    /// Эмодзи реакции.
+    pub(crate) emoji: String,
+    /// Временная метка установки реакции.
+    pub(crate) created_at: DateTime<Utc>,
+}
+
+/// Закрепленное сообщение текстовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PinnedMessage {
+    /// Закрепленное сообщение.
+    pub(crate) message_id: Uuid,
+    /// Комната, в которой закреплено сообщение.
+    pub(crate) room_id: Uuid,
+    /// Пользователь, закрепивший сообщение.
+    pub(crate) pinned_by_user_id: Uuid,
+    /// Временная метка закрепления.
+    pub(crate) pinned_at: DateTime<Utc>,
+}
+
+/// Отметка прочтения текстовой комнаты участником.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RoomReadState {
+    /// Прочитанная комната.
+    pub(crate) room_id: Uuid,
+    /// Участник, которому принадлежит отметка.
+    pub(crate) user_id: Uuid,
+    /// Последнее прочитанное сообщение.
+    pub(crate) last_read_message_id: Uuid,
+    /// Временная метка создания последнего прочитанного сообщения.
+    pub(crate) last_read_message_created_at: DateTime<Utc>,
+    /// Временная метка последнего сдвига отметки.
+    pub(crate) updated_at: DateTime<Utc>,
+}
+
+/// Счетчики непрочитанного в одной комнате.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct RoomUnreadCounts {
+    /// Неудаленные сообщения других участников новее отметки прочтения.
+    pub(crate) unread_count: u64,
+    /// Непрочитанные сообщения, упоминающие пользователя или одну из его ролей.
+    pub(crate) mention_count: u64,
+}
+
+/// Вид вложения чата, определенный по содержимому файла.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChatAttachmentKind {
+    /// Изображение с известными размерами.
+    Image,
+    /// Видеофайл.
+    Video,
+    /// Аудиофайл.
+    Audio,
+    /// Любой другой файл.
+    File,
+}
+
+impl ChatAttachmentKind {
+    /// Возвращает строковое значение для хранения.
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Image => "image",
+            Self::Video => "video",
+            Self::Audio => "audio",
+            Self::File => "file",
+        }
+    }
+
+    /// Читает вид вложения из строки хранилища.
+    pub(crate) fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "image" => Some(Self::Image),
+            "video" => Some(Self::Video),
+            "audio" => Some(Self::Audio),
+            "file" => Some(Self::File),
+            _ => None,
+        }
+    }
+}
+
+/// Метаданные вложения чата.
 #[derive(Debug, Clone)]
 pub(crate) struct ChatAttachment {
     /// Стабильный идентификатор вложения.
@@ -45,14 +160,16 @@ pub(crate) struct ChatAttachment {
     pub(crate) bucket: String,
     /// Ключ S3-объекта.
     pub(crate) object_key: String,
-    /// Проверенный MIME-тип изображения.
+    /// Вид вложения.
+    pub(crate) kind: ChatAttachmentKind,
+    /// MIME-тип, определенный по содержимому.
     pub(crate) content_type: String,
     /// Исходный размер загрузки в байтах.
     pub(crate) byte_size: i64,
-    /// Ширина изображения в пикселях.
-    pub(crate) width: i32,
-    /// Высота изображения в пикселях.
-    pub(crate) height: i32,
+    /// Ширина изображения в пикселях; у остальных видов не задается.
+    pub(crate) width: Option<i32>,
+    /// Высота изображения в пикселях; у остальных видов не задается.
+    pub(crate) height: Option<i32>,
     /// SHA-256-хэш загруженных байтов.
     pub(crate) sha256: String,
     /// Необязательное исходное имя файла из запроса загрузки.
@@ -61,7 +178,7 @@ pub(crate) struct ChatAttachment {
     pub(crate) created_at: DateTime<Utc>,
 }
 
-/// Метаданные нового вложения-изображения чата.
+/// Метаданные нового вложения чата.
 #[derive(Debug, Clone)]
 pub(crate) struct NewChatAttachment {
     /// Стабильный идентификатор вложения.
@@ -78,14 +195,16 @@ pub(crate) struct NewChatAttachment {
     pub(crate) bucket: String,
     /// Ключ S3-объекта.
     pub(crate) object_key: String,
-    /// Проверенный MIME-тип изображения.
+    /// Вид вложения.
+    pub(crate) kind: ChatAttachmentKind,
+    /// MIME-тип, определенный по содержимому.
     pub(crate) content_type: String,
     /// Исходный размер загрузки в байтах.
     pub(crate) byte_size: i64,
-    /// Ширина изображения в пикселях.
-    pub(crate) width: i32,
-    /// Высота изображения в пикселях.
-    pub(crate) height: i32,
+    /// Ширина изображения в пикселях; у остальных видов не задается.
+    pub(crate) width: Option<i32>,
+    /// Высота изображения в пикселях; у остальных видов не задается.
+    pub(crate) height: Option<i32>,
     /// SHA-256-хэш загруженных байтов.
     pub(crate) sha256: String,
     /// Необязательное исходное имя файла из запроса загрузки.
@@ -0,0 +1,28 @@
+//! Сущность реакции на сообщение.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных реакции на сообщение комнаты или личное сообщение.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "message_reactions")]
+pub struct Model {
+    /// Стабильный идентификатор реакции.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Сообщение комнаты; задано только для реакций в текстовом чате.
+    pub text_message_id: Option<Uuid>,
+    /// Личное сообщение; задано только для реакций в личных диалогах.
+    pub dm_message_id: Option<Uuid>,
+    /// Пользователь, поставивший реакцию.
+    pub user_id: Uuid,
+    /// Эмодзи реакции.
+    pub emoji: String,
+    /// Временная метка установки реакции.
+    pub created_at: DateTimeUtc,
+}
+
+/// Связи реакции на сообщение.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
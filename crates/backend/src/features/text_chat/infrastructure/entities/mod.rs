@@ -1,4 +1,9 @@
 //! Сущности SeaORM для инфраструктуры текстового чата.
 
+pub(crate) mod message_reactions;
 pub(crate) mod text_chat_attachments;
+pub(crate) mod text_message_edits;
+pub(crate) mod text_message_mentions;
+pub(crate) mod text_message_pins;
 pub(crate) mod text_messages;
+pub(crate) mod text_room_read_states;
@@ -21,14 +21,16 @@ pub struct Model {
     pub bucket: String,
     /// Ключ S3-объекта.
     pub object_key: String,
-    /// Проверенный MIME-тип изображения.
+    /// Вид вложения: `image`, `video`, `audio` или `file`.
+    pub kind: String,
+    /// MIME-тип, определенный по содержимому.
     pub content_type: String,
     /// Исходный размер загрузки в байтах.
     pub byte_size: i64,
     /// Ширина изображения в пикселях.
-    pub width: i32,
+    pub width: Option<i32>,
     /// Высота изображения в пикселях.
-    pub height: i32,
+    pub height: Option<i32>,
     /// SHA-256-хэш загруженных байтов.
     pub sha256: String,
     /// Необязательное исходное имя файла из запроса загрузки.
@@ -0,0 +1,26 @@
+//! Сущность записи истории изменений текстового сообщения.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных с предыдущей версией текста сообщения.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "text_message_edits")]
+pub struct Model {
+    /// Стабильный идентификатор записи истории.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Измененное сообщение.
+    pub message_id: Uuid,
+    /// Пользователь, изменивший сообщение.
+    pub editor_user_id: Uuid,
+    /// Тело сообщения до изменения.
+    pub previous_body: String,
+    /// Временная метка изменения.
+    pub edited_at: DateTimeUtc,
+}
+
+/// Связи записи истории изменений текстового сообщения.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
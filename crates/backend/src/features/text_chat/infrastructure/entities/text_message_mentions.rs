@@ -0,0 +1,26 @@
+//! Сущность упоминания в текстовом сообщении.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных разрешенного упоминания пользователя или роли.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "text_message_mentions")]
+pub struct Model {
+    /// Стабильный идентификатор упоминания.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub id: Uuid,
+    /// Сообщение, содержащее упоминание.
+    pub message_id: Uuid,
+    /// Вид цели: `user` или `role`.
+    pub kind: String,
+    /// Идентификатор упомянутого пользователя или роли.
+    pub target_id: Uuid,
+    /// Снимок никнейма или названия роли.
+    pub name: String,
+}
+
+/// Связи упоминания в текстовом сообщении.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,24 @@
+//! Сущность закрепленного сообщения текстовой комнаты.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных закрепленного сообщения комнаты.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "text_message_pins")]
+pub struct Model {
+    /// Закрепленное сообщение.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub message_id: Uuid,
+    /// Комната, в которой закреплено сообщение.
+    pub room_id: Uuid,
+    /// Пользователь, закрепивший сообщение.
+    pub pinned_by_user_id: Uuid,
+    /// Временная метка закрепления.
+    pub pinned_at: DateTimeUtc,
+}
+
+/// Связи закрепленного сообщения.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
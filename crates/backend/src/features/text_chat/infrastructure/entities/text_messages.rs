@@ -25,6 +25,9 @@ pub struct Model {
     pub deleted_at: Option<DateTimeUtc>,
     /// Пользователь, удаливший сообщение; для модераторских удалений может отличаться от автора.
     pub deleted_by_user_id: Option<Uuid>,
+    /// Временная метка последнего изменения текста; задается при редактировании автором.
+    pub edited_at: Option<DateTimeUtc>,
+    pub reply_to_message_id: Option<Uuid>,
 }
 
 /// Связи текстового сообщения.
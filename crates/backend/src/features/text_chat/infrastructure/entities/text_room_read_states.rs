@@ -0,0 +1,27 @@
+//! Сущность отметки прочтения текстовой комнаты.
+
+use sea_orm::entity::prelude::*;
+
+/// Строка базы данных с последним прочитанным участником сообщением комнаты.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel)]
+#[sea_orm(table_name = "text_room_read_states")]
+pub struct Model {
+    /// Комната, к которой относится отметка.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub room_id: Uuid,
+    /// Участник, прочитавший комнату.
+    #[sea_orm(primary_key, auto_increment = false)]
+    pub user_id: Uuid,
+    /// Последнее прочитанное сообщение.
+    pub last_read_message_id: Uuid,
+    /// Время создания последнего прочитанного сообщения.
+    pub last_read_message_created_at: DateTimeUtc,
+    /// Время последнего обновления отметки.
+    pub updated_at: DateTimeUtc,
+}
+
+/// Связи отметки прочтения текстовой комнаты.
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,5 +1,6 @@
 //! Простое in-memory-хранилище текстового чата.
 
+use std::collections::HashMap;
 use std::sync::Mutex;
 
 use anyhow::anyhow;
@@ -7,14 +8,25 @@ use async_trait::async_trait;
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::features::text_chat::domain::{ChatAttachment, NewChatAttachment, TextMessage};
-use crate::features::text_chat::infrastructure::{HISTORY_LIMIT, TextChatStore, TextMessagePage};
+use crate::features::text_chat::domain::{
+    ChatAttachment, MessageMention, MessageReaction, NewChatAttachment, PinnedMessage,
+    RoomReadState, RoomUnreadCounts, TextMessage,
+};
+use crate::features::text_chat::infrastructure::{
+    HistoryAnchor, SEARCH_LIMIT, TextChatStore, TextMessagePage,
+};
+
+mod pins;
+mod read_state;
 
 /// In-memory-хранилище текстового чата для локального запуска и тестов.
 #[derive(Default)]
 pub(crate) struct InMemoryTextChatStore {
     messages: Mutex<Vec<TextMessage>>,
     attachments: Mutex<Vec<ChatAttachment>>,
+    reactions: Mutex<Vec<MessageReaction>>,
+    read_states: Mutex<Vec<RoomReadState>>,
+    pins: Mutex<Vec<PinnedMessage>>,
 }
 
 #[async_trait]
@@ -51,6 +63,7 @@ impl TextChatStore for InMemoryTextChatStore {
                 message_id: attachment.message_id,
                 bucket: attachment.bucket,
                 object_key: attachment.object_key,
+                kind: attachment.kind,
                 content_type: attachment.content_type,
                 byte_size: attachment.byte_size,
                 width: attachment.width,
@@ -79,16 +92,27 @@ impl TextChatStore for InMemoryTextChatStore {
     async fn room_message_page(
         &self,
         room_id: &Uuid,
-        before_message_id: Option<&Uuid>,
+        anchor: HistoryAnchor,
     ) -> anyhow::Result<TextMessagePage> {
         let mut messages = self
             .messages
             .lock()
             .map_err(|_| poisoned())?
             .iter()
-            .filter(|message| message.room_id == *room_id && message.deleted_at.is_none())
+            .filter(|message| message.room_id == *room_id)
             .cloned()
             .collect::<Vec<_>>();
+        let cursor = match anchor.message_id() {
+            Some(message_id) => Some(
+                messages
+                    .iter()
+                    .find(|message| message.id == message_id)
+                    .map(|message| (message.created_at, message.id))
+                    .ok_or_else(|| anyhow!("message history cursor was not found"))?,
+            ),
+            None => None,
+        };
+        messages.retain(|message| message.deleted_at.is_none());
         let attachments = self.attachments.lock().map_err(|_| poisoned())?.clone();
         for message in &mut messages {
             message.attachments = attachments
@@ -98,24 +122,74 @@ impl TextChatStore for InMemoryTextChatStore {
                 .collect();
         }
         messages.sort_by_key(|message| (message.created_at, message.id));
-        if let Some(before_message_id) = before_message_id {
-            let Some(cursor_index) = messages
+        let newer = match cursor {
+            Some(cursor) => {
+                let split = messages.partition_point(|message| {
+                    let key = (message.created_at, message.id);
+                    key < cursor || (anchor.anchor_is_older() && key == cursor)
+                });
+                messages.split_off(split)
+            }
+            None => Vec::new(),
+        };
+        let (older_limit, newer_limit) = anchor.page_limits();
+
+        Ok(history_window(messages, newer, older_limit, newer_limit))
+    }
+
+    async fn search_room_messages(
+        &self,
+        room_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<Option<TextMessagePage>> {
+        let query = query.to_lowercase();
+        let mut messages = self
+            .messages
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|message| {
+                room_ids.contains(&message.room_id)
+                    && message.deleted_at.is_none()
+                    && message.body.to_lowercase().contains(&query)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        let attachments = self.attachments.lock().map_err(|_| poisoned())?.clone();
+        for message in &mut messages {
+            message.attachments = attachments
                 .iter()
-                .position(|message| message.id == *before_message_id)
-            else {
-                return Err(anyhow!("message history cursor was not found"));
-            };
-            messages.truncate(cursor_index);
+                .filter(|attachment| attachment.message_id == Some(message.id))
+                .cloned()
+                .collect();
         }
-        let start = messages
-            .len()
-            .saturating_sub(usize::try_from(HISTORY_LIMIT).unwrap_or(50));
-        let has_more = start > 0;
+        Ok(page_before(messages, before_message_id, SEARCH_LIMIT))
+    }
 
-        Ok(TextMessagePage {
-            messages: messages.split_off(start),
-            has_more,
-        })
+    async fn find_room_messages(
+        &self,
+        room_id: &Uuid,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<TextMessage>> {
+        let mut messages = self
+            .messages
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|message| message.room_id == *room_id && message_ids.contains(&message.id))
+            .cloned()
+            .collect::<Vec<_>>();
+        let attachments = self.attachments.lock().map_err(|_| poisoned())?;
+        for message in &mut messages {
+            message.attachments = attachments
+                .iter()
+                .filter(|attachment| attachment.message_id == Some(message.id))
+                .cloned()
+                .collect();
+        }
+
+        Ok(messages)
     }
 
     async fn soft_delete_message(
@@ -141,8 +215,194 @@ impl TextChatStore for InMemoryTextChatStore {
 
         Ok(Some(message.clone()))
     }
+
+    async fn edit_message(
+        &self,
+        server_id: &Uuid,
+        room_id: &Uuid,
+        message_id: &Uuid,
+        editor_user_id: &Uuid,
+        body: String,
+        mentions: Vec<MessageMention>,
+    ) -> anyhow::Result<Option<TextMessage>> {
+        let mut messages = self.messages.lock().map_err(|_| poisoned())?;
+        let Some(message) = messages.iter_mut().find(|m| {
+            m.id == *message_id
+                && m.server_id == *server_id
+                && m.room_id == *room_id
+                && m.deleted_at.is_none()
+                && m.author_user_id == *editor_user_id
+        }) else {
+            return Ok(None);
+        };
+        message.body = body;
+        message.mentions = mentions;
+        message.edited_at = Some(Utc::now());
+
+        let mut message = message.clone();
+        message.attachments = self
+            .attachments
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|attachment| attachment.message_id == Some(message.id))
+            .cloned()
+            .collect();
+
+        Ok(Some(message))
+    }
+
+    async fn add_message_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        let mut reactions = self.reactions.lock().map_err(|_| poisoned())?;
+        if reactions.iter().any(|reaction| {
+            reaction.message_id == *message_id
+                && reaction.user_id == *user_id
+                && reaction.emoji == emoji
+        }) {
+            return Ok(false);
+        }
+        reactions.push(MessageReaction {
+            message_id: *message_id,
+            user_id: *user_id,
+            emoji: emoji.to_owned(),
+            created_at: Utc::now(),
+        });
+
+        Ok(true)
+    }
+
+    async fn remove_message_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        let mut reactions = self.reactions.lock().map_err(|_| poisoned())?;
+        let before = reactions.len();
+        reactions.retain(|reaction| {
+            reaction.message_id != *message_id
+                || reaction.user_id != *user_id
+                || reaction.emoji != emoji
+        });
+
+        Ok(reactions.len() != before)
+    }
+
+    async fn message_reactions(
+        &self,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<MessageReaction>> {
+        Ok(self
+            .reactions
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|reaction| message_ids.contains(&reaction.message_id))
+            .cloned()
+            .collect())
+    }
+
+    async fn pin_message(&self, pin: PinnedMessage) -> anyhow::Result<bool> {
+        self.insert_pin(pin)
+    }
+
+    async fn unpin_message(&self, room_id: &Uuid, message_id: &Uuid) -> anyhow::Result<bool> {
+        self.delete_pin(room_id, message_id)
+    }
+
+    async fn room_pinned_messages(&self, room_id: &Uuid) -> anyhow::Result<Vec<PinnedMessage>> {
+        self.room_pins(room_id)
+    }
+
+    async fn room_read_state(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>> {
+        Ok(self
+            .read_states
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .find(|state| state.room_id == *room_id && state.user_id == *user_id)
+            .cloned())
+    }
+
+    async fn mark_room_read(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+        message_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>> {
+        self.mark_read(room_id, user_id, message_id)
+    }
+
+    async fn seed_room_read_states(&self, user_id: &Uuid, room_ids: &[Uuid]) -> anyhow::Result<()> {
+        self.seed_read_states(user_id, room_ids)
+    }
+
+    async fn room_unread_counts(
+        &self,
+        user_id: &Uuid,
+        role_ids: &[Uuid],
+        room_ids: &[Uuid],
+    ) -> anyhow::Result<HashMap<Uuid, RoomUnreadCounts>> {
+        self.unread_counts(user_id, role_ids, room_ids)
+    }
 }
 
 fn poisoned() -> anyhow::Error {
     anyhow!("in-memory text chat store lock poisoned")
 }
+
+/// Возвращает последние `limit` сообщений перед курсором в порядке от старых к новым.
+fn page_before(
+    mut messages: Vec<TextMessage>,
+    before_message_id: Option<&Uuid>,
+    limit: u64,
+) -> Option<TextMessagePage> {
+    messages.sort_by_key(|message| (message.created_at, message.id));
+    if let Some(before_message_id) = before_message_id {
+        let cursor_index = messages
+            .iter()
+            .position(|message| message.id == *before_message_id)?;
+        messages.truncate(cursor_index);
+    }
+    let start = messages
+        .len()
+        .saturating_sub(usize::try_from(limit).unwrap_or(50));
+
+    Some(TextMessagePage {
+        messages: messages.split_off(start),
+        has_more_before: start > 0,
+        has_more_after: before_message_id.is_some(),
+    })
+}
+
+/// Собирает страницу из хвоста `older` и начала `newer`, отсортированных от старых к новым.
+fn history_window(
+    mut older: Vec<TextMessage>,
+    mut newer: Vec<TextMessage>,
+    older_limit: u64,
+    newer_limit: u64,
+) -> TextMessagePage {
+    let start = older
+        .len()
+        .saturating_sub(usize::try_from(older_limit).unwrap_or(50));
+    let newer_limit = usize::try_from(newer_limit).unwrap_or(50);
+    let has_more_after = newer.len() > newer_limit;
+    newer.truncate(newer_limit);
+    let mut messages = older.split_off(start);
+    messages.extend(newer);
+
+    TextMessagePage {
+        messages,
+        has_more_before: start > 0,
+        has_more_after,
+    }
+}
@@ -0,0 +1,40 @@
+//! Закрепленные сообщения in-memory-хранилища текстового чата.
+
+use uuid::Uuid;
+
+use super::{InMemoryTextChatStore, poisoned};
+use crate::features::text_chat::domain::PinnedMessage;
+
+impl InMemoryTextChatStore {
+    pub(super) fn insert_pin(&self, pin: PinnedMessage) -> anyhow::Result<bool> {
+        let mut pins = self.pins.lock().map_err(|_| poisoned())?;
+        if pins.iter().any(|saved| saved.message_id == pin.message_id) {
+            return Ok(false);
+        }
+        pins.push(pin);
+
+        Ok(true)
+    }
+
+    pub(super) fn delete_pin(&self, room_id: &Uuid, message_id: &Uuid) -> anyhow::Result<bool> {
+        let mut pins = self.pins.lock().map_err(|_| poisoned())?;
+        let before = pins.len();
+        pins.retain(|pin| pin.message_id != *message_id || pin.room_id != *room_id);
+
+        Ok(pins.len() != before)
+    }
+
+    pub(super) fn room_pins(&self, room_id: &Uuid) -> anyhow::Result<Vec<PinnedMessage>> {
+        let mut pins = self
+            .pins
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .filter(|pin| pin.room_id == *room_id)
+            .cloned()
+            .collect::<Vec<_>>();
+        pins.sort_by_key(|pin| std::cmp::Reverse((pin.pinned_at, pin.message_id)));
+
+        Ok(pins)
+    }
+}
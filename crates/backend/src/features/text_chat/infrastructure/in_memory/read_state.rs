@@ -0,0 +1,130 @@
+//! Отметки прочтения комнат in-memory-хранилища текстового чата.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use super::{InMemoryTextChatStore, poisoned};
+use crate::features::text_chat::domain::{MentionKind, RoomReadState, RoomUnreadCounts};
+
+impl InMemoryTextChatStore {
+    pub(super) fn mark_read(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+        message_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>> {
+        let Some(position) = self
+            .messages
+            .lock()
+            .map_err(|_| poisoned())?
+            .iter()
+            .find(|message| message.id == *message_id && message.room_id == *room_id)
+            .map(|message| (message.created_at, message.id))
+        else {
+            return Ok(None);
+        };
+        let mut read_states = self.read_states.lock().map_err(|_| poisoned())?;
+        let state = RoomReadState {
+            room_id: *room_id,
+            user_id: *user_id,
+            last_read_message_id: position.1,
+            last_read_message_created_at: position.0,
+            updated_at: Utc::now(),
+        };
+        match read_states
+            .iter_mut()
+            .find(|state| state.room_id == *room_id && state.user_id == *user_id)
+        {
+            Some(current)
+                if position
+                    <= (
+                        current.last_read_message_created_at,
+                        current.last_read_message_id,
+                    ) =>
+            {
+                Ok(Some(current.clone()))
+            }
+            Some(current) => {
+                *current = state.clone();
+                Ok(Some(state))
+            }
+            None => {
+                read_states.push(state.clone());
+                Ok(Some(state))
+            }
+        }
+    }
+
+    pub(super) fn seed_read_states(&self, user_id: &Uuid, room_ids: &[Uuid]) -> anyhow::Result<()> {
+        let messages = self.messages.lock().map_err(|_| poisoned())?;
+        let mut read_states = self.read_states.lock().map_err(|_| poisoned())?;
+        for room_id in room_ids {
+            if read_states
+                .iter()
+                .any(|state| state.room_id == *room_id && state.user_id == *user_id)
+            {
+                continue;
+            }
+            let Some(latest) = messages
+                .iter()
+                .filter(|message| message.room_id == *room_id)
+                .max_by_key(|message| (message.created_at, message.id))
+            else {
+                continue;
+            };
+            read_states.push(RoomReadState {
+                room_id: *room_id,
+                user_id: *user_id,
+                last_read_message_id: latest.id,
+                last_read_message_created_at: latest.created_at,
+                updated_at: Utc::now(),
+            });
+        }
+        Ok(())
+    }
+
+    pub(super) fn unread_counts(
+        &self,
+        user_id: &Uuid,
+        role_ids: &[Uuid],
+        room_ids: &[Uuid],
+    ) -> anyhow::Result<HashMap<Uuid, RoomUnreadCounts>> {
+        let read_states = self.read_states.lock().map_err(|_| poisoned())?.clone();
+        let messages = self.messages.lock().map_err(|_| poisoned())?;
+        let mut counts = room_ids
+            .iter()
+            .map(|room_id| (*room_id, RoomUnreadCounts::default()))
+            .collect::<HashMap<_, _>>();
+        for message in messages
+            .iter()
+            .filter(|message| message.deleted_at.is_none() && message.author_user_id != *user_id)
+        {
+            let Some(room_counts) = counts.get_mut(&message.room_id) else {
+                continue;
+            };
+            let marker = read_states
+                .iter()
+                .find(|state| state.room_id == message.room_id && state.user_id == *user_id)
+                .map(|state| {
+                    (
+                        state.last_read_message_created_at,
+                        state.last_read_message_id,
+                    )
+                });
+            if marker.is_some_and(|marker| (message.created_at, message.id) <= marker) {
+                continue;
+            }
+            room_counts.unread_count += 1;
+            if message.mentions.iter().any(|mention| match mention.kind {
+                MentionKind::User => mention.target_id == *user_id,
+                MentionKind::Role => role_ids.contains(&mention.target_id),
+            }) {
+                room_counts.mention_count += 1;
+            }
+        }
+
+        Ok(counts)
+    }
+}
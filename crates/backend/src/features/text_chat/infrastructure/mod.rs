@@ -4,28 +4,84 @@ mod entities;
 mod in_memory;
 mod object_storage;
 mod postgres;
+mod postgres_history;
+mod postgres_mentions;
+mod postgres_pins;
+mod postgres_read_state;
+mod postgres_search;
+
+use std::collections::HashMap;
 
 use async_trait::async_trait;
 use uuid::Uuid;
 
-use crate::features::text_chat::domain::{ChatAttachment, NewChatAttachment, TextMessage};
+use crate::features::text_chat::domain::{
+    ChatAttachment, MessageMention, MessageReaction, NewChatAttachment, PinnedMessage,
+    RoomReadState, RoomUnreadCounts, TextMessage,
+};
 
 pub(crate) use in_memory::InMemoryTextChatStore;
 #[cfg(test)]
 pub(crate) use object_storage::InMemoryChatAttachmentObjectStore;
 pub(crate) use object_storage::{
     ChatAttachmentObjectStore, DisabledChatAttachmentObjectStore, S3ChatAttachmentObjectStore,
+    connect_s3_client,
 };
 pub(crate) use postgres::PostgresTextChatStore;
 
 const HISTORY_LIMIT: u64 = 50;
+const SEARCH_LIMIT: u64 = 25;
 
 /// Одна страница текстовых сообщений.
 pub(crate) struct TextMessagePage {
     /// Сообщения в порядке от старых к новым.
     pub(crate) messages: Vec<TextMessage>,
     /// Доступны ли более старые сообщения перед этой страницей.
-    pub(crate) has_more: bool,
+    pub(crate) has_more_before: bool,
+    /// Доступны ли более новые сообщения после этой страницы.
+    pub(crate) has_more_after: bool,
+}
+
+/// Положение страницы истории комнаты относительно сообщения-якоря.
+///
+/// Якорем может быть и мягко удаленное сообщение: страница строится по его
+/// положению в истории, но само оно в страницу не попадает.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HistoryAnchor {
+    /// Последние сообщения комнаты.
+    Latest,
+    /// Сообщения строго старше якоря.
+    Before(Uuid),
+    /// Сообщения строго новее якоря.
+    After(Uuid),
+    /// Окно, в середине которого находится якорь.
+    Around(Uuid),
+}
+
+impl HistoryAnchor {
+    /// Возвращает идентификатор сообщения-якоря, если он задан.
+    pub(crate) fn message_id(self) -> Option<Uuid> {
+        match self {
+            Self::Latest => None,
+            Self::Before(message_id) | Self::After(message_id) | Self::Around(message_id) => {
+                Some(message_id)
+            }
+        }
+    }
+
+    /// Сколько сообщений страница берет до и после якоря.
+    fn page_limits(self) -> (u64, u64) {
+        match self {
+            Self::Latest | Self::Before(_) => (HISTORY_LIMIT, 0),
+            Self::After(_) => (0, HISTORY_LIMIT),
+            Self::Around(_) => (HISTORY_LIMIT / 2, HISTORY_LIMIT - HISTORY_LIMIT / 2),
+        }
+    }
+
+    /// Относится ли сам якорь к более старой части страницы.
+    fn anchor_is_older(self) -> bool {
+        !matches!(self, Self::Before(_))
+    }
 }
 
 /// Граница хранилища текстового чата.
@@ -44,12 +100,36 @@ pub(crate) trait TextChatStore: Send + Sync {
     ) -> anyhow::Result<Option<ChatAttachment>>;
 
     /// Загружает одну страницу сообщений комнаты в порядке от старых к новым.
+    ///
+    /// Возвращает ошибку, если якорь не принадлежит комнате `room_id`.
     async fn room_message_page(
         &self,
         room_id: &Uuid,
-        before_message_id: Option<&Uuid>,
+        anchor: HistoryAnchor,
     ) -> anyhow::Result<TextMessagePage>;
 
+    /// Ищет неудаленные сообщения перечисленных комнат по тексту.
+    ///
+    /// Возвращает страницу совпадений в порядке от старых к новым. Курсор
+    /// `before_message_id` должен быть одним из ранее найденных сообщений,
+    /// иначе возвращается `None`.
+    async fn search_room_messages(
+        &self,
+        room_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<Option<TextMessagePage>>;
+
+    /// Находит сообщения комнаты по идентификаторам, включая мягко удаленные.
+    ///
+    /// Используется для проверки и отображения цитируемых сообщений: поиск
+    /// ограничен `room_id`, поэтому сообщения других комнат не возвращаются.
+    async fn find_room_messages(
+        &self,
+        room_id: &Uuid,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<TextMessage>>;
+
     /// Мягко удаляет сообщение, фиксируя, кто его удалил.
     ///
     /// Удаление всегда ограничено сообщением, которое реально принадлежит
@@ -73,4 +153,103 @@ pub(crate) trait TextChatStore: Send + Sync {
         deleted_by_user_id: &Uuid,
         require_authorship: bool,
     ) -> anyhow::Result<Option<TextMessage>>;
+
+    /// Заменяет текст и упоминания сообщения.
+    ///
+    /// Постоянное хранилище сохраняет предыдущий текст в истории изменений
+    /// для аудита; in-memory-хранилище историю не ведет.
+    ///
+    /// Как и [`TextChatStore::soft_delete_message`], изменение ограничено
+    /// сообщением, которое реально принадлежит паре `server_id`/`room_id`.
+    /// Изменять сообщение может только его автор, поэтому `editor_user_id`
+    /// всегда сверяется с автором.
+    ///
+    /// Возвращает `Some(updated_message)` при успехе, `None`, когда сообщение
+    /// не существует, удалено, не принадлежит указанной комнате или
+    /// `editor_user_id` не является автором.
+    async fn edit_message(
+        &self,
+        server_id: &Uuid,
+        room_id: &Uuid,
+        message_id: &Uuid,
+        editor_user_id: &Uuid,
+        body: String,
+        mentions: Vec<MessageMention>,
+    ) -> anyhow::Result<Option<TextMessage>>;
+
+    /// Ставит реакцию пользователя на сообщение.
+    ///
+    /// Возвращает `false`, если такая реакция пользователя уже существует.
+    async fn add_message_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool>;
+
+    /// Снимает реакцию пользователя с сообщения.
+    ///
+    /// Возвращает `false`, если такой реакции не было.
+    async fn remove_message_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool>;
+
+    /// Загружает реакции на сообщения в порядке их установки.
+    async fn message_reactions(&self, message_ids: &[Uuid])
+    -> anyhow::Result<Vec<MessageReaction>>;
+
+    /// Закрепляет сообщение комнаты.
+    ///
+    /// Возвращает `false`, если сообщение уже закреплено.
+    async fn pin_message(&self, pin: PinnedMessage) -> anyhow::Result<bool>;
+
+    /// Открепляет сообщение комнаты.
+    ///
+    /// Возвращает `false`, если сообщение не было закреплено в комнате `room_id`.
+    async fn unpin_message(&self, room_id: &Uuid, message_id: &Uuid) -> anyhow::Result<bool>;
+
+    /// Загружает закрепления комнаты от недавно закрепленных к давно закрепленным.
+    ///
+    /// Закрепления удаленных сообщений тоже возвращаются; отфильтровать их —
+    /// задача вызывающего кода.
+    async fn room_pinned_messages(&self, room_id: &Uuid) -> anyhow::Result<Vec<PinnedMessage>>;
+
+    /// Загружает отметку прочтения комнаты участником.
+    async fn room_read_state(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>>;
+
+    /// Сдвигает отметку прочтения комнаты к сообщению `message_id`.
+    ///
+    /// Отметка только продвигается вперед: если сообщение старше уже
+    /// прочитанного, возвращается текущая отметка без изменений. Возвращает
+    /// `None`, когда сообщение не принадлежит комнате `room_id`.
+    async fn mark_room_read(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+        message_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>>;
+
+    /// Ставит отметку прочтения на последнее сообщение каждой комнаты, где у
+    /// участника отметки еще нет.
+    async fn seed_room_read_states(&self, user_id: &Uuid, room_ids: &[Uuid]) -> anyhow::Result<()>;
+
+    /// Считает непрочитанные сообщения и упоминания в перечисленных комнатах.
+    ///
+    /// Собственные и удаленные сообщения не учитываются. Отметка ставится при
+    /// вступлении на сервер, поэтому без нее непрочитанными считаются все
+    /// сообщения комнаты: они появились уже после вступления. Упоминанием
+    /// считается упоминание `user_id` или любой роли из `role_ids`.
+    async fn room_unread_counts(
+        &self,
+        user_id: &Uuid,
+        role_ids: &[Uuid],
+        room_ids: &[Uuid],
+    ) -> anyhow::Result<HashMap<Uuid, RoomUnreadCounts>>;
 }
@@ -42,32 +42,35 @@ pub(crate) struct S3ChatAttachmentObjectStore {
 impl S3ChatAttachmentObjectStore {
     /// Создает клиент S3-совместимого объектного хранилища.
     pub(crate) async fn from_config(config: &S3Config) -> Self {
-        let credentials = Credentials::new(
-            config.access_key_id.clone(),
-            config.secret_access_key.clone(),
-            None,
-            None,
-            "chat-images-s3",
-        );
-        let sdk_config = aws_config::defaults(BehaviorVersion::latest())
-            .region(Region::new(config.region.clone()))
-            .endpoint_url(config.endpoint.clone())
-            .credentials_provider(credentials)
-            .load()
-            .await;
-        let client = Client::from_conf(
-            S3ConfigBuilder::from(&sdk_config)
-                .force_path_style(config.force_path_style)
-                .build(),
-        );
-
         Self {
-            client,
+            client: connect_s3_client(config, "chat-images-s3").await,
             bucket: config.bucket.clone(),
         }
     }
 }
 
+/// Создает клиент S3-совместимого API с ключами из конфигурации.
+pub(crate) async fn connect_s3_client(config: &S3Config, provider_name: &'static str) -> Client {
+    let credentials = Credentials::new(
+        config.access_key_id.clone(),
+        config.secret_access_key.clone(),
+        None,
+        None,
+        provider_name,
+    );
+    let sdk_config = aws_config::defaults(BehaviorVersion::latest())
+        .region(Region::new(config.region.clone()))
+        .endpoint_url(config.endpoint.clone())
+        .credentials_provider(credentials)
+        .load()
+        .await;
+    Client::from_conf(
+        S3ConfigBuilder::from(&sdk_config)
+            .force_path_style(config.force_path_style)
+            .build(),
+    )
+}
+
 #[async_trait]
 impl ChatAttachmentObjectStore for S3ChatAttachmentObjectStore {
     fn bucket(&self) -> Option<&str> {
@@ -3,15 +3,32 @@
 use async_trait::async_trait;
 use chrono::Utc;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter,
-    QueryOrder, QuerySelect, Set,
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, Set, TransactionTrait, TryInsertResult,
+    sea_query::{LockType, OnConflict},
 };
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::features::text_chat::domain::{ChatAttachment, NewChatAttachment, TextMessage};
-use crate::features::text_chat::infrastructure::entities::{text_chat_attachments, text_messages};
-use crate::features::text_chat::infrastructure::{HISTORY_LIMIT, TextChatStore, TextMessagePage};
+use crate::features::text_chat::domain::{
+    ChatAttachment, ChatAttachmentKind, MessageMention, MessageReaction, NewChatAttachment,
+    PinnedMessage, RoomReadState, RoomUnreadCounts, TextMessage,
+};
+use crate::features::text_chat::infrastructure::entities::{
+    message_reactions, text_chat_attachments, text_message_edits, text_messages,
+};
+use crate::features::text_chat::infrastructure::postgres_history::room_history_page;
+use crate::features::text_chat::infrastructure::postgres_mentions::{
+    hydrate_mentions, insert_mentions, replace_mentions,
+};
+use crate::features::text_chat::infrastructure::postgres_pins::{
+    delete_pin, insert_pin, room_pins,
+};
+use crate::features::text_chat::infrastructure::postgres_read_state::{
+    find_read_state, mark_read, seed_read_states, unread_counts,
+};
+use crate::features::text_chat::infrastructure::postgres_search::search_page;
+use crate::features::text_chat::infrastructure::{HistoryAnchor, TextChatStore, TextMessagePage};
 
 /// Postgres-backed text chat storage.
 pub(crate) struct PostgresTextChatStore {
@@ -38,9 +55,12 @@ impl TextChatStore for PostgresTextChatStore {
             created_at: Set(message.created_at),
             deleted_at: Set(None),
             deleted_by_user_id: Set(None),
+            edited_at: Set(None),
+            reply_to_message_id: Set(message.reply_to_message_id),
         }
         .insert(&self.database)
         .await?;
+        insert_mentions(&self.database, message.id, &message.mentions).await?;
 
         let attachment_ids = message
             .attachments
@@ -70,6 +90,7 @@ impl TextChatStore for PostgresTextChatStore {
             bucket: Set(attachment.bucket),
             message_id: Set(attachment.message_id),
             object_key: Set(attachment.object_key),
+            kind: Set(attachment.kind.as_str().to_owned()),
             content_type: Set(attachment.content_type),
             byte_size: Set(attachment.byte_size),
             width: Set(attachment.width),
@@ -97,55 +118,61 @@ impl TextChatStore for PostgresTextChatStore {
     async fn room_message_page(
         &self,
         room_id: &Uuid,
-        before_message_id: Option<&Uuid>,
+        anchor: HistoryAnchor,
     ) -> anyhow::Result<TextMessagePage> {
-        let before_message = match before_message_id {
-            Some(message_id) => Some(
-                text_messages::Entity::find()
-                    .filter(text_messages::Column::RoomId.eq(*room_id))
-                    .filter(text_messages::Column::Id.eq(*message_id))
-                    .one(&self.database)
-                    .await?
-                    .ok_or_else(|| anyhow::anyhow!("message history cursor was not found"))?,
-            ),
-            None => None,
+        let mut page = room_history_page(&self.database, room_id, anchor).await?;
+        hydrate_attachments(&self.database, &mut page.messages).await?;
+        hydrate_mentions(&self.database, &mut page.messages).await?;
+
+        Ok(page)
+    }
+
+    async fn search_room_messages(
+        &self,
+        room_ids: &[Uuid],
+        query: &str,
+        before_message_id: Option<&Uuid>,
+    ) -> anyhow::Result<Option<TextMessagePage>> {
+        if room_ids.is_empty() {
+            return Ok(Some(TextMessagePage {
+                messages: Vec::new(),
+                has_more_before: false,
+                has_more_after: false,
+            }));
+        }
+
+        let Some(mut page) =
+            search_page(&self.database, room_ids, query, before_message_id).await?
+        else {
+            return Ok(None);
         };
-        let mut filter = Condition::all()
-            .add(text_messages::Column::RoomId.eq(*room_id))
-            .add(text_messages::Column::DeletedAt.is_null());
-
-        if let Some(message) = before_message {
-            filter = filter.add(
-                Condition::any()
-                    .add(text_messages::Column::CreatedAt.lt(message.created_at))
-                    .add(
-                        Condition::all()
-                            .add(text_messages::Column::CreatedAt.eq(message.created_at))
-                            .add(text_messages::Column::Id.lt(message.id)),
-                    ),
-            );
+        hydrate_attachments(&self.database, &mut page.messages).await?;
+        hydrate_mentions(&self.database, &mut page.messages).await?;
+
+        Ok(Some(page))
+    }
+
+    async fn find_room_messages(
+        &self,
+        room_id: &Uuid,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<TextMessage>> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
         }
 
         let mut messages = text_messages::Entity::find()
-            .filter(filter)
-            .order_by_desc(text_messages::Column::CreatedAt)
-            .order_by_desc(text_messages::Column::Id)
-            .limit(HISTORY_LIMIT + 1)
+            .filter(text_messages::Column::RoomId.eq(*room_id))
+            .filter(text_messages::Column::Id.is_in(message_ids.iter().copied()))
             .all(&self.database)
             .await?
             .into_iter()
             .map(Into::into)
             .collect::<Vec<_>>();
-        let has_more = messages.len() > usize::try_from(HISTORY_LIMIT).unwrap_or(50);
-
-        if has_more {
-            messages.truncate(usize::try_from(HISTORY_LIMIT).unwrap_or(50));
-        }
-
-        messages.sort_by_key(|message: &TextMessage| (message.created_at, message.id));
         hydrate_attachments(&self.database, &mut messages).await?;
+        hydrate_mentions(&self.database, &mut messages).await?;
 
-        Ok(TextMessagePage { messages, has_more })
+        Ok(messages)
     }
 
     async fn soft_delete_message(
@@ -180,6 +207,168 @@ impl TextChatStore for PostgresTextChatStore {
 
         Ok(Some(updated.into()))
     }
+
+    async fn edit_message(
+        &self,
+        server_id: &Uuid,
+        room_id: &Uuid,
+        message_id: &Uuid,
+        editor_user_id: &Uuid,
+        body: String,
+        mentions: Vec<MessageMention>,
+    ) -> anyhow::Result<Option<TextMessage>> {
+        // Та же привязка к server_id/room_id, что и при удалении: проверка доступа
+        // выполняется для запрошенной комнаты, а авторство сверяется в самом запросе.
+        let transaction = self.database.begin().await?;
+        let Some(row) = text_messages::Entity::find()
+            .filter(text_messages::Column::Id.eq(*message_id))
+            .filter(text_messages::Column::ServerId.eq(*server_id))
+            .filter(text_messages::Column::RoomId.eq(*room_id))
+            .filter(text_messages::Column::AuthorUserId.eq(*editor_user_id))
+            .filter(text_messages::Column::DeletedAt.is_null())
+            .lock(LockType::Update)
+            .one(&transaction)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let edited_at = Utc::now();
+        text_message_edits::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            message_id: Set(row.id),
+            editor_user_id: Set(*editor_user_id),
+            previous_body: Set(row.body.clone()),
+            edited_at: Set(edited_at),
+        }
+        .insert(&transaction)
+        .await?;
+        let mut active: text_messages::ActiveModel = row.into();
+        active.body = Set(body);
+        active.edited_at = Set(Some(edited_at));
+        let updated = active.update(&transaction).await?;
+        replace_mentions(&transaction, updated.id, &mentions).await?;
+        transaction.commit().await?;
+
+        let mut messages = vec![TextMessage::from(updated)];
+        hydrate_attachments(&self.database, &mut messages).await?;
+        hydrate_mentions(&self.database, &mut messages).await?;
+
+        Ok(messages.pop())
+    }
+
+    async fn add_message_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        let result = message_reactions::Entity::insert(message_reactions::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            text_message_id: Set(Some(*message_id)),
+            dm_message_id: Set(None),
+            user_id: Set(*user_id),
+            emoji: Set(emoji.to_owned()),
+            created_at: Set(Utc::now()),
+        })
+        .on_conflict(
+            OnConflict::columns([
+                message_reactions::Column::TextMessageId,
+                message_reactions::Column::UserId,
+                message_reactions::Column::Emoji,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .do_nothing()
+        .exec(&self.database)
+        .await?;
+
+        Ok(matches!(result, TryInsertResult::Inserted(_)))
+    }
+
+    async fn remove_message_reaction(
+        &self,
+        message_id: &Uuid,
+        user_id: &Uuid,
+        emoji: &str,
+    ) -> anyhow::Result<bool> {
+        let result = message_reactions::Entity::delete_many()
+            .filter(message_reactions::Column::TextMessageId.eq(*message_id))
+            .filter(message_reactions::Column::UserId.eq(*user_id))
+            .filter(message_reactions::Column::Emoji.eq(emoji))
+            .exec(&self.database)
+            .await?;
+
+        Ok(result.rows_affected > 0)
+    }
+
+    async fn message_reactions(
+        &self,
+        message_ids: &[Uuid],
+    ) -> anyhow::Result<Vec<MessageReaction>> {
+        if message_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        Ok(message_reactions::Entity::find()
+            .filter(message_reactions::Column::TextMessageId.is_in(message_ids.iter().copied()))
+            .order_by_asc(message_reactions::Column::CreatedAt)
+            .all(&self.database)
+            .await?
+            .into_iter()
+            .filter_map(|row| {
+                Some(MessageReaction {
+                    message_id: row.text_message_id?,
+                    user_id: row.user_id,
+                    emoji: row.emoji,
+                    created_at: row.created_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn pin_message(&self, pin: PinnedMessage) -> anyhow::Result<bool> {
+        insert_pin(&self.database, pin).await
+    }
+
+    async fn unpin_message(&self, room_id: &Uuid, message_id: &Uuid) -> anyhow::Result<bool> {
+        delete_pin(&self.database, room_id, message_id).await
+    }
+
+    async fn room_pinned_messages(&self, room_id: &Uuid) -> anyhow::Result<Vec<PinnedMessage>> {
+        room_pins(&self.database, room_id).await
+    }
+
+    async fn room_read_state(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>> {
+        find_read_state(&self.database, room_id, user_id).await
+    }
+
+    async fn mark_room_read(
+        &self,
+        room_id: &Uuid,
+        user_id: &Uuid,
+        message_id: &Uuid,
+    ) -> anyhow::Result<Option<RoomReadState>> {
+        mark_read(&self.database, room_id, user_id, message_id).await
+    }
+
+    async fn seed_room_read_states(&self, user_id: &Uuid, room_ids: &[Uuid]) -> anyhow::Result<()> {
+        seed_read_states(&self.database, user_id, room_ids).await
+    }
+
+    async fn room_unread_counts(
+        &self,
+        user_id: &Uuid,
+        role_ids: &[Uuid],
+        room_ids: &[Uuid],
+    ) -> anyhow::Result<HashMap<Uuid, RoomUnreadCounts>> {
+        unread_counts(&self.database, user_id, role_ids, room_ids).await
+    }
 }
 
 impl From<text_messages::Model> for TextMessage {
@@ -195,6 +384,9 @@ impl From<text_messages::Model> for TextMessage {
             created_at: row.created_at,
             deleted_at: row.deleted_at,
             deleted_by_user_id: row.deleted_by_user_id,
+            edited_at: row.edited_at,
+            reply_to_message_id: row.reply_to_message_id,
+            mentions: Vec::new(),
         }
     }
 }
@@ -209,6 +401,8 @@ impl From<text_chat_attachments::Model> for ChatAttachment {
             message_id: row.message_id,
             bucket: row.bucket,
             object_key: row.object_key,
+            // Неизвестный вид безопаснее всего отдавать как обычный файл.
+            kind: ChatAttachmentKind::from_str(&row.kind).unwrap_or(ChatAttachmentKind::File),
             content_type: row.content_type,
             byte_size: row.byte_size,
             width: row.width,
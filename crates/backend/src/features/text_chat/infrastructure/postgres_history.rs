@@ -0,0 +1,126 @@
+//! Keyset pagination of room history for the Postgres text chat store.
+
+use chrono::{DateTime, Utc};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, Order, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use uuid::Uuid;
+
+use crate::features::text_chat::domain::TextMessage;
+use crate::features::text_chat::infrastructure::entities::text_messages;
+use crate::features::text_chat::infrastructure::{HistoryAnchor, TextMessagePage};
+
+/// Loads one room history page without attachments and mentions.
+///
+/// The anchor is looked up among all room messages, including soft-deleted
+/// ones, so a page can still be positioned around a removed message.
+pub(super) async fn room_history_page(
+    database: &DatabaseConnection,
+    room_id: &Uuid,
+    anchor: HistoryAnchor,
+) -> anyhow::Result<TextMessagePage> {
+    let visible = Condition::all()
+        .add(text_messages::Column::RoomId.eq(*room_id))
+        .add(text_messages::Column::DeletedAt.is_null());
+    let (older_limit, newer_limit) = anchor.page_limits();
+    let Some(message_id) = anchor.message_id() else {
+        let (messages, has_more_before) =
+            page_side(database, visible, Order::Desc, older_limit).await?;
+        return Ok(TextMessagePage {
+            messages,
+            has_more_before,
+            has_more_after: false,
+        });
+    };
+
+    let cursor = text_messages::Entity::find()
+        .filter(text_messages::Column::RoomId.eq(*room_id))
+        .filter(text_messages::Column::Id.eq(message_id))
+        .one(database)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("message history cursor was not found"))?;
+    let (older, newer) = if anchor.anchor_is_older() {
+        (
+            older_than(&cursor).add(text_messages::Column::Id.eq(cursor.id)),
+            newer_than(&cursor),
+        )
+    } else {
+        (
+            older_than(&cursor),
+            newer_than(&cursor).add(text_messages::Column::Id.eq(cursor.id)),
+        )
+    };
+
+    let (mut messages, has_more_before) = page_side(
+        database,
+        visible.clone().add(older),
+        Order::Desc,
+        older_limit,
+    )
+    .await?;
+    let (newer_messages, has_more_after) =
+        page_side(database, visible.add(newer), Order::Asc, newer_limit).await?;
+    messages.extend(newer_messages);
+
+    Ok(TextMessagePage {
+        messages,
+        has_more_before,
+        has_more_after,
+    })
+}
+
+/// Builds the keyset condition for messages strictly older than the cursor.
+pub(super) fn older_than(message: &text_messages::Model) -> Condition {
+    Condition::any()
+        .add(text_messages::Column::CreatedAt.lt(message.created_at))
+        .add(
+            Condition::all()
+                .add(text_messages::Column::CreatedAt.eq(message.created_at))
+                .add(text_messages::Column::Id.lt(message.id)),
+        )
+}
+
+/// Builds the keyset condition for messages strictly newer than the cursor.
+fn newer_than(message: &text_messages::Model) -> Condition {
+    newer_than_position(message.created_at, message.id)
+}
+
+/// Builds the keyset condition for messages strictly newer than a history position.
+pub(super) fn newer_than_position(created_at: DateTime<Utc>, message_id: Uuid) -> Condition {
+    Condition::any()
+        .add(text_messages::Column::CreatedAt.gt(created_at))
+        .add(
+            Condition::all()
+                .add(text_messages::Column::CreatedAt.eq(created_at))
+                .add(text_messages::Column::Id.gt(message_id)),
+        )
+}
+
+/// Fetches up to `limit` messages walking away from the cursor in `order`.
+///
+/// Returns them oldest to newest together with whether more messages remain
+/// further in the same direction. A zero limit only checks for more messages.
+async fn page_side(
+    database: &DatabaseConnection,
+    filter: Condition,
+    order: Order,
+    limit: u64,
+) -> anyhow::Result<(Vec<TextMessage>, bool)> {
+    let mut messages = text_messages::Entity::find()
+        .filter(filter)
+        .order_by(text_messages::Column::CreatedAt, order.clone())
+        .order_by(text_messages::Column::Id, order)
+        .limit(limit + 1)
+        .all(database)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<TextMessage>>();
+    let limit = usize::try_from(limit).unwrap_or(50);
+    let has_more = messages.len() > limit;
+    messages.truncate(limit);
+    messages.sort_by_key(|message| (message.created_at, message.id));
+
+    Ok((messages, has_more))
+}
@@ -0,0 +1,111 @@
+//! Postgres storage of resolved message mentions.
+
+use std::collections::HashMap;
+
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, Set};
+use uuid::Uuid;
+
+use crate::features::text_chat::domain::{MentionKind, MessageMention, TextMessage};
+use crate::features::text_chat::infrastructure::entities::text_message_mentions;
+
+/// Replaces the stored mention targets of one message.
+pub(super) async fn replace_mentions<C>(
+    database: &C,
+    message_id: Uuid,
+    mentions: &[MessageMention],
+) -> anyhow::Result<()>
+where
+    C: ConnectionTrait,
+{
+    text_message_mentions::Entity::delete_many()
+        .filter(text_message_mentions::Column::MessageId.eq(message_id))
+        .exec(database)
+        .await?;
+
+    insert_mentions(database, message_id, mentions).await
+}
+
+/// Stores mention targets of a newly inserted message.
+pub(super) async fn insert_mentions<C>(
+    database: &C,
+    message_id: Uuid,
+    mentions: &[MessageMention],
+) -> anyhow::Result<()>
+where
+    C: ConnectionTrait,
+{
+    if mentions.is_empty() {
+        return Ok(());
+    }
+
+    text_message_mentions::Entity::insert_many(mentions.iter().map(|mention| {
+        text_message_mentions::ActiveModel {
+            id: Set(Uuid::new_v4()),
+            message_id: Set(message_id),
+            kind: Set(kind_value(mention.kind).to_owned()),
+            target_id: Set(mention.target_id),
+            name: Set(mention.name.clone()),
+        }
+    }))
+    .exec(database)
+    .await?;
+
+    Ok(())
+}
+
+/// Loads mention targets for already fetched messages.
+pub(super) async fn hydrate_mentions<C>(
+    database: &C,
+    messages: &mut [TextMessage],
+) -> anyhow::Result<()>
+where
+    C: ConnectionTrait,
+{
+    let message_ids = messages
+        .iter()
+        .map(|message| message.id)
+        .collect::<Vec<_>>();
+    if message_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut by_message_id: HashMap<Uuid, Vec<MessageMention>> = HashMap::new();
+    for row in text_message_mentions::Entity::find()
+        .filter(text_message_mentions::Column::MessageId.is_in(message_ids))
+        .all(database)
+        .await?
+    {
+        let Some(kind) = parse_kind(&row.kind) else {
+            continue;
+        };
+        by_message_id
+            .entry(row.message_id)
+            .or_default()
+            .push(MessageMention {
+                kind,
+                target_id: row.target_id,
+                name: row.name,
+            });
+    }
+
+    for message in messages {
+        message.mentions = by_message_id.remove(&message.id).unwrap_or_default();
+    }
+
+    Ok(())
+}
+
+pub(super) fn kind_value(kind: MentionKind) -> &'static str {
+    match kind {
+        MentionKind::User => "user",
+        MentionKind::Role => "role",
+    }
+}
+
+fn parse_kind(value: &str) -> Option<MentionKind> {
+    match value {
+        "user" => Some(MentionKind::User),
+        "role" => Some(MentionKind::Role),
+        _ => None,
+    }
+}
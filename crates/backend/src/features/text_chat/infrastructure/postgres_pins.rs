@@ -0,0 +1,75 @@
+//! Pinned messages of server text rooms in Postgres.
+
+use sea_orm::{
+    ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set, TryInsertResult,
+    sea_query::OnConflict,
+};
+use uuid::Uuid;
+
+use crate::features::text_chat::domain::PinnedMessage;
+use crate::features::text_chat::infrastructure::entities::text_message_pins;
+
+/// Pins a message unless it is already pinned.
+pub(super) async fn insert_pin(
+    database: &DatabaseConnection,
+    pin: PinnedMessage,
+) -> anyhow::Result<bool> {
+    let result = text_message_pins::Entity::insert(text_message_pins::ActiveModel {
+        message_id: Set(pin.message_id),
+        room_id: Set(pin.room_id),
+        pinned_by_user_id: Set(pin.pinned_by_user_id),
+        pinned_at: Set(pin.pinned_at),
+    })
+    .on_conflict(
+        OnConflict::column(text_message_pins::Column::MessageId)
+            .do_nothing()
+            .to_owned(),
+    )
+    .do_nothing()
+    .exec(database)
+    .await?;
+
+    Ok(matches!(result, TryInsertResult::Inserted(_)))
+}
+
+/// Removes the pin of a message in the room.
+pub(super) async fn delete_pin(
+    database: &DatabaseConnection,
+    room_id: &Uuid,
+    message_id: &Uuid,
+) -> anyhow::Result<bool> {
+    let result = text_message_pins::Entity::delete_many()
+        .filter(text_message_pins::Column::MessageId.eq(*message_id))
+        .filter(text_message_pins::Column::RoomId.eq(*room_id))
+        .exec(database)
+        .await?;
+
+    Ok(result.rows_affected > 0)
+}
+
+/// Loads pins of a room, most recently pinned first.
+pub(super) async fn room_pins(
+    database: &DatabaseConnection,
+    room_id: &Uuid,
+) -> anyhow::Result<Vec<PinnedMessage>> {
+    Ok(text_message_pins::Entity::find()
+        .filter(text_message_pins::Column::RoomId.eq(*room_id))
+        .order_by_desc(text_message_pins::Column::PinnedAt)
+        .order_by_desc(text_message_pins::Column::MessageId)
+        .all(database)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+impl From<text_message_pins::Model> for PinnedMessage {
+    fn from(row: text_message_pins::Model) -> Self {
+        Self {
+            message_id: row.message_id,
+            room_id: row.room_id,
+            pinned_by_user_id: row.pinned_by_user_id,
+            pinned_at: row.pinned_at,
+        }
+    }
+}
@@ -0,0 +1,244 @@
+//! Read markers and unread counts of server text rooms in Postgres.
+
+use std::collections::HashMap;
+
+use chrono::Utc;
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    Set,
+    sea_query::{Expr, OnConflict, Query},
+};
+use uuid::Uuid;
+
+use crate::features::text_chat::domain::{MentionKind, RoomReadState, RoomUnreadCounts};
+use crate::features::text_chat::infrastructure::entities::{
+    text_message_mentions, text_messages, text_room_read_states,
+};
+use crate::features::text_chat::infrastructure::postgres_history::newer_than_position;
+use crate::features::text_chat::infrastructure::postgres_mentions::kind_value;
+
+/// Loads the read marker of one member in one room.
+pub(super) async fn find_read_state(
+    database: &DatabaseConnection,
+    room_id: &Uuid,
+    user_id: &Uuid,
+) -> anyhow::Result<Option<RoomReadState>> {
+    Ok(
+        text_room_read_states::Entity::find_by_id((*room_id, *user_id))
+            .one(database)
+            .await?
+            .map(Into::into),
+    )
+}
+
+/// Moves the read marker forward to `message_id` when it is newer.
+///
+/// Returns `None` when the message does not belong to the room.
+pub(super) async fn mark_read(
+    database: &DatabaseConnection,
+    room_id: &Uuid,
+    user_id: &Uuid,
+    message_id: &Uuid,
+) -> anyhow::Result<Option<RoomReadState>> {
+    let Some(message) = text_messages::Entity::find_by_id(*message_id)
+        .filter(text_messages::Column::RoomId.eq(*room_id))
+        .one(database)
+        .await?
+    else {
+        return Ok(None);
+    };
+    if let Some(current) = find_read_state(database, room_id, user_id).await?
+        && (message.created_at, message.id)
+            <= (
+                current.last_read_message_created_at,
+                current.last_read_message_id,
+            )
+    {
+        return Ok(Some(current));
+    }
+
+    // The marker only moves forward, so a concurrent request that already stored
+    // a newer message must win over this one.
+    let newer_marker = Condition::any()
+        .add(text_room_read_states::Column::LastReadMessageCreatedAt.lt(message.created_at))
+        .add(
+            Condition::all()
+                .add(text_room_read_states::Column::LastReadMessageCreatedAt.eq(message.created_at))
+                .add(text_room_read_states::Column::LastReadMessageId.lt(message.id)),
+        );
+    text_room_read_states::Entity::insert(text_room_read_states::ActiveModel {
+        room_id: Set(*room_id),
+        user_id: Set(*user_id),
+        last_read_message_id: Set(message.id),
+        last_read_message_created_at: Set(message.created_at),
+        updated_at: Set(Utc::now()),
+    })
+    .on_conflict(
+        OnConflict::columns([
+            text_room_read_states::Column::RoomId,
+            text_room_read_states::Column::UserId,
+        ])
+        .update_columns([
+            text_room_read_states::Column::LastReadMessageId,
+            text_room_read_states::Column::LastReadMessageCreatedAt,
+            text_room_read_states::Column::UpdatedAt,
+        ])
+        .action_cond_where(newer_marker)
+        .to_owned(),
+    )
+    .do_nothing()
+    .exec(database)
+    .await?;
+
+    find_read_state(database, room_id, user_id).await
+}
+
+/// Marks the latest message of each listed room as read for a member without a marker.
+pub(super) async fn seed_read_states(
+    database: &DatabaseConnection,
+    user_id: &Uuid,
+    room_ids: &[Uuid],
+) -> anyhow::Result<()> {
+    if room_ids.is_empty() {
+        return Ok(());
+    }
+    let latest = text_messages::Entity::find()
+        .filter(text_messages::Column::RoomId.is_in(room_ids.iter().copied()))
+        .distinct_on([text_messages::Column::RoomId])
+        .order_by_asc(text_messages::Column::RoomId)
+        .order_by_desc(text_messages::Column::CreatedAt)
+        .order_by_desc(text_messages::Column::Id)
+        .all(database)
+        .await?;
+    if latest.is_empty() {
+        return Ok(());
+    }
+
+    let now = Utc::now();
+    text_room_read_states::Entity::insert_many(latest.into_iter().map(|message| {
+        text_room_read_states::ActiveModel {
+            room_id: Set(message.room_id),
+            user_id: Set(*user_id),
+            last_read_message_id: Set(message.id),
+            last_read_message_created_at: Set(message.created_at),
+            updated_at: Set(now),
+        }
+    }))
+    .on_conflict(
+        OnConflict::columns([
+            text_room_read_states::Column::RoomId,
+            text_room_read_states::Column::UserId,
+        ])
+        .do_nothing()
+        .to_owned(),
+    )
+    .do_nothing()
+    .exec(database)
+    .await?;
+    Ok(())
+}
+
+/// Counts unread messages and mentions of a member in each listed room.
+pub(super) async fn unread_counts(
+    database: &DatabaseConnection,
+    user_id: &Uuid,
+    role_ids: &[Uuid],
+    room_ids: &[Uuid],
+) -> anyhow::Result<HashMap<Uuid, RoomUnreadCounts>> {
+    if room_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let markers = text_room_read_states::Entity::find()
+        .filter(text_room_read_states::Column::UserId.eq(*user_id))
+        .filter(text_room_read_states::Column::RoomId.is_in(room_ids.iter().copied()))
+        .all(database)
+        .await?
+        .into_iter()
+        .map(|row| (row.room_id, row))
+        .collect::<HashMap<_, _>>();
+    let mentioned = Query::select()
+        .column(text_message_mentions::Column::MessageId)
+        .from(text_message_mentions::Entity)
+        .cond_where(
+            Condition::any()
+                .add(
+                    Condition::all()
+                        .add(text_message_mentions::Column::Kind.eq(kind_value(MentionKind::User)))
+                        .add(text_message_mentions::Column::TargetId.eq(*user_id)),
+                )
+                .add(
+                    Condition::all()
+                        .add(text_message_mentions::Column::Kind.eq(kind_value(MentionKind::Role)))
+                        .add(
+                            text_message_mentions::Column::TargetId.is_in(role_ids.iter().copied()),
+                        ),
+                ),
+        )
+        .to_owned();
+
+    // Rooms without a marker count every message, the others only messages past it.
+    let (marked, unmarked): (Vec<Uuid>, Vec<Uuid>) = room_ids
+        .iter()
+        .copied()
+        .partition(|room_id| markers.contains_key(room_id));
+    let mut unread_rooms =
+        Condition::any().add(text_messages::Column::RoomId.is_in(unmarked.iter().copied()));
+    for room_id in marked {
+        let marker = &markers[&room_id];
+        unread_rooms = unread_rooms.add(
+            Condition::all()
+                .add(text_messages::Column::RoomId.eq(room_id))
+                .add(newer_than_position(
+                    marker.last_read_message_created_at,
+                    marker.last_read_message_id,
+                )),
+        );
+    }
+    let rows: Vec<(Uuid, i64, i64)> = text_messages::Entity::find()
+        .select_only()
+        .column(text_messages::Column::RoomId)
+        .column_as(Expr::col(text_messages::Column::Id).count(), "unread_count")
+        .column_as(
+            Expr::expr(Expr::case(
+                text_messages::Column::Id.in_subquery(mentioned),
+                Expr::col(text_messages::Column::Id),
+            ))
+            .count(),
+            "mention_count",
+        )
+        .filter(text_messages::Column::DeletedAt.is_null())
+        .filter(text_messages::Column::AuthorUserId.ne(*user_id))
+        .filter(unread_rooms)
+        .group_by(text_messages::Column::RoomId)
+        .into_tuple()
+        .all(database)
+        .await?;
+
+    let mut counts = room_ids
+        .iter()
+        .map(|room_id| (*room_id, RoomUnreadCounts::default()))
+        .collect::<HashMap<_, _>>();
+    for (room_id, unread_count, mention_count) in rows {
+        counts.insert(
+            room_id,
+            RoomUnreadCounts {
+                unread_count: u64::try_from(unread_count).unwrap_or_default(),
+                mention_count: u64::try_from(mention_count).unwrap_or_default(),
+            },
+        );
+    }
+
+    Ok(counts)
+}
+
+impl From<text_room_read_states::Model> for RoomReadState {
+    fn from(row: text_room_read_states::Model) -> Self {
+        Self {
+            room_id: row.room_id,
+            user_id: row.user_id,
+            last_read_message_id: row.last_read_message_id,
+            last_read_message_created_at: row.last_read_message_created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Full-text search over room messages for the Postgres text chat store.
+
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, QuerySelect,
+    Select, sea_query::Expr,
+};
+use uuid::Uuid;
+
+use crate::features::text_chat::domain::TextMessage;
+use crate::features::text_chat::infrastructure::entities::text_messages;
+use crate::features::text_chat::infrastructure::postgres_history::older_than;
+use crate::features::text_chat::infrastructure::{SEARCH_LIMIT, TextMessagePage};
+
+/// Loads one page of search matches without attachments and mentions.
+///
+/// Returns `None` when the cursor is not itself a match, mirroring the
+/// in-memory store: a page can only continue from a previously returned result.
+pub(super) async fn search_page(
+    database: &DatabaseConnection,
+    room_ids: &[Uuid],
+    query: &str,
+    before_message_id: Option<&Uuid>,
+) -> anyhow::Result<Option<TextMessagePage>> {
+    let mut filter = search_match(room_ids, query);
+    if let Some(message_id) = before_message_id {
+        let Some(message) = search_cursor(room_ids, query, message_id)
+            .one(database)
+            .await?
+        else {
+            return Ok(None);
+        };
+        filter = filter.add(older_than(&message));
+    }
+
+    let mut messages = text_messages::Entity::find()
+        .filter(filter)
+        .order_by_desc(text_messages::Column::CreatedAt)
+        .order_by_desc(text_messages::Column::Id)
+        .limit(SEARCH_LIMIT + 1)
+        .all(database)
+        .await?
+        .into_iter()
+        .map(Into::into)
+        .collect::<Vec<_>>();
+    let has_more = messages.len() > usize::try_from(SEARCH_LIMIT).unwrap_or(25);
+
+    if has_more {
+        messages.truncate(usize::try_from(SEARCH_LIMIT).unwrap_or(25));
+    }
+
+    messages.sort_by_key(|message: &TextMessage| (message.created_at, message.id));
+
+    Ok(Some(TextMessagePage {
+        messages,
+        has_more_before: has_more,
+        has_more_after: before_message_id.is_some(),
+    }))
+}
+
+/// Selects the cursor message only if it matches the same search.
+fn search_cursor(
+    room_ids: &[Uuid],
+    query: &str,
+    message_id: &Uuid,
+) -> Select<text_messages::Entity> {
+    text_messages::Entity::find()
+        .filter(search_match(room_ids, query))
+        .filter(text_messages::Column::Id.eq(*message_id))
+}
+
+/// Builds the condition for visible room messages matching the query.
+fn search_match(room_ids: &[Uuid], query: &str) -> Condition {
+    // Выражение должно в точности совпадать с индексом `idx_text_messages_body_search`.
+    Condition::all()
+        .add(text_messages::Column::RoomId.is_in(room_ids.iter().copied()))
+        .add(text_messages::Column::DeletedAt.is_null())
+        .add(Expr::cust_with_values(
+            "to_tsvector('simple', body) @@ plainto_tsquery('simple', $1)",
+            [query],
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use sea_orm::{DbBackend, QueryTrait};
+    use uuid::Uuid;
+
+    use super::search_cursor;
+
+    #[test]
+    fn cursor_must_be_a_visible_search_match() {
+        let (room_id, message_id) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let sql = search_cursor(&[room_id], "релиз", &message_id)
+            .build(DbBackend::Postgres)
+            .to_string();
+
+        assert!(sql.contains(r#""text_messages"."deleted_at" IS NULL"#));
+        assert!(sql.contains("to_tsvector('simple', body) @@ plainto_tsquery('simple', 'релиз')"));
+        assert!(sql.contains(&format!(r#""text_messages"."id" = '{message_id}'"#)));
+    }
+}
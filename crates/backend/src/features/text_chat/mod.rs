@@ -5,4 +5,34 @@ pub(crate) mod domain;
 pub(crate) mod infrastructure;
 pub(crate) mod policy;
 pub(crate) mod realtime;
+mod transport;
 pub(crate) mod validation;
+
+use axum::{
+    Router,
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+};
+
+use crate::state::AppState;
+
+pub(crate) use transport::attachment_response;
+
+/// Собирает REST-маршруты текстового чата, вложенные в маршруты серверов.
+pub(crate) fn server_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{server_id}/messages/search",
+            get(transport::search_messages),
+        )
+        .route(
+            "/{server_id}/rooms/{room_id}/attachments",
+            post(transport::upload_attachment).layer(DefaultBodyLimit::max(
+                application::MAX_ATTACHMENT_UPLOAD_BYTES,
+            )),
+        )
+        .route(
+            "/{server_id}/attachments/{attachment_id}",
+            get(transport::attachment),
+        )
+}
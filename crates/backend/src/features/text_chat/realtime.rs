@@ -3,8 +3,10 @@
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use cheenhub_contracts::realtime::{
-    ChatImageLoadedResponse, DeleteMessage, LoadChatImage, LoadRoomHistory, RealtimeEnvelope,
-    RealtimeKind, RealtimeModule, RejectionCode, SendMessage, TextChatKind, UploadChatImage,
+    AddReaction, ChatImageLoadedResponse, DeleteMessage, EditMessage, ListPinnedMessages,
+    LoadChatImage, LoadRoomHistory, MarkRoomRead, PinMessage, RealtimeEnvelope, RealtimeKind,
+    RealtimeModule, RejectionCode, RemoveReaction, SendMessage, TextChatKind, UnpinMessage,
+    UploadChatAttachment,
 };
 use cheenhub_contracts::rest::AuthUser;
 use uuid::Uuid;
@@ -61,7 +63,7 @@ pub(crate) async fn handle(
         }
         RealtimeKind::TextChat(TextChatKind::UploadImage) => {
             let request_id = require_request_id(&envelope)?;
-            let payload: UploadChatImage = decode_payload(&envelope)?;
+            let payload: UploadChatAttachment = decode_payload(&envelope)?;
             let bytes = match BASE64.decode(payload.data_base64.as_bytes()) {
                 Ok(bytes) => bytes,
                 Err(error) => {
@@ -69,13 +71,13 @@ pub(crate) async fn handle(
                         request_id = %request_id,
                         user_id = %user_id,
                         %error,
-                        "rejected malformed text chat image upload payload"
+                        "rejected malformed text chat attachment upload payload"
                     );
                     return send_rejection(
                         send,
                         Some(request_id),
                         RejectionCode::BadRequest,
-                        "Не удалось прочитать изображение.",
+                        "Не удалось прочитать файл.",
                     )
                     .await;
                 }
@@ -84,9 +86,9 @@ pub(crate) async fn handle(
                 request_id = %request_id,
                 user_id = %user_id,
                 input_bytes = bytes.len(),
-                "received text chat image upload over realtime"
+                "received text chat attachment upload over realtime"
             );
-            match application::upload_chat_image(
+            match application::upload_chat_attachment(
                 state,
                 user_id,
                 payload.server_id,
@@ -154,6 +156,125 @@ pub(crate) async fn handle(
                 Err(error) => reject_application_error(send, Some(request_id), error).await,
             }
         }
+        RealtimeKind::TextChat(TextChatKind::EditMessage) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: EditMessage = decode_payload(&envelope)?;
+            match application::edit_message(state, user, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::EditMessageAccepted),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::TextChat(TextChatKind::AddReaction) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: AddReaction = decode_payload(&envelope)?;
+            match application::add_reaction(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::ReactionsChanged),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::TextChat(TextChatKind::RemoveReaction) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: RemoveReaction = decode_payload(&envelope)?;
+            match application::remove_reaction(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::ReactionsChanged),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::TextChat(TextChatKind::MarkRoomRead) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: MarkRoomRead = decode_payload(&envelope)?;
+            match application::mark_room_read(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::RoomReadUpdated),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::TextChat(TextChatKind::PinMessage) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: PinMessage = decode_payload(&envelope)?;
+            match application::pin_message(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::MessagePinned),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::TextChat(TextChatKind::UnpinMessage) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: UnpinMessage = decode_payload(&envelope)?;
+            match application::unpin_message(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::MessagePinned),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::TextChat(TextChatKind::ListPinnedMessages) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: ListPinnedMessages = decode_payload(&envelope)?;
+            match application::list_pinned_messages(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::TextChat,
+                        RealtimeKind::TextChat(TextChatKind::PinnedMessages),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
         RealtimeKind::TextChat(_) => {
             send_rejection(
                 send,
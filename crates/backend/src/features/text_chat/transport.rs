@@ -0,0 +1,295 @@
+//! HTTP-адаптер текстового чата.
+
+use axum::{
+    Json,
+    body::Bytes,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use cheenhub_contracts::realtime::ChatAttachmentUploadResponse;
+use cheenhub_contracts::rest::{ApiError, SearchServerMessagesResponse};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::features::auth::application::require_current_user;
+use crate::features::auth::error::AuthError;
+use crate::features::text_chat::application::{
+    self, SearchServerMessages, TextChatApplicationError,
+};
+use crate::features::text_chat::domain::ChatAttachmentKind;
+use crate::state::AppState;
+
+/// Query-параметры поиска сообщений на сервере.
+#[derive(Deserialize)]
+pub(crate) struct SearchMessagesQuery {
+    /// Строка поиска.
+    q: Option<String>,
+    /// Комната, которой нужно ограничить поиск.
+    room_id: Option<String>,
+    /// Найденное ранее сообщение, перед которым нужно вернуть более старые совпадения.
+    before_message_id: Option<String>,
+}
+
+/// Ищет сообщения в текстовых комнатах сервера.
+pub(crate) async fn search_messages(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(server_id): Path<String>,
+    Query(query): Query<SearchMessagesQuery>,
+) -> Result<Json<SearchServerMessagesResponse>, TextChatApplicationError> {
+    let token = bearer_token(&headers)?;
+    let (user, _) = require_current_user(&state, token)
+        .await
+        .map_err(map_auth_error)?;
+    let request = SearchServerMessages {
+        query: query.q.unwrap_or_default(),
+        room_id: query.room_id,
+        before_message_id: query.before_message_id,
+    };
+    application::search_server_messages(&state, &user.id, server_id, request)
+        .await
+        .map(Json)
+}
+
+/// Query-параметры загрузки вложения.
+#[derive(Deserialize)]
+pub(crate) struct UploadAttachmentQuery {
+    /// Исходное имя файла.
+    filename: Option<String>,
+}
+
+/// Загружает вложение комнаты из тела запроса.
+pub(crate) async fn upload_attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((server_id, room_id)): Path<(String, String)>,
+    Query(query): Query<UploadAttachmentQuery>,
+    bytes: Bytes,
+) -> Result<Json<ChatAttachmentUploadResponse>, TextChatApplicationError> {
+    let token = bearer_token(&headers)?;
+    let (user, _) = require_current_user(&state, token)
+        .await
+        .map_err(map_auth_error)?;
+    application::upload_chat_attachment(
+        &state,
+        &user.id,
+        server_id,
+        room_id,
+        query.filename,
+        &bytes,
+    )
+    .await
+    .map(Json)
+}
+
+/// Отдает вложение комнаты участнику сервера.
+///
+/// Тип берется из сохраненного результата проверки содержимого, а
+/// `nosniff` и sandbox-CSP не дают браузеру исполнить файл как страницу.
+pub(crate) async fn attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((server_id, attachment_id)): Path<(String, String)>,
+) -> Result<Response, TextChatApplicationError> {
+    let token = bearer_token(&headers)?;
+    let (user, _) = require_current_user(&state, token)
+        .await
+        .map_err(map_auth_error)?;
+    let (attachment, bytes) = application::chat_attachment(&state, &user.id, attachment_id).await?;
+    if attachment.server_id.to_string() != server_id {
+        return Err(TextChatApplicationError::NotFound(
+            "Вложение не найдено.".to_owned(),
+        ));
+    }
+
+    let extension = attachment
+        .object_key
+        .rsplit_once('.')
+        .map_or("bin", |(_, extension)| extension);
+    Ok(attachment_response(
+        attachment.kind,
+        attachment.id,
+        extension,
+        attachment.original_filename.as_deref(),
+        attachment.content_type,
+        bytes,
+    ))
+}
+
+/// Собирает ответ с байтами вложения и заголовками безопасной отдачи.
+///
+/// Используется и комнатами, и личными сообщениями, чтобы загруженный
+/// пользователем файл нигде не отдавался без `nosniff` и sandbox-CSP.
+pub(crate) fn attachment_response(
+    kind: ChatAttachmentKind,
+    id: Uuid,
+    extension: &str,
+    original_filename: Option<&str>,
+    content_type: String,
+    bytes: Vec<u8>,
+) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                content_disposition(kind, id, extension, original_filename),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_owned()),
+            (
+                header::CONTENT_SECURITY_POLICY,
+                "default-src 'none'; sandbox".to_owned(),
+            ),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_owned()),
+        ],
+        bytes,
+    )
+        .into_response()
+}
+
+/// Собирает `Content-Disposition` с ASCII-именем и исходным именем по RFC 6266.
+///
+/// Медиа открываются во вкладке, остальные файлы всегда скачиваются.
+fn content_disposition(
+    kind: ChatAttachmentKind,
+    id: Uuid,
+    extension: &str,
+    original_filename: Option<&str>,
+) -> String {
+    let disposition = match kind {
+        ChatAttachmentKind::File => "attachment",
+        ChatAttachmentKind::Image | ChatAttachmentKind::Video | ChatAttachmentKind::Audio => {
+            "inline"
+        }
+    };
+    let fallback = format!("attachment-{id}.{extension}");
+    match original_filename {
+        Some(filename) => format!(
+            "{disposition}; filename=\"{fallback}\"; filename*=UTF-8''{}",
+            percent_encode_filename(filename)
+        ),
+        None => format!("{disposition}; filename=\"{fallback}\""),
+    }
+}
+
+fn percent_encode_filename(filename: &str) -> String {
+    filename
+        .bytes()
+        .map(|byte| {
+            if byte.is_ascii_alphanumeric() || b"!#$&+-.^_`|~".contains(&byte) {
+                char::from(byte).to_string()
+            } else {
+                format!("%{byte:02X}")
+            }
+        })
+        .collect()
+}
+
+impl IntoResponse for TextChatApplicationError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match self {
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, "bad_request", message),
+            Self::Unauthorized(message) => (StatusCode::UNAUTHORIZED, "unauthorized", message),
+            Self::NotFound(message) => (StatusCode::NOT_FOUND, "not_found", message),
+            Self::Misconfigured {
+                feature,
+                missing,
+                message,
+            } => {
+                tracing::warn!(
+                    feature,
+                    missing_env = ?missing,
+                    "text chat feature is not configured"
+                );
+                (StatusCode::SERVICE_UNAVAILABLE, "misconfigured", message)
+            }
+            Self::Internal(error) => {
+                tracing::error!(%error, "text chat request failed");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal_error",
+                    "Не удалось выполнить действие чата.".to_owned(),
+                )
+            }
+        };
+        (
+            status,
+            Json(ApiError {
+                code: code.to_owned(),
+                message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<&str, TextChatApplicationError> {
+    let value = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(unauthorized)?;
+
+    value
+        .strip_prefix("Bearer ")
+        .filter(|token| !token.is_empty())
+        .ok_or_else(unauthorized)
+}
+
+fn map_auth_error(error: AuthError) -> TextChatApplicationError {
+    match error {
+        AuthError::BadRequest(message)
+        | AuthError::Unauthorized(message)
+        | AuthError::RefreshRejected { message, .. }
+        | AuthError::RefreshRotationInProgress(message) => {
+            TextChatApplicationError::Unauthorized(message)
+        }
+        AuthError::Conflict(message) | AuthError::RateLimited(message) => {
+            TextChatApplicationError::BadRequest(message)
+        }
+        AuthError::Misconfigured { message, .. } => {
+            TextChatApplicationError::Internal(anyhow::anyhow!(message))
+        }
+        AuthError::Internal(error) => TextChatApplicationError::Internal(error),
+    }
+}
+
+fn unauthorized() -> TextChatApplicationError {
+    TextChatApplicationError::Unauthorized("Войди, чтобы продолжить.".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use super::content_disposition;
+    use crate::features::text_chat::domain::ChatAttachmentKind;
+
+    #[test]
+    fn content_disposition_never_echoes_raw_filename() {
+        let header = content_disposition(
+            ChatAttachmentKind::File,
+            Uuid::nil(),
+            "txt",
+            Some("отчет \"v2\";\r\nX: 1.txt"),
+        );
+
+        assert_eq!(
+            header,
+            "attachment; filename=\"attachment-00000000-0000-0000-0000-000000000000.txt\"; \
+             filename*=UTF-8''%D0%BE%D1%82%D1%87%D0%B5%D1%82%20%22v2%22%3B%0D%0AX%3A%201.txt"
+        );
+    }
+
+    #[test]
+    fn media_is_inline_and_files_are_downloaded() {
+        assert!(
+            content_disposition(ChatAttachmentKind::Video, Uuid::nil(), "mp4", None)
+                .starts_with("inline;")
+        );
+        assert!(
+            content_disposition(ChatAttachmentKind::File, Uuid::nil(), "bin", None)
+                .starts_with("attachment;")
+        );
+    }
+}
@@ -1,11 +1,19 @@
 //! Вспомогательные функции валидации текстового чата.
 
+mod emoji;
+
 const MAX_MESSAGE_BODY_CHARS: usize = 2000;
+const MAX_REACTION_EMOJI_BYTES: usize = 32;
+const MAX_MENTIONS_PER_MESSAGE: usize = 20;
+const MIN_SEARCH_QUERY_CHARS: usize = 2;
+const MAX_SEARCH_QUERY_CHARS: usize = 100;
 
 /// Проверенное тело сообщения текстового чата.
 pub(crate) struct ValidMessageBody {
     /// Обрезанное тело сообщения.
     pub(crate) body: String,
+    /// Уникальные имена из `@`-токенов в нижнем регистре, в порядке появления.
+    pub(crate) mention_names: Vec<String>,
 }
 
 /// Проверяет и нормализует тело сообщения.
@@ -18,5 +26,130 @@ pub(crate) fn message_body(body: String) -> Result<ValidMessageBody, &'static st
         return Err("Сообщение слишком длинное.");
     }
 
-    Ok(ValidMessageBody { body })
+    let mention_names = mention_names(&body);
+    Ok(ValidMessageBody {
+        body,
+        mention_names,
+    })
+}
+
+/// Извлекает имена из `@`-токенов тела сообщения.
+///
+/// Токен начинается с `@` в начале текста или после пробела и продолжается,
+/// пока идут буквы, цифры или `_`. Поэтому адреса почты не считаются
+/// упоминаниями, а роли с пробелами в названии упомянуть нельзя. Разрешать
+/// имена в пользователей и роли должен вызывающий код.
+fn mention_names(body: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut previous = None;
+    let mut chars = body.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        let at_token_start = ch == '@' && previous.is_none_or(char::is_whitespace);
+        previous = Some(ch);
+        if !at_token_start {
+            continue;
+        }
+
+        let start = index + ch.len_utf8();
+        let mut end = start;
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !(next.is_alphanumeric() || next == '_') {
+                break;
+            }
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let name = body[start..end].to_lowercase();
+        if !name.is_empty() && !names.contains(&name) {
+            names.push(name);
+            if names.len() == MAX_MENTIONS_PER_MESSAGE {
+                break;
+            }
+        }
+    }
+
+    names
+}
+
+/// Проверяет и нормализует эмодзи реакции.
+///
+/// Реакцией может быть ровно один эмодзи, включая ZWJ-цепочки, флаги и
+/// клавиши, но не произвольный текст.
+pub(crate) fn reaction_emoji(emoji: String) -> Result<String, &'static str> {
+    let emoji = emoji.trim().to_owned();
+    if emoji.is_empty() {
+        return Err("Реакция не может быть пустой.");
+    }
+    if emoji.len() > MAX_REACTION_EMOJI_BYTES || !emoji::is_single_emoji(&emoji) {
+        return Err("Недопустимая реакция.");
+    }
+
+    Ok(emoji)
+}
+
+/// Проверяет и нормализует строку поиска по сообщениям.
+pub(crate) fn search_query(query: &str) -> Result<String, &'static str> {
+    let query = query.trim();
+    let chars = query.chars().count();
+    if chars < MIN_SEARCH_QUERY_CHARS {
+        return Err("Запрос для поиска слишком короткий.");
+    }
+    if chars > MAX_SEARCH_QUERY_CHARS {
+        return Err("Запрос для поиска слишком длинный.");
+    }
+
+    Ok(query.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{message_body, reaction_emoji, search_query};
+
+    #[test]
+    fn collects_unique_lowercased_mention_names() {
+        let valid = message_body("@Alice привет, @bob и @alice! @Модераторы".to_owned())
+            .expect("body should be valid");
+
+        assert_eq!(valid.mention_names, ["alice", "bob", "модераторы"]);
+    }
+
+    #[test]
+    fn ignores_email_addresses_and_bare_at_signs() {
+        let valid = message_body("пиши на team@example.com или @ сюда".to_owned())
+            .expect("body should be valid");
+
+        assert!(valid.mention_names.is_empty());
+    }
+
+    #[test]
+    fn reaction_accepts_emoji_sequences_and_rejects_words() {
+        for emoji in [
+            "👍",
+            "❤️",
+            "👍🏽",
+            "👩\u{200D}💻",
+            "👨\u{200D}👩\u{200D}👧",
+            "🇷🇺",
+            "1️⃣",
+            "#\u{20E3}",
+            "🏴\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}",
+        ] {
+            assert_eq!(reaction_emoji(emoji.to_owned()), Ok(emoji.to_owned()));
+        }
+        for text in ["привет", "ok", "1", "é", "👍👍", "👍 👍", "🇷", "\u{200D}👍"]
+        {
+            assert!(
+                reaction_emoji(text.to_owned()).is_err(),
+                "{text:?} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn search_query_is_trimmed_and_bounded() {
+        assert_eq!(search_query("  привет  "), Ok("привет".to_owned()));
+        assert!(search_query(" я ").is_err());
+        assert!(search_query(&"а".repeat(101)).is_err());
+    }
 }
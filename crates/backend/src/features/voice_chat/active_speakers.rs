@@ -0,0 +1,198 @@
+//! Оценка говорящих участников по уровню звука голосовых датаграмм.
+//!
+//! Отправитель кладет в голосовую датаграмму уровень звука кадра, relay
+//! сглаживает его по каждому участнику и по таймеру комнаты решает, кто сейчас
+//! говорит и кто доминирует. Клиенты получают готовый список и не декодируют
+//! каждый поток ради индикаторов.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use cheenhub_contracts::media::AUDIO_LEVEL_SILENCE;
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence, VoicePresenceTarget};
+
+/// Как часто relay пересчитывает говорящих комнаты.
+pub(crate) const ACTIVE_SPEAKERS_EVALUATION_INTERVAL: Duration = Duration::from_millis(100);
+/// Минимальный интервал между событиями об одной комнате.
+const ACTIVE_SPEAKERS_EVENT_INTERVAL: Duration = Duration::from_millis(300);
+/// Клиенты не отправляют кадры в тишине, поэтому участник без датаграмм дольше
+/// этого интервала считается замолчавшим.
+const SPEAKER_SILENCE_TIMEOUT: Duration = Duration::from_millis(400);
+/// Вес нового кадра в сглаженной громкости.
+const LOUDNESS_SMOOTHING: f32 = 0.3;
+/// Сглаженная громкость в дБ над порогом тишины, с которой участник считается говорящим.
+const SPEAKING_LOUDNESS: f32 = 67.0;
+/// На сколько дБ другой говорящий должен перекрыть доминирующего, чтобы сменить его.
+const DOMINANT_SWITCH_MARGIN: f32 = 3.0;
+/// Сколько самых громких говорящих попадает в событие.
+const MAX_ACTIVE_SPEAKERS: usize = 6;
+
+/// Говорящие участники комнаты.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct ActiveSpeakers {
+    /// Сначала доминирующий, затем остальные по убыванию громкости.
+    pub(crate) speaker_user_ids: Vec<Uuid>,
+    /// Доминирующий говорящий.
+    pub(crate) dominant_user_id: Option<Uuid>,
+}
+
+/// Результат пересчета говорящих комнаты.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ActiveSpeakersUpdate {
+    /// Клиенты уже знают актуальный список или событие пока рано отправлять.
+    Unchanged,
+    /// Список изменился, и его нужно разослать участникам.
+    Changed(ActiveSpeakers),
+    /// В комнате никто не говорит и об этом уже сообщено; слежение можно прекратить.
+    Idle,
+}
+
+struct SpeakerLoudness {
+    smoothed: f32,
+    heard_at: Instant,
+}
+
+#[derive(Default)]
+struct RoomSpeakers {
+    loudness: HashMap<Uuid, SpeakerLoudness>,
+    announced: ActiveSpeakers,
+    announced_at: Option<Instant>,
+}
+
+/// Сглаженная громкость участников комнат, за которыми следит relay.
+#[derive(Default)]
+pub(super) struct ActiveSpeakerTracker {
+    rooms: HashMap<VoicePresenceTarget, RoomSpeakers>,
+}
+
+impl ActiveSpeakerTracker {
+    /// Учитывает уровень кадра; `true`, если комната только что попала под слежение.
+    fn record_at(
+        &mut self,
+        target: VoicePresenceTarget,
+        user_id: Uuid,
+        level: u8,
+        now: Instant,
+    ) -> bool {
+        let started = !self.rooms.contains_key(&target);
+        let loudness = f32::from(AUDIO_LEVEL_SILENCE - level.min(AUDIO_LEVEL_SILENCE));
+        let room = self.rooms.entry(target).or_default();
+        room.loudness
+            .entry(user_id)
+            .and_modify(|speaker| {
+                // Кадр после паузы начинает оценку заново, а не с затухшего значения.
+                if now.saturating_duration_since(speaker.heard_at) > SPEAKER_SILENCE_TIMEOUT {
+                    speaker.smoothed = loudness;
+                } else {
+                    speaker.smoothed += LOUDNESS_SMOOTHING * (loudness - speaker.smoothed);
+                }
+                speaker.heard_at = now;
+            })
+            .or_insert(SpeakerLoudness {
+                smoothed: loudness,
+                heard_at: now,
+            });
+        started
+    }
+
+    fn evaluate_at(&mut self, target: VoicePresenceTarget, now: Instant) -> ActiveSpeakersUpdate {
+        let Some(room) = self.rooms.get_mut(&target) else {
+            return ActiveSpeakersUpdate::Idle;
+        };
+        room.loudness.retain(|_, speaker| {
+            now.saturating_duration_since(speaker.heard_at) <= SPEAKER_SILENCE_TIMEOUT
+        });
+        let current = room.current_speakers();
+        if current == room.announced {
+            if room.loudness.is_empty() && current.speaker_user_ids.is_empty() {
+                self.rooms.remove(&target);
+                return ActiveSpeakersUpdate::Idle;
+            }
+            return ActiveSpeakersUpdate::Unchanged;
+        }
+        if room.announced_at.is_some_and(|announced_at| {
+            now.saturating_duration_since(announced_at) < ACTIVE_SPEAKERS_EVENT_INTERVAL
+        }) {
+            return ActiveSpeakersUpdate::Unchanged;
+        }
+        room.announced = current.clone();
+        room.announced_at = Some(now);
+        ActiveSpeakersUpdate::Changed(current)
+    }
+
+    pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
+        for presence in removed {
+            if let Some(room) = self.rooms.get_mut(&presence.target()) {
+                room.loudness.remove(&presence.user_id);
+            }
+        }
+    }
+}
+
+impl RoomSpeakers {
+    fn current_speakers(&self) -> ActiveSpeakers {
+        let mut speakers = self
+            .loudness
+            .iter()
+            .filter(|(_, speaker)| speaker.smoothed >= SPEAKING_LOUDNESS)
+            .map(|(user_id, speaker)| (*user_id, speaker.smoothed))
+            .collect::<Vec<_>>();
+        speakers.sort_by(|left, right| right.1.total_cmp(&left.1).then(left.0.cmp(&right.0)));
+        speakers.truncate(MAX_ACTIVE_SPEAKERS);
+
+        // Доминирующий меняется только при заметном перевесе, чтобы фокус не
+        // прыгал между собеседниками с близкой громкостью.
+        let loudest = speakers.first().copied();
+        let previous = self.announced.dominant_user_id.and_then(|user_id| {
+            speakers
+                .iter()
+                .find(|(speaker_id, _)| *speaker_id == user_id)
+                .copied()
+        });
+        let dominant = match (previous, loudest) {
+            (Some(previous), Some(loudest)) if loudest.1 - previous.1 < DOMINANT_SWITCH_MARGIN => {
+                Some(previous.0)
+            }
+            (_, loudest) => loudest.map(|(user_id, _)| user_id),
+        };
+        if let Some(dominant) = dominant {
+            speakers.sort_by_key(|(user_id, _)| *user_id != dominant);
+        }
+
+        ActiveSpeakers {
+            speaker_user_ids: speakers.into_iter().map(|(user_id, _)| user_id).collect(),
+            dominant_user_id: dominant,
+        }
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Учитывает уровень звука голосовой датаграммы участника.
+    ///
+    /// Возвращает `true`, если комната только что попала под слежение и для нее
+    /// нужно запустить пересчет говорящих.
+    pub(crate) async fn record_audio_level(&self, presence: &VoicePresence, level: u8) -> bool {
+        self.active_speakers.lock().await.record_at(
+            presence.target(),
+            presence.user_id,
+            level,
+            Instant::now(),
+        )
+    }
+
+    /// Пересчитывает говорящих комнаты.
+    pub(crate) async fn evaluate_active_speakers(
+        &self,
+        target: VoicePresenceTarget,
+    ) -> ActiveSpeakersUpdate {
+        self.active_speakers
+            .lock()
+            .await
+            .evaluate_at(target, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,138 @@
+use super::*;
+use crate::features::voice_chat::infrastructure::VoicePresenceTargetKind;
+use crate::features::voice_chat::test_builders::voice_presence;
+
+fn target() -> VoicePresenceTarget {
+    VoicePresenceTarget {
+        kind: VoicePresenceTargetKind::Server,
+        server_id: Uuid::new_v4(),
+        room_id: Uuid::new_v4(),
+    }
+}
+
+fn changed(update: ActiveSpeakersUpdate) -> ActiveSpeakers {
+    match update {
+        ActiveSpeakersUpdate::Changed(speakers) => speakers,
+        other => panic!("expected changed speakers, got {other:?}"),
+    }
+}
+
+#[test]
+fn loudest_speaker_dominates_and_quiet_frames_are_ignored() {
+    let mut tracker = ActiveSpeakerTracker::default();
+    let room = target();
+    let (alice, bob, carol) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+
+    assert!(tracker.record_at(room, alice, 40, now));
+    assert!(!tracker.record_at(room, bob, 25, now));
+    assert!(!tracker.record_at(room, carol, 90, now));
+    let speakers = changed(tracker.evaluate_at(room, now));
+
+    assert_eq!(speakers.speaker_user_ids, vec![bob, alice]);
+    assert_eq!(speakers.dominant_user_id, Some(bob));
+    assert_eq!(
+        tracker.evaluate_at(room, now + ACTIVE_SPEAKERS_EVALUATION_INTERVAL),
+        ActiveSpeakersUpdate::Unchanged
+    );
+}
+
+#[test]
+fn dominant_speaker_switches_only_with_clear_margin() {
+    let mut tracker = ActiveSpeakerTracker::default();
+    let room = target();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+    tracker.record_at(room, alice, 30, now);
+    assert_eq!(
+        changed(tracker.evaluate_at(room, now)).dominant_user_id,
+        Some(alice)
+    );
+
+    let later = now + ACTIVE_SPEAKERS_EVENT_INTERVAL;
+    tracker.record_at(room, alice, 30, later);
+    tracker.record_at(room, bob, 28, later);
+    let speakers = changed(tracker.evaluate_at(room, later));
+    assert_eq!(speakers.speaker_user_ids, vec![alice, bob]);
+    assert_eq!(speakers.dominant_user_id, Some(alice));
+
+    let latest = later + ACTIVE_SPEAKERS_EVENT_INTERVAL;
+    tracker.record_at(room, alice, 30, latest);
+    tracker.record_at(room, bob, 20, latest);
+    let speakers = changed(tracker.evaluate_at(room, latest));
+    assert_eq!(speakers.speaker_user_ids, vec![bob, alice]);
+    assert_eq!(speakers.dominant_user_id, Some(bob));
+}
+
+#[test]
+fn events_are_rate_limited_per_room() {
+    let mut tracker = ActiveSpeakerTracker::default();
+    let room = target();
+    let (alice, bob) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+    tracker.record_at(room, alice, 30, now);
+    changed(tracker.evaluate_at(room, now));
+
+    let soon = now + ACTIVE_SPEAKERS_EVALUATION_INTERVAL;
+    tracker.record_at(room, bob, 30, soon);
+    assert_eq!(
+        tracker.evaluate_at(room, soon),
+        ActiveSpeakersUpdate::Unchanged
+    );
+
+    let later = now + ACTIVE_SPEAKERS_EVENT_INTERVAL;
+    tracker.record_at(room, bob, 30, later);
+    assert_eq!(
+        changed(tracker.evaluate_at(room, later)).speaker_user_ids,
+        vec![alice, bob]
+    );
+}
+
+#[test]
+fn silent_room_is_announced_once_and_then_released() {
+    let mut tracker = ActiveSpeakerTracker::default();
+    let room = target();
+    let alice = Uuid::new_v4();
+    let now = Instant::now();
+    tracker.record_at(room, alice, 30, now);
+    changed(tracker.evaluate_at(room, now));
+
+    let silent = now + SPEAKER_SILENCE_TIMEOUT + ACTIVE_SPEAKERS_EVALUATION_INTERVAL;
+    assert_eq!(
+        changed(tracker.evaluate_at(room, silent)),
+        ActiveSpeakers::default()
+    );
+    assert_eq!(
+        tracker.evaluate_at(room, silent + ACTIVE_SPEAKERS_EVALUATION_INTERVAL),
+        ActiveSpeakersUpdate::Idle
+    );
+    assert!(tracker.record_at(room, alice, 30, silent + ACTIVE_SPEAKERS_EVENT_INTERVAL));
+}
+
+#[tokio::test]
+async fn departed_participant_stops_speaking() {
+    let store = InMemoryVoicePresenceStore::default();
+    let room = target();
+    let presence = VoicePresence {
+        target_kind: room.kind,
+        ..voice_presence(room.server_id, room.room_id, Uuid::new_v4())
+    };
+    assert!(store.record_audio_level(&presence, 20).await);
+    assert_eq!(
+        changed(store.evaluate_active_speakers(room).await).dominant_user_id,
+        Some(presence.user_id)
+    );
+
+    store
+        .active_speakers
+        .lock()
+        .await
+        .remove_presences(std::slice::from_ref(&presence));
+
+    let mut tracker = store.active_speakers.lock().await;
+    let later = Instant::now() + ACTIVE_SPEAKERS_EVENT_INTERVAL;
+    assert_eq!(
+        changed(tracker.evaluate_at(room, later)),
+        ActiveSpeakers::default()
+    );
+}
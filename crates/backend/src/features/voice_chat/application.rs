@@ -3,8 +3,8 @@
 use cheenhub_contracts::realtime::{
     DirectMessageVoiceRoomsSnapshot, JoinDirectMessageVoiceRoom, JoinVoiceRoom, KickVoiceMember,
     LeaveDirectMessageVoiceRoom, LeaveVoiceRoom, ListDirectMessageVoiceRooms, ListServerVoiceRooms,
-    RealtimeKind, RealtimeModule, ServerRoleKind, ServerRolePermission, ServerVoiceRoomsSnapshot,
-    StopVoiceVideoStream, VoiceChatKind, VoiceRoomSnapshot, VoiceVideoStreamEnded,
+    RealtimeKind, RealtimeModule, ServerVoiceRoomsSnapshot, StopVoiceVideoStream, VoiceChatKind,
+    VoiceRoomSnapshot, VoiceVideoStreamEnded,
 };
 use cheenhub_contracts::rest::{AuthUser, ServerRoomKind};
 use chrono::Utc;
@@ -14,23 +14,47 @@ use crate::features::social::{self, DirectMessageVoiceAccess, SocialError};
 use crate::features::voice_chat::infrastructure::VoicePresence;
 use crate::state::AppState;
 
+mod access;
+mod active_speakers;
+mod audio_mixing;
 mod avatar;
 mod direct_calls;
 mod fanout;
+mod key_frames;
+mod media_settings;
+mod moderation;
 mod presence;
+mod receiver_reports;
+mod recording;
 mod uplink;
+mod video_codecs;
+mod video_subscriptions;
 
+use access::{user_can_kick_voice, user_has_server_access};
+pub(crate) use active_speakers::track_audio_level;
+pub(crate) use audio_mixing::set_audio_mixing;
 pub(crate) use avatar::update_user_avatar;
 pub(crate) use direct_calls::{
     cancel_direct_call, end_direct_call, list_direct_calls, respond_direct_call, start_direct_call,
 };
 use fanout::{
-    direct_message_voice_target, fanout_removed_rooms, fanout_snapshot, participant_summary,
-    room_snapshot, server_voice_target,
+    direct_message_voice_target, fanout_removed_rooms, fanout_snapshot, participant_summaries,
+    recording_status, room_snapshot, server_voice_target,
 };
+pub(crate) use key_frames::request_key_frame;
+pub(crate) use media_settings::apply_server_media_settings;
+use media_settings::refresh_server_media_settings;
+pub(crate) use moderation::{server_deafen_member, server_mute_member};
 use presence::active_presence_for_user;
 pub(crate) use presence::disconnect_realtime_stream;
+pub(crate) use receiver_reports::record_receiver_report;
+pub(crate) use recording::{
+    list_recordings, record_room_media, recording_file, start_recording, stop_recording,
+};
 pub(crate) use uplink::{bind_microphone_uplink, issue_microphone_uplink_grant};
+pub(crate) use video_codecs::advertise_codec_capabilities;
+use video_codecs::announce_video_codec_change;
+pub(crate) use video_subscriptions::{announce_video_activity, subscribe_video, unsubscribe_video};
 
 /// Входит в одну комнату с поддержкой голоса и возвращает текущий снимок участников.
 pub(crate) async fn join_room(
@@ -44,6 +68,7 @@ pub(crate) async fn join_room(
     let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
     let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
     ensure_room_voice_available(state, user_id, &server_id, &room_id).await?;
+    refresh_server_media_settings(state, &server_id).await?;
     let target = server_voice_target(server_id, room_id);
     let removed = state
         .voice_presence_store
@@ -57,6 +82,8 @@ pub(crate) async fn join_room(
             nickname: user.nickname.clone(),
             avatar_url: user.avatar_url.clone(),
             joined_at: Utc::now(),
+            server_muted: false,
+            server_deafened: false,
         })
         .await;
 
@@ -117,6 +144,8 @@ pub(crate) async fn join_direct_message_room(
             nickname: user.nickname.clone(),
             avatar_url: user.avatar_url.clone(),
             joined_at: Utc::now(),
+            server_muted: false,
+            server_deafened: false,
         })
         .await;
 
@@ -228,17 +257,23 @@ pub(crate) async fn list_server_voice_rooms(
         ));
     }
 
-    let rooms = state
+    let mut rooms = Vec::new();
+    for (room_id, participants) in state
         .voice_presence_store
         .server_room_participants(&server_id)
         .await
-        .into_iter()
-        .map(|(room_id, participants)| VoiceRoomSnapshot {
+    {
+        rooms.push(VoiceRoomSnapshot {
             server_id: server_id.to_string(),
             room_id: room_id.to_string(),
-            participants: participants.iter().map(participant_summary).collect(),
-        })
-        .collect::<Vec<_>>();
+            participants: participant_summaries(state, &participants).await,
+            recording: recording_status(state, server_voice_target(server_id, room_id)).await,
+            audio_mixing: state
+                .voice_presence_store
+                .room_mixing_enabled(server_id, room_id)
+                .await,
+        });
+    }
 
     tracing::debug!(
         server_id = %server_id,
@@ -411,64 +446,6 @@ async fn ensure_room_voice_available(
     }
 }
 
-async fn user_can_kick_voice(
-    state: &AppState,
-    user_id: &Uuid,
-    server_id: &Uuid,
-) -> anyhow::Result<bool> {
-    let Some(server) = state.server_store.find_server(server_id).await? else {
-        return Ok(false);
-    };
-    if server.owner_user_id == *user_id {
-        return Ok(true);
-    }
-    if state
-        .server_store
-        .find_active_server_member(server_id, user_id)
-        .await?
-        .is_none()
-    {
-        return Ok(false);
-    }
-
-    let roles = state.server_store.list_server_roles(server_id).await?;
-    let member_roles = state
-        .server_store
-        .list_server_member_roles(server_id)
-        .await?;
-    let user_role_ids: Vec<_> = member_roles
-        .iter()
-        .filter(|(uid, _)| uid == user_id)
-        .map(|(_, rid)| *rid)
-        .collect();
-
-    Ok(roles.iter().any(|role| {
-        (role.kind == ServerRoleKind::Member || user_role_ids.contains(&role.id))
-            && role
-                .permissions
-                .contains(&ServerRolePermission::KickVoiceMembers)
-    }))
-}
-
-async fn user_has_server_access(
-    state: &AppState,
-    user_id: &Uuid,
-    server_id: &Uuid,
-) -> anyhow::Result<bool> {
-    let Some(server) = state.server_store.find_server(server_id).await? else {
-        return Ok(false);
-    };
-    if server.owner_user_id == *user_id {
-        return Ok(true);
-    }
-
-    Ok(state
-        .server_store
-        .find_active_server_member(server_id, user_id)
-        .await?
-        .is_some())
-}
-
 async fn ensure_direct_message_voice_available(
     state: &AppState,
     user_id: &Uuid,
@@ -0,0 +1,87 @@
+//! Рассылка говорящих участников голосовой комнаты.
+
+use cheenhub_contracts::realtime::{
+    RealtimeKind, RealtimeModule, VoiceActiveSpeakersChanged, VoiceChatKind,
+};
+use tokio::time::MissedTickBehavior;
+
+use crate::features::voice_chat::active_speakers::{
+    ACTIVE_SPEAKERS_EVALUATION_INTERVAL, ActiveSpeakers, ActiveSpeakersUpdate,
+};
+use crate::features::voice_chat::infrastructure::{VoicePresence, VoicePresenceTarget};
+use crate::state::AppState;
+
+/// Учитывает уровень звука голосового кадра и при необходимости запускает
+/// пересчет говорящих комнаты.
+pub(crate) async fn track_audio_level(state: &AppState, presence: &VoicePresence, level: u8) {
+    if state
+        .voice_presence_store
+        .record_audio_level(presence, level)
+        .await
+    {
+        tokio::spawn(watch_active_speakers(state.clone(), presence.target()));
+    }
+}
+
+/// Пересчитывает говорящих комнаты, пока в ней кто-то говорит.
+async fn watch_active_speakers(state: AppState, target: VoicePresenceTarget) {
+    let mut interval = tokio::time::interval(ACTIVE_SPEAKERS_EVALUATION_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        match state
+            .voice_presence_store
+            .evaluate_active_speakers(target)
+            .await
+        {
+            ActiveSpeakersUpdate::Unchanged => {}
+            ActiveSpeakersUpdate::Changed(speakers) => {
+                fanout_active_speakers(&state, target, speakers).await;
+            }
+            ActiveSpeakersUpdate::Idle => break,
+        }
+    }
+
+    tracing::trace!(
+        server_id = %target.server_id,
+        room_id = %target.room_id,
+        "voice room active speaker tracking stopped"
+    );
+}
+
+async fn fanout_active_speakers(
+    state: &AppState,
+    target: VoicePresenceTarget,
+    speakers: ActiveSpeakers,
+) {
+    let stream_ids = state
+        .voice_presence_store
+        .room_participants(target.kind, &target.server_id, &target.room_id)
+        .await
+        .into_iter()
+        .map(|presence| presence.realtime_stream_id)
+        .collect::<Vec<_>>();
+    if stream_ids.is_empty() {
+        return;
+    }
+
+    state
+        .realtime_hub
+        .fanout_to_streams(
+            RealtimeModule::VoiceChat,
+            &target.route_id(),
+            RealtimeKind::VoiceChat(VoiceChatKind::ActiveSpeakersChanged),
+            &stream_ids,
+            VoiceActiveSpeakersChanged {
+                server_id: target.server_id.to_string(),
+                room_id: target.room_id.to_string(),
+                speaker_user_ids: speakers
+                    .speaker_user_ids
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect(),
+                dominant_user_id: speakers.dominant_user_id.map(|user_id| user_id.to_string()),
+            },
+        )
+        .await;
+}
@@ -0,0 +1,140 @@
+//! Переключение и работа серверного сведения звука голосовых комнат.
+
+use bytes::Bytes;
+use cheenhub_contracts::realtime::{SetVoiceRoomAudioMixing, VoiceRoomSnapshot};
+use tokio::time::MissedTickBehavior;
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::VoicePresenceTargetKind;
+use crate::features::voice_chat::media::mixing::{
+    MIX_INTERVAL, MixParticipant, MixingStartError, RoomMixingRun,
+};
+use crate::state::AppState;
+
+use super::access::user_can_manage_media;
+use super::fanout::{fanout_snapshot, room_snapshot, server_voice_target};
+use super::{VoiceChatApplicationError, ensure_room_voice_available, parse_id};
+
+/// Включает или выключает серверное сведение звука голосовой комнаты.
+pub(crate) async fn set_audio_mixing(
+    state: &AppState,
+    user_id: &Uuid,
+    request: SetVoiceRoomAudioMixing,
+) -> Result<VoiceRoomSnapshot, VoiceChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    ensure_room_voice_available(state, user_id, &server_id, &room_id).await?;
+    if !user_can_manage_media(state, user_id, &server_id)
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?
+    {
+        return Err(VoiceChatApplicationError::Unauthorized(
+            "Недостаточно прав для настройки звука голосовой комнаты.".to_owned(),
+        ));
+    }
+
+    let target = server_voice_target(server_id, room_id);
+    if request.enabled {
+        let bitrate_bps = state
+            .voice_presence_store
+            .server_audio_policy(&server_id)
+            .await
+            .max_bitrate_bps;
+        let started = state
+            .voice_presence_store
+            .enable_room_mixing(server_id, room_id, bitrate_bps)
+            .await
+            .map_err(|error| {
+                VoiceChatApplicationError::BadRequest(
+                    match error {
+                        MixingStartError::Unavailable => {
+                            "Сведение звука недоступно на этом сервере."
+                        }
+                        MixingStartError::EmptyRoom => "В голосовой комнате нет участников.",
+                    }
+                    .to_owned(),
+                )
+            })?;
+        if let Some(run) = started {
+            tokio::spawn(run_room_mixer(state.clone(), run));
+        }
+    } else {
+        state
+            .voice_presence_store
+            .disable_room_mixing(server_id, room_id)
+            .await;
+    }
+
+    tracing::info!(
+        server_id = %server_id,
+        room_id = %room_id,
+        user_id = %user_id,
+        enabled = request.enabled,
+        "switched voice room audio mixing"
+    );
+    let snapshot = room_snapshot(state, target).await;
+    fanout_snapshot(state, target, snapshot.clone()).await;
+
+    Ok(snapshot)
+}
+
+/// Сводит голоса комнаты каждые 20 мс, пока этот запуск сведения не выключат
+/// или комнату не покинет последний участник.
+async fn run_room_mixer(state: AppState, run: RoomMixingRun) {
+    let mut interval = tokio::time::interval(MIX_INTERVAL);
+    interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    loop {
+        interval.tick().await;
+        if !mix_room_step(&state, &run).await {
+            break;
+        }
+    }
+
+    tracing::debug!(
+        server_id = %run.server_id(),
+        room_id = %run.room_id(),
+        "voice room audio mixer stopped"
+    );
+}
+
+/// Выполняет один шаг сведения и рассылает смеси; `false`, если запуск сведения больше не действует.
+pub(super) async fn mix_room_step(state: &AppState, run: &RoomMixingRun) -> bool {
+    let (server_id, room_id) = (run.server_id(), run.room_id());
+    let participants = state
+        .voice_presence_store
+        .room_participants(VoicePresenceTargetKind::Server, &server_id, &room_id)
+        .await
+        .iter()
+        .map(|presence| MixParticipant {
+            session_id: presence.session_id,
+            user_id: presence.user_id,
+            deafened: presence.server_deafened,
+        })
+        .collect::<Vec<_>>();
+    let Some(frames) = state
+        .voice_presence_store
+        .mix_room_tick(run, &participants)
+        .await
+    else {
+        return false;
+    };
+    for frame in frames {
+        let bytes = match frame.datagram.encode() {
+            Ok(bytes) => Bytes::from(bytes),
+            Err(error) => {
+                tracing::warn!(
+                    server_id = %server_id,
+                    room_id = %room_id,
+                    %error,
+                    "failed to encode mixed voice datagram"
+                );
+                continue;
+            }
+        };
+        state
+            .realtime_hub
+            .fanout_datagram_to_sessions(&[frame.session_id], bytes)
+            .await;
+    }
+    true
+}
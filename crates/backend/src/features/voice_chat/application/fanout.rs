@@ -1,7 +1,8 @@
 //! Рассылка снимков голосового присутствия участникам комнат.
 
 use cheenhub_contracts::realtime::{
-    RealtimeKind, RealtimeModule, VoiceChatKind, VoiceRoomParticipant, VoiceRoomSnapshot,
+    RealtimeKind, RealtimeModule, VoiceChatKind, VoiceRoomParticipant, VoiceRoomRecordingStatus,
+    VoiceRoomSnapshot,
 };
 use uuid::Uuid;
 
@@ -11,6 +12,9 @@ use crate::features::voice_chat::infrastructure::{
 };
 use crate::state::AppState;
 
+use super::announce_video_codec_change;
+use super::recording::stop_abandoned_recording;
+
 pub(super) async fn fanout_removed_rooms(
     state: &AppState,
     removed: Vec<VoicePresence>,
@@ -38,18 +42,46 @@ pub(super) async fn room_snapshot(
     let participants = state
         .voice_presence_store
         .room_participants(target.kind, &target.server_id, &target.room_id)
-        .await
-        .iter()
-        .map(participant_summary)
-        .collect();
+        .await;
+    // Снимок строится после каждого изменения состава, поэтому здесь же
+    // завершается запись комнаты, из которой вышел последний участник.
+    if participants.is_empty() && target.kind == VoicePresenceTargetKind::Server {
+        stop_abandoned_recording(state, target.server_id, target.room_id).await;
+    }
+    let participants = participant_summaries(state, &participants).await;
 
     VoiceRoomSnapshot {
         server_id: target.route_id().to_string(),
         room_id: target.room_id.to_string(),
         participants,
+        recording: recording_status(state, target).await,
+        audio_mixing: target.kind == VoicePresenceTargetKind::Server
+            && state
+                .voice_presence_store
+                .room_mixing_enabled(target.server_id, target.room_id)
+                .await,
     }
 }
 
+/// Возвращает видимое участникам состояние идущей записи комнаты.
+pub(super) async fn recording_status(
+    state: &AppState,
+    target: VoicePresenceTarget,
+) -> Option<VoiceRoomRecordingStatus> {
+    if target.kind != VoicePresenceTargetKind::Server {
+        return None;
+    }
+    state
+        .voice_presence_store
+        .room_recording(target.server_id, target.room_id)
+        .await
+        .map(|recording| VoiceRoomRecordingStatus {
+            recording_id: recording.id.to_string(),
+            started_by_user_id: recording.started_by_user_id.to_string(),
+            started_at: recording.started_at.to_rfc3339(),
+        })
+}
+
 pub(super) async fn fanout_snapshot(
     state: &AppState,
     target: VoicePresenceTarget,
@@ -103,6 +135,7 @@ pub(super) async fn fanout_snapshot(
             snapshot,
         )
         .await;
+    announce_video_codec_change(state, target).await;
 }
 
 pub(super) fn server_voice_target(server_id: Uuid, room_id: Uuid) -> VoicePresenceTarget {
@@ -139,11 +172,33 @@ async fn direct_message_user_ids(
     }
 }
 
-pub(super) fn participant_summary(presence: &VoicePresence) -> VoiceRoomParticipant {
+/// Собирает публичные описания участников вместе с оценками качества их соединения.
+pub(super) async fn participant_summaries(
+    state: &AppState,
+    participants: &[VoicePresence],
+) -> Vec<VoiceRoomParticipant> {
+    let qualities = state
+        .voice_presence_store
+        .connection_qualities(participants)
+        .await;
+    participants
+        .iter()
+        .zip(qualities)
+        .map(|(presence, connection_quality)| participant_summary(presence, connection_quality))
+        .collect()
+}
+
+fn participant_summary(
+    presence: &VoicePresence,
+    connection_quality: Option<u8>,
+) -> VoiceRoomParticipant {
     VoiceRoomParticipant {
         user_id: presence.user_id.to_string(),
         nickname: presence.nickname.clone(),
         avatar_url: presence.avatar_url.clone(),
         joined_at: presence.joined_at.to_rfc3339(),
+        connection_quality,
+        server_muted: presence.server_muted,
+        server_deafened: presence.server_deafened,
     }
 }
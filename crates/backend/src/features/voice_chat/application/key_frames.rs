@@ -0,0 +1,119 @@
+//! Пересылка запросов ключевых кадров отправителям видео.
+
+use cheenhub_contracts::realtime::{
+    RealtimeKind, RealtimeModule, RequestVoiceKeyFrame, VoiceChatKind, VoiceKeyFrameRequested,
+    VoiceVideoStreamSource,
+};
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::VoicePresence;
+use crate::state::AppState;
+
+use super::{VoiceChatApplicationError, active_presence_for_user, parse_id};
+
+/// Пересылает отправителю видео запрос ключевого кадра от участника той же комнаты.
+///
+/// Частые запросы одной публикации схлопываются, поэтому отправитель получает
+/// не больше одного запроса за интервал независимо от числа зрителей.
+pub(crate) async fn request_key_frame(
+    state: &AppState,
+    realtime_stream_id: Uuid,
+    session_id: Uuid,
+    user_id: &Uuid,
+    request: RequestVoiceKeyFrame,
+) -> Result<(), VoiceChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    let publisher_user_id = parse_id(&request.publisher_user_id, "Пользователь не найден.")?;
+    let Some(presence) = active_presence_for_user(state, &room_id, user_id).await else {
+        return Err(VoiceChatApplicationError::NotFound(
+            "Пользователь не находится в этой голосовой комнате.".to_owned(),
+        ));
+    };
+    if presence.server_id != server_id || presence.room_id != room_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Комната не найдена.".to_owned(),
+        ));
+    }
+    if presence.realtime_stream_id != realtime_stream_id || presence.session_id != session_id {
+        return Err(VoiceChatApplicationError::Unauthorized(
+            "Запрос принадлежит другой realtime-сессии.".to_owned(),
+        ));
+    }
+    if publisher_user_id == *user_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Нельзя запросить ключевой кадр у собственного видеопотока.".to_owned(),
+        ));
+    }
+
+    let Some(publisher) = state
+        .voice_presence_store
+        .room_presence_for_user(presence.target_kind, &room_id, &publisher_user_id)
+        .await
+        .filter(|publisher| publisher.server_id == server_id)
+    else {
+        tracing::debug!(
+            server_id = %server_id,
+            room_id = %room_id,
+            user_id = %user_id,
+            publisher_user_id = %publisher_user_id,
+            source = ?request.source,
+            "ignored key frame request for participant outside voice room"
+        );
+        return Ok(());
+    };
+    if !forward_key_frame_request(state, &publisher, request.source).await {
+        tracing::trace!(
+            server_id = %server_id,
+            room_id = %room_id,
+            user_id = %user_id,
+            publisher_user_id = %publisher_user_id,
+            source = ?request.source,
+            "collapsed key frame request into recent one"
+        );
+        return Ok(());
+    }
+
+    tracing::debug!(
+        server_id = %server_id,
+        room_id = %room_id,
+        target_kind = ?presence.target_kind,
+        user_id = %user_id,
+        publisher_user_id = %publisher_user_id,
+        source = ?request.source,
+        "forwarded key frame request to video publisher"
+    );
+
+    Ok(())
+}
+
+/// Пересылает отправителю запрос ключевого кадра, если его не просили недавно.
+pub(super) async fn forward_key_frame_request(
+    state: &AppState,
+    publisher: &VoicePresence,
+    source: VoiceVideoStreamSource,
+) -> bool {
+    if !state
+        .voice_presence_store
+        .admit_key_frame_request(publisher, source)
+        .await
+    {
+        return false;
+    }
+
+    state
+        .realtime_hub
+        .fanout_to_streams(
+            RealtimeModule::VoiceChat,
+            &publisher.server_id,
+            RealtimeKind::VoiceChat(VoiceChatKind::KeyFrameRequested),
+            &[publisher.realtime_stream_id],
+            VoiceKeyFrameRequested {
+                server_id: publisher.server_id.to_string(),
+                room_id: publisher.room_id.to_string(),
+                source,
+            },
+        )
+        .await;
+    true
+}
@@ -0,0 +1,55 @@
+//! Применение настроек голоса и видео сервера к relay голосовых комнат.
+
+use uuid::Uuid;
+
+use crate::features::servers::infrastructure::ServerMediaSettings;
+use crate::features::voice_chat::audio_policy::RoomAudioPolicy;
+use crate::features::voice_chat::media_policy::RoomVideoPolicy;
+use crate::state::AppState;
+
+use super::VoiceChatApplicationError;
+
+/// Обновляет ограничения аудио и видео, которые relay применяет к комнатам сервера.
+pub(crate) async fn apply_server_media_settings(state: &AppState, settings: &ServerMediaSettings) {
+    state
+        .voice_presence_store
+        .set_server_audio_policy(
+            settings.server_id,
+            RoomAudioPolicy {
+                max_bitrate_bps: settings.audio_bitrate_bps,
+            },
+        )
+        .await;
+    state
+        .voice_presence_store
+        .set_server_video_policy(
+            settings.server_id,
+            RoomVideoPolicy {
+                allowed_presets: settings.allowed_video_presets.clone(),
+                max_publishers: Some(settings.max_video_publishers),
+            },
+        )
+        .await;
+    tracing::debug!(
+        server_id = %settings.server_id,
+        audio_bitrate_bps = settings.audio_bitrate_bps,
+        allowed_video_presets = ?settings.allowed_video_presets,
+        max_video_publishers = settings.max_video_publishers,
+        "applied server media settings to voice relay"
+    );
+}
+
+/// Загружает сохраненные настройки сервера перед входом в его голосовую комнату.
+pub(super) async fn refresh_server_media_settings(
+    state: &AppState,
+    server_id: &Uuid,
+) -> Result<(), VoiceChatApplicationError> {
+    let settings = state
+        .server_store
+        .find_server_media_settings(server_id)
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?
+        .unwrap_or_else(|| ServerMediaSettings::defaults(*server_id));
+    apply_server_media_settings(state, &settings).await;
+    Ok(())
+}
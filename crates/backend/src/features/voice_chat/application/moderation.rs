@@ -0,0 +1,99 @@
+//! Серверная модерация микрофона и звука участников голосовых комнат.
+
+use cheenhub_contracts::realtime::{
+    ServerDeafenVoiceMember, ServerMuteVoiceMember, VoiceRoomSnapshot,
+};
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::VoiceModeration;
+use crate::state::AppState;
+
+use super::access::user_can_moderate_voice;
+use super::fanout::{fanout_snapshot, room_snapshot, server_voice_target};
+use super::{VoiceChatApplicationError, parse_id};
+
+/// Отключает или возвращает микрофон участнику голосовой комнаты.
+pub(crate) async fn server_mute_member(
+    state: &AppState,
+    moderator_user_id: &Uuid,
+    request: ServerMuteVoiceMember,
+) -> Result<VoiceRoomSnapshot, VoiceChatApplicationError> {
+    let muted = request.muted;
+    moderate_member(
+        state,
+        moderator_user_id,
+        (&request.server_id, &request.room_id, &request.user_id),
+        "server-muted voice member",
+        move |moderation| moderation.muted = muted,
+    )
+    .await
+}
+
+/// Отключает или возвращает звук участнику голосовой комнаты.
+pub(crate) async fn server_deafen_member(
+    state: &AppState,
+    moderator_user_id: &Uuid,
+    request: ServerDeafenVoiceMember,
+) -> Result<VoiceRoomSnapshot, VoiceChatApplicationError> {
+    let deafened = request.deafened;
+    moderate_member(
+        state,
+        moderator_user_id,
+        (&request.server_id, &request.room_id, &request.user_id),
+        "server-deafened voice member",
+        move |moderation| moderation.deafened = deafened,
+    )
+    .await
+}
+
+async fn moderate_member(
+    state: &AppState,
+    moderator_user_id: &Uuid,
+    (server_id, room_id, user_id): (&str, &str, &str),
+    action: &'static str,
+    moderate: impl FnOnce(&mut VoiceModeration),
+) -> Result<VoiceRoomSnapshot, VoiceChatApplicationError> {
+    let server_id = parse_id(server_id, "Сервер не найден.")?;
+    let room_id = parse_id(room_id, "Комната не найдена.")?;
+    let target_user_id = parse_id(user_id, "Пользователь не найден.")?;
+
+    if *moderator_user_id == target_user_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Нельзя модерировать самого себя.".to_owned(),
+        ));
+    }
+
+    if !user_can_moderate_voice(state, moderator_user_id, &server_id)
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?
+    {
+        return Err(VoiceChatApplicationError::Unauthorized(
+            "Недостаточно прав для модерации голосовой комнаты.".to_owned(),
+        ));
+    }
+
+    let Some(moderation) = state
+        .voice_presence_store
+        .moderate_user_in_room(&target_user_id, &server_id, &room_id, moderate)
+        .await
+    else {
+        return Err(VoiceChatApplicationError::NotFound(
+            "Пользователь не находится в этой голосовой комнате.".to_owned(),
+        ));
+    };
+
+    tracing::info!(
+        server_id = %server_id,
+        room_id = %room_id,
+        moderator_user_id = %moderator_user_id,
+        user_id = %target_user_id,
+        server_muted = moderation.muted,
+        server_deafened = moderation.deafened,
+        "{action}"
+    );
+    let target = server_voice_target(server_id, room_id);
+    let snapshot = room_snapshot(state, target).await;
+    fanout_snapshot(state, target, snapshot.clone()).await;
+
+    Ok(snapshot)
+}
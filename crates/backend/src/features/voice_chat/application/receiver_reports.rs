@@ -0,0 +1,134 @@
+//! Прием отчетов получателей медиа и рассылка оценок качества соединения.
+
+use cheenhub_contracts::realtime::{
+    RealtimeKind, RealtimeModule, ReceiverReport, VoiceChatKind, VoiceUplinkLossReported,
+};
+use uuid::Uuid;
+
+use crate::features::voice_chat::connection_quality::ReceptionSample;
+use crate::features::voice_chat::infrastructure::VoicePresence;
+use crate::state::AppState;
+
+use super::{
+    VoiceChatApplicationError, active_presence_for_user, fanout_snapshot, parse_id, room_snapshot,
+};
+
+/// Учитывает отчет получателя и рассылает комнате снимок, если оценка участника заметно изменилась.
+///
+/// Отправителям с заметно изменившимися потерями приходит адресное событие,
+/// по которому их кодер подстраивает избыточность FEC.
+///
+/// Отчеты идут по сетевому realtime-потоку, поэтому принадлежность проверяется
+/// по WebTransport-сессии, а не по потоку голосового модуля.
+pub(crate) async fn record_receiver_report(
+    state: &AppState,
+    session_id: Uuid,
+    user_id: &Uuid,
+    report: ReceiverReport,
+) -> Result<(), VoiceChatApplicationError> {
+    let server_id = parse_id(&report.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&report.room_id, "Комната не найдена.")?;
+    let Some(presence) = active_presence_for_user(state, &room_id, user_id).await else {
+        return Err(VoiceChatApplicationError::NotFound(
+            "Пользователь не находится в этой голосовой комнате.".to_owned(),
+        ));
+    };
+    if presence.server_id != server_id || presence.room_id != room_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Комната не найдена.".to_owned(),
+        ));
+    }
+    if presence.session_id != session_id {
+        return Err(VoiceChatApplicationError::Unauthorized(
+            "Отчет принадлежит другой realtime-сессии.".to_owned(),
+        ));
+    }
+
+    let participants = state
+        .voice_presence_store
+        .room_participants(presence.target_kind, &server_id, &room_id)
+        .await;
+    let samples = report
+        .senders
+        .into_iter()
+        .filter_map(|sender| {
+            let sender_user_id = Uuid::parse_str(&sender.user_id).ok()?;
+            let in_room = sender_user_id != *user_id
+                && participants
+                    .iter()
+                    .any(|participant| participant.user_id == sender_user_id);
+            in_room.then_some((
+                sender_user_id,
+                ReceptionSample {
+                    received_frames: sender.received_frames,
+                    lost_frames: sender.lost_frames,
+                    late_frames: sender.late_frames,
+                    jitter_ms: sender.jitter_ms,
+                },
+            ))
+        })
+        .collect::<Vec<_>>();
+    if samples.is_empty() {
+        return Ok(());
+    }
+
+    tracing::trace!(
+        server_id = %server_id,
+        room_id = %room_id,
+        user_id = %user_id,
+        senders = samples.len(),
+        "recorded voice receiver report"
+    );
+    let outcome = state
+        .voice_presence_store
+        .record_receiver_report(&presence, samples)
+        .await;
+    for (sender_user_id, loss_percent) in outcome.uplink_losses {
+        let Some(sender) = participants
+            .iter()
+            .find(|participant| participant.user_id == sender_user_id)
+        else {
+            continue;
+        };
+        announce_uplink_loss(state, sender, loss_percent).await;
+    }
+    if !outcome.quality_changed {
+        return Ok(());
+    }
+
+    let target = presence.target();
+    let snapshot = room_snapshot(state, target).await;
+    tracing::debug!(
+        server_id = %server_id,
+        room_id = %room_id,
+        target_kind = ?presence.target_kind,
+        "voice participant connection quality changed"
+    );
+    fanout_snapshot(state, target, snapshot).await;
+
+    Ok(())
+}
+
+async fn announce_uplink_loss(state: &AppState, sender: &VoicePresence, loss_percent: u8) {
+    tracing::debug!(
+        server_id = %sender.server_id,
+        room_id = %sender.room_id,
+        user_id = %sender.user_id,
+        loss_percent,
+        "announcing voice uplink loss to sender"
+    );
+    state
+        .realtime_hub
+        .fanout_to_streams(
+            RealtimeModule::VoiceChat,
+            &sender.server_id,
+            RealtimeKind::VoiceChat(VoiceChatKind::UplinkLossReported),
+            &[sender.realtime_stream_id],
+            VoiceUplinkLossReported {
+                server_id: sender.server_id.to_string(),
+                room_id: sender.room_id.to_string(),
+                loss_percent,
+            },
+        )
+        .await;
+}
@@ -0,0 +1,348 @@
+//! Запуск, остановка и выдача записей голосовых комнат сервера.
+
+use cheenhub_contracts::media::MediaDatagram;
+use cheenhub_contracts::realtime::{
+    StartVoiceRoomRecording, StopVoiceRoomRecording, VoiceRoomSnapshot,
+};
+use cheenhub_contracts::rest::{
+    ListVoiceRecordingsResponse, VoiceRecordingSummary, VoiceRecordingTrack,
+};
+use futures_util::future::join_all;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::{VoicePresence, VoicePresenceTargetKind};
+use crate::features::voice_chat::recording::{
+    RECORDING_WRITE_QUEUE, RecordingStartError, RoomRecording,
+};
+use crate::state::AppState;
+
+use super::access::{user_can_record_voice, user_has_server_access};
+use super::fanout::{fanout_snapshot, room_snapshot, server_voice_target};
+use super::key_frames::forward_key_frame_request;
+use super::{VoiceChatApplicationError, ensure_room_voice_available, parse_id};
+
+mod archive;
+
+use archive::{
+    MANIFEST_FILE_NAME, RecordingManifest, index_key, index_prefix, recording_prefix,
+    spawn_recording_writer,
+};
+
+/// Сколько записей отдает одна страница списка.
+const RECORDINGS_PAGE_SIZE: usize = 50;
+
+/// Запускает запись голосовой комнаты по запросу участника с правом записи.
+pub(crate) async fn start_recording(
+    state: &AppState,
+    user_id: &Uuid,
+    request: StartVoiceRoomRecording,
+) -> Result<VoiceRoomSnapshot, VoiceChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    ensure_room_voice_available(state, user_id, &server_id, &room_id).await?;
+    ensure_can_record(state, user_id, &server_id).await?;
+    if !state.voice_recording_archive.is_configured() {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Запись голосовых комнат не настроена на этом сервере.".to_owned(),
+        ));
+    }
+    let target = server_voice_target(server_id, room_id);
+    if state
+        .voice_presence_store
+        .room_participants(target.kind, &server_id, &room_id)
+        .await
+        .is_empty()
+    {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "В голосовой комнате нет участников.".to_owned(),
+        ));
+    }
+    let (sink, receiver) = mpsc::channel(RECORDING_WRITE_QUEUE);
+    let recording = state
+        .voice_presence_store
+        .start_room_recording(server_id, room_id, *user_id, sink)
+        .await
+        .map_err(|error| {
+            VoiceChatApplicationError::BadRequest(
+                match error {
+                    RecordingStartError::AlreadyRecording => "Комната уже записывается.",
+                    RecordingStartError::TooManyRecordings => {
+                        "Сейчас идет слишком много записей. Попробуй позже."
+                    }
+                }
+                .to_owned(),
+            )
+        })?;
+    spawn_recording_writer(
+        state.voice_recording_archive.clone(),
+        server_id,
+        room_id,
+        recording.clone(),
+        receiver,
+    );
+
+    tracing::info!(
+        server_id = %server_id,
+        room_id = %room_id,
+        recording_id = %recording.id,
+        user_id = %user_id,
+        "started voice room recording"
+    );
+    let snapshot = room_snapshot(state, target).await;
+    fanout_snapshot(state, target, snapshot.clone()).await;
+
+    Ok(snapshot)
+}
+
+/// Останавливает запись голосовой комнаты; хвосты файлов и манифест дописываются в фоне.
+pub(crate) async fn stop_recording(
+    state: &AppState,
+    user_id: &Uuid,
+    request: StopVoiceRoomRecording,
+) -> Result<VoiceRoomSnapshot, VoiceChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    ensure_can_record(state, user_id, &server_id).await?;
+    let Some(recording) = state
+        .voice_presence_store
+        .take_room_recording(server_id, room_id)
+        .await
+    else {
+        return Err(VoiceChatApplicationError::NotFound(
+            "Комната сейчас не записывается.".to_owned(),
+        ));
+    };
+
+    finish_recording(recording);
+
+    let target = server_voice_target(server_id, room_id);
+    let snapshot = room_snapshot(state, target).await;
+    fanout_snapshot(state, target, snapshot.clone()).await;
+
+    Ok(snapshot)
+}
+
+/// Завершает запись опустевшей комнаты, чтобы она не висела без участников.
+pub(super) async fn stop_abandoned_recording(state: &AppState, server_id: Uuid, room_id: Uuid) {
+    let Some(recording) = state
+        .voice_presence_store
+        .take_room_recording(server_id, room_id)
+        .await
+    else {
+        return;
+    };
+    tracing::info!(
+        server_id = %server_id,
+        room_id = %room_id,
+        recording_id = %recording.id,
+        "stopped voice room recording after room became empty"
+    );
+    finish_recording(recording);
+}
+
+fn finish_recording(recording: RoomRecording) {
+    tokio::spawn(recording.finish());
+}
+
+/// Передает ретранслируемую датаграмму идущей записи комнаты отправителя.
+///
+/// Если записи нужен ключевой кадр, запрос уходит отправителю через общий
+/// ограничитель частоты запросов ключевых кадров.
+pub(crate) async fn record_room_media(
+    state: &AppState,
+    presence: &VoicePresence,
+    datagram: &MediaDatagram,
+) {
+    if presence.target_kind != VoicePresenceTargetKind::Server {
+        return;
+    }
+    let Some(source) = state
+        .voice_presence_store
+        .record_room_media(presence, datagram)
+        .await
+    else {
+        return;
+    };
+    if forward_key_frame_request(state, presence, source).await {
+        tracing::debug!(
+            server_id = %presence.server_id,
+            room_id = %presence.room_id,
+            publisher_user_id = %presence.user_id,
+            source = ?source,
+            "requested key frame for voice room recording"
+        );
+    }
+}
+
+/// Перечисляет завершенные записи голосовых комнат сервера от новых к старым.
+///
+/// Записи читаются из индекса сервера страницами; `before_recording_id`
+/// продолжает список после уже полученной записи.
+pub(crate) async fn list_recordings(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: String,
+    before_recording_id: Option<String>,
+) -> Result<ListVoiceRecordingsResponse, VoiceChatApplicationError> {
+    let server_id = parse_id(&server_id, "Сервер не найден.")?;
+    ensure_server_access(state, user_id, &server_id).await?;
+    let start_after = match before_recording_id {
+        Some(recording_id) => {
+            let recording_id = parse_id(&recording_id, "Запись не найдена.")?;
+            let manifest = read_manifest(state, &server_id, &recording_id)
+                .await
+                .ok_or_else(|| {
+                    VoiceChatApplicationError::NotFound("Запись не найдена.".to_owned())
+                })?;
+            Some(index_key(&manifest))
+        }
+        None => None,
+    };
+
+    let mut keys = state
+        .voice_recording_archive
+        .list_objects(
+            &index_prefix(&server_id),
+            start_after.as_deref(),
+            RECORDINGS_PAGE_SIZE + 1,
+        )
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?;
+    let has_more = keys.len() > RECORDINGS_PAGE_SIZE;
+    keys.truncate(RECORDINGS_PAGE_SIZE);
+    let entries = join_all(
+        keys.iter()
+            .map(|key| state.voice_recording_archive.get_object(key)),
+    )
+    .await;
+
+    let mut recordings = Vec::with_capacity(entries.len());
+    for (key, entry) in keys.iter().zip(entries) {
+        let bytes = entry.map_err(VoiceChatApplicationError::Internal)?;
+        match serde_json::from_slice::<RecordingManifest>(&bytes) {
+            Ok(manifest) => recordings.push(recording_summary(state, manifest)),
+            Err(error) => {
+                tracing::warn!(%key, %error, "skipping unreadable voice recording index entry");
+            }
+        }
+    }
+
+    Ok(ListVoiceRecordingsResponse {
+        recordings,
+        has_more,
+    })
+}
+
+/// Возвращает MIME-тип и байты одного файла завершенной записи.
+pub(crate) async fn recording_file(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: String,
+    recording_id: String,
+    file_name: String,
+) -> Result<(String, Vec<u8>), VoiceChatApplicationError> {
+    let server_id = parse_id(&server_id, "Сервер не найден.")?;
+    let recording_id = parse_id(&recording_id, "Запись не найдена.")?;
+    ensure_server_access(state, user_id, &server_id).await?;
+
+    let not_found = || VoiceChatApplicationError::NotFound("Запись не найдена.".to_owned());
+    let manifest = read_manifest(state, &server_id, &recording_id)
+        .await
+        .ok_or_else(not_found)?;
+    // Имя файла сверяется с манифестом, поэтому в ключ попадают только имена, созданные сервером.
+    let track = manifest
+        .tracks
+        .into_iter()
+        .find(|track| track.file_name == file_name)
+        .ok_or_else(not_found)?;
+    let bytes = state
+        .voice_recording_archive
+        .get_object(&format!(
+            "{}/{}",
+            recording_prefix(&server_id, &recording_id),
+            track.file_name
+        ))
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?;
+
+    Ok((track.content_type, bytes))
+}
+
+async fn read_manifest(
+    state: &AppState,
+    server_id: &Uuid,
+    recording_id: &Uuid,
+) -> Option<RecordingManifest> {
+    let bytes = state
+        .voice_recording_archive
+        .get_object(&format!(
+            "{}/{MANIFEST_FILE_NAME}",
+            recording_prefix(server_id, recording_id)
+        ))
+        .await
+        .ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn recording_summary(state: &AppState, manifest: RecordingManifest) -> VoiceRecordingSummary {
+    let base_url = state.cheenhub_api_base_url.trim_end_matches('/');
+    VoiceRecordingSummary {
+        id: manifest.id.to_string(),
+        server_id: manifest.server_id.to_string(),
+        room_id: manifest.room_id.to_string(),
+        started_by_user_id: manifest.started_by_user_id.to_string(),
+        started_at: manifest.started_at.to_rfc3339(),
+        finished_at: manifest.finished_at.to_rfc3339(),
+        tracks: manifest
+            .tracks
+            .into_iter()
+            .map(|track| VoiceRecordingTrack {
+                user_id: track.user_id.to_string(),
+                nickname: track.nickname,
+                kind: track.kind,
+                url: format!(
+                    "{base_url}/servers/{}/voice-recordings/{}/files/{}",
+                    manifest.server_id, manifest.id, track.file_name
+                ),
+                file_name: track.file_name,
+                content_type: track.content_type,
+                byte_size: track.byte_size,
+            })
+            .collect(),
+    }
+}
+
+async fn ensure_can_record(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &Uuid,
+) -> Result<(), VoiceChatApplicationError> {
+    if user_can_record_voice(state, user_id, server_id)
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?
+    {
+        Ok(())
+    } else {
+        Err(VoiceChatApplicationError::Unauthorized(
+            "Недостаточно прав для записи голосовой комнаты.".to_owned(),
+        ))
+    }
+}
+
+async fn ensure_server_access(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &Uuid,
+) -> Result<(), VoiceChatApplicationError> {
+    if user_has_server_access(state, user_id, server_id)
+        .await
+        .map_err(VoiceChatApplicationError::Internal)?
+    {
+        Ok(())
+    } else {
+        Err(VoiceChatApplicationError::Unauthorized(
+            "Нет доступа к этому серверу.".to_owned(),
+        ))
+    }
+}
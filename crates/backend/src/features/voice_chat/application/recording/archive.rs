@@ -0,0 +1,219 @@
+//! Раскладка записей в хранилище и задача, сохраняющая их файлы по мере записи.
+
+use std::sync::Arc;
+
+use cheenhub_contracts::rest::VoiceRecordingTrackKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::{
+    VoiceRecordingArchive, VoiceRecordingObjectWriter,
+};
+use crate::features::voice_chat::recording::{RecordedFile, RecordingWrite, RoomRecordingInfo};
+
+pub(super) const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Сохраненное описание завершенной записи рядом с ее файлами и в индексе сервера.
+#[derive(Serialize, Deserialize)]
+pub(super) struct RecordingManifest {
+    pub(super) id: Uuid,
+    pub(super) server_id: Uuid,
+    pub(super) room_id: Uuid,
+    pub(super) started_by_user_id: Uuid,
+    pub(super) started_at: DateTime<Utc>,
+    pub(super) finished_at: DateTime<Utc>,
+    pub(super) tracks: Vec<RecordingManifestTrack>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(super) struct RecordingManifestTrack {
+    pub(super) user_id: Uuid,
+    pub(super) nickname: String,
+    pub(super) kind: VoiceRecordingTrackKind,
+    pub(super) file_name: String,
+    pub(super) content_type: String,
+    pub(super) byte_size: u64,
+}
+
+/// Файл записи, открытый в хранилище.
+struct OpenFile {
+    file: RecordedFile,
+    writer: Option<Box<dyn VoiceRecordingObjectWriter>>,
+    byte_size: u64,
+}
+
+/// Запускает задачу, которая дописывает части файлов записи в хранилище и
+/// после остановки сохраняет манифест и запись индекса сервера.
+pub(super) fn spawn_recording_writer(
+    archive: Arc<dyn VoiceRecordingArchive>,
+    server_id: Uuid,
+    room_id: Uuid,
+    recording: RoomRecordingInfo,
+    receiver: mpsc::Receiver<RecordingWrite>,
+) {
+    tokio::spawn(async move {
+        let recording_id = recording.id;
+        if let Err(error) =
+            write_recording(archive.as_ref(), server_id, room_id, recording, receiver).await
+        {
+            tracing::error!(
+                server_id = %server_id,
+                room_id = %room_id,
+                recording_id = %recording_id,
+                %error,
+                "failed to save voice room recording"
+            );
+        }
+    });
+}
+
+async fn write_recording(
+    archive: &dyn VoiceRecordingArchive,
+    server_id: Uuid,
+    room_id: Uuid,
+    recording: RoomRecordingInfo,
+    mut receiver: mpsc::Receiver<RecordingWrite>,
+) -> anyhow::Result<()> {
+    let prefix = recording_prefix(&server_id, &recording.id);
+    let mut files: Vec<OpenFile> = Vec::new();
+    let finished_at = loop {
+        match receiver.recv().await {
+            Some(RecordingWrite::Chunk { file, bytes }) => {
+                append_chunk(archive, &prefix, &mut files, file, bytes).await;
+            }
+            Some(RecordingWrite::Finished { finished_at }) => break Some(finished_at),
+            None => break None,
+        }
+    };
+
+    let mut tracks = Vec::new();
+    for open in files {
+        let Some(writer) = open.writer else {
+            continue;
+        };
+        if let Err(error) = writer.finish().await {
+            tracing::warn!(
+                recording_id = %recording.id,
+                file_name = %open.file.file_name,
+                %error,
+                "failed to finish voice room recording file"
+            );
+            continue;
+        }
+        tracks.push(RecordingManifestTrack {
+            user_id: open.file.user_id,
+            nickname: open.file.nickname,
+            kind: open.file.kind,
+            file_name: open.file.file_name,
+            content_type: open.file.content_type.to_owned(),
+            byte_size: open.byte_size,
+        });
+    }
+    let Some(finished_at) = finished_at else {
+        anyhow::bail!("recording was dropped before it was stopped");
+    };
+
+    let manifest = RecordingManifest {
+        id: recording.id,
+        server_id,
+        room_id,
+        started_by_user_id: recording.started_by_user_id,
+        started_at: recording.started_at,
+        finished_at,
+        tracks,
+    };
+    let bytes = serde_json::to_vec(&manifest)?;
+    archive
+        .put_object(
+            &format!("{prefix}/{MANIFEST_FILE_NAME}"),
+            "application/json",
+            bytes.clone(),
+        )
+        .await?;
+    // Индекс пишется последним: запись появляется в списке, только когда все ее файлы готовы.
+    archive
+        .put_object(&index_key(&manifest), "application/json", bytes)
+        .await?;
+
+    tracing::info!(
+        server_id = %manifest.server_id,
+        room_id = %manifest.room_id,
+        recording_id = %manifest.id,
+        tracks = manifest.tracks.len(),
+        "saved voice room recording"
+    );
+    Ok(())
+}
+
+async fn append_chunk(
+    archive: &dyn VoiceRecordingArchive,
+    prefix: &str,
+    files: &mut Vec<OpenFile>,
+    file: RecordedFile,
+    bytes: Vec<u8>,
+) {
+    let existing = files
+        .iter()
+        .position(|open| open.file.file_name == file.file_name);
+    let position = match existing {
+        Some(position) => position,
+        None => {
+            let key = format!("{prefix}/{}", file.file_name);
+            let writer = match archive.create_object(&key, file.content_type).await {
+                Ok(writer) => Some(writer),
+                Err(error) => {
+                    tracing::warn!(%key, %error, "failed to create voice room recording file");
+                    None
+                }
+            };
+            files.push(OpenFile {
+                file,
+                writer,
+                byte_size: 0,
+            });
+            files.len() - 1
+        }
+    };
+    let open = &mut files[position];
+    let Some(writer) = open.writer.as_mut() else {
+        return;
+    };
+    let len = bytes.len() as u64;
+    match writer.append(bytes).await {
+        Ok(()) => open.byte_size += len,
+        Err(error) => {
+            // Файл с пропущенной частью не читается, поэтому он исключается из записи.
+            tracing::warn!(
+                file_name = %open.file.file_name,
+                %error,
+                "failed to append voice room recording file"
+            );
+            open.writer = None;
+        }
+    }
+}
+
+/// Префикс индекса записей сервера: по записи на объект, от новых к старым.
+pub(super) fn index_prefix(server_id: &Uuid) -> String {
+    format!("{}/index/", server_prefix(server_id))
+}
+
+/// Ключ записи в индексе; инвертированное время начала сортирует новые записи первыми.
+pub(super) fn index_key(manifest: &RecordingManifest) -> String {
+    let inverted_ms = i64::MAX - manifest.started_at.timestamp_millis();
+    format!(
+        "{}{inverted_ms:019}-{}.json",
+        index_prefix(&manifest.server_id),
+        manifest.id
+    )
+}
+
+fn server_prefix(server_id: &Uuid) -> String {
+    format!("voice-recordings/{server_id}")
+}
+
+pub(super) fn recording_prefix(server_id: &Uuid, recording_id: &Uuid) -> String {
+    format!("{}/{recording_id}", server_prefix(server_id))
+}
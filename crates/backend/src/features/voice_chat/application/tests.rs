@@ -19,13 +19,19 @@ use crate::features::servers::infrastructure::InMemoryServerStore;
 use crate::features::social::infrastructure::InMemorySocialStore;
 use crate::features::text_chat::infrastructure::InMemoryTextChatStore;
 use crate::features::voice_chat::infrastructure::{
-    InMemoryDirectCallStore, InMemoryVoicePresenceStore,
+    InMemoryDirectCallStore, InMemoryVoicePresenceStore, InMemoryVoiceRecordingArchive,
 };
 use crate::realtime::hub::RealtimeHub;
 use crate::state::AppState;
 
+mod audio_mixing;
 mod direct_messages;
+mod key_frames;
+mod media_benchmark;
+mod moderation;
 mod nickname;
+mod recording;
+mod video_subscriptions;
 
 pub(super) fn state() -> AppState {
     AppState {
@@ -42,6 +48,12 @@ pub(super) fn state() -> AppState {
         image_store: Arc::new(
             crate::features::images::infrastructure::InMemoryImageStore::default(),
         ),
+        link_preview_store: Arc::new(
+            crate::features::link_previews::infrastructure::InMemoryLinkPreviewStore::default(),
+        ),
+        link_preview_fetcher: Arc::new(
+            crate::features::link_previews::LinkPreviewFetcher::public_only(),
+        ),
         push_notifications: Arc::new(
             crate::features::push_notifications::application::PushNotifications::disabled(
                 Arc::new(InMemoryAuthStore::default()),
@@ -50,6 +62,7 @@ pub(super) fn state() -> AppState {
         image_processing_queue: Arc::new(tokio::sync::Semaphore::new(1)),
         voice_presence_store: Arc::new(InMemoryVoicePresenceStore::default()),
         direct_call_store: Arc::new(InMemoryDirectCallStore::default()),
+        voice_recording_archive: Arc::new(InMemoryVoiceRecordingArchive::default()),
         realtime_hub: Arc::new(RealtimeHub::default()),
         auth_keys: AuthKeys::generate_for_tests(),
         access_token_lifetime_minutes: 15,
@@ -0,0 +1,83 @@
+//! Voice room audio mixing switch tests.
+
+use cheenhub_contracts::realtime::{JoinVoiceRoom, SetVoiceRoomAudioMixing};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::{create_room, registered_user, state};
+use crate::features::voice_chat::application::{
+    VoiceChatApplicationError, join_room, set_audio_mixing,
+};
+
+#[tokio::test]
+async fn owner_switches_audio_mixing_of_occupied_room() {
+    let state = state();
+    let (user, user_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+    join_room(
+        &state,
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        &user,
+        &user_id,
+        JoinVoiceRoom {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("join should succeed");
+
+    let enabled = set_audio_mixing(
+        &state,
+        &user_id,
+        SetVoiceRoomAudioMixing {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            enabled: true,
+        },
+    )
+    .await;
+    if cfg!(feature = "audio-mixing") {
+        assert!(enabled.expect("owner should enable mixing").audio_mixing);
+    } else {
+        assert!(matches!(
+            enabled,
+            Err(VoiceChatApplicationError::BadRequest(_))
+        ));
+    }
+
+    let snapshot = set_audio_mixing(
+        &state,
+        &user_id,
+        SetVoiceRoomAudioMixing {
+            server_id,
+            room_id,
+            enabled: false,
+        },
+    )
+    .await
+    .expect("owner should disable mixing");
+    assert!(!snapshot.audio_mixing);
+}
+
+#[tokio::test]
+async fn empty_room_cannot_enable_audio_mixing() {
+    let state = state();
+    let (_, user_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+
+    let error = set_audio_mixing(
+        &state,
+        &user_id,
+        SetVoiceRoomAudioMixing {
+            server_id,
+            room_id,
+            enabled: true,
+        },
+    )
+    .await
+    .expect_err("empty room should not enable mixing");
+
+    assert!(matches!(error, VoiceChatApplicationError::BadRequest(_)));
+}
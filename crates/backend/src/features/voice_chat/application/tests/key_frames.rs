@@ -0,0 +1,62 @@
+//! Voice key frame request tests.
+
+use cheenhub_contracts::realtime::{JoinVoiceRoom, RequestVoiceKeyFrame, VoiceVideoStreamSource};
+use cheenhub_contracts::rest::ServerRoomKind;
+
+use super::{create_room, registered_user, state};
+use crate::features::voice_chat::application::{
+    VoiceChatApplicationError, join_room, request_key_frame,
+};
+
+#[tokio::test]
+async fn key_frame_request_requires_another_joined_publisher() {
+    let state = state();
+    let (user, user_id) = registered_user(&state).await;
+    let stream_id = uuid::Uuid::new_v4();
+    let session_id = uuid::Uuid::new_v4();
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+    join_room(
+        &state,
+        stream_id,
+        session_id,
+        &user,
+        &user_id,
+        JoinVoiceRoom {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("join should succeed");
+
+    let error = request_key_frame(
+        &state,
+        stream_id,
+        session_id,
+        &user_id,
+        RequestVoiceKeyFrame {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            publisher_user_id: user_id.to_string(),
+            source: VoiceVideoStreamSource::Camera,
+        },
+    )
+    .await
+    .expect_err("own video stream should be rejected");
+    assert!(matches!(error, VoiceChatApplicationError::BadRequest(_)));
+
+    request_key_frame(
+        &state,
+        stream_id,
+        session_id,
+        &user_id,
+        RequestVoiceKeyFrame {
+            server_id,
+            room_id,
+            publisher_user_id: uuid::Uuid::new_v4().to_string(),
+            source: VoiceVideoStreamSource::ScreenShare,
+        },
+    )
+    .await
+    .expect("request for absent publisher should be ignored");
+}
@@ -0,0 +1,300 @@
+//! Замер голосовой ретрансляции: пересылка против серверного сведения.
+//!
+//! Оба пути проходят через рабочий код: каждая датаграмма говорящего идет
+//! через `handle_voice_frame` (допуск, запись, поиск получателей) и
+//! `RealtimeHub::fanout_datagram_to_sessions` в WebSocket-приемники; режим
+//! сведения дополнительно выполняет `mix_room_step` на каждом шаге в 20 мс.
+//! Шаги идут в темпе реального времени, как того требует политика аудио, а в
+//! замер попадает только время работы шага. Результаты пишутся в `target/media-benchmark.md` и сведены в
+//! `docs/realtime-architecture.md`.
+//!
+//! Запуск: `cargo test --release -p cheenhub_backend --features audio-mixing
+//! media_benchmark -- --ignored`. Без `audio-mixing` сведение измеряется с
+//! синтетическим [`ToneCodec`]: это стоимость конвейера сведения без Opus.
+
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use cheenhub_contracts::media::MediaDatagram;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::state;
+use crate::features::voice_chat::application::audio_mixing::mix_room_step;
+use crate::features::voice_chat::infrastructure::VoicePresence;
+use crate::features::voice_chat::media::handle_voice_frame;
+use crate::features::voice_chat::media::mixing::{
+    MIX_FRAME_SAMPLES, MIX_INTERVAL, MixCodec, VoiceDecoder, VoiceEncoder, build_codec,
+};
+use crate::features::voice_chat::test_builders::{voice_datagram, voice_presence};
+use crate::realtime::{DatagramSink, WebSocketOutbound};
+use crate::state::AppState;
+
+/// Две секунды звука на сценарий и путь.
+const TICKS: u64 = 100;
+const BITRATE_BPS: u32 = 32_000;
+/// TOC-байт CELT 20 мс моно, код 0 (один кадр в пакете).
+const CELT_20MS_TOC: u8 = 31 << 3;
+/// Размер пакета 20 мс при [`BITRATE_BPS`].
+const PACKET_BYTES: usize = (BITRATE_BPS / 8 / 50) as usize;
+const SCENARIOS: [(usize, usize); 3] = [(10, 3), (25, 5), (50, 8)];
+
+/// Синтетический кодек для сборок без libopus.
+///
+/// Пакет размером по битрейту с корректным TOC-байтом несет только громкость
+/// синусоиды, поэтому проходит политику аудио, а декодирование и кодирование
+/// почти ничего не стоят.
+struct ToneCodec;
+
+struct ToneDecoder;
+
+struct ToneEncoder;
+
+impl MixCodec for ToneCodec {
+    fn decoder(&self) -> Option<Box<dyn VoiceDecoder>> {
+        Some(Box::new(ToneDecoder))
+    }
+
+    fn encoder(&self, _bitrate_bps: u32) -> Option<Box<dyn VoiceEncoder>> {
+        Some(Box::new(ToneEncoder))
+    }
+}
+
+impl VoiceDecoder for ToneDecoder {
+    fn decode(&mut self, packet: &[u8]) -> Option<Vec<f32>> {
+        let amplitude = f32::from(*packet.get(1)?) / f32::from(u8::MAX);
+        Some(
+            (0..MIX_FRAME_SAMPLES)
+                .map(|sample| amplitude * (sample as f32 * 0.05).sin())
+                .collect(),
+        )
+    }
+}
+
+impl VoiceEncoder for ToneEncoder {
+    fn encode(&mut self, pcm: &[f32]) -> Option<Vec<u8>> {
+        let peak = pcm
+            .iter()
+            .fold(0.0_f32, |peak, sample| peak.max(sample.abs()));
+        let mut packet = vec![0x55; PACKET_BYTES];
+        packet[0] = CELT_20MS_TOC;
+        packet[1] = (peak.min(1.0) * f32::from(u8::MAX)) as u8;
+        Some(packet)
+    }
+}
+
+struct Room {
+    server_id: Uuid,
+    room_id: Uuid,
+    participants: Vec<(Uuid, Uuid, mpsc::Receiver<WebSocketOutbound>)>,
+}
+
+/// Доставленный трафик и затраченное время одного пути.
+struct Measurement {
+    per_tick: Duration,
+    datagrams_per_receiver_tick: f64,
+    bytes_per_tick: u64,
+}
+
+async fn room(state: &AppState, participants: usize) -> Room {
+    let (server_id, room_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let mut entries = Vec::with_capacity(participants);
+    for index in 0..participants {
+        let (session_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let (outbound, receiver) = mpsc::channel(4 * TICKS as usize * participants);
+        state
+            .realtime_hub
+            .register_session(
+                session_id,
+                user_id,
+                Uuid::new_v4(),
+                DatagramSink::websocket(outbound),
+            )
+            .await;
+        state
+            .voice_presence_store
+            .join(VoicePresence {
+                session_id,
+                nickname: format!("participant-{index}"),
+                ..voice_presence(server_id, room_id, user_id)
+            })
+            .await;
+        entries.push((session_id, user_id, receiver));
+    }
+    Room {
+        server_id,
+        room_id,
+        participants: entries,
+    }
+}
+
+/// Заранее кодирует пакеты говорящих кодеком `codec`; без кодека — пакет
+/// размером по битрейту с корректным TOC-байтом.
+fn speaker_packets(
+    room_id: Uuid,
+    speakers: usize,
+    codec: Option<&Arc<dyn MixCodec>>,
+) -> Vec<Vec<MediaDatagram>> {
+    let mut encoders = (0..speakers)
+        .map(|_| codec.and_then(|codec| codec.encoder(BITRATE_BPS)))
+        .collect::<Vec<_>>();
+    (0..TICKS)
+        .map(|tick| {
+            encoders
+                .iter_mut()
+                .enumerate()
+                .map(|(index, encoder)| {
+                    let pcm = (0..MIX_FRAME_SAMPLES)
+                        .map(|sample| {
+                            let time = (tick as usize * MIX_FRAME_SAMPLES + sample) as f32;
+                            0.2 * (time * (0.01 + index as f32 * 0.003)).sin()
+                        })
+                        .collect::<Vec<_>>();
+                    let payload = encoder
+                        .as_mut()
+                        .and_then(|encoder| encoder.encode(&pcm))
+                        .unwrap_or_else(|| {
+                            let mut payload = vec![0x55; PACKET_BYTES];
+                            payload[0] = CELT_20MS_TOC;
+                            payload
+                        });
+                    voice_datagram(room_id, Uuid::nil(), tick, payload)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Опустошает очереди получателей и возвращает доставленные датаграммы и байты.
+fn drain(room: &mut Room) -> (Vec<u64>, u64) {
+    let mut bytes = 0;
+    let counts = room
+        .participants
+        .iter_mut()
+        .map(|(_, _, receiver)| {
+            let mut count = 0;
+            while let Ok(outbound) = receiver.try_recv() {
+                if let WebSocketOutbound::Datagram(datagram) = outbound {
+                    count += 1;
+                    bytes += datagram.len() as u64;
+                }
+            }
+            count
+        })
+        .collect();
+    (counts, bytes)
+}
+
+/// Измеряет пересылку или, если передан кодек, сведение этим кодеком.
+async fn measure(
+    state: &AppState,
+    participants: usize,
+    speakers: usize,
+    mixing_codec: Option<Arc<dyn MixCodec>>,
+) -> Measurement {
+    let mut room = room(state, participants).await;
+    let mut mixing = None;
+    let packets = match mixing_codec {
+        Some(codec) => {
+            state
+                .voice_presence_store
+                .replace_mixing_codec(Some(codec.clone()));
+            mixing = state
+                .voice_presence_store
+                .enable_room_mixing(room.server_id, room.room_id, BITRATE_BPS)
+                .await
+                .expect("room mixing should start");
+            assert!(mixing.is_some(), "room mixing should start");
+            speaker_packets(room.room_id, speakers, Some(&codec))
+        }
+        None => speaker_packets(room.room_id, speakers, build_codec().as_ref()),
+    };
+    let speaker_ids = room.participants[..speakers]
+        .iter()
+        .map(|(session_id, user_id, _)| (*session_id, *user_id))
+        .collect::<Vec<_>>();
+
+    let started = tokio::time::Instant::now();
+    let mut busy = Duration::ZERO;
+    for (index, tick) in (1_u32..).zip(&packets) {
+        let step = Instant::now();
+        for ((session_id, user_id), datagram) in speaker_ids.iter().zip(tick) {
+            handle_voice_frame(state, *session_id, *user_id, datagram.clone()).await;
+        }
+        if let Some(run) = &mixing {
+            assert!(mix_room_step(state, run).await);
+        }
+        busy += step.elapsed();
+        tokio::time::sleep_until(started + MIX_INTERVAL * index).await;
+    }
+
+    let (counts, bytes) = drain(&mut room);
+    for (index, count) in counts.iter().enumerate() {
+        let expected = if mixing.is_some() {
+            TICKS
+        } else {
+            TICKS * (speakers - usize::from(index < speakers)) as u64
+        };
+        assert!(
+            *count <= expected && *count + 2 >= expected,
+            "participant {index} received {count} of {expected} datagrams"
+        );
+    }
+    Measurement {
+        per_tick: busy / TICKS as u32,
+        datagrams_per_receiver_tick: counts.iter().sum::<u64>() as f64
+            / (participants as u64 * TICKS) as f64,
+        bytes_per_tick: bytes / TICKS,
+    }
+}
+
+fn row(
+    report: &mut String,
+    participants: usize,
+    speakers: usize,
+    path: &str,
+    measurement: &Measurement,
+) {
+    let _ = writeln!(
+        report,
+        "| {participants} | {speakers} | {path} | {:.1} | {:.2} | {} |",
+        measurement.per_tick.as_secs_f64() * 1e6,
+        measurement.datagrams_per_receiver_tick,
+        measurement.bytes_per_tick,
+    );
+}
+
+#[tokio::test]
+#[ignore = "benchmark harness, see module docs"]
+async fn forwarding_versus_mixing() {
+    let mut report = String::from(
+        "| Participants | Speakers | Path | us per 20 ms tick | Streams per receiver | Bytes per tick |\n\
+         |---|---|---|---|---|---|\n",
+    );
+    let (mixing_codec, mixing_path) = match build_codec() {
+        Some(codec) => (codec, "mixing"),
+        None => (
+            Arc::new(ToneCodec) as Arc<dyn MixCodec>,
+            "mixing (tone codec)",
+        ),
+    };
+    for (participants, speakers) in SCENARIOS {
+        let forwarding = measure(&state(), participants, speakers, None).await;
+        let mixing = measure(&state(), participants, speakers, Some(mixing_codec.clone())).await;
+        row(
+            &mut report,
+            participants,
+            speakers,
+            "forwarding",
+            &forwarding,
+        );
+        row(&mut report, participants, speakers, mixing_path, &mixing);
+    }
+
+    let path = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../target/media-benchmark.md"
+    );
+    std::fs::write(path, report).expect("benchmark report should be written");
+}
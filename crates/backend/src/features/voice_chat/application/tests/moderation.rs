@@ -0,0 +1,173 @@
+//! Voice room server mute and deafen tests.
+
+use cheenhub_contracts::realtime::{ServerDeafenVoiceMember, ServerMuteVoiceMember};
+use cheenhub_contracts::rest::ServerRoomKind;
+use uuid::Uuid;
+
+use super::{create_room, registered_user, state};
+use crate::features::voice_chat::application::{
+    VoiceChatApplicationError, server_deafen_member, server_mute_member,
+};
+use crate::features::voice_chat::infrastructure::{VoicePresence, VoicePresenceTargetKind};
+use crate::features::voice_chat::test_builders::voice_presence;
+use crate::state::AppState;
+
+fn member_presence(server_id: &str, room_id: &str, user_id: Uuid) -> VoicePresence {
+    voice_presence(
+        server_id.parse().expect("server id is uuid"),
+        room_id.parse().expect("room id is uuid"),
+        user_id,
+    )
+}
+
+async fn set_muted(
+    state: &AppState,
+    owner_id: &Uuid,
+    server_id: &str,
+    room_id: &str,
+    member_id: Uuid,
+    muted: bool,
+) {
+    server_mute_member(
+        state,
+        owner_id,
+        ServerMuteVoiceMember {
+            server_id: server_id.to_owned(),
+            room_id: room_id.to_owned(),
+            user_id: member_id.to_string(),
+            muted,
+        },
+    )
+    .await
+    .expect("owner should change member mute");
+}
+
+#[tokio::test]
+async fn owner_server_mutes_and_deafens_joined_member() {
+    let state = state();
+    let (_, owner_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &owner_id, "voice", ServerRoomKind::Voice).await;
+    let member_id = Uuid::new_v4();
+    state
+        .voice_presence_store
+        .join(member_presence(&server_id, &room_id, member_id))
+        .await;
+
+    let snapshot = server_mute_member(
+        &state,
+        &owner_id,
+        ServerMuteVoiceMember {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            user_id: member_id.to_string(),
+            muted: true,
+        },
+    )
+    .await
+    .expect("owner should mute member");
+    assert!(snapshot.participants[0].server_muted);
+    assert!(!snapshot.participants[0].server_deafened);
+
+    let snapshot = server_deafen_member(
+        &state,
+        &owner_id,
+        ServerDeafenVoiceMember {
+            server_id,
+            room_id,
+            user_id: member_id.to_string(),
+            deafened: true,
+        },
+    )
+    .await
+    .expect("owner should deafen member");
+    assert!(snapshot.participants[0].server_muted);
+    assert!(snapshot.participants[0].server_deafened);
+}
+
+#[tokio::test]
+async fn server_mute_requires_permission_and_joined_target() {
+    let state = state();
+    let (_, owner_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &owner_id, "voice", ServerRoomKind::Voice).await;
+    let request = ServerMuteVoiceMember {
+        server_id,
+        room_id,
+        user_id: Uuid::new_v4().to_string(),
+        muted: true,
+    };
+
+    let error = server_mute_member(&state, &Uuid::new_v4(), request.clone())
+        .await
+        .expect_err("outsider should not mute members");
+    assert!(matches!(error, VoiceChatApplicationError::Unauthorized(_)));
+
+    let error = server_mute_member(
+        &state,
+        &owner_id,
+        ServerMuteVoiceMember {
+            user_id: owner_id.to_string(),
+            ..request.clone()
+        },
+    )
+    .await
+    .expect_err("moderator should not mute themselves");
+    assert!(matches!(error, VoiceChatApplicationError::BadRequest(_)));
+
+    let error = server_mute_member(&state, &owner_id, request)
+        .await
+        .expect_err("absent member should not be muted");
+    assert!(matches!(error, VoiceChatApplicationError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn server_mute_survives_leave_and_rejoin_until_unmuted() {
+    let state = state();
+    let (_, owner_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &owner_id, "voice", ServerRoomKind::Voice).await;
+    let member_id = Uuid::new_v4();
+    let presence = member_presence(&server_id, &room_id, member_id);
+    let (server_uuid, room_uuid) = (presence.server_id, presence.room_id);
+    state.voice_presence_store.join(presence.clone()).await;
+    set_muted(&state, &owner_id, &server_id, &room_id, member_id, true).await;
+
+    state
+        .voice_presence_store
+        .leave_room(
+            &presence.realtime_stream_id,
+            VoicePresenceTargetKind::Server,
+            &server_uuid,
+            &room_uuid,
+        )
+        .await;
+    state
+        .voice_presence_store
+        .join(member_presence(&server_id, &room_id, member_id))
+        .await;
+    let rejoined = state
+        .voice_presence_store
+        .room_presence_for_user(VoicePresenceTargetKind::Server, &room_uuid, &member_id)
+        .await
+        .expect("member rejoined");
+    assert!(rejoined.server_muted);
+
+    state
+        .voice_presence_store
+        .leave_room(
+            &rejoined.realtime_stream_id,
+            VoicePresenceTargetKind::Server,
+            &server_uuid,
+            &room_uuid,
+        )
+        .await;
+    set_muted(&state, &owner_id, &server_id, &room_id, member_id, false).await;
+    state
+        .voice_presence_store
+        .join(member_presence(&server_id, &room_id, member_id))
+        .await;
+    let rejoined = state
+        .voice_presence_store
+        .room_presence_for_user(VoicePresenceTargetKind::Server, &room_uuid, &member_id)
+        .await
+        .expect("member rejoined");
+    assert!(!rejoined.server_muted);
+}
@@ -0,0 +1,271 @@
+//! Voice room recording tests.
+
+use std::time::Duration;
+
+use cheenhub_contracts::realtime::{
+    JoinVoiceRoom, LeaveVoiceRoom, StartVoiceRoomRecording, StopVoiceRoomRecording,
+};
+use cheenhub_contracts::rest::{
+    ListVoiceRecordingsResponse, ServerRoomKind, VoiceRecordingTrackKind,
+};
+use uuid::Uuid;
+
+use super::{create_room, registered_user, state};
+use crate::features::voice_chat::application::{
+    VoiceChatApplicationError, join_room, leave_room, list_recordings, record_room_media,
+    recording_file, start_recording, stop_recording,
+};
+use crate::features::voice_chat::infrastructure::VoicePresenceTargetKind;
+use crate::features::voice_chat::test_builders::voice_datagram;
+use crate::state::AppState;
+
+#[tokio::test]
+async fn recording_is_announced_saved_and_listed() {
+    let state = state();
+    let (user, user_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+    join_room(
+        &state,
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        &user,
+        &user_id,
+        JoinVoiceRoom {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("join should succeed");
+
+    let snapshot = start_recording(
+        &state,
+        &user_id,
+        StartVoiceRoomRecording {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("owner should start recording");
+    let status = snapshot.recording.expect("snapshot announces recording");
+    assert_eq!(status.started_by_user_id, user_id.to_string());
+    let error = start_recording(
+        &state,
+        &user_id,
+        StartVoiceRoomRecording {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect_err("room is already recording");
+    assert!(matches!(error, VoiceChatApplicationError::BadRequest(_)));
+
+    send_voice(&state, &room_id, &user_id).await;
+    let snapshot = stop_recording(
+        &state,
+        &user_id,
+        StopVoiceRoomRecording {
+            server_id: server_id.clone(),
+            room_id,
+        },
+    )
+    .await
+    .expect("owner should stop recording");
+    assert!(snapshot.recording.is_none());
+
+    let listed = saved_recordings(&state, &user_id, &server_id, 1).await;
+    assert!(!listed.has_more);
+    let recording = &listed.recordings[0];
+    assert_eq!(recording.id, status.recording_id);
+    assert_eq!(recording.tracks.len(), 1);
+    let track = &recording.tracks[0];
+    assert_eq!(track.kind, VoiceRecordingTrackKind::Voice);
+    assert_eq!(
+        track.url,
+        format!(
+            "http://localhost/api/servers/{server_id}/voice-recordings/{}/files/{}",
+            recording.id, track.file_name
+        )
+    );
+
+    let (content_type, bytes) = recording_file(
+        &state,
+        &user_id,
+        server_id.clone(),
+        recording.id.clone(),
+        track.file_name.clone(),
+    )
+    .await
+    .expect("recorded track should download");
+    assert_eq!(content_type, "audio/ogg");
+    assert_eq!(bytes.len() as u64, track.byte_size);
+    assert_eq!(&bytes[..4], b"OggS");
+    let error = recording_file(
+        &state,
+        &user_id,
+        server_id,
+        recording.id.clone(),
+        "manifest.json".to_owned(),
+    )
+    .await
+    .expect_err("only listed tracks are downloadable");
+    assert!(matches!(error, VoiceChatApplicationError::NotFound(_)));
+}
+
+#[tokio::test]
+async fn recording_stops_when_last_participant_leaves() {
+    let state = state();
+    let (user, user_id) = registered_user(&state).await;
+    let stream_id = Uuid::new_v4();
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+    join_room(
+        &state,
+        stream_id,
+        Uuid::new_v4(),
+        &user,
+        &user_id,
+        JoinVoiceRoom {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("join should succeed");
+    start_recording(
+        &state,
+        &user_id,
+        StartVoiceRoomRecording {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("owner should start recording");
+    send_voice(&state, &room_id, &user_id).await;
+
+    let snapshot = leave_room(
+        &state,
+        stream_id,
+        &user_id,
+        LeaveVoiceRoom {
+            server_id: server_id.clone(),
+            room_id,
+        },
+    )
+    .await
+    .expect("leave should succeed");
+
+    assert!(snapshot.recording.is_none());
+    let listed = saved_recordings(&state, &user_id, &server_id, 1).await;
+    assert_eq!(listed.recordings[0].tracks.len(), 1);
+}
+
+#[tokio::test]
+async fn recordings_are_listed_newest_first_with_cursor() {
+    let state = state();
+    let (user, user_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+    join_room(
+        &state,
+        Uuid::new_v4(),
+        Uuid::new_v4(),
+        &user,
+        &user_id,
+        JoinVoiceRoom {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("join should succeed");
+    for _ in 0..2 {
+        start_recording(
+            &state,
+            &user_id,
+            StartVoiceRoomRecording {
+                server_id: server_id.clone(),
+                room_id: room_id.clone(),
+            },
+        )
+        .await
+        .expect("owner should start recording");
+        send_voice(&state, &room_id, &user_id).await;
+        stop_recording(
+            &state,
+            &user_id,
+            StopVoiceRoomRecording {
+                server_id: server_id.clone(),
+                room_id: room_id.clone(),
+            },
+        )
+        .await
+        .expect("owner should stop recording");
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+
+    let listed = saved_recordings(&state, &user_id, &server_id, 2).await;
+    assert!(listed.recordings[0].started_at > listed.recordings[1].started_at);
+    let older = list_recordings(
+        &state,
+        &user_id,
+        server_id,
+        Some(listed.recordings[0].id.clone()),
+    )
+    .await
+    .expect("member should list older recordings");
+    assert_eq!(older.recordings.len(), 1);
+    assert_eq!(older.recordings[0].id, listed.recordings[1].id);
+    assert!(!older.has_more);
+}
+
+#[tokio::test]
+async fn empty_room_cannot_be_recorded() {
+    let state = state();
+    let (_, user_id) = registered_user(&state).await;
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+
+    let error = start_recording(
+        &state,
+        &user_id,
+        StartVoiceRoomRecording { server_id, room_id },
+    )
+    .await
+    .expect_err("empty room should not start recording");
+
+    assert!(matches!(error, VoiceChatApplicationError::BadRequest(_)));
+}
+
+/// Waits until the recording writer adds `count` recordings to the server index.
+async fn saved_recordings(
+    state: &AppState,
+    user_id: &Uuid,
+    server_id: &str,
+    count: usize,
+) -> ListVoiceRecordingsResponse {
+    for _ in 0..200 {
+        let listed = list_recordings(state, user_id, server_id.to_owned(), None)
+            .await
+            .expect("member should list recordings");
+        if listed.recordings.len() >= count {
+            assert_eq!(listed.recordings.len(), count);
+            return listed;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    panic!("recordings were not saved in time");
+}
+
+async fn send_voice(state: &AppState, room_id: &str, user_id: &Uuid) {
+    let room_id = Uuid::parse_str(room_id).expect("room id should be uuid");
+    let presence = state
+        .voice_presence_store
+        .room_presence_for_user(VoicePresenceTargetKind::Server, &room_id, user_id)
+        .await
+        .expect("user is in the room");
+    for sequence in 0..5 {
+        let datagram = voice_datagram(room_id, *user_id, sequence, vec![0x78, 0x01, 0x02]);
+        record_room_media(state, &presence, &datagram).await;
+    }
+}
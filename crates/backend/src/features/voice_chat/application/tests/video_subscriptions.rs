@@ -0,0 +1,129 @@
+//! Voice video subscription tests.
+
+use cheenhub_contracts::realtime::{
+    JoinVoiceRoom, SubscribeVoiceVideo, UnsubscribeVoiceVideo, VoiceVideoStreamSource,
+};
+use cheenhub_contracts::rest::ServerRoomKind;
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+
+use super::{create_room, registered_user, state};
+use crate::features::voice_chat::application::{
+    VoiceChatApplicationError, join_room, subscribe_video, unsubscribe_video,
+};
+
+#[tokio::test]
+async fn joined_session_receives_only_subscribed_video() {
+    let state = state();
+    let (user, user_id) = registered_user(&state).await;
+    let stream_id = uuid::Uuid::new_v4();
+    let session_id = uuid::Uuid::new_v4();
+    let publisher_user_id = uuid::Uuid::new_v4();
+    let (server_id, room_id) = create_room(&state, &user_id, "voice", ServerRoomKind::Voice).await;
+    join_room(
+        &state,
+        stream_id,
+        session_id,
+        &user,
+        &user_id,
+        JoinVoiceRoom {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+        },
+    )
+    .await
+    .expect("join should succeed");
+    let room_uuid = uuid::Uuid::parse_str(&room_id).expect("room id should be uuid");
+    let subscribed_sessions = |source| {
+        state.voice_presence_store.video_subscriber_sessions(
+            room_uuid,
+            publisher_user_id,
+            source,
+            VideoSpatialLayer::High,
+            &[VideoSpatialLayer::High],
+            vec![session_id],
+        )
+    };
+
+    let error = subscribe_video(
+        &state,
+        stream_id,
+        session_id,
+        &user_id,
+        SubscribeVoiceVideo {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            user_id: user_id.to_string(),
+            source: VoiceVideoStreamSource::Camera,
+            layer: VideoSpatialLayer::High,
+        },
+    )
+    .await
+    .expect_err("own video stream should be rejected");
+    assert!(matches!(error, VoiceChatApplicationError::BadRequest(_)));
+    let error = subscribe_video(
+        &state,
+        uuid::Uuid::new_v4(),
+        session_id,
+        &user_id,
+        SubscribeVoiceVideo {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            user_id: publisher_user_id.to_string(),
+            source: VoiceVideoStreamSource::Camera,
+            layer: VideoSpatialLayer::High,
+        },
+    )
+    .await
+    .expect_err("stale realtime stream should be rejected");
+    assert!(matches!(error, VoiceChatApplicationError::Unauthorized(_)));
+    assert!(
+        subscribed_sessions(VoiceVideoStreamSource::Camera)
+            .await
+            .is_empty()
+    );
+
+    subscribe_video(
+        &state,
+        stream_id,
+        session_id,
+        &user_id,
+        SubscribeVoiceVideo {
+            server_id: server_id.clone(),
+            room_id: room_id.clone(),
+            user_id: publisher_user_id.to_string(),
+            source: VoiceVideoStreamSource::Camera,
+            layer: VideoSpatialLayer::High,
+        },
+    )
+    .await
+    .expect("subscription should succeed");
+    assert_eq!(
+        subscribed_sessions(VoiceVideoStreamSource::Camera).await,
+        vec![session_id]
+    );
+    assert!(
+        subscribed_sessions(VoiceVideoStreamSource::ScreenShare)
+            .await
+            .is_empty()
+    );
+
+    unsubscribe_video(
+        &state,
+        stream_id,
+        session_id,
+        &user_id,
+        UnsubscribeVoiceVideo {
+            server_id,
+            room_id,
+            user_id: publisher_user_id.to_string(),
+            source: VoiceVideoStreamSource::Camera,
+        },
+    )
+    .await
+    .expect("unsubscription should succeed");
+    assert!(
+        subscribed_sessions(VoiceVideoStreamSource::Camera)
+            .await
+            .is_empty()
+    );
+}
@@ -0,0 +1,113 @@
+//! Согласование видеокодека публикаций голосовой комнаты.
+
+use cheenhub_contracts::realtime::{
+    AdvertiseVoiceCodecCapabilities, RealtimeKind, RealtimeModule, VoiceChatKind,
+    VoiceVideoCodecSelected,
+};
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::VoicePresenceTarget;
+use crate::features::voice_chat::video_codecs::VideoCodecCapabilities;
+use crate::state::AppState;
+
+use super::{VoiceChatApplicationError, active_presence_for_user, parse_id};
+
+/// Запоминает видеокодеки клиента и возвращает кодек, выбранный для комнаты.
+///
+/// Если выбор комнаты изменился, остальные участники получают событие
+/// `VideoCodecSelected`. Текущий клиент кодирует только VP9 и объявляет только
+/// его, поэтому AV1 и H.264 выбираются лишь для клиентов, которые их кодируют.
+pub(crate) async fn advertise_codec_capabilities(
+    state: &AppState,
+    realtime_stream_id: Uuid,
+    session_id: Uuid,
+    user_id: &Uuid,
+    request: AdvertiseVoiceCodecCapabilities,
+) -> Result<VoiceVideoCodecSelected, VoiceChatApplicationError> {
+    let server_id = parse_id(&request.server_id, "Сервер не найден.")?;
+    let room_id = parse_id(&request.room_id, "Комната не найдена.")?;
+    let Some(presence) = active_presence_for_user(state, &room_id, user_id).await else {
+        return Err(VoiceChatApplicationError::NotFound(
+            "Пользователь не находится в этой голосовой комнате.".to_owned(),
+        ));
+    };
+    if presence.server_id != server_id || presence.room_id != room_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Комната не найдена.".to_owned(),
+        ));
+    }
+    if presence.realtime_stream_id != realtime_stream_id || presence.session_id != session_id {
+        return Err(VoiceChatApplicationError::Unauthorized(
+            "Запрос принадлежит другой realtime-сессии.".to_owned(),
+        ));
+    }
+
+    tracing::debug!(
+        server_id = %server_id,
+        room_id = %room_id,
+        user_id = %user_id,
+        decoders = ?request.decoders,
+        encoders = ?request.encoders,
+        "recorded voice video codec capabilities"
+    );
+    state
+        .voice_presence_store
+        .record_video_codec_capabilities(
+            &presence,
+            VideoCodecCapabilities {
+                decoders: request.decoders,
+                encoders: request.encoders,
+            },
+        )
+        .await;
+    let target = presence.target();
+    announce_video_codec_change(state, target).await;
+
+    Ok(VoiceVideoCodecSelected {
+        server_id: target.route_id().to_string(),
+        room_id: room_id.to_string(),
+        codec: state.voice_presence_store.room_video_codec(room_id).await,
+    })
+}
+
+/// Пересчитывает кодек комнаты после изменения состава и рассылает его участникам, если он сменился.
+pub(super) async fn announce_video_codec_change(state: &AppState, target: VoicePresenceTarget) {
+    let participants = state
+        .voice_presence_store
+        .room_participants(target.kind, &target.server_id, &target.room_id)
+        .await;
+    let Some(codec) = state
+        .voice_presence_store
+        .refresh_room_video_codec(target.room_id, &participants)
+        .await
+    else {
+        return;
+    };
+
+    let stream_ids = participants
+        .iter()
+        .map(|participant| participant.realtime_stream_id)
+        .collect::<Vec<_>>();
+    tracing::info!(
+        server_id = %target.server_id,
+        room_id = %target.room_id,
+        target_kind = ?target.kind,
+        ?codec,
+        recipients = stream_ids.len(),
+        "selected voice room video codec"
+    );
+    state
+        .realtime_hub
+        .fanout_to_streams(
+            RealtimeModule::VoiceChat,
+            &target.route_id(),
+            RealtimeKind::VoiceChat(VoiceChatKind::VideoCodecSelected),
+            &stream_ids,
+            VoiceVideoCodecSelected {
+                server_id: target.route_id().to_string(),
+                room_id: target.room_id.to_string(),
+                codec,
+            },
+        )
+        .await;
+}
@@ -0,0 +1,176 @@
+//! Подписки зрителей на видеопотоки участников голосовой комнаты.
+
+use cheenhub_contracts::realtime::{
+    RealtimeKind, RealtimeModule, SubscribeVoiceVideo, UnsubscribeVoiceVideo, VoiceChatKind,
+    VoiceVideoStreamActive, VoiceVideoStreamSource,
+};
+use uuid::Uuid;
+
+use crate::features::voice_chat::infrastructure::VoicePresence;
+use crate::state::AppState;
+
+use super::key_frames::forward_key_frame_request;
+use super::{VoiceChatApplicationError, active_presence_for_user, parse_id};
+
+/// Подписывает сессию участника на видеопоток другого участника той же комнаты.
+///
+/// Новому подписчику и подписчику, сменившему слой, нужен ключевой кадр,
+/// поэтому отправитель сразу получает запрос на него.
+pub(crate) async fn subscribe_video(
+    state: &AppState,
+    realtime_stream_id: Uuid,
+    session_id: Uuid,
+    user_id: &Uuid,
+    request: SubscribeVoiceVideo,
+) -> Result<(), VoiceChatApplicationError> {
+    let (presence, publisher_user_id) = subscriber_presence(
+        state,
+        realtime_stream_id,
+        session_id,
+        user_id,
+        &request.server_id,
+        &request.room_id,
+        &request.user_id,
+    )
+    .await?;
+    if !state
+        .voice_presence_store
+        .subscribe_video(&presence, publisher_user_id, request.source, request.layer)
+        .await
+    {
+        return Ok(());
+    }
+
+    tracing::debug!(
+        server_id = %presence.server_id,
+        room_id = %presence.room_id,
+        target_kind = ?presence.target_kind,
+        user_id = %user_id,
+        publisher_user_id = %publisher_user_id,
+        source = ?request.source,
+        layer = ?request.layer,
+        "subscribed voice participant to video stream"
+    );
+    if let Some(publisher) = state
+        .voice_presence_store
+        .room_presence_for_user(presence.target_kind, &presence.room_id, &publisher_user_id)
+        .await
+        .filter(|publisher| publisher.server_id == presence.server_id)
+    {
+        forward_key_frame_request(state, &publisher, request.source).await;
+    }
+
+    Ok(())
+}
+
+/// Отписывает сессию участника от видеопотока другого участника той же комнаты.
+pub(crate) async fn unsubscribe_video(
+    state: &AppState,
+    realtime_stream_id: Uuid,
+    session_id: Uuid,
+    user_id: &Uuid,
+    request: UnsubscribeVoiceVideo,
+) -> Result<(), VoiceChatApplicationError> {
+    let (presence, publisher_user_id) = subscriber_presence(
+        state,
+        realtime_stream_id,
+        session_id,
+        user_id,
+        &request.server_id,
+        &request.room_id,
+        &request.user_id,
+    )
+    .await?;
+    if state
+        .voice_presence_store
+        .unsubscribe_video(&presence, publisher_user_id, request.source)
+        .await
+    {
+        tracing::debug!(
+            server_id = %presence.server_id,
+            room_id = %presence.room_id,
+            target_kind = ?presence.target_kind,
+            user_id = %user_id,
+            publisher_user_id = %publisher_user_id,
+            source = ?request.source,
+            "unsubscribed voice participant from video stream"
+        );
+    }
+
+    Ok(())
+}
+
+/// Напоминает неподписанным участникам комнаты, что отправитель публикует видео.
+pub(crate) async fn announce_video_activity(
+    state: &AppState,
+    publisher: &VoicePresence,
+    source: VoiceVideoStreamSource,
+) {
+    let stream_ids = state
+        .voice_presence_store
+        .video_activity_recipients(publisher, source)
+        .await;
+    if stream_ids.is_empty() {
+        return;
+    }
+
+    tracing::trace!(
+        server_id = %publisher.server_id,
+        room_id = %publisher.room_id,
+        user_id = %publisher.user_id,
+        source = ?source,
+        recipients = stream_ids.len(),
+        "fanning out voice video stream activity event"
+    );
+    state
+        .realtime_hub
+        .fanout_to_streams(
+            RealtimeModule::VoiceChat,
+            &publisher.server_id,
+            RealtimeKind::VoiceChat(VoiceChatKind::VideoStreamActive),
+            &stream_ids,
+            VoiceVideoStreamActive {
+                server_id: publisher.server_id.to_string(),
+                room_id: publisher.room_id.to_string(),
+                user_id: publisher.user_id.to_string(),
+                source,
+            },
+        )
+        .await;
+}
+
+async fn subscriber_presence(
+    state: &AppState,
+    realtime_stream_id: Uuid,
+    session_id: Uuid,
+    user_id: &Uuid,
+    server_id: &str,
+    room_id: &str,
+    publisher_user_id: &str,
+) -> Result<(VoicePresence, Uuid), VoiceChatApplicationError> {
+    let server_id = parse_id(server_id, "Сервер не найден.")?;
+    let room_id = parse_id(room_id, "Комната не найдена.")?;
+    let publisher_user_id = parse_id(publisher_user_id, "Пользователь не найден.")?;
+    let Some(presence) = active_presence_for_user(state, &room_id, user_id).await else {
+        return Err(VoiceChatApplicationError::NotFound(
+            "Пользователь не находится в этой голосовой комнате.".to_owned(),
+        ));
+    };
+    if presence.server_id != server_id || presence.room_id != room_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Комната не найдена.".to_owned(),
+        ));
+    }
+    if presence.realtime_stream_id != realtime_stream_id || presence.session_id != session_id {
+        return Err(VoiceChatApplicationError::Unauthorized(
+            "Подписка принадлежит другой realtime-сессии.".to_owned(),
+        ));
+    }
+    if publisher_user_id == *user_id {
+        return Err(VoiceChatApplicationError::BadRequest(
+            "Нельзя подписаться на собственный видеопоток.".to_owned(),
+        ));
+    }
+
+    Ok((presence, publisher_user_id))
+}
@@ -0,0 +1,261 @@
+//! Проверка ограничений исходящего аудио голосовой комнаты.
+
+use std::time::{Duration, Instant};
+
+use cheenhub_contracts::media::{MediaCodec, MediaDatagram};
+use cheenhub_contracts::realtime::DEFAULT_AUDIO_BITRATE_BPS;
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
+use opus::parse_packet_duration_us;
+
+mod opus;
+
+const MIN_PACKET_DURATION_US: u32 = 10_000;
+const MAX_PACKET_DURATION_US: u32 = 60_000;
+const BITRATE_BLOCK_DURATION: Duration = Duration::from_secs(1);
+/// Запас на всплески VBR-кодировщика сверх настроенного битрейта.
+const BITRATE_TOLERANCE_PERCENT: u64 = 25;
+/// Запас на один крупный пакет Opus сверх накопленного бюджета битов.
+const BITRATE_BURST_ALLOWANCE_BITS: u64 = 8 * 1_275;
+/// Сколько медиавремени неизрасходованного битрейта публикация может копить на тихих кадрах.
+const BITRATE_BUDGET_DEPTH: Duration = Duration::from_millis(500);
+/// Запас на джиттер доставки, после которого аудио считается присланным быстрее реального времени.
+const MEDIA_JITTER_ALLOWANCE: Duration = Duration::from_millis(500);
+
+/// Ограничения аудио, действующие в голосовых комнатах одного сервера.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RoomAudioPolicy {
+    /// Верхняя граница битрейта микрофона в битах в секунду.
+    pub(crate) max_bitrate_bps: u32,
+}
+
+impl Default for RoomAudioPolicy {
+    fn default() -> Self {
+        Self {
+            max_bitrate_bps: DEFAULT_AUDIO_BITRATE_BPS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AudioAdmission {
+    Forward,
+    Drop(AudioDropReason),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AudioDropReason {
+    UnsupportedCodec,
+    MalformedOpusPacket,
+    UnsupportedFrameDuration {
+        duration_us: u32,
+    },
+    BitrateLimitExceeded {
+        max_bitrate_bps: u32,
+        observed_bitrate_bps: u32,
+    },
+    PacketRateExceeded {
+        ahead_ms: u32,
+    },
+    BitrateBlockActive,
+}
+
+#[derive(Default)]
+pub(super) struct AudioPublicationTracker {
+    publications: Vec<AudioPublication>,
+}
+
+impl AudioPublicationTracker {
+    pub(super) fn inspect(
+        &mut self,
+        session_id: Uuid,
+        user_id: Uuid,
+        datagram: &MediaDatagram,
+        policy: RoomAudioPolicy,
+    ) -> AudioAdmission {
+        self.inspect_at(session_id, user_id, datagram, policy, Instant::now())
+    }
+
+    fn inspect_at(
+        &mut self,
+        session_id: Uuid,
+        user_id: Uuid,
+        datagram: &MediaDatagram,
+        policy: RoomAudioPolicy,
+        now: Instant,
+    ) -> AudioAdmission {
+        if datagram.codec != MediaCodec::Opus {
+            return AudioAdmission::Drop(AudioDropReason::UnsupportedCodec);
+        }
+        let Some(duration_us) = parse_packet_duration_us(&datagram.payload) else {
+            return AudioAdmission::Drop(AudioDropReason::MalformedOpusPacket);
+        };
+        if !(MIN_PACKET_DURATION_US..=MAX_PACKET_DURATION_US).contains(&duration_us) {
+            return AudioAdmission::Drop(AudioDropReason::UnsupportedFrameDuration { duration_us });
+        }
+
+        let key = AudioPublicationKey {
+            session_id,
+            user_id,
+            room_id: datagram.room_id,
+        };
+        let publication = match self.publications.iter_mut().find(|entry| entry.key == key) {
+            Some(publication) => publication,
+            None => {
+                self.publications.push(AudioPublication::new(key, now));
+                self.publications
+                    .last_mut()
+                    .expect("publication was inserted")
+            }
+        };
+        publication.inspect_packet(datagram.payload.len(), duration_us, policy, now)
+    }
+
+    pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
+        self.publications.retain(|publication| {
+            !removed.iter().any(|presence| {
+                publication.key.user_id == presence.user_id
+                    && publication.key.room_id == presence.room_id
+            })
+        });
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    pub(super) async fn inspect_audio_datagram(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        datagram: &MediaDatagram,
+        policy: RoomAudioPolicy,
+    ) -> AudioAdmission {
+        self.audio_publications
+            .lock()
+            .await
+            .inspect(session_id, user_id, datagram, policy)
+    }
+
+    /// Возвращает ограничения аудио серверных комнат или ограничения по умолчанию.
+    pub(super) async fn server_audio_policy(&self, server_id: &Uuid) -> RoomAudioPolicy {
+        self.server_audio_policies
+            .lock()
+            .await
+            .get(server_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Запоминает ограничения аудио, которые relay применяет к комнатам сервера.
+    pub(super) async fn set_server_audio_policy(&self, server_id: Uuid, policy: RoomAudioPolicy) {
+        self.server_audio_policies
+            .lock()
+            .await
+            .insert(server_id, policy);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AudioPublicationKey {
+    session_id: Uuid,
+    user_id: Uuid,
+    room_id: Uuid,
+}
+
+/// Бюджет битов пополняется медиавременем пакетов, бюджет медиавремени — временем прихода;
+/// каждый пакет списывается сразу, и первый пакет сверх бюджета блокирует публикацию.
+struct AudioPublication {
+    key: AudioPublicationKey,
+    /// Биты, которые публикация еще может отправить без превышения битрейта.
+    bit_budget: u64,
+    /// Медиавремя, на которое публикация еще может опередить реальное время.
+    media_budget_us: u64,
+    budget_refilled_at: Instant,
+    blocked_until: Option<Instant>,
+}
+
+impl AudioPublication {
+    fn new(key: AudioPublicationKey, now: Instant) -> Self {
+        let mut publication = Self {
+            key,
+            bit_budget: 0,
+            media_budget_us: 0,
+            budget_refilled_at: now,
+            blocked_until: None,
+        };
+        publication.reset_budget(now);
+        publication
+    }
+
+    fn inspect_packet(
+        &mut self,
+        payload_len: usize,
+        duration_us: u32,
+        policy: RoomAudioPolicy,
+        now: Instant,
+    ) -> AudioAdmission {
+        if let Some(blocked_until) = self.blocked_until {
+            if now < blocked_until {
+                return AudioAdmission::Drop(AudioDropReason::BitrateBlockActive);
+            }
+            self.blocked_until = None;
+            self.reset_budget(now);
+        }
+
+        let bit_budget_depth = allowed_bits(policy, BITRATE_BUDGET_DEPTH.as_micros() as u64)
+            + BITRATE_BURST_ALLOWANCE_BITS;
+        let bit_budget = self
+            .bit_budget
+            .saturating_add(allowed_bits(policy, u64::from(duration_us)))
+            .min(bit_budget_depth);
+        let packet_bits = payload_len as u64 * 8;
+        let Some(bit_budget) = bit_budget.checked_sub(packet_bits) else {
+            let observed_bitrate_bps = packet_bits * 1_000_000 / u64::from(duration_us);
+            self.block(now);
+            return AudioAdmission::Drop(AudioDropReason::BitrateLimitExceeded {
+                max_bitrate_bps: policy.max_bitrate_bps,
+                observed_bitrate_bps: u32::try_from(observed_bitrate_bps).unwrap_or(u32::MAX),
+            });
+        };
+
+        let allowance_us = MEDIA_JITTER_ALLOWANCE.as_micros() as u64;
+        let elapsed_us = now
+            .saturating_duration_since(self.budget_refilled_at)
+            .as_micros()
+            .min(u128::from(allowance_us)) as u64;
+        let media_budget_us = (self.media_budget_us + elapsed_us).min(allowance_us);
+        let Some(media_budget_us) = media_budget_us.checked_sub(u64::from(duration_us)) else {
+            let ahead_us = allowance_us - media_budget_us + u64::from(duration_us);
+            self.block(now);
+            return AudioAdmission::Drop(AudioDropReason::PacketRateExceeded {
+                ahead_ms: u32::try_from(ahead_us / 1_000).unwrap_or(u32::MAX),
+            });
+        };
+
+        self.bit_budget = bit_budget;
+        self.media_budget_us = media_budget_us;
+        self.budget_refilled_at = now;
+        AudioAdmission::Forward
+    }
+
+    fn block(&mut self, now: Instant) {
+        self.blocked_until = Some(now + BITRATE_BLOCK_DURATION);
+        self.reset_budget(now);
+    }
+
+    fn reset_budget(&mut self, now: Instant) {
+        // Глубина бюджета битов зависит от политики, поэтому следующий пакет обрежет его сам.
+        self.bit_budget = u64::MAX;
+        self.media_budget_us = MEDIA_JITTER_ALLOWANCE.as_micros() as u64;
+        self.budget_refilled_at = now;
+    }
+}
+
+/// Биты, которые разрешены за `media_us` медиавремени при настроенном битрейте и запасе VBR.
+fn allowed_bits(policy: RoomAudioPolicy, media_us: u64) -> u64 {
+    u64::from(policy.max_bitrate_bps) * media_us / 1_000_000 * (100 + BITRATE_TOLERANCE_PERCENT)
+        / 100
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,31 @@
+//! Чтение длительности пакета Opus из TOC byte (RFC 6716, раздел 3.1).
+
+/// Максимальная длительность одного пакета Opus по спецификации.
+const MAX_PACKET_DURATION_US: u32 = 120_000;
+
+/// Возвращает длительность аудио в пакете Opus в микросекундах.
+pub(super) fn parse_packet_duration_us(payload: &[u8]) -> Option<u32> {
+    let toc = *payload.first()?;
+    let frame_duration_us = frame_duration_us(toc >> 3);
+    let frame_count = match toc & 0b11 {
+        0 => 1,
+        1 | 2 => 2,
+        _ => {
+            let count = u32::from(payload.get(1)? & 0b0011_1111);
+            (count > 0).then_some(count)?
+        }
+    };
+    let duration_us = frame_duration_us * frame_count;
+    (duration_us <= MAX_PACKET_DURATION_US).then_some(duration_us)
+}
+
+fn frame_duration_us(config: u8) -> u32 {
+    match config {
+        // SILK-only: 10, 20, 40, 60 мс.
+        0..=11 => [10_000, 20_000, 40_000, 60_000][usize::from(config % 4)],
+        // Hybrid: 10, 20 мс.
+        12..=15 => [10_000, 20_000][usize::from(config % 2)],
+        // CELT-only: 2.5, 5, 10, 20 мс.
+        _ => [2_500, 5_000, 10_000, 20_000][usize::from(config % 4)],
+    }
+}
@@ -0,0 +1,198 @@
+use super::*;
+use crate::features::voice_chat::test_builders;
+
+const CELT_20MS_TOC: u8 = 31 << 3;
+
+#[test]
+fn opus_packet_duration_is_read_from_toc() {
+    assert_eq!(
+        opus::parse_packet_duration_us(&[CELT_20MS_TOC]),
+        Some(20_000)
+    );
+    assert_eq!(
+        opus::parse_packet_duration_us(&[CELT_20MS_TOC | 0b01]),
+        Some(40_000)
+    );
+    assert_eq!(opus::parse_packet_duration_us(&[3 << 3]), Some(60_000));
+    assert_eq!(
+        opus::parse_packet_duration_us(&[CELT_20MS_TOC | 0b11, 6]),
+        Some(120_000)
+    );
+    assert_eq!(
+        opus::parse_packet_duration_us(&[CELT_20MS_TOC | 0b11, 7]),
+        None
+    );
+    assert_eq!(
+        opus::parse_packet_duration_us(&[CELT_20MS_TOC | 0b11, 0]),
+        None
+    );
+    assert_eq!(
+        opus::parse_packet_duration_us(&[CELT_20MS_TOC | 0b11]),
+        None
+    );
+    assert_eq!(opus::parse_packet_duration_us(&[]), None);
+}
+
+#[test]
+fn malformed_and_unsupported_packets_are_rejected() {
+    let mut tracker = AudioPublicationTracker::default();
+    let policy = RoomAudioPolicy::default();
+    let now = Instant::now();
+    let mut datagram = voice_datagram(1, 80);
+
+    datagram.payload = vec![16 << 3];
+    assert_eq!(
+        tracker.inspect_at(Uuid::new_v4(), Uuid::new_v4(), &datagram, policy, now),
+        AudioAdmission::Drop(AudioDropReason::UnsupportedFrameDuration { duration_us: 2_500 })
+    );
+    datagram.payload = Vec::new();
+    assert_eq!(
+        tracker.inspect_at(Uuid::new_v4(), Uuid::new_v4(), &datagram, policy, now),
+        AudioAdmission::Drop(AudioDropReason::MalformedOpusPacket)
+    );
+    datagram.codec = MediaCodec::Vp9;
+    assert_eq!(
+        tracker.inspect_at(Uuid::new_v4(), Uuid::new_v4(), &datagram, policy, now),
+        AudioAdmission::Drop(AudioDropReason::UnsupportedCodec)
+    );
+}
+
+#[test]
+fn stream_within_configured_bitrate_is_forwarded() {
+    let mut tracker = AudioPublicationTracker::default();
+    let session_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let started = Instant::now();
+    let room_id = Uuid::new_v4();
+
+    for sequence in 0..150 {
+        let mut datagram = voice_datagram(sequence, 80);
+        datagram.room_id = room_id;
+        assert_eq!(
+            tracker.inspect_at(
+                session_id,
+                user_id,
+                &datagram,
+                RoomAudioPolicy::default(),
+                started + Duration::from_millis(sequence * 20),
+            ),
+            AudioAdmission::Forward
+        );
+    }
+}
+
+#[test]
+fn sustained_bitrate_violation_blocks_publication_for_a_while() {
+    let mut tracker = AudioPublicationTracker::default();
+    let session_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let started = Instant::now();
+    let room_id = Uuid::new_v4();
+    let policy = RoomAudioPolicy {
+        max_bitrate_bps: 32_000,
+    };
+    let mut inspect = |sequence: u64, at_ms: u64| {
+        let mut datagram = voice_datagram(sequence, 160);
+        datagram.room_id = room_id;
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &datagram,
+            policy,
+            started + Duration::from_millis(at_ms),
+        )
+    };
+
+    for sequence in 0..61 {
+        assert_eq!(inspect(sequence, sequence * 20), AudioAdmission::Forward);
+    }
+    assert_eq!(
+        inspect(61, 1_220),
+        AudioAdmission::Drop(AudioDropReason::BitrateLimitExceeded {
+            max_bitrate_bps: 32_000,
+            observed_bitrate_bps: 64_000,
+        })
+    );
+    assert_eq!(
+        inspect(62, 1_240),
+        AudioAdmission::Drop(AudioDropReason::BitrateBlockActive)
+    );
+    assert_eq!(inspect(63, 2_220), AudioAdmission::Forward);
+}
+
+#[test]
+fn flood_is_dropped_as_soon_as_bit_budget_runs_out() {
+    let mut tracker = AudioPublicationTracker::default();
+    let session_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let started = Instant::now();
+    let room_id = Uuid::new_v4();
+    let admissions = (0..200)
+        .map(|sequence| {
+            let mut datagram = voice_datagram(sequence, 1_275);
+            datagram.room_id = room_id;
+            tracker.inspect_at(
+                session_id,
+                user_id,
+                &datagram,
+                RoomAudioPolicy::default(),
+                started + Duration::from_millis(sequence),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let first_drop = admissions
+        .iter()
+        .position(|admission| *admission != AudioAdmission::Forward)
+        .expect("flood should be dropped");
+    assert!(first_drop < 10, "flood dropped only at packet {first_drop}");
+    assert_eq!(
+        admissions[first_drop],
+        AudioAdmission::Drop(AudioDropReason::BitrateLimitExceeded {
+            max_bitrate_bps: 32_000,
+            observed_bitrate_bps: 510_000,
+        })
+    );
+    assert!(
+        admissions[first_drop + 1..].iter().all(
+            |admission| *admission == AudioAdmission::Drop(AudioDropReason::BitrateBlockActive)
+        )
+    );
+}
+
+#[test]
+fn audio_sent_faster_than_real_time_is_blocked() {
+    let mut tracker = AudioPublicationTracker::default();
+    let session_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let started = Instant::now();
+    let room_id = Uuid::new_v4();
+    let mut inspect = |sequence: u64, at: Instant| {
+        let mut datagram = voice_datagram(sequence, 80);
+        datagram.room_id = room_id;
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &datagram,
+            RoomAudioPolicy::default(),
+            at,
+        )
+    };
+
+    for sequence in 0..25 {
+        assert_eq!(inspect(sequence, started), AudioAdmission::Forward);
+    }
+    assert_eq!(
+        inspect(25, started),
+        AudioAdmission::Drop(AudioDropReason::PacketRateExceeded { ahead_ms: 520 })
+    );
+}
+
+fn voice_datagram(sequence: u64, payload_len: usize) -> MediaDatagram {
+    let mut payload = vec![0; payload_len];
+    payload[0] = CELT_20MS_TOC;
+    MediaDatagram {
+        timestamp_us: 0,
+        ..test_builders::voice_datagram(Uuid::new_v4(), Uuid::nil(), sequence, payload)
+    }
+}
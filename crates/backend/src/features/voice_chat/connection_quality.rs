@@ -0,0 +1,303 @@
+//! Оценка качества соединения участников по отчетам получателей медиа.
+
+use std::time::{Duration, Instant};
+
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
+
+/// Через сколько отчет получателя перестает влиять на оценку.
+const RECEIVER_REPORT_TTL: Duration = Duration::from_secs(15);
+
+/// Минимальное изменение оценки, ради которого комнате рассылается новый снимок.
+const QUALITY_BROADCAST_STEP: u8 = 10;
+
+/// Штраф оценки за каждый процент потерянных кадров.
+const LOSS_PENALTY_PER_PERCENT: f64 = 4.0;
+
+/// Штраф оценки за каждый процент опоздавших кадров.
+const LATE_PENALTY_PER_PERCENT: f64 = 2.0;
+
+/// Миллисекунды джиттера, стоящие одно очко оценки.
+const JITTER_MS_PER_PENALTY_POINT: f64 = 4.0;
+
+/// Минимальное изменение потерь отправителя в процентах, о котором ему стоит сообщить.
+const UPLINK_LOSS_ANNOUNCE_STEP: u8 = 2;
+
+/// Статистика приема кадров одного отправителя у одного получателя.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ReceptionSample {
+    /// Число дошедших кадров.
+    pub(crate) received_frames: u64,
+    /// Число потерянных кадров.
+    pub(crate) lost_frames: u64,
+    /// Число опоздавших кадров.
+    pub(crate) late_frames: u64,
+    /// Джиттер голосовых кадров в миллисекундах.
+    pub(crate) jitter_ms: u32,
+}
+
+impl ReceptionSample {
+    fn loss_percent(self) -> Option<u8> {
+        let expected = self.received_frames.saturating_add(self.lost_frames);
+        if expected == 0 {
+            return None;
+        }
+        Some((self.lost_frames as f64 * 100.0 / expected as f64).round() as u8)
+    }
+
+    fn score(self) -> Option<u8> {
+        let expected = self.received_frames.saturating_add(self.lost_frames);
+        if expected == 0 {
+            return None;
+        }
+        let expected = expected as f64;
+        let loss_percent = self.lost_frames as f64 * 100.0 / expected;
+        let late_percent = self.late_frames as f64 * 100.0 / expected;
+        let penalty = loss_percent * LOSS_PENALTY_PER_PERCENT
+            + late_percent * LATE_PENALTY_PER_PERCENT
+            + f64::from(self.jitter_ms) / JITTER_MS_PER_PENALTY_POINT;
+
+        Some((100.0 - penalty).clamp(0.0, 100.0).round() as u8)
+    }
+}
+
+/// Результат учета одного отчета получателя.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ReceiverReportOutcome {
+    /// Оценка кого-то из участников сдвинулась настолько, что комнате нужен новый снимок.
+    pub(crate) quality_changed: bool,
+    /// Отправители, которым нужно сообщить новую долю потерь их голоса.
+    pub(crate) uplink_losses: Vec<(Uuid, u8)>,
+}
+
+#[derive(Default)]
+pub(super) struct ConnectionQualityTracker {
+    samples: Vec<StoredReceptionSample>,
+    announced: Vec<AnnouncedQuality>,
+    announced_uplink_losses: Vec<AnnouncedQuality>,
+}
+
+impl ConnectionQualityTracker {
+    /// Сохраняет отчет получателя и возвращает, сдвинулась ли чья-то оценка в комнате.
+    fn record_at(
+        &mut self,
+        room_id: Uuid,
+        receiver_user_id: Uuid,
+        samples: Vec<(Uuid, ReceptionSample)>,
+        now: Instant,
+    ) -> bool {
+        self.samples.retain(|stored| {
+            now.saturating_duration_since(stored.received_at) < RECEIVER_REPORT_TTL
+        });
+        for (sender_user_id, sample) in samples {
+            let key = ReceptionKey {
+                room_id,
+                receiver_user_id,
+                sender_user_id,
+            };
+            match self.samples.iter_mut().find(|stored| stored.key == key) {
+                Some(stored) => {
+                    stored.sample = sample;
+                    stored.received_at = now;
+                }
+                None => self.samples.push(StoredReceptionSample {
+                    key,
+                    sample,
+                    received_at: now,
+                }),
+            }
+        }
+
+        let mut user_ids = self
+            .samples
+            .iter()
+            .filter(|stored| stored.key.room_id == room_id)
+            .flat_map(|stored| [stored.key.receiver_user_id, stored.key.sender_user_id])
+            .collect::<Vec<_>>();
+        user_ids.sort();
+        user_ids.dedup();
+
+        let mut changed = false;
+        for user_id in user_ids {
+            let Some(score) = self.score_at(room_id, user_id, now) else {
+                continue;
+            };
+            match self
+                .announced
+                .iter_mut()
+                .find(|announced| announced.room_id == room_id && announced.user_id == user_id)
+            {
+                Some(announced) if announced.value.abs_diff(score) < QUALITY_BROADCAST_STEP => {}
+                Some(announced) => {
+                    announced.value = score;
+                    changed = true;
+                }
+                None => {
+                    self.announced.push(AnnouncedQuality {
+                        room_id,
+                        user_id,
+                        value: score,
+                    });
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Оценивает соединение участника по свежим отчетам комнаты.
+    ///
+    /// Потери, которые видят все получатели, указывают на канал отправителя, а
+    /// потери у одного получателя от всех отправителей — на его собственный канал.
+    /// Поэтому каждая сторона берется по лучшему отчету, а итог — по худшей стороне:
+    /// чужой слабый канал не портит оценку участника.
+    fn score_at(&self, room_id: Uuid, user_id: Uuid, now: Instant) -> Option<u8> {
+        let uplink = self.best_score_at(room_id, now, |key| key.sender_user_id == user_id);
+        let downlink = self.best_score_at(room_id, now, |key| key.receiver_user_id == user_id);
+
+        match (uplink, downlink) {
+            (Some(uplink), Some(downlink)) => Some(uplink.min(downlink)),
+            (score, None) | (None, score) => score,
+        }
+    }
+
+    /// Возвращает отправителей, чьи потери заметно изменились с прошлого сообщения.
+    ///
+    /// Потери берутся по лучшему получателю: только они точно случились на канале
+    /// отправителя, и только от них помогает избыточность его кодера.
+    fn uplink_loss_changes_at(
+        &mut self,
+        room_id: Uuid,
+        sender_user_ids: &[Uuid],
+        now: Instant,
+    ) -> Vec<(Uuid, u8)> {
+        let mut changes = Vec::new();
+        for &sender_user_id in sender_user_ids {
+            let Some(loss_percent) = self
+                .samples
+                .iter()
+                .filter(|stored| {
+                    stored.key.room_id == room_id
+                        && stored.key.sender_user_id == sender_user_id
+                        && now.saturating_duration_since(stored.received_at) < RECEIVER_REPORT_TTL
+                })
+                .filter_map(|stored| stored.sample.loss_percent())
+                .min()
+            else {
+                continue;
+            };
+            match self.announced_uplink_losses.iter_mut().find(|announced| {
+                announced.room_id == room_id && announced.user_id == sender_user_id
+            }) {
+                Some(announced)
+                    if announced.value.abs_diff(loss_percent) < UPLINK_LOSS_ANNOUNCE_STEP => {}
+                Some(announced) => {
+                    announced.value = loss_percent;
+                    changes.push((sender_user_id, loss_percent));
+                }
+                None => {
+                    self.announced_uplink_losses.push(AnnouncedQuality {
+                        room_id,
+                        user_id: sender_user_id,
+                        value: loss_percent,
+                    });
+                    changes.push((sender_user_id, loss_percent));
+                }
+            }
+        }
+        changes
+    }
+
+    fn best_score_at(
+        &self,
+        room_id: Uuid,
+        now: Instant,
+        side: impl Fn(&ReceptionKey) -> bool,
+    ) -> Option<u8> {
+        self.samples
+            .iter()
+            .filter(|stored| {
+                stored.key.room_id == room_id
+                    && now.saturating_duration_since(stored.received_at) < RECEIVER_REPORT_TTL
+                    && side(&stored.key)
+            })
+            .filter_map(|stored| stored.sample.score())
+            .max()
+    }
+
+    pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
+        self.samples.retain(|stored| {
+            !removed.iter().any(|presence| {
+                stored.key.room_id == presence.room_id
+                    && (stored.key.receiver_user_id == presence.user_id
+                        || stored.key.sender_user_id == presence.user_id)
+            })
+        });
+        let departed = |announced: &AnnouncedQuality| {
+            removed.iter().any(|presence| {
+                announced.room_id == presence.room_id && announced.user_id == presence.user_id
+            })
+        };
+        self.announced.retain(|announced| !departed(announced));
+        self.announced_uplink_losses
+            .retain(|announced| !departed(announced));
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Сохраняет отчет получателя и возвращает, кого нужно оповестить об изменениях.
+    pub(crate) async fn record_receiver_report(
+        &self,
+        receiver: &VoicePresence,
+        samples: Vec<(Uuid, ReceptionSample)>,
+    ) -> ReceiverReportOutcome {
+        let sender_user_ids = samples
+            .iter()
+            .map(|(sender_user_id, _)| *sender_user_id)
+            .collect::<Vec<_>>();
+        let now = Instant::now();
+        let mut tracker = self.connection_quality.lock().await;
+        let quality_changed = tracker.record_at(receiver.room_id, receiver.user_id, samples, now);
+        ReceiverReportOutcome {
+            quality_changed,
+            uplink_losses: tracker.uplink_loss_changes_at(receiver.room_id, &sender_user_ids, now),
+        }
+    }
+
+    /// Возвращает оценки качества соединения участников в том же порядке.
+    pub(crate) async fn connection_qualities(
+        &self,
+        participants: &[VoicePresence],
+    ) -> Vec<Option<u8>> {
+        let tracker = self.connection_quality.lock().await;
+        let now = Instant::now();
+        participants
+            .iter()
+            .map(|participant| tracker.score_at(participant.room_id, participant.user_id, now))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ReceptionKey {
+    room_id: Uuid,
+    receiver_user_id: Uuid,
+    sender_user_id: Uuid,
+}
+
+struct StoredReceptionSample {
+    key: ReceptionKey,
+    sample: ReceptionSample,
+    received_at: Instant,
+}
+
+/// Последнее сообщенное значение участника: оценка соединения или процент потерь.
+struct AnnouncedQuality {
+    room_id: Uuid,
+    user_id: Uuid,
+    value: u8,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,97 @@
+use super::*;
+use crate::features::voice_chat::test_builders::voice_presence;
+
+fn sample(received_frames: u64, lost_frames: u64) -> ReceptionSample {
+    ReceptionSample {
+        received_frames,
+        lost_frames,
+        late_frames: 0,
+        jitter_ms: 0,
+    }
+}
+
+#[test]
+fn loss_late_frames_and_jitter_lower_the_score() {
+    assert_eq!(sample(0, 0).score(), None);
+    assert_eq!(sample(250, 0).score(), Some(100));
+    assert_eq!(sample(95, 5).score(), Some(80));
+    assert_eq!(
+        ReceptionSample {
+            late_frames: 5,
+            jitter_ms: 40,
+            ..sample(100, 0)
+        }
+        .score(),
+        Some(80)
+    );
+    assert_eq!(sample(50, 50).score(), Some(0));
+}
+
+#[test]
+fn one_weak_receiver_does_not_blame_senders() {
+    let mut tracker = ConnectionQualityTracker::default();
+    let room_id = Uuid::new_v4();
+    let (sender, healthy, weak) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+
+    assert!(tracker.record_at(room_id, healthy, vec![(sender, sample(250, 0))], now));
+    assert!(tracker.record_at(room_id, weak, vec![(sender, sample(150, 100))], now));
+
+    assert_eq!(tracker.score_at(room_id, sender, now), Some(100));
+    assert_eq!(tracker.score_at(room_id, weak, now), Some(0));
+    assert_eq!(tracker.score_at(room_id, healthy, now), Some(100));
+}
+
+#[test]
+fn small_score_changes_are_not_rebroadcast() {
+    let mut tracker = ConnectionQualityTracker::default();
+    let room_id = Uuid::new_v4();
+    let (sender, receiver) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+
+    assert!(tracker.record_at(room_id, receiver, vec![(sender, sample(100, 0))], now));
+    assert!(!tracker.record_at(room_id, receiver, vec![(sender, sample(99, 1))], now));
+    assert!(tracker.record_at(room_id, receiver, vec![(sender, sample(90, 10))], now));
+}
+
+#[test]
+fn stale_and_departed_reports_stop_counting() {
+    let mut tracker = ConnectionQualityTracker::default();
+    let room_id = Uuid::new_v4();
+    let (sender, receiver) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+
+    tracker.record_at(room_id, receiver, vec![(sender, sample(100, 0))], now);
+    assert_eq!(
+        tracker.score_at(room_id, sender, now + RECEIVER_REPORT_TTL),
+        None
+    );
+
+    tracker.remove_presences(&[voice_presence(Uuid::new_v4(), room_id, receiver)]);
+    assert_eq!(tracker.score_at(room_id, sender, now), None);
+}
+
+#[test]
+fn uplink_loss_follows_best_receiver_and_skips_small_changes() {
+    let mut tracker = ConnectionQualityTracker::default();
+    let room_id = Uuid::new_v4();
+    let (sender, near, far) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+
+    tracker.record_at(room_id, far, vec![(sender, sample(70, 30))], now);
+    assert_eq!(
+        tracker.uplink_loss_changes_at(room_id, &[sender], now),
+        vec![(sender, 30)]
+    );
+    tracker.record_at(room_id, near, vec![(sender, sample(92, 8))], now);
+    assert_eq!(
+        tracker.uplink_loss_changes_at(room_id, &[sender], now),
+        vec![(sender, 8)]
+    );
+    tracker.record_at(room_id, near, vec![(sender, sample(93, 7))], now);
+    assert!(
+        tracker
+            .uplink_loss_changes_at(room_id, &[sender], now)
+            .is_empty()
+    );
+}
@@ -1,17 +1,34 @@
 //! Инфраструктура присутствия голосового чата.
 
+use std::collections::HashMap;
+
 use chrono::{DateTime, Utc};
 use tokio::sync::Mutex;
 use uuid::Uuid;
 
-use super::media_policy::VideoPublicationTracker;
+use super::active_speakers::ActiveSpeakerTracker;
+use super::audio_policy::{AudioPublicationTracker, RoomAudioPolicy};
+use super::connection_quality::ConnectionQualityTracker;
+use super::key_frames::KeyFrameRequestTracker;
+use super::media::mixing::MixingTracker;
+use super::media_policy::{RoomVideoPolicy, VideoPublicationTracker};
+use super::recording::RecordingTracker;
+use super::video_codecs::VideoCodecTracker;
+use super::video_subscriptions::VideoSubscriptionTracker;
 
 mod direct_calls;
+mod recording_archive;
 mod uplink;
 
 pub(crate) use direct_calls::{
     DirectCall, DirectCallStoreError, DirectCallTransition, InMemoryDirectCallStore,
 };
+#[cfg(test)]
+pub(crate) use recording_archive::InMemoryVoiceRecordingArchive;
+pub(crate) use recording_archive::{
+    DirectoryVoiceRecordingArchive, DisabledVoiceRecordingArchive, S3VoiceRecordingArchive,
+    VoiceRecordingArchive, VoiceRecordingObjectWriter,
+};
 pub(crate) use uplink::{
     ConsumeMicrophoneUplinkGrantError, MicrophoneUplinkBinding, MicrophoneUplinkGrant,
 };
@@ -19,10 +36,33 @@ pub(crate) use uplink::{
 /// In-memory-хранилище голосового присутствия для активных потоков realtime-модуля.
 #[derive(Default)]
 pub(crate) struct InMemoryVoicePresenceStore {
-    entries: Mutex<Vec<VoicePresence>>,
+    pub(super) entries: Mutex<Vec<VoicePresence>>,
     microphone_uplink_grants: Mutex<Vec<MicrophoneUplinkGrant>>,
     microphone_uplink_bindings: Mutex<Vec<MicrophoneUplinkBinding>>,
     pub(super) video_publications: Mutex<VideoPublicationTracker>,
+    pub(super) server_video_policies: Mutex<HashMap<Uuid, RoomVideoPolicy>>,
+    pub(super) audio_publications: Mutex<AudioPublicationTracker>,
+    pub(super) server_audio_policies: Mutex<HashMap<Uuid, RoomAudioPolicy>>,
+    pub(super) key_frame_requests: Mutex<KeyFrameRequestTracker>,
+    pub(super) video_subscriptions: Mutex<VideoSubscriptionTracker>,
+    pub(super) connection_quality: Mutex<ConnectionQualityTracker>,
+    pub(super) video_codecs: Mutex<VideoCodecTracker>,
+    pub(super) recordings: Mutex<RecordingTracker>,
+    pub(super) mixing: std::sync::RwLock<MixingTracker>,
+    pub(super) active_speakers: Mutex<ActiveSpeakerTracker>,
+    server_moderation: Mutex<HashMap<(Uuid, Uuid), VoiceModeration>>,
+}
+
+/// Серверная модерация участника, которая переживает выход и повторный вход в комнату.
+///
+/// Как и присутствие, модерация живет только в памяти процесса: перезапуск
+/// сервера снимает все ограничения.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct VoiceModeration {
+    /// Модератор отключил участнику микрофон.
+    pub(crate) muted: bool,
+    /// Модератор отключил участнику звук.
+    pub(crate) deafened: bool,
 }
 
 /// Активная запись присутствия в голосовой комнате.
@@ -46,6 +86,10 @@ pub(crate) struct VoicePresence {
     pub(crate) avatar_url: Option<String>,
     /// Время присоединения.
     pub(crate) joined_at: DateTime<Utc>,
+    /// Модератор отключил участнику микрофон: его голос не пересылается.
+    pub(crate) server_muted: bool,
+    /// Модератор отключил участнику звук: голос комнаты ему не пересылается.
+    pub(crate) server_deafened: bool,
 }
 
 /// Тип цели голосового присутствия.
@@ -70,7 +114,7 @@ pub(crate) struct VoicePresenceTarget {
 
 impl InMemoryVoicePresenceStore {
     /// Заменяет присутствие одного пользователя или realtime-потока и возвращает удаленные записи.
-    pub(crate) async fn join(&self, presence: VoicePresence) -> Vec<VoicePresence> {
+    pub(crate) async fn join(&self, mut presence: VoicePresence) -> Vec<VoicePresence> {
         let removed = {
             let mut entries = self.entries.lock().await;
             let mut removed = Vec::new();
@@ -85,11 +129,23 @@ impl InMemoryVoicePresenceStore {
                 }
                 !should_remove
             });
+            if presence.target_kind == VoicePresenceTargetKind::Server {
+                let moderation = self
+                    .server_moderation
+                    .lock()
+                    .await
+                    .get(&(presence.server_id, user_id))
+                    .copied()
+                    .unwrap_or_default();
+                presence.server_muted = moderation.muted;
+                presence.server_deafened = moderation.deafened;
+            }
             entries.push(presence);
             removed
         };
         self.revoke_microphone_uplinks_for(&removed).await;
-        self.clear_video_publications_for(&removed).await;
+        self.clear_media_publications_for(&removed).await;
+        self.stop_mixing_in_emptied_rooms(&removed).await;
 
         removed
     }
@@ -136,6 +192,47 @@ impl InMemoryVoicePresenceStore {
         .await
     }
 
+    /// Меняет серверную модерацию участника комнаты и возвращает ее новое значение.
+    ///
+    /// Модерация хранится по паре сервер–пользователь и применяется при следующих входах,
+    /// пока модератор явно ее не снимет. Ограничение можно наложить только на участника
+    /// комнаты, а снять — и после его выхода; иначе возвращается `None`.
+    pub(crate) async fn moderate_user_in_room(
+        &self,
+        user_id: &Uuid,
+        server_id: &Uuid,
+        room_id: &Uuid,
+        moderate: impl FnOnce(&mut VoiceModeration),
+    ) -> Option<VoiceModeration> {
+        let mut entries = self.entries.lock().await;
+        let entry = entries.iter_mut().find(|entry| {
+            entry.target_kind == VoicePresenceTargetKind::Server
+                && &entry.user_id == user_id
+                && &entry.server_id == server_id
+                && &entry.room_id == room_id
+        });
+        let mut server_moderation = self.server_moderation.lock().await;
+        let key = (*server_id, *user_id);
+        let previous = server_moderation.get(&key).copied().unwrap_or_default();
+        let mut moderation = previous;
+        moderate(&mut moderation);
+        let restricts =
+            (moderation.muted && !previous.muted) || (moderation.deafened && !previous.deafened);
+        if entry.is_none() && restricts {
+            return None;
+        }
+        if moderation == VoiceModeration::default() {
+            server_moderation.remove(&key);
+        } else {
+            server_moderation.insert(key, moderation);
+        }
+        if let Some(entry) = entry {
+            entry.server_muted = moderation.muted;
+            entry.server_deafened = moderation.deafened;
+        }
+        Some(moderation)
+    }
+
     async fn remove_presence(
         &self,
         should_remove: impl Fn(&VoicePresence) -> bool,
@@ -155,16 +252,35 @@ impl InMemoryVoicePresenceStore {
             removed
         };
         self.revoke_microphone_uplinks_for(&removed).await;
-        self.clear_video_publications_for(&removed).await;
+        self.clear_media_publications_for(&removed).await;
+        self.stop_mixing_in_emptied_rooms(&removed).await;
 
         removed
     }
 
-    async fn clear_video_publications_for(&self, removed: &[VoicePresence]) {
+    async fn clear_media_publications_for(&self, removed: &[VoicePresence]) {
         self.video_publications
             .lock()
             .await
             .remove_presences(removed);
+        self.audio_publications
+            .lock()
+            .await
+            .remove_presences(removed);
+        self.key_frame_requests
+            .lock()
+            .await
+            .remove_presences(removed);
+        self.video_subscriptions
+            .lock()
+            .await
+            .remove_presences(removed);
+        self.connection_quality
+            .lock()
+            .await
+            .remove_presences(removed);
+        self.video_codecs.lock().await.remove_presences(removed);
+        self.active_speakers.lock().await.remove_presences(removed);
     }
 
     /// Перечисляет активных участников одной комнаты.
@@ -281,11 +397,14 @@ impl InMemoryVoicePresenceStore {
     }
 
     /// Перечисляет активных получателей медиа в одной комнате, исключая одну сессию отправителя.
+    ///
+    /// Для голоса `skip_deafened` исключает участников, которым модератор отключил звук.
     pub(crate) async fn media_recipient_sessions(
         &self,
         target_kind: VoicePresenceTargetKind,
         room_id: &Uuid,
         sender_session_id: &Uuid,
+        skip_deafened: bool,
     ) -> Vec<Uuid> {
         self.entries
             .lock()
@@ -295,6 +414,7 @@ impl InMemoryVoicePresenceStore {
                 entry.target_kind == target_kind
                     && &entry.room_id == room_id
                     && &entry.session_id != sender_session_id
+                    && !(skip_deafened && entry.server_deafened)
             })
             .map(|entry| entry.session_id)
             .collect()
@@ -320,154 +440,4 @@ impl VoicePresenceTarget {
 }
 
 #[cfg(test)]
-mod tests {
-    use chrono::Utc;
-    use uuid::Uuid;
-
-    use super::{InMemoryVoicePresenceStore, VoicePresence, VoicePresenceTargetKind};
-
-    fn presence(
-        realtime_stream_id: Uuid,
-        session_id: Uuid,
-        server_id: Uuid,
-        room_id: Uuid,
-        user_id: Uuid,
-    ) -> VoicePresence {
-        VoicePresence {
-            realtime_stream_id,
-            session_id,
-            target_kind: VoicePresenceTargetKind::Server,
-            server_id,
-            room_id,
-            user_id,
-            nickname: "voice_user".to_owned(),
-            avatar_url: None,
-            joined_at: Utc::now(),
-        }
-    }
-
-    #[tokio::test]
-    async fn room_presence_authorizes_only_joined_users() {
-        let store = InMemoryVoicePresenceStore::default();
-        let room_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-
-        assert!(
-            store
-                .room_presence_for_user(VoicePresenceTargetKind::Server, &room_id, &user_id)
-                .await
-                .is_none()
-        );
-
-        store
-            .join(presence(
-                Uuid::new_v4(),
-                Uuid::new_v4(),
-                Uuid::new_v4(),
-                room_id,
-                user_id,
-            ))
-            .await;
-
-        assert!(
-            store
-                .room_presence_for_user(VoicePresenceTargetKind::Server, &room_id, &user_id)
-                .await
-                .is_some()
-        );
-    }
-
-    #[tokio::test]
-    async fn media_recipients_exclude_sender_and_other_rooms() {
-        let store = InMemoryVoicePresenceStore::default();
-        let server_id = Uuid::new_v4();
-        let room_id = Uuid::new_v4();
-        let other_room_id = Uuid::new_v4();
-        let sender_session_id = Uuid::new_v4();
-        let recipient_session_id = Uuid::new_v4();
-        let other_room_session_id = Uuid::new_v4();
-
-        store
-            .join(presence(
-                Uuid::new_v4(),
-                sender_session_id,
-                server_id,
-                room_id,
-                Uuid::new_v4(),
-            ))
-            .await;
-        store
-            .join(presence(
-                Uuid::new_v4(),
-                recipient_session_id,
-                server_id,
-                room_id,
-                Uuid::new_v4(),
-            ))
-            .await;
-        store
-            .join(presence(
-                Uuid::new_v4(),
-                other_room_session_id,
-                server_id,
-                other_room_id,
-                Uuid::new_v4(),
-            ))
-            .await;
-
-        let recipients = store
-            .media_recipient_sessions(
-                VoicePresenceTargetKind::Server,
-                &room_id,
-                &sender_session_id,
-            )
-            .await;
-
-        assert_eq!(recipients, vec![recipient_session_id]);
-    }
-
-    #[tokio::test]
-    async fn replacing_user_presence_makes_old_session_stale() {
-        let store = InMemoryVoicePresenceStore::default();
-        let server_id = Uuid::new_v4();
-        let first_room_id = Uuid::new_v4();
-        let second_room_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-        let old_session_id = Uuid::new_v4();
-        let new_session_id = Uuid::new_v4();
-
-        store
-            .join(presence(
-                Uuid::new_v4(),
-                old_session_id,
-                server_id,
-                first_room_id,
-                user_id,
-            ))
-            .await;
-        store
-            .join(presence(
-                Uuid::new_v4(),
-                new_session_id,
-                server_id,
-                second_room_id,
-                user_id,
-            ))
-            .await;
-
-        assert!(
-            store
-                .room_presence_for_user(VoicePresenceTargetKind::Server, &first_room_id, &user_id)
-                .await
-                .is_none()
-        );
-        assert_eq!(
-            store
-                .room_presence_for_user(VoicePresenceTargetKind::Server, &second_room_id, &user_id)
-                .await
-                .expect("new presence should remain")
-                .session_id,
-            new_session_id
-        );
-    }
-}
+mod tests;
@@ -0,0 +1,318 @@
+//! Хранилище файлов записей голосовых комнат.
+
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+#[cfg(test)]
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+use tokio::io::AsyncWriteExt;
+
+mod s3;
+
+pub(crate) use s3::S3VoiceRecordingArchive;
+
+/// Граница хранилища файлов записей голосовых комнат.
+///
+/// Ключи объектов разделяются `/` и строятся сервером из идентификаторов,
+/// поэтому хранилище не проверяет их повторно.
+#[async_trait]
+pub(crate) trait VoiceRecordingArchive: Send + Sync {
+    /// Настроено ли хранилище.
+    fn is_configured(&self) -> bool;
+
+    /// Записывает один объект.
+    async fn put_object(&self, key: &str, content_type: &str, bytes: Vec<u8>)
+    -> anyhow::Result<()>;
+
+    /// Открывает объект, который дописывается по частям по мере записи.
+    async fn create_object(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> anyhow::Result<Box<dyn VoiceRecordingObjectWriter>>;
+
+    /// Читает один объект.
+    async fn get_object(&self, key: &str) -> anyhow::Result<Vec<u8>>;
+
+    /// Перечисляет не больше `limit` ключей под префиксом в порядке возрастания,
+    /// начиная после ключа `start_after`.
+    async fn list_objects(
+        &self,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<String>>;
+}
+
+/// Объект хранилища, открытый для дозаписи.
+#[async_trait]
+pub(crate) trait VoiceRecordingObjectWriter: Send {
+    /// Дописывает байты в конец объекта.
+    async fn append(&mut self, bytes: Vec<u8>) -> anyhow::Result<()>;
+
+    /// Завершает объект; после этого он доступен для чтения целиком.
+    async fn finish(self: Box<Self>) -> anyhow::Result<()>;
+}
+
+/// Хранилище записей в локальном каталоге.
+pub(crate) struct DirectoryVoiceRecordingArchive {
+    root: PathBuf,
+}
+
+impl DirectoryVoiceRecordingArchive {
+    /// Создает хранилище в каталоге `root`; каталог создается при первой записи.
+    pub(crate) fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+#[async_trait]
+impl VoiceRecordingArchive for DirectoryVoiceRecordingArchive {
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        _content_type: &str,
+        bytes: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(path, bytes).await?;
+
+        Ok(())
+    }
+
+    async fn create_object(
+        &self,
+        key: &str,
+        _content_type: &str,
+    ) -> anyhow::Result<Box<dyn VoiceRecordingObjectWriter>> {
+        let path = self.root.join(key);
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        Ok(Box::new(DirectoryObjectWriter {
+            file: tokio::fs::File::create(path).await?,
+        }))
+    }
+
+    async fn get_object(&self, key: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(tokio::fs::read(self.root.join(key)).await?)
+    }
+
+    async fn list_objects(
+        &self,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut keys = Vec::new();
+        let mut directories = vec![self.root.join(prefix)];
+        while let Some(directory) = directories.pop() {
+            let mut entries = match tokio::fs::read_dir(&directory).await {
+                Ok(entries) => entries,
+                Err(error) if error.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(error.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_type().await?.is_dir() {
+                    directories.push(path);
+                } else if let Some(key) = relative_key(&self.root, &path)
+                    && start_after.is_none_or(|start_after| key.as_str() > start_after)
+                {
+                    keys.push(key);
+                }
+            }
+        }
+        keys.sort_unstable();
+        keys.truncate(limit);
+
+        Ok(keys)
+    }
+}
+
+/// Файл записи в локальном каталоге, открытый для дозаписи.
+struct DirectoryObjectWriter {
+    file: tokio::fs::File,
+}
+
+#[async_trait]
+impl VoiceRecordingObjectWriter for DirectoryObjectWriter {
+    async fn append(&mut self, bytes: Vec<u8>) -> anyhow::Result<()> {
+        self.file.write_all(&bytes).await?;
+        // Страницы и кластеры сразу доходят до диска и переживают падение процесса.
+        self.file.flush().await?;
+
+        Ok(())
+    }
+
+    async fn finish(mut self: Box<Self>) -> anyhow::Result<()> {
+        self.file.sync_all().await?;
+
+        Ok(())
+    }
+}
+
+fn relative_key(root: &Path, path: &Path) -> Option<String> {
+    let parts = path
+        .strip_prefix(root)
+        .ok()?
+        .components()
+        .map(|component| component.as_os_str().to_str())
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("/"))
+}
+
+/// Отключенное хранилище, используемое когда ни каталог, ни S3 для записей не настроены.
+#[derive(Default)]
+pub(crate) struct DisabledVoiceRecordingArchive;
+
+#[async_trait]
+impl VoiceRecordingArchive for DisabledVoiceRecordingArchive {
+    fn is_configured(&self) -> bool {
+        false
+    }
+
+    async fn put_object(
+        &self,
+        _key: &str,
+        _content_type: &str,
+        _bytes: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!("voice recording storage is not configured"))
+    }
+
+    async fn create_object(
+        &self,
+        _key: &str,
+        _content_type: &str,
+    ) -> anyhow::Result<Box<dyn VoiceRecordingObjectWriter>> {
+        Err(anyhow::anyhow!("voice recording storage is not configured"))
+    }
+
+    async fn get_object(&self, _key: &str) -> anyhow::Result<Vec<u8>> {
+        Err(anyhow::anyhow!("voice recording storage is not configured"))
+    }
+
+    async fn list_objects(
+        &self,
+        _prefix: &str,
+        _start_after: Option<&str>,
+        _limit: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+}
+
+/// In-memory-хранилище записей для локальных тестов.
+#[derive(Default)]
+#[cfg(test)]
+pub(crate) struct InMemoryVoiceRecordingArchive {
+    objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+}
+
+#[async_trait]
+#[cfg(test)]
+impl VoiceRecordingArchive for InMemoryVoiceRecordingArchive {
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        _content_type: &str,
+        bytes: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        self.objects
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory voice recording archive lock poisoned"))?
+            .insert(key.to_owned(), bytes);
+
+        Ok(())
+    }
+
+    async fn create_object(
+        &self,
+        key: &str,
+        _content_type: &str,
+    ) -> anyhow::Result<Box<dyn VoiceRecordingObjectWriter>> {
+        self.objects
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory voice recording archive lock poisoned"))?
+            .insert(key.to_owned(), Vec::new());
+
+        Ok(Box::new(InMemoryObjectWriter {
+            objects: Arc::clone(&self.objects),
+            key: key.to_owned(),
+        }))
+    }
+
+    async fn get_object(&self, key: &str) -> anyhow::Result<Vec<u8>> {
+        self.objects
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory voice recording archive lock poisoned"))?
+            .get(key)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("voice recording object was not found"))
+    }
+
+    async fn list_objects(
+        &self,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        let mut keys = self
+            .objects
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory voice recording archive lock poisoned"))?
+            .keys()
+            .filter(|key| {
+                key.starts_with(prefix)
+                    && start_after.is_none_or(|start_after| key.as_str() > start_after)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        keys.sort_unstable();
+        keys.truncate(limit);
+
+        Ok(keys)
+    }
+}
+
+/// Объект in-memory-хранилища, видимый для чтения уже во время дозаписи.
+#[cfg(test)]
+struct InMemoryObjectWriter {
+    objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    key: String,
+}
+
+#[async_trait]
+#[cfg(test)]
+impl VoiceRecordingObjectWriter for InMemoryObjectWriter {
+    async fn append(&mut self, bytes: Vec<u8>) -> anyhow::Result<()> {
+        self.objects
+            .lock()
+            .map_err(|_| anyhow::anyhow!("in-memory voice recording archive lock poisoned"))?
+            .entry(self.key.clone())
+            .or_default()
+            .extend_from_slice(&bytes);
+
+        Ok(())
+    }
+
+    async fn finish(self: Box<Self>) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
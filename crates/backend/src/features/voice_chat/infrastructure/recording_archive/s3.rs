@@ -0,0 +1,197 @@
+//! S3-совместимое хранилище файлов записей голосовых комнат.
+
+use async_trait::async_trait;
+use aws_sdk_s3::Client;
+use aws_sdk_s3::primitives::ByteStream;
+use aws_sdk_s3::types::{CompletedMultipartUpload, CompletedPart};
+
+use super::{VoiceRecordingArchive, VoiceRecordingObjectWriter};
+use crate::config::S3Config;
+use crate::features::text_chat::infrastructure::connect_s3_client;
+
+/// Минимальный размер части multipart-загрузки S3, кроме последней.
+const S3_MIN_PART_BYTES: usize = 5 * 1024 * 1024;
+
+/// S3-совместимое хранилище записей.
+pub(crate) struct S3VoiceRecordingArchive {
+    client: Client,
+    bucket: String,
+}
+
+impl S3VoiceRecordingArchive {
+    /// Создает клиент S3-совместимого хранилища записей.
+    pub(crate) async fn from_config(config: &S3Config) -> Self {
+        Self {
+            client: connect_s3_client(config, "voice-recordings-s3").await,
+            bucket: config.bucket.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl VoiceRecordingArchive for S3VoiceRecordingArchive {
+    fn is_configured(&self) -> bool {
+        true
+    }
+
+    async fn put_object(
+        &self,
+        key: &str,
+        content_type: &str,
+        bytes: Vec<u8>,
+    ) -> anyhow::Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .body(ByteStream::from(bytes))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    async fn create_object(
+        &self,
+        key: &str,
+        content_type: &str,
+    ) -> anyhow::Result<Box<dyn VoiceRecordingObjectWriter>> {
+        let output = self
+            .client
+            .create_multipart_upload()
+            .bucket(&self.bucket)
+            .key(key)
+            .content_type(content_type)
+            .send()
+            .await?;
+        let upload_id = output
+            .upload_id()
+            .ok_or_else(|| anyhow::anyhow!("S3 did not return multipart upload id"))?
+            .to_owned();
+
+        Ok(Box::new(S3ObjectWriter {
+            client: self.client.clone(),
+            bucket: self.bucket.clone(),
+            key: key.to_owned(),
+            upload_id,
+            buffer: Vec::new(),
+            parts: Vec::new(),
+        }))
+    }
+
+    async fn get_object(&self, key: &str) -> anyhow::Result<Vec<u8>> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await?;
+
+        Ok(output.body.collect().await?.into_bytes().to_vec())
+    }
+
+    async fn list_objects(
+        &self,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> anyhow::Result<Vec<String>> {
+        let output = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(prefix)
+            .set_start_after(start_after.map(str::to_owned))
+            .max_keys(i32::try_from(limit).unwrap_or(i32::MAX))
+            .send()
+            .await?;
+
+        Ok(output
+            .contents()
+            .iter()
+            .filter_map(|object| object.key().map(str::to_owned))
+            .collect())
+    }
+}
+
+/// Multipart-загрузка S3, в которую части уходят по мере записи.
+struct S3ObjectWriter {
+    client: Client,
+    bucket: String,
+    key: String,
+    upload_id: String,
+    buffer: Vec<u8>,
+    parts: Vec<CompletedPart>,
+}
+
+impl S3ObjectWriter {
+    async fn upload_part(&mut self) -> anyhow::Result<()> {
+        let part_number = i32::try_from(self.parts.len() + 1)?;
+        let output = self
+            .client
+            .upload_part()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .upload_id(&self.upload_id)
+            .part_number(part_number)
+            .body(ByteStream::from(std::mem::take(&mut self.buffer)))
+            .send()
+            .await?;
+        self.parts.push(
+            CompletedPart::builder()
+                .part_number(part_number)
+                .set_e_tag(output.e_tag().map(str::to_owned))
+                .build(),
+        );
+
+        Ok(())
+    }
+
+    async fn complete(&mut self) -> anyhow::Result<()> {
+        self.upload_part().await?;
+        self.client
+            .complete_multipart_upload()
+            .bucket(&self.bucket)
+            .key(&self.key)
+            .upload_id(&self.upload_id)
+            .multipart_upload(
+                CompletedMultipartUpload::builder()
+                    .set_parts(Some(std::mem::take(&mut self.parts)))
+                    .build(),
+            )
+            .send()
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl VoiceRecordingObjectWriter for S3ObjectWriter {
+    async fn append(&mut self, bytes: Vec<u8>) -> anyhow::Result<()> {
+        self.buffer.extend_from_slice(&bytes);
+        if self.buffer.len() >= S3_MIN_PART_BYTES {
+            self.upload_part().await?;
+        }
+
+        Ok(())
+    }
+
+    async fn finish(mut self: Box<Self>) -> anyhow::Result<()> {
+        let result = self.complete().await;
+        if result.is_err() {
+            let _ = self
+                .client
+                .abort_multipart_upload()
+                .bucket(&self.bucket)
+                .key(&self.key)
+                .upload_id(&self.upload_id)
+                .send()
+                .await;
+        }
+
+        result
+    }
+}
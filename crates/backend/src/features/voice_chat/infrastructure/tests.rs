@@ -0,0 +1,199 @@
+use uuid::Uuid;
+
+use super::{InMemoryVoicePresenceStore, VoicePresence, VoicePresenceTargetKind};
+use crate::features::voice_chat::test_builders::voice_presence;
+
+fn presence(
+    realtime_stream_id: Uuid,
+    session_id: Uuid,
+    server_id: Uuid,
+    room_id: Uuid,
+    user_id: Uuid,
+) -> VoicePresence {
+    VoicePresence {
+        realtime_stream_id,
+        session_id,
+        ..voice_presence(server_id, room_id, user_id)
+    }
+}
+
+#[tokio::test]
+async fn room_presence_authorizes_only_joined_users() {
+    let store = InMemoryVoicePresenceStore::default();
+    let room_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+
+    assert!(
+        store
+            .room_presence_for_user(VoicePresenceTargetKind::Server, &room_id, &user_id)
+            .await
+            .is_none()
+    );
+
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            room_id,
+            user_id,
+        ))
+        .await;
+
+    assert!(
+        store
+            .room_presence_for_user(VoicePresenceTargetKind::Server, &room_id, &user_id)
+            .await
+            .is_some()
+    );
+}
+
+#[tokio::test]
+async fn media_recipients_exclude_sender_and_other_rooms() {
+    let store = InMemoryVoicePresenceStore::default();
+    let server_id = Uuid::new_v4();
+    let room_id = Uuid::new_v4();
+    let other_room_id = Uuid::new_v4();
+    let sender_session_id = Uuid::new_v4();
+    let recipient_session_id = Uuid::new_v4();
+    let other_room_session_id = Uuid::new_v4();
+
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            sender_session_id,
+            server_id,
+            room_id,
+            Uuid::new_v4(),
+        ))
+        .await;
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            recipient_session_id,
+            server_id,
+            room_id,
+            Uuid::new_v4(),
+        ))
+        .await;
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            other_room_session_id,
+            server_id,
+            other_room_id,
+            Uuid::new_v4(),
+        ))
+        .await;
+
+    let recipients = store
+        .media_recipient_sessions(
+            VoicePresenceTargetKind::Server,
+            &room_id,
+            &sender_session_id,
+            true,
+        )
+        .await;
+
+    assert_eq!(recipients, vec![recipient_session_id]);
+}
+
+#[tokio::test]
+async fn deafened_participant_receives_video_but_not_voice() {
+    let store = InMemoryVoicePresenceStore::default();
+    let server_id = Uuid::new_v4();
+    let room_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let sender_session_id = Uuid::new_v4();
+    let deafened_session_id = Uuid::new_v4();
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            deafened_session_id,
+            server_id,
+            room_id,
+            user_id,
+        ))
+        .await;
+
+    let moderated = store
+        .moderate_user_in_room(&user_id, &server_id, &room_id, |moderation| {
+            moderation.deafened = true;
+        })
+        .await
+        .expect("joined user is moderated");
+    assert!(moderated.deafened && !moderated.muted);
+    assert!(
+        store
+            .moderate_user_in_room(&Uuid::new_v4(), &server_id, &room_id, |moderation| {
+                moderation.muted = true;
+            })
+            .await
+            .is_none()
+    );
+
+    let voice_recipients = store
+        .media_recipient_sessions(
+            VoicePresenceTargetKind::Server,
+            &room_id,
+            &sender_session_id,
+            true,
+        )
+        .await;
+    let video_recipients = store
+        .media_recipient_sessions(
+            VoicePresenceTargetKind::Server,
+            &room_id,
+            &sender_session_id,
+            false,
+        )
+        .await;
+
+    assert!(voice_recipients.is_empty());
+    assert_eq!(video_recipients, vec![deafened_session_id]);
+}
+
+#[tokio::test]
+async fn replacing_user_presence_makes_old_session_stale() {
+    let store = InMemoryVoicePresenceStore::default();
+    let server_id = Uuid::new_v4();
+    let first_room_id = Uuid::new_v4();
+    let second_room_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let old_session_id = Uuid::new_v4();
+    let new_session_id = Uuid::new_v4();
+
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            old_session_id,
+            server_id,
+            first_room_id,
+            user_id,
+        ))
+        .await;
+    store
+        .join(presence(
+            Uuid::new_v4(),
+            new_session_id,
+            server_id,
+            second_room_id,
+            user_id,
+        ))
+        .await;
+
+    assert!(
+        store
+            .room_presence_for_user(VoicePresenceTargetKind::Server, &first_room_id, &user_id)
+            .await
+            .is_none()
+    );
+    assert_eq!(
+        store
+            .room_presence_for_user(VoicePresenceTargetKind::Server, &second_room_id, &user_id)
+            .await
+            .expect("new presence should remain")
+            .session_id,
+        new_session_id
+    );
+}
@@ -264,6 +264,8 @@ mod tests {
                 nickname: "voice_user".to_owned(),
                 avatar_url: None,
                 joined_at: Utc::now(),
+                server_muted: false,
+                server_deafened: false,
             })
             .await;
         let grant_id = Uuid::new_v4();
@@ -0,0 +1,91 @@
+//! Агрегация запросов ключевых кадров от получателей видео.
+
+use std::time::{Duration, Instant};
+
+use cheenhub_contracts::realtime::VoiceVideoStreamSource;
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
+
+/// Минимальный интервал между запросами ключевого кадра одной публикации.
+///
+/// Запросы нескольких получателей внутри интервала схлопываются в один: ключевой
+/// кадр отправителя расходится всем участникам комнаты.
+const KEY_FRAME_REQUEST_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+pub(super) struct KeyFrameRequestTracker {
+    publications: Vec<KeyFrameRequestPublication>,
+}
+
+impl KeyFrameRequestTracker {
+    fn admit_at(&mut self, key: KeyFrameRequestKey, now: Instant) -> bool {
+        match self
+            .publications
+            .iter_mut()
+            .find(|publication| publication.key == key)
+        {
+            Some(publication)
+                if now.saturating_duration_since(publication.forwarded_at)
+                    < KEY_FRAME_REQUEST_INTERVAL =>
+            {
+                false
+            }
+            Some(publication) => {
+                publication.forwarded_at = now;
+                true
+            }
+            None => {
+                self.publications.push(KeyFrameRequestPublication {
+                    key,
+                    forwarded_at: now,
+                });
+                true
+            }
+        }
+    }
+
+    pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
+        self.publications.retain(|publication| {
+            !removed.iter().any(|presence| {
+                publication.key.user_id == presence.user_id
+                    && publication.key.room_id == presence.room_id
+            })
+        });
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Возвращает, нужно ли переслать запрос ключевого кадра отправителю видео.
+    pub(crate) async fn admit_key_frame_request(
+        &self,
+        publisher: &VoicePresence,
+        source: VoiceVideoStreamSource,
+    ) -> bool {
+        self.key_frame_requests.lock().await.admit_at(
+            KeyFrameRequestKey {
+                session_id: publisher.session_id,
+                user_id: publisher.user_id,
+                room_id: publisher.room_id,
+                source,
+            },
+            Instant::now(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct KeyFrameRequestKey {
+    session_id: Uuid,
+    user_id: Uuid,
+    room_id: Uuid,
+    source: VoiceVideoStreamSource,
+}
+
+struct KeyFrameRequestPublication {
+    key: KeyFrameRequestKey,
+    forwarded_at: Instant,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,42 @@
+use super::*;
+use crate::features::voice_chat::test_builders::voice_presence;
+
+fn key(source: VoiceVideoStreamSource) -> KeyFrameRequestKey {
+    KeyFrameRequestKey {
+        session_id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        room_id: Uuid::new_v4(),
+        source,
+    }
+}
+
+#[test]
+fn requests_within_interval_are_collapsed_per_publication() {
+    let mut tracker = KeyFrameRequestTracker::default();
+    let camera = key(VoiceVideoStreamSource::Camera);
+    let screen = KeyFrameRequestKey {
+        source: VoiceVideoStreamSource::ScreenShare,
+        ..camera
+    };
+    let now = Instant::now();
+
+    assert!(tracker.admit_at(camera, now));
+    assert!(!tracker.admit_at(camera, now + Duration::from_millis(100)));
+    assert!(tracker.admit_at(screen, now + Duration::from_millis(100)));
+    assert!(tracker.admit_at(camera, now + KEY_FRAME_REQUEST_INTERVAL));
+}
+
+#[test]
+fn removed_presence_resets_request_interval() {
+    let mut tracker = KeyFrameRequestTracker::default();
+    let camera = key(VoiceVideoStreamSource::Camera);
+    let now = Instant::now();
+    assert!(tracker.admit_at(camera, now));
+
+    tracker.remove_presences(&[VoicePresence {
+        session_id: camera.session_id,
+        ..voice_presence(Uuid::new_v4(), camera.room_id, camera.user_id)
+    }]);
+
+    assert!(tracker.admit_at(camera, now + Duration::from_millis(1)));
+}
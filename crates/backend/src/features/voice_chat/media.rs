@@ -2,16 +2,18 @@
 
 use bytes::Bytes;
 use cheenhub_contracts::media::MediaDatagram;
-use cheenhub_contracts::video_presets::{
-    BASE_CAMERA_VIDEO_PRESETS, BASE_SCREEN_SHARE_VIDEO_PRESETS, VideoPresetId,
-};
+use cheenhub_contracts::realtime::{VoiceVideoCodec, VoiceVideoStreamSource};
 use tracing::{debug, warn};
 use uuid::Uuid;
 
+use super::application::{announce_video_activity, record_room_media, track_audio_level};
+use super::audio_policy::{AudioAdmission, AudioDropReason, RoomAudioPolicy};
 use super::infrastructure::VoicePresenceTargetKind;
-use super::media_policy::{VideoAdmission, VideoDropReason};
+use super::media_policy::{RoomVideoPolicy, VideoAdmission, VideoDropReason};
 use crate::state::AppState;
 
+pub(super) mod mixing;
+
 /// Обрабатывает одну декодированную медиадатаграмму голоса.
 pub(crate) async fn handle_voice_frame(
     state: &AppState,
@@ -36,7 +38,7 @@ pub(crate) async fn handle_screen_frame(
         datagram,
         "screen",
         false,
-        Some(BASE_SCREEN_SHARE_VIDEO_PRESETS),
+        Some(VoiceVideoStreamSource::ScreenShare),
     )
     .await;
 }
@@ -55,7 +57,7 @@ pub(crate) async fn handle_camera_frame(
         datagram,
         "camera",
         false,
-        Some(BASE_CAMERA_VIDEO_PRESETS),
+        Some(VoiceVideoStreamSource::Camera),
     )
     .await;
 }
@@ -67,7 +69,7 @@ async fn handle_room_media_frame(
     mut datagram: MediaDatagram,
     media_kind: &'static str,
     allow_microphone_uplink: bool,
-    allowed_video_presets: Option<&'static [VideoPresetId]>,
+    video_source: Option<VoiceVideoStreamSource>,
 ) {
     debug!(
         %session_id,
@@ -115,10 +117,68 @@ async fn handle_room_media_frame(
         return;
     }
 
-    if let Some(allowed_video_presets) = allowed_video_presets {
+    if video_source.is_none() && presence.server_muted {
+        debug!(
+            %session_id,
+            %user_id,
+            room_id = %datagram.room_id,
+            sequence = datagram.sequence,
+            "dropping voice datagram from server-muted participant"
+        );
+        return;
+    }
+
+    if allow_microphone_uplink {
+        let policy = match presence.target_kind {
+            VoicePresenceTargetKind::Server => {
+                state
+                    .voice_presence_store
+                    .server_audio_policy(&presence.server_id)
+                    .await
+            }
+            VoicePresenceTargetKind::DirectMessage => RoomAudioPolicy::default(),
+        };
         let admission = state
             .voice_presence_store
-            .inspect_video_datagram(session_id, user_id, &datagram, allowed_video_presets)
+            .inspect_audio_datagram(session_id, user_id, &datagram, policy)
+            .await;
+        if !audio_admission_allows_fanout(
+            admission,
+            session_id,
+            user_id,
+            datagram.room_id,
+            datagram.sequence,
+        ) {
+            return;
+        }
+    }
+
+    let video_codec = VoiceVideoCodec::from_media_codec(datagram.codec);
+    if video_source.is_some() {
+        if video_codec.is_none() {
+            debug!(
+                %session_id,
+                %user_id,
+                room_id = %datagram.room_id,
+                media_kind,
+                codec = ?datagram.codec,
+                sequence = datagram.sequence,
+                "dropping video datagram with non-video codec"
+            );
+            return;
+        }
+        let policy = match presence.target_kind {
+            VoicePresenceTargetKind::Server => {
+                state
+                    .voice_presence_store
+                    .server_video_policy(&presence.server_id)
+                    .await
+            }
+            VoicePresenceTargetKind::DirectMessage => RoomVideoPolicy::default(),
+        };
+        let admission = state
+            .voice_presence_store
+            .inspect_video_datagram(session_id, user_id, &datagram, &policy)
             .await;
         if !video_admission_allows_fanout(
             admission,
@@ -133,14 +193,56 @@ async fn handle_room_media_frame(
     }
 
     datagram.sender_user_id = user_id;
+    // Уровень звука нужен только relay: получатели узнают говорящих из события комнаты.
+    if let Some(level) = datagram.audio_level.take()
+        && video_source.is_none()
+    {
+        track_audio_level(state, &presence, level).await;
+    }
+    record_room_media(state, &presence, &datagram).await;
+    if video_source.is_none()
+        && presence.target_kind == VoicePresenceTargetKind::Server
+        && state
+            .voice_presence_store
+            .mix_room_voice(&presence, &datagram)
+            .await
+    {
+        return;
+    }
     let recipients = state
         .voice_presence_store
         .media_recipient_sessions(
             presence.target_kind,
             &datagram.room_id,
             &presence.session_id,
+            video_source.is_none(),
         )
         .await;
+    let recipients = match (video_source, video_codec) {
+        (Some(source), Some(codec)) => {
+            announce_video_activity(state, &presence, source).await;
+            let active_layers = state
+                .voice_presence_store
+                .active_video_layers(datagram.room_id, user_id, datagram.kind)
+                .await;
+            let subscribers = state
+                .voice_presence_store
+                .video_subscriber_sessions(
+                    datagram.room_id,
+                    user_id,
+                    source,
+                    datagram.spatial_layer,
+                    &active_layers,
+                    recipients,
+                )
+                .await;
+            state
+                .voice_presence_store
+                .video_decoder_sessions(&presence, codec, subscribers)
+                .await
+        }
+        _ => recipients,
+    };
     if recipients.is_empty() {
         return;
     }
@@ -200,7 +302,16 @@ fn video_admission_allows_fanout(
             observed_frames,
             "blocked video publication after sustained FPS limit violation"
         ),
-        VideoDropReason::InvalidVp9KeyFrame | VideoDropReason::MalformedFragment => warn!(
+        VideoDropReason::PublisherLimitReached { max_publishers } => debug!(
+            %session_id,
+            %user_id,
+            %room_id,
+            media_kind,
+            sequence,
+            max_publishers,
+            "dropping video datagram over room publisher limit"
+        ),
+        VideoDropReason::InvalidKeyFrame | VideoDropReason::MalformedFragment => warn!(
             %session_id,
             %user_id,
             %room_id,
@@ -224,6 +335,58 @@ fn video_admission_allows_fanout(
     false
 }
 
+fn audio_admission_allows_fanout(
+    admission: AudioAdmission,
+    session_id: Uuid,
+    user_id: Uuid,
+    room_id: Uuid,
+    sequence: u64,
+) -> bool {
+    let AudioAdmission::Drop(reason) = admission else {
+        return true;
+    };
+    match reason {
+        AudioDropReason::BitrateLimitExceeded {
+            max_bitrate_bps,
+            observed_bitrate_bps,
+        } => warn!(
+            %session_id,
+            %user_id,
+            %room_id,
+            sequence,
+            max_bitrate_bps,
+            observed_bitrate_bps,
+            "blocked voice publication after exceeding its bitrate budget"
+        ),
+        AudioDropReason::PacketRateExceeded { ahead_ms } => warn!(
+            %session_id,
+            %user_id,
+            %room_id,
+            sequence,
+            ahead_ms,
+            "blocked voice publication sending audio faster than real time"
+        ),
+        AudioDropReason::UnsupportedCodec
+        | AudioDropReason::MalformedOpusPacket
+        | AudioDropReason::UnsupportedFrameDuration { .. } => warn!(
+            %session_id,
+            %user_id,
+            %room_id,
+            sequence,
+            reason = ?reason,
+            "dropping malformed voice datagram"
+        ),
+        AudioDropReason::BitrateBlockActive => debug!(
+            %session_id,
+            %user_id,
+            %room_id,
+            sequence,
+            "dropping voice datagram while publication is blocked"
+        ),
+    }
+    false
+}
+
 async fn active_presence_for_user(
     state: &AppState,
     room_id: &Uuid,
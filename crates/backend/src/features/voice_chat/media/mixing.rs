@@ -0,0 +1,347 @@
+//! Серверное сведение голосов комнаты в один поток на получателя.
+//!
+//! В режиме сведения relay декодирует голос говорящих, каждые 20 мс выбирает
+//! самых громких и кодирует смеси. Слушатели получают общую смесь, а недавно
+//! говоривший участник — личную смесь без своего голоса, пока не помолчит
+//! [`PERSONAL_MIX_IDLE`]. Каждый поток получателя выходит из одного кодировщика
+//! Opus, и получатель декодирует не больше двух потоков вместо N.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+use cheenhub_contracts::media::{
+    MIXED_PERSONAL_VOICE_SENDER_USER_ID, MIXED_VOICE_SENDER_USER_ID, MediaCodec, MediaDatagram,
+    MediaDatagramKind,
+};
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use uuid::Uuid;
+
+#[cfg(feature = "audio-mixing")]
+mod opus_codec;
+mod rooms;
+
+pub(crate) use rooms::{MixingStartError, MixingTracker, RoomMixingRun};
+
+/// Шаг сведения: один кадр Opus 20 мс.
+pub(crate) const MIX_INTERVAL: Duration = Duration::from_millis(20);
+const MIX_FRAME_DURATION_US: u32 = 20_000;
+/// Сэмплов моно 48 кГц в одном шаге сведения.
+pub(crate) const MIX_FRAME_SAMPLES: usize = 960;
+/// Сколько самых громких говорящих попадает в смесь.
+pub(super) const MAX_MIXED_SPEAKERS: usize = 3;
+/// Уровень ниже этого считается тишиной и не занимает место в смеси.
+const MIN_SPEAKER_LEVEL: f32 = 0.001;
+/// Предел буфера говорящего; более старый звук отбрасывается, чтобы задержка не копилась.
+const MAX_BUFFERED_SAMPLES: usize = MIX_FRAME_SAMPLES * 4;
+/// Молчание, после которого личный кодировщик освобождается и участник
+/// возвращается к общей смеси.
+pub(super) const PERSONAL_MIX_IDLE: Duration = Duration::from_secs(5);
+
+/// Декодер голосового потока одного говорящего в моно PCM 48 кГц.
+pub(crate) trait VoiceDecoder: Send {
+    /// Декодирует один пакет; `None`, если пакет поврежден.
+    fn decode(&mut self, packet: &[u8]) -> Option<Vec<f32>>;
+}
+
+/// Кодировщик смеси одного получателя из моно PCM 48 кГц.
+pub(crate) trait VoiceEncoder: Send {
+    /// Кодирует один кадр сведения; `None`, если кодировщик отказал.
+    fn encode(&mut self, pcm: &[f32]) -> Option<Vec<u8>>;
+}
+
+/// Кодек, которым сведение декодирует голоса и кодирует смеси.
+pub(crate) trait MixCodec: Send + Sync {
+    /// Создает декодер для нового говорящего.
+    fn decoder(&self) -> Option<Box<dyn VoiceDecoder>>;
+
+    /// Создает кодировщик для нового получателя.
+    fn encoder(&self, bitrate_bps: u32) -> Option<Box<dyn VoiceEncoder>>;
+}
+
+/// Кодек сведения этой сборки; без feature `audio-mixing` сведение недоступно.
+pub(crate) fn build_codec() -> Option<Arc<dyn MixCodec>> {
+    #[cfg(feature = "audio-mixing")]
+    {
+        Some(Arc::new(opus_codec::OpusMixCodec))
+    }
+    #[cfg(not(feature = "audio-mixing"))]
+    {
+        None
+    }
+}
+
+/// Нормализованный RMS кадра, как у `microphone::core::rms_level` клиента.
+pub(super) fn rms_level(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let square_sum = samples
+        .iter()
+        .map(|sample| sample.clamp(-1.0, 1.0))
+        .map(|sample| sample * sample)
+        .sum::<f32>();
+    (square_sum / samples.len() as f32).sqrt()
+}
+
+/// Сведенный кадр для одной realtime-сессии получателя.
+pub(crate) struct MixedVoiceFrame {
+    /// Сессия, которой адресован кадр.
+    pub(crate) session_id: Uuid,
+    /// Голосовая датаграмма со сведенным звуком.
+    pub(crate) datagram: MediaDatagram,
+}
+
+/// Участник сведения: говорящий и получатель смеси со своей realtime-сессией.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MixParticipant {
+    /// Realtime-сессия участника.
+    pub(crate) session_id: Uuid,
+    /// Участник комнаты.
+    pub(crate) user_id: Uuid,
+    /// Сервер заглушил участнику звук: его голос сводится, но смесь он не получает.
+    pub(crate) deafened: bool,
+}
+
+impl MixParticipant {
+    fn same_session(&self, other: &Self) -> bool {
+        self.session_id == other.session_id && self.user_id == other.user_id
+    }
+}
+
+struct SpeakerState {
+    user_id: Uuid,
+    decoder: Box<dyn VoiceDecoder>,
+    samples: VecDeque<f32>,
+    last_sequence: Option<u64>,
+}
+
+struct ReceiverState {
+    participant: MixParticipant,
+    /// Личный кодировщик; пока его нет, получатель слушает общую смесь.
+    ///
+    /// Общая и личная смеси приходят от разных отправителей со своими номерами,
+    /// поэтому декодер клиента никогда не получает вперемешку пакеты двух
+    /// кодировщиков одного потока.
+    personal_encoder: Option<Box<dyn VoiceEncoder>>,
+    /// Метка шага, на котором получатель последний раз попал в смесь.
+    last_mixed_us: u64,
+    shared_sequence: u64,
+    personal_sequence: u64,
+}
+
+/// Сведение голосов одной комнаты сервера.
+pub(crate) struct RoomMixer {
+    room_id: Uuid,
+    codec: Arc<dyn MixCodec>,
+    bitrate_bps: u32,
+    timestamp_us: u64,
+    shared_encoder: Option<Box<dyn VoiceEncoder>>,
+    speakers: Vec<SpeakerState>,
+    receivers: Vec<ReceiverState>,
+}
+
+impl RoomMixer {
+    /// Создает сведение комнаты с кодеком и битрейтом исходящих смесей.
+    pub(crate) fn new(room_id: Uuid, codec: Arc<dyn MixCodec>, bitrate_bps: u32) -> Self {
+        Self {
+            room_id,
+            shared_encoder: codec.encoder(bitrate_bps),
+            codec,
+            bitrate_bps,
+            timestamp_us: 0,
+            speakers: Vec::new(),
+            receivers: Vec::new(),
+        }
+    }
+
+    /// Декодирует голосовую датаграмму говорящего в его буфер.
+    ///
+    /// Повторы и опоздавшие пакеты отбрасываются: их место в буфере уже прошло.
+    pub(crate) fn push_voice(&mut self, user_id: Uuid, datagram: &MediaDatagram) {
+        let position = match self
+            .speakers
+            .iter()
+            .position(|speaker| speaker.user_id == user_id)
+        {
+            Some(position) => position,
+            None => {
+                let Some(decoder) = self.codec.decoder() else {
+                    return;
+                };
+                self.speakers.push(SpeakerState {
+                    user_id,
+                    decoder,
+                    samples: VecDeque::new(),
+                    last_sequence: None,
+                });
+                self.speakers.len() - 1
+            }
+        };
+        let speaker = &mut self.speakers[position];
+        if speaker
+            .last_sequence
+            .is_some_and(|last| datagram.sequence <= last)
+        {
+            return;
+        }
+        speaker.last_sequence = Some(datagram.sequence);
+        let Some(pcm) = speaker.decoder.decode(&datagram.payload) else {
+            return;
+        };
+        speaker.samples.extend(pcm);
+        let overflow = speaker.samples.len().saturating_sub(MAX_BUFFERED_SAMPLES);
+        speaker.samples.drain(..overflow);
+    }
+
+    /// Сводит один шаг для текущих участников комнаты.
+    ///
+    /// Получатель без чужих голосов в этом шаге ничего не получает, как и при
+    /// пересылке, когда все молчат. Заглушенные сервером участники тоже ничего
+    /// не получают, но их голос сводится для остальных.
+    pub(crate) fn tick(&mut self, participants: &[MixParticipant]) -> Vec<MixedVoiceFrame> {
+        self.sync_participants(participants);
+        let timestamp_us = self.timestamp_us;
+        self.timestamp_us += u64::from(MIX_FRAME_DURATION_US);
+        self.release_idle_personal_encoders(timestamp_us);
+
+        let mut frames = Vec::new();
+        for speaker in &mut self.speakers {
+            if speaker.samples.len() < MIX_FRAME_SAMPLES {
+                continue;
+            }
+            let frame = speaker
+                .samples
+                .drain(..MIX_FRAME_SAMPLES)
+                .collect::<Vec<_>>();
+            let level = rms_level(&frame);
+            if level >= MIN_SPEAKER_LEVEL {
+                frames.push((speaker.user_id, level, frame));
+            }
+        }
+        frames.sort_by(|left, right| right.1.total_cmp(&left.1));
+        frames.truncate(MAX_MIXED_SPEAKERS);
+        if frames.is_empty() {
+            return Vec::new();
+        }
+
+        // Слушатели без личной смеси слышат одно и то же, поэтому общая смесь
+        // кодируется один раз; отдельно кодируются только личные смеси.
+        let shared_payload = match self.shared_encoder.as_mut() {
+            Some(encoder) => encoder.encode(&mix_frames(&frames, None)),
+            None => None,
+        };
+        let mut output = Vec::new();
+        for receiver in &mut self.receivers {
+            if receiver.participant.deafened {
+                receiver.personal_encoder = None;
+                continue;
+            }
+            let user_id = receiver.participant.user_id;
+            let speaking = frames.iter().any(|(speaker, _, _)| *speaker == user_id);
+            if speaking {
+                receiver.last_mixed_us = timestamp_us;
+                if receiver.personal_encoder.is_none() {
+                    receiver.personal_encoder = self.codec.encoder(self.bitrate_bps);
+                }
+            }
+            let (payload, sender_user_id, sequence) = match receiver.personal_encoder.as_mut() {
+                Some(_) if speaking && frames.len() == 1 => continue,
+                Some(encoder) => (
+                    encoder.encode(&mix_frames(&frames, Some(user_id))),
+                    MIXED_PERSONAL_VOICE_SENDER_USER_ID,
+                    &mut receiver.personal_sequence,
+                ),
+                None => (
+                    shared_payload.clone(),
+                    MIXED_VOICE_SENDER_USER_ID,
+                    &mut receiver.shared_sequence,
+                ),
+            };
+            let Some(payload) = payload else {
+                continue;
+            };
+            output.push(MixedVoiceFrame {
+                session_id: receiver.participant.session_id,
+                datagram: MediaDatagram {
+                    kind: MediaDatagramKind::VoiceFrame,
+                    codec: MediaCodec::Opus,
+                    flags: 0,
+                    sequence: *sequence,
+                    timestamp_us,
+                    duration_us: MIX_FRAME_DURATION_US,
+                    room_id: self.room_id,
+                    sender_user_id,
+                    spatial_layer: VideoSpatialLayer::High,
+                    audio_level: None,
+                    payload,
+                },
+            });
+            *sequence += 1;
+        }
+        output
+    }
+
+    /// Освобождает личные кодировщики участников, давно не попадавших в смесь.
+    fn release_idle_personal_encoders(&mut self, timestamp_us: u64) {
+        let idle_us = PERSONAL_MIX_IDLE.as_micros() as u64;
+        for receiver in &mut self.receivers {
+            if receiver.personal_encoder.is_some()
+                && timestamp_us.saturating_sub(receiver.last_mixed_us) >= idle_us
+            {
+                receiver.personal_encoder = None;
+            }
+        }
+    }
+
+    /// Сохраняет состояние оставшихся участников и забывает ушедших.
+    fn sync_participants(&mut self, participants: &[MixParticipant]) {
+        self.receivers.retain(|state| {
+            participants
+                .iter()
+                .any(|participant| participant.same_session(&state.participant))
+        });
+        for participant in participants {
+            if let Some(state) = self
+                .receivers
+                .iter_mut()
+                .find(|state| state.participant.same_session(participant))
+            {
+                state.participant.deafened = participant.deafened;
+                continue;
+            }
+            self.receivers.push(ReceiverState {
+                participant: *participant,
+                personal_encoder: None,
+                last_mixed_us: 0,
+                shared_sequence: 0,
+                personal_sequence: 0,
+            });
+        }
+        self.speakers.retain(|speaker| {
+            participants
+                .iter()
+                .any(|participant| participant.user_id == speaker.user_id)
+        });
+    }
+}
+
+/// Складывает кадры говорящих, кроме `excluded_user_id`, с ограничением амплитуды.
+fn mix_frames(frames: &[(Uuid, f32, Vec<f32>)], excluded_user_id: Option<Uuid>) -> Vec<f32> {
+    let mut mix = vec![0.0_f32; MIX_FRAME_SAMPLES];
+    for (user_id, _, frame) in frames {
+        if Some(*user_id) == excluded_user_id {
+            continue;
+        }
+        for (mixed, sample) in mix.iter_mut().zip(frame) {
+            *mixed += sample;
+        }
+    }
+    for sample in &mut mix {
+        *sample = sample.clamp(-1.0, 1.0);
+    }
+    mix
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,62 @@
+//! Opus-кодек серверного сведения.
+
+use opus::{Application, Bitrate, Channels, Decoder, Encoder, Signal};
+
+use super::{MIX_FRAME_SAMPLES, MixCodec, VoiceDecoder, VoiceEncoder};
+
+const OPUS_SAMPLE_RATE: u32 = 48_000;
+/// Самый длинный пакет Opus, 120 мс при 48 кГц.
+const MAX_DECODED_SAMPLES: usize = 5_760;
+const MAX_OPUS_PACKET_BYTES: usize = 4_000;
+
+/// Opus через системную libopus.
+pub(super) struct OpusMixCodec;
+
+impl MixCodec for OpusMixCodec {
+    fn decoder(&self) -> Option<Box<dyn VoiceDecoder>> {
+        match Decoder::new(OPUS_SAMPLE_RATE, Channels::Mono) {
+            Ok(decoder) => Some(Box::new(OpusVoiceDecoder(decoder))),
+            Err(error) => {
+                tracing::warn!(%error, "failed to create voice mixing opus decoder");
+                None
+            }
+        }
+    }
+
+    fn encoder(&self, bitrate_bps: u32) -> Option<Box<dyn VoiceEncoder>> {
+        let encoder = Encoder::new(OPUS_SAMPLE_RATE, Channels::Mono, Application::Voip).and_then(
+            |mut encoder| {
+                encoder.set_bitrate(Bitrate::Bits(bitrate_bps as i32))?;
+                encoder.set_signal(Signal::Voice)?;
+                Ok(encoder)
+            },
+        );
+        match encoder {
+            Ok(encoder) => Some(Box::new(OpusVoiceEncoder(encoder))),
+            Err(error) => {
+                tracing::warn!(%error, "failed to create voice mixing opus encoder");
+                None
+            }
+        }
+    }
+}
+
+struct OpusVoiceDecoder(Decoder);
+
+impl VoiceDecoder for OpusVoiceDecoder {
+    fn decode(&mut self, packet: &[u8]) -> Option<Vec<f32>> {
+        let mut pcm = vec![0.0; MAX_DECODED_SAMPLES];
+        let samples = self.0.decode_float(packet, &mut pcm, false).ok()?;
+        pcm.truncate(samples);
+        Some(pcm)
+    }
+}
+
+struct OpusVoiceEncoder(Encoder);
+
+impl VoiceEncoder for OpusVoiceEncoder {
+    fn encode(&mut self, pcm: &[f32]) -> Option<Vec<u8>> {
+        debug_assert_eq!(pcm.len(), MIX_FRAME_SAMPLES);
+        self.0.encode_vec_float(pcm, MAX_OPUS_PACKET_BYTES).ok()
+    }
+}
@@ -0,0 +1,234 @@
+//! Комнаты в режиме сведения и доступ к ним из пересылки голоса.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use cheenhub_contracts::media::MediaDatagram;
+use uuid::Uuid;
+
+use super::super::super::infrastructure::{
+    InMemoryVoicePresenceStore, VoicePresence, VoicePresenceTargetKind,
+};
+use super::{MixCodec, MixParticipant, MixedVoiceFrame, RoomMixer, build_codec};
+
+/// Предел голосовых датаграмм комнаты, ждущих следующего шага сведения.
+const MAX_PENDING_VOICE: usize = 512;
+
+/// Сведение одной комнаты: очередь пришедшего голоса и состояние кодеков.
+///
+/// Пересылка только ставит датаграмму в очередь; декодирование и кодирование
+/// выполняются на шаге сведения вне рабочих потоков tokio.
+struct RoomMixing {
+    pending_voice: Mutex<Vec<(Uuid, MediaDatagram)>>,
+    mixer: Mutex<RoomMixer>,
+}
+
+impl RoomMixing {
+    fn queue_voice(&self, user_id: Uuid, datagram: &MediaDatagram) {
+        let mut pending = lock(&self.pending_voice);
+        if pending.len() < MAX_PENDING_VOICE {
+            pending.push((user_id, datagram.clone()));
+        }
+    }
+
+    fn tick(&self, participants: &[MixParticipant]) -> Vec<MixedVoiceFrame> {
+        let pending = std::mem::take(&mut *lock(&self.pending_voice));
+        let mut mixer = lock(&self.mixer);
+        for (user_id, datagram) in &pending {
+            mixer.push_voice(*user_id, datagram);
+        }
+        mixer.tick(participants)
+    }
+}
+
+/// Сведение комнаты, включенное одним вызовом `enable_room_mixing`.
+///
+/// Задача сведения держит свой запуск и останавливается, когда трекер больше
+/// не указывает на него: после выключения или повторного включения комнаты.
+#[derive(Clone)]
+pub(crate) struct RoomMixingRun {
+    server_id: Uuid,
+    room_id: Uuid,
+    room: Arc<RoomMixing>,
+}
+
+impl RoomMixingRun {
+    /// Сервер комнаты.
+    pub(crate) fn server_id(&self) -> Uuid {
+        self.server_id
+    }
+
+    /// Комната, голоса которой сводятся.
+    pub(crate) fn room_id(&self) -> Uuid {
+        self.room_id
+    }
+}
+
+/// Комнаты в режиме сведения.
+pub(crate) struct MixingTracker {
+    codec: Option<Arc<dyn MixCodec>>,
+    rooms: HashMap<(Uuid, Uuid), Arc<RoomMixing>>,
+}
+
+impl Default for MixingTracker {
+    fn default() -> Self {
+        Self {
+            codec: build_codec(),
+            rooms: HashMap::new(),
+        }
+    }
+}
+
+/// Причина, по которой сведение комнаты не включилось.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum MixingStartError {
+    /// Сведение недоступно в этой сборке бэкенда.
+    Unavailable,
+    /// В комнате нет участников.
+    EmptyRoom,
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Включает сведение комнаты с участниками; `Ok(None)`, если оно уже включено.
+    pub(crate) async fn enable_room_mixing(
+        &self,
+        server_id: Uuid,
+        room_id: Uuid,
+        bitrate_bps: u32,
+    ) -> Result<Option<RoomMixingRun>, MixingStartError> {
+        // Состав комнаты заблокирован до включения, чтобы последний участник
+        // не ушел между проверкой и запуском сведения.
+        let entries = self.entries.lock().await;
+        let mut tracker = write(&self.mixing);
+        let codec = tracker.codec.clone().ok_or(MixingStartError::Unavailable)?;
+        if !entries
+            .iter()
+            .any(|entry| in_server_room(entry, server_id, room_id))
+        {
+            return Err(MixingStartError::EmptyRoom);
+        }
+        if tracker.rooms.contains_key(&(server_id, room_id)) {
+            return Ok(None);
+        }
+        let room = Arc::new(RoomMixing {
+            pending_voice: Mutex::new(Vec::new()),
+            mixer: Mutex::new(RoomMixer::new(room_id, codec, bitrate_bps)),
+        });
+        tracker
+            .rooms
+            .insert((server_id, room_id), Arc::clone(&room));
+        Ok(Some(RoomMixingRun {
+            server_id,
+            room_id,
+            room,
+        }))
+    }
+
+    /// Выключает сведение комнаты; `false`, если оно не было включено.
+    pub(crate) async fn disable_room_mixing(&self, server_id: Uuid, room_id: Uuid) -> bool {
+        write(&self.mixing)
+            .rooms
+            .remove(&(server_id, room_id))
+            .is_some()
+    }
+
+    /// Сводит ли сервер голоса комнаты.
+    pub(crate) async fn room_mixing_enabled(&self, server_id: Uuid, room_id: Uuid) -> bool {
+        read(&self.mixing).rooms.contains_key(&(server_id, room_id))
+    }
+
+    /// Передает голос участника сведению его комнаты.
+    ///
+    /// Возвращает `true`, если датаграмма поглощена сведением и не пересылается.
+    pub(crate) async fn mix_room_voice(
+        &self,
+        presence: &VoicePresence,
+        datagram: &MediaDatagram,
+    ) -> bool {
+        let tracker = read(&self.mixing);
+        let Some(room) = tracker.rooms.get(&(presence.server_id, presence.room_id)) else {
+            return false;
+        };
+        room.queue_voice(presence.user_id, datagram);
+        true
+    }
+
+    /// Выполняет один шаг сведения; `None`, если этот запуск сведения уже не действует.
+    pub(crate) async fn mix_room_tick(
+        &self,
+        run: &RoomMixingRun,
+        participants: &[MixParticipant],
+    ) -> Option<Vec<MixedVoiceFrame>> {
+        let (server_id, room_id) = (run.server_id, run.room_id);
+        let current = read(&self.mixing)
+            .rooms
+            .get(&(server_id, room_id))
+            .is_some_and(|room| Arc::ptr_eq(room, &run.room));
+        if !current {
+            return None;
+        }
+        let room = Arc::clone(&run.room);
+        let participants = participants.to_vec();
+        match tokio::task::spawn_blocking(move || room.tick(&participants)).await {
+            Ok(frames) => Some(frames),
+            Err(error) => {
+                tracing::warn!(
+                    server_id = %server_id,
+                    room_id = %room_id,
+                    %error,
+                    "voice room audio mixing step failed"
+                );
+                Some(Vec::new())
+            }
+        }
+    }
+
+    /// Выключает сведение комнат, из которых ушел последний участник.
+    pub(in crate::features::voice_chat) async fn stop_mixing_in_emptied_rooms(
+        &self,
+        removed: &[VoicePresence],
+    ) {
+        if read(&self.mixing).rooms.is_empty() {
+            return;
+        }
+        let entries = self.entries.lock().await;
+        let mut tracker = write(&self.mixing);
+        for presence in removed {
+            if presence.target_kind == VoicePresenceTargetKind::Server
+                && !entries
+                    .iter()
+                    .any(|entry| in_server_room(entry, presence.server_id, presence.room_id))
+            {
+                tracker
+                    .rooms
+                    .remove(&(presence.server_id, presence.room_id));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+impl InMemoryVoicePresenceStore {
+    /// Подменяет кодек сведения; включенные комнаты сохраняют прежний.
+    pub(crate) fn replace_mixing_codec(&self, codec: Option<Arc<dyn MixCodec>>) {
+        write(&self.mixing).codec = codec;
+    }
+}
+
+fn in_server_room(presence: &VoicePresence, server_id: Uuid, room_id: Uuid) -> bool {
+    presence.target_kind == VoicePresenceTargetKind::Server
+        && presence.server_id == server_id
+        && presence.room_id == room_id
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn read(tracker: &RwLock<MixingTracker>) -> RwLockReadGuard<'_, MixingTracker> {
+    tracker.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn write(tracker: &RwLock<MixingTracker>) -> RwLockWriteGuard<'_, MixingTracker> {
+    tracker.write().unwrap_or_else(PoisonError::into_inner)
+}
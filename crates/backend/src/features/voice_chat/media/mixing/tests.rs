@@ -0,0 +1,387 @@
+use super::*;
+use crate::features::voice_chat::infrastructure::{
+    InMemoryVoicePresenceStore, VoicePresenceTargetKind,
+};
+use crate::features::voice_chat::test_builders::{voice_datagram, voice_presence};
+
+/// Кодек без сжатия: пакет хранит сэмплы f32 в little-endian.
+struct PcmCodec;
+
+struct PcmDecoder;
+
+struct PcmEncoder;
+
+impl MixCodec for PcmCodec {
+    fn decoder(&self) -> Option<Box<dyn VoiceDecoder>> {
+        Some(Box::new(PcmDecoder))
+    }
+
+    fn encoder(&self, _bitrate_bps: u32) -> Option<Box<dyn VoiceEncoder>> {
+        Some(Box::new(PcmEncoder))
+    }
+}
+
+impl VoiceDecoder for PcmDecoder {
+    fn decode(&mut self, packet: &[u8]) -> Option<Vec<f32>> {
+        Some(
+            packet
+                .chunks_exact(4)
+                .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                .collect(),
+        )
+    }
+}
+
+impl VoiceEncoder for PcmEncoder {
+    fn encode(&mut self, pcm: &[f32]) -> Option<Vec<u8>> {
+        Some(pcm.iter().flat_map(|sample| sample.to_le_bytes()).collect())
+    }
+}
+
+fn voice(room_id: Uuid, sequence: u64, amplitude: f32) -> MediaDatagram {
+    let payload = PcmEncoder
+        .encode(&[amplitude; MIX_FRAME_SAMPLES])
+        .expect("pcm encodes");
+    voice_datagram(room_id, Uuid::nil(), sequence, payload)
+}
+
+fn receiver() -> MixParticipant {
+    MixParticipant {
+        session_id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(),
+        deafened: false,
+    }
+}
+
+fn mixer(room_id: Uuid) -> RoomMixer {
+    RoomMixer::new(room_id, Arc::new(PcmCodec), 32_000)
+}
+
+fn first_sample(frame: &MixedVoiceFrame) -> f32 {
+    PcmDecoder
+        .decode(&frame.datagram.payload)
+        .expect("pcm decodes")[0]
+}
+
+#[test]
+fn rms_level_matches_constant_signal() {
+    assert_eq!(rms_level(&[]), 0.0);
+    assert!((rms_level(&[0.5; 960]) - 0.5).abs() < 1e-6);
+    assert_eq!(rms_level(&[4.0, -4.0]), 1.0);
+}
+
+#[test]
+fn each_receiver_hears_others_without_own_voice() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let (alice, bob, carol) = (receiver(), receiver(), receiver());
+    mixer.tick(&[alice, bob, carol]);
+    mixer.push_voice(alice.user_id, &voice(room_id, 0, 0.1));
+    mixer.push_voice(bob.user_id, &voice(room_id, 0, 0.2));
+
+    let frames = mixer.tick(&[alice, bob, carol]);
+
+    assert_eq!(frames.len(), 3);
+    let heard = |receiver: MixParticipant, sender_user_id: Uuid| {
+        let frame = frames
+            .iter()
+            .find(|frame| frame.session_id == receiver.session_id)
+            .expect("receiver gets a mix");
+        assert_eq!(frame.datagram.sender_user_id, sender_user_id);
+        assert_eq!(frame.datagram.room_id, room_id);
+        first_sample(frame)
+    };
+    assert!((heard(alice, MIXED_PERSONAL_VOICE_SENDER_USER_ID) - 0.2).abs() < 1e-6);
+    assert!((heard(bob, MIXED_PERSONAL_VOICE_SENDER_USER_ID) - 0.1).abs() < 1e-6);
+    assert!((heard(carol, MIXED_VOICE_SENDER_USER_ID) - 0.3).abs() < 1e-6);
+}
+
+#[test]
+fn deafened_speaker_is_heard_but_gets_no_mix() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let alice = MixParticipant {
+        deafened: true,
+        ..receiver()
+    };
+    let (bob, carol) = (receiver(), receiver());
+    let participants = [alice, bob, carol];
+
+    for sequence in 0..2 {
+        mixer.push_voice(alice.user_id, &voice(room_id, sequence, 0.1));
+        mixer.push_voice(bob.user_id, &voice(room_id, sequence, 0.2));
+        let frames = mixer.tick(&participants);
+
+        assert_eq!(frames.len(), 2);
+        assert!(
+            frames
+                .iter()
+                .all(|frame| frame.session_id != alice.session_id)
+        );
+        let heard = |receiver: MixParticipant| {
+            first_sample(
+                frames
+                    .iter()
+                    .find(|frame| frame.session_id == receiver.session_id)
+                    .expect("receiver gets a mix"),
+            )
+        };
+        assert!((heard(bob) - 0.1).abs() < 1e-6);
+        assert!((heard(carol) - 0.3).abs() < 1e-6);
+        assert_eq!(frames[0].datagram.sequence, sequence);
+    }
+}
+
+#[test]
+fn former_speaker_stays_on_personal_stream() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let (alice, bob) = (receiver(), receiver());
+    let receivers = [alice, bob];
+    let alice_frame = |frames: Vec<MixedVoiceFrame>| {
+        frames
+            .into_iter()
+            .find(|frame| frame.session_id == alice.session_id)
+            .expect("alice gets a mix")
+            .datagram
+    };
+
+    mixer.push_voice(bob.user_id, &voice(room_id, 0, 0.2));
+    let shared = alice_frame(mixer.tick(&receivers));
+    assert_eq!(shared.sender_user_id, MIXED_VOICE_SENDER_USER_ID);
+
+    mixer.push_voice(alice.user_id, &voice(room_id, 0, 0.1));
+    mixer.push_voice(bob.user_id, &voice(room_id, 1, 0.2));
+    let personal = alice_frame(mixer.tick(&receivers));
+    assert_eq!(personal.sender_user_id, MIXED_PERSONAL_VOICE_SENDER_USER_ID);
+    assert_eq!(personal.sequence, 0);
+
+    // Алиса замолчала, но ее декодер продолжает поток личного кодировщика.
+    mixer.push_voice(bob.user_id, &voice(room_id, 2, 0.2));
+    let later = alice_frame(mixer.tick(&receivers));
+    assert_eq!(later.sender_user_id, MIXED_PERSONAL_VOICE_SENDER_USER_ID);
+    assert_eq!(later.sequence, 1);
+}
+
+#[test]
+fn idle_speaker_returns_to_shared_stream() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let (alice, bob) = (receiver(), receiver());
+    let receivers = [alice, bob];
+    let mut alice_frame = |sequence: u64, alice_speaks: bool| {
+        if alice_speaks {
+            mixer.push_voice(alice.user_id, &voice(room_id, 0, 0.1));
+        }
+        mixer.push_voice(bob.user_id, &voice(room_id, sequence, 0.2));
+        mixer
+            .tick(&receivers)
+            .into_iter()
+            .find(|frame| frame.session_id == alice.session_id)
+            .expect("alice gets a mix")
+            .datagram
+    };
+    assert_eq!(alice_frame(0, false).sequence, 0);
+    assert_eq!(
+        alice_frame(1, true).sender_user_id,
+        MIXED_PERSONAL_VOICE_SENDER_USER_ID
+    );
+
+    let idle_ticks = (PERSONAL_MIX_IDLE.as_micros() / MIX_INTERVAL.as_micros()) as u64;
+    for sequence in 2..=idle_ticks {
+        assert_eq!(
+            alice_frame(sequence, false).sender_user_id,
+            MIXED_PERSONAL_VOICE_SENDER_USER_ID
+        );
+    }
+    let shared = alice_frame(idle_ticks + 1, false);
+    assert_eq!(shared.sender_user_id, MIXED_VOICE_SENDER_USER_ID);
+    assert_eq!(shared.sequence, 1);
+}
+
+#[test]
+fn only_loudest_speakers_are_mixed() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let listener = receiver();
+    let speakers = [0.05, 0.01, 0.2, 0.1].map(|amplitude| (receiver(), amplitude));
+    let mut receivers = speakers.map(|(speaker, _)| speaker).to_vec();
+    receivers.push(listener);
+    for (speaker, amplitude) in speakers {
+        mixer.push_voice(speaker.user_id, &voice(room_id, 0, amplitude));
+    }
+
+    let frames = mixer.tick(&receivers);
+
+    let mix = frames
+        .iter()
+        .find(|frame| frame.session_id == listener.session_id)
+        .expect("listener gets a mix");
+    assert_eq!(MAX_MIXED_SPEAKERS, 3);
+    assert!((first_sample(mix) - 0.35).abs() < 1e-6);
+}
+
+#[test]
+fn silence_and_lone_speaker_produce_no_frames() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let (alice, bob) = (receiver(), receiver());
+    mixer.push_voice(alice.user_id, &voice(room_id, 0, 0.0));
+    assert!(mixer.tick(&[alice, bob]).is_empty());
+
+    mixer.push_voice(alice.user_id, &voice(room_id, 1, 0.3));
+    let frames = mixer.tick(&[alice, bob]);
+
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].session_id, bob.session_id);
+}
+
+#[test]
+fn late_and_duplicate_packets_are_dropped() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let (alice, bob) = (receiver(), receiver());
+    mixer.push_voice(alice.user_id, &voice(room_id, 5, 0.3));
+    mixer.push_voice(alice.user_id, &voice(room_id, 5, 0.3));
+    mixer.push_voice(alice.user_id, &voice(room_id, 4, 0.3));
+
+    assert_eq!(mixer.tick(&[alice, bob]).len(), 1);
+    assert!(mixer.tick(&[alice, bob]).is_empty());
+}
+
+#[test]
+fn mixed_sequence_advances_per_receiver() {
+    let room_id = Uuid::new_v4();
+    let mut mixer = mixer(room_id);
+    let (alice, bob) = (receiver(), receiver());
+    for sequence in 0..3 {
+        mixer.push_voice(alice.user_id, &voice(room_id, sequence, 0.3));
+        let frames = mixer.tick(&[alice, bob]);
+        assert_eq!(frames[0].datagram.sequence, sequence);
+        assert_eq!(
+            frames[0].datagram.timestamp_us,
+            sequence * u64::from(MIX_FRAME_DURATION_US)
+        );
+    }
+}
+
+#[tokio::test]
+async fn store_consumes_voice_only_while_mixing_is_enabled() {
+    let store = InMemoryVoicePresenceStore::default();
+    store.replace_mixing_codec(Some(Arc::new(PcmCodec)));
+    let (server_id, room_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let presence = voice_presence(server_id, room_id, Uuid::new_v4());
+    store.join(presence.clone()).await;
+    let listener = receiver();
+    let speaker = MixParticipant {
+        session_id: presence.session_id,
+        user_id: presence.user_id,
+        deafened: false,
+    };
+    let datagram = voice(room_id, 0, 0.3);
+
+    assert!(!store.mix_room_voice(&presence, &datagram).await);
+    let run = store
+        .enable_room_mixing(server_id, room_id, 32_000)
+        .await
+        .expect("room has participants")
+        .expect("mixing was off");
+    assert!(
+        store
+            .enable_room_mixing(server_id, room_id, 32_000)
+            .await
+            .expect("room has participants")
+            .is_none()
+    );
+    assert!(store.room_mixing_enabled(server_id, room_id).await);
+    assert!(store.mix_room_voice(&presence, &datagram).await);
+    let frames = store
+        .mix_room_tick(&run, &[speaker, listener])
+        .await
+        .expect("mixing is enabled");
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].session_id, listener.session_id);
+
+    assert!(store.disable_room_mixing(server_id, room_id).await);
+    assert!(!store.mix_room_voice(&presence, &datagram).await);
+    assert!(
+        store
+            .mix_room_tick(&run, &[speaker, listener])
+            .await
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn reenabling_mixing_retires_the_previous_run() {
+    let store = InMemoryVoicePresenceStore::default();
+    store.replace_mixing_codec(Some(Arc::new(PcmCodec)));
+    let (server_id, room_id) = (Uuid::new_v4(), Uuid::new_v4());
+    store
+        .join(voice_presence(server_id, room_id, Uuid::new_v4()))
+        .await;
+    let enable = || async {
+        store
+            .enable_room_mixing(server_id, room_id, 32_000)
+            .await
+            .expect("room has participants")
+            .expect("mixing was off")
+    };
+
+    let first = enable().await;
+    assert!(store.disable_room_mixing(server_id, room_id).await);
+    let second = enable().await;
+
+    assert!(store.mix_room_tick(&first, &[]).await.is_none());
+    assert!(store.mix_room_tick(&second, &[]).await.is_some());
+}
+
+#[tokio::test]
+async fn store_reports_unavailable_codec() {
+    let store = InMemoryVoicePresenceStore::default();
+    store.replace_mixing_codec(None);
+
+    assert_eq!(
+        store
+            .enable_room_mixing(Uuid::new_v4(), Uuid::new_v4(), 32_000)
+            .await
+            .err(),
+        Some(MixingStartError::Unavailable)
+    );
+}
+
+#[tokio::test]
+async fn last_participant_leaving_stops_mixing() {
+    let store = InMemoryVoicePresenceStore::default();
+    store.replace_mixing_codec(Some(Arc::new(PcmCodec)));
+    let (server_id, room_id) = (Uuid::new_v4(), Uuid::new_v4());
+    assert_eq!(
+        store
+            .enable_room_mixing(server_id, room_id, 32_000)
+            .await
+            .err(),
+        Some(MixingStartError::EmptyRoom)
+    );
+    let [alice, bob] = [(); 2].map(|()| voice_presence(server_id, room_id, Uuid::new_v4()));
+    store.join(alice.clone()).await;
+    store.join(bob.clone()).await;
+    assert!(matches!(
+        store.enable_room_mixing(server_id, room_id, 32_000).await,
+        Ok(Some(_))
+    ));
+
+    for (presence, still_mixed) in [(&alice, true), (&bob, false)] {
+        store
+            .leave_room(
+                &presence.realtime_stream_id,
+                VoicePresenceTargetKind::Server,
+                &server_id,
+                &room_id,
+            )
+            .await;
+        assert_eq!(
+            store.room_mixing_enabled(server_id, room_id).await,
+            still_mixed
+        );
+    }
+}
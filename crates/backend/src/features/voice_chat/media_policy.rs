@@ -1,19 +1,22 @@
 //! Проверка ограничений исходящих видеопубликаций голосовой комнаты.
 
+use std::collections::HashSet;
 use std::time::{Duration, Instant};
 
 use cheenhub_contracts::{
     media::{
-        MEDIA_DATAGRAM_FLAG_FRAGMENTED, MEDIA_DATAGRAM_FLAG_KEY_FRAME, MediaDatagram,
+        MEDIA_DATAGRAM_FLAG_FRAGMENTED, MEDIA_DATAGRAM_FLAG_KEY_FRAME, MediaCodec, MediaDatagram,
         MediaDatagramKind,
     },
-    video_presets::{VideoPresetId, VideoStreamSource},
+    video_presets::{ALL_VIDEO_PRESETS, VideoPresetId, VideoSpatialLayer, VideoStreamSource},
 };
 use uuid::Uuid;
 
 use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
-use vp9::parse_key_frame_dimensions;
 
+mod av1;
+mod bits;
+mod h264;
 mod vp9;
 
 const VIDEO_FRAGMENT_HEADER_LEN: usize = 8;
@@ -21,6 +24,26 @@ const FPS_MEASUREMENT_WINDOW: Duration = Duration::from_secs(1);
 const FPS_BLOCK_DURATION: Duration = Duration::from_secs(1);
 const FPS_JITTER_ALLOWANCE: u32 = 2;
 const RECENT_SEQUENCE_LIMIT: usize = 128;
+/// После такой паузы публикация перестает занимать место в лимите видео комнаты.
+const PUBLICATION_IDLE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Ограничения видео, действующие в голосовых комнатах одного сервера.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RoomVideoPolicy {
+    /// Пресеты, с которыми разрешено публиковать камеру и экран.
+    pub(crate) allowed_presets: Vec<VideoPresetId>,
+    /// Сколько пользователей одной комнаты могут публиковать видео одновременно.
+    pub(crate) max_publishers: Option<u32>,
+}
+
+impl Default for RoomVideoPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_presets: ALL_VIDEO_PRESETS.to_vec(),
+            max_publishers: None,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum VideoAdmission {
@@ -33,10 +56,11 @@ pub(super) enum VideoDropReason {
     MalformedFragment,
     AwaitingFirstFragment,
     AwaitingKeyFrame,
-    InvalidVp9KeyFrame,
+    InvalidKeyFrame,
     UnsupportedResolution { width: u32, height: u32 },
     FpsLimitExceeded { max_fps: u32, observed_frames: u32 },
     FpsBlockActive,
+    PublisherLimitReached { max_publishers: u32 },
 }
 
 #[derive(Default)]
@@ -50,15 +74,9 @@ impl VideoPublicationTracker {
         session_id: Uuid,
         user_id: Uuid,
         datagram: &MediaDatagram,
-        allowed_presets: &[VideoPresetId],
+        policy: &RoomVideoPolicy,
     ) -> VideoAdmission {
-        self.inspect_at(
-            session_id,
-            user_id,
-            datagram,
-            allowed_presets,
-            Instant::now(),
-        )
+        self.inspect_at(session_id, user_id, datagram, policy, Instant::now())
     }
 
     fn inspect_at(
@@ -66,7 +84,7 @@ impl VideoPublicationTracker {
         session_id: Uuid,
         user_id: Uuid,
         datagram: &MediaDatagram,
-        allowed_presets: &[VideoPresetId],
+        policy: &RoomVideoPolicy,
         now: Instant,
     ) -> VideoAdmission {
         let key = VideoPublicationKey {
@@ -74,7 +92,9 @@ impl VideoPublicationTracker {
             user_id,
             room_id: datagram.room_id,
             kind: datagram.kind,
+            layer: datagram.spatial_layer,
         };
+        let other_publishers = self.active_publishers(datagram.room_id, user_id, now);
         let publication = match self.publications.iter_mut().find(|entry| entry.key == key) {
             Some(publication) => publication,
             None => {
@@ -98,18 +118,69 @@ impl VideoPublicationTracker {
             return decision;
         }
 
-        let is_key_frame = datagram.flags & MEDIA_DATAGRAM_FLAG_KEY_FRAME != 0;
-        let decision = publication.inspect_frame(
-            datagram.sequence,
-            is_key_frame,
-            fragment.vp9_payload,
-            allowed_presets,
-            now,
-        );
+        let decision = match policy.max_publishers {
+            Some(max_publishers)
+                if !publication.is_active(now) && other_publishers >= max_publishers as usize =>
+            {
+                VideoAdmission::Drop(VideoDropReason::PublisherLimitReached { max_publishers })
+            }
+            _ => {
+                let is_key_frame = datagram.flags & MEDIA_DATAGRAM_FLAG_KEY_FRAME != 0;
+                publication.inspect_frame(
+                    datagram.sequence,
+                    is_key_frame,
+                    datagram.codec,
+                    fragment.payload,
+                    &policy.allowed_presets,
+                    now,
+                )
+            }
+        };
+        if decision == VideoAdmission::Forward {
+            publication.last_forwarded_at = Some(now);
+        }
         publication.remember(datagram.sequence, decision);
         decision
     }
 
+    /// Считает других пользователей, которые сейчас публикуют видео в комнате.
+    fn active_publishers(&self, room_id: Uuid, user_id: Uuid, now: Instant) -> usize {
+        self.publications
+            .iter()
+            .filter(|publication| {
+                publication.key.room_id == room_id
+                    && publication.key.user_id != user_id
+                    && publication.is_active(now)
+            })
+            .map(|publication| publication.key.user_id)
+            .collect::<HashSet<_>>()
+            .len()
+    }
+
+    /// Перечисляет simulcast-слои, которые пользователь сейчас публикует для одного источника.
+    fn active_layers(
+        &self,
+        room_id: Uuid,
+        user_id: Uuid,
+        kind: MediaDatagramKind,
+        now: Instant,
+    ) -> Vec<VideoSpatialLayer> {
+        let mut layers = self
+            .publications
+            .iter()
+            .filter(|publication| {
+                publication.key.room_id == room_id
+                    && publication.key.user_id == user_id
+                    && publication.key.kind == kind
+                    && publication.is_active(now)
+            })
+            .map(|publication| publication.key.layer)
+            .collect::<Vec<_>>();
+        layers.sort();
+        layers.dedup();
+        layers
+    }
+
     pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
         self.publications.retain(|publication| {
             !removed.iter().any(|presence| {
@@ -127,12 +198,43 @@ impl InMemoryVoicePresenceStore {
         session_id: Uuid,
         user_id: Uuid,
         datagram: &MediaDatagram,
-        allowed_presets: &[VideoPresetId],
+        policy: &RoomVideoPolicy,
     ) -> VideoAdmission {
         self.video_publications
             .lock()
             .await
-            .inspect(session_id, user_id, datagram, allowed_presets)
+            .inspect(session_id, user_id, datagram, policy)
+    }
+
+    /// Возвращает активные simulcast-слои публикации, из которых relay выбирает слой получателю.
+    pub(super) async fn active_video_layers(
+        &self,
+        room_id: Uuid,
+        user_id: Uuid,
+        kind: MediaDatagramKind,
+    ) -> Vec<VideoSpatialLayer> {
+        self.video_publications
+            .lock()
+            .await
+            .active_layers(room_id, user_id, kind, Instant::now())
+    }
+
+    /// Возвращает ограничения видео серверных комнат или ограничения по умолчанию.
+    pub(super) async fn server_video_policy(&self, server_id: &Uuid) -> RoomVideoPolicy {
+        self.server_video_policies
+            .lock()
+            .await
+            .get(server_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Запоминает ограничения видео, которые relay применяет к комнатам сервера.
+    pub(super) async fn set_server_video_policy(&self, server_id: Uuid, policy: RoomVideoPolicy) {
+        self.server_video_policies
+            .lock()
+            .await
+            .insert(server_id, policy);
     }
 }
 
@@ -142,6 +244,7 @@ struct VideoPublicationKey {
     user_id: Uuid,
     room_id: Uuid,
     kind: MediaDatagramKind,
+    layer: VideoSpatialLayer,
 }
 
 struct VideoPublication {
@@ -150,6 +253,7 @@ struct VideoPublication {
     window_started_at: Instant,
     window_frames: u32,
     blocked_until: Option<Instant>,
+    last_forwarded_at: Option<Instant>,
     recent_decisions: Vec<(u64, VideoAdmission)>,
 }
 
@@ -161,15 +265,24 @@ impl VideoPublication {
             window_started_at: now,
             window_frames: 0,
             blocked_until: None,
+            last_forwarded_at: None,
             recent_decisions: Vec::new(),
         }
     }
 
+    fn is_active(&self, now: Instant) -> bool {
+        self.selected_preset.is_some()
+            && self.last_forwarded_at.is_some_and(|forwarded_at| {
+                now.saturating_duration_since(forwarded_at) < PUBLICATION_IDLE_TIMEOUT
+            })
+    }
+
     fn inspect_frame(
         &mut self,
         _sequence: u64,
         is_key_frame: bool,
-        vp9_payload: &[u8],
+        codec: MediaCodec,
+        payload: &[u8],
         allowed_presets: &[VideoPresetId],
         now: Instant,
     ) -> VideoAdmission {
@@ -180,12 +293,12 @@ impl VideoPublication {
         }
 
         if is_key_frame {
-            let Some((width, height)) = parse_key_frame_dimensions(vp9_payload) else {
+            let Some((width, height)) = key_frame_dimensions(codec, payload) else {
                 self.block();
-                return VideoAdmission::Drop(VideoDropReason::InvalidVp9KeyFrame);
+                return VideoAdmission::Drop(VideoDropReason::InvalidKeyFrame);
             };
             let Some(preset) = allowed_presets.iter().copied().find(|preset| {
-                let spec = preset.spec();
+                let spec = preset.layer_spec(self.key.layer);
                 Some(spec.source) == source_for_kind(self.key.kind)
                     && spec.width == width
                     && spec.height == height
@@ -212,7 +325,7 @@ impl VideoPublication {
         self.window_frames = self.window_frames.saturating_add(1);
         let elapsed = now.saturating_duration_since(self.window_started_at);
         if elapsed >= FPS_MEASUREMENT_WINDOW {
-            let max_fps = preset.spec().max_fps;
+            let max_fps = preset.layer_spec(self.key.layer).max_fps;
             let allowed_frames = max_fps
                 .saturating_mul(elapsed.as_millis().min(u128::from(u32::MAX)) as u32)
                 / 1_000
@@ -254,14 +367,14 @@ impl VideoPublication {
 
 struct FrameFragment<'a> {
     is_first: bool,
-    vp9_payload: &'a [u8],
+    payload: &'a [u8],
 }
 
 fn frame_fragment(datagram: &MediaDatagram) -> Result<FrameFragment<'_>, VideoDropReason> {
     if datagram.flags & MEDIA_DATAGRAM_FLAG_FRAGMENTED == 0 {
         return Ok(FrameFragment {
             is_first: true,
-            vp9_payload: &datagram.payload,
+            payload: &datagram.payload,
         });
     }
     if datagram.payload.len() < VIDEO_FRAGMENT_HEADER_LEN {
@@ -274,10 +387,20 @@ fn frame_fragment(datagram: &MediaDatagram) -> Result<FrameFragment<'_>, VideoDr
     }
     Ok(FrameFragment {
         is_first: fragment_index == 0,
-        vp9_payload: &datagram.payload[VIDEO_FRAGMENT_HEADER_LEN..],
+        payload: &datagram.payload[VIDEO_FRAGMENT_HEADER_LEN..],
     })
 }
 
+/// Читает размеры key frame парсером кодека публикации.
+pub(super) fn key_frame_dimensions(codec: MediaCodec, payload: &[u8]) -> Option<(u32, u32)> {
+    match codec {
+        MediaCodec::Vp9 => vp9::parse_key_frame_dimensions(payload),
+        MediaCodec::Av1 => av1::parse_key_frame_dimensions(payload),
+        MediaCodec::H264 => h264::parse_key_frame_dimensions(payload),
+        MediaCodec::Opus => None,
+    }
+}
+
 fn source_for_kind(kind: MediaDatagramKind) -> Option<VideoStreamSource> {
     match kind {
         MediaDatagramKind::CameraFrame => Some(VideoStreamSource::Camera),
@@ -287,211 +410,4 @@ fn source_for_kind(kind: MediaDatagramKind) -> Option<VideoStreamSource> {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use cheenhub_contracts::{
-        media::{MediaCodec, MediaDatagram},
-        video_presets::{BASE_CAMERA_VIDEO_PRESETS, BASE_SCREEN_SHARE_VIDEO_PRESETS},
-    };
-
-    #[test]
-    fn screen_policy_accepts_both_base_resolutions() {
-        for (sequence, width, height) in [(1, 1280, 720), (2, 1920, 1080)] {
-            let mut tracker = VideoPublicationTracker::default();
-            let mut datagram = video_datagram(sequence, true, width, height);
-            datagram.kind = MediaDatagramKind::ScreenFrame;
-            assert_eq!(
-                tracker.inspect_at(
-                    Uuid::new_v4(),
-                    Uuid::new_v4(),
-                    &datagram,
-                    BASE_SCREEN_SHARE_VIDEO_PRESETS,
-                    Instant::now(),
-                ),
-                VideoAdmission::Forward
-            );
-        }
-    }
-
-    #[test]
-    fn camera_policy_rejects_1080p() {
-        let mut tracker = VideoPublicationTracker::default();
-        let datagram = video_datagram(1, true, 1920, 1080);
-        assert_eq!(
-            tracker.inspect_at(
-                Uuid::new_v4(),
-                Uuid::new_v4(),
-                &datagram,
-                BASE_CAMERA_VIDEO_PRESETS,
-                Instant::now(),
-            ),
-            VideoAdmission::Drop(VideoDropReason::UnsupportedResolution {
-                width: 1920,
-                height: 1080,
-            })
-        );
-    }
-
-    #[test]
-    fn fragmented_frame_is_counted_once() {
-        let mut tracker = VideoPublicationTracker::default();
-        let session_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-        let now = Instant::now();
-        let first = fragmented(video_datagram(1, true, 1280, 720), 0, 2);
-        let mut second = fragmented(video_datagram(1, true, 1280, 720), 1, 2);
-        second.room_id = first.room_id;
-        assert_eq!(
-            tracker.inspect_at(session_id, user_id, &first, BASE_CAMERA_VIDEO_PRESETS, now),
-            VideoAdmission::Forward
-        );
-        assert_eq!(
-            tracker.inspect_at(session_id, user_id, &second, BASE_CAMERA_VIDEO_PRESETS, now),
-            VideoAdmission::Forward
-        );
-        assert_eq!(tracker.publications[0].window_frames, 1);
-    }
-
-    #[test]
-    fn sustained_fps_violation_blocks_until_later_key_frame() {
-        let mut tracker = VideoPublicationTracker::default();
-        let session_id = Uuid::new_v4();
-        let user_id = Uuid::new_v4();
-        let started = Instant::now();
-        let key = video_datagram(1, true, 1280, 720);
-        let room_id = key.room_id;
-        assert_eq!(
-            tracker.inspect_at(
-                session_id,
-                user_id,
-                &key,
-                BASE_CAMERA_VIDEO_PRESETS,
-                started
-            ),
-            VideoAdmission::Forward
-        );
-        for sequence in 2..=26 {
-            let mut frame = video_datagram(sequence, false, 0, 0);
-            frame.room_id = room_id;
-            assert_eq!(
-                tracker.inspect_at(
-                    session_id,
-                    user_id,
-                    &frame,
-                    BASE_CAMERA_VIDEO_PRESETS,
-                    started + Duration::from_millis(sequence * 30),
-                ),
-                VideoAdmission::Forward
-            );
-        }
-        let mut violating = video_datagram(27, false, 0, 0);
-        violating.room_id = room_id;
-        assert!(matches!(
-            tracker.inspect_at(
-                session_id,
-                user_id,
-                &violating,
-                BASE_CAMERA_VIDEO_PRESETS,
-                started + Duration::from_millis(1_010),
-            ),
-            VideoAdmission::Drop(VideoDropReason::FpsLimitExceeded { .. })
-        ));
-        let mut early_key = video_datagram(28, true, 1280, 720);
-        early_key.room_id = room_id;
-        assert_eq!(
-            tracker.inspect_at(
-                session_id,
-                user_id,
-                &early_key,
-                BASE_CAMERA_VIDEO_PRESETS,
-                started + Duration::from_millis(1_500),
-            ),
-            VideoAdmission::Drop(VideoDropReason::FpsBlockActive)
-        );
-        let mut later_key = video_datagram(29, true, 1280, 720);
-        later_key.room_id = room_id;
-        assert_eq!(
-            tracker.inspect_at(
-                session_id,
-                user_id,
-                &later_key,
-                BASE_CAMERA_VIDEO_PRESETS,
-                started + Duration::from_millis(2_100),
-            ),
-            VideoAdmission::Forward
-        );
-    }
-
-    fn video_datagram(sequence: u64, key_frame: bool, width: u32, height: u32) -> MediaDatagram {
-        MediaDatagram {
-            kind: MediaDatagramKind::CameraFrame,
-            codec: MediaCodec::Vp9,
-            flags: if key_frame {
-                MEDIA_DATAGRAM_FLAG_KEY_FRAME
-            } else {
-                0
-            },
-            sequence,
-            timestamp_us: 0,
-            duration_us: 0,
-            room_id: Uuid::new_v4(),
-            sender_user_id: Uuid::nil(),
-            payload: if key_frame {
-                vp9_key_frame(width, height)
-            } else {
-                Vec::new()
-            },
-        }
-    }
-
-    fn fragmented(mut datagram: MediaDatagram, index: u16, count: u16) -> MediaDatagram {
-        let bytes = std::mem::take(&mut datagram.payload);
-        datagram.flags |= MEDIA_DATAGRAM_FLAG_FRAGMENTED;
-        datagram.payload = Vec::new();
-        datagram
-            .payload
-            .extend_from_slice(&(bytes.len() as u32).to_be_bytes());
-        datagram.payload.extend_from_slice(&index.to_be_bytes());
-        datagram.payload.extend_from_slice(&count.to_be_bytes());
-        datagram.payload.extend_from_slice(&bytes);
-        datagram
-    }
-
-    fn vp9_key_frame(width: u32, height: u32) -> Vec<u8> {
-        let mut writer = BitWriter::default();
-        writer.write(0b10, 2);
-        writer.write(0, 1);
-        writer.write(0, 1);
-        writer.write(0, 1);
-        writer.write(0, 1);
-        writer.write(1, 1);
-        writer.write(0, 1);
-        writer.write(0x49_83_42, 24);
-        writer.write(1, 3);
-        writer.write(0, 1);
-        writer.write(width - 1, 16);
-        writer.write(height - 1, 16);
-        writer.bytes
-    }
-
-    #[derive(Default)]
-    struct BitWriter {
-        bytes: Vec<u8>,
-        bit_offset: usize,
-    }
-
-    impl BitWriter {
-        fn write(&mut self, value: u32, count: usize) {
-            for bit_index in (0..count).rev() {
-                if self.bit_offset.is_multiple_of(8) {
-                    self.bytes.push(0);
-                }
-                let bit = ((value >> bit_index) & 1) as u8;
-                let byte_index = self.bit_offset / 8;
-                let shift = 7 - self.bit_offset % 8;
-                self.bytes[byte_index] |= bit << shift;
-                self.bit_offset += 1;
-            }
-        }
-    }
-}
+mod tests;
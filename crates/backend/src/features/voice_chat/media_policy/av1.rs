@@ -0,0 +1,157 @@
+//! Чтение размеров кадра из AV1 sequence header OBU.
+
+use super::bits::BitReader;
+
+const OBU_SEQUENCE_HEADER: u32 = 1;
+
+/// Возвращает размеры кадра из sequence header, который кодировщик кладёт в key frame.
+///
+/// Relay берёт `max_frame_width/height`: WebCodecs и MediaCodec записывают туда
+/// размер сконфигурированного потока, а frame header меняет размер только при
+/// явном `frame_size_override_flag`.
+pub(super) fn parse_key_frame_dimensions(payload: &[u8]) -> Option<(u32, u32)> {
+    let mut remaining = payload;
+    while !remaining.is_empty() {
+        let (obu_type, obu_payload, rest) = next_obu(remaining)?;
+        if obu_type == OBU_SEQUENCE_HEADER {
+            return parse_sequence_header(obu_payload);
+        }
+        remaining = rest;
+    }
+    None
+}
+
+/// Делит low-overhead поток на тип, полезную нагрузку и остаток после первого OBU.
+fn next_obu(bytes: &[u8]) -> Option<(u32, &[u8], &[u8])> {
+    let header = *bytes.first()?;
+    (header & 0x80 == 0).then_some(())?; // obu_forbidden_bit
+    let obu_type = u32::from((header >> 3) & 0x0f);
+    let has_extension = header & 0x04 != 0;
+    let has_size_field = header & 0x02 != 0;
+    let mut offset = 1 + usize::from(has_extension);
+    if !has_size_field {
+        let payload = bytes.get(offset..)?;
+        return Some((obu_type, payload, &[]));
+    }
+
+    let mut size = 0_u64;
+    for index in 0..8 {
+        let byte = *bytes.get(offset)?;
+        offset += 1;
+        size |= u64::from(byte & 0x7f) << (index * 7);
+        if byte & 0x80 == 0 {
+            let end = offset.checked_add(usize::try_from(size).ok()?)?;
+            return Some((obu_type, bytes.get(offset..end)?, bytes.get(end..)?));
+        }
+    }
+    None
+}
+
+fn parse_sequence_header(payload: &[u8]) -> Option<(u32, u32)> {
+    let mut bits = BitReader::new(payload);
+    bits.read(3)?; // seq_profile
+    bits.read(1)?; // still_picture
+    let reduced_still_picture_header = bits.flag()?;
+    if reduced_still_picture_header {
+        bits.read(5)?; // seq_level_idx[0]
+    } else {
+        if bits.flag()? {
+            // timing_info
+            bits.read(32)?; // num_units_in_display_tick
+            bits.read(32)?; // time_scale
+            if bits.flag()? {
+                bits.read_exp_golomb()?; // num_ticks_per_picture_minus_1
+            }
+        }
+        let decoder_model_info_present = bits.flag()?;
+        let mut buffer_delay_length = 0;
+        if decoder_model_info_present {
+            buffer_delay_length = bits.read(5)? as usize + 1;
+            bits.read(32)?; // num_units_in_decoding_tick
+            bits.read(5)?; // buffer_removal_time_length_minus_1
+            bits.read(5)?; // frame_presentation_time_length_minus_1
+        }
+        let initial_display_delay_present = bits.flag()?;
+        let operating_points = bits.read(5)? + 1;
+        for _ in 0..operating_points {
+            bits.read(12)?; // operating_point_idc
+            if bits.read(5)? > 7 {
+                bits.read(1)?; // seq_tier
+            }
+            if decoder_model_info_present && bits.flag()? {
+                bits.read(buffer_delay_length)?; // decoder_buffer_delay
+                bits.read(buffer_delay_length)?; // encoder_buffer_delay
+                bits.read(1)?; // low_delay_mode_flag
+            }
+            if initial_display_delay_present && bits.flag()? {
+                bits.read(4)?; // initial_display_delay_minus_1
+            }
+        }
+    }
+
+    let width_bits = bits.read(4)? as usize + 1;
+    let height_bits = bits.read(4)? as usize + 1;
+    let width = bits.read(width_bits)?.checked_add(1)?;
+    let height = bits.read(height_bits)?.checked_add(1)?;
+    Some((width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::bits::BitWriter;
+    use super::parse_key_frame_dimensions;
+
+    #[test]
+    fn parses_sequence_header_after_temporal_delimiter() {
+        let mut payload = vec![0x12, 0x00];
+        payload.extend(sized_obu(1, &sequence_header(1280, 720)));
+        payload.extend(sized_obu(6, &[0xaa, 0xbb]));
+        assert_eq!(parse_key_frame_dimensions(&payload), Some((1280, 720)));
+    }
+
+    #[test]
+    fn parses_sequence_header_without_size_field() {
+        let mut payload = vec![0x08];
+        payload.extend(sequence_header(1920, 1080));
+        assert_eq!(parse_key_frame_dimensions(&payload), Some((1920, 1080)));
+    }
+
+    #[test]
+    fn rejects_frame_without_sequence_header() {
+        let payload = sized_obu(6, &[0x10, 0x20, 0x30]);
+        assert_eq!(parse_key_frame_dimensions(&payload), None);
+    }
+
+    #[test]
+    fn rejects_truncated_obu() {
+        let mut payload = sized_obu(1, &sequence_header(1280, 720));
+        payload.truncate(payload.len() - 1);
+        assert_eq!(parse_key_frame_dimensions(&payload), None);
+    }
+
+    fn sized_obu(obu_type: u8, payload: &[u8]) -> Vec<u8> {
+        let mut bytes = vec![obu_type << 3 | 0x02, payload.len() as u8];
+        bytes.extend_from_slice(payload);
+        bytes
+    }
+
+    fn sequence_header(width: u32, height: u32) -> Vec<u8> {
+        let mut writer = BitWriter::default();
+        writer.write(0, 3); // seq_profile
+        writer.write(0, 1); // still_picture
+        writer.write(0, 1); // reduced_still_picture_header
+        writer.write(0, 1); // timing_info_present_flag
+        writer.write(0, 1); // decoder_model_info_present_flag
+        writer.write(0, 1); // initial_display_delay_present_flag
+        writer.write(0, 5); // operating_points_cnt_minus_1
+        writer.write(0, 12); // operating_point_idc
+        writer.write(8, 5); // seq_level_idx 4.0
+        writer.write(0, 1); // seq_tier
+        writer.write(15, 4); // frame_width_bits_minus_1
+        writer.write(15, 4); // frame_height_bits_minus_1
+        writer.write(width - 1, 16);
+        writer.write(height - 1, 16);
+        writer.write(0, 7); // остаток заголовка не нужен relay
+        writer.bytes
+    }
+}
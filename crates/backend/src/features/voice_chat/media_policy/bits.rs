@@ -0,0 +1,92 @@
+//! Побитовое чтение заголовков видеокодеков.
+
+/// Читает биты MSB-first, как их записывают VP9, AV1 и H.264.
+pub(super) struct BitReader<'a> {
+    bytes: &'a [u8],
+    bit_offset: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub(super) fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            bit_offset: 0,
+        }
+    }
+
+    /// Читает до 32 бит как беззнаковое число.
+    pub(super) fn read(&mut self, count: usize) -> Option<u32> {
+        let mut value = 0_u32;
+        for _ in 0..count {
+            let byte = *self.bytes.get(self.bit_offset / 8)?;
+            let shift = 7 - self.bit_offset % 8;
+            value = (value << 1) | u32::from((byte >> shift) & 1);
+            self.bit_offset += 1;
+        }
+        Some(value)
+    }
+
+    /// Читает флаг из одного бита.
+    pub(super) fn flag(&mut self) -> Option<bool> {
+        self.read(1).map(|bit| bit == 1)
+    }
+
+    /// Читает число в exp-Golomb коде `ue(v)` из H.264 и `uvlc()` из AV1.
+    pub(super) fn read_exp_golomb(&mut self) -> Option<u32> {
+        let mut leading_zeros = 0;
+        while !self.flag()? {
+            leading_zeros += 1;
+            if leading_zeros > 31 {
+                return None;
+            }
+        }
+        let suffix = self.read(leading_zeros)?;
+        ((1_u64 << leading_zeros) - 1 + u64::from(suffix))
+            .try_into()
+            .ok()
+    }
+
+    /// Читает знаковое число в exp-Golomb коде `se(v)` из H.264.
+    pub(super) fn read_signed_exp_golomb(&mut self) -> Option<i32> {
+        let code = i64::from(self.read_exp_golomb()?);
+        let value = if code % 2 == 1 {
+            (code + 1) / 2
+        } else {
+            -(code / 2)
+        };
+        value.try_into().ok()
+    }
+}
+
+/// Пишет биты MSB-first для тестовых заголовков кодеков.
+#[cfg(test)]
+#[derive(Default)]
+pub(super) struct BitWriter {
+    pub(super) bytes: Vec<u8>,
+    bit_offset: usize,
+}
+
+#[cfg(test)]
+impl BitWriter {
+    pub(super) fn write(&mut self, value: u32, count: usize) {
+        for bit_index in (0..count).rev() {
+            if self.bit_offset.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            let bit = ((value >> bit_index) & 1) as u8;
+            let byte_index = self.bit_offset / 8;
+            let shift = 7 - self.bit_offset % 8;
+            self.bytes[byte_index] |= bit << shift;
+            self.bit_offset += 1;
+        }
+    }
+
+    pub(super) fn write_exp_golomb(&mut self, value: u32) {
+        let code = u64::from(value) + 1;
+        let bits = 64 - code.leading_zeros() as usize;
+        self.write(0, bits - 1);
+        for bit_index in (0..bits).rev() {
+            self.write(((code >> bit_index) & 1) as u32, 1);
+        }
+    }
+}
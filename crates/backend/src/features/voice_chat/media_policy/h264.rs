@@ -0,0 +1,227 @@
+//! Чтение размеров кадра из H.264 sequence parameter set.
+
+use super::bits::BitReader;
+
+const NAL_UNIT_TYPE_SPS: u8 = 7;
+const HIGH_PROFILES: [u32; 12] = [100, 110, 122, 244, 44, 83, 86, 118, 128, 138, 139, 134];
+
+/// Возвращает размеры кадра из SPS, который кодировщик кладёт перед IDR в Annex B потоке.
+pub(super) fn parse_key_frame_dimensions(payload: &[u8]) -> Option<(u32, u32)> {
+    let sps = nal_units(payload).find(|nal| nal.first().is_some_and(is_sps))?;
+    parse_sps(&without_emulation_prevention(&sps[1..]))
+}
+
+fn is_sps(header: &u8) -> bool {
+    header & 0x80 == 0 && header & 0x1f == NAL_UNIT_TYPE_SPS
+}
+
+/// Перечисляет NAL units между start code `00 00 01` и `00 00 00 01`.
+fn nal_units(payload: &[u8]) -> impl Iterator<Item = &[u8]> {
+    let mut starts = Vec::new();
+    let mut index = 0;
+    while index + 3 <= payload.len() {
+        if payload[index..index + 3] == [0, 0, 1] {
+            starts.push(index + 3);
+            index += 3;
+        } else {
+            index += 1;
+        }
+    }
+    let ends = starts
+        .iter()
+        .skip(1)
+        .map(|next| {
+            let end = next - 3;
+            if end > 0 && payload[end - 1] == 0 {
+                end - 1
+            } else {
+                end
+            }
+        })
+        .chain(std::iter::once(payload.len()))
+        .collect::<Vec<_>>();
+    starts
+        .into_iter()
+        .zip(ends)
+        .filter_map(move |(start, end)| payload.get(start..end))
+}
+
+/// Убирает `emulation_prevention_three_byte` из RBSP.
+fn without_emulation_prevention(bytes: &[u8]) -> Vec<u8> {
+    let mut rbsp = Vec::with_capacity(bytes.len());
+    let mut zeros = 0;
+    for &byte in bytes {
+        if zeros >= 2 && byte == 3 {
+            zeros = 0;
+            continue;
+        }
+        zeros = if byte == 0 { zeros + 1 } else { 0 };
+        rbsp.push(byte);
+    }
+    rbsp
+}
+
+fn parse_sps(rbsp: &[u8]) -> Option<(u32, u32)> {
+    let mut bits = BitReader::new(rbsp);
+    let profile_idc = bits.read(8)?;
+    bits.read(8)?; // constraint_set flags и reserved_zero_2bits
+    bits.read(8)?; // level_idc
+    bits.read_exp_golomb()?; // seq_parameter_set_id
+
+    let mut chroma_format_idc = 1;
+    let mut separate_colour_plane = false;
+    if HIGH_PROFILES.contains(&profile_idc) || profile_idc == 135 {
+        chroma_format_idc = bits.read_exp_golomb()?;
+        if chroma_format_idc == 3 {
+            separate_colour_plane = bits.flag()?;
+        }
+        bits.read_exp_golomb()?; // bit_depth_luma_minus8
+        bits.read_exp_golomb()?; // bit_depth_chroma_minus8
+        bits.read(1)?; // qpprime_y_zero_transform_bypass_flag
+        if bits.flag()? {
+            let lists = if chroma_format_idc == 3 { 12 } else { 8 };
+            for list in 0..lists {
+                if bits.flag()? {
+                    skip_scaling_list(&mut bits, if list < 6 { 16 } else { 64 })?;
+                }
+            }
+        }
+    }
+
+    bits.read_exp_golomb()?; // log2_max_frame_num_minus4
+    match bits.read_exp_golomb()? {
+        0 => {
+            bits.read_exp_golomb()?; // log2_max_pic_order_cnt_lsb_minus4
+        }
+        1 => {
+            bits.read(1)?; // delta_pic_order_always_zero_flag
+            bits.read_signed_exp_golomb()?; // offset_for_non_ref_pic
+            bits.read_signed_exp_golomb()?; // offset_for_top_to_bottom_field
+            for _ in 0..bits.read_exp_golomb()? {
+                bits.read_signed_exp_golomb()?; // offset_for_ref_frame
+            }
+        }
+        2 => {}
+        _ => return None,
+    }
+    bits.read_exp_golomb()?; // max_num_ref_frames
+    bits.read(1)?; // gaps_in_frame_num_value_allowed_flag
+    let width_in_mbs = bits.read_exp_golomb()?.checked_add(1)?;
+    let height_in_map_units = bits.read_exp_golomb()?.checked_add(1)?;
+    let frame_mbs_only = bits.flag()?;
+    if !frame_mbs_only {
+        bits.read(1)?; // mb_adaptive_frame_field_flag
+    }
+    bits.read(1)?; // direct_8x8_inference_flag
+    let (crop_left, crop_right, crop_top, crop_bottom) = if bits.flag()? {
+        (
+            bits.read_exp_golomb()?,
+            bits.read_exp_golomb()?,
+            bits.read_exp_golomb()?,
+            bits.read_exp_golomb()?,
+        )
+    } else {
+        (0, 0, 0, 0)
+    };
+
+    let field_factor = if frame_mbs_only { 1 } else { 2 };
+    let chroma_array_type = if separate_colour_plane {
+        0
+    } else {
+        chroma_format_idc
+    };
+    let (crop_unit_x, crop_unit_y) = match chroma_array_type {
+        0 => (1, field_factor),
+        1 => (2, 2 * field_factor),
+        2 => (2, field_factor),
+        3 => (1, field_factor),
+        _ => return None,
+    };
+    let width = width_in_mbs.checked_mul(16)?.checked_sub(
+        crop_left
+            .checked_add(crop_right)?
+            .checked_mul(crop_unit_x)?,
+    )?;
+    let height = height_in_map_units
+        .checked_mul(16 * field_factor)?
+        .checked_sub(
+            crop_top
+                .checked_add(crop_bottom)?
+                .checked_mul(crop_unit_y)?,
+        )?;
+    (width > 0 && height > 0).then_some((width, height))
+}
+
+fn skip_scaling_list(bits: &mut BitReader<'_>, size: usize) -> Option<()> {
+    let mut last_scale = 8_i32;
+    let mut next_scale = 8_i32;
+    for _ in 0..size {
+        if next_scale != 0 {
+            let delta_scale = bits.read_signed_exp_golomb()?;
+            next_scale = (last_scale + delta_scale).rem_euclid(256);
+        }
+        if next_scale != 0 {
+            last_scale = next_scale;
+        }
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::bits::BitWriter;
+    use super::parse_key_frame_dimensions;
+
+    #[test]
+    fn parses_baseline_sps_with_1080p_cropping() {
+        let mut payload = vec![0, 0, 0, 1, 0x09, 0xf0];
+        payload.extend([0, 0, 0, 1]);
+        payload.extend(baseline_sps(1920, 1080));
+        payload.extend([0, 0, 1, 0x65, 0x88, 0x84]);
+        assert_eq!(parse_key_frame_dimensions(&payload), Some((1920, 1080)));
+    }
+
+    #[test]
+    fn parses_known_high_profile_sps_bytes() {
+        // SPS x264 High 3.1 для 1280x720 с `00 00 03` внутри RBSP.
+        let payload = [
+            0, 0, 0, 1, 0x67, 0x64, 0x00, 0x1f, 0xac, 0xd9, 0x40, 0x50, 0x05, 0xbb, 0x01, 0x10,
+            0x00, 0x00, 0x03, 0x00, 0x10, 0x00, 0x00, 0x03, 0x03, 0xc0, 0xf1, 0x83, 0x19, 0x60,
+        ];
+        assert_eq!(parse_key_frame_dimensions(&payload), Some((1280, 720)));
+    }
+
+    #[test]
+    fn rejects_access_unit_without_sps() {
+        let payload = [0, 0, 0, 1, 0x65, 0x88, 0x84, 0x00, 0x33];
+        assert_eq!(parse_key_frame_dimensions(&payload), None);
+    }
+
+    fn baseline_sps(width: u32, height: u32) -> Vec<u8> {
+        let width_in_mbs = width.div_ceil(16);
+        let height_in_mbs = height.div_ceil(16);
+        let mut writer = BitWriter::default();
+        writer.write(0x67, 8); // nal header: nal_ref_idc 3, SPS
+        writer.write(66, 8); // profile_idc: Baseline
+        writer.write(0xc0, 8); // constraint_set0/1
+        writer.write(40, 8); // level_idc 4.0
+        writer.write_exp_golomb(0); // seq_parameter_set_id
+        writer.write_exp_golomb(0); // log2_max_frame_num_minus4
+        writer.write_exp_golomb(2); // pic_order_cnt_type
+        writer.write_exp_golomb(1); // max_num_ref_frames
+        writer.write(0, 1); // gaps_in_frame_num_value_allowed_flag
+        writer.write_exp_golomb(width_in_mbs - 1);
+        writer.write_exp_golomb(height_in_mbs - 1);
+        writer.write(1, 1); // frame_mbs_only_flag
+        writer.write(1, 1); // direct_8x8_inference_flag
+        let crop_bottom = (height_in_mbs * 16 - height) / 2;
+        writer.write(1, 1); // frame_cropping_flag
+        writer.write_exp_golomb(0);
+        writer.write_exp_golomb(0);
+        writer.write_exp_golomb(0);
+        writer.write_exp_golomb(crop_bottom);
+        writer.write(0, 1); // vui_parameters_present_flag
+        writer.write(1, 1); // rbsp_stop_one_bit
+        writer.bytes
+    }
+}
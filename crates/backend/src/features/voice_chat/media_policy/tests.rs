@@ -0,0 +1,316 @@
+use super::bits::BitWriter;
+use super::*;
+use crate::features::voice_chat::test_builders::camera_datagram;
+use cheenhub_contracts::{
+    media::{MediaCodec, MediaDatagram},
+    video_presets::{BASE_CAMERA_VIDEO_PRESETS, BASE_SCREEN_SHARE_VIDEO_PRESETS},
+};
+
+#[test]
+fn screen_policy_accepts_both_base_resolutions() {
+    for (sequence, width, height) in [(1, 1280, 720), (2, 1920, 1080)] {
+        let mut tracker = VideoPublicationTracker::default();
+        let mut datagram = video_datagram(sequence, true, width, height);
+        datagram.kind = MediaDatagramKind::ScreenFrame;
+        assert_eq!(
+            tracker.inspect_at(
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                &datagram,
+                &policy(BASE_SCREEN_SHARE_VIDEO_PRESETS),
+                Instant::now(),
+            ),
+            VideoAdmission::Forward
+        );
+    }
+}
+
+#[test]
+fn camera_policy_rejects_1080p() {
+    let mut tracker = VideoPublicationTracker::default();
+    let datagram = video_datagram(1, true, 1920, 1080);
+    assert_eq!(
+        tracker.inspect_at(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &datagram,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            Instant::now(),
+        ),
+        VideoAdmission::Drop(VideoDropReason::UnsupportedResolution {
+            width: 1920,
+            height: 1080,
+        })
+    );
+}
+
+#[test]
+fn h264_key_frame_is_admitted_by_sps_dimensions() {
+    let mut tracker = VideoPublicationTracker::default();
+    let mut datagram = video_datagram(1, true, 1280, 720);
+    datagram.codec = MediaCodec::H264;
+    datagram.payload = vec![
+        0, 0, 0, 1, 0x67, 0x64, 0x00, 0x1f, 0xac, 0xd9, 0x40, 0x50, 0x05, 0xbb, 0x01, 0x10, 0x00,
+        0x00, 0x03, 0x00, 0x10, 0x00, 0x00, 0x03, 0x03, 0xc0, 0xf1, 0x83, 0x19, 0x60, 0, 0, 1,
+        0x65, 0x88,
+    ];
+    assert_eq!(
+        tracker.inspect_at(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &datagram,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            Instant::now(),
+        ),
+        VideoAdmission::Forward
+    );
+}
+
+#[test]
+fn key_frame_is_parsed_with_datagram_codec() {
+    let mut tracker = VideoPublicationTracker::default();
+    let mut datagram = video_datagram(1, true, 1280, 720);
+    datagram.codec = MediaCodec::Av1;
+    assert_eq!(
+        tracker.inspect_at(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            &datagram,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            Instant::now(),
+        ),
+        VideoAdmission::Drop(VideoDropReason::InvalidKeyFrame)
+    );
+}
+
+#[test]
+fn fragmented_frame_is_counted_once() {
+    let mut tracker = VideoPublicationTracker::default();
+    let session_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let now = Instant::now();
+    let first = fragmented(video_datagram(1, true, 1280, 720), 0, 2);
+    let mut second = fragmented(video_datagram(1, true, 1280, 720), 1, 2);
+    second.room_id = first.room_id;
+    assert_eq!(
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &first,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            now
+        ),
+        VideoAdmission::Forward
+    );
+    assert_eq!(
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &second,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            now
+        ),
+        VideoAdmission::Forward
+    );
+    assert_eq!(tracker.publications[0].window_frames, 1);
+}
+
+#[test]
+fn sustained_fps_violation_blocks_until_later_key_frame() {
+    let mut tracker = VideoPublicationTracker::default();
+    let session_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    let started = Instant::now();
+    let key = video_datagram(1, true, 1280, 720);
+    let room_id = key.room_id;
+    assert_eq!(
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &key,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            started
+        ),
+        VideoAdmission::Forward
+    );
+    for sequence in 2..=26 {
+        let mut frame = video_datagram(sequence, false, 0, 0);
+        frame.room_id = room_id;
+        assert_eq!(
+            tracker.inspect_at(
+                session_id,
+                user_id,
+                &frame,
+                &policy(BASE_CAMERA_VIDEO_PRESETS),
+                started + Duration::from_millis(sequence * 30),
+            ),
+            VideoAdmission::Forward
+        );
+    }
+    let mut violating = video_datagram(27, false, 0, 0);
+    violating.room_id = room_id;
+    assert!(matches!(
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &violating,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            started + Duration::from_millis(1_010),
+        ),
+        VideoAdmission::Drop(VideoDropReason::FpsLimitExceeded { .. })
+    ));
+    let mut early_key = video_datagram(28, true, 1280, 720);
+    early_key.room_id = room_id;
+    assert_eq!(
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &early_key,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            started + Duration::from_millis(1_500),
+        ),
+        VideoAdmission::Drop(VideoDropReason::FpsBlockActive)
+    );
+    let mut later_key = video_datagram(29, true, 1280, 720);
+    later_key.room_id = room_id;
+    assert_eq!(
+        tracker.inspect_at(
+            session_id,
+            user_id,
+            &later_key,
+            &policy(BASE_CAMERA_VIDEO_PRESETS),
+            started + Duration::from_millis(2_100),
+        ),
+        VideoAdmission::Forward
+    );
+}
+
+#[test]
+fn publisher_limit_blocks_new_publishers_until_slot_frees() {
+    let mut tracker = VideoPublicationTracker::default();
+    let room_id = Uuid::new_v4();
+    let limited = RoomVideoPolicy {
+        max_publishers: Some(1),
+        ..policy(BASE_CAMERA_VIDEO_PRESETS)
+    };
+    let started = Instant::now();
+    let mut first = video_datagram(1, true, 1280, 720);
+    first.room_id = room_id;
+    let mut second = video_datagram(1, true, 1280, 720);
+    second.room_id = room_id;
+    let (first_user, second_user) = (Uuid::new_v4(), Uuid::new_v4());
+
+    assert_eq!(
+        tracker.inspect_at(Uuid::new_v4(), first_user, &first, &limited, started),
+        VideoAdmission::Forward
+    );
+    assert_eq!(
+        tracker.inspect_at(Uuid::new_v4(), second_user, &second, &limited, started),
+        VideoAdmission::Drop(VideoDropReason::PublisherLimitReached { max_publishers: 1 })
+    );
+    second.sequence = 2;
+    assert_eq!(
+        tracker.inspect_at(
+            Uuid::new_v4(),
+            second_user,
+            &second,
+            &limited,
+            started + PUBLICATION_IDLE_TIMEOUT,
+        ),
+        VideoAdmission::Forward
+    );
+}
+
+#[test]
+fn simulcast_layers_are_admitted_as_one_publisher() {
+    let mut tracker = VideoPublicationTracker::default();
+    let room_id = Uuid::new_v4();
+    let limited = RoomVideoPolicy {
+        max_publishers: Some(1),
+        ..policy(BASE_CAMERA_VIDEO_PRESETS)
+    };
+    let (session_id, user_id) = (Uuid::new_v4(), Uuid::new_v4());
+    let now = Instant::now();
+    let mut high = video_datagram(1, true, 1280, 720);
+    high.room_id = room_id;
+    let mut low = video_datagram(1, true, 640, 360);
+    low.room_id = room_id;
+    low.spatial_layer = VideoSpatialLayer::Low;
+    let mut oversized_low = video_datagram(2, true, 1280, 720);
+    oversized_low.room_id = room_id;
+    oversized_low.spatial_layer = VideoSpatialLayer::Low;
+
+    assert_eq!(
+        tracker.inspect_at(session_id, user_id, &high, &limited, now),
+        VideoAdmission::Forward
+    );
+    assert_eq!(
+        tracker.inspect_at(session_id, user_id, &low, &limited, now),
+        VideoAdmission::Forward
+    );
+    assert_eq!(
+        tracker.active_layers(room_id, user_id, MediaDatagramKind::CameraFrame, now),
+        vec![VideoSpatialLayer::Low, VideoSpatialLayer::High]
+    );
+    assert_eq!(
+        tracker.inspect_at(session_id, user_id, &oversized_low, &limited, now),
+        VideoAdmission::Drop(VideoDropReason::UnsupportedResolution {
+            width: 1280,
+            height: 720,
+        })
+    );
+    assert_eq!(
+        tracker.active_layers(room_id, user_id, MediaDatagramKind::CameraFrame, now),
+        vec![VideoSpatialLayer::High]
+    );
+}
+
+fn policy(allowed_presets: &[VideoPresetId]) -> RoomVideoPolicy {
+    RoomVideoPolicy {
+        allowed_presets: allowed_presets.to_vec(),
+        max_publishers: None,
+    }
+}
+
+fn video_datagram(sequence: u64, key_frame: bool, width: u32, height: u32) -> MediaDatagram {
+    let payload = if key_frame {
+        vp9_key_frame(width, height)
+    } else {
+        Vec::new()
+    };
+    MediaDatagram {
+        timestamp_us: 0,
+        duration_us: 0,
+        ..camera_datagram(Uuid::new_v4(), Uuid::nil(), sequence, key_frame, payload)
+    }
+}
+
+fn fragmented(mut datagram: MediaDatagram, index: u16, count: u16) -> MediaDatagram {
+    let bytes = std::mem::take(&mut datagram.payload);
+    datagram.flags |= MEDIA_DATAGRAM_FLAG_FRAGMENTED;
+    datagram.payload = Vec::new();
+    datagram
+        .payload
+        .extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+    datagram.payload.extend_from_slice(&index.to_be_bytes());
+    datagram.payload.extend_from_slice(&count.to_be_bytes());
+    datagram.payload.extend_from_slice(&bytes);
+    datagram
+}
+
+fn vp9_key_frame(width: u32, height: u32) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    writer.write(0b10, 2);
+    writer.write(0, 1);
+    writer.write(0, 1);
+    writer.write(0, 1);
+    writer.write(0, 1);
+    writer.write(1, 1);
+    writer.write(0, 1);
+    writer.write(0x49_83_42, 24);
+    writer.write(1, 3);
+    writer.write(0, 1);
+    writer.write(width - 1, 16);
+    writer.write(height - 1, 16);
+    writer.bytes
+}
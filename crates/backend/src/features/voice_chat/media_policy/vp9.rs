@@ -1,5 +1,7 @@
 //! Чтение размеров кадра из VP9 uncompressed header.
 
+use super::bits::BitReader;
+
 /// Возвращает закодированные размеры VP9 key frame.
 pub(super) fn parse_key_frame_dimensions(payload: &[u8]) -> Option<(u32, u32)> {
     let mut bits = BitReader::new(payload);
@@ -35,33 +37,9 @@ pub(super) fn parse_key_frame_dimensions(payload: &[u8]) -> Option<(u32, u32)> {
     Some((width, height))
 }
 
-struct BitReader<'a> {
-    bytes: &'a [u8],
-    bit_offset: usize,
-}
-
-impl<'a> BitReader<'a> {
-    fn new(bytes: &'a [u8]) -> Self {
-        Self {
-            bytes,
-            bit_offset: 0,
-        }
-    }
-
-    fn read(&mut self, count: usize) -> Option<u32> {
-        let mut value = 0_u32;
-        for _ in 0..count {
-            let byte = *self.bytes.get(self.bit_offset / 8)?;
-            let shift = 7 - self.bit_offset % 8;
-            value = (value << 1) | u32::from((byte >> shift) & 1);
-            self.bit_offset += 1;
-        }
-        Some(value)
-    }
-}
-
 #[cfg(test)]
 mod tests {
+    use super::super::bits::BitWriter;
     use super::parse_key_frame_dimensions;
 
     #[test]
@@ -101,25 +79,4 @@ mod tests {
         writer.write(height - 1, 16);
         writer.bytes
     }
-
-    #[derive(Default)]
-    struct BitWriter {
-        bytes: Vec<u8>,
-        bit_offset: usize,
-    }
-
-    impl BitWriter {
-        fn write(&mut self, value: u32, count: usize) {
-            for bit_index in (0..count).rev() {
-                if self.bit_offset.is_multiple_of(8) {
-                    self.bytes.push(0);
-                }
-                let bit = ((value >> bit_index) & 1) as u8;
-                let byte_index = self.bit_offset / 8;
-                let shift = 7 - self.bit_offset % 8;
-                self.bytes[byte_index] |= bit << shift;
-                self.bit_offset += 1;
-            }
-        }
-    }
 }
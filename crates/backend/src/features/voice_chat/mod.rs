@@ -1,7 +1,34 @@
 //! Voice chat presence feature.
 
+mod active_speakers;
 pub(crate) mod application;
+mod audio_policy;
+mod connection_quality;
 pub(crate) mod infrastructure;
+mod key_frames;
 pub(crate) mod media;
 mod media_policy;
 pub(crate) mod realtime;
+mod recording;
+#[cfg(test)]
+pub(crate) mod test_builders;
+mod transport;
+mod video_codecs;
+mod video_subscriptions;
+
+use axum::{Router, routing::get};
+
+use crate::state::AppState;
+
+/// Собирает REST-маршруты записей голосовых комнат, вложенные в маршруты серверов.
+pub(crate) fn server_routes() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/{server_id}/voice-recordings",
+            get(transport::list_recordings),
+        )
+        .route(
+            "/{server_id}/voice-recordings/{recording_id}/files/{file_name}",
+            get(transport::recording_file),
+        )
+}
@@ -1,11 +1,13 @@
 //! Voice chat realtime adapter.
 
 use cheenhub_contracts::realtime::{
-    BindMicrophoneUplink, CancelDirectCall, EndDirectCall, IssueMicrophoneUplinkGrant,
-    JoinDirectMessageVoiceRoom, JoinVoiceRoom, KickVoiceMember, LeaveDirectMessageVoiceRoom,
-    LeaveVoiceRoom, ListDirectCalls, ListDirectMessageVoiceRooms, ListServerVoiceRooms,
-    RealtimeEnvelope, RealtimeKind, RealtimeModule, RejectionCode, RespondDirectCall,
-    StartDirectCall, StopVoiceVideoStream, VoiceChatKind,
+    AdvertiseVoiceCodecCapabilities, BindMicrophoneUplink, CancelDirectCall, EndDirectCall,
+    IssueMicrophoneUplinkGrant, JoinDirectMessageVoiceRoom, JoinVoiceRoom,
+    LeaveDirectMessageVoiceRoom, LeaveVoiceRoom, ListDirectCalls, ListDirectMessageVoiceRooms,
+    ListServerVoiceRooms, RealtimeEnvelope, RealtimeKind, RealtimeModule, ReceiverReport,
+    RejectionCode, RequestVoiceKeyFrame, RespondDirectCall, SetVoiceRoomAudioMixing,
+    StartDirectCall, StartVoiceRoomRecording, StopVoiceRoomRecording, StopVoiceVideoStream,
+    SubscribeVoiceVideo, UnsubscribeVoiceVideo, VoiceChatKind,
 };
 use cheenhub_contracts::rest::AuthUser;
 use uuid::Uuid;
@@ -17,6 +19,8 @@ use crate::realtime::protocol::{
 };
 use crate::state::AppState;
 
+mod moderation;
+
 /// Handles one voice chat module envelope.
 pub(crate) async fn handle(
     state: &AppState,
@@ -206,10 +210,49 @@ pub(crate) async fn handle(
                 Err(error) => reject_application_error(send, Some(request_id), error).await,
             }
         }
-        RealtimeKind::VoiceChat(VoiceChatKind::KickVoiceMember) => {
+        RealtimeKind::VoiceChat(
+            VoiceChatKind::KickVoiceMember
+            | VoiceChatKind::ServerMuteVoiceMember
+            | VoiceChatKind::ServerDeafenVoiceMember,
+        ) => moderation::handle(state, user_id, send, envelope).await,
+        RealtimeKind::VoiceChat(VoiceChatKind::StartRecording) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: StartVoiceRoomRecording = decode_payload(&envelope)?;
+            match application::start_recording(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::VoiceChat,
+                        RealtimeKind::VoiceChat(VoiceChatKind::VoiceRoomSnapshot),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::VoiceChat(VoiceChatKind::StopRecording) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: StopVoiceRoomRecording = decode_payload(&envelope)?;
+            match application::stop_recording(state, user_id, payload).await {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::VoiceChat,
+                        RealtimeKind::VoiceChat(VoiceChatKind::VoiceRoomSnapshot),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
+        RealtimeKind::VoiceChat(VoiceChatKind::SetAudioMixing) => {
             let request_id = require_request_id(&envelope)?;
-            let payload: KickVoiceMember = decode_payload(&envelope)?;
-            match application::kick_member(state, user_id, payload).await {
+            let payload: SetVoiceRoomAudioMixing = decode_payload(&envelope)?;
+            match application::set_audio_mixing(state, user_id, payload).await {
                 Ok(response) => {
                     write_envelope(
                         send,
@@ -272,6 +315,76 @@ pub(crate) async fn handle(
                 Err(error) => reject_application_error(send, envelope.request_id, error).await,
             }
         }
+        RealtimeKind::VoiceChat(VoiceChatKind::RequestKeyFrame) => {
+            let payload: RequestVoiceKeyFrame = decode_payload(&envelope)?;
+            match application::request_key_frame(
+                state,
+                realtime_stream_id,
+                session_id,
+                user_id,
+                payload,
+            )
+            .await
+            {
+                Ok(()) => Ok(()),
+                Err(error) => reject_application_error(send, envelope.request_id, error).await,
+            }
+        }
+        RealtimeKind::VoiceChat(VoiceChatKind::SubscribeVideo) => {
+            let payload: SubscribeVoiceVideo = decode_payload(&envelope)?;
+            match application::subscribe_video(
+                state,
+                realtime_stream_id,
+                session_id,
+                user_id,
+                payload,
+            )
+            .await
+            {
+                Ok(()) => Ok(()),
+                Err(error) => reject_application_error(send, envelope.request_id, error).await,
+            }
+        }
+        RealtimeKind::VoiceChat(VoiceChatKind::UnsubscribeVideo) => {
+            let payload: UnsubscribeVoiceVideo = decode_payload(&envelope)?;
+            match application::unsubscribe_video(
+                state,
+                realtime_stream_id,
+                session_id,
+                user_id,
+                payload,
+            )
+            .await
+            {
+                Ok(()) => Ok(()),
+                Err(error) => reject_application_error(send, envelope.request_id, error).await,
+            }
+        }
+        RealtimeKind::VoiceChat(VoiceChatKind::AdvertiseCodecCapabilities) => {
+            let request_id = require_request_id(&envelope)?;
+            let payload: AdvertiseVoiceCodecCapabilities = decode_payload(&envelope)?;
+            match application::advertise_codec_capabilities(
+                state,
+                realtime_stream_id,
+                session_id,
+                user_id,
+                payload,
+            )
+            .await
+            {
+                Ok(response) => {
+                    write_envelope(
+                        send,
+                        RealtimeModule::VoiceChat,
+                        RealtimeKind::VoiceChat(VoiceChatKind::VideoCodecSelected),
+                        Some(request_id),
+                        response,
+                    )
+                    .await
+                }
+                Err(error) => reject_application_error(send, Some(request_id), error).await,
+            }
+        }
         RealtimeKind::VoiceChat(VoiceChatKind::IssueMicrophoneUplinkGrant) => {
             let request_id = require_request_id(&envelope)?;
             let payload: IssueMicrophoneUplinkGrant = decode_payload(&envelope)?;
@@ -329,6 +442,21 @@ pub(crate) async fn handle(
     }
 }
 
+/// Handles one receiver report that arrived on the network module stream.
+pub(crate) async fn handle_receiver_report(
+    state: &AppState,
+    user_id: &Uuid,
+    session_id: Uuid,
+    send: &EnvelopeSink,
+    envelope: RealtimeEnvelope,
+) -> anyhow::Result<()> {
+    let payload: ReceiverReport = decode_payload(&envelope)?;
+    match application::record_receiver_report(state, session_id, user_id, payload).await {
+        Ok(()) => Ok(()),
+        Err(error) => reject_application_error(send, envelope.request_id, error).await,
+    }
+}
+
 async fn reject_application_error(
     send: &EnvelopeSink,
     request_id: Option<Uuid>,
@@ -0,0 +1,59 @@
+//! Realtime-запросы модерации участников голосовой комнаты.
+
+use cheenhub_contracts::realtime::{
+    KickVoiceMember, RealtimeEnvelope, RealtimeKind, RealtimeModule, ServerDeafenVoiceMember,
+    ServerMuteVoiceMember, VoiceChatKind, VoiceRoomSnapshot,
+};
+use uuid::Uuid;
+
+use crate::features::voice_chat::application::{self, VoiceChatApplicationError};
+use crate::realtime::EnvelopeSink;
+use crate::realtime::protocol::{decode_payload, require_request_id, write_envelope};
+use crate::state::AppState;
+
+use super::reject_application_error;
+
+/// Handles kick, server mute and server deafen requests for voice room members.
+pub(super) async fn handle(
+    state: &AppState,
+    user_id: &Uuid,
+    send: &EnvelopeSink,
+    envelope: RealtimeEnvelope,
+) -> anyhow::Result<()> {
+    let request_id = require_request_id(&envelope)?;
+    let result = match envelope.kind {
+        RealtimeKind::VoiceChat(VoiceChatKind::ServerMuteVoiceMember) => {
+            let payload: ServerMuteVoiceMember = decode_payload(&envelope)?;
+            application::server_mute_member(state, user_id, payload).await
+        }
+        RealtimeKind::VoiceChat(VoiceChatKind::ServerDeafenVoiceMember) => {
+            let payload: ServerDeafenVoiceMember = decode_payload(&envelope)?;
+            application::server_deafen_member(state, user_id, payload).await
+        }
+        _ => {
+            let payload: KickVoiceMember = decode_payload(&envelope)?;
+            application::kick_member(state, user_id, payload).await
+        }
+    };
+    respond_with_snapshot(send, request_id, result).await
+}
+
+async fn respond_with_snapshot(
+    send: &EnvelopeSink,
+    request_id: Uuid,
+    result: Result<VoiceRoomSnapshot, VoiceChatApplicationError>,
+) -> anyhow::Result<()> {
+    match result {
+        Ok(response) => {
+            write_envelope(
+                send,
+                RealtimeModule::VoiceChat,
+                RealtimeKind::VoiceChat(VoiceChatKind::VoiceRoomSnapshot),
+                Some(request_id),
+                response,
+            )
+            .await
+        }
+        Err(error) => reject_application_error(send, Some(request_id), error).await,
+    }
+}
@@ -0,0 +1,483 @@
+//! Запись медиа голосовых комнат сервера в файлы Ogg Opus и WebM VP9.
+
+use std::collections::VecDeque;
+use std::time::Instant;
+
+use cheenhub_contracts::media::{MediaDatagram, MediaDatagramKind};
+use cheenhub_contracts::realtime::VoiceVideoStreamSource;
+use cheenhub_contracts::rest::VoiceRecordingTrackKind;
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use chrono::{DateTime, Utc};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
+
+mod ogg;
+mod tracks;
+mod webm;
+
+use tracks::{VideoTrack, VoiceTrack};
+
+/// Предел размера одной записи; после него новые кадры не записываются.
+const MAX_RECORDING_BYTES: usize = 512 * 1024 * 1024;
+/// Сколько комнат может записываться одновременно на всем сервере.
+const MAX_CONCURRENT_RECORDINGS: usize = 4;
+/// Предел байтов, ждущих задачу сохранения; если хранилище отстает сильнее,
+/// запись перестает захватывать медиа, чтобы не копить его в памяти.
+const MAX_BACKLOG_BYTES: usize = 16 * 1024 * 1024;
+/// Емкость очереди между записью и задачей сохранения.
+pub(crate) const RECORDING_WRITE_QUEUE: usize = 64;
+
+/// Идущая запись одной голосовой комнаты сервера.
+///
+/// Закрытые страницы Ogg и кластеры WebM сразу уходят задаче сохранения, поэтому
+/// в памяти остаются только незакрытые страницы и кластеры.
+pub(crate) struct RoomRecording {
+    /// Стабильный идентификатор записи.
+    pub(crate) id: Uuid,
+    /// Сервер записываемой комнаты.
+    pub(crate) server_id: Uuid,
+    /// Записываемая комната.
+    pub(crate) room_id: Uuid,
+    /// Пользователь, запустивший запись.
+    pub(crate) started_by_user_id: Uuid,
+    /// Время начала записи.
+    pub(crate) started_at: DateTime<Utc>,
+    started: Instant,
+    capture_stopped: bool,
+    voice_tracks: Vec<VoiceTrack>,
+    video_tracks: Vec<(VoiceVideoStreamSource, VideoTrack)>,
+    sink: mpsc::Sender<RecordingWrite>,
+    backlog: VecDeque<RecordingWrite>,
+    backlog_len: usize,
+}
+
+/// Сообщение задаче, сохраняющей файлы записи в хранилище.
+pub(crate) enum RecordingWrite {
+    /// Очередные байты файла дорожки; части одного файла идут по порядку.
+    Chunk {
+        /// Файл, которому принадлежат байты.
+        file: RecordedFile,
+        /// Байты контейнера.
+        bytes: Vec<u8>,
+    },
+    /// Запись остановлена, все части уже отправлены.
+    Finished {
+        /// Время остановки записи.
+        finished_at: DateTime<Utc>,
+    },
+}
+
+/// Один файл записи.
+#[derive(Debug, Clone)]
+pub(crate) struct RecordedFile {
+    /// Участник, чье медиа содержит файл.
+    pub(crate) user_id: Uuid,
+    /// Снимок ника участника.
+    pub(crate) nickname: String,
+    /// Вид дорожки.
+    pub(crate) kind: VoiceRecordingTrackKind,
+    /// Имя файла внутри записи.
+    pub(crate) file_name: String,
+    /// MIME-тип файла.
+    pub(crate) content_type: &'static str,
+}
+
+/// Причина, по которой запись комнаты не запустилась.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RecordingStartError {
+    /// Комнату уже записывают.
+    AlreadyRecording,
+    /// Достигнут общий предел одновременных записей.
+    TooManyRecordings,
+}
+
+impl RoomRecording {
+    fn new(
+        server_id: Uuid,
+        room_id: Uuid,
+        started_by_user_id: Uuid,
+        sink: mpsc::Sender<RecordingWrite>,
+        now: Instant,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            server_id,
+            room_id,
+            started_by_user_id,
+            started_at: Utc::now(),
+            started: now,
+            capture_stopped: false,
+            voice_tracks: Vec::new(),
+            video_tracks: Vec::new(),
+            sink,
+            backlog: VecDeque::new(),
+            backlog_len: 0,
+        }
+    }
+
+    /// Добавляет ретранслируемую датаграмму участника комнаты.
+    ///
+    /// Возвращает источник видео, для которого записи нужен ключевой кадр.
+    fn record(
+        &mut self,
+        presence: &VoicePresence,
+        datagram: &MediaDatagram,
+        now: Instant,
+    ) -> Option<VoiceVideoStreamSource> {
+        if self.capture_stopped {
+            return None;
+        }
+        if self.byte_len() >= MAX_RECORDING_BYTES {
+            self.stop_capturing(
+                "voice room recording reached its size limit and stopped capturing media",
+            );
+            return None;
+        }
+        let arrival_offset_us = now
+            .saturating_duration_since(self.started)
+            .as_micros()
+            .min(u128::from(u64::MAX)) as u64;
+
+        let key_frame_source = self.record_track(presence, datagram, arrival_offset_us);
+        self.flush_ready();
+        key_frame_source
+    }
+
+    fn record_track(
+        &mut self,
+        presence: &VoicePresence,
+        datagram: &MediaDatagram,
+        arrival_offset_us: u64,
+    ) -> Option<VoiceVideoStreamSource> {
+        let source = match datagram.kind {
+            MediaDatagramKind::VoiceFrame => {
+                self.record_voice(presence, datagram, arrival_offset_us);
+                return None;
+            }
+            MediaDatagramKind::CameraFrame => VoiceVideoStreamSource::Camera,
+            MediaDatagramKind::ScreenFrame => VoiceVideoStreamSource::ScreenShare,
+        };
+        // Simulcast-публикации пишутся верхним слоем, который отправитель шлет всегда.
+        if datagram.spatial_layer != VideoSpatialLayer::High {
+            return None;
+        }
+        let track = match self.video_tracks.iter_mut().position(|(candidate, track)| {
+            *candidate == source && track.user_id == presence.user_id
+        }) {
+            Some(position) => &mut self.video_tracks[position].1,
+            None => {
+                self.video_tracks.push((
+                    source,
+                    VideoTrack::new(presence.user_id, presence.nickname.clone()),
+                ));
+                &mut self.video_tracks.last_mut().expect("track was inserted").1
+            }
+        };
+        track.push(datagram, arrival_offset_us).then_some(source)
+    }
+
+    fn record_voice(&mut self, presence: &VoicePresence, datagram: &MediaDatagram, offset_us: u64) {
+        match self
+            .voice_tracks
+            .iter_mut()
+            .find(|track| track.user_id == presence.user_id)
+        {
+            Some(track) => track.push(datagram, offset_us),
+            None => {
+                let serial = self.voice_tracks.len() as u32 + 1;
+                let mut track = VoiceTrack::new(
+                    presence.user_id,
+                    presence.nickname.clone(),
+                    serial,
+                    datagram,
+                    offset_us,
+                );
+                track.push(datagram, offset_us);
+                self.voice_tracks.push(track);
+            }
+        }
+    }
+
+    fn byte_len(&self) -> usize {
+        self.voice_tracks
+            .iter()
+            .map(VoiceTrack::byte_len)
+            .chain(self.video_tracks.iter().map(|(_, track)| track.byte_len()))
+            .sum()
+    }
+
+    /// Передает закрытые страницы и кластеры задаче сохранения, не дожидаясь ее.
+    fn flush_ready(&mut self) {
+        for track in &mut self.voice_tracks {
+            let bytes = track.take_ready();
+            push_chunk(
+                &mut self.backlog,
+                &mut self.backlog_len,
+                voice_file(track),
+                bytes,
+            );
+        }
+        for (source, track) in &mut self.video_tracks {
+            let bytes = track.take_ready();
+            push_chunk(
+                &mut self.backlog,
+                &mut self.backlog_len,
+                video_file(*source, track),
+                bytes,
+            );
+        }
+
+        while let Some(write) = self.backlog.pop_front() {
+            let len = write_len(&write);
+            match self.sink.try_send(write) {
+                Ok(()) => self.backlog_len -= len,
+                Err(mpsc::error::TrySendError::Full(write)) => {
+                    self.backlog.push_front(write);
+                    break;
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => {
+                    self.backlog.clear();
+                    self.backlog_len = 0;
+                    self.stop_capturing(
+                        "voice room recording writer stopped; media is no longer captured",
+                    );
+                    return;
+                }
+            }
+        }
+        if self.backlog_len > MAX_BACKLOG_BYTES {
+            self.stop_capturing(
+                "voice room recording storage fell behind and stopped capturing media",
+            );
+        }
+    }
+
+    fn stop_capturing(&mut self, reason: &'static str) {
+        self.capture_stopped = true;
+        tracing::warn!(
+            recording_id = %self.id,
+            server_id = %self.server_id,
+            room_id = %self.room_id,
+            "{reason}"
+        );
+    }
+
+    /// Закрывает контейнеры всех дорожек и дожидается, пока задача сохранения
+    /// примет их хвосты и сообщение о завершении.
+    pub(crate) async fn finish(self) {
+        let Self {
+            id,
+            sink,
+            mut backlog,
+            mut backlog_len,
+            voice_tracks,
+            video_tracks,
+            ..
+        } = self;
+        for track in voice_tracks {
+            let file = voice_file(&track);
+            push_chunk(&mut backlog, &mut backlog_len, file, track.finish());
+        }
+        for (source, track) in video_tracks {
+            let file = video_file(source, &track);
+            if let Some(bytes) = track.finish() {
+                push_chunk(&mut backlog, &mut backlog_len, file, bytes);
+            }
+        }
+        backlog.push_back(RecordingWrite::Finished {
+            finished_at: Utc::now(),
+        });
+
+        for write in backlog {
+            if sink.send(write).await.is_err() {
+                tracing::warn!(
+                    recording_id = %id,
+                    "voice room recording writer stopped before recording was finished"
+                );
+                return;
+            }
+        }
+    }
+}
+
+fn push_chunk(
+    backlog: &mut VecDeque<RecordingWrite>,
+    backlog_len: &mut usize,
+    file: RecordedFile,
+    bytes: Vec<u8>,
+) {
+    if bytes.is_empty() {
+        return;
+    }
+    *backlog_len += bytes.len();
+    backlog.push_back(RecordingWrite::Chunk { file, bytes });
+}
+
+fn write_len(write: &RecordingWrite) -> usize {
+    match write {
+        RecordingWrite::Chunk { bytes, .. } => bytes.len(),
+        RecordingWrite::Finished { .. } => 0,
+    }
+}
+
+fn voice_file(track: &VoiceTrack) -> RecordedFile {
+    RecordedFile {
+        user_id: track.user_id,
+        nickname: track.nickname.clone(),
+        kind: VoiceRecordingTrackKind::Voice,
+        file_name: format!("{}-voice.ogg", track.user_id),
+        content_type: "audio/ogg",
+    }
+}
+
+fn video_file(source: VoiceVideoStreamSource, track: &VideoTrack) -> RecordedFile {
+    let (kind, suffix) = match source {
+        VoiceVideoStreamSource::Camera => (VoiceRecordingTrackKind::Camera, "camera"),
+        VoiceVideoStreamSource::ScreenShare => (VoiceRecordingTrackKind::ScreenShare, "screen"),
+    };
+    RecordedFile {
+        user_id: track.user_id,
+        nickname: track.nickname.clone(),
+        kind,
+        file_name: format!("{}-{suffix}.webm", track.user_id),
+        content_type: "video/webm",
+    }
+}
+
+/// Идущие записи голосовых комнат.
+#[derive(Default)]
+pub(super) struct RecordingTracker {
+    recordings: Vec<RoomRecording>,
+}
+
+impl RecordingTracker {
+    fn start(
+        &mut self,
+        server_id: Uuid,
+        room_id: Uuid,
+        started_by_user_id: Uuid,
+        sink: mpsc::Sender<RecordingWrite>,
+        now: Instant,
+    ) -> Result<&RoomRecording, RecordingStartError> {
+        if self.find(server_id, room_id).is_some() {
+            return Err(RecordingStartError::AlreadyRecording);
+        }
+        if self.recordings.len() >= MAX_CONCURRENT_RECORDINGS {
+            return Err(RecordingStartError::TooManyRecordings);
+        }
+        self.recordings.push(RoomRecording::new(
+            server_id,
+            room_id,
+            started_by_user_id,
+            sink,
+            now,
+        ));
+        Ok(self.recordings.last().expect("recording was inserted"))
+    }
+
+    fn find(&self, server_id: Uuid, room_id: Uuid) -> Option<&RoomRecording> {
+        self.recordings
+            .iter()
+            .find(|recording| recording.server_id == server_id && recording.room_id == room_id)
+    }
+
+    fn take(&mut self, server_id: Uuid, room_id: Uuid) -> Option<RoomRecording> {
+        let position = self.recordings.iter().position(|recording| {
+            recording.server_id == server_id && recording.room_id == room_id
+        })?;
+        Some(self.recordings.remove(position))
+    }
+
+    fn record(
+        &mut self,
+        presence: &VoicePresence,
+        datagram: &MediaDatagram,
+        now: Instant,
+    ) -> Option<VoiceVideoStreamSource> {
+        self.recordings
+            .iter_mut()
+            .find(|recording| {
+                recording.server_id == presence.server_id && recording.room_id == presence.room_id
+            })?
+            .record(presence, datagram, now)
+    }
+}
+
+/// Сведения об идущей записи для снимка комнаты.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RoomRecordingInfo {
+    /// Стабильный идентификатор записи.
+    pub(crate) id: Uuid,
+    /// Пользователь, запустивший запись.
+    pub(crate) started_by_user_id: Uuid,
+    /// Время начала записи.
+    pub(crate) started_at: DateTime<Utc>,
+}
+
+impl From<&RoomRecording> for RoomRecordingInfo {
+    fn from(recording: &RoomRecording) -> Self {
+        Self {
+            id: recording.id,
+            started_by_user_id: recording.started_by_user_id,
+            started_at: recording.started_at,
+        }
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Запускает запись комнаты, чьи файлы уходят в `sink` по мере записи.
+    pub(crate) async fn start_room_recording(
+        &self,
+        server_id: Uuid,
+        room_id: Uuid,
+        started_by_user_id: Uuid,
+        sink: mpsc::Sender<RecordingWrite>,
+    ) -> Result<RoomRecordingInfo, RecordingStartError> {
+        self.recordings
+            .lock()
+            .await
+            .start(server_id, room_id, started_by_user_id, sink, Instant::now())
+            .map(RoomRecordingInfo::from)
+    }
+
+    /// Возвращает идущую запись комнаты.
+    pub(crate) async fn room_recording(
+        &self,
+        server_id: Uuid,
+        room_id: Uuid,
+    ) -> Option<RoomRecordingInfo> {
+        self.recordings
+            .lock()
+            .await
+            .find(server_id, room_id)
+            .map(RoomRecordingInfo::from)
+    }
+
+    /// Останавливает запись комнаты и отдает ее дорожки для завершения.
+    pub(crate) async fn take_room_recording(
+        &self,
+        server_id: Uuid,
+        room_id: Uuid,
+    ) -> Option<RoomRecording> {
+        self.recordings.lock().await.take(server_id, room_id)
+    }
+
+    /// Передает ретранслируемую датаграмму записи комнаты отправителя, если она идет.
+    ///
+    /// Возвращает источник видео, для которого записи нужен ключевой кадр.
+    pub(crate) async fn record_room_media(
+        &self,
+        presence: &VoicePresence,
+        datagram: &MediaDatagram,
+    ) -> Option<VoiceVideoStreamSource> {
+        self.recordings
+            .lock()
+            .await
+            .record(presence, datagram, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests;
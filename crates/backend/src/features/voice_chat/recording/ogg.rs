@@ -0,0 +1,164 @@
+//! Упаковка пакетов Opus в контейнер Ogg (RFC 7845).
+
+/// Частота granule position Ogg Opus независимо от исходной частоты.
+pub(super) const OPUS_GRANULE_RATE: u64 = 48_000;
+
+const MAX_PAGE_SEGMENTS: usize = 255;
+/// Страница закрывается, когда накопила примерно секунду звука.
+const PAGE_TARGET_SAMPLES: u64 = OPUS_GRANULE_RATE;
+const HEADER_TYPE_BEGIN_OF_STREAM: u8 = 0x02;
+const HEADER_TYPE_END_OF_STREAM: u8 = 0x04;
+const VENDOR: &[u8] = b"cheenhub";
+
+/// Пишет один логический поток Ogg Opus постранично.
+///
+/// Закрытые страницы копятся в буфере, пока их не заберет [`Self::take_pages`].
+pub(super) struct OggOpusWriter {
+    serial: u32,
+    bytes: Vec<u8>,
+    taken_len: usize,
+    page_sequence: u32,
+    written_samples: u64,
+    flushed_granule: u64,
+    pending_segments: Vec<u8>,
+    pending_data: Vec<u8>,
+}
+
+impl OggOpusWriter {
+    /// Создает поток и сразу пишет страницы заголовков `OpusHead` и `OpusTags`.
+    pub(super) fn new(serial: u32, channels: u8) -> Self {
+        let mut writer = Self {
+            serial,
+            bytes: Vec::new(),
+            taken_len: 0,
+            page_sequence: 0,
+            written_samples: 0,
+            flushed_granule: 0,
+            pending_segments: Vec::new(),
+            pending_data: Vec::new(),
+        };
+
+        let mut head = Vec::with_capacity(19);
+        head.extend_from_slice(b"OpusHead");
+        head.push(1);
+        head.push(channels.max(1));
+        head.extend_from_slice(&0_u16.to_le_bytes());
+        head.extend_from_slice(&48_000_u32.to_le_bytes());
+        head.extend_from_slice(&0_i16.to_le_bytes());
+        head.push(0);
+        writer.write_page(HEADER_TYPE_BEGIN_OF_STREAM, 0, &lacing(head.len()), &head);
+
+        let mut tags = Vec::with_capacity(16 + VENDOR.len());
+        tags.extend_from_slice(b"OpusTags");
+        tags.extend_from_slice(&(VENDOR.len() as u32).to_le_bytes());
+        tags.extend_from_slice(VENDOR);
+        tags.extend_from_slice(&0_u32.to_le_bytes());
+        writer.write_page(0, 0, &lacing(tags.len()), &tags);
+
+        writer
+    }
+
+    /// Количество сэмплов 48 кГц, уже добавленных в поток.
+    pub(super) fn written_samples(&self) -> u64 {
+        self.written_samples
+    }
+
+    /// Текущий размер потока вместе с еще не закрытой страницей.
+    pub(super) fn byte_len(&self) -> usize {
+        self.taken_len + self.bytes.len() + self.pending_data.len() + self.pending_segments.len()
+    }
+
+    /// Отдает закрытые с прошлого вызова страницы.
+    pub(super) fn take_pages(&mut self) -> Vec<u8> {
+        self.taken_len += self.bytes.len();
+        std::mem::take(&mut self.bytes)
+    }
+
+    /// Добавляет один пакет Opus длительностью `samples` сэмплов 48 кГц.
+    pub(super) fn push_packet(&mut self, packet: &[u8], samples: u64) {
+        let segments = lacing(packet.len());
+        if self.pending_segments.len() + segments.len() > MAX_PAGE_SEGMENTS {
+            self.flush_page(0);
+        }
+        self.pending_segments.extend_from_slice(&segments);
+        self.pending_data.extend_from_slice(packet);
+        self.written_samples += samples;
+        if self.pending_samples() >= PAGE_TARGET_SAMPLES {
+            self.flush_page(0);
+        }
+    }
+
+    /// Закрывает поток страницей end-of-stream и возвращает еще не отданные страницы.
+    pub(super) fn finish(mut self) -> Vec<u8> {
+        self.flush_page(HEADER_TYPE_END_OF_STREAM);
+        self.bytes
+    }
+
+    fn pending_samples(&self) -> u64 {
+        self.written_samples - self.flushed_granule
+    }
+
+    fn flush_page(&mut self, header_type: u8) {
+        if self.pending_segments.is_empty() && header_type == 0 {
+            return;
+        }
+        let segments = std::mem::take(&mut self.pending_segments);
+        let data = std::mem::take(&mut self.pending_data);
+        self.write_page(header_type, self.written_samples, &segments, &data);
+        self.flushed_granule = self.written_samples;
+    }
+
+    fn write_page(&mut self, header_type: u8, granule: u64, segments: &[u8], data: &[u8]) {
+        let start = self.bytes.len();
+        self.bytes.extend_from_slice(b"OggS");
+        self.bytes.push(0);
+        self.bytes.push(header_type);
+        self.bytes.extend_from_slice(&granule.to_le_bytes());
+        self.bytes.extend_from_slice(&self.serial.to_le_bytes());
+        self.bytes
+            .extend_from_slice(&self.page_sequence.to_le_bytes());
+        self.bytes.extend_from_slice(&0_u32.to_le_bytes());
+        self.bytes.push(segments.len() as u8);
+        self.bytes.extend_from_slice(segments);
+        self.bytes.extend_from_slice(data);
+        let checksum = crc32(&self.bytes[start..]);
+        self.bytes[start + 22..start + 26].copy_from_slice(&checksum.to_le_bytes());
+        self.page_sequence += 1;
+    }
+}
+
+/// Таблица lacing для пакета: значения 255 и завершающий остаток, который может быть нулем.
+fn lacing(len: usize) -> Vec<u8> {
+    let mut segments = vec![255_u8; len / 255];
+    segments.push((len % 255) as u8);
+    segments
+}
+
+/// CRC-32 страниц Ogg: полином 0x04C11DB7 без отражения битов и финального XOR.
+pub(super) fn crc32(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0_u32, |crc, byte| {
+        (crc << 8) ^ CRC_TABLE[(((crc >> 24) as u8) ^ byte) as usize]
+    })
+}
+
+const CRC_TABLE: [u32; 256] = crc_table();
+
+const fn crc_table() -> [u32; 256] {
+    let mut table = [0_u32; 256];
+    let mut index = 0;
+    while index < 256 {
+        let mut value = (index as u32) << 24;
+        let mut bit = 0;
+        while bit < 8 {
+            value = if value & 0x8000_0000 != 0 {
+                (value << 1) ^ 0x04C1_1DB7
+            } else {
+                value << 1
+            };
+            bit += 1;
+        }
+        table[index] = value;
+        index += 1;
+    }
+    table
+}
@@ -0,0 +1,368 @@
+use std::time::{Duration, Instant};
+
+use cheenhub_contracts::media::{MEDIA_DATAGRAM_FLAG_FRAGMENTED, MediaDatagram};
+use cheenhub_contracts::realtime::VoiceVideoStreamSource;
+use cheenhub_contracts::rest::VoiceRecordingTrackKind;
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use super::ogg::crc32;
+use super::{
+    MAX_CONCURRENT_RECORDINGS, RecordedFile, RecordingStartError, RecordingTracker, RecordingWrite,
+};
+use crate::features::voice_chat::infrastructure::VoicePresence;
+use crate::features::voice_chat::test_builders;
+
+#[tokio::test]
+async fn voice_track_is_aligned_to_recording_start_in_valid_ogg_pages() {
+    let started = Instant::now();
+    let alice = presence("alice");
+    let mut tracker = RecordingTracker::default();
+    let receiver = start(&mut tracker, &alice, started);
+
+    // Алиса заговорила через секунду после начала записи, затем замолчала на 200 мс.
+    for index in 0..10_u64 {
+        let timestamp_us = 5_000_000 + index * 20_000;
+        let arrived = started + Duration::from_millis(1_000 + index * 20);
+        tracker.record(
+            &alice,
+            &voice_datagram(&alice, index, timestamp_us),
+            arrived,
+        );
+    }
+    let resumed_us = 5_000_000 + 400_000;
+    tracker.record(
+        &alice,
+        &voice_datagram(&alice, 10, resumed_us),
+        started + Duration::from_millis(1_400),
+    );
+
+    let files = finish(&mut tracker, &alice, receiver).await;
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].0.kind, VoiceRecordingTrackKind::Voice);
+    assert_eq!(files[0].0.content_type, "audio/ogg");
+
+    let pages = ogg_pages(&files[0].1);
+    assert!(pages.len() >= 3);
+    assert_eq!(&pages[0].body[..8], b"OpusHead");
+    assert_eq!(pages[0].header_type, 0x02);
+    assert_eq!(&pages[1].body[..8], b"OpusTags");
+    let last = pages.last().expect("stream has pages");
+    assert_eq!(last.header_type, 0x04);
+    // 1 с тишины, 10 пакетов, 200 мс паузы и еще один пакет по 20 мс.
+    assert_eq!(last.granule, 48_000 + 10 * 960 + 10 * 960 + 960);
+}
+
+#[tokio::test]
+async fn late_voice_packets_do_not_rewind_the_track() {
+    let started = Instant::now();
+    let alice = presence("alice");
+    let mut tracker = RecordingTracker::default();
+    let receiver = start(&mut tracker, &alice, started);
+
+    tracker.record(&alice, &voice_datagram(&alice, 1, 20_000), started);
+    tracker.record(&alice, &voice_datagram(&alice, 2, 40_000), started);
+    tracker.record(&alice, &voice_datagram(&alice, 0, 0), started);
+
+    let files = finish(&mut tracker, &alice, receiver).await;
+    let last = ogg_pages(&files[0].1).pop().expect("stream has pages");
+    assert_eq!(last.granule, 2 * 960);
+}
+
+#[tokio::test]
+async fn fragmented_camera_frames_are_muxed_into_webm_after_key_frame() {
+    let started = Instant::now();
+    let bob = presence("bob");
+    let mut tracker = RecordingTracker::default();
+    let receiver = start(&mut tracker, &bob, started);
+
+    let delta = camera_datagram(&bob, 0, 0, false, vec![0x86; 40]);
+    assert_eq!(
+        tracker.record(&bob, &delta, started),
+        Some(VoiceVideoStreamSource::Camera)
+    );
+
+    let mut key_frame = vp9_key_frame(1280, 720);
+    key_frame.extend(std::iter::repeat_n(0x11, 1_500));
+    for fragment in fragments(camera_datagram(&bob, 1, 33_000, true, key_frame), 3) {
+        assert_eq!(tracker.record(&bob, &fragment, started), None);
+    }
+    let next = camera_datagram(&bob, 2, 66_000, false, vec![0x86; 40]);
+    assert_eq!(tracker.record(&bob, &next, started), None);
+    let low_layer = MediaDatagram {
+        spatial_layer: VideoSpatialLayer::Low,
+        ..camera_datagram(&bob, 3, 99_000, false, vec![0x86; 20])
+    };
+    assert_eq!(tracker.record(&bob, &low_layer, started), None);
+
+    let files = finish(&mut tracker, &bob, receiver).await;
+    assert_eq!(files.len(), 1);
+    let (file, bytes) = &files[0];
+    assert_eq!(file.kind, VoiceRecordingTrackKind::Camera);
+    assert_eq!(file.file_name, format!("{}-camera.webm", bob.user_id));
+    assert_eq!(&bytes[..4], &[0x1A, 0x45, 0xDF, 0xA3]);
+    assert!(contains(bytes, b"webm"));
+    assert!(contains(bytes, b"V_VP9"));
+    // Сегмент живого потока: размер неизвестен, поэтому кластеры дописываются без перемотки.
+    assert!(contains(
+        bytes,
+        &[
+            0x18, 0x53, 0x80, 0x67, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF
+        ]
+    ));
+    // Ключевой кадр открывает кластер, дельта-кадр идет через 33 мс, слой Low не пишется.
+    assert_eq!(count(bytes, &[0xA3, 0x45, 0xE9, 0x81, 0, 0, 0x80]), 1);
+    assert_eq!(count(bytes, &[0xA3, 0xAC, 0x81, 0, 33, 0]), 1);
+    assert!(!contains(bytes, &[0xA3, 0x98, 0x81]));
+}
+
+#[test]
+fn lost_video_fragment_waits_for_next_key_frame() {
+    let started = Instant::now();
+    let bob = presence("bob");
+    let mut tracker = RecordingTracker::default();
+    let _receiver = start(&mut tracker, &bob, started);
+
+    let key_frame = camera_datagram(&bob, 0, 0, true, vp9_key_frame(1280, 720));
+    assert_eq!(tracker.record(&bob, &key_frame, started), None);
+    let incomplete = camera_datagram(&bob, 1, 33_000, false, vec![0x86; 2_000]);
+    let first_fragment = fragments(incomplete, 2).remove(0);
+    tracker.record(&bob, &first_fragment, started);
+    let mut needs_key_frame = None;
+    for sequence in 2..8 {
+        let delta = camera_datagram(&bob, sequence, sequence * 33_000, false, vec![0x86; 2_000]);
+        for fragment in fragments(delta, 2).into_iter().take(1) {
+            needs_key_frame = tracker.record(&bob, &fragment, started);
+        }
+    }
+
+    assert_eq!(needs_key_frame, Some(VoiceVideoStreamSource::Camera));
+}
+
+#[tokio::test]
+async fn recording_ignores_other_rooms_and_refuses_second_start() {
+    let started = Instant::now();
+    let alice = presence("alice");
+    let mut tracker = RecordingTracker::default();
+    let receiver = start(&mut tracker, &alice, started);
+    let (sink, _) = mpsc::channel(1);
+    assert!(matches!(
+        tracker.start(alice.server_id, alice.room_id, alice.user_id, sink, started),
+        Err(RecordingStartError::AlreadyRecording)
+    ));
+
+    let elsewhere = VoicePresence {
+        room_id: Uuid::new_v4(),
+        ..presence("carol")
+    };
+    tracker.record(&elsewhere, &voice_datagram(&elsewhere, 0, 0), started);
+
+    let files = finish(&mut tracker, &alice, receiver).await;
+    assert!(files.is_empty());
+    assert!(tracker.find(alice.server_id, alice.room_id).is_none());
+}
+
+#[test]
+fn concurrent_recordings_are_limited_across_rooms() {
+    let started = Instant::now();
+    let mut tracker = RecordingTracker::default();
+    let mut receivers = Vec::new();
+    for _ in 0..MAX_CONCURRENT_RECORDINGS {
+        let room = VoicePresence {
+            room_id: Uuid::new_v4(),
+            ..presence("alice")
+        };
+        receivers.push(start(&mut tracker, &room, started));
+    }
+
+    let (sink, _) = mpsc::channel(1);
+    assert!(matches!(
+        tracker.start(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            sink,
+            started
+        ),
+        Err(RecordingStartError::TooManyRecordings)
+    ));
+}
+
+#[test]
+fn closed_ogg_pages_leave_memory_before_recording_stops() {
+    let started = Instant::now();
+    let alice = presence("alice");
+    let mut tracker = RecordingTracker::default();
+    let mut receiver = start(&mut tracker, &alice, started);
+
+    // Чуть больше секунды звука закрывает первую страницу с пакетами.
+    for index in 0..55_u64 {
+        tracker.record(
+            &alice,
+            &voice_datagram(&alice, index, index * 20_000),
+            started + Duration::from_millis(index * 20),
+        );
+    }
+
+    let mut streamed = Vec::new();
+    while let Ok(write) = receiver.try_recv() {
+        match write {
+            RecordingWrite::Chunk { bytes, .. } => streamed.extend_from_slice(&bytes),
+            RecordingWrite::Finished { .. } => panic!("recording is still running"),
+        }
+    }
+    let pages = ogg_pages(&streamed);
+    assert_eq!(pages.len(), 3);
+    assert_eq!(pages[2].granule, 50 * 960);
+}
+
+fn start(
+    tracker: &mut RecordingTracker,
+    presence: &VoicePresence,
+    now: Instant,
+) -> mpsc::Receiver<RecordingWrite> {
+    let (sink, receiver) = mpsc::channel(1_024);
+    tracker
+        .start(
+            presence.server_id,
+            presence.room_id,
+            presence.user_id,
+            sink,
+            now,
+        )
+        .expect("recording starts");
+    receiver
+}
+
+/// Stops the recording and joins the streamed chunks into files.
+async fn finish(
+    tracker: &mut RecordingTracker,
+    presence: &VoicePresence,
+    mut receiver: mpsc::Receiver<RecordingWrite>,
+) -> Vec<(RecordedFile, Vec<u8>)> {
+    tracker
+        .take(presence.server_id, presence.room_id)
+        .expect("recording is active")
+        .finish()
+        .await;
+    let mut files: Vec<(RecordedFile, Vec<u8>)> = Vec::new();
+    loop {
+        match receiver.recv().await.expect("recording sends finish") {
+            RecordingWrite::Chunk { file, bytes } => {
+                match files
+                    .iter_mut()
+                    .find(|(existing, _)| existing.file_name == file.file_name)
+                {
+                    Some((_, existing)) => existing.extend_from_slice(&bytes),
+                    None => files.push((file, bytes)),
+                }
+            }
+            RecordingWrite::Finished { .. } => return files,
+        }
+    }
+}
+
+struct OggPage {
+    header_type: u8,
+    granule: u64,
+    body: Vec<u8>,
+}
+
+fn ogg_pages(mut bytes: &[u8]) -> Vec<OggPage> {
+    let mut pages = Vec::new();
+    while !bytes.is_empty() {
+        assert_eq!(&bytes[..4], b"OggS");
+        let segments = usize::from(bytes[26]);
+        let body_len: usize = bytes[27..27 + segments]
+            .iter()
+            .map(|value| usize::from(*value))
+            .sum();
+        let page_len = 27 + segments + body_len;
+        let mut page = bytes[..page_len].to_vec();
+        let checksum = u32::from_le_bytes(page[22..26].try_into().expect("crc bytes"));
+        page[22..26].fill(0);
+        assert_eq!(crc32(&page), checksum, "page checksum matches");
+        pages.push(OggPage {
+            header_type: bytes[5],
+            granule: u64::from_le_bytes(bytes[6..14].try_into().expect("granule bytes")),
+            body: bytes[27 + segments..page_len].to_vec(),
+        });
+        bytes = &bytes[page_len..];
+    }
+    pages
+}
+
+fn presence(nickname: &str) -> VoicePresence {
+    VoicePresence {
+        nickname: nickname.to_owned(),
+        ..test_builders::voice_presence(Uuid::from_u128(1), Uuid::from_u128(2), Uuid::new_v4())
+    }
+}
+
+fn voice_datagram(presence: &VoicePresence, sequence: u64, timestamp_us: u64) -> MediaDatagram {
+    let payload = vec![0x78, 0x01, 0x02, 0x03];
+    MediaDatagram {
+        timestamp_us,
+        ..test_builders::voice_datagram(presence.room_id, presence.user_id, sequence, payload)
+    }
+}
+
+fn camera_datagram(
+    presence: &VoicePresence,
+    sequence: u64,
+    timestamp_us: u64,
+    key_frame: bool,
+    payload: Vec<u8>,
+) -> MediaDatagram {
+    MediaDatagram {
+        timestamp_us,
+        ..test_builders::camera_datagram(
+            presence.room_id,
+            presence.user_id,
+            sequence,
+            key_frame,
+            payload,
+        )
+    }
+}
+
+fn fragments(datagram: MediaDatagram, count: u16) -> Vec<MediaDatagram> {
+    let total_len = datagram.payload.len() as u32;
+    let chunk_len = datagram.payload.len().div_ceil(usize::from(count));
+    datagram
+        .payload
+        .chunks(chunk_len)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let mut payload = total_len.to_be_bytes().to_vec();
+            payload.extend_from_slice(&(index as u16).to_be_bytes());
+            payload.extend_from_slice(&count.to_be_bytes());
+            payload.extend_from_slice(chunk);
+            MediaDatagram {
+                flags: datagram.flags | MEDIA_DATAGRAM_FLAG_FRAGMENTED,
+                payload,
+                ..datagram.clone()
+            }
+        })
+        .collect()
+}
+
+/// Заголовок ключевого кадра VP9 profile 0 с цветовым пространством BT.601.
+fn vp9_key_frame(width: u32, height: u32) -> Vec<u8> {
+    let size = (u64::from(width - 1) << 16) | u64::from(height - 1);
+    let mut frame = vec![0x82, 0x49, 0x83, 0x42];
+    frame.extend_from_slice(&(size << 4).to_be_bytes()[3..]);
+    frame
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    count(haystack, needle) > 0
+}
+
+fn count(haystack: &[u8], needle: &[u8]) -> usize {
+    haystack
+        .windows(needle.len())
+        .filter(|window| *window == needle)
+        .count()
+}
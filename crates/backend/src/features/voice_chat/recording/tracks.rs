@@ -0,0 +1,262 @@
+//! Дорожки участников внутри записи голосовой комнаты.
+
+use cheenhub_contracts::media::{
+    MEDIA_DATAGRAM_FLAG_FRAGMENTED, MEDIA_DATAGRAM_FLAG_KEY_FRAME, MediaCodec, MediaDatagram,
+};
+use uuid::Uuid;
+
+use super::ogg::{OPUS_GRANULE_RATE, OggOpusWriter};
+use super::webm::WebmVp9Writer;
+use crate::features::voice_chat::media_policy::key_frame_dimensions;
+
+/// Пустой кадр Opus 20 мс (CELT fullband): декодер восполняет его тишиной.
+const OPUS_SILENCE_PACKET: [u8; 1] = [0xF8];
+const OPUS_SILENCE_SAMPLES: u64 = OPUS_GRANULE_RATE / 50;
+const OPUS_TOC_STEREO: u8 = 0b0000_0100;
+/// При большем расхождении меток отправителя с временем прихода дорожка
+/// переякоряется: отправитель перезапустил кодер или вернулся в комнату.
+const MAX_CLOCK_DRIFT_US: u64 = 1_000_000;
+const VIDEO_FRAGMENT_HEADER_LEN: usize = 8;
+const MAX_PENDING_VIDEO_FRAMES: usize = 4;
+const MAX_VIDEO_FRAME_BYTES: usize = 2 * 1024 * 1024;
+
+/// Переводит метки времени отправителя в позицию от начала записи.
+struct TrackClock {
+    anchor_timestamp_us: u64,
+    anchor_offset_us: u64,
+}
+
+impl TrackClock {
+    fn new(timestamp_us: u64, arrival_offset_us: u64) -> Self {
+        Self {
+            anchor_timestamp_us: timestamp_us,
+            anchor_offset_us: arrival_offset_us,
+        }
+    }
+
+    fn position_us(&mut self, timestamp_us: u64, arrival_offset_us: u64) -> u64 {
+        let position = i128::from(self.anchor_offset_us) + i128::from(timestamp_us)
+            - i128::from(self.anchor_timestamp_us);
+        if position.abs_diff(i128::from(arrival_offset_us)) <= u128::from(MAX_CLOCK_DRIFT_US) {
+            return position.max(0) as u64;
+        }
+        *self = Self::new(timestamp_us, arrival_offset_us);
+        arrival_offset_us
+    }
+}
+
+/// Голос одного участника в Ogg Opus.
+pub(super) struct VoiceTrack {
+    pub(super) user_id: Uuid,
+    pub(super) nickname: String,
+    clock: TrackClock,
+    writer: OggOpusWriter,
+}
+
+impl VoiceTrack {
+    pub(super) fn new(
+        user_id: Uuid,
+        nickname: String,
+        serial: u32,
+        datagram: &MediaDatagram,
+        arrival_offset_us: u64,
+    ) -> Self {
+        let channels = match datagram.payload.first() {
+            Some(toc) if toc & OPUS_TOC_STEREO != 0 => 2,
+            _ => 1,
+        };
+        Self {
+            user_id,
+            nickname,
+            clock: TrackClock::new(datagram.timestamp_us, arrival_offset_us),
+            writer: OggOpusWriter::new(serial, channels),
+        }
+    }
+
+    pub(super) fn byte_len(&self) -> usize {
+        self.writer.byte_len()
+    }
+
+    /// Кладет пакет на его место во времени, заполняя паузу перед ним тишиной.
+    ///
+    /// Опоздавшие пакеты, чье место уже занято, отбрасываются.
+    pub(super) fn push(&mut self, datagram: &MediaDatagram, arrival_offset_us: u64) {
+        let samples = u64::from(datagram.duration_us) * OPUS_GRANULE_RATE / 1_000_000;
+        if samples == 0 || datagram.payload.is_empty() {
+            return;
+        }
+        let position = self
+            .clock
+            .position_us(datagram.timestamp_us, arrival_offset_us)
+            * OPUS_GRANULE_RATE
+            / 1_000_000;
+        if position + samples / 2 < self.writer.written_samples() {
+            return;
+        }
+        while self.writer.written_samples() + OPUS_SILENCE_SAMPLES <= position {
+            self.writer
+                .push_packet(&OPUS_SILENCE_PACKET, OPUS_SILENCE_SAMPLES);
+        }
+        self.writer.push_packet(&datagram.payload, samples);
+    }
+
+    /// Отдает закрытые с прошлого вызова страницы Ogg.
+    pub(super) fn take_ready(&mut self) -> Vec<u8> {
+        self.writer.take_pages()
+    }
+
+    pub(super) fn finish(self) -> Vec<u8> {
+        self.writer.finish()
+    }
+}
+
+/// Камера или экран одного участника в WebM VP9.
+pub(super) struct VideoTrack {
+    pub(super) user_id: Uuid,
+    pub(super) nickname: String,
+    clock: Option<TrackClock>,
+    writer: Option<WebmVp9Writer>,
+    needs_key_frame: bool,
+    /// Потеря кадра замечена при сборке и еще не превращена в запрос ключевого кадра.
+    frame_lost: bool,
+    /// Последний пропущенный кодек, о котором уже предупредили в журнале.
+    skipped_codec: Option<MediaCodec>,
+    pending: Vec<PendingVideoFrame>,
+}
+
+struct PendingVideoFrame {
+    sequence: u64,
+    total_len: usize,
+    received_len: usize,
+    fragments: Vec<Option<Vec<u8>>>,
+}
+
+impl VideoTrack {
+    pub(super) fn new(user_id: Uuid, nickname: String) -> Self {
+        Self {
+            user_id,
+            nickname,
+            clock: None,
+            writer: None,
+            needs_key_frame: true,
+            frame_lost: false,
+            skipped_codec: None,
+            pending: Vec::new(),
+        }
+    }
+
+    pub(super) fn byte_len(&self) -> usize {
+        self.writer.as_ref().map_or(0, WebmVp9Writer::byte_len)
+    }
+
+    /// Добавляет датаграмму кадра и возвращает, что дорожке нужен ключевой кадр.
+    pub(super) fn push(&mut self, datagram: &MediaDatagram, arrival_offset_us: u64) -> bool {
+        if datagram.codec != MediaCodec::Vp9 {
+            if self.skipped_codec != Some(datagram.codec) {
+                self.skipped_codec = Some(datagram.codec);
+                tracing::warn!(
+                    user_id = %self.user_id,
+                    codec = ?datagram.codec,
+                    "skipping voice recording video that is not VP9"
+                );
+            }
+            return false;
+        }
+        let Some(frame) = self.reassemble(datagram) else {
+            return std::mem::take(&mut self.frame_lost);
+        };
+        let key_frame = datagram.flags & MEDIA_DATAGRAM_FLAG_KEY_FRAME != 0;
+        if self.needs_key_frame && !key_frame {
+            return true;
+        }
+        if self.writer.is_none() {
+            let Some((width, height)) = key_frame_dimensions(MediaCodec::Vp9, &frame) else {
+                return true;
+            };
+            self.writer = Some(WebmVp9Writer::new(width, height));
+        }
+        self.needs_key_frame = false;
+
+        let clock = self
+            .clock
+            .get_or_insert_with(|| TrackClock::new(datagram.timestamp_us, arrival_offset_us));
+        let position_ms = clock.position_us(datagram.timestamp_us, arrival_offset_us) / 1_000;
+        if let Some(writer) = self.writer.as_mut() {
+            writer.push_frame(position_ms, key_frame, &frame);
+        }
+        false
+    }
+
+    /// Отдает заголовки и кластеры WebM, закрытые с прошлого вызова.
+    pub(super) fn take_ready(&mut self) -> Vec<u8> {
+        self.writer
+            .as_mut()
+            .map(WebmVp9Writer::take_ready)
+            .unwrap_or_default()
+    }
+
+    /// Возвращает хвост WebM или `None`, если ключевой кадр так и не пришел.
+    pub(super) fn finish(self) -> Option<Vec<u8>> {
+        self.writer.map(WebmVp9Writer::finish)
+    }
+
+    fn reassemble(&mut self, datagram: &MediaDatagram) -> Option<Vec<u8>> {
+        if datagram.flags & MEDIA_DATAGRAM_FLAG_FRAGMENTED == 0 {
+            return Some(datagram.payload.clone());
+        }
+        let payload = &datagram.payload;
+        if payload.len() < VIDEO_FRAGMENT_HEADER_LEN {
+            return None;
+        }
+        let total_len = u32::from_be_bytes([payload[0], payload[1], payload[2], payload[3]]);
+        let total_len = total_len as usize;
+        let index = usize::from(u16::from_be_bytes([payload[4], payload[5]]));
+        let count = usize::from(u16::from_be_bytes([payload[6], payload[7]]));
+        if count == 0 || index >= count || total_len > MAX_VIDEO_FRAME_BYTES {
+            return None;
+        }
+
+        let position = match self
+            .pending
+            .iter()
+            .position(|frame| frame.sequence == datagram.sequence)
+        {
+            Some(position) => position,
+            None => {
+                if self.pending.len() == MAX_PENDING_VIDEO_FRAMES {
+                    // Недособранный кадр потерян: следующие дельта-кадры без него не декодируются.
+                    self.pending.remove(0);
+                    self.needs_key_frame = true;
+                    self.frame_lost = true;
+                }
+                self.pending.push(PendingVideoFrame {
+                    sequence: datagram.sequence,
+                    total_len,
+                    received_len: 0,
+                    fragments: vec![None; count],
+                });
+                self.pending.len() - 1
+            }
+        };
+        let frame = &mut self.pending[position];
+        if frame.fragments.len() != count || frame.total_len != total_len {
+            return None;
+        }
+        let bytes = &payload[VIDEO_FRAGMENT_HEADER_LEN..];
+        if frame.fragments[index].is_none() {
+            frame.received_len += bytes.len();
+            frame.fragments[index] = Some(bytes.to_vec());
+        }
+        if frame.fragments.iter().any(Option::is_none) {
+            return None;
+        }
+
+        let frame = self.pending.remove(position);
+        if frame.received_len != frame.total_len {
+            self.needs_key_frame = true;
+            self.frame_lost = true;
+            return None;
+        }
+        Some(frame.fragments.into_iter().flatten().flatten().collect())
+    }
+}
@@ -0,0 +1,195 @@
+//! Упаковка кадров VP9 в контейнер WebM (Matroska).
+
+const EBML: u32 = 0x1A45_DFA3;
+const EBML_VERSION: u32 = 0x4286;
+const EBML_READ_VERSION: u32 = 0x42F7;
+const EBML_MAX_ID_LENGTH: u32 = 0x42F2;
+const EBML_MAX_SIZE_LENGTH: u32 = 0x42F3;
+const DOC_TYPE: u32 = 0x4282;
+const DOC_TYPE_VERSION: u32 = 0x4287;
+const DOC_TYPE_READ_VERSION: u32 = 0x4285;
+const SEGMENT: u32 = 0x1853_8067;
+const INFO: u32 = 0x1549_A966;
+const TIMECODE_SCALE: u32 = 0x2A_D7B1;
+const MUXING_APP: u32 = 0x4D80;
+const WRITING_APP: u32 = 0x5741;
+const TRACKS: u32 = 0x1654_AE6B;
+const TRACK_ENTRY: u32 = 0xAE;
+const TRACK_NUMBER: u32 = 0xD7;
+const TRACK_UID: u32 = 0x73C5;
+const TRACK_TYPE: u32 = 0x83;
+const CODEC_ID: u32 = 0x86;
+const VIDEO: u32 = 0xE0;
+const PIXEL_WIDTH: u32 = 0xB0;
+const PIXEL_HEIGHT: u32 = 0xBA;
+const CLUSTER: u32 = 0x1F43_B675;
+const CLUSTER_TIMECODE: u32 = 0xE7;
+const SIMPLE_BLOCK: u32 = 0xA3;
+
+const TRACK_TYPE_VIDEO: u64 = 1;
+const SIMPLE_BLOCK_KEY_FRAME: u8 = 0x80;
+/// Кластер начинается с ключевого кадра, но не живет дольше этого времени.
+const MAX_CLUSTER_DURATION_MS: u64 = 5_000;
+const APP_NAME: &str = "cheenhub";
+/// Размер элемента EBML «неизвестен»: сегмент продолжается до конца файла.
+const UNKNOWN_SIZE: [u8; 8] = [0x01, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+
+/// Пишет одну видеодорожку VP9 в WebM по кластерам.
+///
+/// Сегмент пишется с неизвестным размером и без длительности, как живой поток:
+/// заголовки и закрытые кластеры можно отдавать в хранилище сразу, не
+/// возвращаясь к началу файла. Все времена задаются в миллисекундах от начала
+/// записи комнаты, поэтому дорожка, подключившаяся позже, начинается с
+/// ненулевого timecode.
+pub(super) struct WebmVp9Writer {
+    ready: Vec<u8>,
+    taken_len: usize,
+    cluster: Option<Cluster>,
+    last_timecode_ms: u64,
+}
+
+struct Cluster {
+    timecode_ms: u64,
+    blocks: Vec<u8>,
+}
+
+impl WebmVp9Writer {
+    /// Создает дорожку с размерами первого ключевого кадра и пишет заголовки файла.
+    pub(super) fn new(width: u32, height: u32) -> Self {
+        let mut header = Vec::new();
+        write_uint(&mut header, EBML_VERSION, 1);
+        write_uint(&mut header, EBML_READ_VERSION, 1);
+        write_uint(&mut header, EBML_MAX_ID_LENGTH, 4);
+        write_uint(&mut header, EBML_MAX_SIZE_LENGTH, 8);
+        write_element(&mut header, DOC_TYPE, b"webm");
+        write_uint(&mut header, DOC_TYPE_VERSION, 4);
+        write_uint(&mut header, DOC_TYPE_READ_VERSION, 2);
+
+        let mut info = Vec::new();
+        write_uint(&mut info, TIMECODE_SCALE, 1_000_000);
+        write_element(&mut info, MUXING_APP, APP_NAME.as_bytes());
+        write_element(&mut info, WRITING_APP, APP_NAME.as_bytes());
+
+        let mut video = Vec::new();
+        write_uint(&mut video, PIXEL_WIDTH, u64::from(width));
+        write_uint(&mut video, PIXEL_HEIGHT, u64::from(height));
+        let mut track = Vec::new();
+        write_uint(&mut track, TRACK_NUMBER, 1);
+        write_uint(&mut track, TRACK_UID, 1);
+        write_uint(&mut track, TRACK_TYPE, TRACK_TYPE_VIDEO);
+        write_element(&mut track, CODEC_ID, b"V_VP9");
+        write_element(&mut track, VIDEO, &video);
+        let mut tracks = Vec::new();
+        write_element(&mut tracks, TRACK_ENTRY, &track);
+
+        let mut ready = Vec::new();
+        write_element(&mut ready, EBML, &header);
+        write_id(&mut ready, SEGMENT);
+        ready.extend_from_slice(&UNKNOWN_SIZE);
+        write_element(&mut ready, INFO, &info);
+        write_element(&mut ready, TRACKS, &tracks);
+
+        Self {
+            ready,
+            taken_len: 0,
+            cluster: None,
+            last_timecode_ms: 0,
+        }
+    }
+
+    /// Текущий размер файла вместе с еще не закрытым кластером.
+    pub(super) fn byte_len(&self) -> usize {
+        self.taken_len
+            + self.ready.len()
+            + self
+                .cluster
+                .as_ref()
+                .map_or(0, |cluster| cluster.blocks.len())
+    }
+
+    /// Отдает заголовки и кластеры, закрытые с прошлого вызова.
+    pub(super) fn take_ready(&mut self) -> Vec<u8> {
+        self.taken_len += self.ready.len();
+        std::mem::take(&mut self.ready)
+    }
+
+    /// Добавляет один полный кадр VP9; timecode не может идти назад.
+    pub(super) fn push_frame(&mut self, timecode_ms: u64, key_frame: bool, frame: &[u8]) {
+        let timecode_ms = timecode_ms.max(self.last_timecode_ms);
+        let starts_cluster = match &self.cluster {
+            Some(cluster) => {
+                let elapsed = timecode_ms - cluster.timecode_ms;
+                (key_frame && elapsed > 0) || elapsed >= MAX_CLUSTER_DURATION_MS
+            }
+            None => true,
+        };
+        if starts_cluster {
+            self.close_cluster();
+            self.cluster = Some(Cluster {
+                timecode_ms,
+                blocks: Vec::new(),
+            });
+        }
+        let cluster = self.cluster.as_mut().expect("cluster was opened");
+        let relative = (timecode_ms - cluster.timecode_ms) as i16;
+
+        let mut block = Vec::with_capacity(frame.len() + 4);
+        block.push(0x81);
+        block.extend_from_slice(&relative.to_be_bytes());
+        block.push(if key_frame { SIMPLE_BLOCK_KEY_FRAME } else { 0 });
+        block.extend_from_slice(frame);
+        write_element(&mut cluster.blocks, SIMPLE_BLOCK, &block);
+        self.last_timecode_ms = timecode_ms;
+    }
+
+    /// Закрывает последний кластер и возвращает еще не отданные байты файла.
+    pub(super) fn finish(mut self) -> Vec<u8> {
+        self.close_cluster();
+        self.ready
+    }
+
+    fn close_cluster(&mut self) {
+        let Some(cluster) = self.cluster.take() else {
+            return;
+        };
+        let mut body = Vec::with_capacity(cluster.blocks.len() + 10);
+        write_uint(&mut body, CLUSTER_TIMECODE, cluster.timecode_ms);
+        body.extend_from_slice(&cluster.blocks);
+        write_element(&mut self.ready, CLUSTER, &body);
+    }
+}
+
+fn write_element(out: &mut Vec<u8>, id: u32, data: &[u8]) {
+    write_id(out, id);
+    write_size(out, data.len() as u64);
+    out.extend_from_slice(data);
+}
+
+fn write_id(out: &mut Vec<u8>, id: u32) {
+    let id_bytes = id.to_be_bytes();
+    let first = id_bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(id_bytes.len() - 1);
+    out.extend_from_slice(&id_bytes[first..]);
+}
+
+fn write_uint(out: &mut Vec<u8>, id: u32, value: u64) {
+    let bytes = value.to_be_bytes();
+    let first = bytes
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(bytes.len() - 1);
+    write_element(out, id, &bytes[first..]);
+}
+
+/// Пишет размер элемента EBML минимальным variable-size integer.
+fn write_size(out: &mut Vec<u8>, size: u64) {
+    let mut length = 1;
+    // Значение из одних единиц зарезервировано под неизвестный размер.
+    while length < 8 && size >= (1_u64 << (7 * length)) - 1 {
+        length += 1;
+    }
+    let marked = size | (1_u64 << (7 * length));
+    out.extend_from_slice(&marked.to_be_bytes()[8 - length..]);
+}
@@ -0,0 +1,80 @@
+//! Тестовые конструкторы присутствия и медиадатаграмм голосового чата.
+//!
+//! Остальные поля тесты переопределяют через `..builder(...)`.
+
+use cheenhub_contracts::media::{
+    MEDIA_DATAGRAM_FLAG_KEY_FRAME, MediaCodec, MediaDatagram, MediaDatagramKind,
+};
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use uuid::Uuid;
+
+use super::infrastructure::{VoicePresence, VoicePresenceTargetKind};
+
+const VOICE_FRAME_DURATION_US: u32 = 20_000;
+const CAMERA_FRAME_DURATION_US: u32 = 33_000;
+
+/// Участник серверной комнаты без модерации с новыми потоком и сессией.
+pub(crate) fn voice_presence(server_id: Uuid, room_id: Uuid, user_id: Uuid) -> VoicePresence {
+    VoicePresence {
+        realtime_stream_id: Uuid::new_v4(),
+        session_id: Uuid::new_v4(),
+        target_kind: VoicePresenceTargetKind::Server,
+        server_id,
+        room_id,
+        user_id,
+        nickname: "participant".to_owned(),
+        avatar_url: None,
+        joined_at: chrono::Utc::now(),
+        server_muted: false,
+        server_deafened: false,
+    }
+}
+
+/// Голосовой кадр Opus на 20 мс; метка времени следует из номера кадра.
+pub(crate) fn voice_datagram(
+    room_id: Uuid,
+    sender_user_id: Uuid,
+    sequence: u64,
+    payload: Vec<u8>,
+) -> MediaDatagram {
+    MediaDatagram {
+        kind: MediaDatagramKind::VoiceFrame,
+        codec: MediaCodec::Opus,
+        flags: 0,
+        sequence,
+        timestamp_us: sequence * u64::from(VOICE_FRAME_DURATION_US),
+        duration_us: VOICE_FRAME_DURATION_US,
+        room_id,
+        sender_user_id,
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload,
+    }
+}
+
+/// Кадр камеры VP9 верхнего слоя; метка времени следует из номера кадра.
+pub(crate) fn camera_datagram(
+    room_id: Uuid,
+    sender_user_id: Uuid,
+    sequence: u64,
+    key_frame: bool,
+    payload: Vec<u8>,
+) -> MediaDatagram {
+    MediaDatagram {
+        kind: MediaDatagramKind::CameraFrame,
+        codec: MediaCodec::Vp9,
+        flags: if key_frame {
+            MEDIA_DATAGRAM_FLAG_KEY_FRAME
+        } else {
+            0
+        },
+        sequence,
+        timestamp_us: sequence * u64::from(CAMERA_FRAME_DURATION_US),
+        duration_us: CAMERA_FRAME_DURATION_US,
+        room_id,
+        sender_user_id,
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload,
+    }
+}
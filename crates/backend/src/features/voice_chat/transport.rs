@@ -0,0 +1,127 @@
+//! HTTP-адаптер записей голосовых комнат.
+
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use cheenhub_contracts::rest::{ApiError, ListVoiceRecordingsResponse};
+use serde::Deserialize;
+
+use crate::features::auth::application::require_current_user;
+use crate::features::auth::error::AuthError;
+use crate::features::voice_chat::application::{self, VoiceChatApplicationError};
+use crate::state::AppState;
+
+/// Query-параметры списка записей голосовых комнат.
+#[derive(Deserialize)]
+pub(crate) struct ListRecordingsQuery {
+    /// Запись-курсор, после которой нужно вернуть более старую страницу.
+    before_recording_id: Option<String>,
+}
+
+/// Перечисляет завершенные записи голосовых комнат сервера.
+pub(crate) async fn list_recordings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(server_id): Path<String>,
+    Query(query): Query<ListRecordingsQuery>,
+) -> Result<Json<ListVoiceRecordingsResponse>, VoiceChatApplicationError> {
+    let token = bearer_token(&headers)?;
+    let (user, _) = require_current_user(&state, token)
+        .await
+        .map_err(map_auth_error)?;
+    application::list_recordings(&state, &user.id, server_id, query.before_recording_id)
+        .await
+        .map(Json)
+}
+
+/// Отдает один файл завершенной записи участнику сервера.
+pub(crate) async fn recording_file(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path((server_id, recording_id, file_name)): Path<(String, String, String)>,
+) -> Result<Response, VoiceChatApplicationError> {
+    let token = bearer_token(&headers)?;
+    let (user, _) = require_current_user(&state, token)
+        .await
+        .map_err(map_auth_error)?;
+    let (content_type, bytes) =
+        application::recording_file(&state, &user.id, server_id, recording_id, file_name.clone())
+            .await?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, content_type),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{file_name}\""),
+            ),
+            (header::X_CONTENT_TYPE_OPTIONS, "nosniff".to_owned()),
+            (header::CACHE_CONTROL, "private, max-age=3600".to_owned()),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+impl IntoResponse for VoiceChatApplicationError {
+    fn into_response(self) -> Response {
+        let (status, code, message) = match self {
+            Self::BadRequest(message) => (StatusCode::BAD_REQUEST, "bad_request", message),
+            Self::Unauthorized(message) => (StatusCode::UNAUTHORIZED, "unauthorized", message),
+            Self::NotFound(message) => (StatusCode::NOT_FOUND, "not_found", message),
+            Self::Internal(error) => {
+                tracing::error!(%error, "voice recording request failed");
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "internal_error",
+                    "Не удалось получить запись голосовой комнаты.".to_owned(),
+                )
+            }
+        };
+        (
+            status,
+            Json(ApiError {
+                code: code.to_owned(),
+                message,
+            }),
+        )
+            .into_response()
+    }
+}
+
+fn bearer_token(headers: &HeaderMap) -> Result<&str, VoiceChatApplicationError> {
+    let value = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .ok_or_else(unauthorized)?;
+
+    value
+        .strip_prefix("Bearer ")
+        .filter(|token| !token.is_empty())
+        .ok_or_else(unauthorized)
+}
+
+fn map_auth_error(error: AuthError) -> VoiceChatApplicationError {
+    match error {
+        AuthError::BadRequest(message)
+        | AuthError::Unauthorized(message)
+        | AuthError::RefreshRejected { message, .. }
+        | AuthError::RefreshRotationInProgress(message) => {
+            VoiceChatApplicationError::Unauthorized(message)
+        }
+        AuthError::Conflict(message) | AuthError::RateLimited(message) => {
+            VoiceChatApplicationError::BadRequest(message)
+        }
+        AuthError::Misconfigured { message, .. } => {
+            VoiceChatApplicationError::Internal(anyhow::anyhow!(message))
+        }
+        AuthError::Internal(error) => VoiceChatApplicationError::Internal(error),
+    }
+}
+
+fn unauthorized() -> VoiceChatApplicationError {
+    VoiceChatApplicationError::Unauthorized("Войди, чтобы продолжить.".to_owned())
+}
@@ -0,0 +1,224 @@
+//! Выбор видеокодека комнаты и доставка видео участникам с подходящим декодером.
+
+use cheenhub_contracts::realtime::VoiceVideoCodec;
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
+
+/// Порядок предпочтения при равной поддержке: AV1 дает то же качество меньшим
+/// битрейтом, H.264 остается запасным кодеком с повсеместным аппаратным декодером.
+const CODEC_PREFERENCE: [VoiceVideoCodec; 3] = [
+    VoiceVideoCodec::Av1,
+    VoiceVideoCodec::Vp9,
+    VoiceVideoCodec::H264,
+];
+
+/// Видеокодеки, с которыми работает клиент участника.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VideoCodecCapabilities {
+    /// Кодеки, которые клиент умеет декодировать.
+    pub(crate) decoders: Vec<VoiceVideoCodec>,
+    /// Кодеки, которыми клиент умеет публиковать видео.
+    pub(crate) encoders: Vec<VoiceVideoCodec>,
+}
+
+impl Default for VideoCodecCapabilities {
+    /// Клиенты без согласования кодеков работают только с VP9.
+    fn default() -> Self {
+        Self {
+            decoders: vec![VoiceVideoCodec::Vp9],
+            encoders: vec![VoiceVideoCodec::Vp9],
+        }
+    }
+}
+
+/// Выбирает кодек публикаций комнаты.
+///
+/// Кандидат должен декодироваться каждым участником, показывающим видео, и
+/// кодироваться хотя бы одним участником. Среди кандидатов выигрывает кодек,
+/// которым могут публиковать больше участников, затем — порядок
+/// [`CODEC_PREFERENCE`]; без кандидатов комната остается на VP9. Участники с
+/// пустым списком декодеров не показывают видео и на выбор не влияют.
+fn select_video_codec(participants: &[&VideoCodecCapabilities]) -> VoiceVideoCodec {
+    let mut best = VoiceVideoCodec::Vp9;
+    let mut best_encoders = 0;
+    for codec in CODEC_PREFERENCE {
+        let decodable = participants.iter().all(|capabilities| {
+            capabilities.decoders.is_empty() || capabilities.decoders.contains(&codec)
+        });
+        let encoders = participants
+            .iter()
+            .filter(|capabilities| capabilities.encoders.contains(&codec))
+            .count();
+        if decodable && encoders > best_encoders {
+            best = codec;
+            best_encoders = encoders;
+        }
+    }
+    best
+}
+
+/// Объявленные кодеки участников и выбранный кодек каждой комнаты.
+#[derive(Default)]
+pub(super) struct VideoCodecTracker {
+    capabilities: Vec<StoredCapabilities>,
+    rooms: Vec<RoomVideoCodecs>,
+}
+
+impl VideoCodecTracker {
+    fn record(&mut self, room_id: Uuid, user_id: Uuid, capabilities: VideoCodecCapabilities) {
+        match self
+            .capabilities
+            .iter_mut()
+            .find(|stored| stored.room_id == room_id && stored.user_id == user_id)
+        {
+            Some(stored) => stored.capabilities = capabilities,
+            None => self.capabilities.push(StoredCapabilities {
+                room_id,
+                user_id,
+                capabilities,
+            }),
+        }
+    }
+
+    /// Пересчитывает выбор комнаты и возвращает кодек, если он изменился.
+    fn refresh(
+        &mut self,
+        room_id: Uuid,
+        participants: &[VoicePresence],
+    ) -> Option<VoiceVideoCodec> {
+        if participants.is_empty() {
+            self.rooms.retain(|room| room.room_id != room_id);
+            return None;
+        }
+
+        let legacy = VideoCodecCapabilities::default();
+        let capabilities = participants
+            .iter()
+            .map(|participant| {
+                self.capabilities
+                    .iter()
+                    .find(|stored| {
+                        stored.room_id == room_id && stored.user_id == participant.user_id
+                    })
+                    .map_or(&legacy, |stored| &stored.capabilities)
+            })
+            .collect::<Vec<_>>();
+        let selected = select_video_codec(&capabilities);
+
+        let room = match self.rooms.iter_mut().find(|room| room.room_id == room_id) {
+            Some(room) => room,
+            None => {
+                self.rooms.push(RoomVideoCodecs {
+                    room_id,
+                    selected: VoiceVideoCodec::Vp9,
+                });
+                self.rooms.last_mut().expect("room was inserted")
+            }
+        };
+        (std::mem::replace(&mut room.selected, selected) != selected).then_some(selected)
+    }
+
+    fn selected(&self, room_id: Uuid) -> VoiceVideoCodec {
+        self.rooms
+            .iter()
+            .find(|room| room.room_id == room_id)
+            .map_or(VoiceVideoCodec::Vp9, |room| room.selected)
+    }
+
+    /// Декодирует ли клиент участника кодек; до объявления клиент считается VP9-only.
+    fn decodes(&self, room_id: Uuid, user_id: Uuid, codec: VoiceVideoCodec) -> bool {
+        match self
+            .capabilities
+            .iter()
+            .find(|stored| stored.room_id == room_id && stored.user_id == user_id)
+        {
+            Some(stored) => stored.capabilities.decoders.contains(&codec),
+            None => codec == VoiceVideoCodec::Vp9,
+        }
+    }
+
+    pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
+        self.capabilities.retain(|stored| {
+            !removed.iter().any(|presence| {
+                stored.room_id == presence.room_id && stored.user_id == presence.user_id
+            })
+        });
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Запоминает видеокодеки, объявленные клиентом участника.
+    pub(crate) async fn record_video_codec_capabilities(
+        &self,
+        presence: &VoicePresence,
+        capabilities: VideoCodecCapabilities,
+    ) {
+        self.video_codecs
+            .lock()
+            .await
+            .record(presence.room_id, presence.user_id, capabilities);
+    }
+
+    /// Пересчитывает кодек комнаты по текущим участникам и возвращает его, если он изменился.
+    pub(crate) async fn refresh_room_video_codec(
+        &self,
+        room_id: Uuid,
+        participants: &[VoicePresence],
+    ) -> Option<VoiceVideoCodec> {
+        self.video_codecs
+            .lock()
+            .await
+            .refresh(room_id, participants)
+    }
+
+    /// Возвращает выбранный кодек комнаты; до первого выбора комната работает с VP9.
+    pub(crate) async fn room_video_codec(&self, room_id: Uuid) -> VoiceVideoCodec {
+        self.video_codecs.lock().await.selected(room_id)
+    }
+
+    /// Оставляет получателей, чьи клиенты декодируют кодек публикации.
+    ///
+    /// Участник без подходящего декодера не получает видео, но остальным
+    /// участникам комнаты оно пересылается.
+    pub(crate) async fn video_decoder_sessions(
+        &self,
+        publisher: &VoicePresence,
+        codec: VoiceVideoCodec,
+        recipients: Vec<Uuid>,
+    ) -> Vec<Uuid> {
+        let participants = self
+            .room_participants(
+                publisher.target_kind,
+                &publisher.server_id,
+                &publisher.room_id,
+            )
+            .await;
+        let tracker = self.video_codecs.lock().await;
+        recipients
+            .into_iter()
+            .filter(|session_id| {
+                participants
+                    .iter()
+                    .find(|participant| participant.session_id == *session_id)
+                    .is_some_and(|participant| {
+                        tracker.decodes(publisher.room_id, participant.user_id, codec)
+                    })
+            })
+            .collect()
+    }
+}
+
+struct StoredCapabilities {
+    room_id: Uuid,
+    user_id: Uuid,
+    capabilities: VideoCodecCapabilities,
+}
+
+struct RoomVideoCodecs {
+    room_id: Uuid,
+    selected: VoiceVideoCodec,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,128 @@
+use super::*;
+use crate::features::voice_chat::test_builders::voice_presence;
+
+use VoiceVideoCodec::{Av1, H264, Vp9};
+
+fn capabilities(
+    decoders: &[VoiceVideoCodec],
+    encoders: &[VoiceVideoCodec],
+) -> VideoCodecCapabilities {
+    VideoCodecCapabilities {
+        decoders: decoders.to_vec(),
+        encoders: encoders.to_vec(),
+    }
+}
+
+#[test]
+fn prefers_av1_when_every_participant_handles_it() {
+    let modern = capabilities(&[Av1, Vp9, H264], &[Av1, Vp9, H264]);
+    assert_eq!(select_video_codec(&[&modern, &modern]), Av1);
+}
+
+#[test]
+fn one_participant_without_decoder_limits_the_room() {
+    let modern = capabilities(&[Av1, Vp9, H264], &[Av1, Vp9, H264]);
+    let hardware_only = capabilities(&[H264], &[H264]);
+    assert_eq!(select_video_codec(&[&modern, &hardware_only]), H264);
+}
+
+#[test]
+fn encoders_break_ties_between_commonly_decodable_codecs() {
+    let vp9_publisher = capabilities(&[Av1, Vp9], &[Vp9]);
+    assert_eq!(select_video_codec(&[&vp9_publisher, &vp9_publisher]), Vp9);
+}
+
+#[test]
+fn candidate_must_be_decodable_by_every_viewer() {
+    let av1 = capabilities(&[Av1, Vp9], &[Av1]);
+    let vp9_only = capabilities(&[Vp9], &[Vp9]);
+    assert_eq!(select_video_codec(&[&av1, &av1, &vp9_only]), Vp9);
+}
+
+#[test]
+fn codec_nobody_encodes_falls_back_to_vp9() {
+    let h264_viewer = capabilities(&[H264], &[]);
+    let av1_viewer = capabilities(&[Av1, H264], &[Av1]);
+    assert_eq!(select_video_codec(&[&h264_viewer, &av1_viewer]), Vp9);
+}
+
+#[test]
+fn participants_without_video_do_not_constrain_selection() {
+    let modern = capabilities(&[Av1, Vp9], &[Av1, Vp9]);
+    let audio_only = capabilities(&[], &[]);
+    assert_eq!(select_video_codec(&[&modern, &audio_only]), Av1);
+}
+
+#[test]
+fn unadvertised_participant_falls_back_to_vp9_until_it_advertises() {
+    let mut tracker = VideoCodecTracker::default();
+    let room_id = Uuid::new_v4();
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+    let participants = [
+        voice_presence(Uuid::new_v4(), room_id, first),
+        voice_presence(Uuid::new_v4(), room_id, second),
+    ];
+    let modern = capabilities(&[Av1, Vp9], &[Av1, Vp9]);
+
+    tracker.record(room_id, first, modern.clone());
+    assert_eq!(tracker.refresh(room_id, &participants), None);
+    assert!(tracker.decodes(room_id, first, Av1));
+    assert!(!tracker.decodes(room_id, second, Av1));
+
+    tracker.record(room_id, second, modern);
+    assert_eq!(tracker.refresh(room_id, &participants), Some(Av1));
+    assert_eq!(tracker.selected(room_id), Av1);
+    assert!(tracker.decodes(room_id, second, Av1));
+    assert!(!tracker.decodes(room_id, second, H264));
+
+    tracker.remove_presences(&participants[1..]);
+    assert_eq!(tracker.refresh(room_id, &participants[..1]), None);
+    assert_eq!(tracker.refresh(room_id, &participants), Some(Vp9));
+}
+
+#[test]
+fn empty_room_forgets_selection() {
+    let mut tracker = VideoCodecTracker::default();
+    let room_id = Uuid::new_v4();
+    let user_id = Uuid::new_v4();
+    tracker.record(room_id, user_id, capabilities(&[H264], &[H264]));
+    assert_eq!(
+        tracker.refresh(room_id, &[voice_presence(Uuid::new_v4(), room_id, user_id)]),
+        Some(H264)
+    );
+
+    assert_eq!(tracker.refresh(room_id, &[]), None);
+    assert_eq!(tracker.selected(room_id), Vp9);
+}
+
+#[tokio::test]
+async fn video_skips_only_viewers_without_decoder() {
+    let store = InMemoryVoicePresenceStore::default();
+    let room_id = Uuid::new_v4();
+    let server_id = Uuid::new_v4();
+    let [publisher, av1_viewer, h264_viewer] =
+        [(); 3].map(|()| voice_presence(server_id, room_id, Uuid::new_v4()));
+    for participant in [&publisher, &av1_viewer, &h264_viewer] {
+        store.join(participant.clone()).await;
+    }
+    store
+        .record_video_codec_capabilities(&av1_viewer, capabilities(&[Av1, Vp9], &[Vp9]))
+        .await;
+    store
+        .record_video_codec_capabilities(&h264_viewer, capabilities(&[H264], &[H264]))
+        .await;
+    let recipients = vec![av1_viewer.session_id, h264_viewer.session_id];
+
+    assert_eq!(
+        store
+            .video_decoder_sessions(&publisher, Av1, recipients.clone())
+            .await,
+        vec![av1_viewer.session_id]
+    );
+    assert_eq!(
+        store
+            .video_decoder_sessions(&publisher, H264, recipients)
+            .await,
+        vec![h264_viewer.session_id]
+    );
+}
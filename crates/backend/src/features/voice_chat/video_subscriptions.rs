@@ -0,0 +1,267 @@
+//! Подписки участников голосовой комнаты на видеопотоки.
+
+use std::time::{Duration, Instant};
+
+use cheenhub_contracts::realtime::VoiceVideoStreamSource;
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use uuid::Uuid;
+
+use super::infrastructure::{InMemoryVoicePresenceStore, VoicePresence};
+
+/// Интервал между событиями активности одной публикации для неподписанных зрителей.
+///
+/// Должен быть заметно меньше таймаута, после которого клиент снимает отметку
+/// активного видео без новых кадров.
+const VIDEO_ACTIVITY_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Default)]
+pub(super) struct VideoSubscriptionTracker {
+    subscriptions: Vec<VideoSubscriptionEntry>,
+    announcements: Vec<VideoActivityAnnouncement>,
+}
+
+impl VideoSubscriptionTracker {
+    /// Добавляет подписку или меняет ее слой; возвращает, изменилось ли что-то.
+    fn subscribe(&mut self, subscription: VideoSubscription, layer: VideoSpatialLayer) -> bool {
+        match self
+            .subscriptions
+            .iter_mut()
+            .find(|entry| entry.subscription == subscription)
+        {
+            Some(entry) if entry.layer == layer => false,
+            Some(entry) => {
+                entry.layer = layer;
+                true
+            }
+            None => {
+                self.subscriptions.push(VideoSubscriptionEntry {
+                    subscription,
+                    layer,
+                });
+                true
+            }
+        }
+    }
+
+    fn unsubscribe(&mut self, subscription: &VideoSubscription) -> bool {
+        let previous_len = self.subscriptions.len();
+        self.subscriptions
+            .retain(|entry| &entry.subscription != subscription);
+        self.subscriptions.len() != previous_len
+    }
+
+    fn subscribed_layer(&self, subscription: &VideoSubscription) -> Option<VideoSpatialLayer> {
+        self.subscriptions
+            .iter()
+            .find(|entry| &entry.subscription == subscription)
+            .map(|entry| entry.layer)
+    }
+
+    fn is_subscribed(&self, subscription: &VideoSubscription) -> bool {
+        self.subscribed_layer(subscription).is_some()
+    }
+
+    fn admit_announcement_at(&mut self, key: VideoActivityKey, now: Instant) -> bool {
+        match self
+            .announcements
+            .iter_mut()
+            .find(|announcement| announcement.key == key)
+        {
+            Some(announcement)
+                if now.saturating_duration_since(announcement.announced_at)
+                    < VIDEO_ACTIVITY_INTERVAL =>
+            {
+                false
+            }
+            Some(announcement) => {
+                announcement.announced_at = now;
+                true
+            }
+            None => {
+                self.announcements.push(VideoActivityAnnouncement {
+                    key,
+                    announced_at: now,
+                });
+                true
+            }
+        }
+    }
+
+    /// Подписки отправителя переживают его переподключение: открытые плитки
+    /// зрителей продолжают ждать кадры того же пользователя.
+    pub(super) fn remove_presences(&mut self, removed: &[VoicePresence]) {
+        self.subscriptions.retain(|entry| {
+            !removed.iter().any(|presence| {
+                entry.subscription.subscriber_session_id == presence.session_id
+                    && entry.subscription.room_id == presence.room_id
+            })
+        });
+        self.announcements.retain(|announcement| {
+            !removed.iter().any(|presence| {
+                announcement.key.user_id == presence.user_id
+                    && announcement.key.room_id == presence.room_id
+            })
+        });
+    }
+}
+
+impl InMemoryVoicePresenceStore {
+    /// Подписывает сессию участника на слой видеопотока и возвращает, изменилась ли подписка.
+    pub(crate) async fn subscribe_video(
+        &self,
+        subscriber: &VoicePresence,
+        publisher_user_id: Uuid,
+        source: VoiceVideoStreamSource,
+        layer: VideoSpatialLayer,
+    ) -> bool {
+        self.video_subscriptions.lock().await.subscribe(
+            VideoSubscription::new(subscriber, publisher_user_id, source),
+            layer,
+        )
+    }
+
+    /// Отписывает сессию участника от видеопотока и возвращает, была ли подписка.
+    pub(crate) async fn unsubscribe_video(
+        &self,
+        subscriber: &VoicePresence,
+        publisher_user_id: Uuid,
+        source: VoiceVideoStreamSource,
+    ) -> bool {
+        self.video_subscriptions
+            .lock()
+            .await
+            .unsubscribe(&VideoSubscription::new(
+                subscriber,
+                publisher_user_id,
+                source,
+            ))
+    }
+
+    /// Оставляет среди получателей медиа только сессии, которым relay выбрал слой датаграммы.
+    pub(super) async fn video_subscriber_sessions(
+        &self,
+        room_id: Uuid,
+        publisher_user_id: Uuid,
+        source: VoiceVideoStreamSource,
+        datagram_layer: VideoSpatialLayer,
+        active_layers: &[VideoSpatialLayer],
+        recipients: Vec<Uuid>,
+    ) -> Vec<Uuid> {
+        let subscriptions = self.video_subscriptions.lock().await;
+        recipients
+            .into_iter()
+            .filter(|session_id| {
+                subscriptions
+                    .subscribed_layer(&VideoSubscription {
+                        subscriber_session_id: *session_id,
+                        room_id,
+                        publisher_user_id,
+                        source,
+                    })
+                    .is_some_and(|wanted| {
+                        forwarded_layer(wanted, active_layers).unwrap_or(wanted) == datagram_layer
+                    })
+            })
+            .collect()
+    }
+
+    /// Возвращает realtime-потоки участников, которым пора напомнить об активной
+    /// публикации, или пустой список, если напоминание отправлялось недавно.
+    pub(crate) async fn video_activity_recipients(
+        &self,
+        publisher: &VoicePresence,
+        source: VoiceVideoStreamSource,
+    ) -> Vec<Uuid> {
+        let admitted = self.video_subscriptions.lock().await.admit_announcement_at(
+            VideoActivityKey {
+                session_id: publisher.session_id,
+                user_id: publisher.user_id,
+                room_id: publisher.room_id,
+                source,
+            },
+            Instant::now(),
+        );
+        if !admitted {
+            return Vec::new();
+        }
+
+        let participants = self
+            .room_participants(
+                publisher.target_kind,
+                &publisher.server_id,
+                &publisher.room_id,
+            )
+            .await;
+        let subscriptions = self.video_subscriptions.lock().await;
+        participants
+            .into_iter()
+            .filter(|participant| {
+                participant.session_id != publisher.session_id
+                    && !subscriptions.is_subscribed(&VideoSubscription::new(
+                        participant,
+                        publisher.user_id,
+                        source,
+                    ))
+            })
+            .map(|participant| participant.realtime_stream_id)
+            .collect()
+    }
+}
+
+/// Выбирает слой для получателя: нужный, если отправитель его публикует, иначе
+/// ближайший более низкий, а если такого нет — самый низкий из доступных.
+fn forwarded_layer(
+    wanted: VideoSpatialLayer,
+    active_layers: &[VideoSpatialLayer],
+) -> Option<VideoSpatialLayer> {
+    active_layers
+        .iter()
+        .copied()
+        .filter(|layer| *layer <= wanted)
+        .max()
+        .or_else(|| active_layers.iter().copied().min())
+}
+
+struct VideoSubscriptionEntry {
+    subscription: VideoSubscription,
+    layer: VideoSpatialLayer,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VideoSubscription {
+    subscriber_session_id: Uuid,
+    room_id: Uuid,
+    publisher_user_id: Uuid,
+    source: VoiceVideoStreamSource,
+}
+
+impl VideoSubscription {
+    fn new(
+        subscriber: &VoicePresence,
+        publisher_user_id: Uuid,
+        source: VoiceVideoStreamSource,
+    ) -> Self {
+        Self {
+            subscriber_session_id: subscriber.session_id,
+            room_id: subscriber.room_id,
+            publisher_user_id,
+            source,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct VideoActivityKey {
+    session_id: Uuid,
+    user_id: Uuid,
+    room_id: Uuid,
+    source: VoiceVideoStreamSource,
+}
+
+struct VideoActivityAnnouncement {
+    key: VideoActivityKey,
+    announced_at: Instant,
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::features::voice_chat::test_builders::voice_presence;
+
+#[test]
+fn subscriber_removal_keeps_other_viewers_subscribed() {
+    let mut tracker = VideoSubscriptionTracker::default();
+    let room_id = Uuid::new_v4();
+    let publisher = voice_presence(Uuid::new_v4(), room_id, Uuid::new_v4());
+    let leaving = voice_presence(Uuid::new_v4(), room_id, Uuid::new_v4());
+    let staying = voice_presence(Uuid::new_v4(), room_id, Uuid::new_v4());
+    let leaving_camera =
+        VideoSubscription::new(&leaving, publisher.user_id, VoiceVideoStreamSource::Camera);
+    let staying_camera =
+        VideoSubscription::new(&staying, publisher.user_id, VoiceVideoStreamSource::Camera);
+
+    assert!(tracker.subscribe(leaving_camera, VideoSpatialLayer::High));
+    assert!(!tracker.subscribe(leaving_camera, VideoSpatialLayer::High));
+    assert!(tracker.subscribe(leaving_camera, VideoSpatialLayer::Low));
+    assert!(tracker.subscribe(staying_camera, VideoSpatialLayer::Low));
+    tracker.remove_presences(&[leaving, publisher]);
+
+    assert!(!tracker.is_subscribed(&leaving_camera));
+    assert!(tracker.is_subscribed(&staying_camera));
+    assert!(!tracker.is_subscribed(&VideoSubscription {
+        source: VoiceVideoStreamSource::ScreenShare,
+        ..staying_camera
+    }));
+}
+
+#[test]
+fn receivers_fall_back_to_published_layers() {
+    use VideoSpatialLayer::{High, Low};
+
+    assert_eq!(forwarded_layer(Low, &[Low, High]), Some(Low));
+    assert_eq!(forwarded_layer(High, &[Low, High]), Some(High));
+    assert_eq!(forwarded_layer(Low, &[High]), Some(High));
+    assert_eq!(forwarded_layer(High, &[Low]), Some(Low));
+    assert_eq!(forwarded_layer(High, &[]), None);
+}
+
+#[test]
+fn activity_announcements_are_spaced_per_publication() {
+    let mut tracker = VideoSubscriptionTracker::default();
+    let publisher = voice_presence(Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    let camera = VideoActivityKey {
+        session_id: publisher.session_id,
+        user_id: publisher.user_id,
+        room_id: publisher.room_id,
+        source: VoiceVideoStreamSource::Camera,
+    };
+    let screen = VideoActivityKey {
+        source: VoiceVideoStreamSource::ScreenShare,
+        ..camera
+    };
+    let now = Instant::now();
+
+    assert!(tracker.admit_announcement_at(camera, now));
+    assert!(!tracker.admit_announcement_at(camera, now + Duration::from_millis(100)));
+    assert!(tracker.admit_announcement_at(screen, now + Duration::from_millis(100)));
+    assert!(tracker.admit_announcement_at(camera, now + VIDEO_ACTIVITY_INTERVAL));
+
+    tracker.remove_presences(&[publisher]);
+    assert!(tracker.admit_announcement_at(camera, now + VIDEO_ACTIVITY_INTERVAL));
+}
@@ -2,7 +2,7 @@
 
 use axum::{Router, http::StatusCode, routing::get};
 
-use crate::features::{auth, images, push_notifications, servers, social};
+use crate::features::{auth, images, push_notifications, servers, social, text_chat, voice_chat};
 use crate::realtime;
 use crate::state::AppState;
 
@@ -16,7 +16,12 @@ pub(crate) fn router() -> Router<AppState> {
         .nest("/direct", social::dm_routes())
         .nest("/direct-messages", social::dm_routes())
         .route("/realtime/ws", get(realtime::websocket::upgrade))
-        .nest("/servers", servers::routes())
+        .nest(
+            "/servers",
+            servers::routes()
+                .merge(text_chat::server_routes())
+                .merge(voice_chat::server_routes()),
+        )
         .fallback(not_found)
 }
 
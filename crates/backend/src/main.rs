@@ -21,6 +21,7 @@ type Stores = (
     Arc<dyn features::text_chat::infrastructure::TextChatStore>,
     Arc<dyn features::text_chat::infrastructure::ChatAttachmentObjectStore>,
     Arc<dyn features::images::infrastructure::ImageStore>,
+    Arc<dyn features::link_previews::infrastructure::LinkPreviewStore>,
     Arc<features::push_notifications::application::PushNotifications>,
 );
 
@@ -71,6 +72,40 @@ async fn main() -> anyhow::Result<()> {
             Arc::new(features::text_chat::infrastructure::DisabledChatAttachmentObjectStore)
         }
     };
+    let voice_recording_archive: Arc<
+        dyn features::voice_chat::infrastructure::VoiceRecordingArchive,
+    > = match &config.voice_recordings {
+        config::VoiceRecordingStorageConfig::Directory(directory) => {
+            tracing::info!(%directory, "configured voice recording directory storage");
+            Arc::new(
+                features::voice_chat::infrastructure::DirectoryVoiceRecordingArchive::new(
+                    directory,
+                ),
+            )
+        }
+        config::VoiceRecordingStorageConfig::S3(s3_config) => {
+            tracing::info!(
+                bucket = %s3_config.bucket,
+                endpoint = %s3_config.endpoint,
+                region = %s3_config.region,
+                force_path_style = s3_config.force_path_style,
+                "configured voice recording S3 storage"
+            );
+            Arc::new(
+                features::voice_chat::infrastructure::S3VoiceRecordingArchive::from_config(
+                    s3_config,
+                )
+                .await,
+            )
+        }
+        config::VoiceRecordingStorageConfig::Disabled => {
+            tracing::warn!(
+                missing_env = ?["VOICE_RECORDINGS_DIR", "VOICE_RECORDINGS_S3_BUCKET"],
+                "voice room recording is disabled until recording storage is configured"
+            );
+            Arc::new(features::voice_chat::infrastructure::DisabledVoiceRecordingArchive)
+        }
+    };
     let (
         auth_store,
         server_store,
@@ -78,6 +113,7 @@ async fn main() -> anyhow::Result<()> {
         text_chat_store,
         chat_attachment_object_store,
         image_store,
+        link_preview_store,
         push_notifications,
     ): Stores = match config.auth_store {
         config::AuthStoreConfig::Postgres => {
@@ -126,8 +162,13 @@ async fn main() -> anyhow::Result<()> {
                 ),
                 chat_attachment_object_store.clone(),
                 Arc::new(features::images::infrastructure::PostgresImageStore::new(
-                    database,
+                    database.clone(),
                 )),
+                Arc::new(
+                    features::link_previews::infrastructure::PostgresLinkPreviewStore::new(
+                        database,
+                    ),
+                ),
                 push_notifications,
             )
         }
@@ -146,6 +187,9 @@ async fn main() -> anyhow::Result<()> {
                 Arc::new(features::text_chat::infrastructure::InMemoryTextChatStore::default()),
                 chat_attachment_object_store,
                 Arc::new(features::images::infrastructure::InMemoryImageStore::default()),
+                Arc::new(
+                    features::link_previews::infrastructure::InMemoryLinkPreviewStore::default(),
+                ),
                 push_notifications,
             )
         }
@@ -169,6 +213,8 @@ async fn main() -> anyhow::Result<()> {
         text_chat_store,
         chat_attachment_object_store,
         image_store,
+        link_preview_store,
+        link_preview_fetcher: Arc::new(features::link_previews::LinkPreviewFetcher::public_only()),
         push_notifications: push_notifications.clone(),
         image_processing_queue: Arc::new(tokio::sync::Semaphore::new(1)),
         voice_presence_store: Arc::new(
@@ -177,6 +223,7 @@ async fn main() -> anyhow::Result<()> {
         direct_call_store: Arc::new(
             features::voice_chat::infrastructure::InMemoryDirectCallStore::default(),
         ),
+        voice_recording_archive,
         realtime_hub: Arc::new(realtime::hub::RealtimeHub::default()),
         auth_keys,
         access_token_lifetime_minutes: config.access_token_lifetime_minutes,
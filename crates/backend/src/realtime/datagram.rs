@@ -102,10 +102,10 @@ async fn dispatch(state: &AppState, session_id: Uuid, user_id: Uuid, datagram: M
         MediaDatagramKind::VoiceFrame if datagram.codec == MediaCodec::Opus => {
             voice_chat::media::handle_voice_frame(state, session_id, user_id, datagram).await;
         }
-        MediaDatagramKind::ScreenFrame if datagram.codec == MediaCodec::Vp9 => {
+        MediaDatagramKind::ScreenFrame if datagram.codec.is_video() => {
             voice_chat::media::handle_screen_frame(state, session_id, user_id, datagram).await;
         }
-        MediaDatagramKind::CameraFrame if datagram.codec == MediaCodec::Vp9 => {
+        MediaDatagramKind::CameraFrame if datagram.codec.is_video() => {
             voice_chat::media::handle_camera_frame(state, session_id, user_id, datagram).await;
         }
         _ => {
@@ -5,7 +5,9 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use cheenhub_contracts::realtime::{
     NetworkKind, Ping, Pong, RealtimeEnvelope, RealtimeKind, RealtimeModule, RejectionCode,
 };
+use uuid::Uuid;
 
+use crate::features::voice_chat;
 use crate::state::AppState;
 
 use super::protocol::{decode_payload, require_request_id, send_rejection, write_envelope};
@@ -13,7 +15,9 @@ use super::sink::EnvelopeSink;
 
 /// Обрабатывает один конверт сетевого модуля.
 pub(crate) async fn handle(
-    _state: &AppState,
+    state: &AppState,
+    user_id: &Uuid,
+    session_id: Uuid,
     send: &EnvelopeSink,
     envelope: RealtimeEnvelope,
 ) -> anyhow::Result<()> {
@@ -36,6 +40,10 @@ pub(crate) async fn handle(
             )
             .await
         }
+        RealtimeKind::Network(NetworkKind::ReceiverReport) => {
+            voice_chat::realtime::handle_receiver_report(state, user_id, session_id, send, envelope)
+                .await
+        }
         RealtimeKind::Network(_) => {
             send_rejection(
                 send,
@@ -23,7 +23,9 @@ pub(crate) async fn dispatch(
 ) -> anyhow::Result<()> {
     match envelope.module {
         RealtimeModule::Control => control::handle(state, send, envelope).await,
-        RealtimeModule::Network => network::handle(state, send, envelope).await,
+        RealtimeModule::Network => {
+            network::handle(state, user_id, session_id, send, envelope).await
+        }
         RealtimeModule::Server => servers::realtime::handle(state, user_id, send, envelope).await,
         RealtimeModule::Social => social::realtime::handle(state, user_id, send, envelope).await,
         RealtimeModule::TextChat => {
@@ -8,12 +8,14 @@ use crate::features::auth::email::AuthMailer;
 use crate::features::auth::infrastructure::AuthStore;
 use crate::features::auth::security::keys::AuthKeys;
 use crate::features::images::infrastructure::ImageStore;
+use crate::features::link_previews::LinkPreviewFetcher;
+use crate::features::link_previews::infrastructure::LinkPreviewStore;
 use crate::features::push_notifications::application::PushNotifications;
 use crate::features::servers::infrastructure::ServerStore;
 use crate::features::social::infrastructure::SocialStore;
 use crate::features::text_chat::infrastructure::{ChatAttachmentObjectStore, TextChatStore};
 use crate::features::voice_chat::infrastructure::{
-    InMemoryDirectCallStore, InMemoryVoicePresenceStore,
+    InMemoryDirectCallStore, InMemoryVoicePresenceStore, VoiceRecordingArchive,
 };
 use crate::realtime::hub::RealtimeHub;
 
@@ -34,6 +36,10 @@ pub(crate) struct AppState {
     pub(crate) chat_attachment_object_store: Arc<dyn ChatAttachmentObjectStore>,
     /// Бэкенд хранения изображений.
     pub(crate) image_store: Arc<dyn ImageStore>,
+    /// Кеш карточек ссылок из сообщений.
+    pub(crate) link_preview_store: Arc<dyn LinkPreviewStore>,
+    /// Загрузчик страниц для карточек ссылок с защитой от SSRF.
+    pub(crate) link_preview_fetcher: Arc<LinkPreviewFetcher>,
     /// Координатор регистрации и постоянной доставки push-уведомлений.
     pub(crate) push_notifications: Arc<PushNotifications>,
     /// Очередь на уровне процесса, ограничивающая параллельность обработки изображений.
@@ -42,6 +48,8 @@ pub(crate) struct AppState {
     pub(crate) voice_presence_store: Arc<InMemoryVoicePresenceStore>,
     /// Незавершённые приглашения и активные личные звонки.
     pub(crate) direct_call_store: Arc<InMemoryDirectCallStore>,
+    /// Хранилище файлов записей голосовых комнат.
+    pub(crate) voice_recording_archive: Arc<dyn VoiceRecordingArchive>,
     /// Общий реестр потоков realtime и хаб вещания.
     pub(crate) realtime_hub: Arc<RealtimeHub>,
     /// Ключи подписи Access JWT.
@@ -247,6 +247,9 @@ class MainActivity : WryActivity() {
     fun consumeCheenHubPendingFriendRequests(): Boolean =
         CheenHubPushStore.consumePendingFriendRequests(this)
 
+    fun consumeCheenHubPendingMention(): String? =
+        CheenHubPushStore.consumePendingMention(this)
+
     fun setCheenHubActiveDirectMessageConversationId(conversationId: String?) {
         CheenHubPushStore.setActiveConversationId(this, conversationId)
     }
@@ -298,6 +301,23 @@ class MainActivity : WryActivity() {
                     }
                 Log.i(CHEENHUB_PUSH_LOG_TAG, "Friend-request notification intent accepted")
             }
+            CHEENHUB_OPEN_MENTION_ACTION -> {
+                val target = intent.getStringExtra(CHEENHUB_MENTION_TARGET_EXTRA)
+                    ?.let(CheenHubMentionPayload::normalizeTarget)
+                if (target == null) {
+                    Log.w(CHEENHUB_PUSH_LOG_TAG, "Mention notification intent rejected")
+                    return
+                }
+                CheenHubPushStore.setPendingMention(this, target)
+                runCatching { nativeOnCheenHubMentionNotificationOpened(target) }
+                    .onFailure {
+                        Log.d(
+                            CHEENHUB_PUSH_LOG_TAG,
+                            "Native mention callback is not ready; pending destination was stored",
+                        )
+                    }
+                Log.i(CHEENHUB_PUSH_LOG_TAG, "Mention notification intent accepted")
+            }
         }
     }
 
@@ -704,6 +724,8 @@ class MainActivity : WryActivity() {
 
     private external fun nativeOnCheenHubFriendRequestNotificationOpened()
 
+    private external fun nativeOnCheenHubMentionNotificationOpened(target: String)
+
     private external fun nativeOnCheenHubVoiceAudioFocusChanged(focusChange: Int)
 
     override fun onDestroy() {
@@ -764,7 +786,10 @@ private const val CHEENHUB_OPEN_DIRECT_MESSAGE_ACTION =
     "ru.cheenhub.action.OPEN_DIRECT_MESSAGE"
 private const val CHEENHUB_OPEN_FRIEND_REQUESTS_ACTION =
     "ru.cheenhub.action.OPEN_FRIEND_REQUESTS"
+private const val CHEENHUB_OPEN_MENTION_ACTION =
+    "ru.cheenhub.action.OPEN_MENTION"
 private const val CHEENHUB_CONVERSATION_ID_EXTRA = "cheenhub_conversation_id"
+private const val CHEENHUB_MENTION_TARGET_EXTRA = "cheenhub_mention_target"
 
 class CheenHubApplication : Application() {
     override fun onCreate() {
@@ -847,6 +872,12 @@ class CheenHubFirebaseMessagingService : FirebaseMessagingService() {
             Log.i(CHEENHUB_PUSH_LOG_TAG, "Friend-request notification shown")
             return
         }
+        val mention = CheenHubMentionPayload.parse(remoteMessage.data)
+        if (mention != null) {
+            CheenHubNotifications.showMention(this, mention)
+            Log.i(CHEENHUB_PUSH_LOG_TAG, "Mention notification shown")
+            return
+        }
         Log.w(CHEENHUB_PUSH_LOG_TAG, "Rejected malformed or unsupported FCM data payload")
     }
 
@@ -999,6 +1030,84 @@ private data class CheenHubFriendRequestPayload(
     }
 }
 
+private data class CheenHubMentionPayload(
+    val messageId: String,
+    val serverId: String,
+    val roomId: String,
+    val senderUserId: String,
+    val senderNickname: String,
+    val bodyPreview: String,
+    val createdAtMillis: Long,
+) {
+    /** Адрес сообщения `server_id/room_id/message_id` для перехода из уведомления. */
+    val target: String
+        get() = "$serverId/$roomId/$messageId"
+
+    companion object {
+        private const val SCHEMA_VERSION = "1"
+        private const val KIND = "mention"
+        private const val MAX_NICKNAME_LENGTH = 100
+        private const val MAX_BODY_LENGTH = 500
+
+        fun parse(data: Map<String, String>): CheenHubMentionPayload? {
+            if (data["schema_version"] != SCHEMA_VERSION || data["kind"] != KIND) return null
+            val messageId = data["message_id"].validUuid() ?: return null
+            val serverId = data["server_id"].validUuid() ?: return null
+            val roomId = data["room_id"].validUuid() ?: return null
+            val senderUserId = data["sender_user_id"].validUuid() ?: return null
+            val nickname = data["sender_nickname"].boundedText(MAX_NICKNAME_LENGTH) ?: return null
+            val body = data["body_preview"].boundedText(MAX_BODY_LENGTH) ?: return null
+            val createdAt = parseTimestamp(data["created_at"] ?: return null) ?: return null
+            return CheenHubMentionPayload(
+                messageId,
+                serverId,
+                roomId,
+                senderUserId,
+                nickname,
+                body,
+                createdAt,
+            )
+        }
+
+        fun normalizeTarget(value: String): String? {
+            val parts = value.split('/')
+            if (parts.size != 3) return null
+            val normalized = parts.map { it.validUuid() ?: return null }
+            return normalized.joinToString("/")
+        }
+
+        private fun String?.validUuid(): String? = this?.let { value ->
+            runCatching { UUID.fromString(value).toString() }.getOrNull()
+        }
+
+        private fun String?.boundedText(maxLength: Int): String? = this
+            ?.trim()
+            ?.takeIf {
+                it.isNotEmpty() && it.codePointCount(0, it.length) <= maxLength
+            }
+
+        private fun parseTimestamp(value: String): Long? {
+            value.toLongOrNull()?.takeIf { it > 0 }?.let { return it }
+            val patterns = listOf(
+                "yyyy-MM-dd'T'HH:mm:ss.SSSSSSSSSXXX",
+                "yyyy-MM-dd'T'HH:mm:ss.SSSXXX",
+                "yyyy-MM-dd'T'HH:mm:ssXXX",
+            )
+            for (pattern in patterns) {
+                try {
+                    return SimpleDateFormat(pattern, Locale.US).apply {
+                        isLenient = false
+                        timeZone = TimeZone.getTimeZone("UTC")
+                    }.parse(value)?.time
+                } catch (_: ParseException) {
+                    // Следующий формат проверяется без вывода содержимого payload в лог.
+                }
+            }
+            return null
+        }
+    }
+}
+
 private data class CheenHubConversationHistory(
     val conversationId: String,
     val senderUserId: String,
@@ -1019,6 +1128,7 @@ private object CheenHubPushStore {
     private const val FCM_TOKEN = "fcm_token"
     private const val PENDING_CONVERSATION = "pending_conversation_id"
     private const val PENDING_FRIEND_REQUESTS = "pending_friend_requests"
+    private const val PENDING_MENTION = "pending_mention_target"
     private const val ACTIVE_CONVERSATION = "active_conversation_id"
     private const val APP_FOREGROUND = "app_foreground"
     private const val HISTORY = "direct_message_history"
@@ -1061,6 +1171,17 @@ private object CheenHubPushStore {
         pending
     }
 
+    fun setPendingMention(context: Context, target: String) {
+        preferences(context).edit().putString(PENDING_MENTION, target).apply()
+    }
+
+    fun consumePendingMention(context: Context): String? = synchronized(this) {
+        val preferences = preferences(context)
+        preferences.getString(PENDING_MENTION, null)?.also {
+            preferences.edit().remove(PENDING_MENTION).apply()
+        }
+    }
+
     fun setActiveConversationId(context: Context, conversationId: String?) {
         val editor = preferences(context).edit()
         val normalized = conversationId.validUuidOrNull()
@@ -1192,8 +1313,10 @@ private object CheenHubPushStore {
 private object CheenHubNotifications {
     const val CONVERSATION_NOTIFICATION_ID = 2001
     private const val FRIEND_REQUEST_NOTIFICATION_ID = 2003
+    private const val MENTION_NOTIFICATION_ID = 2004
     private const val DIRECT_MESSAGES_CHANNEL_ID = "cheenhub_direct_messages"
     private const val FRIEND_REQUESTS_CHANNEL_ID = "cheenhub_friend_requests"
+    private const val MENTIONS_CHANNEL_ID = "cheenhub_mentions"
 
     fun ensureChannel(context: Context) {
         if (Build.VERSION.SDK_INT < Build.VERSION_CODES.O) return
@@ -1211,8 +1334,15 @@ private object CheenHubNotifications {
         ).apply {
             description = "Уведомления о новых приглашениях в друзья CheenHub"
         }
+        val mentions = NotificationChannel(
+            MENTIONS_CHANNEL_ID,
+            "Упоминания",
+            NotificationManager.IMPORTANCE_HIGH,
+        ).apply {
+            description = "Уведомления об упоминаниях в комнатах серверов CheenHub"
+        }
         context.getSystemService(NotificationManager::class.java)
-            .createNotificationChannels(listOf(directMessages, friendRequests))
+            .createNotificationChannels(listOf(directMessages, friendRequests, mentions))
     }
 
     fun showConversation(context: Context, history: CheenHubConversationHistory) {
@@ -1309,6 +1439,42 @@ private object CheenHubNotifications {
             notification,
         )
     }
+
+    fun showMention(context: Context, mention: CheenHubMentionPayload) {
+        ensureChannel(context)
+        val intent = Intent(context, MainActivity::class.java)
+            .setAction(CHEENHUB_OPEN_MENTION_ACTION)
+            .putExtra(CHEENHUB_MENTION_TARGET_EXTRA, mention.target)
+            .addFlags(Intent.FLAG_ACTIVITY_CLEAR_TOP or Intent.FLAG_ACTIVITY_SINGLE_TOP)
+        val pendingIntent = PendingIntent.getActivity(
+            context,
+            mention.messageId.hashCode(),
+            intent,
+            PendingIntent.FLAG_UPDATE_CURRENT or PendingIntent.FLAG_IMMUTABLE,
+        )
+        val builder = if (Build.VERSION.SDK_INT >= Build.VERSION_CODES.O) {
+            android.app.Notification.Builder(context, MENTIONS_CHANNEL_ID)
+        } else {
+            @Suppress("DEPRECATION")
+            android.app.Notification.Builder(context)
+        }
+        val notification = builder
+            .setSmallIcon(R.drawable.ic_notification)
+            .setContentTitle("${mention.senderNickname} упомянул вас")
+            .setContentText(mention.bodyPreview)
+            .setCategory(android.app.Notification.CATEGORY_MESSAGE)
+            .setAutoCancel(true)
+            .setOnlyAlertOnce(true)
+            .setShowWhen(true)
+            .setWhen(mention.createdAtMillis)
+            .setContentIntent(pendingIntent)
+            .build()
+        context.getSystemService(NotificationManager::class.java).notify(
+            "cheenhub_mention:${mention.messageId}",
+            MENTION_NOTIFICATION_ID,
+            notification,
+        )
+    }
 }
 
 private object CheenHubCallNotifications {
@@ -7,6 +7,7 @@ const WARNING_INTERVAL_MS = 5_000;
 const LEVEL_INTERVAL_MS = 50;
 
 let active = null;
+let pushToTalkPressed = false;
 
 self.onmessage = (event) => {
   const message = event.data || {};
@@ -14,12 +15,17 @@ self.onmessage = (event) => {
     void start(message).catch((error) => post("error", { message: errorMessage(error) }));
   } else if (message.kind === "set-bitrate") {
     setBitrate(message.bitrateBps);
+  } else if (message.kind === "set-packet-loss") {
+    setPacketLoss(message.packetLossPercent);
+  } else if (message.kind === "set-push-to-talk") {
+    setPushToTalk(message.pressed);
   } else if (message.kind === "stop") {
     void stopActive();
   }
 };
 
 async function start(config) {
+  pushToTalkPressed = config.pushToTalkPressed === true;
   await stopActive();
   validateStartConfig(config);
   post("status", { message: "starting microphone uplink worker" });
@@ -27,7 +33,7 @@ async function start(config) {
   try {
     const wasm = await import(config.wasmBindgenUrl);
     await wasm.default(config.wasmUrl);
-    if (wasm.microphone_worker_abi_version() < 3) {
+    if (wasm.microphone_worker_abi_version() < 5) {
       throw new Error("microphone worker wasm ABI is too old");
     }
 
@@ -38,9 +44,18 @@ async function start(config) {
       config.vadThreshold,
       config.vadActivationDelayUs,
       config.vadReleaseDelayUs,
+      config.pushToTalkReleaseDelayUs,
       config.inputGain,
       config.roomId,
     );
+    const processing = config.processing || {};
+    processor.set_processing(
+      processing.highPass === true,
+      processing.echoSuppression === true,
+      processing.noiseSuppression === true,
+      processing.noiseGate === true,
+      processing.autoGain === true,
+    );
     transport = await connectRealtime(config, wasm);
     const encoder = createEncoder(config, processor, transport);
     const startedWallMs = Date.now();
@@ -51,6 +66,7 @@ async function start(config) {
       transport,
       sampleRateHz: config.sampleRateHz,
       bitrateBps: config.bitrateBps,
+      packetLossPercent: packetLossPercent(config.packetLossPercent),
       startedWallMs,
       lastChunkWallMs: startedWallMs,
       lastTimestampUs: null,
@@ -62,6 +78,7 @@ async function start(config) {
       lastWarningMs: 0,
       closed: false,
     };
+    processor.set_push_to_talk_pressed(pushToTalkPressed);
     active.port.onmessage = (event) => handleWorkletMessage(event.data, active);
     active.port.start();
     post("ready", { transport: transport.kind });
@@ -99,7 +116,32 @@ function setBitrate(value) {
     return;
   }
   current.bitrateBps = bitrateBps;
-  current.encoder.configure(encoderConfig(current.sampleRateHz, 1, bitrateBps));
+  current.encoder.configure(
+    encoderConfig(current.sampleRateHz, 1, bitrateBps, current.packetLossPercent),
+  );
+}
+
+function setPacketLoss(value) {
+  const current = active;
+  const lossPercent = packetLossPercent(value);
+  if (!current || lossPercent === current.packetLossPercent) {
+    return;
+  }
+  current.packetLossPercent = lossPercent;
+  current.encoder.configure(
+    encoderConfig(current.sampleRateHz, 1, current.bitrateBps, lossPercent),
+  );
+}
+
+function setPushToTalk(pressed) {
+  pushToTalkPressed = pressed === true;
+  if (active && !active.closed) {
+    active.processor.set_push_to_talk_pressed(pushToTalkPressed);
+  }
+}
+
+function packetLossPercent(value) {
+  return Math.min(100, Math.max(0, Math.round(Number(value) || 0)));
 }
 
 function createEncoder(config, processor, transport) {
@@ -107,11 +149,18 @@ function createEncoder(config, processor, transport) {
     output: (chunk) => handleEncodedChunk(chunk, processor, transport),
     error: (error) => failActive(`microphone worker encoder failed: ${errorMessage(error)}`),
   });
-  encoder.configure(encoderConfig(config.sampleRateHz, config.channels, config.bitrateBps));
+  encoder.configure(
+    encoderConfig(
+      config.sampleRateHz,
+      config.channels,
+      config.bitrateBps,
+      packetLossPercent(config.packetLossPercent),
+    ),
+  );
   return encoder;
 }
 
-function encoderConfig(sampleRateHz, channels, bitrateBps) {
+function encoderConfig(sampleRateHz, channels, bitrateBps, lossPercent) {
   return {
     codec: "opus",
     sampleRate: sampleRateHz,
@@ -122,6 +171,7 @@ function encoderConfig(sampleRateHz, channels, bitrateBps) {
       usedtx: true,
       application: "voip",
       frameDuration: 20_000,
+      packetlossperc: lossPercent,
     },
   };
 }
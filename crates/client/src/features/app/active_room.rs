@@ -13,6 +13,7 @@ use dioxus::prelude::*;
 pub(crate) struct ActiveRoomContext {
     room_id: Signal<Option<String>>,
     conversation_id: Signal<Option<String>>,
+    message_focus: Signal<Option<(String, String)>>,
 }
 
 impl ActiveRoomContext {
@@ -20,10 +21,12 @@ impl ActiveRoomContext {
     pub(crate) fn new(
         room_id: Signal<Option<String>>,
         conversation_id: Signal<Option<String>>,
+        message_focus: Signal<Option<(String, String)>>,
     ) -> Self {
         Self {
             room_id,
             conversation_id,
+            message_focus,
         }
     }
 
@@ -48,4 +51,25 @@ impl ActiveRoomContext {
         let mut current = self.conversation_id;
         current.set(conversation_id);
     }
+
+    /// Запоминает сообщение, к которому текстовый чат комнаты перейдет после открытия.
+    #[cfg_attr(
+        not(any(target_arch = "wasm32", target_os = "android")),
+        allow(dead_code)
+    )]
+    pub(crate) fn request_message_focus(&self, room_id: String, message_id: String) {
+        let mut current = self.message_focus;
+        current.set(Some((room_id, message_id)));
+    }
+
+    /// Возвращает и удаляет запрошенный переход к сообщению этой комнаты.
+    pub(crate) fn take_message_focus(&self, room_id: &str) -> Option<String> {
+        let mut current = self.message_focus;
+        let (focus_room_id, message_id) = current()?;
+        if focus_room_id != room_id {
+            return None;
+        }
+        current.set(None);
+        Some(message_id)
+    }
 }
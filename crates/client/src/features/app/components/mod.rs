@@ -16,6 +16,7 @@ pub(crate) mod room_editor_modal;
 pub(crate) mod room_header;
 pub(crate) mod room_instance;
 mod room_list_item;
+mod room_unread;
 pub(crate) mod server_avatar;
 pub(crate) mod server_context_menu;
 pub(crate) mod server_instance;
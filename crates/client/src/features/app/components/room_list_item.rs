@@ -30,11 +30,13 @@ pub(super) fn RoomListItem(
     let hidden_voice_participant_count = voice_participants
         .len()
         .saturating_sub(visible_voice_participants.len());
+    let has_unread = room.unread_count > 0 && !is_active;
 
     rsx! {
         div {
             "data-active": if is_active { "true" } else { "false" },
-            class: "group relative flex w-full items-center justify-between rounded-lg border border-transparent px-2.5 py-2 text-left text-zinc-400 transition-[background,border-color,color,transform,opacity] duration-150 hover:border-zinc-800 hover:bg-zinc-900 hover:text-zinc-100 data-[active=true]:border-accent/25 data-[active=true]:bg-accent/10 data-[active=true]:text-zinc-100",
+            "data-unread": if has_unread { "true" } else { "false" },
+            class: "group relative flex w-full items-center justify-between rounded-lg border border-transparent px-2.5 py-2 text-left text-zinc-400 transition-[background,border-color,color,transform,opacity] duration-150 hover:border-zinc-800 hover:bg-zinc-900 hover:text-zinc-100 data-[unread=true]:text-zinc-100 data-[active=true]:border-accent/25 data-[active=true]:bg-accent/10 data-[active=true]:text-zinc-100",
             button {
                 r#type: "button",
                 class: "flex min-w-0 flex-1 items-center gap-2 text-left",
@@ -43,6 +45,18 @@ pub(super) fn RoomListItem(
                 span { class: room_icon_class(room.kind), "{room_icon(room.kind)}" }
                 span { class: room_name_class, "{room.name}" }
             }
+            if room.mention_count > 0 {
+                span {
+                    class: "ml-2 flex h-4 min-w-4 shrink-0 items-center justify-center rounded-full bg-red-500 px-1 text-[10px] font-bold leading-none text-white",
+                    "aria-label": "Непрочитанных упоминаний: {room.mention_count}",
+                    "{unread_badge_label(room.mention_count)}"
+                }
+            } else if has_unread {
+                span {
+                    class: "ml-2 h-2 w-2 shrink-0 rounded-full bg-zinc-200",
+                    "aria-label": "Непрочитанных сообщений: {room.unread_count}",
+                }
+            }
             if show_voice_participants {
                 div { class: "group/voice-tooltip relative ml-2 flex shrink-0 items-center",
                     div { class: "flex items-center -space-x-1",
@@ -125,3 +139,11 @@ fn room_actions_class(compact_when_settings_active: bool) -> &'static str {
         "ml-2 flex shrink-0 items-center gap-1 opacity-0 transition group-hover:opacity-100 group-focus-within:opacity-100"
     }
 }
+
+fn unread_badge_label(count: u32) -> String {
+    if count > 99 {
+        "99+".to_owned()
+    } else {
+        count.to_string()
+    }
+}
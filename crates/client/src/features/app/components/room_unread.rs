@@ -0,0 +1,68 @@
+//! Живые счетчики непрочитанного для списка комнат сервера.
+
+use cheenhub_contracts::rest::ServerRoomSummary;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::features::realtime::RealtimeHandle;
+use crate::features::text_chat::realtime::{TextChatEvent, subscribe_text_chat};
+
+/// Поддерживает счетчики комнат в актуальном состоянии по событиям текстового чата.
+///
+/// Новые сообщения других участников увеличивают счетчики, а отметка прочтения
+/// с любой сессии пользователя заменяет их значениями сервера.
+pub(super) fn use_room_unread_counts(
+    mut rooms: Signal<Option<Vec<ServerRoomSummary>>>,
+    current_user_id: String,
+) {
+    let realtime = use_context::<RealtimeHandle>();
+    use_hook(move || {
+        spawn(async move {
+            let mut receiver = subscribe_text_chat(&realtime);
+            while let Some(event) = receiver.next().await {
+                let mut next_rooms = rooms.peek().clone();
+                if let Some(next_rooms) = next_rooms.as_mut()
+                    && apply_unread_event(next_rooms, event, &current_user_id)
+                {
+                    rooms.set(Some(next_rooms.clone()));
+                }
+            }
+        });
+    });
+}
+
+/// Применяет событие к счетчикам и возвращает, изменилась ли какая-то комната.
+fn apply_unread_event(
+    rooms: &mut [ServerRoomSummary],
+    event: TextChatEvent,
+    current_user_id: &str,
+) -> bool {
+    match event {
+        TextChatEvent::Created(message) if message.author_user_id != current_user_id => {
+            update_room(rooms, &message.room_id, |room| {
+                room.unread_count = room.unread_count.saturating_add(1);
+            })
+        }
+        TextChatEvent::Mentioned(message) => update_room(rooms, &message.room_id, |room| {
+            room.mention_count = room.mention_count.saturating_add(1);
+        }),
+        TextChatEvent::ReadUpdated(payload) => update_room(rooms, &payload.room_id, |room| {
+            room.unread_count = payload.unread_count;
+            room.mention_count = payload.mention_count;
+        }),
+        _ => false,
+    }
+}
+
+fn update_room(
+    rooms: &mut [ServerRoomSummary],
+    room_id: &str,
+    update: impl FnOnce(&mut ServerRoomSummary),
+) -> bool {
+    let Some(room) = rooms.iter_mut().find(|room| room.id == room_id) else {
+        return false;
+    };
+    update(room);
+
+    true
+}
@@ -7,11 +7,10 @@ use crate::Route;
 use crate::features::app::active_room::ActiveRoomContext;
 use crate::features::app::api;
 use crate::features::app::current_user::CurrentUserContext;
+use crate::features::app::server_permissions::ServerPermissionsContext;
 use crate::features::server_settings::ServerSettingsScope;
 use crate::features::voice_chat::VoiceConnectionHandle;
 
-use crate::features::app::server_permissions::ServerPermissionsContext;
-
 use super::app_shell::{AppModal, ServerShellState, room_kind_attr};
 use super::app_sidebar_footer::AppSidebarFooter;
 use super::avatar::use_avatar_seed;
@@ -46,6 +45,7 @@ pub(crate) fn ServerRoomsScope(
     let active_room_ctx = use_context::<ActiveRoomContext>();
     use_avatar_seed(current_user.id.clone());
     let mut rooms = use_signal(|| None::<Vec<ServerRoomSummary>>);
+    super::room_unread::use_room_unread_counts(rooms, current_user.id.clone());
     let mut active_room_id = use_signal(|| None::<String>);
     let mut room_action_status = use_signal(String::new);
     let mut room_modal = use_signal(|| None::<RoomModal>);
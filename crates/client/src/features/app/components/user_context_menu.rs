@@ -8,11 +8,16 @@ pub(crate) fn UserContextMenu(
     name: String,
     is_self: bool,
     can_kick_voice: bool,
+    #[props(default)] can_moderate_voice: bool,
+    #[props(default)] server_muted: bool,
+    #[props(default)] server_deafened: bool,
     volume: u32,
     x: f64,
     y: f64,
     on_volume_change: EventHandler<u32>,
     on_kick_voice: EventHandler<()>,
+    #[props(default)] on_server_mute: EventHandler<bool>,
+    #[props(default)] on_server_deafen: EventHandler<bool>,
 ) -> Element {
     let top = y + 8.0;
     let pos_style = format!(
@@ -104,6 +109,23 @@ pub(crate) fn UserContextMenu(
                     }
                 }
 
+                if can_moderate_voice {
+                    div { class: "mx-1 my-1 border-t border-zinc-800/70" }
+
+                    button {
+                        r#type: "button",
+                        class: "flex w-full items-center justify-between rounded-[10px] px-2.5 py-2 text-left text-[13px] text-zinc-300 transition-[background,color] duration-100 hover:bg-zinc-900 hover:text-zinc-100",
+                        onclick: move |_| on_server_mute.call(!server_muted),
+                        span { if server_muted { "Вернуть микрофон" } else { "Отключить микрофон" } }
+                    }
+                    button {
+                        r#type: "button",
+                        class: "flex w-full items-center justify-between rounded-[10px] px-2.5 py-2 text-left text-[13px] text-zinc-300 transition-[background,color] duration-100 hover:bg-zinc-900 hover:text-zinc-100",
+                        onclick: move |_| on_server_deafen.call(!server_deafened),
+                        span { if server_deafened { "Вернуть звук" } else { "Отключить звук" } }
+                    }
+                }
+
                 if can_kick_voice {
                     div { class: "mx-1 my-1 border-t border-zinc-800/70" }
 
@@ -38,7 +38,9 @@ pub(crate) fn AppPage() -> Element {
     // Контекст активной комнаты для фильтрации уведомлений.
     let active_room_id = use_signal(|| None::<String>);
     let active_conversation_id = use_signal(|| None::<String>);
-    let active_room_context = ActiveRoomContext::new(active_room_id, active_conversation_id);
+    let message_focus = use_signal(|| None::<(String, String)>);
+    let active_room_context =
+        ActiveRoomContext::new(active_room_id, active_conversation_id, message_focus);
     use_context_provider(move || active_room_context);
 
     use_effect(move || {
@@ -10,8 +10,14 @@ pub(crate) struct ServerPermissionsContext {
     pub(crate) can_create_invite_links: bool,
     /// Может ли пользователь исключать участников из голосовых комнат.
     pub(crate) can_kick_voice: bool,
+    /// Может ли пользователь отключать микрофон и звук участникам голосовых комнат.
+    pub(crate) can_moderate_voice: bool,
     /// Может ли пользователь удалять чужие сообщения.
     pub(crate) can_delete_messages: bool,
+    /// Может ли пользователь закреплять и откреплять сообщения.
+    pub(crate) can_pin_messages: bool,
+    /// Может ли пользователь менять настройки голоса и видео сервера.
+    pub(crate) can_manage_media_settings: bool,
 }
 
 impl ServerPermissionsContext {
@@ -23,7 +29,13 @@ impl ServerPermissionsContext {
                 ServerRolePermission::CreateInviteLinks,
             ),
             can_kick_voice: has_permission(server, ServerRolePermission::KickVoiceMembers),
+            can_moderate_voice: has_permission(server, ServerRolePermission::ModerateVoiceMembers),
             can_delete_messages: has_permission(server, ServerRolePermission::DeleteMessages),
+            can_pin_messages: has_permission(server, ServerRolePermission::PinMessages),
+            can_manage_media_settings: has_permission(
+                server,
+                ServerRolePermission::ManageMediaSettings,
+            ),
         }
     }
 }
@@ -25,6 +25,21 @@ pub(crate) struct VoiceFrame {
     pub(crate) bytes: Vec<u8>,
 }
 
+/// Статистика приема голосовых кадров одного отправителя с прошлого снимка.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct VoiceReceiveStats {
+    /// Frames that reached the jitter buffer, including late ones.
+    pub(crate) received_frames: u64,
+    /// Sequence positions skipped because the frame never arrived in time.
+    pub(crate) lost_frames: u64,
+    /// Frames that arrived after their playout position had passed.
+    pub(crate) late_frames: u64,
+    /// Smoothed interarrival jitter estimate in microseconds.
+    pub(crate) jitter_us: u32,
+    /// Playout delay the jitter buffer currently holds frames for, in microseconds.
+    pub(crate) playout_delay_us: u32,
+}
+
 /// Короткий системный звук уведомления.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum NotificationSound {
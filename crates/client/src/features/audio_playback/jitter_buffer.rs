@@ -2,10 +2,20 @@
 
 use std::collections::BTreeMap;
 
-use crate::features::audio_playback::backend::VoiceFrame;
+use crate::features::audio_playback::backend::{VoiceFrame, VoiceReceiveStats};
 
 const MAX_PENDING_FRAMES: usize = 80;
 const SEQUENCE_RESET_BACKWARD_THRESHOLD: u64 = 64;
+/// RFC 3550 smoothing divisor for the interarrival jitter estimate.
+const JITTER_SMOOTHING_DIVISOR: f64 = 16.0;
+/// Longest gap, in frames, filled with concealment before playout jumps ahead silently.
+const MAX_CONCEALED_FRAMES: u64 = 5;
+/// How many jitter estimates of delay the adaptive target keeps in front of playout.
+const JITTER_TARGET_MULTIPLIER: f64 = 4.0;
+/// Largest encoded frame treated as silence that may be dropped to shrink the delay.
+const SILENT_FRAME_MAX_BYTES: usize = 8;
+/// Arrival pause after which the next frame starts a new talk spurt.
+const TALK_SPURT_GAP_US: u64 = 300_000;
 
 /// Per-sender encoded voice jitter buffer.
 #[derive(Default)]
@@ -13,6 +23,31 @@ pub(super) struct JitterBuffer {
     pending: BTreeMap<u64, QueuedVoiceFrame>,
     next_sequence: Option<u64>,
     playout_started: bool,
+    receive_stats: VoiceReceiveStats,
+    last_transit_us: Option<i64>,
+    jitter_us: f64,
+    playout_delay_us: Option<u64>,
+    last_arrival_us: Option<u64>,
+}
+
+/// Range the adaptive playout delay is allowed to move within.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct PlayoutDelayBounds {
+    /// Shortest delay the buffer converges to on a stable link, in microseconds.
+    pub(super) min_us: u64,
+    /// Longest delay the buffer may grow to, in microseconds.
+    pub(super) max_us: u64,
+}
+
+impl PlayoutDelayBounds {
+    /// Adapts between the manual floor and ceiling settings; the floor never exceeds the ceiling.
+    pub(super) fn between(floor_us: u32, ceiling_us: u32) -> Self {
+        let max_us = u64::from(ceiling_us);
+        Self {
+            min_us: u64::from(floor_us).min(max_us),
+            max_us,
+        }
+    }
 }
 
 /// Result of pushing a frame into a jitter buffer.
@@ -39,17 +74,54 @@ pub(super) enum JitterBufferPush {
     },
 }
 
+/// One playout position released by the jitter buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum VoicePlayout {
+    /// A received frame to decode normally.
+    Frame(VoiceFrame),
+    /// A lost frame the decoder should reconstruct.
+    Concealed(ConcealedVoiceFrame),
+}
+
+/// Position of a lost frame inside a skipped sequence gap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct ConcealedVoiceFrame {
+    /// Sequence of the lost frame.
+    pub(super) sequence: u64,
+    /// Estimated timestamp of the lost frame in microseconds.
+    pub(super) timestamp_us: u64,
+    /// Duration the decoder should synthesize in microseconds.
+    pub(super) duration_us: u32,
+    /// First frame received after the gap.
+    pub(super) next_frame: VoiceFrame,
+}
+
+impl ConcealedVoiceFrame {
+    /// Returns the frame whose in-band FEC data describes this lost frame.
+    ///
+    /// Opus only embeds the previous frame, so earlier positions of a longer
+    /// gap fall back to packet-loss concealment.
+    #[allow(dead_code)]
+    pub(super) fn fec_source(&self) -> Option<&VoiceFrame> {
+        (self.next_frame.sequence == self.sequence.saturating_add(1)).then_some(&self.next_frame)
+    }
+}
+
 /// Frames and diagnostics released by one buffer drain.
 #[derive(Default)]
 pub(super) struct JitterBufferDrain {
-    /// Encoded voice frames ready to decode in playout order.
-    pub(super) ready_frames: Vec<VoiceFrame>,
+    /// Received and concealed frames ready to decode in playout order.
+    pub(super) playout: Vec<VoicePlayout>,
     /// Микросекунды до следующей полезной попытки опустошить буфер.
     pub(super) next_wake_us: Option<u32>,
     /// Number of missing sequence positions skipped to keep audio moving.
     pub(super) skipped_sequences: u64,
     /// Number of stale queued frames discarded during this drain.
     pub(super) dropped_stale_frames: usize,
+    /// Number of silent frames dropped to shrink the playout delay.
+    pub(super) dropped_silent_frames: usize,
+    /// Playout delay applied by this drain, in microseconds.
+    pub(super) playout_delay_us: u64,
 }
 
 struct QueuedVoiceFrame {
@@ -60,6 +132,77 @@ struct QueuedVoiceFrame {
 impl JitterBuffer {
     /// Pushes one inbound frame into the reorder queue.
     pub(super) fn push(&mut self, frame: VoiceFrame, now_us: u64) -> JitterBufferPush {
+        let timestamp_us = frame.timestamp_us;
+        self.observe_arrival(now_us);
+        let outcome = self.enqueue(frame, now_us);
+        if outcome == JitterBufferPush::DroppedDuplicate {
+            return outcome;
+        }
+        self.receive_stats.received_frames = self.receive_stats.received_frames.saturating_add(1);
+        match outcome {
+            JitterBufferPush::DroppedStale { .. } => {
+                self.receive_stats.late_frames = self.receive_stats.late_frames.saturating_add(1);
+            }
+            JitterBufferPush::Reset { .. } => {
+                self.last_transit_us = None;
+                self.playout_delay_us = None;
+                self.observe_transit(timestamp_us, now_us);
+            }
+            _ => self.observe_transit(timestamp_us, now_us),
+        }
+
+        outcome
+    }
+
+    /// Returns receive statistics gathered since the previous call and restarts the counters.
+    pub(super) fn take_receive_stats(&mut self) -> VoiceReceiveStats {
+        let stats = VoiceReceiveStats {
+            jitter_us: self.jitter_us.round().min(f64::from(u32::MAX)) as u32,
+            playout_delay_us: self
+                .playout_delay_us
+                .unwrap_or_default()
+                .min(u64::from(u32::MAX)) as u32,
+            ..self.receive_stats
+        };
+        self.receive_stats = VoiceReceiveStats::default();
+        stats
+    }
+
+    /// Lets the delay jump straight to the jitter-based target at a talk-spurt boundary,
+    /// where a changed delay cannot be heard.
+    fn observe_arrival(&mut self, now_us: u64) {
+        let previous_arrival_us = self.last_arrival_us.replace(now_us);
+        if self.pending.is_empty()
+            && previous_arrival_us
+                .is_some_and(|arrival_us| now_us.saturating_sub(arrival_us) >= TALK_SPURT_GAP_US)
+        {
+            self.playout_delay_us = None;
+        }
+    }
+
+    /// Grows the playout delay at once when jitter rises and caps it by the ceiling.
+    ///
+    /// A lower target is approached only by dropping silent frames during playout.
+    fn adapt_playout_delay(&mut self, bounds: PlayoutDelayBounds) -> (u64, u64) {
+        let jitter_target_us = (self.jitter_us * JITTER_TARGET_MULTIPLIER).round() as u64;
+        let target_us = jitter_target_us.clamp(bounds.min_us, bounds.max_us);
+        let playout_delay_us = match self.playout_delay_us {
+            Some(current_us) if current_us > target_us => current_us.min(bounds.max_us),
+            _ => target_us,
+        };
+        self.playout_delay_us = Some(playout_delay_us);
+        (playout_delay_us, target_us)
+    }
+
+    fn observe_transit(&mut self, timestamp_us: u64, now_us: u64) {
+        let transit_us = now_us as i64 - timestamp_us as i64;
+        if let Some(last_transit_us) = self.last_transit_us.replace(transit_us) {
+            let delta_us = transit_us.abs_diff(last_transit_us) as f64;
+            self.jitter_us += (delta_us - self.jitter_us) / JITTER_SMOOTHING_DIVISOR;
+        }
+    }
+
+    fn enqueue(&mut self, frame: VoiceFrame, now_us: u64) -> JitterBufferPush {
         let sequence = frame.sequence;
         if let Some(expected_sequence) = self.next_sequence {
             if sequence < expected_sequence {
@@ -109,9 +252,10 @@ impl JitterBuffer {
     pub(super) fn drain_ready(
         &mut self,
         now_us: u64,
-        target_playout_delay_us: u64,
+        bounds: PlayoutDelayBounds,
     ) -> JitterBufferDrain {
         let mut drain = JitterBufferDrain::default();
+        let (mut target_playout_delay_us, converge_to_us) = self.adapt_playout_delay(bounds);
 
         while let Some(expected_sequence) = self.next_sequence {
             self.drop_queued_stale(expected_sequence, &mut drain);
@@ -127,9 +271,15 @@ impl JitterBuffer {
                     .pending
                     .remove(&expected_sequence)
                     .expect("expected frame exists in jitter buffer");
-                drain.ready_frames.push(queued.frame);
-                self.playout_started = true;
                 self.next_sequence = Some(expected_sequence.saturating_add(1));
+                let excess_us = target_playout_delay_us.saturating_sub(converge_to_us);
+                if self.playout_started && can_drop_silent(&queued.frame, excess_us) {
+                    target_playout_delay_us -= u64::from(queued.frame.duration_us);
+                    drain.dropped_silent_frames = drain.dropped_silent_frames.saturating_add(1);
+                    continue;
+                }
+                drain.playout.push(VoicePlayout::Frame(queued.frame));
+                self.playout_started = true;
                 continue;
             }
 
@@ -145,10 +295,29 @@ impl JitterBuffer {
             drain.skipped_sequences = drain
                 .skipped_sequences
                 .saturating_add(next_available_sequence.saturating_sub(expected_sequence));
+            if self.playout_started {
+                conceal_gap(
+                    expected_sequence,
+                    next_available_sequence,
+                    &queued.frame,
+                    &mut drain,
+                );
+            }
             self.playout_started = true;
             self.next_sequence = Some(next_available_sequence);
         }
 
+        self.playout_delay_us = Some(target_playout_delay_us);
+        drain.playout_delay_us = target_playout_delay_us;
+
+        self.receive_stats.lost_frames = self
+            .receive_stats
+            .lost_frames
+            .saturating_add(drain.skipped_sequences);
+        self.receive_stats.late_frames = self
+            .receive_stats
+            .late_frames
+            .saturating_add(drain.dropped_stale_frames as u64);
         drain
     }
 
@@ -182,6 +351,35 @@ impl JitterBuffer {
     }
 }
 
+/// Queues concealment for the tail of a gap, right before the next received frame.
+fn conceal_gap(
+    expected_sequence: u64,
+    next_available_sequence: u64,
+    next_frame: &VoiceFrame,
+    drain: &mut JitterBufferDrain,
+) {
+    let missing = next_available_sequence.saturating_sub(expected_sequence);
+    let concealed = missing.min(MAX_CONCEALED_FRAMES);
+    for frames_before_next in (1..=concealed).rev() {
+        let offset_us = frames_before_next.saturating_mul(u64::from(next_frame.duration_us));
+        drain
+            .playout
+            .push(VoicePlayout::Concealed(ConcealedVoiceFrame {
+                sequence: next_available_sequence - frames_before_next,
+                timestamp_us: next_frame.timestamp_us.saturating_sub(offset_us),
+                duration_us: next_frame.duration_us,
+                next_frame: next_frame.clone(),
+            }));
+    }
+}
+
+/// Returns whether dropping this frame shortens playout without cutting speech.
+fn can_drop_silent(frame: &VoiceFrame, excess_delay_us: u64) -> bool {
+    frame.bytes.len() <= SILENT_FRAME_MAX_BYTES
+        && frame.duration_us > 0
+        && excess_delay_us >= u64::from(frame.duration_us)
+}
+
 fn delay_until_us(deadline_us: u64, now_us: u64) -> u32 {
     deadline_us
         .saturating_sub(now_us)
@@ -189,134 +387,5 @@ fn delay_until_us(deadline_us: u64, now_us: u64) -> u32 {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::features::audio_playback::PlaybackCodec;
-
-    const TEST_TARGET_PLAYOUT_DELAY_US: u64 = 120_000;
-
-    #[test]
-    fn holds_frame_until_target_delay() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(0), 1_000_000);
-
-        let early = buffer.drain_ready(1_119_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert!(early.ready_frames.is_empty());
-        assert_eq!(early.next_wake_us, Some(1_000));
-
-        let ready = buffer.drain_ready(1_120_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert_eq!(sequences(&ready.ready_frames), vec![0]);
-        assert_eq!(ready.next_wake_us, None);
-        assert!(buffer.is_empty());
-    }
-
-    #[test]
-    fn reorders_out_of_order_frames() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(1), 1_000_000);
-        buffer.push(frame(0), 1_020_000);
-
-        let first_deadline = buffer.drain_ready(1_120_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert!(first_deadline.ready_frames.is_empty());
-        assert_eq!(first_deadline.next_wake_us, Some(20_000));
-
-        let ready = buffer.drain_ready(1_140_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert_eq!(sequences(&ready.ready_frames), vec![0, 1]);
-    }
-
-    #[test]
-    fn skips_missing_sequences_after_playout_delay() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(0), 1_000_000);
-        buffer.push(frame(2), 1_010_000);
-
-        let first = buffer.drain_ready(1_120_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert_eq!(sequences(&first.ready_frames), vec![0]);
-        assert_eq!(first.next_wake_us, Some(10_000));
-        assert_eq!(first.skipped_sequences, 0);
-
-        let second = buffer.drain_ready(1_130_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert_eq!(sequences(&second.ready_frames), vec![2]);
-        assert_eq!(second.skipped_sequences, 1);
-    }
-
-    #[test]
-    fn drops_stale_frames() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(0), 1_000_000);
-        buffer.drain_ready(1_120_000, TEST_TARGET_PLAYOUT_DELAY_US);
-
-        assert_eq!(
-            buffer.push(frame(0), 1_130_000),
-            JitterBufferPush::DroppedStale {
-                expected_sequence: 1
-            }
-        );
-    }
-
-    #[test]
-    fn resets_after_sender_sequence_restart() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(100), 1_000_000);
-        buffer.drain_ready(1_120_000, TEST_TARGET_PLAYOUT_DELAY_US);
-
-        assert_eq!(
-            buffer.push(frame(0), 2_000_000),
-            JitterBufferPush::Reset {
-                previous_expected_sequence: 101,
-                pending_frames: 1
-            }
-        );
-
-        let ready = buffer.drain_ready(2_120_000, TEST_TARGET_PLAYOUT_DELAY_US);
-        assert_eq!(sequences(&ready.ready_frames), vec![0]);
-    }
-
-    #[test]
-    fn uses_configured_target_delay() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(0), 1_000_000);
-
-        let early = buffer.drain_ready(1_159_000, 160_000);
-        assert!(early.ready_frames.is_empty());
-        assert_eq!(early.next_wake_us, Some(1_000));
-
-        let ready = buffer.drain_ready(1_160_000, 160_000);
-        assert_eq!(sequences(&ready.ready_frames), vec![0]);
-    }
-
-    #[test]
-    fn preserves_half_millisecond_target_delay() {
-        let mut buffer = JitterBuffer::default();
-
-        buffer.push(frame(0), 1_000_000);
-
-        let early = buffer.drain_ready(1_000_499, 500);
-        assert!(early.ready_frames.is_empty());
-        assert_eq!(early.next_wake_us, Some(1));
-
-        let ready = buffer.drain_ready(1_000_500, 500);
-        assert_eq!(sequences(&ready.ready_frames), vec![0]);
-    }
-
-    fn frame(sequence: u64) -> VoiceFrame {
-        VoiceFrame {
-            sender_user_id: "sender".to_owned(),
-            sequence,
-            timestamp_us: sequence.saturating_mul(10_000),
-            duration_us: 10_000,
-            codec: PlaybackCodec::Opus,
-            bytes: vec![1, 2, 3],
-        }
-    }
-
-    fn sequences(frames: &[VoiceFrame]) -> Vec<u64> {
-        frames.iter().map(|frame| frame.sequence).collect()
-    }
-}
+#[path = "jitter_buffer/tests.rs"]
+mod tests;
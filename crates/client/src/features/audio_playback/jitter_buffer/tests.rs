@@ -0,0 +1,323 @@
+use super::*;
+use crate::features::audio_playback::PlaybackCodec;
+
+const TEST_TARGET_PLAYOUT_DELAY_US: u64 = 120_000;
+
+#[test]
+fn holds_frame_until_target_delay() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+
+    let early = buffer.drain_ready(1_119_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert!(early.playout.is_empty());
+    assert_eq!(early.next_wake_us, Some(1_000));
+
+    let ready = buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert_eq!(sequences(&ready.playout), vec![0]);
+    assert_eq!(ready.next_wake_us, None);
+    assert!(buffer.is_empty());
+}
+
+#[test]
+fn reorders_out_of_order_frames() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(1), 1_000_000);
+    buffer.push(frame(0), 1_020_000);
+
+    let first_deadline = buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert!(first_deadline.playout.is_empty());
+    assert_eq!(first_deadline.next_wake_us, Some(20_000));
+
+    let ready = buffer.drain_ready(1_140_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert_eq!(sequences(&ready.playout), vec![0, 1]);
+}
+
+#[test]
+fn skips_missing_sequences_after_playout_delay() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.push(frame(2), 1_010_000);
+
+    let first = buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert_eq!(sequences(&first.playout), vec![0]);
+    assert_eq!(first.next_wake_us, Some(10_000));
+    assert_eq!(first.skipped_sequences, 0);
+
+    let second = buffer.drain_ready(1_130_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert_eq!(sequences(&second.playout), vec![2]);
+    assert_eq!(second.skipped_sequences, 1);
+}
+
+#[test]
+fn drops_stale_frames() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+
+    assert_eq!(
+        buffer.push(frame(0), 1_130_000),
+        JitterBufferPush::DroppedStale {
+            expected_sequence: 1
+        }
+    );
+}
+
+#[test]
+fn resets_after_sender_sequence_restart() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(100), 1_000_000);
+    buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+
+    assert_eq!(
+        buffer.push(frame(0), 2_000_000),
+        JitterBufferPush::Reset {
+            previous_expected_sequence: 101,
+            pending_frames: 1
+        }
+    );
+
+    let ready = buffer.drain_ready(2_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    assert_eq!(sequences(&ready.playout), vec![0]);
+}
+
+#[test]
+fn uses_configured_target_delay() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+
+    let early = buffer.drain_ready(1_159_000, fixed(160_000));
+    assert!(early.playout.is_empty());
+    assert_eq!(early.next_wake_us, Some(1_000));
+
+    let ready = buffer.drain_ready(1_160_000, fixed(160_000));
+    assert_eq!(sequences(&ready.playout), vec![0]);
+}
+
+#[test]
+fn preserves_half_millisecond_target_delay() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+
+    let early = buffer.drain_ready(1_000_499, fixed(500));
+    assert!(early.playout.is_empty());
+    assert_eq!(early.next_wake_us, Some(1));
+
+    let ready = buffer.drain_ready(1_000_500, fixed(500));
+    assert_eq!(sequences(&ready.playout), vec![0]);
+}
+
+#[test]
+fn reports_loss_late_frames_and_jitter_since_last_snapshot() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.push(frame(2), 1_030_000);
+    buffer.drain_ready(1_200_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    buffer.push(frame(1), 1_210_000);
+
+    let stats = buffer.take_receive_stats();
+    assert_eq!(stats.received_frames, 3);
+    assert_eq!(stats.lost_frames, 1);
+    assert_eq!(stats.late_frames, 1);
+    assert_eq!(stats.jitter_us, 625);
+
+    let idle = buffer.take_receive_stats();
+    assert_eq!((idle.received_frames, idle.lost_frames), (0, 0));
+    assert_eq!(idle.jitter_us, 625);
+}
+
+#[test]
+fn conceals_single_loss_with_next_frame_fec() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    buffer.push(frame(2), 1_040_000);
+
+    let drain = buffer.drain_ready(1_160_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+
+    assert_eq!(
+        drain.playout,
+        vec![
+            VoicePlayout::Concealed(ConcealedVoiceFrame {
+                sequence: 1,
+                timestamp_us: 10_000,
+                duration_us: 10_000,
+                next_frame: frame(2),
+            }),
+            VoicePlayout::Frame(frame(2)),
+        ]
+    );
+    assert!(matches!(
+        &drain.playout[0],
+        VoicePlayout::Concealed(concealed) if concealed.fec_source() == Some(&frame(2))
+    ));
+}
+
+#[test]
+fn conceals_burst_loss_with_plc_before_fec() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    buffer.push(frame(4), 1_080_000);
+
+    let drain = buffer.drain_ready(1_200_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+
+    assert_eq!(
+        concealed(&drain.playout),
+        vec![(1, false), (2, false), (3, true)]
+    );
+    assert_eq!(sequences(&drain.playout), vec![4]);
+    assert_eq!(drain.skipped_sequences, 3);
+}
+
+#[test]
+fn long_gap_conceals_only_frames_next_to_resumed_audio() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.drain_ready(1_120_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+    buffer.push(frame(20), 1_400_000);
+
+    let drain = buffer.drain_ready(1_520_000, fixed(TEST_TARGET_PLAYOUT_DELAY_US));
+
+    assert_eq!(
+        concealed(&drain.playout),
+        vec![
+            (15, false),
+            (16, false),
+            (17, false),
+            (18, false),
+            (19, true)
+        ]
+    );
+    assert_eq!(drain.skipped_sequences, 19);
+}
+
+#[test]
+fn grows_playout_delay_with_measured_jitter() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.push(frame(1), 1_170_000);
+
+    let drain = buffer.drain_ready(1_210_000, PlayoutDelayBounds::between(20_000, 200_000));
+    assert_eq!(sequences(&drain.playout), vec![0, 1]);
+    assert_eq!(drain.playout_delay_us, 40_000);
+    assert_eq!(buffer.take_receive_stats().playout_delay_us, 40_000);
+}
+
+#[test]
+fn caps_playout_delay_at_manual_setting() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.push(frame(1), 1_170_000);
+
+    let drain = buffer.drain_ready(1_210_000, PlayoutDelayBounds::between(20_000, 30_000));
+    assert_eq!(drain.playout_delay_us, 30_000);
+}
+
+#[test]
+fn holds_playout_delay_at_manual_floor_on_clean_link() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.push(frame(1), 1_020_000);
+
+    let drain = buffer.drain_ready(1_080_000, PlayoutDelayBounds::between(60_000, 200_000));
+    assert_eq!(drain.playout_delay_us, 60_000);
+    assert_eq!(PlayoutDelayBounds::between(60_000, 30_000), fixed(30_000));
+}
+
+#[test]
+fn shrinks_playout_delay_by_dropping_silent_frames() {
+    let mut buffer = JitterBuffer::default();
+
+    for sequence in 0..6 {
+        let frame = if sequence == 3 {
+            speech_frame(sequence)
+        } else {
+            frame(sequence)
+        };
+        buffer.push(frame, 1_000_000 + sequence * 10_000);
+    }
+    buffer.drain_ready(1_060_000, fixed(60_000));
+
+    let drain = buffer.drain_ready(1_200_000, PlayoutDelayBounds::between(20_000, 200_000));
+    assert_eq!(sequences(&drain.playout), vec![3]);
+    assert_eq!(drain.dropped_silent_frames, 4);
+    assert_eq!(drain.skipped_sequences, 0);
+    assert_eq!(drain.playout_delay_us, 20_000);
+}
+
+#[test]
+fn resets_playout_delay_at_talk_spurt_start() {
+    let mut buffer = JitterBuffer::default();
+
+    buffer.push(frame(0), 1_000_000);
+    buffer.drain_ready(1_060_000, fixed(60_000));
+    let mut resumed = frame(1);
+    resumed.timestamp_us = 500_000;
+    buffer.push(resumed, 1_500_000);
+
+    let drain = buffer.drain_ready(1_520_000, PlayoutDelayBounds::between(20_000, 200_000));
+    assert_eq!(sequences(&drain.playout), vec![1]);
+    assert_eq!(drain.dropped_silent_frames, 0);
+    assert_eq!(drain.playout_delay_us, 20_000);
+}
+
+fn fixed(delay_us: u64) -> PlayoutDelayBounds {
+    PlayoutDelayBounds {
+        min_us: delay_us,
+        max_us: delay_us,
+    }
+}
+
+fn speech_frame(sequence: u64) -> VoiceFrame {
+    VoiceFrame {
+        bytes: vec![7; 60],
+        ..frame(sequence)
+    }
+}
+
+fn frame(sequence: u64) -> VoiceFrame {
+    VoiceFrame {
+        sender_user_id: "sender".to_owned(),
+        sequence,
+        timestamp_us: sequence.saturating_mul(10_000),
+        duration_us: 10_000,
+        codec: PlaybackCodec::Opus,
+        bytes: vec![1, 2, 3],
+    }
+}
+
+fn sequences(playout: &[VoicePlayout]) -> Vec<u64> {
+    playout
+        .iter()
+        .filter_map(|playout| match playout {
+            VoicePlayout::Frame(frame) => Some(frame.sequence),
+            VoicePlayout::Concealed(_) => None,
+        })
+        .collect()
+}
+
+fn concealed(playout: &[VoicePlayout]) -> Vec<(u64, bool)> {
+    playout
+        .iter()
+        .filter_map(|playout| match playout {
+            VoicePlayout::Concealed(concealed) => {
+                Some((concealed.sequence, concealed.fec_source().is_some()))
+            }
+            VoicePlayout::Frame(_) => None,
+        })
+        .collect()
+}
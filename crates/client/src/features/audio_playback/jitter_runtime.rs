@@ -1,13 +1,14 @@
 //! Runtime bridge between inbound voice frames and the jitter buffer.
 
-use dioxus::prelude::{debug, warn};
+use dioxus::prelude::{debug, trace, warn};
 use std::time::Duration;
 use web_time::{SystemTime, UNIX_EPOCH};
 
 use crate::features::runtime::sleep_duration;
 
 use super::browser_helpers::js_error_message;
-use super::jitter_buffer::JitterBufferPush;
+use super::jitter_buffer::{JitterBufferPush, PlayoutDelayBounds, VoicePlayout};
+use super::playback_pipeline::concealment_frame;
 use super::{
     AUDIO_PLAYBACK_WARNING_INTERVAL_MS, AudioPlaybackHandle, AudioPlaybackInner,
     audio_playback_now_ms, should_emit_sender_warning,
@@ -40,7 +41,7 @@ impl AudioPlaybackHandle {
                 .or_default()
                 .push(frame, jitter_now_us())
         };
-        let target_delay_us = self.inner.borrow().jitter_buffer_us;
+        let max_delay_us = self.inner.borrow().jitter_buffer_us;
 
         match outcome {
             JitterBufferPush::Accepted { pending_frames } => {
@@ -48,7 +49,7 @@ impl AudioPlaybackHandle {
                     debug!(
                         %sender_user_id,
                         sequence,
-                        target_delay_us,
+                        max_delay_us,
                         "started inbound voice jitter buffer"
                     );
                 }
@@ -62,7 +63,7 @@ impl AudioPlaybackHandle {
                             %sender_user_id,
                             sequence,
                             pending_frames,
-                            target_delay_us,
+                            max_delay_us,
                             "inbound voice jitter buffer pending frames are backing up"
                         );
                     }
@@ -165,11 +166,13 @@ impl AudioPlaybackHandle {
                 return None;
             }
 
-            let target_delay_us = inner.jitter_buffer_us;
+            let bounds =
+                PlayoutDelayBounds::between(inner.jitter_buffer_min_us, inner.jitter_buffer_us);
             let drain = {
                 let buffer = inner.jitter_buffers.get_mut(sender_user_id)?;
-                buffer.drain_ready(now_us, u64::from(target_delay_us))
+                buffer.drain_ready(now_us, bounds)
             };
+            let target_delay_us = drain.playout_delay_us;
             let should_warn = (drain.skipped_sequences > 0 || drain.dropped_stale_frames > 0)
                 && should_warn_jitter(&mut inner, sender_user_id, audio_playback_now_ms());
             (drain, target_delay_us, should_warn)
@@ -195,8 +198,26 @@ impl AudioPlaybackHandle {
             }
         }
 
+        if drain.dropped_silent_frames > 0 {
+            trace!(
+                %sender_user_id,
+                dropped_silent_frames = drain.dropped_silent_frames,
+                target_delay_us,
+                "shortened inbound voice jitter buffer delay"
+            );
+        }
+
         let next_wake_us = drain.next_wake_us;
-        for frame in drain.ready_frames {
+        for playout in drain.playout {
+            let frame = match playout {
+                VoicePlayout::Frame(frame) => frame,
+                VoicePlayout::Concealed(concealed) => {
+                    let Some(frame) = concealment_frame(&concealed) else {
+                        continue;
+                    };
+                    frame
+                }
+            };
             if let Err(error) = self.decode_voice_frame(frame) {
                 warn!(
                     error = %js_error_message(error),
@@ -19,6 +19,7 @@ use self::mixer::{
     clear_mixer, clear_voice_senders, remove_sender, update_output_gain, update_sender_gain,
 };
 use super::platform_engine::{NativePlaybackEngine, create_engine};
+use crate::features::audio_playback::backend::VoiceReceiveStats;
 use crate::features::audio_playback::output_devices::AudioOutputDevice;
 use crate::features::audio_playback::storage;
 
@@ -31,6 +32,7 @@ pub(crate) struct AudioPlaybackHandle {
     selected_output_device_id: Signal<Option<String>>,
     selected_output_device_label: Signal<Option<String>>,
     output_volume_percent: Signal<u32>,
+    jitter_buffer_min_us: Signal<u32>,
     jitter_buffer_us: Signal<u32>,
     inner: Rc<RefCell<AudioPlaybackInner>>,
 }
@@ -42,6 +44,7 @@ struct AudioPlaybackInner {
     jitter_buffers: HashMap<String, JitterBuffer>,
     jitter_drainers: HashMap<String, u64>,
     next_jitter_drainer_generation: u64,
+    jitter_buffer_min_us: u32,
     jitter_buffer_us: u32,
     jitter_warning_at_ms: HashMap<String, u64>,
     decoder_warning_at_ms: HashMap<String, u64>,
@@ -120,12 +123,34 @@ impl AudioPlaybackHandle {
         }
     }
 
-    /// Возвращает текущую задержку jitter buffer для входящего голоса в микросекундах.
+    /// Возвращает нижнюю границу адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn jitter_buffer_min_us(&self) -> u32 {
+        (self.jitter_buffer_min_us)()
+    }
+
+    /// Обновляет нижнюю границу адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn set_jitter_buffer_min_us(&self, buffer_us: u32) {
+        let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
+        if *self.jitter_buffer_min_us.peek() == buffer_us {
+            return;
+        }
+
+        info!(
+            buffer_us,
+            "inbound voice jitter buffer floor preference changed"
+        );
+        storage::save_jitter_buffer_min_us(buffer_us);
+        let mut jitter_buffer_signal = self.jitter_buffer_min_us;
+        jitter_buffer_signal.set(buffer_us);
+        self.inner.borrow_mut().jitter_buffer_min_us = buffer_us;
+    }
+
+    /// Возвращает предел адаптивной задержки jitter buffer для входящего голоса в микросекундах.
     pub(crate) fn jitter_buffer_us(&self) -> u32 {
         (self.jitter_buffer_us)()
     }
 
-    /// Обновляет задержку jitter buffer для входящего голоса в микросекундах.
+    /// Обновляет предел адаптивной задержки jitter buffer для входящего голоса в микросекундах.
     pub(crate) fn set_jitter_buffer_us(&self, buffer_us: u32) {
         let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
         if *self.jitter_buffer_us.peek() == buffer_us {
@@ -180,6 +205,16 @@ impl AudioPlaybackHandle {
         (self.selected_output_device_id)()
     }
 
+    /// Возвращает статистику приема по отправителям с прошлого вызова.
+    pub(crate) fn take_receive_stats(&self) -> Vec<(String, VoiceReceiveStats)> {
+        self.inner
+            .borrow_mut()
+            .jitter_buffers
+            .iter_mut()
+            .map(|(sender_user_id, buffer)| (sender_user_id.clone(), buffer.take_receive_stats()))
+            .collect()
+    }
+
     /// Останавливает состояние воспроизведения одного отправителя.
     #[allow(dead_code)]
     pub(crate) fn stop_sender(&self, sender_user_id: &str) {
@@ -287,6 +322,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
     let muted = use_signal(|| false);
     let stored_output_device = storage::load_output_device();
     let output_volume_value = storage::load_output_volume_percent();
+    let jitter_buffer_min_us_value = storage::load_jitter_buffer_min_us();
     let jitter_buffer_us_value = storage::load_jitter_buffer_us();
     let selected_output_device_id = use_signal({
         let stored_output_device = stored_output_device.clone();
@@ -299,6 +335,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
     let selected_output_device_label =
         use_signal(move || stored_output_device.and_then(|device| device.label));
     let output_volume_percent = use_signal(move || output_volume_value);
+    let jitter_buffer_min_us = use_signal(move || jitter_buffer_min_us_value);
     let jitter_buffer_us = use_signal(move || jitter_buffer_us_value);
     let inner = use_hook(move || {
         Rc::new(RefCell::new(AudioPlaybackInner {
@@ -308,6 +345,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
             jitter_buffers: HashMap::new(),
             jitter_drainers: HashMap::new(),
             next_jitter_drainer_generation: 0,
+            jitter_buffer_min_us: jitter_buffer_min_us_value,
             jitter_buffer_us: jitter_buffer_us_value,
             jitter_warning_at_ms: HashMap::new(),
             decoder_warning_at_ms: HashMap::new(),
@@ -320,6 +358,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
         selected_output_device_id,
         selected_output_device_label,
         output_volume_percent,
+        jitter_buffer_min_us,
         jitter_buffer_us,
         inner,
     };
@@ -2,10 +2,12 @@
 
 use std::time::Duration;
 
-use dioxus::prelude::{debug, spawn, warn};
+use dioxus::prelude::{debug, spawn, trace, warn};
 use opus::{Channels, Decoder};
 
-use super::jitter_buffer::JitterBufferPush;
+use super::jitter_buffer::{
+    ConcealedVoiceFrame, JitterBufferPush, PlayoutDelayBounds, VoicePlayout,
+};
 use super::mixer::{SENDER_BACKLOG_WARN_SAMPLES, queue_sender_samples, queued_sender_samples};
 use super::{
     AUDIO_SAMPLE_RATE_HZ, AudioPlaybackHandle, playback_now_ms, playback_now_us,
@@ -15,6 +17,8 @@ use crate::features::audio_playback::backend::{PlaybackCodec, VoiceFrame};
 use crate::features::runtime::sleep_duration;
 
 const MAX_OPUS_FRAME_SAMPLES: usize = 5_760;
+/// Opus восстанавливает только фреймы, кратные 2.5 мс при 48 кГц.
+const OPUS_FRAME_SAMPLE_STEP: usize = 120;
 const AUDIO_PLAYBACK_WARNING_INTERVAL_MS: u64 = 5_000;
 const JITTER_PENDING_WARN_FRAMES: usize = 12;
 const JITTER_DRAIN_WAKE_LATE_WARN_MS: u64 = 120;
@@ -51,7 +55,7 @@ impl AudioPlaybackHandle {
                 .or_default()
                 .push(frame, playback_now_us())
         };
-        let target_delay_us = self.inner.borrow().jitter_buffer_us;
+        let max_delay_us = self.inner.borrow().jitter_buffer_us;
 
         match outcome {
             JitterBufferPush::Accepted { pending_frames } => {
@@ -59,7 +63,7 @@ impl AudioPlaybackHandle {
                     debug!(
                         %sender_user_id,
                         sequence,
-                        target_delay_us,
+                        max_delay_us,
                         "started native inbound voice jitter buffer"
                     );
                 }
@@ -70,7 +74,7 @@ impl AudioPlaybackHandle {
                         %sender_user_id,
                         sequence,
                         pending_frames,
-                        target_delay_us,
+                        max_delay_us,
                         "native inbound voice jitter buffer pending frames are backing up"
                     );
                 }
@@ -167,11 +171,13 @@ impl AudioPlaybackHandle {
                 return None;
             }
 
-            let target_delay_us = inner.jitter_buffer_us;
+            let bounds =
+                PlayoutDelayBounds::between(inner.jitter_buffer_min_us, inner.jitter_buffer_us);
             let drain = {
                 let buffer = inner.jitter_buffers.get_mut(sender_user_id)?;
-                buffer.drain_ready(now_us, u64::from(target_delay_us))
+                buffer.drain_ready(now_us, bounds)
             };
+            let target_delay_us = drain.playout_delay_us;
             let should_warn = (drain.skipped_sequences > 0 || drain.dropped_stale_frames > 0)
                 && should_emit_sender_warning(
                     &mut inner.jitter_warning_at_ms,
@@ -202,9 +208,22 @@ impl AudioPlaybackHandle {
             }
         }
 
+        if drain.dropped_silent_frames > 0 {
+            trace!(
+                %sender_user_id,
+                dropped_silent_frames = drain.dropped_silent_frames,
+                target_delay_us,
+                "shortened native inbound voice jitter buffer delay"
+            );
+        }
+
         let next_wake_us = drain.next_wake_us;
-        for frame in drain.ready_frames {
-            if let Err(error) = self.decode_voice_frame(frame) {
+        for playout in drain.playout {
+            let result = match playout {
+                VoicePlayout::Frame(frame) => self.decode_voice_frame(frame),
+                VoicePlayout::Concealed(concealed) => self.conceal_voice_frame(concealed),
+            };
+            if let Err(error) = result {
                 warn!(
                     %error,
                     %sender_user_id,
@@ -217,18 +236,45 @@ impl AudioPlaybackHandle {
     }
 
     fn decode_voice_frame(&self, frame: VoiceFrame) -> Result<(), String> {
-        let sender_user_id = frame.sender_user_id;
-        let sequence = frame.sequence;
+        self.queue_decoded(
+            frame.sender_user_id,
+            frame.sequence,
+            MAX_OPUS_FRAME_SAMPLES,
+            |decoder, output| decoder.decode_float(&frame.bytes, output, false),
+        )
+    }
+
+    /// Восстанавливает потерянный фрейм из FEC следующего фрейма или через PLC декодера.
+    fn conceal_voice_frame(&self, concealed: ConcealedVoiceFrame) -> Result<(), String> {
+        let samples = concealed_frame_samples(concealed.duration_us);
+        self.queue_decoded(
+            concealed.next_frame.sender_user_id.clone(),
+            concealed.sequence,
+            samples,
+            |decoder, output| match concealed.fec_source() {
+                Some(source) => decoder
+                    .decode_float(&source.bytes, output, true)
+                    .or_else(|_| decoder.decode_float(&[], output, false)),
+                None => decoder.decode_float(&[], output, false),
+            },
+        )
+    }
+
+    fn queue_decoded(
+        &self,
+        sender_user_id: String,
+        sequence: u64,
+        max_samples: usize,
+        decode: impl FnOnce(&mut Decoder, &mut [f32]) -> opus::Result<usize>,
+    ) -> Result<(), String> {
         let mixer = {
             let mut inner = self.inner.borrow_mut();
             let decoder = inner
                 .decoders
                 .entry(sender_user_id.clone())
                 .or_insert_with(create_decoder);
-            let mut decoded = vec![0.0_f32; MAX_OPUS_FRAME_SAMPLES];
-            let samples = decoder
-                .decode_float(&frame.bytes, &mut decoded, false)
-                .map_err(opus_error)?;
+            let mut decoded = vec![0.0_f32; max_samples];
+            let samples = decode(decoder, &mut decoded).map_err(opus_error)?;
             decoded.truncate(samples);
             let gain = inner
                 .user_volumes
@@ -293,6 +339,13 @@ fn create_decoder() -> Decoder {
         .expect("native opus decoder can be created for 48 kHz mono")
 }
 
+fn concealed_frame_samples(duration_us: u32) -> usize {
+    let samples = u64::from(duration_us) * u64::from(AUDIO_SAMPLE_RATE_HZ) / 1_000_000;
+    let samples = usize::try_from(samples).unwrap_or(MAX_OPUS_FRAME_SAMPLES);
+    (samples / OPUS_FRAME_SAMPLE_STEP * OPUS_FRAME_SAMPLE_STEP)
+        .clamp(OPUS_FRAME_SAMPLE_STEP, MAX_OPUS_FRAME_SAMPLES)
+}
+
 fn opus_error(error: impl std::fmt::Display) -> String {
     format!("Native audio backend opus вернул ошибку: {error}")
 }
@@ -5,6 +5,8 @@ use std::sync::{Arc, Mutex};
 
 use dioxus::prelude::warn;
 
+use crate::features::microphone::publish_playback_level;
+
 /// Очередь, после которой выводится warning о задержке воспроизведения.
 pub(super) const SENDER_BACKLOG_WARN_SAMPLES: usize = 48_000;
 const SENDER_BACKLOG_DROP_SAMPLES: usize = 96_000;
@@ -61,14 +63,20 @@ impl NativeOutputMixer {
     ) {
         match self.mixer.try_lock() {
             Ok(mut mixer) => {
+                let mut square_sum = 0.0_f32;
                 for frame_index in 0..frame_count {
-                    write_frame(frame_index, self.resampler.next_sample(&mut mixer));
+                    let sample = self.resampler.next_sample(&mut mixer);
+                    square_sum += sample * sample;
+                    write_frame(frame_index, sample);
                 }
+                let rms = (square_sum / frame_count.max(1) as f32).sqrt();
+                publish_playback_level(rms);
             }
             Err(_) => {
                 for frame_index in 0..frame_count {
                     write_frame(frame_index, 0.0);
                 }
+                publish_playback_level(0.0);
             }
         }
     }
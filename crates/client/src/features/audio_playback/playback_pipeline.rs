@@ -15,11 +15,14 @@ use super::browser_diagnostics::{
     DecodeOutputTiming, ScheduleAudioTiming, diagnostics_enabled, elapsed_us_since,
 };
 use super::browser_helpers::{js_error_message, set_property};
+use super::jitter_buffer::ConcealedVoiceFrame;
 use crate::features::audio_playback::backend::VoiceFrame;
 
 const INITIAL_PLAYBACK_BUFFER_SECONDS: f64 = 0.03;
 const CONTINUOUS_PLAYBACK_MARGIN_SECONDS: f64 = 0.02;
 const PLAYBACK_SCHEDULE_WARNING_INTERVAL_SECONDS: f64 = 5.0;
+/// Keeps the TOC configuration and stereo bits while selecting one frame per packet.
+const OPUS_TOC_CONFIG_AND_STEREO_MASK: u8 = 0b1111_1100;
 
 pub(super) struct ScheduledAudioSource {
     pub(super) source: AudioBufferSourceNode,
@@ -134,6 +137,22 @@ pub(super) fn encoded_audio_chunk(frame: &VoiceFrame) -> Result<EncodedAudioChun
     EncodedAudioChunk::new(&init.into())
 }
 
+/// Builds an Opus packet that makes the browser decoder run packet-loss concealment.
+///
+/// WebCodecs has no way to request in-band FEC decoding, but a packet holding only
+/// the TOC byte declares an empty frame, which Opus decoders treat as lost.
+pub(super) fn concealment_frame(concealed: &ConcealedVoiceFrame) -> Option<VoiceFrame> {
+    let toc = *concealed.next_frame.bytes.first()?;
+    Some(VoiceFrame {
+        sender_user_id: concealed.next_frame.sender_user_id.clone(),
+        sequence: concealed.sequence,
+        timestamp_us: concealed.timestamp_us,
+        duration_us: concealed.duration_us,
+        codec: concealed.next_frame.codec,
+        bytes: vec![toc & OPUS_TOC_CONFIG_AND_STEREO_MASK],
+    })
+}
+
 fn decoder_config() -> JsValue {
     let object = Object::new();
     set_property(&object, "codec", &JsValue::from_str("opus"));
@@ -6,10 +6,15 @@ use dioxus_sdk_storage::{LocalStorage, StorageBacking};
 const OUTPUT_DEVICE_ID_KEY: &str = "cheenhub.audio_playback.output_device_id";
 const OUTPUT_DEVICE_LABEL_KEY: &str = "cheenhub.audio_playback.output_device_label";
 const OUTPUT_VOLUME_PERCENT_KEY: &str = "cheenhub.audio_playback.output_volume_percent";
-const JITTER_BUFFER_MS_KEY: &str = "cheenhub.audio_playback.jitter_buffer_ms";
+/// Ключ, под которым раньше хранилась фиксированная задержка джиттер-буфера.
+const LEGACY_JITTER_BUFFER_MS_KEY: &str = "cheenhub.audio_playback.jitter_buffer_ms";
+const JITTER_BUFFER_MIN_MS_KEY: &str = "cheenhub.audio_playback.jitter_buffer_min_ms";
+const JITTER_BUFFER_MAX_MS_KEY: &str = "cheenhub.audio_playback.jitter_buffer_max_ms";
 const DEFAULT_OUTPUT_VOLUME_PERCENT: u32 = 100;
-/// Задержка джиттер-буфера для входящего голоса по умолчанию, в микросекундах.
-pub(crate) const DEFAULT_JITTER_BUFFER_US: u32 = 10_000;
+/// Нижняя граница адаптивной задержки джиттер-буфера для входящего голоса по умолчанию, в микросекундах.
+pub(crate) const DEFAULT_JITTER_BUFFER_MIN_US: u32 = 20_000;
+/// Потолок адаптивной задержки джиттер-буфера для входящего голоса по умолчанию, в микросекундах.
+pub(crate) const DEFAULT_JITTER_BUFFER_US: u32 = 200_000;
 /// Минимальная задержка джиттер-буфера для входящего голоса, в микросекундах.
 pub(crate) const MIN_JITTER_BUFFER_US: u32 = 500;
 /// Максимальная задержка джиттер-буфера для входящего голоса, в микросекундах.
@@ -80,9 +85,37 @@ pub(crate) fn save_output_volume_percent(volume_percent: u32) {
     );
 }
 
-/// Загружает предпочтительную задержку jitter buffer входящего голоса в микросекундах.
+/// Загружает нижнюю границу адаптивной задержки jitter buffer входящего голоса в микросекундах.
+pub(crate) fn load_jitter_buffer_min_us() -> u32 {
+    migrate_legacy_jitter_buffer();
+    let buffer_us = get::<LocalStorage>(JITTER_BUFFER_MIN_MS_KEY)
+        .and_then(|value| parse_jitter_buffer_us(&value))
+        .map(clamp_jitter_buffer_us)
+        .unwrap_or(DEFAULT_JITTER_BUFFER_MIN_US);
+    info!(
+        buffer_us,
+        "loaded inbound voice jitter buffer floor preference"
+    );
+    buffer_us
+}
+
+/// Сохраняет нижнюю границу адаптивной задержки jitter buffer входящего голоса в микросекундах.
+pub(crate) fn save_jitter_buffer_min_us(buffer_us: u32) {
+    let buffer_us = clamp_jitter_buffer_us(buffer_us);
+    set::<LocalStorage>(
+        JITTER_BUFFER_MIN_MS_KEY,
+        &format_jitter_buffer_ms(buffer_us),
+    );
+    info!(
+        buffer_us,
+        "saved inbound voice jitter buffer floor preference"
+    );
+}
+
+/// Загружает предел адаптивной задержки jitter buffer входящего голоса в микросекундах.
 pub(crate) fn load_jitter_buffer_us() -> u32 {
-    let buffer_us = get::<LocalStorage>(JITTER_BUFFER_MS_KEY)
+    migrate_legacy_jitter_buffer();
+    let buffer_us = get::<LocalStorage>(JITTER_BUFFER_MAX_MS_KEY)
         .and_then(|value| parse_jitter_buffer_us(&value))
         .map(clamp_jitter_buffer_us)
         .unwrap_or(DEFAULT_JITTER_BUFFER_US);
@@ -90,11 +123,13 @@ pub(crate) fn load_jitter_buffer_us() -> u32 {
     buffer_us
 }
 
-/// Сохраняет предпочтительную задержку jitter buffer входящего голоса в микросекундах.
+/// Сохраняет предел адаптивной задержки jitter buffer входящего голоса в микросекундах.
 pub(crate) fn save_jitter_buffer_us(buffer_us: u32) {
     let buffer_us = clamp_jitter_buffer_us(buffer_us);
-    let buffer_ms = f64::from(buffer_us) / 1_000.0;
-    set::<LocalStorage>(JITTER_BUFFER_MS_KEY, &buffer_ms.to_string());
+    set::<LocalStorage>(
+        JITTER_BUFFER_MAX_MS_KEY,
+        &format_jitter_buffer_ms(buffer_us),
+    );
     info!(buffer_us, "saved inbound voice jitter buffer preference");
 }
 
@@ -103,6 +138,45 @@ pub(crate) fn clamp_jitter_buffer_us(buffer_us: u32) -> u32 {
     buffer_us.clamp(MIN_JITTER_BUFFER_US, MAX_JITTER_BUFFER_US)
 }
 
+/// Переносит фиксированную задержку старого формата в границы адаптивной задержки.
+fn migrate_legacy_jitter_buffer() {
+    let Some(legacy) = get::<LocalStorage>(LEGACY_JITTER_BUFFER_MS_KEY) else {
+        return;
+    };
+    remove::<LocalStorage>(LEGACY_JITTER_BUFFER_MS_KEY);
+    let Some(fixed_us) = parse_jitter_buffer_us(&legacy) else {
+        return;
+    };
+
+    let (floor_us, ceiling_us) = legacy_jitter_buffer_bounds(clamp_jitter_buffer_us(fixed_us));
+    set::<LocalStorage>(JITTER_BUFFER_MIN_MS_KEY, &format_jitter_buffer_ms(floor_us));
+    set::<LocalStorage>(
+        JITTER_BUFFER_MAX_MS_KEY,
+        &format_jitter_buffer_ms(ceiling_us),
+    );
+    info!(
+        fixed_us,
+        floor_us, ceiling_us, "migrated fixed inbound voice jitter buffer to adaptive bounds"
+    );
+}
+
+/// Возвращает нижнюю границу и предел адаптивной задержки для фиксированной задержки старого формата.
+///
+/// Ручная задержка остается пределом. Задержка ниже нижней границы по умолчанию
+/// становится нижней границей, а предел возвращается к значению по умолчанию,
+/// иначе буфер свелся бы к той же фиксированной задержке.
+fn legacy_jitter_buffer_bounds(fixed_us: u32) -> (u32, u32) {
+    if fixed_us < DEFAULT_JITTER_BUFFER_MIN_US {
+        (fixed_us, DEFAULT_JITTER_BUFFER_US)
+    } else {
+        (DEFAULT_JITTER_BUFFER_MIN_US, fixed_us)
+    }
+}
+
+fn format_jitter_buffer_ms(buffer_us: u32) -> String {
+    (f64::from(buffer_us) / 1_000.0).to_string()
+}
+
 fn parse_jitter_buffer_us(value: &str) -> Option<u32> {
     value
         .parse::<f64>()
@@ -141,7 +215,10 @@ where
 
 #[cfg(test)]
 mod tests {
-    use super::parse_jitter_buffer_us;
+    use super::{
+        DEFAULT_JITTER_BUFFER_MIN_US, DEFAULT_JITTER_BUFFER_US, legacy_jitter_buffer_bounds,
+        parse_jitter_buffer_us,
+    };
 
     #[test]
     fn reads_legacy_integer_milliseconds_as_microseconds() {
@@ -152,4 +229,20 @@ mod tests {
     fn reads_fractional_milliseconds_as_microseconds() {
         assert_eq!(parse_jitter_buffer_us("0.5"), Some(500));
     }
+
+    #[test]
+    fn keeps_legacy_fixed_delay_as_adaptive_ceiling() {
+        assert_eq!(
+            legacy_jitter_buffer_bounds(120_000),
+            (DEFAULT_JITTER_BUFFER_MIN_US, 120_000)
+        );
+    }
+
+    #[test]
+    fn moves_legacy_fixed_delay_below_default_floor_to_floor() {
+        assert_eq!(
+            legacy_jitter_buffer_bounds(10_000),
+            (10_000, DEFAULT_JITTER_BUFFER_US)
+        );
+    }
 }
@@ -14,7 +14,7 @@ use std::rc::Rc;
 
 use dioxus::prelude::*;
 
-use super::backend::{NotificationSound, PlaybackCodec, VoiceFrame};
+use super::backend::{NotificationSound, PlaybackCodec, VoiceFrame, VoiceReceiveStats};
 use super::output_devices::AudioOutputDevice;
 use super::storage;
 
@@ -25,6 +25,7 @@ pub(crate) struct AudioPlaybackHandle {
     selected_output_device_id: Signal<Option<String>>,
     selected_output_device_label: Signal<Option<String>>,
     output_volume_percent: Signal<u32>,
+    jitter_buffer_min_us: Signal<u32>,
     jitter_buffer_us: Signal<u32>,
     warned_unsupported: Rc<Cell<bool>>,
 }
@@ -76,12 +77,33 @@ impl AudioPlaybackHandle {
         volume_signal.set(volume_percent);
     }
 
-    /// Возвращает текущую задержку jitter buffer для входящего голоса в микросекундах.
+    /// Возвращает нижнюю границу адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn jitter_buffer_min_us(&self) -> u32 {
+        (self.jitter_buffer_min_us)()
+    }
+
+    /// Обновляет нижнюю границу адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn set_jitter_buffer_min_us(&self, buffer_us: u32) {
+        let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
+        if *self.jitter_buffer_min_us.peek() == buffer_us {
+            return;
+        }
+
+        info!(
+            buffer_us,
+            "inbound voice jitter buffer floor preference changed"
+        );
+        storage::save_jitter_buffer_min_us(buffer_us);
+        let mut jitter_buffer_signal = self.jitter_buffer_min_us;
+        jitter_buffer_signal.set(buffer_us);
+    }
+
+    /// Возвращает предел адаптивной задержки jitter buffer для входящего голоса в микросекундах.
     pub(crate) fn jitter_buffer_us(&self) -> u32 {
         (self.jitter_buffer_us)()
     }
 
-    /// Обновляет задержку jitter buffer для входящего голоса в микросекундах.
+    /// Обновляет предел адаптивной задержки jitter buffer для входящего голоса в микросекундах.
     pub(crate) fn set_jitter_buffer_us(&self, buffer_us: u32) {
         let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
         if *self.jitter_buffer_us.peek() == buffer_us {
@@ -135,6 +157,11 @@ impl AudioPlaybackHandle {
         (self.selected_output_device_id)()
     }
 
+    /// Без backend'а воспроизведения статистики приема нет.
+    pub(crate) fn take_receive_stats(&self) -> Vec<(String, VoiceReceiveStats)> {
+        Vec::new()
+    }
+
     /// Останавливает состояние воспроизведения одного отправителя.
     #[allow(dead_code)]
     pub(crate) fn stop_sender(&self, _sender_user_id: &str) {}
@@ -220,6 +247,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
     let muted = use_signal(|| false);
     let stored_output_device = storage::load_output_device();
     let output_volume_value = storage::load_output_volume_percent();
+    let jitter_buffer_min_us_value = storage::load_jitter_buffer_min_us();
     let jitter_buffer_us_value = storage::load_jitter_buffer_us();
     let selected_output_device_id = use_signal({
         let stored_output_device = stored_output_device.clone();
@@ -232,12 +260,14 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
     let selected_output_device_label =
         use_signal(move || stored_output_device.and_then(|device| device.label));
     let output_volume_percent = use_signal(move || output_volume_value);
+    let jitter_buffer_min_us = use_signal(move || jitter_buffer_min_us_value);
     let jitter_buffer_us = use_signal(move || jitter_buffer_us_value);
     let handle = AudioPlaybackHandle {
         muted,
         selected_output_device_id,
         selected_output_device_label,
         output_volume_percent,
+        jitter_buffer_min_us,
         jitter_buffer_us,
         warned_unsupported: Rc::new(Cell::new(false)),
     };
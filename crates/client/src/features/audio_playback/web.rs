@@ -12,6 +12,8 @@ mod jitter_buffer;
 mod jitter_runtime;
 #[path = "playback_pipeline.rs"]
 mod playback_pipeline;
+#[path = "web_jitter_preferences.rs"]
+mod web_jitter_preferences;
 #[path = "web_notifications.rs"]
 mod web_notifications;
 #[path = "web_voice_playback.rs"]
@@ -25,7 +27,7 @@ use self::playback_pipeline::{
     ScheduledAudioSource, SenderPlayback, create_sender_playback, encoded_audio_chunk,
 };
 use self::web_notifications::ConnectionSignalLoopState;
-use super::backend::VoiceFrame;
+use super::backend::{VoiceFrame, VoiceReceiveStats};
 use super::output_devices::AudioOutputDevice;
 use super::storage;
 use dioxus::prelude::*;
@@ -47,6 +49,7 @@ pub(crate) struct AudioPlaybackHandle {
     selected_output_device_id: Signal<Option<String>>,
     selected_output_device_label: Signal<Option<String>>,
     output_volume_percent: Signal<u32>,
+    jitter_buffer_min_us: Signal<u32>,
     jitter_buffer_us: Signal<u32>,
     pub(super) inner: Rc<RefCell<AudioPlaybackInner>>,
 }
@@ -58,6 +61,7 @@ pub(super) struct AudioPlaybackInner {
     pub(in crate::features::audio_playback::web) jitter_buffers: HashMap<String, JitterBuffer>,
     pub(in crate::features::audio_playback::web) jitter_drainers: HashMap<String, u64>,
     pub(in crate::features::audio_playback::web) next_jitter_drainer_generation: u64,
+    pub(in crate::features::audio_playback::web) jitter_buffer_min_us: u32,
     pub(in crate::features::audio_playback::web) jitter_buffer_us: u32,
     pub(in crate::features::audio_playback::web) jitter_warning_at_ms: HashMap<String, u64>,
     pub(in crate::features::audio_playback::web) decoder_queue_warning_at_ms: HashMap<String, u64>,
@@ -145,25 +149,6 @@ impl AudioPlaybackHandle {
         }
     }
 
-    /// Возвращает текущую задержку jitter buffer для входящего голоса в микросекундах.
-    pub(crate) fn jitter_buffer_us(&self) -> u32 {
-        (self.jitter_buffer_us)()
-    }
-
-    /// Обновляет задержку jitter buffer для входящего голоса в микросекундах.
-    pub(crate) fn set_jitter_buffer_us(&self, buffer_us: u32) {
-        let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
-        if *self.jitter_buffer_us.peek() == buffer_us {
-            return;
-        }
-
-        info!(buffer_us, "inbound voice jitter buffer preference changed");
-        storage::save_jitter_buffer_us(buffer_us);
-        let mut jitter_buffer_signal = self.jitter_buffer_us;
-        jitter_buffer_signal.set(buffer_us);
-        self.inner.borrow_mut().jitter_buffer_us = buffer_us;
-    }
-
     /// Stores the preferred audio output device and applies it to the active context.
     pub(crate) fn set_output_device(&self, device: &AudioOutputDevice) {
         if device.device_id.is_empty() {
@@ -205,6 +190,16 @@ impl AudioPlaybackHandle {
         (self.selected_output_device_id)()
     }
 
+    /// Returns per-sender receive statistics accumulated since the previous call.
+    pub(crate) fn take_receive_stats(&self) -> Vec<(String, VoiceReceiveStats)> {
+        self.inner
+            .borrow_mut()
+            .jitter_buffers
+            .iter_mut()
+            .map(|(sender_user_id, buffer)| (sender_user_id.clone(), buffer.take_receive_stats()))
+            .collect()
+    }
+
     /// Stops playback state for one sender.
     pub(crate) fn stop_sender(&self, sender_user_id: &str) {
         let (sender, sources) = {
@@ -396,6 +391,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
     let muted = use_signal(|| false);
     let stored_output_device = storage::load_output_device();
     let output_volume_value = storage::load_output_volume_percent();
+    let jitter_buffer_min_us_value = storage::load_jitter_buffer_min_us();
     let jitter_buffer_us_value = storage::load_jitter_buffer_us();
     let selected_output_device_id = use_signal({
         let stored_output_device = stored_output_device.clone();
@@ -408,6 +404,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
     let selected_output_device_label =
         use_signal(move || stored_output_device.and_then(|device| device.label));
     let output_volume_percent = use_signal(move || output_volume_value);
+    let jitter_buffer_min_us = use_signal(move || jitter_buffer_min_us_value);
     let jitter_buffer_us = use_signal(move || jitter_buffer_us_value);
     let output_gain = gain_from_percent(output_volume_value);
     let handle = AudioPlaybackHandle {
@@ -415,6 +412,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
         selected_output_device_id,
         selected_output_device_label,
         output_volume_percent,
+        jitter_buffer_min_us,
         jitter_buffer_us,
         inner: Rc::new(RefCell::new(AudioPlaybackInner {
             context: None,
@@ -423,6 +421,7 @@ pub(crate) fn AudioPlaybackProvider(children: Element) -> Element {
             jitter_buffers: HashMap::new(),
             jitter_drainers: HashMap::new(),
             next_jitter_drainer_generation: 0,
+            jitter_buffer_min_us: jitter_buffer_min_us_value,
             jitter_buffer_us: jitter_buffer_us_value,
             jitter_warning_at_ms: HashMap::new(),
             decoder_queue_warning_at_ms: HashMap::new(),
@@ -0,0 +1,48 @@
+//! Настройки jitter buffer входящего голоса в browser-воспроизведении.
+
+use dioxus::prelude::*;
+
+use super::{AudioPlaybackHandle, storage};
+
+impl AudioPlaybackHandle {
+    /// Возвращает нижнюю границу адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn jitter_buffer_min_us(&self) -> u32 {
+        (self.jitter_buffer_min_us)()
+    }
+
+    /// Обновляет нижнюю границу адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn set_jitter_buffer_min_us(&self, buffer_us: u32) {
+        let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
+        if *self.jitter_buffer_min_us.peek() == buffer_us {
+            return;
+        }
+
+        info!(
+            buffer_us,
+            "inbound voice jitter buffer floor preference changed"
+        );
+        storage::save_jitter_buffer_min_us(buffer_us);
+        let mut jitter_buffer_signal = self.jitter_buffer_min_us;
+        jitter_buffer_signal.set(buffer_us);
+        self.inner.borrow_mut().jitter_buffer_min_us = buffer_us;
+    }
+
+    /// Возвращает предел адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn jitter_buffer_us(&self) -> u32 {
+        (self.jitter_buffer_us)()
+    }
+
+    /// Обновляет предел адаптивной задержки jitter buffer для входящего голоса в микросекундах.
+    pub(crate) fn set_jitter_buffer_us(&self, buffer_us: u32) {
+        let buffer_us = storage::clamp_jitter_buffer_us(buffer_us);
+        if *self.jitter_buffer_us.peek() == buffer_us {
+            return;
+        }
+
+        info!(buffer_us, "inbound voice jitter buffer preference changed");
+        storage::save_jitter_buffer_us(buffer_us);
+        let mut jitter_buffer_signal = self.jitter_buffer_us;
+        jitter_buffer_signal.set(buffer_us);
+        self.inner.borrow_mut().jitter_buffer_us = buffer_us;
+    }
+}
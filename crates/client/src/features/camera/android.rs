@@ -9,7 +9,8 @@ use crate::features::video_encoding::{
     VideoEncoderConfig, VideoEncodingAcceleratorKind, VideoEncodingManager, VideoFrameEncoder,
     android_video_capture_bridge,
 };
-use dioxus::logger::tracing::error;
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use dioxus::logger::tracing::{error, warn};
 use futures_util::future::LocalBoxFuture;
 use std::{cell::Cell, rc::Rc, time::Duration};
 
@@ -41,6 +42,7 @@ impl CameraBackend for AndroidCameraBackend {
                             timestamp_us: f.timestamp_us,
                             duration_us: f.duration_us,
                             codec: CameraCodec::Vp9,
+                            layer: VideoSpatialLayer::High,
                             key_frame: f.key_frame,
                             width: f.width,
                             height: f.height,
@@ -101,4 +103,10 @@ impl CameraSession for AndroidCameraSession {
             encoder.close().map_err(|e| CameraError::new(e.to_string()))
         })
     }
+
+    fn request_key_frame(&self) {
+        if let Err(error) = self.encoder.request_key_frame() {
+            warn!(%error, "failed to request Android camera VP9 key frame");
+        }
+    }
 }
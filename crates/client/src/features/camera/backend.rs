@@ -3,7 +3,7 @@
 use std::fmt;
 use std::rc::Rc;
 
-use cheenhub_contracts::video_presets::{VideoPresetId, VideoPresetSpec};
+use cheenhub_contracts::video_presets::{VideoPresetId, VideoPresetSpec, VideoSpatialLayer};
 use futures_util::future::LocalBoxFuture;
 
 /// Callback, вызываемый для каждого закодированного кадра камеры.
@@ -64,6 +64,8 @@ pub(crate) struct EncodedCameraFrame {
     pub(crate) duration_us: u32,
     /// Кодек закодированного кадра.
     pub(crate) codec: CameraCodec,
+    /// Слой simulcast, к которому относится кадр.
+    pub(crate) layer: VideoSpatialLayer,
     /// Может ли этот кадр открыть поток декодера.
     pub(crate) key_frame: bool,
     /// Ширина закодированного кадра.
@@ -93,6 +95,9 @@ pub(crate) enum CameraStatus {
 pub(crate) trait CameraSession {
     /// Останавливает захват и освобождает ресурсы backend'а.
     fn stop(&self) -> LocalBoxFuture<'static, Result<(), CameraError>>;
+
+    /// Просит кодировщик сделать следующий кадр ключевым.
+    fn request_key_frame(&self);
 }
 
 /// Backend захвата камеры.
@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 
+use cheenhub_contracts::video_presets::VideoPresetId;
 use dioxus::prelude::*;
 
 use crate::features::toast::ToastHandle;
@@ -17,16 +18,26 @@ pub(crate) struct CameraHandle {
     pub(super) status: Signal<CameraStatus>,
     pub(super) session: Signal<Option<Rc<dyn CameraSession>>>,
     pub(super) generation: Signal<u64>,
+    pub(super) allowed_presets: Signal<Vec<VideoPresetId>>,
     pub(super) backend: Rc<dyn CameraBackend>,
     pub(super) toast: ToastHandle,
 }
 
 impl CameraHandle {
-    /// Запускает захват камеры с конфигурацией по умолчанию.
+    /// Запускает захват камеры с первым пресетом, разрешенным текущей комнатой.
     pub(crate) fn start(&self, on_frame: CameraFrameCallback) {
         if matches!(self.status(), CameraStatus::Starting | CameraStatus::Live) {
             return;
         }
+        let Some(preset) = self.allowed_presets.peek().first().copied() else {
+            info!("camera capture is disabled by server media settings");
+            self.toast.warning("Камера отключена на этом сервере.");
+            return;
+        };
+        let config = CameraConfig {
+            preset,
+            ..CameraConfig::default()
+        };
 
         let backend = self.backend.clone();
         let mut session = self.session;
@@ -39,7 +50,7 @@ impl CameraHandle {
         info!("starting camera capture");
         spawn(async move {
             let callbacks = camera_callbacks(on_frame.clone(), session, status, generation);
-            match backend.start(CameraConfig::default(), callbacks).await {
+            match backend.start(config, callbacks).await {
                 Ok(next_session) => {
                     if generation() != start_generation {
                         if let Err(error) = next_session.stop().await {
@@ -102,6 +113,28 @@ impl CameraHandle {
         }
     }
 
+    /// Задает пресеты камеры, разрешенные настройками сервера текущей комнаты.
+    pub(crate) fn set_allowed_presets(&self, presets: Vec<VideoPresetId>) {
+        let mut allowed_presets = self.allowed_presets;
+        if *allowed_presets.peek() == presets {
+            return;
+        }
+        if presets.is_empty()
+            && matches!(self.status(), CameraStatus::Live | CameraStatus::Starting)
+        {
+            info!("stopping camera capture disabled by server media settings");
+            self.stop();
+        }
+        allowed_presets.set(presets);
+    }
+
+    /// Просит активный захват закодировать следующий кадр как ключевой.
+    pub(crate) fn request_key_frame(&self) {
+        if let Some(session) = self.session.peek().as_ref() {
+            session.request_key_frame();
+        }
+    }
+
     /// Возвращает текущее состояние камеры.
     pub(crate) fn status(&self) -> CameraStatus {
         (self.status)()
@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 
+use cheenhub_contracts::video_presets::BASE_CAMERA_VIDEO_PRESETS;
 use dioxus::prelude::*;
 
 use crate::features::toast::ToastHandle;
@@ -16,12 +17,14 @@ pub(crate) fn CameraProvider(children: Element) -> Element {
     let status = use_signal(|| CameraStatus::Idle);
     let session = use_signal(|| None::<Rc<dyn CameraSession>>);
     let generation = use_signal(|| 0);
+    let allowed_presets = use_signal(|| BASE_CAMERA_VIDEO_PRESETS.to_vec());
     let toast = use_context::<ToastHandle>();
     let backend = default_backend();
     let handle = CameraHandle {
         status,
         session,
         generation,
+        allowed_presets,
         backend,
         toast,
     };
@@ -4,6 +4,7 @@
 use std::cell::Cell;
 use std::rc::Rc;
 
+use cheenhub_contracts::video_presets::{VideoPresetId, VideoSpatialLayer};
 use dioxus::prelude::warn;
 use futures_util::FutureExt;
 use futures_util::future::LocalBoxFuture;
@@ -24,7 +25,8 @@ use super::backend::{
 };
 use super::browser_capture::{first_video_track, log_selected_video_track, request_camera_stream};
 
-const KEY_FRAME_INTERVAL_SECONDS: u32 = 2;
+/// Резервный интервал ключевых кадров на случай потерянных запросов получателей.
+const FALLBACK_KEY_FRAME_INTERVAL_SECONDS: u32 = 10;
 const UNSUPPORTED_CAMERA_MESSAGE: &str = concat!(
     "Этот браузер не поддерживает камеру в CheenHub. ",
     "Воспользуйтесь браузером на базе Chromium или нативным клиентом."
@@ -44,16 +46,17 @@ impl CameraBackend for WebCameraBackend {
 }
 
 struct BrowserCameraSession {
-    encoder: BrowserVideoEncoder,
+    encoders: Vec<BrowserVideoEncoder>,
     stream: MediaStream,
     track: web_sys::MediaStreamTrack,
     frame_reader: BrowserVideoFrameReaderHandle,
     closed: Rc<Cell<bool>>,
+    key_frame_requested: Rc<Cell<bool>>,
 }
 
 impl CameraSession for BrowserCameraSession {
     fn stop(&self) -> LocalBoxFuture<'static, Result<(), CameraError>> {
-        let encoder = self.encoder.handle();
+        let encoders = encoder_handles(&self.encoders);
         let stream = self.stream.clone();
         let track = self.track.clone();
         let frame_reader = self.frame_reader.clone();
@@ -65,11 +68,17 @@ impl CameraSession for BrowserCameraSession {
             frame_reader.stop();
             track.stop();
             stop_media_stream(&stream);
-            encoder.close().map_err(camera_video_encoding_error)?;
+            for encoder in encoders {
+                encoder.close().map_err(camera_video_encoding_error)?;
+            }
             Ok(())
         }
         .boxed_local()
     }
+
+    fn request_key_frame(&self) {
+        self.key_frame_requested.set(true);
+    }
 }
 
 async fn start_browser_session(
@@ -91,28 +100,14 @@ async fn start_browser_session(
     };
     log_selected_video_track(&track);
     let preset = config.preset_spec();
-    let encoder_config = VideoEncoderConfig::vp9(
-        preset.width,
-        preset.height,
-        preset.max_fps,
-        preset.bitrate_bps,
-    );
-    ensure_video_encoder_available(&stream, encoder_config.clone()).await?;
+    ensure_video_encoder_available(
+        &stream,
+        layer_encoder_config(config.preset, VideoSpatialLayer::High),
+    )
+    .await?;
 
-    let output_on_frame = callbacks.on_frame.clone();
-    let on_video_frame = Rc::new(move |frame: EncodedVideoFrame| {
-        output_on_frame(camera_frame_from_video(frame));
-    });
-    let manager = BrowserVideoEncodingManager;
-    let encoder = match manager
-        .create_encoder(
-            VideoEncodingAcceleratorKind::WebCodecs,
-            encoder_config,
-            on_video_frame,
-        )
-        .await
-    {
-        Ok(encoder) => encoder,
+    let encoders = match create_layer_encoders(config.preset, &callbacks).await {
+        Ok(encoders) => encoders,
         Err(error) => {
             stop_media_stream(&stream);
             return Err(camera_video_encoding_error(error));
@@ -121,7 +116,7 @@ async fn start_browser_session(
     let frame_reader = match BrowserVideoFrameReader::from_stream(&track, &stream).await {
         Ok(reader) => reader,
         Err(error) => {
-            let _ = encoder.close();
+            close_encoders(&encoders);
             stop_media_stream(&stream);
             return Err(camera_video_encoding_error(error));
         }
@@ -129,29 +124,75 @@ async fn start_browser_session(
     let frame_reader_handle = frame_reader.handle();
 
     let closed = Rc::new(Cell::new(false));
-    let key_frame_interval_frames = preset
-        .max_fps
-        .saturating_mul(KEY_FRAME_INTERVAL_SECONDS)
-        .max(1);
+    let key_frame_requested = Rc::new(Cell::new(false));
     spawn_video_reader(
         stream.clone(),
         frame_reader,
-        encoder.handle(),
+        encoder_handles(&encoders),
         closed.clone(),
+        key_frame_requested.clone(),
         callbacks,
-        key_frame_interval_frames,
         preset.max_fps,
     );
 
     Ok(Rc::new(BrowserCameraSession {
-        encoder,
+        encoders,
         stream,
         track,
         frame_reader: frame_reader_handle,
         closed,
+        key_frame_requested,
     }))
 }
 
+/// Создает кодировщики всех слоев simulcast; без основного слоя камера не запускается,
+/// а недоступный уменьшенный слой только сужает выбор получателей.
+async fn create_layer_encoders(
+    preset: VideoPresetId,
+    callbacks: &CameraCallbacks,
+) -> Result<Vec<BrowserVideoEncoder>, VideoEncodingError> {
+    let manager = BrowserVideoEncodingManager;
+    let mut encoders = Vec::with_capacity(VideoSpatialLayer::ALL.len());
+    for layer in VideoSpatialLayer::ALL {
+        let output_on_frame = callbacks.on_frame.clone();
+        let on_video_frame = Rc::new(move |frame: EncodedVideoFrame| {
+            output_on_frame(camera_frame_from_video(frame, layer));
+        });
+        match manager
+            .create_encoder(
+                VideoEncodingAcceleratorKind::WebCodecs,
+                layer_encoder_config(preset, layer),
+                on_video_frame,
+            )
+            .await
+        {
+            Ok(encoder) => encoders.push(encoder),
+            Err(error) if layer == VideoSpatialLayer::High => {
+                close_encoders(&encoders);
+                return Err(error);
+            }
+            Err(error) => warn!(%error, layer = ?layer, "failed to create camera layer encoder"),
+        }
+    }
+
+    Ok(encoders)
+}
+
+fn layer_encoder_config(preset: VideoPresetId, layer: VideoSpatialLayer) -> VideoEncoderConfig {
+    let spec = preset.layer_spec(layer);
+    VideoEncoderConfig::vp9(spec.width, spec.height, spec.max_fps, spec.bitrate_bps)
+}
+
+fn encoder_handles(encoders: &[BrowserVideoEncoder]) -> Vec<BrowserVideoEncoderHandle> {
+    encoders.iter().map(BrowserVideoEncoder::handle).collect()
+}
+
+fn close_encoders(encoders: &[BrowserVideoEncoder]) {
+    for encoder in encoders {
+        let _ = encoder.close();
+    }
+}
+
 async fn ensure_video_encoder_available(
     stream: &MediaStream,
     encoder_config: VideoEncoderConfig,
@@ -192,12 +233,15 @@ fn ensure_browser_camera_support() -> Result<(), CameraError> {
 fn spawn_video_reader(
     stream: MediaStream,
     reader: BrowserVideoFrameReader,
-    encoder: BrowserVideoEncoderHandle,
+    encoders: Vec<BrowserVideoEncoderHandle>,
     closed: Rc<Cell<bool>>,
+    key_frame_requested: Rc<Cell<bool>>,
     callbacks: CameraCallbacks,
-    key_frame_interval_frames: u32,
     max_fps: u32,
 ) {
+    let key_frame_interval_frames = max_fps
+        .saturating_mul(FALLBACK_KEY_FRAME_INTERVAL_SECONDS)
+        .max(1);
     spawn_local(async move {
         let frame_sequence = Rc::new(Cell::new(0_u64));
         let mut frame_rate_gate = VideoFrameRateGate::new(max_fps);
@@ -216,8 +260,12 @@ fn spawn_video_reader(
             }
             let sequence = frame_sequence.get();
             frame_sequence.set(sequence.saturating_add(1));
-            let key_frame = sequence.is_multiple_of(u64::from(key_frame_interval_frames));
-            if let Err(error) = encoder.encode(&frame, key_frame) {
+            let key_frame = key_frame_requested.replace(false)
+                || sequence.is_multiple_of(u64::from(key_frame_interval_frames));
+            if let Err(error) = encoders
+                .iter()
+                .try_for_each(|encoder| encoder.encode(&frame, key_frame))
+            {
                 warn!(%error, "failed to encode camera frame");
                 frame.close();
                 break;
@@ -225,13 +273,13 @@ fn spawn_video_reader(
             frame.close();
         }
 
-        finish_browser_capture(&stream, &encoder, &closed, &callbacks);
+        finish_browser_capture(&stream, &encoders, &closed, &callbacks);
     });
 }
 
 fn finish_browser_capture(
     stream: &MediaStream,
-    encoder: &BrowserVideoEncoderHandle,
+    encoders: &[BrowserVideoEncoderHandle],
     closed: &Rc<Cell<bool>>,
     callbacks: &CameraCallbacks,
 ) {
@@ -239,13 +287,18 @@ fn finish_browser_capture(
         return;
     }
     stop_media_stream(stream);
-    if let Err(error) = encoder.close() {
-        warn!(%error, "failed to close camera encoder after capture ended");
+    for encoder in encoders {
+        if let Err(error) = encoder.close() {
+            warn!(%error, "failed to close camera encoder after capture ended");
+        }
     }
     (callbacks.on_ended)();
 }
 
-fn camera_frame_from_video(frame: EncodedVideoFrame) -> EncodedCameraFrame {
+fn camera_frame_from_video(
+    frame: EncodedVideoFrame,
+    layer: VideoSpatialLayer,
+) -> EncodedCameraFrame {
     EncodedCameraFrame {
         sequence: frame.sequence,
         timestamp_us: frame.timestamp_us,
@@ -253,6 +306,7 @@ fn camera_frame_from_video(frame: EncodedVideoFrame) -> EncodedCameraFrame {
         codec: match frame.codec {
             VideoCodec::Vp9 => CameraCodec::Vp9,
         },
+        layer,
         key_frame: frame.key_frame,
         width: frame.width,
         height: frame.height,
@@ -23,6 +23,11 @@ pub(super) fn oversized_image_message(max_bytes: usize) -> String {
     format!("Изображение слишком большое. Максимум — {max_mebibytes} МБ.")
 }
 
+pub(super) fn oversized_file_message(max_bytes: usize) -> String {
+    let max_mebibytes = max_bytes / (1024 * 1024);
+    format!("Файл слишком большой. Максимум — {max_mebibytes} МБ.")
+}
+
 #[cfg(test)]
 mod tests {
     use super::oversized_image_message;
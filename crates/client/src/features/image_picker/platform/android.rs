@@ -21,14 +21,19 @@ static PICKER_REQUESTS: OnceLock<Mutex<HashMap<i32, PickerSender>>> = OnceLock::
 static NEXT_REQUEST_ID: AtomicI32 = AtomicI32::new(30_000);
 
 /// Показывает кнопку системного Android Photo Picker для одного изображения.
+///
+/// Photo Picker выбирает только изображения, поэтому `accept_any_file` здесь
+/// не расширяет выбор.
 #[component]
 pub(crate) fn ImagePickerButton(
     disabled: bool,
     busy: bool,
     max_bytes: usize,
+    #[props(default)] accept_any_file: bool,
     on_outcome: EventHandler<ImagePickerOutcome>,
     on_active_change: EventHandler<bool>,
 ) -> Element {
+    let _ = accept_any_file;
     let mut is_picking = use_signal(|| false);
     let unavailable = disabled || busy || is_picking();
 
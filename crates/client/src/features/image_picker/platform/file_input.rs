@@ -2,14 +2,22 @@
 
 use dioxus::prelude::*;
 
-use super::super::backend::{ImagePickerOutcome, PickedImage, oversized_image_message};
+use super::super::backend::{
+    ImagePickerOutcome, PickedImage, oversized_file_message, oversized_image_message,
+};
+
+const IMAGE_ACCEPT: &str = "image/png,image/jpeg,image/gif,image/webp";
 
 /// Показывает кнопку выбора одного изображения через платформенный file input.
+///
+/// С `accept_any_file` можно выбрать файл любого типа; проверку содержимого
+/// выполняет сервер.
 #[component]
 pub(crate) fn ImagePickerButton(
     disabled: bool,
     busy: bool,
     max_bytes: usize,
+    #[props(default)] accept_any_file: bool,
     on_outcome: EventHandler<ImagePickerOutcome>,
     on_active_change: EventHandler<bool>,
 ) -> Element {
@@ -23,9 +31,12 @@ pub(crate) fn ImagePickerButton(
             return;
         };
         if file.size() > max_bytes as u64 {
-            on_outcome.call(ImagePickerOutcome::Failed(oversized_image_message(
-                max_bytes,
-            )));
+            let message = if accept_any_file {
+                oversized_file_message(max_bytes)
+            } else {
+                oversized_image_message(max_bytes)
+            };
+            on_outcome.call(ImagePickerOutcome::Failed(message));
             return;
         }
 
@@ -60,17 +71,22 @@ pub(crate) fn ImagePickerButton(
         });
     });
 
+    let title = if accept_any_file {
+        "Прикрепить файл"
+    } else {
+        "Прикрепить изображение"
+    };
     rsx! {
         label {
             class: "flex size-10 shrink-0 cursor-pointer items-center justify-center rounded-xl border border-zinc-800 bg-zinc-900/80 text-zinc-300 transition-[background-color,border-color,color,transform,opacity] duration-150 ease-out hover:-translate-y-px hover:border-white/15 hover:bg-zinc-800 hover:text-zinc-100 active:scale-[0.96] has-[:disabled]:cursor-not-allowed has-[:disabled]:opacity-45 has-[:disabled]:hover:translate-y-0 has-[:disabled]:active:scale-100",
-            title: "Прикрепить изображение",
+            title,
             input {
                 class: "sr-only",
                 r#type: "file",
                 name: "message-image",
-                accept: "image/png,image/jpeg,image/gif,image/webp",
+                accept: (!accept_any_file).then_some(IMAGE_ACCEPT),
                 disabled: unavailable,
-                "aria-label": "Прикрепить изображение",
+                "aria-label": title,
                 onchange: move |event| select_image.call(event),
             }
             if busy || is_reading() {
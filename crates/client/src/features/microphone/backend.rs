@@ -2,6 +2,8 @@
 
 use std::fmt;
 use std::rc::Rc;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use futures_util::future::LocalBoxFuture;
 
@@ -46,6 +48,62 @@ pub(crate) enum MicrophoneActivationMode {
     AlwaysActive,
     /// Encode frames only while voice activation is open.
     VoiceActivated,
+    /// Encode frames while the push-to-talk key is held, plus a short release tail.
+    PushToTalk,
+    /// Encode every frame except while the push-to-talk key is held.
+    PushToMute,
+}
+
+/// Shared push-to-talk key state read by the activation gate on every frame.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PushToTalkKeyState(Arc<AtomicBool>);
+
+impl PushToTalkKeyState {
+    /// Records whether the push-to-talk key is currently held.
+    pub(crate) fn set_pressed(&self, pressed: bool) {
+        self.0.store(pressed, Ordering::Relaxed);
+    }
+
+    /// Returns whether the push-to-talk key is currently held.
+    pub(crate) fn is_pressed(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl PartialEq for PushToTalkKeyState {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Toggles for the PCM processing chain applied before voice activation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MicrophoneProcessingConfig {
+    /// Remove rumble and handling noise below the voice band.
+    pub(crate) high_pass_filter: bool,
+    /// Duck the microphone while remote voices play and the local speaker is silent.
+    ///
+    /// Native capture has no adaptive echo canceller, so echo leaks through during
+    /// double talk; browsers map this to their own `echoCancellation`.
+    pub(crate) echo_suppression: bool,
+    /// Suppress stationary background noise such as fans and hum.
+    pub(crate) noise_suppression: bool,
+    /// Silence residual noise between phrases below a fixed level.
+    pub(crate) noise_gate: bool,
+    /// Level speech towards a consistent loudness.
+    pub(crate) auto_gain_control: bool,
+}
+
+impl Default for MicrophoneProcessingConfig {
+    fn default() -> Self {
+        Self {
+            high_pass_filter: true,
+            echo_suppression: true,
+            noise_suppression: true,
+            noise_gate: false,
+            auto_gain_control: true,
+        }
+    }
 }
 
 /// Microphone capture and encoding configuration.
@@ -59,6 +117,8 @@ pub(crate) struct MicrophoneConfig {
     pub(crate) channels: u8,
     /// Target encoder bitrate in bits per second.
     pub(crate) bitrate_bps: u32,
+    /// Expected uplink packet loss that sizes Opus in-band FEC redundancy.
+    pub(crate) packet_loss_percent: u8,
     /// Audio activation mode used before encoding.
     pub(crate) activation_mode: MicrophoneActivationMode,
     /// RMS level threshold that opens voice activation.
@@ -67,10 +127,16 @@ pub(crate) struct MicrophoneConfig {
     pub(crate) vad_activation_delay_us: u32,
     /// Time activation remains open after the level falls below threshold.
     pub(crate) vad_release_delay_us: u32,
+    /// Time push-to-talk activation remains open after the key is released.
+    pub(crate) push_to_talk_release_delay_us: u32,
+    /// Live push-to-talk key state shared with the capture runtime.
+    pub(crate) push_to_talk: PushToTalkKeyState,
     /// Идентификатор устройства ввода для platform backend; `None` использует системное устройство по умолчанию.
     pub(crate) device_id: Option<String>,
     /// Linear input gain applied before voice activation and encoding.
     pub(crate) input_gain: f32,
+    /// PCM processing stages applied after input gain.
+    pub(crate) processing: MicrophoneProcessingConfig,
 }
 
 impl Default for MicrophoneConfig {
@@ -80,12 +146,16 @@ impl Default for MicrophoneConfig {
             sample_rate_hz: 48_000,
             channels: 1,
             bitrate_bps: 32_000,
+            packet_loss_percent: 10,
             activation_mode: MicrophoneActivationMode::VoiceActivated,
             vad_threshold: 0.02,
             vad_activation_delay_us: 60_000,
             vad_release_delay_us: 250_000,
+            push_to_talk_release_delay_us: 200_000,
+            push_to_talk: PushToTalkKeyState::default(),
             device_id: None,
             input_gain: 1.0,
+            processing: MicrophoneProcessingConfig::default(),
         }
     }
 }
@@ -121,6 +191,8 @@ pub(crate) struct EncodedMicrophoneFrame {
     pub(crate) channels: u8,
     /// Raw encoded frame bytes.
     pub(crate) bytes: Vec<u8>,
+    /// Frame audio level in -dBov, see `cheenhub_contracts::media::audio_level_from_rms`.
+    pub(crate) audio_level: Option<u8>,
 }
 
 /// Current microphone input level for visualization and threshold tuning.
@@ -162,6 +234,18 @@ pub(crate) trait MicrophoneSession {
         &self,
         bitrate_bps: u32,
     ) -> LocalBoxFuture<'static, Result<(), MicrophoneError>>;
+
+    /// Updates the expected packet loss that sizes in-band FEC redundancy.
+    #[allow(dead_code)]
+    fn set_packet_loss_percent(
+        &self,
+        packet_loss_percent: u8,
+    ) -> LocalBoxFuture<'static, Result<(), MicrophoneError>>;
+
+    /// Forwards the push-to-talk key state to an out-of-process activation gate.
+    ///
+    /// In-process backends read [`MicrophoneConfig::push_to_talk`] directly.
+    fn set_push_to_talk_pressed(&self, _pressed: bool) {}
 }
 
 /// Microphone capture backend.
@@ -63,6 +63,8 @@ struct BrowserMicrophoneSession {
     _output_closure: Option<Closure<dyn FnMut(EncodedAudioChunk)>>,
     _error_closure: Option<Closure<dyn FnMut(JsValue)>>,
     bitrate_bps: Rc<Cell<u32>>,
+    sample_rate_hz: u32,
+    channels: u8,
 }
 
 impl MicrophoneSession for BrowserMicrophoneSession {
@@ -116,6 +118,33 @@ impl MicrophoneSession for BrowserMicrophoneSession {
         }
         async move { Ok(()) }.boxed_local()
     }
+
+    fn set_packet_loss_percent(
+        &self,
+        packet_loss_percent: u8,
+    ) -> LocalBoxFuture<'static, Result<(), MicrophoneError>> {
+        if let Some(uplink) = &self.uplink {
+            uplink.set_packet_loss_percent(packet_loss_percent);
+        }
+        let result = match &self.encoder {
+            Some(encoder) if !self.closed.get() => encoder
+                .configure(&encoder_config(
+                    self.sample_rate_hz,
+                    self.channels,
+                    self.bitrate_bps.get(),
+                    packet_loss_percent,
+                ))
+                .map_err(microphone_error),
+            _ => Ok(()),
+        };
+        async move { result }.boxed_local()
+    }
+
+    fn set_push_to_talk_pressed(&self, pressed: bool) {
+        if let Some(uplink) = &self.uplink {
+            uplink.set_push_to_talk_pressed(pressed);
+        }
+    }
 }
 
 async fn start_browser_session(
@@ -139,8 +168,12 @@ async fn start_browser_session(
         return Err(error);
     }
 
-    let browser_encoder_config =
-        encoder_config(sample_rate_hz, config.channels, config.bitrate_bps);
+    let browser_encoder_config = encoder_config(
+        sample_rate_hz,
+        config.channels,
+        config.bitrate_bps,
+        config.packet_loss_percent,
+    );
     verify_encoder_support(&context, &browser_encoder_config).await?;
     let stream = request_stream_or_close_context(&context, config.clone()).await?;
     let track = first_track_or_cleanup(&stream, &context).await?;
@@ -219,6 +252,7 @@ async fn start_browser_session(
                 sample_rate_hz,
                 closed.clone(),
                 browser_encoder.diagnostics.clone(),
+                browser_encoder.audio_level.clone(),
             );
             port.set_onmessage(Some(message_closure.as_ref().unchecked_ref()));
             port.start();
@@ -284,6 +318,8 @@ async fn start_browser_session(
         _output_closure: output_closure,
         _error_closure: encoder_error_closure,
         bitrate_bps: Rc::new(Cell::new(config.bitrate_bps)),
+        sample_rate_hz,
+        channels: config.channels,
     }))
 }
 
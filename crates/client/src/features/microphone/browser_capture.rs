@@ -122,10 +122,17 @@ async fn request_default_microphone_stream(
 fn microphone_track_constraints(config: &MicrophoneConfig) -> MediaTrackConstraints {
     let audio = MediaTrackConstraints::new();
     audio.set_channel_count(&JsValue::from_f64(f64::from(config.channels)));
-    let browser_voice_processing = JsValue::from_bool(browser_voice_processing_enabled());
-    audio.set_echo_cancellation(&browser_voice_processing);
-    audio.set_noise_suppression(&browser_voice_processing);
-    audio.set_auto_gain_control(&browser_voice_processing);
+    let browser_voice_processing = browser_voice_processing_enabled();
+    let processing = config.processing;
+    let noise_suppression =
+        JsValue::from_bool(browser_voice_processing && processing.noise_suppression);
+    audio.set_echo_cancellation(&JsValue::from_bool(
+        browser_voice_processing && processing.echo_suppression,
+    ));
+    audio.set_noise_suppression(&noise_suppression);
+    audio.set_auto_gain_control(&JsValue::from_bool(
+        browser_voice_processing && processing.auto_gain_control,
+    ));
     // Усиленная ML-изоляция голоса Chrome (deep noise suppression) поверх обычного
     // noiseSuppression: лучше давит постоянный фоновый шум (вентилятор, улица, набор
     // на клавиатуре). Это нестандартный constraint, поэтому задаем его через Reflect;
@@ -133,7 +140,7 @@ fn microphone_track_constraints(config: &MicrophoneConfig) -> MediaTrackConstrai
     let _ = Reflect::set(
         audio.as_ref(),
         &JsValue::from_str("voiceIsolation"),
-        &browser_voice_processing,
+        &noise_suppression,
     );
     if let Some(ref device_id) = config.device_id
         && !device_id.is_empty()
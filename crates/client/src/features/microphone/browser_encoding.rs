@@ -5,6 +5,7 @@
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use cheenhub_contracts::media::audio_level_from_rms;
 use dioxus::prelude::{debug, warn};
 use js_sys::{Float32Array, Object, Reflect, Uint8Array};
 use wasm_bindgen::JsCast;
@@ -24,6 +25,7 @@ use super::browser_diagnostics::{
 };
 use super::browser_errors::js_error_message;
 use super::core::{apply_input_gain, duration_us};
+use super::processing::MicrophoneProcessor;
 use super::vad::{VoiceActivityDetector, rms_level};
 
 const MICROPHONE_ENCODER_QUEUE_WARN_FRAMES: u32 = 8;
@@ -33,6 +35,8 @@ pub(super) struct BrowserEncoder {
     pub(super) diagnostics: Rc<MicrophoneEncoderOutputDiagnostics>,
     pub(super) output_closure: Closure<dyn FnMut(EncodedAudioChunk)>,
     pub(super) error_closure: Closure<dyn FnMut(JsValue)>,
+    /// Уровень звука последнего кадра, отданного кодеру.
+    pub(super) audio_level: Rc<Cell<Option<u8>>>,
 }
 
 pub(super) fn create_encoder(
@@ -46,6 +50,8 @@ pub(super) fn create_encoder(
     let channels = config.channels;
     let output_diagnostics = Rc::new(MicrophoneEncoderOutputDiagnostics::new());
     let output_callback_diagnostics = output_diagnostics.clone();
+    let audio_level = Rc::new(Cell::new(None));
+    let output_audio_level = audio_level.clone();
     let output_closure = Closure::wrap(Box::new(move |chunk: EncodedAudioChunk| {
         let diagnostics_enabled = microphone_diagnostics_enabled();
         let started_at = diagnostics_enabled.then(Instant::now);
@@ -73,6 +79,7 @@ pub(super) fn create_encoder(
             sample_rate_hz,
             channels,
             bytes,
+            audio_level: output_audio_level.get(),
         });
         let on_frame_elapsed_us = elapsed_us_since(&on_frame_started_at);
         if diagnostics_enabled {
@@ -105,6 +112,7 @@ pub(super) fn create_encoder(
         diagnostics: output_diagnostics,
         output_closure,
         error_closure,
+        audio_level,
     })
 }
 
@@ -115,8 +123,10 @@ pub(super) fn microphone_message_closure(
     sample_rate_hz: u32,
     closed: Rc<Cell<bool>>,
     encoder_diagnostics: Rc<MicrophoneEncoderOutputDiagnostics>,
+    audio_level: Rc<Cell<Option<u8>>>,
 ) -> Closure<dyn FnMut(MessageEvent)> {
     let detector = Rc::new(RefCell::new(VoiceActivityDetector::new(config.clone())));
+    let processor = RefCell::new(MicrophoneProcessor::browser(&config, sample_rate_hz));
     let diagnostics = Rc::new(MicrophoneWorkletDiagnostics::new());
     Closure::wrap(Box::new(move |event: MessageEvent| {
         if closed.get() {
@@ -127,11 +137,13 @@ pub(super) fn microphone_message_closure(
             WorkletMessageContext {
                 encoder: &encoder,
                 detector: &detector,
+                processor: &processor,
                 callbacks: &callbacks,
                 sample_rate_hz,
                 input_gain: config.input_gain,
                 diagnostics: diagnostics.as_ref(),
                 encoder_diagnostics: encoder_diagnostics.as_ref(),
+                audio_level: audio_level.as_ref(),
             },
         ) {
             warn!(
@@ -142,7 +154,12 @@ pub(super) fn microphone_message_closure(
     }) as Box<dyn FnMut(MessageEvent)>)
 }
 
-pub(super) fn encoder_config(sample_rate_hz: u32, channels: u8, bitrate_bps: u32) -> JsValue {
+pub(super) fn encoder_config(
+    sample_rate_hz: u32,
+    channels: u8,
+    bitrate_bps: u32,
+    packet_loss_percent: u8,
+) -> JsValue {
     let object = Object::new();
     let _ = Reflect::set(
         &object,
@@ -170,7 +187,8 @@ pub(super) fn encoder_config(sample_rate_hz: u32, channels: u8, bitrate_bps: u32
     // - useinbandfec: in-band FEC встраивает сжатую копию предыдущего кадра, поэтому
     //   одиночная потеря пакета восстанавливается без заикания (главный выигрыш на
     //   нестабильном Wi-Fi/мобиле).
-    // - packetlossperc: подсказка кодеку, сколько избыточности FEC закладывать.
+    // - packetlossperc: подсказка кодеку, сколько избыточности FEC закладывать;
+    //   берется из потерь, о которых сервер сообщает по отчетам получателей.
     // - usedtx: во время тишины кодер почти не шлёт данные — экономит трафик; у нас
     //   уже есть собственный VAD, так что паузы безопасны.
     // - application=voip и frameDuration=20мс — профиль и размер кадра для речи
@@ -202,7 +220,7 @@ pub(super) fn encoder_config(sample_rate_hz: u32, channels: u8, bitrate_bps: u32
     let _ = Reflect::set(
         &opus,
         &JsValue::from_str("packetlossperc"),
-        &JsValue::from_f64(10.0),
+        &JsValue::from_f64(f64::from(packet_loss_percent)),
     );
     let _ = Reflect::set(&object, &JsValue::from_str("opus"), &opus);
 
@@ -246,6 +264,7 @@ fn handle_worklet_message(
         });
     let mut samples = chunk.samples;
     apply_input_gain(&mut samples, context.input_gain);
+    context.processor.borrow_mut().process(&mut samples, 0.0);
     let rms = rms_level(&samples);
     let previous_active = context.detector.borrow().is_active();
     let active = context.detector.borrow_mut().update(rms, duration_us);
@@ -286,6 +305,7 @@ fn handle_worklet_message(
         return Ok(());
     }
 
+    context.audio_level.set(Some(audio_level_from_rms(rms)));
     let audio_data_started_at = diagnostics_enabled.then(Instant::now);
     let audio = audio_data_from_samples(&samples, context.sample_rate_hz, chunk.timestamp_us)?;
     let audio_data_elapsed_us = elapsed_us_since(&audio_data_started_at);
@@ -335,11 +355,13 @@ fn handle_worklet_message(
 struct WorkletMessageContext<'a> {
     encoder: &'a AudioEncoder,
     detector: &'a Rc<RefCell<VoiceActivityDetector>>,
+    processor: &'a RefCell<MicrophoneProcessor>,
     callbacks: &'a MicrophoneCallbacks,
     sample_rate_hz: u32,
     input_gain: f32,
     diagnostics: &'a MicrophoneWorkletDiagnostics,
     encoder_diagnostics: &'a MicrophoneEncoderOutputDiagnostics,
+    audio_level: &'a Cell<Option<u8>>,
 }
 
 enum WorkletMessage {
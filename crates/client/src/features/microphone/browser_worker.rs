@@ -17,10 +17,11 @@ use super::backend::{
     MicrophoneLevel, MicrophoneLevelCallback, MicrophoneUplinkConfig,
 };
 use super::browser_errors::js_error_message;
+use super::processing::browser_processing_config;
 
-const MICROPHONE_UPLINK_WORKER_URL: &str = "/audio/microphone-uplink-worker.js?v=4";
-const MICROPHONE_WORKER_WASM_BINDGEN_URL: &str = "/workers/microphone/microphone_worker.js?v=3";
-const MICROPHONE_WORKER_WASM_URL: &str = "/workers/microphone/microphone_worker_bg.wasm?v=3";
+const MICROPHONE_UPLINK_WORKER_URL: &str = "/audio/microphone-uplink-worker.js?v=7";
+const MICROPHONE_WORKER_WASM_BINDGEN_URL: &str = "/workers/microphone/microphone_worker.js?v=5";
+const MICROPHONE_WORKER_WASM_URL: &str = "/workers/microphone/microphone_worker_bg.wasm?v=5";
 const WORKER_START_TIMEOUT_MS: u32 = 10_000;
 
 type WorkerReadySender = Rc<RefCell<Option<oneshot::Sender<Result<(), String>>>>>;
@@ -53,6 +54,29 @@ impl BrowserWorkerUplink {
             let _ = self.worker.post_message(message.as_ref());
         }
     }
+
+    pub(super) fn set_packet_loss_percent(&self, packet_loss_percent: u8) {
+        let message = Object::new();
+        if set_property(&message, "kind", JsValue::from_str("set-packet-loss")).is_ok()
+            && set_property(
+                &message,
+                "packetLossPercent",
+                JsValue::from_f64(f64::from(packet_loss_percent)),
+            )
+            .is_ok()
+        {
+            let _ = self.worker.post_message(message.as_ref());
+        }
+    }
+
+    pub(super) fn set_push_to_talk_pressed(&self, pressed: bool) {
+        let message = Object::new();
+        if set_property(&message, "kind", JsValue::from_str("set-push-to-talk")).is_ok()
+            && set_property(&message, "pressed", JsValue::from_bool(pressed)).is_ok()
+        {
+            let _ = self.worker.post_message(message.as_ref());
+        }
+    }
 }
 
 pub(super) async fn start_worker_uplink(
@@ -196,12 +220,19 @@ fn start_message(
         "bitrateBps",
         JsValue::from_f64(f64::from(config.bitrate_bps)),
     )?;
+    set_property(
+        &message,
+        "packetLossPercent",
+        JsValue::from_f64(f64::from(config.packet_loss_percent)),
+    )?;
     set_property(
         &message,
         "activationMode",
         JsValue::from_str(match config.activation_mode {
             MicrophoneActivationMode::AlwaysActive => "always_active",
             MicrophoneActivationMode::VoiceActivated => "voice_activated",
+            MicrophoneActivationMode::PushToTalk => "push_to_talk",
+            MicrophoneActivationMode::PushToMute => "push_to_mute",
         }),
     )?;
     set_property(
@@ -219,11 +250,37 @@ fn start_message(
         "vadReleaseDelayUs",
         JsValue::from_f64(f64::from(config.vad_release_delay_us)),
     )?;
+    set_property(
+        &message,
+        "pushToTalkReleaseDelayUs",
+        JsValue::from_f64(f64::from(config.push_to_talk_release_delay_us)),
+    )?;
+    set_property(
+        &message,
+        "pushToTalkPressed",
+        JsValue::from_bool(config.push_to_talk.is_pressed()),
+    )?;
     set_property(
         &message,
         "inputGain",
         JsValue::from_f64(f64::from(config.input_gain)),
     )?;
+    set_property(&message, "processing", processing_message(config)?.into())?;
+    Ok(message)
+}
+
+fn processing_message(config: &MicrophoneConfig) -> Result<Object, MicrophoneError> {
+    let processing = browser_processing_config(config.processing);
+    let message = Object::new();
+    for (name, enabled) in [
+        ("highPass", processing.high_pass),
+        ("echoSuppression", processing.echo_suppression),
+        ("noiseSuppression", processing.noise_suppression),
+        ("noiseGate", processing.noise_gate),
+        ("autoGain", processing.auto_gain),
+    ] {
+        set_property(&message, name, JsValue::from_bool(enabled))?;
+    }
     Ok(message)
 }
 
@@ -1,4 +1,8 @@
-//! Общая обработка PCM для browser main runtime, wasm worker и будущего native runtime.
+//! Общая обработка PCM для browser main runtime, wasm worker и native runtime.
+
+mod processing;
+
+pub(crate) use processing::{AudioProcessingChain, AudioProcessingConfig};
 
 const VAD_RELEASE_THRESHOLD_RATIO: f32 = 0.65;
 
@@ -9,6 +13,10 @@ pub(crate) enum CoreActivationMode {
     AlwaysActive,
     /// Пропускает PCM только при открытом voice activation gate.
     VoiceActivated,
+    /// Пропускает PCM, пока удерживается клавиша, и еще release tail после отпускания.
+    PushToTalk,
+    /// Пропускает PCM всегда, кроме времени удержания клавиши.
+    PushToMute,
 }
 
 /// Настройки общего voice activation gate.
@@ -22,6 +30,8 @@ pub(crate) struct VoiceActivationConfig {
     pub(crate) activation_delay_us: u32,
     /// Время удержания gate после падения сигнала.
     pub(crate) release_delay_us: u32,
+    /// Время удержания gate после отпускания клавиши push-to-talk.
+    pub(crate) key_release_delay_us: u32,
 }
 
 /// Stateful voice activation gate без зависимости от UI и platform API.
@@ -31,16 +41,23 @@ pub(crate) struct VoiceActivityDetector {
     active: bool,
     above_threshold_us: u32,
     below_threshold_us: u32,
+    key_pressed: bool,
+    key_released_us: u32,
 }
 
 impl VoiceActivityDetector {
     /// Создает detector с указанными настройками.
     pub(crate) fn new(config: VoiceActivationConfig) -> Self {
         Self {
-            active: matches!(config.mode, CoreActivationMode::AlwaysActive),
+            active: matches!(
+                config.mode,
+                CoreActivationMode::AlwaysActive | CoreActivationMode::PushToMute
+            ),
             config,
             above_threshold_us: 0,
             below_threshold_us: 0,
+            key_pressed: false,
+            key_released_us: 0,
         }
     }
 
@@ -49,10 +66,20 @@ impl VoiceActivityDetector {
         match self.config.mode {
             CoreActivationMode::AlwaysActive => self.active = true,
             CoreActivationMode::VoiceActivated => self.update_voice_activation(rms, duration_us),
+            CoreActivationMode::PushToTalk => self.update_push_to_talk(duration_us),
+            CoreActivationMode::PushToMute => self.active = !self.key_pressed,
         }
         self.active
     }
 
+    /// Сообщает detector текущее состояние клавиши push-to-talk.
+    pub(crate) fn set_key_pressed(&mut self, pressed: bool) {
+        if pressed {
+            self.key_released_us = 0;
+        }
+        self.key_pressed = pressed;
+    }
+
     /// Возвращает текущее состояние gate.
     #[allow(dead_code)]
     pub(crate) fn is_active(&self) -> bool {
@@ -65,6 +92,19 @@ impl VoiceActivityDetector {
         self.config
     }
 
+    /// Обновляет gate push-to-talk с release tail после отпускания клавиши.
+    fn update_push_to_talk(&mut self, duration_us: u32) {
+        if self.key_pressed {
+            self.active = true;
+            self.key_released_us = 0;
+        } else if self.active {
+            self.key_released_us = self.key_released_us.saturating_add(duration_us);
+            if self.key_released_us >= self.config.key_release_delay_us {
+                self.active = false;
+            }
+        }
+    }
+
     /// Обновляет состояние voice activation gate.
     fn update_voice_activation(&mut self, rms: f32, duration_us: u32) {
         let release_threshold = self.config.threshold * VAD_RELEASE_THRESHOLD_RATIO;
@@ -133,6 +173,7 @@ mod tests {
             threshold: 0.2,
             activation_delay_us: 40_000,
             release_delay_us: 80_000,
+            key_release_delay_us: 40_000,
         }
     }
 
@@ -146,6 +187,33 @@ mod tests {
         assert!(!detector.update(0.12, 40_000));
     }
 
+    #[test]
+    fn push_to_talk_follows_key_with_release_tail() {
+        let mut detector = VoiceActivityDetector::new(VoiceActivationConfig {
+            mode: CoreActivationMode::PushToTalk,
+            ..config()
+        });
+        assert!(!detector.update(0.5, 20_000));
+        detector.set_key_pressed(true);
+        assert!(detector.update(0.0, 20_000));
+        detector.set_key_pressed(false);
+        assert!(detector.update(0.0, 20_000));
+        assert!(!detector.update(0.0, 20_000));
+    }
+
+    #[test]
+    fn push_to_mute_closes_gate_while_key_is_held() {
+        let mut detector = VoiceActivityDetector::new(VoiceActivationConfig {
+            mode: CoreActivationMode::PushToMute,
+            ..config()
+        });
+        assert!(detector.is_active());
+        detector.set_key_pressed(true);
+        assert!(!detector.update(0.5, 20_000));
+        detector.set_key_pressed(false);
+        assert!(detector.update(0.0, 20_000));
+    }
+
     #[test]
     fn pcm_helpers_apply_gain_and_measure_rms() {
         let mut samples = [0.25, -0.25];
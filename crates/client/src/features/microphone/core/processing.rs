@@ -0,0 +1,289 @@
+//! Цепочка обработки PCM микрофона перед voice activation и кодированием.
+//!
+//! Стадии работают поблочно и не зависят от platform API, поэтому одна и та же цепочка
+//! используется native capture, browser main runtime и wasm worker.
+
+use std::f32::consts::PI;
+
+use super::rms_level;
+
+#[path = "processing/noise.rs"]
+mod noise;
+
+use noise::NoiseSuppressor;
+
+const HIGH_PASS_CUTOFF_HZ: f32 = 80.0;
+const HIGH_PASS_Q: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+const ECHO_FAR_END_ACTIVE_RMS: f32 = 0.01;
+const ECHO_FAR_END_RELEASE_US: f32 = 150_000.0;
+const ECHO_DOUBLE_TALK_RATIO: f32 = 1.0;
+const ECHO_SUPPRESSION_GAIN: f32 = 0.1;
+
+const NOISE_GATE_OPEN_RMS: f32 = 0.003;
+const NOISE_GATE_CLOSED_GAIN: f32 = 0.05;
+const NOISE_GATE_HOLD_US: u32 = 100_000;
+
+const AGC_TARGET_RMS: f32 = 0.1;
+const AGC_ACTIVITY_RMS: f32 = 0.005;
+const AGC_MIN_GAIN: f32 = 0.25;
+const AGC_MAX_GAIN: f32 = 8.0;
+const AGC_ADAPT_US: f32 = 500_000.0;
+
+const GAIN_ATTACK_US: f32 = 5_000.0;
+const GAIN_RELEASE_US: f32 = 50_000.0;
+
+/// Включённые стадии обработки PCM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct AudioProcessingConfig {
+    /// Срезает гул и низкочастотные удары ниже 80 Гц.
+    pub(crate) high_pass: bool,
+    /// Приглушает микрофон, пока звучит удалённый голос и ближний говорящий молчит.
+    pub(crate) echo_suppression: bool,
+    /// Подавляет стационарный шум по частотным полосам; задерживает сигнал на 256 сэмплов.
+    pub(crate) noise_suppression: bool,
+    /// Глушит сигнал ниже фиксированного порога между фразами.
+    pub(crate) noise_gate: bool,
+    /// Выравнивает громкость речи к целевому уровню.
+    pub(crate) auto_gain: bool,
+}
+
+/// Stateful цепочка: high-pass → echo suppression → noise suppression → gate → AGC.
+#[derive(Debug, Clone)]
+pub(crate) struct AudioProcessingChain {
+    config: AudioProcessingConfig,
+    sample_rate_hz: u32,
+    high_pass: HighPassFilter,
+    echo: EchoSuppressor,
+    noise: NoiseSuppressor,
+    gate: NoiseGate,
+    agc: AutomaticGainControl,
+}
+
+impl AudioProcessingChain {
+    /// Создаёт цепочку для указанной частоты дискретизации.
+    pub(crate) fn new(config: AudioProcessingConfig, sample_rate_hz: u32) -> Self {
+        Self {
+            config,
+            sample_rate_hz: sample_rate_hz.max(1),
+            high_pass: HighPassFilter::new(HIGH_PASS_CUTOFF_HZ, sample_rate_hz.max(1)),
+            echo: EchoSuppressor::default(),
+            noise: NoiseSuppressor::new(sample_rate_hz.max(1)),
+            gate: NoiseGate::default(),
+            agc: AutomaticGainControl::default(),
+        }
+    }
+
+    /// Возвращает включённые стадии.
+    #[allow(dead_code)]
+    pub(crate) fn config(&self) -> AudioProcessingConfig {
+        self.config
+    }
+
+    /// Обрабатывает PCM block на месте.
+    ///
+    /// `far_end_rms` — уровень воспроизводимого удалённого звука; `0.0`, если он неизвестен.
+    pub(crate) fn process(&mut self, samples: &mut [f32], far_end_rms: f32) {
+        if samples.is_empty() {
+            return;
+        }
+        let duration_us = super::duration_us(samples.len(), self.sample_rate_hz) as f32;
+        if self.config.high_pass {
+            self.high_pass.process(samples);
+        }
+        if self.config.echo_suppression {
+            self.echo.process(samples, far_end_rms, duration_us);
+        }
+        if self.config.noise_suppression {
+            self.noise.process(samples);
+        }
+        if self.config.noise_gate {
+            self.gate.process(samples, duration_us);
+        }
+        if self.config.auto_gain {
+            self.agc.process(samples, duration_us);
+        }
+    }
+}
+
+/// Butterworth high-pass второго порядка (RBJ biquad).
+#[derive(Debug, Clone)]
+struct HighPassFilter {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl HighPassFilter {
+    fn new(cutoff_hz: f32, sample_rate_hz: u32) -> Self {
+        let omega = 2.0 * PI * cutoff_hz / sample_rate_hz as f32;
+        let cos = omega.cos();
+        let alpha = omega.sin() / (2.0 * HIGH_PASS_Q);
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            x1: 0.0,
+            x2: 0.0,
+            y1: 0.0,
+            y2: 0.0,
+        }
+    }
+
+    fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            let input = *sample;
+            let output = self.b0 * input + self.b1 * self.x1 + self.b2 * self.x2
+                - self.a1 * self.y1
+                - self.a2 * self.y2;
+            self.x2 = self.x1;
+            self.x1 = input;
+            self.y2 = self.y1;
+            self.y1 = output;
+            *sample = output;
+        }
+    }
+}
+
+/// Приглушение эха по уровню удалённого звука без адаптивного фильтра.
+///
+/// Пока воспроизведение активно и ближний сигнал не громче удалённого (нет double talk),
+/// микрофон приглушается. Огибающая удалённого звука отпускается медленно, чтобы
+/// перекрыть задержку динамик → микрофон.
+#[derive(Debug, Clone)]
+struct EchoSuppressor {
+    far_end_envelope: f32,
+    gain: SmoothedGain,
+}
+
+impl Default for EchoSuppressor {
+    fn default() -> Self {
+        Self {
+            far_end_envelope: 0.0,
+            gain: SmoothedGain::new(1.0),
+        }
+    }
+}
+
+impl EchoSuppressor {
+    fn process(&mut self, samples: &mut [f32], far_end_rms: f32, duration_us: f32) {
+        let decay = 1.0 - smoothing(duration_us, ECHO_FAR_END_RELEASE_US);
+        self.far_end_envelope = far_end_rms.max(self.far_end_envelope * decay);
+        let near_end_rms = rms_level(samples);
+        let echo_only = self.far_end_envelope >= ECHO_FAR_END_ACTIVE_RMS
+            && near_end_rms < self.far_end_envelope * ECHO_DOUBLE_TALK_RATIO;
+        let target = if echo_only {
+            ECHO_SUPPRESSION_GAIN
+        } else {
+            1.0
+        };
+        self.gain.apply(samples, target, duration_us);
+    }
+}
+
+/// Шумовой порог с удержанием после последнего громкого блока.
+#[derive(Debug, Clone)]
+struct NoiseGate {
+    below_threshold_us: u32,
+    gain: SmoothedGain,
+}
+
+impl Default for NoiseGate {
+    fn default() -> Self {
+        Self {
+            below_threshold_us: NOISE_GATE_HOLD_US,
+            gain: SmoothedGain::new(NOISE_GATE_CLOSED_GAIN),
+        }
+    }
+}
+
+impl NoiseGate {
+    fn process(&mut self, samples: &mut [f32], duration_us: f32) {
+        if rms_level(samples) >= NOISE_GATE_OPEN_RMS {
+            self.below_threshold_us = 0;
+        } else {
+            self.below_threshold_us = self.below_threshold_us.saturating_add(duration_us as u32);
+        }
+        let target = if self.below_threshold_us < NOISE_GATE_HOLD_US {
+            1.0
+        } else {
+            NOISE_GATE_CLOSED_GAIN
+        };
+        self.gain.apply(samples, target, duration_us);
+    }
+}
+
+/// Автоматическая регулировка усиления речи к целевому RMS.
+#[derive(Debug, Clone)]
+struct AutomaticGainControl {
+    gain: f32,
+    applied: SmoothedGain,
+}
+
+impl Default for AutomaticGainControl {
+    fn default() -> Self {
+        Self {
+            gain: 1.0,
+            applied: SmoothedGain::new(1.0),
+        }
+    }
+}
+
+impl AutomaticGainControl {
+    fn process(&mut self, samples: &mut [f32], duration_us: f32) {
+        let rms = rms_level(samples);
+        if rms >= AGC_ACTIVITY_RMS {
+            let desired = (AGC_TARGET_RMS / rms).clamp(AGC_MIN_GAIN, AGC_MAX_GAIN);
+            self.gain += (desired - self.gain) * smoothing(duration_us, AGC_ADAPT_US);
+        }
+        self.applied.apply(samples, self.gain, duration_us);
+        for sample in samples {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+    }
+}
+
+/// Усиление с быстрой атакой вверх, плавным спадом и линейной интерполяцией внутри блока.
+#[derive(Debug, Clone)]
+struct SmoothedGain {
+    current: f32,
+}
+
+impl SmoothedGain {
+    fn new(current: f32) -> Self {
+        Self { current }
+    }
+
+    fn apply(&mut self, samples: &mut [f32], target: f32, duration_us: f32) {
+        let time_constant = if target > self.current {
+            GAIN_ATTACK_US
+        } else {
+            GAIN_RELEASE_US
+        };
+        let start = self.current;
+        let end = start + (target - start) * smoothing(duration_us, time_constant);
+        let step = (end - start) / samples.len().max(1) as f32;
+        for (index, sample) in samples.iter_mut().enumerate() {
+            *sample *= start + step * (index + 1) as f32;
+        }
+        self.current = end;
+    }
+}
+
+/// Доля шага экспоненциального сглаживания для блока длительностью `duration_us`.
+fn smoothing(duration_us: f32, time_constant_us: f32) -> f32 {
+    1.0 - (-duration_us / time_constant_us.max(1.0)).exp()
+}
+
+#[cfg(test)]
+#[path = "processing/tests.rs"]
+mod tests;
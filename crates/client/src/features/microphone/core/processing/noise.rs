@@ -0,0 +1,181 @@
+//! Спектральное подавление стационарного шума.
+//!
+//! Сигнал режется на окна по `FRAME` сэмплов с перекрытием в половину окна
+//! (sqrt-Hann на анализе и синтезе), и для каждой частотной полосы отдельно
+//! отслеживается шумовой пол. Усиление полосы считается Wiener-правилом с
+//! decision-directed оценкой априорного SNR, поэтому шум между гармониками
+//! голоса приглушается, даже пока человек говорит. Стадия задерживает сигнал
+//! на `FRAME` сэмплов.
+
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+use super::smoothing;
+
+/// Размер окна анализа; степень двойки для radix-2 FFT.
+pub(super) const FRAME: usize = 256;
+const HOP: usize = FRAME / 2;
+const BINS: usize = FRAME / 2 + 1;
+
+const NOISE_POWER_MIN: f32 = 1e-10;
+const POWER_SMOOTHING_US: f32 = 20_000.0;
+const NOISE_FLOOR_FALL_US: f32 = 50_000.0;
+const NOISE_FLOOR_RISE_US: f32 = 4_000_000.0;
+/// Минимум сглаженной мощности смещен вниз относительно среднего шума.
+const NOISE_FLOOR_BIAS: f32 = 1.5;
+const PRIOR_SNR_SMOOTHING: f32 = 0.9;
+const NOISE_SUPPRESSION_MIN_GAIN: f32 = 0.1;
+
+/// Подавление шума по полосам с overlap-add синтезом.
+#[derive(Debug, Clone)]
+pub(super) struct NoiseSuppressor {
+    hop_us: f32,
+    window: Vec<f32>,
+    pending: Vec<f32>,
+    analysis: Vec<f32>,
+    overlap: Vec<f32>,
+    ready: VecDeque<f32>,
+    bands: Option<Vec<Band>>,
+}
+
+/// Состояние одной частотной полосы.
+#[derive(Debug, Clone, Copy)]
+struct Band {
+    smoothed_power: f32,
+    noise_floor: f32,
+    previous_clean_power: f32,
+}
+
+impl NoiseSuppressor {
+    pub(super) fn new(sample_rate_hz: u32) -> Self {
+        let window = (0..FRAME)
+            .map(|index| (PI * index as f32 / FRAME as f32).sin())
+            .collect();
+        Self {
+            hop_us: super::super::duration_us(HOP, sample_rate_hz) as f32,
+            window,
+            pending: Vec::with_capacity(HOP),
+            analysis: vec![0.0; FRAME],
+            overlap: vec![0.0; FRAME],
+            ready: std::iter::repeat_n(0.0, HOP).collect(),
+            bands: None,
+        }
+    }
+
+    pub(super) fn process(&mut self, samples: &mut [f32]) {
+        for sample in samples {
+            self.pending.push(*sample);
+            if self.pending.len() == HOP {
+                self.process_hop();
+            }
+            *sample = self.ready.pop_front().unwrap_or(0.0);
+        }
+    }
+
+    fn process_hop(&mut self) {
+        self.analysis.copy_within(HOP.., 0);
+        self.analysis[HOP..].copy_from_slice(&self.pending);
+        self.pending.clear();
+
+        let mut re = self
+            .analysis
+            .iter()
+            .zip(&self.window)
+            .map(|(sample, window)| sample * window)
+            .collect::<Vec<_>>();
+        let mut im = vec![0.0; FRAME];
+        fft(&mut re, &mut im, false);
+
+        let gains = self.band_gains(&re, &im);
+        for bin in 0..FRAME {
+            let gain = gains[bin.min(FRAME - bin)];
+            re[bin] *= gain;
+            im[bin] *= gain;
+        }
+        fft(&mut re, &mut im, true);
+
+        for ((overlap, sample), window) in self.overlap.iter_mut().zip(&re).zip(&self.window) {
+            *overlap += sample * window / FRAME as f32;
+        }
+        self.ready.extend(&self.overlap[..HOP]);
+        self.overlap.copy_within(HOP.., 0);
+        self.overlap[HOP..].fill(0.0);
+    }
+
+    fn band_gains(&mut self, re: &[f32], im: &[f32]) -> [f32; BINS] {
+        let power_step = smoothing(self.hop_us, POWER_SMOOTHING_US);
+        let fall_step = smoothing(self.hop_us, NOISE_FLOOR_FALL_US);
+        let rise_step = smoothing(self.hop_us, NOISE_FLOOR_RISE_US);
+        let bands = self.bands.get_or_insert_with(|| {
+            (0..BINS)
+                .map(|bin| {
+                    let power = (re[bin] * re[bin] + im[bin] * im[bin]).max(NOISE_POWER_MIN);
+                    Band {
+                        smoothed_power: power,
+                        noise_floor: power,
+                        previous_clean_power: power,
+                    }
+                })
+                .collect()
+        });
+
+        let mut gains = [1.0; BINS];
+        for (bin, band) in bands.iter_mut().enumerate() {
+            let power = re[bin] * re[bin] + im[bin] * im[bin];
+            band.smoothed_power += (power - band.smoothed_power) * power_step;
+            let step = if band.smoothed_power < band.noise_floor {
+                fall_step
+            } else {
+                rise_step
+            };
+            band.noise_floor = (band.noise_floor + (band.smoothed_power - band.noise_floor) * step)
+                .max(NOISE_POWER_MIN);
+
+            let noise = band.noise_floor * NOISE_FLOOR_BIAS;
+            let posterior_snr = power / noise;
+            let prior_snr = PRIOR_SNR_SMOOTHING * band.previous_clean_power / noise
+                + (1.0 - PRIOR_SNR_SMOOTHING) * (posterior_snr - 1.0).max(0.0);
+            let gain = (prior_snr / (prior_snr + 1.0)).max(NOISE_SUPPRESSION_MIN_GAIN);
+            band.previous_clean_power = gain * gain * power;
+            gains[bin] = gain;
+        }
+        gains
+    }
+}
+
+/// Итеративное radix-2 FFT на месте; `inverse` не нормирует результат.
+fn fft(re: &mut [f32], im: &mut [f32], inverse: bool) {
+    let len = re.len();
+    let mut target = 0;
+    for index in 1..len {
+        let mut bit = len >> 1;
+        while target & bit != 0 {
+            target ^= bit;
+            bit >>= 1;
+        }
+        target |= bit;
+        if index < target {
+            re.swap(index, target);
+            im.swap(index, target);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut size = 2;
+    while size <= len {
+        let angle = sign * 2.0 * PI / size as f32;
+        for start in (0..len).step_by(size) {
+            for offset in 0..size / 2 {
+                let (sin, cos) = (angle * offset as f32).sin_cos();
+                let (even, odd) = (start + offset, start + offset + size / 2);
+                let odd_re = re[odd] * cos - im[odd] * sin;
+                let odd_im = re[odd] * sin + im[odd] * cos;
+                re[odd] = re[even] - odd_re;
+                im[odd] = im[even] - odd_im;
+                re[even] += odd_re;
+                im[even] += odd_im;
+            }
+        }
+        size <<= 1;
+    }
+}
@@ -0,0 +1,215 @@
+use super::*;
+
+const SAMPLE_RATE_HZ: u32 = 48_000;
+const BLOCK: usize = 960;
+
+fn sine(amplitude: f32, frequency_hz: f32, offset: usize) -> Vec<f32> {
+    (0..BLOCK)
+        .map(|index| {
+            let time = (offset + index) as f32 / SAMPLE_RATE_HZ as f32;
+            amplitude * (2.0 * PI * frequency_hz * time).sin()
+        })
+        .collect()
+}
+
+fn noise(amplitude: f32, seed: &mut u32) -> Vec<f32> {
+    (0..BLOCK)
+        .map(|_| {
+            *seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            amplitude * ((*seed >> 8) as f32 / (1 << 24) as f32 * 2.0 - 1.0)
+        })
+        .collect()
+}
+
+fn only(stage: impl FnOnce(&mut AudioProcessingConfig)) -> AudioProcessingChain {
+    let mut config = AudioProcessingConfig::default();
+    stage(&mut config);
+    AudioProcessingChain::new(config, SAMPLE_RATE_HZ)
+}
+
+#[test]
+fn disabled_chain_keeps_samples() {
+    let mut chain = AudioProcessingChain::new(AudioProcessingConfig::default(), SAMPLE_RATE_HZ);
+    let input = sine(0.3, 440.0, 0);
+    let mut output = input.clone();
+    chain.process(&mut output, 0.5);
+    assert_eq!(output, input);
+}
+
+#[test]
+fn high_pass_removes_dc_and_keeps_voice_band() {
+    let mut chain = only(|config| config.high_pass = true);
+    let mut dc = vec![0.5; BLOCK];
+    for _ in 0..10 {
+        dc = vec![0.5; BLOCK];
+        chain.process(&mut dc, 0.0);
+    }
+    assert!(rms_level(&dc) < 0.01);
+
+    let mut chain = only(|config| config.high_pass = true);
+    let mut voice = Vec::new();
+    for block in 0..10 {
+        voice = sine(0.3, 1_000.0, block * BLOCK);
+        chain.process(&mut voice, 0.0);
+    }
+    assert!((rms_level(&voice) - rms_level(&sine(0.3, 1_000.0, 0))).abs() < 0.01);
+}
+
+#[test]
+fn noise_suppression_attenuates_stationary_noise_and_keeps_speech() {
+    let mut chain = only(|config| config.noise_suppression = true);
+    let mut seed = 7;
+    let mut block = Vec::new();
+    for _ in 0..50 {
+        block = noise(0.02, &mut seed);
+        chain.process(&mut block, 0.0);
+    }
+    assert!(rms_level(&block) < 0.02 * 0.35);
+
+    for index in 0..5 {
+        let mut speech = sine(0.3, 300.0, index * BLOCK);
+        for (sample, noise) in speech.iter_mut().zip(noise(0.02, &mut seed)) {
+            *sample += noise;
+        }
+        block = speech;
+        chain.process(&mut block, 0.0);
+    }
+    assert!(rms_level(&block) > rms_level(&sine(0.3, 300.0, 0)) * 0.9);
+}
+
+#[test]
+fn noise_gate_closes_on_quiet_input_after_hold() {
+    let mut chain = only(|config| config.noise_gate = true);
+    let mut loud = Vec::new();
+    for index in 0..2 {
+        loud = sine(0.2, 440.0, index * BLOCK);
+        chain.process(&mut loud, 0.0);
+    }
+    assert!((rms_level(&loud) - rms_level(&sine(0.2, 440.0, 0))).abs() < 0.01);
+
+    let mut quiet = Vec::new();
+    for _ in 0..20 {
+        quiet = sine(0.002, 440.0, 0);
+        chain.process(&mut quiet, 0.0);
+    }
+    assert!(rms_level(&quiet) < 0.002 * 0.1);
+}
+
+#[test]
+fn auto_gain_moves_speech_towards_target() {
+    let mut chain = only(|config| config.auto_gain = true);
+    let mut quiet = Vec::new();
+    for index in 0..150 {
+        quiet = sine(0.03, 300.0, index * BLOCK);
+        chain.process(&mut quiet, 0.0);
+    }
+    assert!((rms_level(&quiet) - AGC_TARGET_RMS).abs() < 0.02);
+
+    let mut chain = only(|config| config.auto_gain = true);
+    let mut loud = Vec::new();
+    for index in 0..150 {
+        loud = sine(0.6, 300.0, index * BLOCK);
+        chain.process(&mut loud, 0.0);
+    }
+    assert!(rms_level(&loud) < 0.2);
+    assert!(loud.iter().all(|sample| sample.abs() <= 1.0));
+}
+
+#[test]
+fn auto_gain_does_not_boost_silence() {
+    let mut chain = only(|config| config.auto_gain = true);
+    let mut hiss = Vec::new();
+    let mut seed = 3;
+    for _ in 0..100 {
+        hiss = noise(0.002, &mut seed);
+        chain.process(&mut hiss, 0.0);
+    }
+    assert!(rms_level(&hiss) < 0.002);
+}
+
+#[test]
+fn echo_suppression_mutes_echo_but_passes_double_talk() {
+    let mut chain = only(|config| config.echo_suppression = true);
+    let mut echo = Vec::new();
+    for index in 0..10 {
+        echo = sine(0.05, 500.0, index * BLOCK);
+        chain.process(&mut echo, 0.2);
+    }
+    assert!(rms_level(&echo) < rms_level(&sine(0.05, 500.0, 0)) * 0.2);
+
+    let mut speech = Vec::new();
+    for index in 0..5 {
+        speech = sine(0.5, 300.0, index * BLOCK);
+        chain.process(&mut speech, 0.2);
+    }
+    assert!(rms_level(&speech) > rms_level(&sine(0.5, 300.0, 0)) * 0.9);
+
+    let mut chain = only(|config| config.echo_suppression = true);
+    let mut near_end = sine(0.05, 500.0, 0);
+    chain.process(&mut near_end, 0.0);
+    assert!((rms_level(&near_end) - rms_level(&sine(0.05, 500.0, 0))).abs() < 1e-4);
+}
+
+/// Гармонический «голос» с основным тоном 150 Гц и слогами по 200 мс.
+fn speech(length: usize) -> Vec<f32> {
+    (0..length)
+        .map(|index| {
+            let time = index as f32 / SAMPLE_RATE_HZ as f32;
+            let syllable = (time * 2.5 % 1.0) < 0.5;
+            if !syllable {
+                return 0.0;
+            }
+            (1..=12)
+                .map(|harmonic| {
+                    let amplitude = 0.05 / harmonic as f32;
+                    amplitude * (2.0 * PI * 150.0 * harmonic as f32 * time).sin()
+                })
+                .sum()
+        })
+        .collect()
+}
+
+#[test]
+fn spectral_noise_suppression_cleans_noise_between_harmonics_and_ignores_clicks() {
+    const CLICK_PERIOD: usize = 12_000;
+    let mut chain = only(|config| config.noise_suppression = true);
+    let clean = speech(250 * BLOCK);
+    let mut seed = 11;
+    let mut input = Vec::with_capacity(clean.len());
+    for block in clean.chunks(BLOCK) {
+        input.extend(
+            block
+                .iter()
+                .zip(noise(0.02, &mut seed))
+                .map(|(clean, noise)| clean + noise),
+        );
+    }
+    for click in input.iter_mut().step_by(CLICK_PERIOD) {
+        *click += 0.8;
+    }
+    let mut output = input.clone();
+    for block in output.chunks_mut(BLOCK) {
+        chain.process(block, 0.0);
+    }
+
+    // Первые 2 секунды уходят на оценку шумового пола; щелчки в сравнение не входят.
+    let mut speech_error = (0.0, 0.0);
+    let mut pause_error = (0.0, 0.0);
+    for index in (100 * BLOCK)..clean.len() - noise::FRAME {
+        let near_click = (index + noise::FRAME) % CLICK_PERIOD < 2 * noise::FRAME;
+        if near_click {
+            continue;
+        }
+        let processed = (output[index + noise::FRAME] - clean[index]).powi(2);
+        let unprocessed = (input[index] - clean[index]).powi(2);
+        let error = if clean[index] == 0.0 {
+            &mut pause_error
+        } else {
+            &mut speech_error
+        };
+        error.0 += processed;
+        error.1 += unprocessed;
+    }
+    assert!(speech_error.0 < speech_error.1 * 0.5);
+    assert!(pause_error.0 < pause_error.1 * 0.1);
+}
@@ -12,10 +12,13 @@ mod browser_worklet;
 mod core;
 mod input_devices;
 mod native;
+mod processing;
 mod provider;
 mod provider_context;
 mod provider_preferences;
+mod provider_push_to_talk;
 mod provider_runtime;
+mod push_to_talk;
 mod storage;
 #[cfg(not(any(
     feature = "web",
@@ -31,12 +34,23 @@ mod vad;
 pub(crate) use backend::{
     EncodedMicrophoneFrame, MicrophoneActivationMode, MicrophoneBackend, MicrophoneCallbacks,
     MicrophoneCodec, MicrophoneConfig, MicrophoneError, MicrophoneErrorCallback,
-    MicrophoneFrameCallback, MicrophoneLevel, MicrophoneLevelCallback, MicrophoneSession,
-    MicrophoneStatus, MicrophoneUplinkConfig,
+    MicrophoneFrameCallback, MicrophoneLevel, MicrophoneLevelCallback, MicrophoneProcessingConfig,
+    MicrophoneSession, MicrophoneStatus, MicrophoneUplinkConfig,
 };
 pub(crate) use input_devices::{
     AudioInputDevice, AudioInputDevicesResult, enumerate_audio_input_devices,
     request_microphone_permission,
 };
+#[cfg(any(
+    target_os = "android",
+    feature = "windows",
+    feature = "linux",
+    feature = "macos"
+))]
+pub(crate) use native::echo_reference::publish_playback_level;
 pub(crate) use provider::MicrophoneHandle;
 pub(crate) use provider_context::MicrophoneProvider;
+pub(crate) use push_to_talk::{
+    PUSH_TO_TALK_KEYS_HINT, is_supported_push_to_talk_key, push_to_talk_key_label,
+};
+pub(crate) use storage::MAX_PUSH_TO_TALK_RELEASE_DELAY_MS;
@@ -20,6 +20,13 @@ mod cpal_capture;
     feature = "macos"
 ))]
 pub(super) mod device_key;
+#[cfg(any(
+    target_os = "android",
+    feature = "windows",
+    feature = "linux",
+    feature = "macos"
+))]
+pub(super) mod echo_reference;
 
 /// Возвращает backend микрофона для текущей платформы.
 pub(super) fn default_backend() -> Rc<dyn MicrophoneBackend> {
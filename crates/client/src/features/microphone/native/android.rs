@@ -115,6 +115,13 @@ impl MicrophoneSession for AndroidMicrophoneSession {
     ) -> futures_util::future::LocalBoxFuture<'static, Result<(), MicrophoneError>> {
         self.inner.set_bitrate_bps(bitrate_bps)
     }
+
+    fn set_packet_loss_percent(
+        &self,
+        packet_loss_percent: u8,
+    ) -> futures_util::future::LocalBoxFuture<'static, Result<(), MicrophoneError>> {
+        self.inner.set_packet_loss_percent(packet_loss_percent)
+    }
 }
 
 fn android_error(error: impl std::fmt::Display) -> MicrophoneError {
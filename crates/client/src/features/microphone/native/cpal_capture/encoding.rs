@@ -1,9 +1,10 @@
 //! Native-кодирование PCM микрофона в Opus.
 
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering};
 use std::sync::{Arc, mpsc};
 use std::thread;
 
+use cheenhub_contracts::media::audio_level_from_rms;
 use dioxus::prelude::{debug, spawn, warn};
 use futures_channel::mpsc as local_mpsc;
 use futures_util::StreamExt;
@@ -13,7 +14,9 @@ use super::super::super::backend::{
     EncodedMicrophoneFrame, MicrophoneCallbacks, MicrophoneCodec, MicrophoneConfig,
     MicrophoneError, MicrophoneLevel,
 };
+use super::super::super::processing::MicrophoneProcessor;
 use super::super::super::vad::{VoiceActivityDetector, rms_level};
+use super::super::echo_reference::playback_level;
 
 const OPUS_FRAME_DURATION_US: u32 = 20_000;
 const MAX_OPUS_PACKET_BYTES: usize = 4_000;
@@ -24,6 +27,7 @@ pub(super) fn spawn_encoder_worker(
     event_sender: local_mpsc::UnboundedSender<NativeMicrophoneEvent>,
     closed: Arc<AtomicBool>,
     bitrate_bps: Arc<AtomicU32>,
+    packet_loss_percent: Arc<AtomicU8>,
     frame_samples: usize,
 ) {
     thread::Builder::new()
@@ -35,6 +39,7 @@ pub(super) fn spawn_encoder_worker(
                 event_sender,
                 closed,
                 bitrate_bps,
+                packet_loss_percent,
                 frame_samples,
             ) {
                 warn!(%error, "native microphone encoder worker stopped with error");
@@ -70,14 +75,17 @@ fn run_encoder_worker(
     mut event_sender: local_mpsc::UnboundedSender<NativeMicrophoneEvent>,
     closed: Arc<AtomicBool>,
     bitrate_bps: Arc<AtomicU32>,
+    packet_loss_percent: Arc<AtomicU8>,
     frame_samples: usize,
 ) -> Result<(), MicrophoneError> {
     let mut encoder = create_encoder(&config)?;
     let mut detector = VoiceActivityDetector::new(config.clone());
+    let mut processor = MicrophoneProcessor::native(&config);
     let mut pending = Vec::with_capacity(frame_samples * 2);
     let mut sequence = 0_u64;
     let mut captured_samples = 0_u64;
     let mut applied_bitrate = config.bitrate_bps;
+    let mut applied_packet_loss = config.packet_loss_percent;
 
     while !closed.load(Ordering::Relaxed) {
         let Ok(mut samples) = pcm_receiver.recv() else {
@@ -86,7 +94,9 @@ fn run_encoder_worker(
         pending.append(&mut samples);
 
         while pending.len() >= frame_samples {
-            let frame: Vec<f32> = pending.drain(..frame_samples).collect();
+            let mut frame: Vec<f32> = pending.drain(..frame_samples).collect();
+            apply_input_gain(&mut frame, config.input_gain);
+            processor.process(&mut frame, playback_level());
             let timestamp_us = timestamp_us(captured_samples, config.sample_rate_hz);
             captured_samples = captured_samples.saturating_add(frame_samples as u64);
             let next_bitrate = bitrate_bps.load(Ordering::Relaxed);
@@ -101,6 +111,18 @@ fn run_encoder_worker(
                 );
             }
 
+            let next_packet_loss = packet_loss_percent.load(Ordering::Relaxed);
+            if next_packet_loss != applied_packet_loss {
+                encoder
+                    .set_packet_loss_perc(i32::from(next_packet_loss))
+                    .map_err(opus_error)?;
+                applied_packet_loss = next_packet_loss;
+                debug!(
+                    packet_loss_percent = next_packet_loss,
+                    "native microphone opus packet loss updated"
+                );
+            }
+
             handle_pcm_frame(
                 &mut encoder,
                 &mut detector,
@@ -125,7 +147,9 @@ fn create_encoder(config: &MicrophoneConfig) -> Result<Encoder, MicrophoneError>
         .map_err(opus_error)?;
     encoder.set_signal(Signal::Voice).map_err(opus_error)?;
     encoder.set_inband_fec(true).map_err(opus_error)?;
-    encoder.set_packet_loss_perc(10).map_err(opus_error)?;
+    encoder
+        .set_packet_loss_perc(i32::from(config.packet_loss_percent))
+        .map_err(opus_error)?;
     encoder.set_dtx(true).map_err(opus_error)?;
     Ok(encoder)
 }
@@ -135,11 +159,10 @@ fn handle_pcm_frame(
     detector: &mut VoiceActivityDetector,
     event_sender: &mut local_mpsc::UnboundedSender<NativeMicrophoneEvent>,
     config: &MicrophoneConfig,
-    mut frame: Vec<f32>,
+    frame: Vec<f32>,
     sequence: u64,
     timestamp_us: u64,
 ) -> Result<(), MicrophoneError> {
-    apply_input_gain(&mut frame, config.input_gain);
     let rms = rms_level(&frame);
     let previous_active = detector.is_active();
     let active = detector.update(rms, OPUS_FRAME_DURATION_US);
@@ -180,6 +203,7 @@ fn handle_pcm_frame(
             sample_rate_hz: config.sample_rate_hz,
             channels: config.channels,
             bytes,
+            audio_level: Some(audio_level_from_rms(rms)),
         }),
     )
 }
@@ -5,7 +5,7 @@ mod samples;
 
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicU32, Ordering};
 use std::sync::{Arc, mpsc};
 
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
@@ -45,6 +45,7 @@ struct CpalMicrophoneSession {
     stream: RefCell<Option<Stream>>,
     closed: Arc<AtomicBool>,
     bitrate_bps: Arc<AtomicU32>,
+    packet_loss_percent: Arc<AtomicU8>,
 }
 
 impl MicrophoneSession for CpalMicrophoneSession {
@@ -70,6 +71,22 @@ impl MicrophoneSession for CpalMicrophoneSession {
         }
         .boxed_local()
     }
+
+    fn set_packet_loss_percent(
+        &self,
+        packet_loss_percent: u8,
+    ) -> futures_util::future::LocalBoxFuture<'static, Result<(), MicrophoneError>> {
+        self.packet_loss_percent
+            .store(packet_loss_percent, Ordering::Relaxed);
+        async move {
+            debug!(
+                packet_loss_percent,
+                "native microphone packet loss update queued"
+            );
+            Ok(())
+        }
+        .boxed_local()
+    }
 }
 
 async fn start_cpal_session(
@@ -91,6 +108,7 @@ async fn start_cpal_session(
     let (event_sender, event_receiver) = futures_channel::mpsc::unbounded();
     let closed = Arc::new(AtomicBool::new(false));
     let bitrate_bps = Arc::new(AtomicU32::new(config.bitrate_bps));
+    let packet_loss_percent = Arc::new(AtomicU8::new(config.packet_loss_percent));
 
     spawn_event_relay(event_receiver, callbacks);
     spawn_encoder_worker(
@@ -99,6 +117,7 @@ async fn start_cpal_session(
         event_sender,
         closed.clone(),
         bitrate_bps.clone(),
+        packet_loss_percent.clone(),
         frame_samples,
     );
 
@@ -123,6 +142,7 @@ async fn start_cpal_session(
         stream: RefCell::new(Some(stream)),
         closed,
         bitrate_bps,
+        packet_loss_percent,
     }))
 }
 
@@ -0,0 +1,15 @@
+//! Уровень воспроизводимого native-звука для подавления эха в захвате микрофона.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static PLAYBACK_LEVEL_BITS: AtomicU32 = AtomicU32::new(0);
+
+/// Публикует RMS последнего блока, отданного в native output.
+pub(crate) fn publish_playback_level(rms: f32) {
+    PLAYBACK_LEVEL_BITS.store(rms.max(0.0).to_bits(), Ordering::Relaxed);
+}
+
+/// Возвращает RMS последнего блока native output.
+pub(crate) fn playback_level() -> f32 {
+    f32::from_bits(PLAYBACK_LEVEL_BITS.load(Ordering::Relaxed))
+}
@@ -0,0 +1,88 @@
+//! Адаптер общей цепочки обработки PCM к настройкам microphone feature.
+
+use super::backend::{MicrophoneConfig, MicrophoneProcessingConfig};
+use super::core::{AudioProcessingChain, AudioProcessingConfig};
+
+/// Обработка PCM микрофона перед voice activation и кодированием.
+#[derive(Debug, Clone)]
+pub(crate) struct MicrophoneProcessor {
+    chain: AudioProcessingChain,
+}
+
+impl MicrophoneProcessor {
+    /// Полная цепочка для native capture, где платформа не обрабатывает звук сама.
+    #[allow(dead_code)]
+    pub(crate) fn native(config: &MicrophoneConfig) -> Self {
+        Self {
+            chain: AudioProcessingChain::new(
+                core_processing_config(config.processing),
+                config.sample_rate_hz,
+            ),
+        }
+    }
+
+    /// Цепочка для browser capture поверх voice processing `getUserMedia`.
+    #[allow(dead_code)]
+    pub(crate) fn browser(config: &MicrophoneConfig, sample_rate_hz: u32) -> Self {
+        Self {
+            chain: AudioProcessingChain::new(
+                browser_processing_config(config.processing),
+                sample_rate_hz,
+            ),
+        }
+    }
+
+    /// Обрабатывает PCM block; `far_end_rms` — уровень воспроизводимого звука.
+    pub(crate) fn process(&mut self, samples: &mut [f32], far_end_rms: f32) {
+        self.chain.process(samples, far_end_rms);
+    }
+}
+
+/// Переводит настройки микрофона в стадии общей цепочки.
+pub(crate) fn core_processing_config(
+    processing: MicrophoneProcessingConfig,
+) -> AudioProcessingConfig {
+    AudioProcessingConfig {
+        high_pass: processing.high_pass_filter,
+        echo_suppression: processing.echo_suppression,
+        noise_suppression: processing.noise_suppression,
+        noise_gate: processing.noise_gate,
+        auto_gain: processing.auto_gain_control,
+    }
+}
+
+/// Оставляет стадии, которых нет в browser voice processing.
+///
+/// Эхо, шум и усиление браузер обрабатывает через constraints `getUserMedia`.
+pub(crate) fn browser_processing_config(
+    processing: MicrophoneProcessingConfig,
+) -> AudioProcessingConfig {
+    AudioProcessingConfig {
+        echo_suppression: false,
+        noise_suppression: false,
+        auto_gain: false,
+        ..core_processing_config(processing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn browser_config_leaves_voice_processing_to_browser() {
+        let processing = MicrophoneProcessingConfig {
+            noise_gate: true,
+            ..MicrophoneProcessingConfig::default()
+        };
+
+        assert_eq!(
+            browser_processing_config(processing),
+            AudioProcessingConfig {
+                high_pass: true,
+                noise_gate: true,
+                ..AudioProcessingConfig::default()
+            }
+        );
+    }
+}
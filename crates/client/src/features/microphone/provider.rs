@@ -6,7 +6,8 @@ use dioxus::prelude::*;
 
 use super::backend::{
     MicrophoneActivationMode, MicrophoneBackend, MicrophoneConfig, MicrophoneFrameCallback,
-    MicrophoneLevel, MicrophoneSession, MicrophoneStatus, MicrophoneUplinkConfig,
+    MicrophoneLevel, MicrophoneProcessingConfig, MicrophoneSession, MicrophoneStatus,
+    MicrophoneUplinkConfig, PushToTalkKeyState,
 };
 use super::provider_runtime::{
     gain_from_percent, microphone_callbacks, next_generation, reset_level, status_from_error,
@@ -34,6 +35,16 @@ pub(crate) struct MicrophoneHandle {
     pub(super) input_volume_percent: Signal<u32>,
     pub(super) activation_mode: Signal<MicrophoneActivationMode>,
     pub(super) vad_threshold_percent: Signal<u32>,
+    /// Клавиша push-to-talk в формате `KeyboardEvent.code`.
+    pub(super) push_to_talk_key: Signal<String>,
+    pub(super) push_to_talk_release_delay_ms: Signal<u32>,
+    /// Состояние клавиши, которое activation gate читает на каждом кадре.
+    pub(super) push_to_talk: PushToTalkKeyState,
+    pub(super) processing: Signal<MicrophoneProcessingConfig>,
+    /// Битрейт кодировщика, разрешенный сервером текущей комнаты.
+    pub(super) bitrate_bps: Signal<u32>,
+    /// Потери голоса, о которых сервер сообщил по отчетам получателей.
+    pub(super) packet_loss_percent: Signal<u8>,
     pub(super) active_capture: Signal<ActiveCapture>,
     /// Last on_frame callback used to start/restart capture.
     /// Kept so that device changes during an active session can trigger a restart.
@@ -141,10 +152,7 @@ impl MicrophoneHandle {
         let mut active_capture = self.active_capture;
         let mut active_on_frame = self.active_on_frame;
         let mut active_uplink = self.active_uplink;
-        let device_id = self.selected_input_device_id.peek().clone();
-        let input_gain = gain_from_percent(*self.input_volume_percent.peek());
-        let activation_mode = *self.activation_mode.peek();
-        let vad_threshold = threshold_from_percent(*self.vad_threshold_percent.peek());
+        let config = self.capture_config();
         let start_generation = next_generation(&mut generation);
         status.set(MicrophoneStatus::Starting);
         active_capture.set(capture);
@@ -161,13 +169,6 @@ impl MicrophoneHandle {
                 status,
                 uplink.clone(),
             );
-            let config = MicrophoneConfig {
-                device_id,
-                input_gain,
-                activation_mode,
-                vad_threshold,
-                ..MicrophoneConfig::default()
-            };
             match backend.start(config, callbacks).await {
                 Ok(next_session) => {
                     if generation() != start_generation {
@@ -199,6 +200,25 @@ impl MicrophoneHandle {
         });
     }
 
+    /// Собирает конфигурацию capture из текущих настроек без подписки на signals.
+    fn capture_config(&self) -> MicrophoneConfig {
+        MicrophoneConfig {
+            device_id: self.selected_input_device_id.peek().clone(),
+            input_gain: gain_from_percent(*self.input_volume_percent.peek()),
+            activation_mode: *self.activation_mode.peek(),
+            vad_threshold: threshold_from_percent(*self.vad_threshold_percent.peek()),
+            bitrate_bps: *self.bitrate_bps.peek(),
+            packet_loss_percent: *self.packet_loss_percent.peek(),
+            push_to_talk_release_delay_us: self
+                .push_to_talk_release_delay_ms
+                .peek()
+                .saturating_mul(1_000),
+            push_to_talk: self.push_to_talk.clone(),
+            processing: *self.processing.peek(),
+            ..MicrophoneConfig::default()
+        }
+    }
+
     /// Restarts microphone capture with a fresh frame callback.
     #[allow(dead_code)]
     pub(crate) fn restart(&self, on_frame: MicrophoneFrameCallback) {
@@ -230,10 +250,7 @@ impl MicrophoneHandle {
         let mut active_capture = self.active_capture;
         let mut active_on_frame = self.active_on_frame;
         let mut active_uplink = self.active_uplink;
-        let device_id = self.selected_input_device_id.peek().clone();
-        let input_gain = gain_from_percent(*self.input_volume_percent.peek());
-        let activation_mode = *self.activation_mode.peek();
-        let vad_threshold = threshold_from_percent(*self.vad_threshold_percent.peek());
+        let config = self.capture_config();
         let restart_generation = next_generation(&mut generation);
         status.set(MicrophoneStatus::Starting);
         active_capture.set(capture);
@@ -259,13 +276,6 @@ impl MicrophoneHandle {
                 status,
                 uplink.clone(),
             );
-            let config = MicrophoneConfig {
-                device_id,
-                input_gain,
-                activation_mode,
-                vad_threshold,
-                ..MicrophoneConfig::default()
-            };
             match backend.start(config, callbacks).await {
                 Ok(next_session) => {
                     if generation() != restart_generation {
@@ -388,10 +398,14 @@ impl MicrophoneHandle {
         (self.level_active)()
     }
 
-    /// Updates the active encoder bitrate.
-    #[allow(dead_code)]
+    /// Updates the encoder bitrate for the active and future capture sessions.
     pub(crate) fn set_bitrate_bps(&self, bitrate_bps: u32) {
-        let Some(active_session) = (self.session)() else {
+        let mut stored_bitrate_bps = self.bitrate_bps;
+        if *stored_bitrate_bps.peek() == bitrate_bps {
+            return;
+        }
+        stored_bitrate_bps.set(bitrate_bps);
+        let Some(active_session) = self.session.peek().clone() else {
             return;
         };
 
@@ -401,6 +415,30 @@ impl MicrophoneHandle {
             }
         });
     }
+
+    /// Updates expected uplink loss for the active and future capture sessions.
+    pub(crate) fn set_packet_loss_percent(&self, packet_loss_percent: u8) {
+        let mut stored_packet_loss_percent = self.packet_loss_percent;
+        if *stored_packet_loss_percent.peek() == packet_loss_percent {
+            return;
+        }
+        stored_packet_loss_percent.set(packet_loss_percent);
+        let Some(active_session) = self.session.peek().clone() else {
+            return;
+        };
+
+        spawn(async move {
+            if let Err(error) = active_session
+                .set_packet_loss_percent(packet_loss_percent)
+                .await
+            {
+                warn!(
+                    %error,
+                    packet_loss_percent, "failed to update microphone packet loss"
+                );
+            }
+        });
+    }
 }
 
 fn should_start_level_preview(status: &MicrophoneStatus, active_capture: ActiveCapture) -> bool {
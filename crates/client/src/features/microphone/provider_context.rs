@@ -5,11 +5,13 @@ use std::rc::Rc;
 use dioxus::prelude::*;
 
 use super::backend::{
-    MicrophoneFrameCallback, MicrophoneSession, MicrophoneStatus, MicrophoneUplinkConfig,
+    MicrophoneConfig, MicrophoneFrameCallback, MicrophoneSession, MicrophoneStatus,
+    MicrophoneUplinkConfig, PushToTalkKeyState,
 };
 use super::native::default_backend;
 use super::provider::{ActiveCapture, MicrophoneHandle};
 use super::provider_runtime::default_level;
+use super::push_to_talk::PushToTalkListener;
 use super::storage;
 
 /// Provides microphone capture state to authenticated app components.
@@ -34,6 +36,12 @@ pub(crate) fn MicrophoneProvider(children: Element) -> Element {
     let input_volume_percent = use_signal(storage::load_input_volume_percent);
     let activation_mode = use_signal(storage::load_activation_mode);
     let vad_threshold_percent = use_signal(storage::load_vad_threshold_percent);
+    let push_to_talk_key = use_signal(storage::load_push_to_talk_key);
+    let push_to_talk_release_delay_ms = use_signal(storage::load_push_to_talk_release_delay_ms);
+    let push_to_talk = use_hook(PushToTalkKeyState::default);
+    let processing = use_signal(storage::load_processing);
+    let bitrate_bps = use_signal(|| MicrophoneConfig::default().bitrate_bps);
+    let packet_loss_percent = use_signal(|| MicrophoneConfig::default().packet_loss_percent);
     let active_capture = use_signal(|| ActiveCapture::None);
     let active_on_frame = use_signal(|| None::<MicrophoneFrameCallback>);
     let active_uplink = use_signal(|| None::<MicrophoneUplinkConfig>);
@@ -50,6 +58,12 @@ pub(crate) fn MicrophoneProvider(children: Element) -> Element {
         input_volume_percent,
         activation_mode,
         vad_threshold_percent,
+        push_to_talk_key,
+        push_to_talk_release_delay_ms,
+        push_to_talk,
+        processing,
+        bitrate_bps,
+        packet_loss_percent,
         active_capture,
         active_on_frame,
         active_uplink,
@@ -57,6 +71,7 @@ pub(crate) fn MicrophoneProvider(children: Element) -> Element {
     use_context_provider(move || handle.clone());
 
     rsx! {
+        PushToTalkListener {}
         {children}
     }
 }
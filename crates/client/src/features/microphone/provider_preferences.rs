@@ -2,7 +2,7 @@
 
 use dioxus::prelude::*;
 
-use super::backend::{MicrophoneActivationMode, MicrophoneStatus};
+use super::backend::{MicrophoneActivationMode, MicrophoneProcessingConfig, MicrophoneStatus};
 use super::input_devices::AudioInputDevice;
 use super::provider::MicrophoneHandle;
 use super::{provider_runtime::persist_input_device, storage};
@@ -156,6 +156,29 @@ impl MicrophoneHandle {
         self.restart_if_active(status, "microphone vad threshold change");
     }
 
+    /// Возвращает включённые стадии обработки микрофона.
+    pub(crate) fn processing(&self) -> MicrophoneProcessingConfig {
+        (self.processing)()
+    }
+
+    /// Обновляет стадии обработки микрофона и перезапускает активный захват.
+    pub(crate) fn set_processing(&self, processing: MicrophoneProcessingConfig) {
+        if *self.processing.peek() == processing {
+            return;
+        }
+
+        let status = self.status_untracked();
+        info!(
+            ?status,
+            ?processing,
+            "microphone processing preference changed"
+        );
+        storage::save_processing(processing);
+        let mut processing_signal = self.processing;
+        processing_signal.set(processing);
+        self.restart_if_active(status, "microphone processing change");
+    }
+
     fn restart_callback(
         &self,
         status: MicrophoneStatus,
@@ -165,7 +188,7 @@ impl MicrophoneHandle {
             .flatten()
     }
 
-    fn restart_if_active(&self, status: MicrophoneStatus, reason: &'static str) {
+    pub(super) fn restart_if_active(&self, status: MicrophoneStatus, reason: &'static str) {
         if let Some(on_frame) = self.restart_callback(status.clone()) {
             let active_capture = *self.active_capture.peek();
             info!(?status, reason, "restarting microphone capture");
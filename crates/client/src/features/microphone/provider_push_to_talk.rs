@@ -0,0 +1,67 @@
+//! Настройки push-to-talk и передача состояния клавиши в capture-сессию.
+
+use dioxus::prelude::*;
+
+use super::provider::MicrophoneHandle;
+use super::push_to_talk::is_supported_push_to_talk_key;
+use super::storage;
+
+impl MicrophoneHandle {
+    /// Возвращает клавишу push-to-talk в формате `KeyboardEvent.code`.
+    pub(crate) fn push_to_talk_key(&self) -> String {
+        (self.push_to_talk_key)()
+    }
+
+    /// Сохраняет клавишу push-to-talk; неподдерживаемые клавиши игнорируются.
+    pub(crate) fn set_push_to_talk_key(&self, key: &str) {
+        if !is_supported_push_to_talk_key(key) {
+            warn!(key, "ignoring unsupported microphone push-to-talk key");
+            return;
+        }
+        if self.push_to_talk_key.peek().as_str() == key {
+            return;
+        }
+
+        info!(key, "microphone push-to-talk key preference changed");
+        storage::save_push_to_talk_key(key);
+        self.set_push_to_talk_pressed(false);
+        let mut push_to_talk_key = self.push_to_talk_key;
+        push_to_talk_key.set(key.to_owned());
+    }
+
+    /// Возвращает release tail push-to-talk в миллисекундах.
+    pub(crate) fn push_to_talk_release_delay_ms(&self) -> u32 {
+        (self.push_to_talk_release_delay_ms)()
+    }
+
+    /// Обновляет release tail push-to-talk в миллисекундах.
+    pub(crate) fn set_push_to_talk_release_delay_ms(&self, delay_ms: u32) {
+        let delay_ms = delay_ms.min(storage::MAX_PUSH_TO_TALK_RELEASE_DELAY_MS);
+        if *self.push_to_talk_release_delay_ms.peek() == delay_ms {
+            return;
+        }
+
+        let status = self.status_untracked();
+        info!(
+            ?status,
+            delay_ms, "microphone push-to-talk release delay preference changed"
+        );
+        storage::save_push_to_talk_release_delay_ms(delay_ms);
+        let mut release_delay = self.push_to_talk_release_delay_ms;
+        release_delay.set(delay_ms);
+        self.restart_if_active(status, "microphone push-to-talk release delay change");
+    }
+
+    /// Передаёт состояние клавиши push-to-talk в activation gate.
+    pub(crate) fn set_push_to_talk_pressed(&self, pressed: bool) {
+        if self.push_to_talk.is_pressed() == pressed {
+            return;
+        }
+
+        debug!(pressed, "microphone push-to-talk key state changed");
+        self.push_to_talk.set_pressed(pressed);
+        if let Some(session) = self.session.peek().as_ref() {
+            session.set_push_to_talk_pressed(pressed);
+        }
+    }
+}
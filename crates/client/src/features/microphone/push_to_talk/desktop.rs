@@ -0,0 +1,30 @@
+//! Desktop-привязка push-to-talk через global hotkey, работающий без фокуса окна.
+
+use dioxus::desktop::{HotKeyState, use_global_shortcut};
+use dioxus::prelude::*;
+
+use super::PushToTalkReleaseGuard;
+use crate::features::microphone::MicrophoneHandle;
+
+/// Регистрирует global hotkey для клавиши push-to-talk.
+///
+/// Набор клавиш ограничен `is_supported_push_to_talk_key`: буквы и цифры сюда
+/// не попадают, иначе они пропали бы из набора текста во всей системе.
+#[component]
+pub(super) fn PushToTalkBinding(code: String) -> Element {
+    let mic = use_context::<MicrophoneHandle>();
+    let handler_mic = mic.clone();
+    let registration = use_global_shortcut(code.as_str(), move |state| {
+        handler_mic.set_push_to_talk_pressed(matches!(state, HotKeyState::Pressed));
+    });
+    use_hook(move || {
+        match &registration {
+            Ok(_) => info!(key = %code, "registered push-to-talk global hotkey"),
+            Err(error) => {
+                warn!(key = %code, ?error, "failed to register push-to-talk global hotkey");
+            }
+        }
+        PushToTalkReleaseGuard { mic }
+    });
+    rsx! {}
+}
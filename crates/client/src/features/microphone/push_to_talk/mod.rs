@@ -0,0 +1,219 @@
+//! Push-to-talk: поддерживаемые клавиши и platform-привязка к состоянию клавиши.
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(target_os = "android"),
+    feature = "desktop"
+))]
+mod desktop;
+#[cfg(not(any(
+    target_arch = "wasm32",
+    all(not(target_os = "android"), feature = "desktop")
+)))]
+mod unsupported;
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+use dioxus::prelude::*;
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    not(target_os = "android"),
+    feature = "desktop"
+))]
+use desktop::PushToTalkBinding;
+#[cfg(not(any(
+    target_arch = "wasm32",
+    all(not(target_os = "android"), feature = "desktop")
+)))]
+use unsupported::PushToTalkBinding;
+#[cfg(target_arch = "wasm32")]
+use web::PushToTalkBinding;
+
+use super::backend::MicrophoneActivationMode;
+use super::provider::MicrophoneHandle;
+
+/// Клавиши без модификаторов, которые одинаково понимают browser и global hotkey.
+const NAMED_KEYS: &[&str] = &[
+    "Backquote",
+    "Space",
+    "CapsLock",
+    "ScrollLock",
+    "Pause",
+    "Insert",
+    "Home",
+    "End",
+    "PageUp",
+    "PageDown",
+];
+
+/// Клавиши, которые не участвуют в наборе текста и безопасны для global hotkey.
+const NON_TYPING_NAMED_KEYS: &[&str] = &["ScrollLock", "Pause", "Insert"];
+
+/// Перехватывает ли клиент клавишу системно, а не только в фокусе окна.
+///
+/// Global hotkey забирает клавишу у всех приложений, поэтому буквы, цифры
+/// и навигация по тексту в desktop-клиенте недоступны.
+const GLOBAL_HOTKEY: bool = cfg!(all(
+    not(target_arch = "wasm32"),
+    not(target_os = "android"),
+    feature = "desktop"
+));
+
+/// Подсказка о допустимых клавишах для текущей платформы.
+pub(crate) const PUSH_TO_TALK_KEYS_HINT: &str = if GLOBAL_HOTKEY {
+    "F1–F24, Scroll Lock, Pause или Insert: клавиша перехватывается во всей системе и не должна мешать набору текста"
+} else {
+    "буквы, цифры, F1–F24 или пробел без модификаторов"
+};
+
+/// Проверяет, можно ли назначить `KeyboardEvent.code` клавишей push-to-talk.
+pub(crate) fn is_supported_push_to_talk_key(code: &str) -> bool {
+    is_push_to_talk_key(code, GLOBAL_HOTKEY)
+}
+
+fn is_push_to_talk_key(code: &str, global_hotkey: bool) -> bool {
+    if global_hotkey {
+        return NON_TYPING_NAMED_KEYS.contains(&code) || is_function_key(code);
+    }
+    if NAMED_KEYS.contains(&code) {
+        return true;
+    }
+    if let Some(letter) = code.strip_prefix("Key") {
+        return letter.len() == 1 && letter.bytes().all(|byte| byte.is_ascii_uppercase());
+    }
+    if let Some(digit) = code
+        .strip_prefix("Digit")
+        .or_else(|| code.strip_prefix("Numpad"))
+    {
+        return digit.len() == 1 && digit.bytes().all(|byte| byte.is_ascii_digit());
+    }
+    is_function_key(code)
+}
+
+fn is_function_key(code: &str) -> bool {
+    code.strip_prefix('F')
+        .filter(|number| {
+            !number.starts_with('0') && number.bytes().all(|byte| byte.is_ascii_digit())
+        })
+        .and_then(|number| number.parse::<u8>().ok())
+        .is_some_and(|number| (1..=24).contains(&number))
+}
+
+/// Возвращает читаемое название клавиши push-to-talk для настроек.
+pub(crate) fn push_to_talk_key_label(code: &str) -> String {
+    if let Some(letter) = code.strip_prefix("Key") {
+        return letter.to_owned();
+    }
+    if let Some(digit) = code.strip_prefix("Digit") {
+        return digit.to_owned();
+    }
+    if let Some(digit) = code.strip_prefix("Numpad") {
+        return format!("Num {digit}");
+    }
+    match code {
+        "Backquote" => "`".to_owned(),
+        "Space" => "Пробел".to_owned(),
+        "CapsLock" => "Caps Lock".to_owned(),
+        "ScrollLock" => "Scroll Lock".to_owned(),
+        "PageUp" => "Page Up".to_owned(),
+        "PageDown" => "Page Down".to_owned(),
+        _ => code.to_owned(),
+    }
+}
+
+/// Отслеживает клавишу push-to-talk, пока выбран режим активации по клавише.
+#[component]
+pub(crate) fn PushToTalkListener() -> Element {
+    let mic = use_context::<MicrophoneHandle>();
+    if !matches!(
+        mic.activation_mode(),
+        MicrophoneActivationMode::PushToTalk | MicrophoneActivationMode::PushToMute
+    ) {
+        return rsx! {};
+    }
+
+    let code = mic.push_to_talk_key();
+    rsx! {
+        PushToTalkBinding { key: "{code}", code: code.clone() }
+    }
+}
+
+/// Отпускает клавишу push-to-talk, когда platform-привязка снимается.
+#[cfg_attr(
+    not(any(
+        target_arch = "wasm32",
+        all(not(target_os = "android"), feature = "desktop")
+    )),
+    allow(dead_code)
+)]
+struct PushToTalkReleaseGuard {
+    mic: MicrophoneHandle,
+}
+
+impl Drop for PushToTalkReleaseGuard {
+    fn drop(&mut self) {
+        self.mic.set_push_to_talk_pressed(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_keys_supported_by_browser() {
+        for code in ["KeyV", "Digit0", "Numpad5", "F1", "F8", "F24", "Backquote"] {
+            assert!(is_push_to_talk_key(code, false), "{code}");
+        }
+    }
+
+    #[test]
+    fn global_hotkey_accepts_only_non_typing_keys() {
+        for code in ["F1", "F13", "F24", "ScrollLock", "Pause", "Insert"] {
+            assert!(is_push_to_talk_key(code, true), "{code}");
+        }
+        for code in [
+            "KeyV",
+            "Digit0",
+            "Numpad5",
+            "Backquote",
+            "Space",
+            "CapsLock",
+            "Home",
+            "PageDown",
+        ] {
+            assert!(!is_push_to_talk_key(code, true), "{code}");
+        }
+    }
+
+    #[test]
+    fn rejects_modifiers_and_unknown_keys() {
+        for code in [
+            "ShiftLeft",
+            "ControlRight",
+            "MetaLeft",
+            "Keya",
+            "KeyAB",
+            "F0",
+            "F25",
+            "F08",
+            "F+8",
+            "NumpadAdd",
+            "",
+        ] {
+            for global_hotkey in [false, true] {
+                assert!(!is_push_to_talk_key(code, global_hotkey), "{code}");
+            }
+        }
+    }
+
+    #[test]
+    fn formats_key_labels() {
+        assert_eq!(push_to_talk_key_label("KeyV"), "V");
+        assert_eq!(push_to_talk_key_label("Digit3"), "3");
+        assert_eq!(push_to_talk_key_label("Numpad3"), "Num 3");
+        assert_eq!(push_to_talk_key_label("F8"), "F8");
+        assert_eq!(push_to_talk_key_label("Space"), "Пробел");
+    }
+}
@@ -0,0 +1,10 @@
+//! Заглушка push-to-talk для платформ без отслеживания клавиатуры.
+
+use dioxus::prelude::*;
+
+/// Не отслеживает клавишу: платформа не даёт ни DOM-событий, ни global hotkey.
+#[component]
+pub(super) fn PushToTalkBinding(code: String) -> Element {
+    use_hook(|| debug!(key = %code, "push-to-talk key tracking is unsupported on this platform"));
+    rsx! {}
+}
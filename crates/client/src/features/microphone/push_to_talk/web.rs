@@ -0,0 +1,93 @@
+//! Browser-привязка push-to-talk через keyboard events окна.
+
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen::prelude::*;
+use web_sys::{Event, KeyboardEvent, Window};
+
+use super::PushToTalkReleaseGuard;
+use crate::features::microphone::MicrophoneHandle;
+
+/// Слушает нажатие и отпускание клавиши push-to-talk, пока вкладка в фокусе.
+#[component]
+pub(super) fn PushToTalkBinding(code: String) -> Element {
+    let mic = use_context::<MicrophoneHandle>();
+    use_hook(move || Rc::new(KeyboardListeners::attach(code, mic)));
+    rsx! {}
+}
+
+struct KeyboardListeners {
+    window: Option<Window>,
+    keydown: Closure<dyn FnMut(KeyboardEvent)>,
+    keyup: Closure<dyn FnMut(KeyboardEvent)>,
+    blur: Closure<dyn FnMut(Event)>,
+    _release: PushToTalkReleaseGuard,
+}
+
+impl KeyboardListeners {
+    fn attach(code: String, mic: MicrophoneHandle) -> Self {
+        let keydown = key_closure(code.clone(), mic.clone(), true);
+        let keyup = key_closure(code.clone(), mic.clone(), false);
+        let blur_mic = mic.clone();
+        let blur = Closure::wrap(Box::new(move |_: Event| {
+            blur_mic.set_push_to_talk_pressed(false);
+        }) as Box<dyn FnMut(Event)>);
+
+        let window = web_sys::window();
+        if let Some(window) = &window {
+            let attached = [
+                ("keydown", keydown.as_ref()),
+                ("keyup", keyup.as_ref()),
+                ("blur", blur.as_ref()),
+            ]
+            .into_iter()
+            .all(|(kind, listener)| {
+                window
+                    .add_event_listener_with_callback(kind, listener.unchecked_ref())
+                    .is_ok()
+            });
+            if attached {
+                info!(key = %code, "attached push-to-talk keyboard listeners");
+            } else {
+                warn!(key = %code, "failed to attach push-to-talk keyboard listeners");
+            }
+        }
+
+        Self {
+            window,
+            keydown,
+            keyup,
+            blur,
+            _release: PushToTalkReleaseGuard { mic },
+        }
+    }
+}
+
+impl Drop for KeyboardListeners {
+    fn drop(&mut self) {
+        let Some(window) = &self.window else {
+            return;
+        };
+        for (kind, listener) in [
+            ("keydown", self.keydown.as_ref()),
+            ("keyup", self.keyup.as_ref()),
+            ("blur", self.blur.as_ref()),
+        ] {
+            let _ = window.remove_event_listener_with_callback(kind, listener.unchecked_ref());
+        }
+    }
+}
+
+fn key_closure(
+    code: String,
+    mic: MicrophoneHandle,
+    pressed: bool,
+) -> Closure<dyn FnMut(KeyboardEvent)> {
+    Closure::wrap(Box::new(move |event: KeyboardEvent| {
+        if event.code() == code && !event.repeat() {
+            mic.set_push_to_talk_pressed(pressed);
+        }
+    }) as Box<dyn FnMut(KeyboardEvent)>)
+}
@@ -3,15 +3,29 @@
 use dioxus::prelude::{info, warn};
 use dioxus_sdk_storage::{LocalStorage, StorageBacking};
 
-use super::backend::MicrophoneActivationMode;
+use super::backend::{MicrophoneActivationMode, MicrophoneProcessingConfig};
+use super::push_to_talk::is_supported_push_to_talk_key;
 
 const INPUT_DEVICE_ID_KEY: &str = "cheenhub.microphone.input_device_id";
 const INPUT_DEVICE_LABEL_KEY: &str = "cheenhub.microphone.input_device_label";
 const INPUT_VOLUME_PERCENT_KEY: &str = "cheenhub.microphone.input_volume_percent";
 const ACTIVATION_MODE_KEY: &str = "cheenhub.microphone.activation_mode";
 const VAD_THRESHOLD_PERCENT_KEY: &str = "cheenhub.microphone.vad_threshold_percent";
+const PUSH_TO_TALK_KEY_KEY: &str = "cheenhub.microphone.push_to_talk_key";
+const PUSH_TO_TALK_RELEASE_DELAY_MS_KEY: &str = "cheenhub.microphone.push_to_talk_release_delay_ms";
+const HIGH_PASS_FILTER_KEY: &str = "cheenhub.microphone.processing.high_pass_filter";
+const ECHO_CANCELLATION_KEY: &str = "cheenhub.microphone.processing.echo_suppression";
+const NOISE_SUPPRESSION_KEY: &str = "cheenhub.microphone.processing.noise_suppression";
+const NOISE_GATE_KEY: &str = "cheenhub.microphone.processing.noise_gate";
+const AUTO_GAIN_CONTROL_KEY: &str = "cheenhub.microphone.processing.auto_gain_control";
 const DEFAULT_INPUT_VOLUME_PERCENT: u32 = 100;
 const DEFAULT_VAD_THRESHOLD_PERCENT: u32 = 20;
+/// Клавиша push-to-talk по умолчанию в формате `KeyboardEvent.code`.
+pub(crate) const DEFAULT_PUSH_TO_TALK_KEY: &str = "F8";
+/// Release tail push-to-talk по умолчанию.
+pub(crate) const DEFAULT_PUSH_TO_TALK_RELEASE_DELAY_MS: u32 = 200;
+/// Максимальный release tail push-to-talk.
+pub(crate) const MAX_PUSH_TO_TALK_RELEASE_DELAY_MS: u32 = 1_000;
 
 /// Stored microphone input device preference.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -87,6 +101,8 @@ pub(crate) fn save_input_volume_percent(volume_percent: u32) {
 pub(crate) fn load_activation_mode() -> MicrophoneActivationMode {
     let mode = match get::<LocalStorage>(ACTIVATION_MODE_KEY).as_deref() {
         Some("always_on") => MicrophoneActivationMode::AlwaysActive,
+        Some("push_to_talk") => MicrophoneActivationMode::PushToTalk,
+        Some("push_to_mute") => MicrophoneActivationMode::PushToMute,
         _ => MicrophoneActivationMode::VoiceActivated,
     };
     info!(?mode, "loaded microphone activation mode preference");
@@ -98,6 +114,8 @@ pub(crate) fn save_activation_mode(mode: MicrophoneActivationMode) {
     let value = match mode {
         MicrophoneActivationMode::AlwaysActive => "always_on",
         MicrophoneActivationMode::VoiceActivated => "voice_activation",
+        MicrophoneActivationMode::PushToTalk => "push_to_talk",
+        MicrophoneActivationMode::PushToMute => "push_to_mute",
     };
     set::<LocalStorage>(ACTIVATION_MODE_KEY, value);
     info!(?mode, "saved microphone activation mode preference");
@@ -123,6 +141,84 @@ pub(crate) fn save_vad_threshold_percent(threshold_percent: u32) {
     );
 }
 
+/// Loads the push-to-talk key binding as a `KeyboardEvent.code` value.
+pub(crate) fn load_push_to_talk_key() -> String {
+    let key = get::<LocalStorage>(PUSH_TO_TALK_KEY_KEY)
+        .filter(|key| is_supported_push_to_talk_key(key))
+        .unwrap_or_else(|| DEFAULT_PUSH_TO_TALK_KEY.to_owned());
+    info!(key = %key, "loaded microphone push-to-talk key preference");
+    key
+}
+
+/// Saves the push-to-talk key binding as a `KeyboardEvent.code` value.
+pub(crate) fn save_push_to_talk_key(key: &str) {
+    set::<LocalStorage>(PUSH_TO_TALK_KEY_KEY, key);
+    info!(key = %key, "saved microphone push-to-talk key preference");
+}
+
+/// Loads the push-to-talk release tail in milliseconds.
+pub(crate) fn load_push_to_talk_release_delay_ms() -> u32 {
+    let delay_ms = get::<LocalStorage>(PUSH_TO_TALK_RELEASE_DELAY_MS_KEY)
+        .and_then(|value| value.parse::<u32>().ok())
+        .map(clamp_release_delay_ms)
+        .unwrap_or(DEFAULT_PUSH_TO_TALK_RELEASE_DELAY_MS);
+    info!(
+        delay_ms,
+        "loaded microphone push-to-talk release delay preference"
+    );
+    delay_ms
+}
+
+/// Saves the push-to-talk release tail in milliseconds.
+pub(crate) fn save_push_to_talk_release_delay_ms(delay_ms: u32) {
+    let delay_ms = clamp_release_delay_ms(delay_ms);
+    set::<LocalStorage>(PUSH_TO_TALK_RELEASE_DELAY_MS_KEY, &delay_ms.to_string());
+    info!(
+        delay_ms,
+        "saved microphone push-to-talk release delay preference"
+    );
+}
+
+/// Loads the microphone processing stage toggles.
+pub(crate) fn load_processing() -> MicrophoneProcessingConfig {
+    let defaults = MicrophoneProcessingConfig::default();
+    let processing = MicrophoneProcessingConfig {
+        high_pass_filter: load_flag(HIGH_PASS_FILTER_KEY, defaults.high_pass_filter),
+        echo_suppression: load_flag(ECHO_CANCELLATION_KEY, defaults.echo_suppression),
+        noise_suppression: load_flag(NOISE_SUPPRESSION_KEY, defaults.noise_suppression),
+        noise_gate: load_flag(NOISE_GATE_KEY, defaults.noise_gate),
+        auto_gain_control: load_flag(AUTO_GAIN_CONTROL_KEY, defaults.auto_gain_control),
+    };
+    info!(?processing, "loaded microphone processing preference");
+    processing
+}
+
+/// Saves the microphone processing stage toggles.
+pub(crate) fn save_processing(processing: MicrophoneProcessingConfig) {
+    for (key, enabled) in [
+        (HIGH_PASS_FILTER_KEY, processing.high_pass_filter),
+        (ECHO_CANCELLATION_KEY, processing.echo_suppression),
+        (NOISE_SUPPRESSION_KEY, processing.noise_suppression),
+        (NOISE_GATE_KEY, processing.noise_gate),
+        (AUTO_GAIN_CONTROL_KEY, processing.auto_gain_control),
+    ] {
+        set::<LocalStorage>(key, if enabled { "true" } else { "false" });
+    }
+    info!(?processing, "saved microphone processing preference");
+}
+
+fn load_flag(key: &str, default: bool) -> bool {
+    match get::<LocalStorage>(key).as_deref() {
+        Some("true") => true,
+        Some("false") => false,
+        _ => default,
+    }
+}
+
+fn clamp_release_delay_ms(delay_ms: u32) -> u32 {
+    delay_ms.min(MAX_PUSH_TO_TALK_RELEASE_DELAY_MS)
+}
+
 fn clamp_volume_percent(volume_percent: u32) -> u32 {
     volume_percent.min(200)
 }
@@ -18,10 +18,13 @@ impl VoiceActivityDetector {
             mode: match config.activation_mode {
                 MicrophoneActivationMode::AlwaysActive => CoreActivationMode::AlwaysActive,
                 MicrophoneActivationMode::VoiceActivated => CoreActivationMode::VoiceActivated,
+                MicrophoneActivationMode::PushToTalk => CoreActivationMode::PushToTalk,
+                MicrophoneActivationMode::PushToMute => CoreActivationMode::PushToMute,
             },
             threshold: config.vad_threshold,
             activation_delay_us: config.vad_activation_delay_us,
             release_delay_us: config.vad_release_delay_us,
+            key_release_delay_us: config.push_to_talk_release_delay_us,
         };
         Self {
             detector: super::core::VoiceActivityDetector::new(core),
@@ -31,6 +34,8 @@ impl VoiceActivityDetector {
 
     /// Обновляет detector одним level sample.
     pub(crate) fn update(&mut self, rms: f32, duration_us: u32) -> bool {
+        self.detector
+            .set_key_pressed(self.config.push_to_talk.is_pressed());
         self.detector.update(rms, duration_us)
     }
 
@@ -107,4 +112,21 @@ mod tests {
         assert!(detector.update(0.0, 20_000));
         assert!(detector.update(0.0, 20_000));
     }
+
+    #[test]
+    fn push_to_talk_reads_shared_key_state() {
+        let config = MicrophoneConfig {
+            activation_mode: MicrophoneActivationMode::PushToTalk,
+            push_to_talk_release_delay_us: 20_000,
+            ..config()
+        };
+        let key = config.push_to_talk.clone();
+        let mut detector = VoiceActivityDetector::new(config);
+
+        assert!(!detector.update(0.3, 20_000));
+        key.set_pressed(true);
+        assert!(detector.update(0.0, 20_000));
+        key.set_pressed(false);
+        assert!(!detector.update(0.0, 20_000));
+    }
 }
@@ -29,6 +29,9 @@ use crate::features::runtime::android::{
 static NOTIFICATION_OPEN_SUBSCRIBERS: OnceLock<Mutex<Vec<mpsc::UnboundedSender<String>>>> =
     OnceLock::new();
 #[cfg(target_os = "android")]
+static MENTION_OPEN_SUBSCRIBERS: OnceLock<Mutex<Vec<mpsc::UnboundedSender<String>>>> =
+    OnceLock::new();
+#[cfg(target_os = "android")]
 static FRIEND_REQUEST_OPEN_SUBSCRIBERS: OnceLock<Mutex<Vec<mpsc::UnboundedSender<()>>>> =
     OnceLock::new();
 
@@ -42,6 +45,7 @@ pub(crate) fn NotificationsProvider(children: Element) -> Element {
         let active_room = use_context::<ActiveRoomContext>();
         let navigator = use_navigator();
         let mut pending_route = use_signal(|| None::<Route>);
+        let mut pending_mention = use_signal(|| None::<MentionTarget>);
 
         use_hook(move || {
             spawn(register_android_installation());
@@ -80,6 +84,34 @@ pub(crate) fn NotificationsProvider(children: Element) -> Element {
                 }
                 warn!("Android notification-open subscription stopped");
             });
+            spawn(async move {
+                let mut opened = subscribe_mention_opens();
+                match take_pending_mention().await {
+                    Ok(Some(target)) => {
+                        info!(
+                            source = "cold_start",
+                            "queued Android mention notification route"
+                        );
+                        pending_mention.set(parse_mention_target(&target));
+                    }
+                    Ok(None) => {}
+                    Err(error) => warn!(
+                        %error,
+                        "failed to consume pending Android mention route"
+                    ),
+                }
+                while let Some(target) = opened.next().await {
+                    if let Err(error) = take_pending_mention().await {
+                        warn!(%error, "failed to clear delivered Android mention route");
+                    }
+                    info!(
+                        source = "activity_intent",
+                        "queued Android mention notification route"
+                    );
+                    pending_mention.set(parse_mention_target(&target));
+                }
+                warn!("Android mention notification-open subscription stopped");
+            });
             spawn(async move {
                 match take_pending_friend_requests().await {
                     Ok(true) => {
@@ -122,6 +154,15 @@ pub(crate) fn NotificationsProvider(children: Element) -> Element {
             navigator.push(route);
         });
 
+        use_effect(move || {
+            let Some(target) = pending_mention() else {
+                return;
+            };
+            pending_mention.set(None);
+            active_room.request_message_focus(target.room_id.clone(), target.message_id.clone());
+            pending_route.set(Some(mention_route(target)));
+        });
+
         use_effect(move || {
             let conversation_id = active_room.conversation_id();
             let Ok(bridge) = android_bridge() else {
@@ -151,6 +192,39 @@ fn friend_requests_route() -> Route {
     Route::AppFriends {}
 }
 
+/// Сообщение с упоминанием, которое открывает нажатое уведомление.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MentionTarget {
+    server_id: String,
+    room_id: String,
+    message_id: String,
+}
+
+/// Разбирает адрес упоминания `server_id/room_id/message_id` из Android-слоя.
+fn parse_mention_target(value: &str) -> Option<MentionTarget> {
+    let mut parts = value.split('/');
+    let target = MentionTarget {
+        server_id: parts.next()?.to_owned(),
+        room_id: parts.next()?.to_owned(),
+        message_id: parts.next()?.to_owned(),
+    };
+    if parts.next().is_some()
+        || [&target.server_id, &target.room_id, &target.message_id]
+            .iter()
+            .any(|part| part.is_empty())
+    {
+        return None;
+    }
+    Some(target)
+}
+
+fn mention_route(target: MentionTarget) -> Route {
+    Route::AppServerRoom {
+        server_id: target.server_id,
+        room_id: target.room_id,
+    }
+}
+
 #[cfg(target_os = "android")]
 async fn register_android_installation() {
     match request_notification_permission().await {
@@ -225,6 +299,20 @@ async fn take_pending_conversation() -> Result<Option<String>, String> {
         .map_err(|_| "Android закрыл callback маршрута уведомления.".to_owned())?
 }
 
+#[cfg(target_os = "android")]
+async fn take_pending_mention() -> Result<Option<String>, String> {
+    let (sender, receiver) = oneshot::channel();
+    android_bridge()
+        .map_err(|error| error.to_string())?
+        .take_pending_mention(Box::new(move |result| {
+            let _ = sender.send(result.map_err(|error| error.to_string()));
+        }))
+        .map_err(|error| error.to_string())?;
+    receiver
+        .await
+        .map_err(|_| "Android закрыл callback маршрута упоминания.".to_owned())?
+}
+
 #[cfg(target_os = "android")]
 async fn take_pending_friend_requests() -> Result<bool, String> {
     let (sender, receiver) = oneshot::channel();
@@ -253,6 +341,20 @@ fn notification_open_subscribers() -> &'static Mutex<Vec<mpsc::UnboundedSender<S
     NOTIFICATION_OPEN_SUBSCRIBERS.get_or_init(|| Mutex::new(Vec::new()))
 }
 
+#[cfg(target_os = "android")]
+fn subscribe_mention_opens() -> mpsc::UnboundedReceiver<String> {
+    let (sender, receiver) = mpsc::unbounded();
+    if let Ok(mut subscribers) = mention_open_subscribers().lock() {
+        subscribers.push(sender);
+    }
+    receiver
+}
+
+#[cfg(target_os = "android")]
+fn mention_open_subscribers() -> &'static Mutex<Vec<mpsc::UnboundedSender<String>>> {
+    MENTION_OPEN_SUBSCRIBERS.get_or_init(|| Mutex::new(Vec::new()))
+}
+
 #[cfg(target_os = "android")]
 fn subscribe_friend_request_opens() -> mpsc::UnboundedReceiver<()> {
     let (sender, receiver) = mpsc::unbounded();
@@ -284,6 +386,23 @@ pub extern "system" fn Java_dev_dioxus_main_MainActivity_nativeOnCheenHubDirectM
     subscribers.retain(|subscriber| subscriber.unbounded_send(conversation_id.clone()).is_ok());
 }
 
+/// Передаёт открытие Android-уведомления об упоминании активному Dioxus provider.
+#[cfg(target_os = "android")]
+#[unsafe(no_mangle)]
+pub extern "system" fn Java_dev_dioxus_main_MainActivity_nativeOnCheenHubMentionNotificationOpened(
+    mut env: JNIEnv<'_>,
+    _activity: JObject<'_>,
+    target: JString<'_>,
+) {
+    let Ok(target) = env.get_string(&target).map(String::from) else {
+        return;
+    };
+    let Ok(mut subscribers) = mention_open_subscribers().lock() else {
+        return;
+    };
+    subscribers.retain(|subscriber| subscriber.unbounded_send(target.clone()).is_ok());
+}
+
 /// Передаёт открытие Android-уведомления о заявке в друзья активному Dioxus provider.
 #[cfg(target_os = "android")]
 #[unsafe(no_mangle)]
@@ -315,4 +434,28 @@ mod tests {
     fn friend_request_notification_click_targets_friends_route() {
         assert_eq!(friend_requests_route(), Route::AppFriends {});
     }
+
+    #[test]
+    fn mention_notification_click_targets_server_room() {
+        let target = parse_mention_target(
+            "0f7e1f43-5b4c-4a53-9f4f-3f2b7c0b8a11/6b1d2c3e-8f3a-4e5b-9c7d-1a2b3c4d5e6f/80c993e1-2fe7-49e0-bcc5-c56c790d98c8",
+        )
+        .expect("mention target should parse");
+
+        assert_eq!(target.message_id, "80c993e1-2fe7-49e0-bcc5-c56c790d98c8");
+        assert_eq!(
+            mention_route(target),
+            Route::AppServerRoom {
+                server_id: "0f7e1f43-5b4c-4a53-9f4f-3f2b7c0b8a11".to_owned(),
+                room_id: "6b1d2c3e-8f3a-4e5b-9c7d-1a2b3c4d5e6f".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn malformed_mention_target_is_rejected() {
+        assert_eq!(parse_mention_target("server/room"), None);
+        assert_eq!(parse_mention_target("server//message"), None);
+        assert_eq!(parse_mention_target("server/room/message/extra"), None);
+    }
 }
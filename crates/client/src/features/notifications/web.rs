@@ -109,8 +109,10 @@ async fn listen_for_text_chat_messages(
     let mut receiver = subscribe_text_chat(&realtime);
 
     while let Some(event) = receiver.next().await {
-        let TextChatEvent::MessageCreated(message) = event else {
-            continue;
+        let (message, mentioned) = match event {
+            TextChatEvent::Created(message) => (message, false),
+            TextChatEvent::Mentioned(message) => (message, true),
+            _ => continue,
         };
 
         // Не показываем уведомления для собственных сообщений.
@@ -123,7 +125,7 @@ async fn listen_for_text_chat_messages(
             continue;
         }
 
-        show_text_chat_notification(&message, &pending_nav);
+        show_text_chat_notification(&message, mentioned, active_room, &pending_nav);
     }
 }
 
@@ -165,17 +167,32 @@ async fn listen_for_dm_messages(
 
 /// Создаёт браузерное уведомление о новом сообщении текстового чата
 /// с навигацией при клике.
-fn show_text_chat_notification(message: &TextChatMessage, pending_nav: &Signal<Option<Route>>) {
+///
+/// Уведомление помечается идентификатором сообщения, поэтому уведомление
+/// об упоминании заменяет уже показанное обычное уведомление о том же сообщении.
+/// Клик по упоминанию открывает комнату на этом сообщении.
+fn show_text_chat_notification(
+    message: &TextChatMessage,
+    mentioned: bool,
+    active_room: ActiveRoomContext,
+    pending_nav: &Signal<Option<Route>>,
+) {
     let body = if message.body.is_empty() {
         "Отправил изображение".to_string()
     } else {
         truncate_message(&message.body)
     };
 
+    let title = if mentioned {
+        format!("{} упомянул вас", message.author_nickname)
+    } else {
+        message.author_nickname.clone()
+    };
     let options = NotificationOptions::new();
     options.set_body(&body);
+    options.set_tag(&message.id);
 
-    let notification = match Notification::new_with_options(&message.author_nickname, &options) {
+    let notification = match Notification::new_with_options(&title, &options) {
         Ok(notification) => notification,
         Err(error) => {
             warn!(
@@ -188,6 +205,7 @@ fn show_text_chat_notification(message: &TextChatMessage, pending_nav: &Signal<O
 
     let server_id = message.server_id.clone();
     let room_id = message.room_id.clone();
+    let message_id = message.id.clone();
     let mut pending_nav = *pending_nav;
 
     let onclick = Closure::once(move |_event: Event| {
@@ -195,6 +213,9 @@ fn show_text_chat_notification(message: &TextChatMessage, pending_nav: &Signal<O
         if let Some(window) = web_sys::window() {
             let _ = window.focus();
         }
+        if mentioned {
+            active_room.request_message_focus(room_id.clone(), message_id);
+        }
         // Устанавливаем маршрут навигации; use_effect в провайдере обработает его.
         pending_nav.set(Some(Route::AppServerRoom { server_id, room_id }));
     });
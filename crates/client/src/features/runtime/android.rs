@@ -114,6 +114,13 @@ pub(crate) trait AndroidBridge: Send + Sync {
         callback: Box<dyn FnOnce(Result<Option<String>, AndroidBridgeError>) + Send>,
     ) -> Result<(), AndroidBridgeError>;
 
+    /// Возвращает и удаляет отложенный переход к упоминанию в виде
+    /// `server_id/room_id/message_id`.
+    fn take_pending_mention(
+        &self,
+        callback: Box<dyn FnOnce(Result<Option<String>, AndroidBridgeError>) + Send>,
+    ) -> Result<(), AndroidBridgeError>;
+
     /// Возвращает и удаляет отложенный переход к входящим заявкам в друзья.
     fn take_pending_friend_requests(
         &self,
@@ -182,6 +182,43 @@ impl AndroidBridge for JniAndroidBridge {
         Ok(())
     }
 
+    fn take_pending_mention(
+        &self,
+        callback: Box<dyn FnOnce(Result<Option<String>, AndroidBridgeError>) + Send>,
+    ) -> Result<(), AndroidBridgeError> {
+        wry::prelude::dispatch(move |env, activity, _| {
+            let result = env
+                .call_method(
+                    activity,
+                    "consumeCheenHubPendingMention",
+                    "()Ljava/lang/String;",
+                    &[],
+                )
+                .and_then(|value| value.l())
+                .map_err(|error| {
+                    AndroidBridgeError::new(format!(
+                        "Не удалось получить переход к упоминанию: {error}"
+                    ))
+                })
+                .and_then(|value| {
+                    if value.is_null() {
+                        Ok(None)
+                    } else {
+                        let value = JString::from(value);
+                        env.get_string(&value)
+                            .map(|value| Some(value.into()))
+                            .map_err(|error| {
+                                AndroidBridgeError::new(format!(
+                                    "Не удалось прочитать адрес упоминания: {error}"
+                                ))
+                            })
+                    }
+                });
+            callback(result);
+        });
+        Ok(())
+    }
+
     fn take_pending_friend_requests(
         &self,
         callback: Box<dyn FnOnce(Result<bool, AndroidBridgeError>) + Send>,
@@ -9,7 +9,8 @@ use crate::features::video_encoding::{
     VideoEncoderConfig, VideoEncodingAcceleratorKind, VideoEncodingManager, VideoFrameEncoder,
     android_video_capture_bridge,
 };
-use dioxus::logger::tracing::error;
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use dioxus::logger::tracing::{error, warn};
 use futures_util::future::LocalBoxFuture;
 use std::{cell::Cell, rc::Rc, time::Duration};
 
@@ -23,7 +24,7 @@ impl ScreenShareBackend for AndroidScreenShareBackend {
         callbacks: ScreenShareCallbacks,
     ) -> LocalBoxFuture<'static, Result<Rc<dyn ScreenShareSession>, ScreenShareError>> {
         Box::pin(async move {
-            let preset = config.preset_for_capture(1280, 720);
+            let preset = config.preset_for_capture(1280, 720).spec();
             let bridge = android_video_capture_bridge().map_err(ScreenShareError::new)?;
             let target = callbacks.on_frame.clone();
             let encoder = AndroidVideoEncodingManager
@@ -41,6 +42,7 @@ impl ScreenShareBackend for AndroidScreenShareBackend {
                             timestamp_us: f.timestamp_us,
                             duration_us: f.duration_us,
                             codec: ScreenShareCodec::Vp9,
+                            layer: VideoSpatialLayer::High,
                             key_frame: f.key_frame,
                             width: f.width,
                             height: f.height,
@@ -102,4 +104,10 @@ impl ScreenShareSession for AndroidScreenShareSession {
                 .map_err(|e| ScreenShareError::new(e.to_string()))
         })
     }
+
+    fn request_key_frame(&self) {
+        if let Err(error) = self.encoder.request_key_frame() {
+            warn!(%error, "failed to request Android MediaProjection VP9 key frame");
+        }
+    }
 }
@@ -4,7 +4,7 @@ use std::fmt;
 use std::rc::Rc;
 
 use cheenhub_contracts::video_presets::{
-    BASE_SCREEN_SHARE_VIDEO_PRESETS, VideoPresetId, VideoPresetSpec, VideoStreamSource,
+    BASE_SCREEN_SHARE_VIDEO_PRESETS, VideoPresetId, VideoSpatialLayer, VideoStreamSource,
 };
 use futures_util::future::LocalBoxFuture;
 
@@ -32,7 +32,7 @@ pub(crate) struct ScreenShareConfig {
 
 impl ScreenShareConfig {
     /// Выбирает лучший разрешённый пресет для размеров источника.
-    pub(crate) fn preset_for_capture(&self, width: u32, height: u32) -> VideoPresetSpec {
+    pub(crate) fn preset_for_capture(&self, width: u32, height: u32) -> VideoPresetId {
         self.allowed_presets
             .iter()
             .copied()
@@ -52,7 +52,6 @@ impl ScreenShareConfig {
                     .find(|preset| preset.spec().source == VideoStreamSource::ScreenShare)
             })
             .unwrap_or(VideoPresetId::Screen720p30)
-            .spec()
     }
 }
 
@@ -71,13 +70,17 @@ mod tests {
 
     #[test]
     fn selects_720p_for_hd_source() {
-        let spec = ScreenShareConfig::default().preset_for_capture(1366, 768);
+        let spec = ScreenShareConfig::default()
+            .preset_for_capture(1366, 768)
+            .spec();
         assert_eq!((spec.width, spec.height, spec.max_fps), (1280, 720, 30));
     }
 
     #[test]
     fn selects_1080p_for_full_hd_source() {
-        let spec = ScreenShareConfig::default().preset_for_capture(2560, 1440);
+        let spec = ScreenShareConfig::default()
+            .preset_for_capture(2560, 1440)
+            .spec();
         assert_eq!((spec.width, spec.height, spec.max_fps), (1920, 1080, 15));
     }
 }
@@ -102,6 +105,8 @@ pub(crate) struct EncodedScreenShareFrame {
     pub(crate) duration_us: u32,
     /// Encoded codec.
     pub(crate) codec: ScreenShareCodec,
+    /// Simulcast layer this frame belongs to.
+    pub(crate) layer: VideoSpatialLayer,
     /// Whether this frame can start a decoder stream.
     pub(crate) key_frame: bool,
     /// Encoded frame width.
@@ -131,6 +136,9 @@ pub(crate) enum ScreenShareStatus {
 pub(crate) trait ScreenShareSession {
     /// Stops capture and releases backend resources.
     fn stop(&self) -> LocalBoxFuture<'static, Result<(), ScreenShareError>>;
+
+    /// Asks the encoder to make the next frame a key frame.
+    fn request_key_frame(&self);
 }
 
 /// Backend захвата экрана.
@@ -4,6 +4,7 @@
 use std::cell::Cell;
 use std::rc::Rc;
 
+use cheenhub_contracts::video_presets::{VideoPresetId, VideoSpatialLayer};
 use dioxus::prelude::warn;
 use futures_util::FutureExt;
 use futures_util::future::LocalBoxFuture;
@@ -24,7 +25,8 @@ use super::browser_capture::{
     first_video_track, log_selected_video_track, request_screen_stream, video_track_settings,
 };
 
-const KEY_FRAME_INTERVAL_SECONDS: u32 = 2;
+/// Ключевые кадры без запроса зрителей нужны, только если запрос потерялся по дороге.
+const FALLBACK_KEY_FRAME_INTERVAL_SECONDS: u32 = 10;
 const UNSUPPORTED_SCREEN_SHARE_MESSAGE: &str = concat!(
     "Этот браузер не поддерживает демонстрацию экрана в CheenHub. ",
     "Воспользуйтесь браузером на базе Chromium или нативным клиентом."
@@ -44,15 +46,16 @@ impl ScreenShareBackend for BrowserScreenShareBackend {
 }
 
 struct BrowserScreenShareSession {
-    encoder: BrowserVideoEncoder,
+    encoders: Vec<BrowserVideoEncoder>,
     track: web_sys::MediaStreamTrack,
     frame_reader: BrowserVideoFrameReaderHandle,
     closed: Rc<Cell<bool>>,
+    key_frame_requested: Rc<Cell<bool>>,
 }
 
 impl ScreenShareSession for BrowserScreenShareSession {
     fn stop(&self) -> LocalBoxFuture<'static, Result<(), ScreenShareError>> {
-        let encoder = self.encoder.handle();
+        let encoders = encoder_handles(&self.encoders);
         let track = self.track.clone();
         let frame_reader = self.frame_reader.clone();
         let closed = self.closed.clone();
@@ -62,11 +65,17 @@ impl ScreenShareSession for BrowserScreenShareSession {
             }
             frame_reader.stop();
             track.stop();
-            encoder.close().map_err(screen_share_video_encoding_error)?;
+            for encoder in encoders {
+                encoder.close().map_err(screen_share_video_encoding_error)?;
+            }
             Ok(())
         }
         .boxed_local()
     }
+
+    fn request_key_frame(&self) {
+        self.key_frame_requested.set(true);
+    }
 }
 
 async fn start_browser_session(
@@ -86,29 +95,16 @@ async fn start_browser_session(
     let settings = video_track_settings(&track);
     let source_width = settings.width.unwrap_or(1280).max(1);
     let source_height = settings.height.unwrap_or(720).max(1);
-    let preset = config.preset_for_capture(source_width, source_height);
-    let encoder_config = VideoEncoderConfig::vp9(
-        preset.width,
-        preset.height,
-        preset.max_fps,
-        preset.bitrate_bps,
-    );
-    ensure_video_encoder_available(&track, encoder_config.clone()).await?;
+    let preset_id = config.preset_for_capture(source_width, source_height);
+    let preset = preset_id.spec();
+    ensure_video_encoder_available(
+        &track,
+        layer_encoder_config(preset_id, VideoSpatialLayer::High),
+    )
+    .await?;
 
-    let output_on_frame = callbacks.on_frame.clone();
-    let on_video_frame = Rc::new(move |frame: EncodedVideoFrame| {
-        output_on_frame(screen_share_frame_from_video(frame));
-    });
-    let manager = BrowserVideoEncodingManager;
-    let encoder = match manager
-        .create_encoder(
-            VideoEncodingAcceleratorKind::WebCodecs,
-            encoder_config,
-            on_video_frame,
-        )
-        .await
-    {
-        Ok(encoder) => encoder,
+    let encoders = match create_layer_encoders(preset_id, &callbacks).await {
+        Ok(encoders) => encoders,
         Err(error) => {
             track.stop();
             return Err(screen_share_video_encoding_error(error));
@@ -117,7 +113,7 @@ async fn start_browser_session(
     let frame_reader = match BrowserVideoFrameReader::from_stream(&track, &stream).await {
         Ok(reader) => reader,
         Err(error) => {
-            let _ = encoder.close();
+            close_encoders(&encoders);
             track.stop();
             return Err(screen_share_video_encoding_error(error));
         }
@@ -125,27 +121,75 @@ async fn start_browser_session(
     let frame_reader_handle = frame_reader.handle();
 
     let closed = Rc::new(Cell::new(false));
-    let key_frame_interval_frames = preset
-        .max_fps
-        .saturating_mul(KEY_FRAME_INTERVAL_SECONDS)
-        .max(1);
+    let key_frame_requested = Rc::new(Cell::new(false));
     spawn_video_reader(
         frame_reader,
-        encoder.handle(),
+        encoder_handles(&encoders),
         closed.clone(),
+        key_frame_requested.clone(),
         callbacks,
-        key_frame_interval_frames,
         preset.max_fps,
     );
 
     Ok(Rc::new(BrowserScreenShareSession {
-        encoder,
+        encoders,
         track,
         frame_reader: frame_reader_handle,
         closed,
+        key_frame_requested,
     }))
 }
 
+/// Creates encoders for every simulcast layer. The high layer is mandatory; a missing
+/// low layer only narrows the layers receivers can pick from.
+async fn create_layer_encoders(
+    preset: VideoPresetId,
+    callbacks: &ScreenShareCallbacks,
+) -> Result<Vec<BrowserVideoEncoder>, VideoEncodingError> {
+    let manager = BrowserVideoEncodingManager;
+    let mut encoders = Vec::with_capacity(VideoSpatialLayer::ALL.len());
+    for layer in VideoSpatialLayer::ALL {
+        let output_on_frame = callbacks.on_frame.clone();
+        let on_video_frame = Rc::new(move |frame: EncodedVideoFrame| {
+            output_on_frame(screen_share_frame_from_video(frame, layer));
+        });
+        match manager
+            .create_encoder(
+                VideoEncodingAcceleratorKind::WebCodecs,
+                layer_encoder_config(preset, layer),
+                on_video_frame,
+            )
+            .await
+        {
+            Ok(encoder) => encoders.push(encoder),
+            Err(error) if layer == VideoSpatialLayer::High => {
+                close_encoders(&encoders);
+                return Err(error);
+            }
+            Err(error) => {
+                warn!(%error, layer = ?layer, "failed to create screen sharing layer encoder")
+            }
+        }
+    }
+
+    Ok(encoders)
+}
+
+fn layer_encoder_config(preset: VideoPresetId, layer: VideoSpatialLayer) -> VideoEncoderConfig {
+    let spec = preset.layer_spec(layer);
+    VideoEncoderConfig::vp9(spec.width, spec.height, spec.max_fps, spec.bitrate_bps)
+}
+
+fn encoder_handles(encoders: &[BrowserVideoEncoder]) -> Vec<BrowserVideoEncoderHandle> {
+    encoders.iter().map(BrowserVideoEncoder::handle).collect()
+}
+
+fn close_encoders(encoders: &[BrowserVideoEncoder]) {
+    for encoder in encoders {
+        let _ = encoder.close();
+    }
+}
+
 async fn ensure_video_encoder_available(
     track: &web_sys::MediaStreamTrack,
     encoder_config: VideoEncoderConfig,
@@ -185,12 +229,15 @@ fn ensure_browser_screen_share_support() -> Result<(), ScreenShareError> {
 
 fn spawn_video_reader(
     reader: BrowserVideoFrameReader,
-    encoder: BrowserVideoEncoderHandle,
+    encoders: Vec<BrowserVideoEncoderHandle>,
     closed: Rc<Cell<bool>>,
+    key_frame_requested: Rc<Cell<bool>>,
     callbacks: ScreenShareCallbacks,
-    key_frame_interval_frames: u32,
     max_fps: u32,
 ) {
+    let key_frame_interval_frames = max_fps
+        .saturating_mul(FALLBACK_KEY_FRAME_INTERVAL_SECONDS)
+        .max(1);
     spawn_local(async move {
         let frame_sequence = Rc::new(Cell::new(0_u64));
         let mut frame_rate_gate = VideoFrameRateGate::new(max_fps);
@@ -209,8 +256,12 @@ fn spawn_video_reader(
             }
             let sequence = frame_sequence.get();
             frame_sequence.set(sequence.saturating_add(1));
-            let key_frame = sequence.is_multiple_of(u64::from(key_frame_interval_frames));
-            if let Err(error) = encoder.encode(&frame, key_frame) {
+            let key_frame = key_frame_requested.replace(false)
+                || sequence.is_multiple_of(u64::from(key_frame_interval_frames));
+            if let Err(error) = encoders
+                .iter()
+                .try_for_each(|encoder| encoder.encode(&frame, key_frame))
+            {
                 warn!(%error, "failed to encode screen sharing frame");
                 frame.close();
                 break;
@@ -218,25 +269,30 @@ fn spawn_video_reader(
             frame.close();
         }
 
-        finish_browser_capture(&encoder, &closed, &callbacks);
+        finish_browser_capture(&encoders, &closed, &callbacks);
     });
 }
 
 fn finish_browser_capture(
-    encoder: &BrowserVideoEncoderHandle,
+    encoders: &[BrowserVideoEncoderHandle],
     closed: &Rc<Cell<bool>>,
     callbacks: &ScreenShareCallbacks,
 ) {
     if closed.replace(true) {
         return;
     }
-    if let Err(error) = encoder.close() {
-        warn!(%error, "failed to close screen sharing encoder after capture ended");
+    for encoder in encoders {
+        if let Err(error) = encoder.close() {
+            warn!(%error, "failed to close screen sharing encoder after capture ended");
+        }
     }
     (callbacks.on_ended)();
 }
 
-fn screen_share_frame_from_video(frame: EncodedVideoFrame) -> EncodedScreenShareFrame {
+fn screen_share_frame_from_video(
+    frame: EncodedVideoFrame,
+    layer: VideoSpatialLayer,
+) -> EncodedScreenShareFrame {
     EncodedScreenShareFrame {
         sequence: frame.sequence,
         timestamp_us: frame.timestamp_us,
@@ -244,6 +300,7 @@ fn screen_share_frame_from_video(frame: EncodedVideoFrame) -> EncodedScreenShare
         codec: match frame.codec {
             VideoCodec::Vp9 => ScreenShareCodec::Vp9,
         },
+        layer,
         key_frame: frame.key_frame,
         width: frame.width,
         height: frame.height,
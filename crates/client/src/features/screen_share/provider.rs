@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 
+use cheenhub_contracts::video_presets::VideoPresetId;
 use dioxus::prelude::*;
 
 use crate::features::toast::ToastHandle;
@@ -18,12 +19,13 @@ pub(crate) struct ScreenShareHandle {
     pub(super) status: Signal<ScreenShareStatus>,
     pub(super) session: Signal<Option<Rc<dyn ScreenShareSession>>>,
     pub(super) generation: Signal<u64>,
+    pub(super) allowed_presets: Signal<Vec<VideoPresetId>>,
     pub(super) backend: Rc<dyn ScreenShareBackend>,
     pub(super) toast: ToastHandle,
 }
 
 impl ScreenShareHandle {
-    /// Starts screen sharing capture with the default configuration.
+    /// Starts screen sharing capture with presets allowed in the current room.
     pub(crate) fn start(&self, on_frame: ScreenShareFrameCallback) {
         if matches!(
             self.status(),
@@ -31,6 +33,17 @@ impl ScreenShareHandle {
         ) {
             return;
         }
+        let allowed_presets = self.allowed_presets.peek().clone();
+        if allowed_presets.is_empty() {
+            info!("screen sharing is disabled by server media settings");
+            self.toast
+                .warning("Демонстрация экрана отключена на этом сервере.");
+            return;
+        }
+        let config = ScreenShareConfig {
+            allowed_presets,
+            ..ScreenShareConfig::default()
+        };
 
         let backend = self.backend.clone();
         let mut session = self.session;
@@ -43,7 +56,7 @@ impl ScreenShareHandle {
         info!("starting screen sharing capture");
         spawn(async move {
             let callbacks = screen_share_callbacks(on_frame.clone(), session, status, generation);
-            match backend.start(ScreenShareConfig::default(), callbacks).await {
+            match backend.start(config, callbacks).await {
                 Ok(next_session) => {
                     if generation() != start_generation {
                         if let Err(error) = next_session.stop().await {
@@ -112,6 +125,31 @@ impl ScreenShareHandle {
         }
     }
 
+    /// Sets screen sharing presets allowed by the server of the current room.
+    pub(crate) fn set_allowed_presets(&self, presets: Vec<VideoPresetId>) {
+        let mut allowed_presets = self.allowed_presets;
+        if *allowed_presets.peek() == presets {
+            return;
+        }
+        if presets.is_empty()
+            && matches!(
+                self.status(),
+                ScreenShareStatus::Live | ScreenShareStatus::Starting
+            )
+        {
+            info!("stopping screen sharing disabled by server media settings");
+            self.stop();
+        }
+        allowed_presets.set(presets);
+    }
+
+    /// Asks the active capture to encode the next frame as a key frame.
+    pub(crate) fn request_key_frame(&self) {
+        if let Some(session) = self.session.peek().as_ref() {
+            session.request_key_frame();
+        }
+    }
+
     /// Returns the current screen sharing status.
     pub(crate) fn status(&self) -> ScreenShareStatus {
         (self.status)()
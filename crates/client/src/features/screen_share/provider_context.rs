@@ -2,6 +2,7 @@
 
 use std::rc::Rc;
 
+use cheenhub_contracts::video_presets::BASE_SCREEN_SHARE_VIDEO_PRESETS;
 use dioxus::prelude::*;
 
 use crate::features::toast::ToastHandle;
@@ -16,12 +17,14 @@ pub(crate) fn ScreenShareProvider(children: Element) -> Element {
     let status = use_signal(|| ScreenShareStatus::Idle);
     let session = use_signal(|| None::<Rc<dyn ScreenShareSession>>);
     let generation = use_signal(|| 0);
+    let allowed_presets = use_signal(|| BASE_SCREEN_SHARE_VIDEO_PRESETS.to_vec());
     let toast = use_context::<ToastHandle>();
     let backend = default_backend();
     let handle = ScreenShareHandle {
         status,
         session,
         generation,
+        allowed_presets,
         backend,
         toast,
     };
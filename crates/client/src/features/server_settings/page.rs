@@ -3,6 +3,8 @@
 use cheenhub_contracts::rest::ServerSummary;
 use dioxus::prelude::*;
 
+use crate::features::app::server_permissions::ServerPermissionsContext;
+
 use super::invites_section::ServerInvitesSettingsSection;
 use super::members_section::ServerMembersSettingsSection;
 use super::overview_section::ServerOverviewSettingsSection;
@@ -150,6 +152,8 @@ pub(crate) fn ServerSettingsPage(
                         ServerSettingsSection::Voice => rsx! {
                             ServerVoiceVideoSettingsSection {
                                 server_id: server.id.clone(),
+                                can_manage: ServerPermissionsContext::from_server(&server)
+                                    .can_manage_media_settings,
                             }
                         },
                         _ => rsx! {
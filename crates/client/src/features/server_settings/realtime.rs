@@ -2,11 +2,11 @@
 
 use cheenhub_contracts::realtime::{
     AssignServerMemberRole, KickServerInviteMember, KickServerMember, ListServerInvites,
-    ListServerMembers, ListServerRoles, RealtimeKind, RealtimeModule, RevokeServerInvite,
-    RevokeServerMemberRole, SaveServerRoles, ServerInviteList, ServerInviteMemberKicked,
-    ServerInviteRevoked, ServerKind, ServerMemberKicked, ServerMemberList,
-    ServerMemberRoleAssigned, ServerMemberRoleRevoked, ServerRoleDraft, ServerRoleList,
-    ServerRolesSaved,
+    ListServerMembers, ListServerRoles, LoadServerMediaSettings, RealtimeKind, RealtimeModule,
+    RevokeServerInvite, RevokeServerMemberRole, SaveServerMediaSettings, SaveServerRoles,
+    ServerInviteList, ServerInviteMemberKicked, ServerInviteRevoked, ServerKind,
+    ServerMediaSettings, ServerMemberKicked, ServerMemberList, ServerMemberRoleAssigned,
+    ServerMemberRoleRevoked, ServerRoleDraft, ServerRoleList, ServerRolesSaved,
 };
 
 use crate::features::realtime::{RealtimeError, RealtimeHandle};
@@ -162,3 +162,36 @@ pub(super) async fn revoke_server_member_role(
         )
         .await
 }
+
+/// Loads server voice and video limits through the realtime session.
+pub(super) async fn load_server_media_settings(
+    realtime: &RealtimeHandle,
+    server_id: String,
+) -> Result<ServerMediaSettings, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::Server,
+            RealtimeKind::Server(ServerKind::LoadServerMediaSettings),
+            LoadServerMediaSettings { server_id },
+        )
+        .await
+}
+
+/// Saves server voice and video limits through the realtime session.
+pub(super) async fn save_server_media_settings(
+    realtime: &RealtimeHandle,
+    settings: ServerMediaSettings,
+) -> Result<ServerMediaSettings, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::Server,
+            RealtimeKind::Server(ServerKind::SaveServerMediaSettings),
+            SaveServerMediaSettings {
+                server_id: settings.server_id,
+                allowed_video_presets: settings.allowed_video_presets,
+                audio_bitrate_bps: settings.audio_bitrate_bps,
+                max_video_publishers: settings.max_video_publishers,
+            },
+        )
+        .await
+}
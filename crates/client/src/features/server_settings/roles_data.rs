@@ -72,6 +72,10 @@ pub(super) enum RolePermission {
     ManageRoles,
     KickVoiceMembers,
     DeleteMessages,
+    PinMessages,
+    ManageMediaSettings,
+    RecordVoiceRoom,
+    ModerateVoiceMembers,
 }
 
 impl RolePermission {
@@ -82,6 +86,10 @@ impl RolePermission {
             RolePermission::ManageRoles,
             RolePermission::KickVoiceMembers,
             RolePermission::DeleteMessages,
+            RolePermission::PinMessages,
+            RolePermission::ManageMediaSettings,
+            RolePermission::RecordVoiceRoom,
+            RolePermission::ModerateVoiceMembers,
         ]
     }
 
@@ -92,6 +100,10 @@ impl RolePermission {
             RolePermission::ManageRoles => "manage_roles",
             RolePermission::KickVoiceMembers => "kick_voice_members",
             RolePermission::DeleteMessages => "delete_messages",
+            RolePermission::PinMessages => "pin_messages",
+            RolePermission::ManageMediaSettings => "manage_media_settings",
+            RolePermission::RecordVoiceRoom => "record_voice_room",
+            RolePermission::ModerateVoiceMembers => "moderate_voice_members",
         }
     }
 
@@ -102,6 +114,10 @@ impl RolePermission {
             RolePermission::ManageRoles => "Управлять ролями",
             RolePermission::KickVoiceMembers => "Кикать из голосовой комнаты",
             RolePermission::DeleteMessages => "Удалять чужие сообщения",
+            RolePermission::PinMessages => "Закреплять сообщения",
+            RolePermission::ManageMediaSettings => "Управлять голосом и видео",
+            RolePermission::RecordVoiceRoom => "Записывать голосовые комнаты",
+            RolePermission::ModerateVoiceMembers => "Отключать микрофон и звук",
         }
     }
 
@@ -116,6 +132,18 @@ impl RolePermission {
             RolePermission::DeleteMessages => {
                 "Удаление любых сообщений в текстовых комнатах сервера."
             }
+            RolePermission::PinMessages => {
+                "Закрепление и открепление сообщений в текстовых комнатах."
+            }
+            RolePermission::ManageMediaSettings => {
+                "Изменение пределов качества голоса и видео на сервере."
+            }
+            RolePermission::RecordVoiceRoom => {
+                "Запуск и остановка записи голосовой комнаты на сервере."
+            }
+            RolePermission::ModerateVoiceMembers => {
+                "Отключение микрофона и звука участникам голосовых комнат."
+            }
         }
     }
 
@@ -126,6 +154,10 @@ impl RolePermission {
             ServerRolePermission::ManageRoles => RolePermission::ManageRoles,
             ServerRolePermission::KickVoiceMembers => RolePermission::KickVoiceMembers,
             ServerRolePermission::DeleteMessages => RolePermission::DeleteMessages,
+            ServerRolePermission::PinMessages => RolePermission::PinMessages,
+            ServerRolePermission::ManageMediaSettings => RolePermission::ManageMediaSettings,
+            ServerRolePermission::RecordVoiceRoom => RolePermission::RecordVoiceRoom,
+            ServerRolePermission::ModerateVoiceMembers => RolePermission::ModerateVoiceMembers,
         }
     }
 
@@ -136,6 +168,10 @@ impl RolePermission {
             RolePermission::ManageRoles => ServerRolePermission::ManageRoles,
             RolePermission::KickVoiceMembers => ServerRolePermission::KickVoiceMembers,
             RolePermission::DeleteMessages => ServerRolePermission::DeleteMessages,
+            RolePermission::PinMessages => ServerRolePermission::PinMessages,
+            RolePermission::ManageMediaSettings => ServerRolePermission::ManageMediaSettings,
+            RolePermission::RecordVoiceRoom => ServerRolePermission::RecordVoiceRoom,
+            RolePermission::ModerateVoiceMembers => ServerRolePermission::ModerateVoiceMembers,
         }
     }
 }
@@ -1,47 +1,47 @@
 //! Секция настроек голоса и видео сервера.
 
+use cheenhub_contracts::realtime::{
+    MAX_AUDIO_BITRATE_BPS, MAX_VIDEO_PUBLISHERS, MIN_AUDIO_BITRATE_BPS, ServerMediaSettings,
+};
+use cheenhub_contracts::video_presets::{ALL_VIDEO_PRESETS, VideoPresetId, VideoStreamSource};
 use dioxus::prelude::*;
 
+use super::realtime;
+use crate::features::realtime::RealtimeHandle;
+use crate::features::toast::ToastHandle;
+
 const AUDIO_CODEC: &str = "Opus";
-const AUDIO_BITRATE_LABEL: &str = "32 кбит/с";
-const AUDIO_BITRATE_KBPS: u32 = 32;
-const AUDIO_BITRATE_MAX_KBPS: u32 = 64;
+const AUDIO_BITRATE_STEP_BPS: u32 = 8_000;
 
 const VIDEO_CODEC: &str = "VP9";
 
 struct VideoProfile {
+    source: VideoStreamSource,
     title: &'static str,
     description: &'static str,
-    bitrate_label: &'static str,
-    bitrate_kbps: u32,
-    bitrate_max_kbps: u32,
-    resolution: &'static str,
-    fps: &'static str,
 }
 
 const CAMERA_PROFILE: VideoProfile = VideoProfile {
+    source: VideoStreamSource::Camera,
     title: "Камера",
-    description: "Пределы видео для камеры на этом сервере.",
-    bitrate_label: "700 кбит/с",
-    bitrate_kbps: 700,
-    bitrate_max_kbps: 2_000,
-    resolution: "1280x720",
-    fps: "24 fps",
+    description: "Разрешенные режимы камеры на этом сервере.",
 };
 
 const SCREEN_SHARE_PROFILE: VideoProfile = VideoProfile {
+    source: VideoStreamSource::ScreenShare,
     title: "Демонстрация экрана",
-    description: "Пределы видео для показа экрана на этом сервере.",
-    bitrate_label: "1.5 Мбит/с",
-    bitrate_kbps: 1_500,
-    bitrate_max_kbps: 2_000,
-    resolution: "1280x720 или 1920x1080",
-    fps: "30 fps или 15 fps",
+    description: "Разрешенные режимы показа экрана на этом сервере.",
 };
 
 /// Рендерит секцию настроек качества голоса и видео сервера.
 #[component]
-pub(crate) fn ServerVoiceVideoSettingsSection(server_id: String) -> Element {
+pub(crate) fn ServerVoiceVideoSettingsSection(server_id: String, can_manage: bool) -> Element {
+    let realtime_handle = use_context::<RealtimeHandle>();
+    let toast = use_context::<ToastHandle>();
+    let mut saved = use_signal(|| None::<ServerMediaSettings>);
+    let mut draft = use_signal(|| None::<ServerMediaSettings>);
+    let mut load_error = use_signal(String::new);
+    let mut is_saving = use_signal(|| false);
     let mount_server_id = server_id.clone();
     use_hook(move || {
         info!(
@@ -50,6 +50,80 @@ pub(crate) fn ServerVoiceVideoSettingsSection(server_id: String) -> Element {
             "opened server voice and video settings section"
         );
     });
+    let load_server_id = server_id.clone();
+    let load_realtime_handle = realtime_handle.clone();
+    let settings_load = use_resource(move || {
+        let realtime_handle = load_realtime_handle.clone();
+        let request_server_id = load_server_id.clone();
+        async move { realtime::load_server_media_settings(&realtime_handle, request_server_id).await }
+    });
+    use_effect(move || {
+        if saved().is_some() {
+            return;
+        }
+        let Some(result) = settings_load.read().clone() else {
+            return;
+        };
+        match result {
+            Ok(settings) => {
+                info!(
+                    server_id = %settings.server_id,
+                    "loaded server media settings in settings ui"
+                );
+                draft.set(Some(settings.clone()));
+                saved.set(Some(settings));
+                load_error.set(String::new());
+            }
+            Err(error) => {
+                warn!(%error, "failed to load server media settings in settings ui");
+                load_error.set(error.to_string());
+            }
+        }
+    });
+
+    let Some(current) = draft() else {
+        let error = load_error();
+        return rsx! {
+            div { class: "rounded-[20px] border border-zinc-800 bg-zinc-950/70 p-5 text-[13px] text-zinc-500",
+                if error.is_empty() {
+                    "Загружаем настройки голоса и видео..."
+                } else {
+                    span { class: "text-red-200", "{error}" }
+                }
+            }
+        };
+    };
+    let dirty = saved().as_ref() != Some(&current);
+    let disabled = !can_manage || is_saving();
+    let audio_kbps = current.audio_bitrate_bps / 1_000;
+    let save = move |_| {
+        let Some(settings) = draft() else {
+            return;
+        };
+        if is_saving() {
+            return;
+        }
+        is_saving.set(true);
+        let save_realtime = realtime_handle.clone();
+        spawn(async move {
+            match realtime::save_server_media_settings(&save_realtime, settings).await {
+                Ok(response) => {
+                    info!(
+                        server_id = %response.server_id,
+                        "saved server media settings in settings ui"
+                    );
+                    draft.set(Some(response.clone()));
+                    saved.set(Some(response));
+                    toast.success("Настройки голоса и видео сохранены.");
+                }
+                Err(error) => {
+                    warn!(%error, "failed to save server media settings in settings ui");
+                    toast.error(error.to_string());
+                }
+            }
+            is_saving.set(false);
+        });
+    };
 
     rsx! {
         div { class: "space-y-4 pb-24 xl:pb-0",
@@ -60,6 +134,29 @@ pub(crate) fn ServerVoiceVideoSettingsSection(server_id: String) -> Element {
                         p { class: "mt-2 max-w-2xl text-[13px] leading-6 text-zinc-500",
                             "Параметры качества, которые применяются к голосовым комнатам сервера."
                         }
+                        if !can_manage {
+                            p { class: "mt-2 text-[12px] text-zinc-400",
+                                "Изменять эти настройки могут участники с правом управлять голосом и видео."
+                            }
+                        }
+                    }
+                    if can_manage {
+                        div { class: "flex shrink-0 items-center gap-2",
+                            button {
+                                r#type: "button",
+                                disabled: !dirty || is_saving(),
+                                class: "rounded-xl border border-zinc-800 bg-zinc-900 px-3 py-2 text-[13px] font-medium text-zinc-300 transition hover:border-zinc-700 hover:text-zinc-100 disabled:opacity-50",
+                                onclick: move |_| draft.set(saved()),
+                                "Отменить"
+                            }
+                            button {
+                                r#type: "button",
+                                disabled: !dirty || is_saving(),
+                                class: "rounded-xl bg-accent px-3 py-2 text-[13px] font-semibold text-white transition hover:bg-blue-400 disabled:bg-accent/45 disabled:text-white/70",
+                                onclick: save,
+                                if is_saving() { "Сохраняем" } else { "Сохранить" }
+                            }
+                        }
                     }
                 }
             }
@@ -67,27 +164,76 @@ pub(crate) fn ServerVoiceVideoSettingsSection(server_id: String) -> Element {
             div { class: "rounded-[20px] border border-zinc-800 bg-zinc-950/70 p-5 shadow-[0_18px_60px_rgba(0,0,0,.22)]",
                 {section_heading(
                     "Аудио",
-                    "Параметры голоса и целевой битрейт микрофона.",
+                    "Кодек голоса и верхняя граница битрейта микрофона.",
                 )}
                 div { class: "mt-5 grid gap-4 lg:grid-cols-2",
                     {single_option_select("Кодек", AUDIO_CODEC)}
-                    {locked_range(
-                        "Битрейт",
-                        AUDIO_BITRATE_LABEL,
-                        AUDIO_BITRATE_KBPS,
-                        AUDIO_BITRATE_MAX_KBPS,
-                    )}
+                    label { class: "block min-w-0",
+                        div { class: "mb-2 flex items-center justify-between gap-3",
+                            span { class: "text-[13px] font-medium text-zinc-300", "Битрейт" }
+                            span { class: "shrink-0 text-[12px] font-medium text-zinc-200", "{audio_kbps} кбит/с" }
+                        }
+                        input {
+                            r#type: "range",
+                            min: "{MIN_AUDIO_BITRATE_BPS}",
+                            max: "{MAX_AUDIO_BITRATE_BPS}",
+                            step: "{AUDIO_BITRATE_STEP_BPS}",
+                            value: "{current.audio_bitrate_bps}",
+                            disabled,
+                            class: "h-10 w-full accent-blue-500 disabled:cursor-default disabled:opacity-80",
+                            "aria-label": "Битрейт: {audio_kbps} кбит/с",
+                            oninput: move |event| {
+                                if let Ok(value) = event.value().parse::<u32>() {
+                                    update_draft(draft, |settings| settings.audio_bitrate_bps = value);
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+
+            div { class: "rounded-[20px] border border-zinc-800 bg-zinc-950/70 p-5 shadow-[0_18px_60px_rgba(0,0,0,.22)]",
+                {section_heading(
+                    "Видео в комнате",
+                    "Сколько участников одной комнаты могут одновременно показывать камеру или экран.",
+                )}
+                label { class: "mt-5 block max-w-xs",
+                    span { class: "mb-2 block text-[13px] font-medium text-zinc-300", "Одновременных видеопотоков" }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "{MAX_VIDEO_PUBLISHERS}",
+                        value: "{current.max_video_publishers}",
+                        disabled,
+                        class: single_option_control_class(),
+                        oninput: move |event| {
+                            if let Ok(value) = event.value().parse::<u32>() {
+                                update_draft(draft, |settings| {
+                                    settings.max_video_publishers = value.clamp(1, MAX_VIDEO_PUBLISHERS);
+                                });
+                            }
+                        },
+                    }
                 }
             }
 
             div { class: "grid gap-4 xl:grid-cols-2",
-                {video_profile_card(CAMERA_PROFILE)}
-                {video_profile_card(SCREEN_SHARE_PROFILE)}
+                {video_profile_card(CAMERA_PROFILE, &current, disabled, draft)}
+                {video_profile_card(SCREEN_SHARE_PROFILE, &current, disabled, draft)}
             }
         }
     }
 }
 
+fn update_draft(
+    mut draft: Signal<Option<ServerMediaSettings>>,
+    update: impl FnOnce(&mut ServerMediaSettings),
+) {
+    if let Some(settings) = draft.write().as_mut() {
+        update(settings);
+    }
+}
+
 fn section_heading(title: &'static str, description: &'static str) -> Element {
     rsx! {
         div {
@@ -97,25 +243,73 @@ fn section_heading(title: &'static str, description: &'static str) -> Element {
     }
 }
 
-fn video_profile_card(profile: VideoProfile) -> Element {
+fn video_profile_card(
+    profile: VideoProfile,
+    settings: &ServerMediaSettings,
+    disabled: bool,
+    draft: Signal<Option<ServerMediaSettings>>,
+) -> Element {
+    let presets = ALL_VIDEO_PRESETS
+        .iter()
+        .copied()
+        .filter(|preset| preset.spec().source == profile.source)
+        .map(|preset| (preset, settings.allowed_video_presets.contains(&preset)));
+
     rsx! {
         div { class: "rounded-[20px] border border-zinc-800 bg-zinc-950/70 p-5 shadow-[0_18px_60px_rgba(0,0,0,.22)]",
             {section_heading(profile.title, profile.description)}
-            div { class: "mt-5 grid gap-4 sm:grid-cols-2",
+            div { class: "mt-5 grid gap-4",
                 {single_option_select("Кодек", VIDEO_CODEC)}
-                {locked_range(
-                    "Битрейт",
-                    profile.bitrate_label,
-                    profile.bitrate_kbps,
-                    profile.bitrate_max_kbps,
-                )}
-                {single_option_select("Максимальное разрешение видео", profile.resolution)}
-                {single_option_select("Частота кадров", profile.fps)}
+                div { class: "space-y-2",
+                    for (preset , allowed) in presets {
+                        label {
+                            key: "{preset_label(preset)}",
+                            class: "flex items-center gap-3 rounded-xl border border-zinc-800 bg-zinc-900/60 px-3 py-2.5 text-[13px] text-zinc-200",
+                            input {
+                                r#type: "checkbox",
+                                checked: allowed,
+                                disabled,
+                                class: "size-4 accent-blue-500",
+                                onchange: move |event| {
+                                    let enabled = event.checked();
+                                    update_draft(draft, |settings| toggle_preset(settings, preset, enabled));
+                                },
+                            }
+                            span { class: "min-w-0 flex-1", "{preset_label(preset)}" }
+                        }
+                    }
+                }
             }
         }
     }
 }
 
+fn toggle_preset(settings: &mut ServerMediaSettings, preset: VideoPresetId, enabled: bool) {
+    settings
+        .allowed_video_presets
+        .retain(|saved| *saved != preset);
+    if enabled {
+        settings.allowed_video_presets.push(preset);
+        settings.allowed_video_presets.sort_by_key(|saved| {
+            ALL_VIDEO_PRESETS
+                .iter()
+                .position(|known| known == saved)
+                .unwrap_or(usize::MAX)
+        });
+    }
+}
+
+fn preset_label(preset: VideoPresetId) -> String {
+    let spec = preset.spec();
+    format!(
+        "{}x{} · {} fps · {} кбит/с",
+        spec.width,
+        spec.height,
+        spec.max_fps,
+        spec.bitrate_bps / 1_000
+    )
+}
+
 fn single_option_select(label: &'static str, value: &'static str) -> Element {
     rsx! {
         label { class: "block min-w-0",
@@ -129,31 +323,6 @@ fn single_option_select(label: &'static str, value: &'static str) -> Element {
     }
 }
 
-fn locked_range(label: &'static str, value_label: &'static str, value: u32, max: u32) -> Element {
-    rsx! {
-        div { class: "group/bitrate relative block min-w-0", tabindex: "0",
-            div { class: "mb-2 flex items-center justify-between gap-3",
-                label { class: "text-[13px] font-medium text-zinc-300", "{label}" }
-                span { class: "shrink-0 text-[12px] font-medium text-zinc-200", "{value_label}" }
-            }
-            input {
-                r#type: "range",
-                min: "0",
-                max: "{max}",
-                value: "{value}",
-                disabled: true,
-                class: "h-10 w-full cursor-default accent-blue-500 disabled:opacity-80",
-                "aria-label": "{label}: {value_label}",
-            }
-            span {
-                role: "tooltip",
-                class: "pointer-events-none absolute right-0 top-[calc(100%+8px)] z-20 w-max max-w-[220px] translate-y-1 rounded-xl border border-zinc-800 bg-zinc-950/95 px-3 py-2 text-[12px] leading-4 text-zinc-200 opacity-0 shadow-[0_16px_40px_rgba(0,0,0,.45)] backdrop-blur-xl transition-[opacity,transform] duration-150 group-hover/bitrate:translate-y-0 group-hover/bitrate:opacity-100 group-focus/bitrate:translate-y-0 group-focus/bitrate:opacity-100",
-                "Настройка битрейта в разработке."
-            }
-        }
-    }
-}
-
 fn single_option_control_class() -> &'static str {
     "h-10 w-full cursor-pointer rounded-xl border border-zinc-800 bg-zinc-950 px-3 text-[13px] text-zinc-100 outline-none transition focus:border-accent/70 focus:ring-4 focus:ring-accent/10"
 }
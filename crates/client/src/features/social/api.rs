@@ -1,11 +1,12 @@
 //! REST API клиента для друзей и личных сообщений.
 
+use cheenhub_contracts::realtime::TextChatAttachment;
 use cheenhub_contracts::rest::{
-    DmConversationSummary, DmImageAttachmentSummary, DmMessageSummary, ListDmConversationsResponse,
-    ListDmMessagesResponse, ListFriendRequestsResponse, ListFriendsResponse,
-    MarkDmConversationReadRequest, MarkDmConversationReadResponse, OpenDmConversationRequest,
-    OpenDmConversationResponse, SearchUsersResponse, SendDmMessageRequest, SendDmMessageResponse,
-    SendFriendRequestRequest, SendFriendRequestResponse, UploadDmImageResponse, UserSearchResult,
+    DmConversationSummary, DmMessageSummary, ListDmConversationsResponse, ListDmMessagesResponse,
+    ListFriendRequestsResponse, ListFriendsResponse, MarkDmConversationReadRequest,
+    MarkDmConversationReadResponse, OpenDmConversationRequest, OpenDmConversationResponse,
+    SearchUsersResponse, SendDmMessageRequest, SendDmMessageResponse, SendFriendRequestRequest,
+    SendFriendRequestResponse, UploadDmAttachmentResponse, UserSearchResult,
 };
 use reqwest::{Response, StatusCode};
 use serde::Serialize;
@@ -177,36 +178,63 @@ pub(crate) async fn mark_dm_conversation_read(
 pub(crate) async fn send_dm_message(
     conversation_id: &str,
     body: String,
-    image_id: Option<String>,
+    attachment_id: Option<String>,
 ) -> Result<DmMessageSummary, String> {
     authorized_json::<SendDmMessageResponse>(
         "POST",
         &format!("/direct-messages/conversations/{conversation_id}/messages"),
-        Some(&SendDmMessageRequest { body, image_id }),
+        Some(&SendDmMessageRequest {
+            body,
+            attachment_id,
+        }),
     )
     .await
     .map(|response| response.message)
 }
 
-/// Загружает изображение для личного сообщения.
-pub(crate) async fn upload_dm_image(
+/// Загружает файл, который затем можно прикрепить к личному сообщению.
+pub(crate) async fn upload_dm_attachment(
     conversation_id: &str,
+    file_name: Option<&str>,
     bytes: Vec<u8>,
-) -> Result<DmImageAttachmentSummary, String> {
-    let path = format!("/direct-messages/conversations/{conversation_id}/images");
+) -> Result<TextChatAttachment, String> {
+    let mut path = format!("/direct-messages/conversations/{conversation_id}/attachments");
+    if let Some(file_name) = file_name {
+        path.push_str("?filename=");
+        path.push_str(&encode_query(file_name));
+    }
     let response = authorized_bytes_response("POST", &path, Some(bytes)).await?;
     if response.status().is_success() {
         response
-            .json::<UploadDmImageResponse>()
+            .json::<UploadDmAttachmentResponse>()
             .await
-            .map(|value| value.image)
+            .map(|value| value.attachment)
             .map_err(|_| "Не удалось прочитать ответ сервера.".to_owned())
     } else {
         Err(auth_api::read_error(response).await)
     }
 }
 
-/// Загружает байты изображения личного сообщения.
+/// Загружает байты вложения личного сообщения.
+pub(crate) async fn load_dm_attachment(
+    conversation_id: &str,
+    attachment_id: &str,
+) -> Result<Vec<u8>, String> {
+    let path =
+        format!("/direct-messages/conversations/{conversation_id}/attachments/{attachment_id}");
+    let response = authorized_bytes_response("GET", &path, None).await?;
+    if response.status().is_success() {
+        response
+            .bytes()
+            .await
+            .map(|bytes| bytes.to_vec())
+            .map_err(|_| "Не удалось загрузить файл.".to_owned())
+    } else {
+        Err(auth_api::read_error(response).await)
+    }
+}
+
+/// Загружает байты изображения старого формата из личного сообщения.
 pub(crate) async fn load_dm_image(
     conversation_id: &str,
     image_id: &str,
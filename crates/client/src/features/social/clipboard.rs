@@ -2,13 +2,13 @@
 
 mod native;
 
-use super::direct_message_pending_image::PendingDirectMessageImage;
+use crate::features::text_chat::PendingChatAttachment;
 use dioxus::prelude::*;
 
 /// Читает изображение из paste-события, не вмешиваясь в текстовую вставку.
 pub(super) fn read_pasted_image(
     event: ClipboardEvent,
-    on_outcome: EventHandler<Result<PendingDirectMessageImage, String>>,
+    on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     native::read_pasted_image(event, on_outcome)
 }
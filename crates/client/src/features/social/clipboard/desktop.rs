@@ -3,12 +3,12 @@
 use dioxus::prelude::*;
 use image::ImageEncoder;
 
-use crate::features::social::direct_message_pending_image::PendingDirectMessageImage;
+use crate::features::text_chat::PendingChatAttachment;
 
 /// Desktop-клиент читает изображение по сочетанию клавиш, а не из browser event.
 pub(super) fn read_pasted_image(
     _event: ClipboardEvent,
-    _on_outcome: EventHandler<Result<PendingDirectMessageImage, String>>,
+    _on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     false
 }
@@ -12,12 +12,12 @@ mod platform;
 #[path = "unsupported.rs"]
 mod platform;
 
-use super::super::direct_message_pending_image::PendingDirectMessageImage;
+use crate::features::text_chat::PendingChatAttachment;
 use dioxus::prelude::*;
 
 pub(super) fn read_pasted_image(
     event: ClipboardEvent,
-    on_outcome: EventHandler<Result<PendingDirectMessageImage, String>>,
+    on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     platform::read_pasted_image(event, on_outcome)
 }
@@ -2,12 +2,12 @@
 
 use dioxus::prelude::*;
 
-use crate::features::social::direct_message_pending_image::PendingDirectMessageImage;
+use crate::features::text_chat::PendingChatAttachment;
 
 /// На неподдерживаемой платформе paste-вложение отсутствует.
 pub(super) fn read_pasted_image(
     _event: ClipboardEvent,
-    _on_outcome: EventHandler<Result<PendingDirectMessageImage, String>>,
+    _on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     false
 }
@@ -4,16 +4,14 @@ use dioxus::prelude::*;
 use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 
-use crate::features::social::direct_message_pending_image::{
-    PendingDirectMessageImage, pending_direct_message_image,
-};
+use crate::features::text_chat::{PendingChatAttachment, pending_chat_attachment};
 
-const MAX_DM_IMAGE_BYTES: usize = 8 * 1024 * 1024;
+const MAX_DM_ATTACHMENT_BYTES: usize = 50 * 1024 * 1024;
 
 /// Синхронно извлекает изображение из browser event и запускает чтение в Dioxus runtime.
 pub(super) fn read_pasted_image(
     event: ClipboardEvent,
-    on_outcome: EventHandler<Result<PendingDirectMessageImage, String>>,
+    on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     let Some(browser_event) = browser_clipboard_event(&event) else {
         warn!("direct message paste event did not contain a browser ClipboardEvent");
@@ -71,14 +69,14 @@ fn browser_clipboard_event(event: &ClipboardEvent) -> Option<web_sys::ClipboardE
 async fn read_image_file(
     file: web_sys::File,
     file_name: Option<String>,
-) -> Result<PendingDirectMessageImage, String> {
+) -> Result<PendingChatAttachment, String> {
     let buffer = JsFuture::from(file.array_buffer())
         .await
         .map_err(|_| "Не удалось прочитать изображение из буфера обмена.".to_owned())?;
-    pending_direct_message_image(
+    pending_chat_attachment(
         file_name,
         js_sys::Uint8Array::new(&buffer).to_vec(),
-        MAX_DM_IMAGE_BYTES,
+        MAX_DM_ATTACHMENT_BYTES,
     )
 }
 
@@ -1,4 +1,4 @@
-//! Форма отправки текста и изображений в выбранный личный диалог.
+//! Форма отправки текста и вложений в выбранный личный диалог.
 
 use std::{cell::Cell, rc::Rc};
 
@@ -6,14 +6,13 @@ use cheenhub_contracts::rest::{DmConversationSummary, DmMessageSummary};
 use dioxus::prelude::*;
 
 use crate::features::image_picker::{ImagePickerButton, ImagePickerOutcome, PickedImage};
-
-use super::direct_message_attachment_preview::DirectMessageAttachmentPreview;
-use super::direct_message_pending_image::{
-    PendingDirectMessageImage, pending_direct_message_image,
+use crate::features::text_chat::{
+    ChatAttachmentPreview, PendingChatAttachment, pending_chat_attachment,
 };
+
 use super::{api, clipboard};
 
-const MAX_DM_IMAGE_BYTES: usize = 8 * 1024 * 1024;
+const MAX_DM_ATTACHMENT_BYTES: usize = 50 * 1024 * 1024;
 const DIRECT_MESSAGE_COMPOSER_GROUP_CLASS: &str = "mx-auto min-w-0 w-full max-w-5xl space-y-2";
 const DIRECT_MESSAGE_COMPOSER_CLASS: &str = concat!(
     "direct-message-input-wrap flex min-w-0 w-full items-end gap-2 rounded-[20px] ",
@@ -35,7 +34,7 @@ pub(super) fn DirectMessageComposer(
     on_outcome: EventHandler<DirectMessageComposerOutcome>,
 ) -> Element {
     let mut draft = use_signal(String::new);
-    let mut pending_attachment = use_signal(|| None::<PendingDirectMessageImage>);
+    let mut pending_attachment = use_signal(|| None::<PendingChatAttachment>);
     let mut status = use_signal(String::new);
     let mut is_sending = use_signal(|| false);
     let mut is_selecting_image = use_signal(|| false);
@@ -70,19 +69,25 @@ pub(super) fn DirectMessageComposer(
         refocus_requested.set(true);
         status.set(String::new());
         spawn(async move {
-            let image_id = match attachment {
+            let attachment_id = match attachment {
                 Some(attachment) => match attachment.uploaded_id {
-                    Some(image_id) => Some(image_id),
-                    None => match api::upload_dm_image(&conversation_id, attachment.bytes).await {
-                        Ok(image) => {
-                            info!(conversation_id, image_id = %image.id, "uploaded pending direct message image");
+                    Some(attachment_id) => Some(attachment_id),
+                    None => match api::upload_dm_attachment(
+                        &conversation_id,
+                        attachment.file_name.as_deref(),
+                        attachment.bytes,
+                    )
+                    .await
+                    {
+                        Ok(uploaded) => {
+                            info!(conversation_id, attachment_id = %uploaded.id, kind = ?uploaded.kind, "uploaded pending direct message attachment");
                             if let Some(pending) = pending_attachment.write().as_mut() {
-                                pending.uploaded_id = Some(image.id.clone());
+                                pending.uploaded_id = Some(uploaded.id.clone());
                             }
-                            Some(image.id)
+                            Some(uploaded.id)
                         }
                         Err(error) => {
-                            warn!(conversation_id, %error, "direct message image upload failed");
+                            warn!(conversation_id, %error, "direct message attachment upload failed");
                             status.set(error);
                             is_sending.set(false);
                             restore_composer_input_focus(
@@ -96,7 +101,7 @@ pub(super) fn DirectMessageComposer(
                 },
                 None => None,
             };
-            match api::send_dm_message(&conversation_id, body, image_id).await {
+            match api::send_dm_message(&conversation_id, body, attachment_id).await {
                 Ok(message) => {
                     debug!(conversation_id, message_id = %message.id, "sent direct message");
                     draft.set(String::new());
@@ -118,43 +123,40 @@ pub(super) fn DirectMessageComposer(
     });
 
     let add_image_conversation_id = conversation_id.clone();
-    let add_pending_image = use_callback(
-        move |result: Result<PendingDirectMessageImage, String>| {
-            if is_sending() || pending_attachment().is_some() {
-                return;
+    let add_pending_image = use_callback(move |result: Result<PendingChatAttachment, String>| {
+        if is_sending() || pending_attachment().is_some() {
+            return;
+        }
+        match result {
+            Ok(attachment) => {
+                info!(
+                    conversation_id = add_image_conversation_id,
+                    byte_size = attachment.byte_size,
+                    has_file_name = attachment.file_name.is_some(),
+                    "added pending direct message attachment"
+                );
+                status.set(String::new());
+                pending_attachment.set(Some(attachment));
             }
-            match result {
-                Ok(attachment) => {
-                    info!(
-                        conversation_id = add_image_conversation_id,
-                        byte_size = attachment.byte_size,
-                        has_file_name = attachment.file_name.is_some(),
-                        "added pending direct message image"
-                    );
-                    status.set(String::new());
-                    pending_attachment.set(Some(attachment));
-                }
-                Err(error) => {
-                    warn!(conversation_id = add_image_conversation_id, %error, "direct message image rejected before upload");
-                    status.set(error);
-                }
+            Err(error) => {
+                warn!(conversation_id = add_image_conversation_id, %error, "direct message attachment rejected before upload");
+                status.set(error);
             }
-        },
-    );
+        }
+    });
     let select_pending_image = use_callback(move |outcome: ImagePickerOutcome| {
         let result = match outcome {
             ImagePickerOutcome::Selected(PickedImage { file_name, bytes }) => {
-                pending_direct_message_image(file_name, bytes, MAX_DM_IMAGE_BYTES)
+                pending_chat_attachment(file_name, bytes, MAX_DM_ATTACHMENT_BYTES)
             }
             ImagePickerOutcome::Failed(error) => Err(error),
         };
         add_pending_image.call(result);
     });
-    let clipboard_outcome =
-        use_callback(move |result: Result<PendingDirectMessageImage, String>| {
-            is_reading_clipboard.set(false);
-            add_pending_image.call(result);
-        });
+    let clipboard_outcome = use_callback(move |result: Result<PendingChatAttachment, String>| {
+        is_reading_clipboard.set(false);
+        add_pending_image.call(result);
+    });
     let clipboard_started = use_callback(move |_| is_reading_clipboard.set(true));
     let remove_conversation_id = conversation_id.clone();
 
@@ -168,12 +170,12 @@ pub(super) fn DirectMessageComposer(
                     }
                 }
                 if let Some(attachment) = pending_attachment() {
-                    DirectMessageAttachmentPreview {
+                    ChatAttachmentPreview {
                         attachment,
                         busy: is_sending(),
                         on_remove: move |_| {
                             if !is_sending() {
-                                info!(conversation_id = remove_conversation_id, "removed pending direct message image");
+                                info!(conversation_id = remove_conversation_id, "removed pending direct message attachment");
                                 pending_attachment.set(None);
                                 status.set(String::new());
                             }
@@ -184,7 +186,8 @@ pub(super) fn DirectMessageComposer(
                 ImagePickerButton {
                     disabled: busy || pending_attachment().is_some(),
                     busy: is_selecting_image() || is_reading_clipboard(),
-                    max_bytes: MAX_DM_IMAGE_BYTES,
+                    max_bytes: MAX_DM_ATTACHMENT_BYTES,
+                    accept_any_file: true,
                     on_outcome: move |outcome| select_pending_image.call(outcome),
                     on_active_change: move |active| is_selecting_image.set(active),
                 }
@@ -217,10 +220,10 @@ pub(super) fn DirectMessageComposer(
                             is_reading_clipboard.set(true);
                             spawn(async move {
                                 match clipboard::read_image_png().await {
-                                    Ok(Some(bytes)) => clipboard_outcome.call(pending_direct_message_image(None, bytes, MAX_DM_IMAGE_BYTES)),
+                                    Ok(Some(bytes)) => clipboard_outcome.call(pending_chat_attachment(None, bytes, MAX_DM_ATTACHMENT_BYTES)),
                                     Ok(None) => is_reading_clipboard.set(false),
                                     Err(error) => {
-                                        warn!(conversation_id, %error, "failed to read direct message image from clipboard");
+                                        warn!(conversation_id, %error, "failed to read direct message attachment from clipboard");
                                         status.set(error);
                                         is_reading_clipboard.set(false);
                                     }
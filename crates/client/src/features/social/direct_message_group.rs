@@ -1,21 +1,25 @@
 //! Группа последовательных личных сообщений одного автора.
 
-use cheenhub_contracts::rest::DmMessageSummary;
+use cheenhub_contracts::realtime::{TextChatAttachment, TextChatAttachmentKind};
+use cheenhub_contracts::rest::{DmImageAttachmentSummary, DmMessageSummary};
 use dioxus::prelude::*;
 
 use crate::features::app::components::avatar::{UserAvatar, use_avatar_seed};
 use crate::features::app::current_user::CurrentUserContext;
-use crate::features::text_chat::{ChatMessageItem, is_appearing_message};
+use crate::features::text_chat::{
+    AttachmentSource, ChatFileAttachment, ChatMessageItem, is_appearing_message,
+};
 
-use super::direct_message_image::DirectMessageImage;
+use super::direct_message_image::{DirectMessageImage, DirectMessageImageSource};
 use super::presentation::dm_as_text_message;
 
-/// Рендерит сообщения и изображения в исходном порядке внутри авторской группы.
+/// Рендерит сообщения и вложения в исходном порядке внутри авторской группы.
 #[component]
 pub(super) fn DirectMessageGroup(
     messages: Vec<DmMessageSummary>,
     appearing_message_ids: Vec<String>,
     removing_message_ids: Vec<String>,
+    on_react: EventHandler<(String, String, bool)>,
 ) -> Element {
     let Some(first_message) = messages.first().cloned() else {
         return rsx! {};
@@ -64,11 +68,33 @@ pub(super) fn DirectMessageGroup(
                             removing: removing_message_ids.contains(&message.id),
                             can_delete_messages: false,
                             on_delete: move |_| {},
+                            editable: false,
+                            on_edit: move |_| {},
+                            detached: false,
+                            on_react: move |reaction| on_react.call(reaction),
                             if let Some(image) = message.image.clone() {
                                 DirectMessageImage {
                                     conversation_id: message.conversation_id.clone(),
                                     author_user_id: message.sender_user_id.clone(),
                                     image,
+                                    source: DirectMessageImageSource::Legacy,
+                                }
+                            }
+                            if let Some(attachment) = message.attachment.clone() {
+                                if attachment.kind == TextChatAttachmentKind::Image {
+                                    DirectMessageImage {
+                                        conversation_id: message.conversation_id.clone(),
+                                        author_user_id: message.sender_user_id.clone(),
+                                        image: image_summary(&attachment),
+                                        source: DirectMessageImageSource::Attachment,
+                                    }
+                                } else {
+                                    ChatFileAttachment {
+                                        key: "{attachment.id}",
+                                        source: AttachmentSource::DirectMessage(message.conversation_id.clone()),
+                                        attachment,
+                                        is_own: message.sender_user_id == current_user.id,
+                                    }
                                 }
                             }
                         }
@@ -78,3 +104,12 @@ pub(super) fn DirectMessageGroup(
         }
     }
 }
+
+fn image_summary(attachment: &TextChatAttachment) -> DmImageAttachmentSummary {
+    DmImageAttachmentSummary {
+        id: attachment.id.clone(),
+        content_type: attachment.content_type.clone(),
+        width: attachment.width.unwrap_or_default(),
+        height: attachment.height.unwrap_or_default(),
+    }
+}
@@ -14,12 +14,22 @@ use crate::features::app::components::chat_image_viewer::{
 
 use super::api;
 
+/// Путь, по которому загружаются байты изображения личного сообщения.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DirectMessageImageSource {
+    /// Изображение старого формата из таблицы изображений.
+    Legacy,
+    /// Типизированное вложение вида `image`.
+    Attachment,
+}
+
 /// Показывает защищённо загруженное изображение личного сообщения.
 #[component]
 pub(crate) fn DirectMessageImage(
     conversation_id: String,
     author_user_id: String,
     image: DmImageAttachmentSummary,
+    source: DirectMessageImageSource,
 ) -> Element {
     let current_user =
         use_context::<crate::features::app::current_user::CurrentUserContext>().require_user();
@@ -37,7 +47,14 @@ pub(crate) fn DirectMessageImage(
         let conversation_id = conversation_id.clone();
         let image_id = image_id.clone();
         async move {
-            let result = api::load_dm_image(&conversation_id, &image_id).await;
+            let result = match source {
+                DirectMessageImageSource::Legacy => {
+                    api::load_dm_image(&conversation_id, &image_id).await
+                }
+                DirectMessageImageSource::Attachment => {
+                    api::load_dm_attachment(&conversation_id, &image_id).await
+                }
+            };
             if let Err(error) = &result {
                 warn!(
                     image_id = %image_id,
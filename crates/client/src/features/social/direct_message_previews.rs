@@ -0,0 +1,34 @@
+//! Карточки ссылок в сообщениях выбранного личного диалога.
+
+use cheenhub_contracts::rest::DmMessageSummary;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::features::realtime::RealtimeHandle;
+
+use super::realtime::subscribe_direct_message_preview_events;
+
+/// Подставляет в загруженные сообщения диалога карточки ссылок, готовые после отправки.
+pub(super) fn use_direct_message_link_previews(
+    conversation_id: String,
+    mut messages: Signal<Vec<DmMessageSummary>>,
+) {
+    let realtime = use_context::<RealtimeHandle>();
+
+    use_hook(move || {
+        spawn(async move {
+            let mut events = subscribe_direct_message_preview_events(&realtime);
+            while let Some(event) = events.next().await {
+                if event.conversation_id != conversation_id {
+                    continue;
+                }
+                let mut next = messages();
+                let Some(message) = next.iter_mut().find(|m| m.id == event.message_id) else {
+                    continue;
+                };
+                message.link_previews = event.previews;
+                messages.set(next);
+            }
+        });
+    });
+}
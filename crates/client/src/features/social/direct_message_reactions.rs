@@ -0,0 +1,65 @@
+//! Реакции на сообщения выбранного личного диалога.
+
+use cheenhub_contracts::rest::DmMessageSummary;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::features::app::current_user::CurrentUserContext;
+use crate::features::realtime::RealtimeHandle;
+use crate::features::text_chat::merge_broadcast_reactions;
+
+use super::realtime::{subscribe_direct_message_reaction_events, toggle_direct_message_reaction};
+
+/// Применяет входящие изменения реакций диалога и возвращает обработчик
+/// установки и снятия реакций текущим пользователем.
+pub(super) fn use_direct_message_reactions(
+    conversation_id: String,
+    mut messages: Signal<Vec<DmMessageSummary>>,
+    mut status: Signal<String>,
+) -> Callback<(String, String, bool)> {
+    let realtime = use_context::<RealtimeHandle>();
+    let current_user_id = use_context::<CurrentUserContext>().require_user().id;
+
+    use_hook({
+        let realtime = realtime.clone();
+        let conversation_id = conversation_id.clone();
+        move || {
+            spawn(async move {
+                let mut events = subscribe_direct_message_reaction_events(&realtime);
+                while let Some(event) = events.next().await {
+                    if event.conversation_id != conversation_id {
+                        continue;
+                    }
+                    let mut next = messages();
+                    let Some(message) = next.iter_mut().find(|m| m.id == event.message_id) else {
+                        continue;
+                    };
+                    let own_change = (event.user_id == current_user_id)
+                        .then_some((event.emoji.as_str(), event.added));
+                    message.reactions =
+                        merge_broadcast_reactions(&message.reactions, event.reactions, own_change);
+                    messages.set(next);
+                }
+            });
+        }
+    });
+
+    use_callback(move |(message_id, emoji, add): (String, String, bool)| {
+        let realtime = realtime.clone();
+        let conversation_id = conversation_id.clone();
+        spawn(async move {
+            match toggle_direct_message_reaction(&realtime, conversation_id, message_id, emoji, add)
+                .await
+            {
+                Ok(changed) => {
+                    let mut next = messages();
+                    if let Some(message) = next.iter_mut().find(|m| m.id == changed.message_id) {
+                        message.reactions = changed.reactions;
+                        messages.set(next);
+                    }
+                }
+                Err(error) => status.set(error.to_string()),
+            }
+        });
+    })
+}
@@ -24,6 +24,8 @@ use crate::features::voice_chat::{DirectCallHandle, VoiceConnectionHandle, Voice
 use super::direct_message_chat_platform;
 use super::direct_message_composer::{DirectMessageComposer, DirectMessageComposerOutcome};
 use super::direct_message_group::DirectMessageGroup;
+use super::direct_message_previews::use_direct_message_link_previews;
+use super::direct_message_reactions::use_direct_message_reactions;
 use super::direct_message_state::DirectMessageState;
 use super::direct_message_voice_surface::DirectMessageVoiceSurface;
 use super::presentation::{
@@ -68,6 +70,8 @@ pub(crate) fn DirectMessageWorkspace(
         list_element,
         pending_scroll,
     };
+    let on_react = use_direct_message_reactions(conversation.id.clone(), messages, status);
+    use_direct_message_link_previews(conversation.id.clone(), messages);
     let mut embedded_chat_height_px = use_signal(|| None::<f64>);
     let mut embedded_chat_resize_origin = use_signal(|| None::<(f64, f64, f64)>);
     let mut content_split_element = use_signal(|| None::<Rc<MountedData>>);
@@ -433,6 +437,7 @@ pub(crate) fn DirectMessageWorkspace(
                                                 messages: group,
                                                 appearing_message_ids: appearing_message_ids_list.clone(),
                                                 removing_message_ids: removing_message_ids_list.clone(),
+                                                on_react: move |reaction| on_react.call(reaction),
                                             }
                                         }
                                     }
@@ -296,6 +296,12 @@ pub(super) fn dm_as_text_message(message: DmMessageSummary) -> TextChatMessage {
         attachments: Vec::new(),
         delivery_status: message.delivery_status,
         created_at: message.created_at,
+        edited_at: None,
+        reply_to_message_id: None,
+        reply_to: None,
+        reactions: message.reactions,
+        mentions: Vec::new(),
+        link_previews: message.link_previews,
     }
 }
 
@@ -1,8 +1,9 @@
 //! Realtime-подписка на social-события.
 
 use cheenhub_contracts::realtime::{
-    DirectMessageCreated, RealtimeEnvelope, RealtimeKind, RealtimeModule, SocialChanged,
-    SocialKind, SocialReady, SubscribeSocial,
+    AddDirectMessageReaction, DirectMessageCreated, DirectMessagePreviewReady,
+    DirectMessageReactionsChanged, RealtimeEnvelope, RealtimeKind, RealtimeModule,
+    RemoveDirectMessageReaction, SocialChanged, SocialKind, SocialReady, SubscribeSocial,
 };
 use dioxus::prelude::{debug, info, warn};
 use futures_channel::mpsc;
@@ -127,6 +128,100 @@ pub(crate) fn subscribe_direct_message_events(
     receiver
 }
 
+/// Ставит или снимает реакцию текущего пользователя на личное сообщение.
+pub(super) async fn toggle_direct_message_reaction(
+    realtime: &RealtimeHandle,
+    conversation_id: String,
+    message_id: String,
+    emoji: String,
+    add: bool,
+) -> Result<DirectMessageReactionsChanged, RealtimeError> {
+    if add {
+        realtime
+            .request(
+                RealtimeModule::Social,
+                RealtimeKind::Social(SocialKind::AddDirectMessageReaction),
+                AddDirectMessageReaction {
+                    conversation_id,
+                    message_id,
+                    emoji,
+                },
+            )
+            .await
+    } else {
+        realtime
+            .request(
+                RealtimeModule::Social,
+                RealtimeKind::Social(SocialKind::RemoveDirectMessageReaction),
+                RemoveDirectMessageReaction {
+                    conversation_id,
+                    message_id,
+                    emoji,
+                },
+            )
+            .await
+    }
+}
+
+/// Подписывается на изменения реакций на личные сообщения текущего пользователя.
+pub(super) fn subscribe_direct_message_reaction_events(
+    realtime: &RealtimeHandle,
+) -> mpsc::UnboundedReceiver<DirectMessageReactionsChanged> {
+    let events = realtime.subscribe_events();
+    let (sender, receiver) = mpsc::unbounded();
+
+    dioxus::prelude::spawn(async move {
+        let mut events = events;
+        while let Some(envelope) = events.next().await {
+            if envelope.module != RealtimeModule::Social
+                || envelope.kind != RealtimeKind::Social(SocialKind::DirectMessageReactionsChanged)
+            {
+                continue;
+            }
+            let Ok(event) =
+                serde_json::from_value::<DirectMessageReactionsChanged>(envelope.payload)
+            else {
+                warn!("failed to decode direct message reactions realtime event");
+                continue;
+            };
+            if sender.unbounded_send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
+/// Подписывается на готовые карточки ссылок в личных сообщениях текущего пользователя.
+pub(super) fn subscribe_direct_message_preview_events(
+    realtime: &RealtimeHandle,
+) -> mpsc::UnboundedReceiver<DirectMessagePreviewReady> {
+    let events = realtime.subscribe_events();
+    let (sender, receiver) = mpsc::unbounded();
+
+    dioxus::prelude::spawn(async move {
+        let mut events = events;
+        while let Some(envelope) = events.next().await {
+            if envelope.module != RealtimeModule::Social
+                || envelope.kind != RealtimeKind::Social(SocialKind::DirectMessagePreviewReady)
+            {
+                continue;
+            }
+            let Ok(event) = serde_json::from_value::<DirectMessagePreviewReady>(envelope.payload)
+            else {
+                warn!("failed to decode direct message link preview realtime event");
+                continue;
+            };
+            if sender.unbounded_send(event).is_err() {
+                break;
+            }
+        }
+    });
+
+    receiver
+}
+
 fn decode_social_event(envelope: RealtimeEnvelope) -> Option<SocialChanged> {
     if envelope.module != RealtimeModule::Social {
         return None;
@@ -0,0 +1,93 @@
+//! REST API клиента для вложений комнат текстового чата.
+
+use cheenhub_contracts::realtime::ChatAttachmentUploadResponse;
+use reqwest::{Response, StatusCode};
+
+use crate::features::auth::api as auth_api;
+
+const NETWORK_ERROR_MESSAGE: &str = "Не удалось связаться с сервером.";
+
+/// Загружает файл, который затем можно прикрепить к сообщению комнаты.
+pub(crate) async fn upload_chat_attachment(
+    server_id: &str,
+    room_id: &str,
+    file_name: Option<&str>,
+    bytes: Vec<u8>,
+) -> Result<ChatAttachmentUploadResponse, String> {
+    let mut path = format!("/servers/{server_id}/rooms/{room_id}/attachments");
+    if let Some(file_name) = file_name {
+        path.push_str("?filename=");
+        path.push_str(&encode_query(file_name));
+    }
+    let response = authorized_bytes_response("POST", &path, Some(bytes)).await?;
+    if response.status().is_success() {
+        response
+            .json::<ChatAttachmentUploadResponse>()
+            .await
+            .map_err(|_| "Не удалось прочитать ответ сервера.".to_owned())
+    } else {
+        Err(auth_api::read_error(response).await)
+    }
+}
+
+/// Загружает байты вложения комнаты.
+pub(crate) async fn load_chat_attachment(
+    server_id: &str,
+    attachment_id: &str,
+) -> Result<Vec<u8>, String> {
+    let path = format!("/servers/{server_id}/attachments/{attachment_id}");
+    let response = authorized_bytes_response("GET", &path, None).await?;
+    if response.status().is_success() {
+        response
+            .bytes()
+            .await
+            .map(|bytes| bytes.to_vec())
+            .map_err(|_| "Не удалось загрузить файл.".to_owned())
+    } else {
+        Err(auth_api::read_error(response).await)
+    }
+}
+
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .flat_map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                vec![char::from(byte)]
+            }
+            _ => format!("%{byte:02X}").chars().collect(),
+        })
+        .collect()
+}
+
+async fn authorized_bytes_response(
+    method: &str,
+    path: &str,
+    body: Option<Vec<u8>>,
+) -> Result<Response, String> {
+    let token = auth_api::fresh_access_token().await?;
+    let response = send_bytes(method, path, &token, body.clone()).await?;
+    if response.status() != StatusCode::UNAUTHORIZED {
+        return Ok(response);
+    }
+    let token = auth_api::refresh_access_token().await?;
+    send_bytes(method, path, &token, body).await
+}
+
+async fn send_bytes(
+    method: &str,
+    path: &str,
+    token: &str,
+    body: Option<Vec<u8>>,
+) -> Result<Response, String> {
+    let request = match method {
+        "GET" => auth_api::get(path),
+        _ => auth_api::post(path),
+    }
+    .header("Authorization", format!("Bearer {token}"));
+    request
+        .body(body.unwrap_or_default())
+        .send()
+        .await
+        .map_err(|_| NETWORK_ERROR_MESSAGE.to_owned())
+}
@@ -1,12 +1,12 @@
 //! Предварительный просмотр ожидающего вложения в форме сообщения.
 
-use super::pending_attachment::{PendingImageAttachment, format_attachment_size};
+use super::pending_attachment::{PendingChatAttachment, format_attachment_size};
 use dioxus::prelude::*;
 
-/// Показывает имя, размер и состояние изображения до отправки.
+/// Показывает имя, размер и состояние вложения до отправки.
 #[component]
 pub(crate) fn ChatAttachmentPreview(
-    attachment: PendingImageAttachment,
+    attachment: PendingChatAttachment,
     busy: bool,
     on_remove: EventHandler<()>,
 ) -> Element {
@@ -14,8 +14,8 @@ pub(crate) fn ChatAttachmentPreview(
         div { class: "flex size-12 shrink-0 items-center justify-center overflow-hidden rounded-xl border border-blue-300/10 bg-blue-400/15 text-blue-200",
             if let Some(preview_data_url) = attachment.preview_data_url.clone() {
                 img { class: "size-12 object-cover", src: "{preview_data_url}", alt: "Предварительный просмотр вложения" }
-            } else if busy { span { class: "size-4 animate-spin rounded-full border-2 border-blue-200/35 border-t-blue-100", "aria-label": "Подготавливаем изображение" } }
-            else { svg { class: "size-4", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24", "aria-hidden": "true", path { stroke_linecap: "round", stroke_linejoin: "round", d: "M4 16 8.586 11.414a2 2 0 0 1 2.828 0L16 16m-2-2 1.586-1.586a2 2 0 0 1 2.828 0L20 14M6 20h12a2 2 0 0 0 2-2V6a2 2 0 0 0-2-2H6a2 2 0 0 0-2 2v12a2 2 0 0 0 2 2Z" } } }
+            } else if busy { span { class: "size-4 animate-spin rounded-full border-2 border-blue-200/35 border-t-blue-100", "aria-label": "Подготавливаем файл" } }
+            else { svg { class: "size-4", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24", "aria-hidden": "true", path { stroke_linecap: "round", stroke_linejoin: "round", d: "M14 3H7a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h10a2 2 0 0 0 2-2V8l-5-5Zm0 0v5h5" } } }
         }
         div { class: "min-w-0 flex-1", p { class: "truncate text-[12px] font-medium", title: "{attachment.display_name}", "{attachment.display_name}" }
             p { class: "mt-0.5 truncate text-[11px] text-zinc-400", role: "status", "aria-live": "polite", "{format_attachment_size(attachment.byte_size)}" if busy { " · Подготавливаем к отправке…" } else { " · Будет отправлено вместе с сообщением" } }
@@ -2,13 +2,13 @@
 
 mod platform;
 
-use super::pending_attachment::PendingImageAttachment;
+use super::pending_attachment::PendingChatAttachment;
 use dioxus::prelude::*;
 
 /// Читает изображение из paste-события, не вмешиваясь в текстовую вставку.
 pub(crate) fn read_pasted_image(
     event: ClipboardEvent,
-    on_outcome: EventHandler<Result<PendingImageAttachment, String>>,
+    on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     platform::read_pasted_image(event, on_outcome)
 }
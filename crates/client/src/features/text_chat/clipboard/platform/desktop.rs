@@ -1,12 +1,12 @@
 //! Desktop-реализация чтения изображения из системного буфера обмена.
 
-use super::super::super::pending_attachment::{PendingImageAttachment, pending_image_attachment};
+use super::super::super::pending_attachment::{PendingChatAttachment, pending_chat_attachment};
 use dioxus::prelude::*;
 use image::ImageEncoder;
 
 pub(crate) fn read_pasted_image(
     event: ClipboardEvent,
-    on_outcome: EventHandler<Result<PendingImageAttachment, String>>,
+    on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     match read_image_png() {
         Ok(Some(bytes)) => {
@@ -16,7 +16,7 @@ pub(crate) fn read_pasted_image(
                 "read text chat image from desktop clipboard"
             );
             spawn(async move {
-                on_outcome.call(pending_image_attachment(None, bytes, 10 * 1024 * 1024));
+                on_outcome.call(pending_chat_attachment(None, bytes, 10 * 1024 * 1024));
             });
             true
         }
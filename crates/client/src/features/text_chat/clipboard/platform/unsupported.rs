@@ -1,11 +1,11 @@
 //! Заглушка paste-вложений для неподдерживаемых клиентских платформ.
 
-use super::super::super::pending_attachment::PendingImageAttachment;
+use super::super::super::pending_attachment::PendingChatAttachment;
 use dioxus::prelude::*;
 
 pub(crate) fn read_pasted_image(
     _event: ClipboardEvent,
-    _on_outcome: EventHandler<Result<PendingImageAttachment, String>>,
+    _on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     false
 }
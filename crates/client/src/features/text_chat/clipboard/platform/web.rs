@@ -5,13 +5,13 @@ use wasm_bindgen::JsCast;
 use wasm_bindgen_futures::JsFuture;
 
 use super::super::super::pending_attachment::{
-    PendingImageAttachment, is_supported_image_mime, pending_image_attachment,
+    PendingChatAttachment, is_supported_image_mime, pending_chat_attachment,
 };
 
 /// Синхронно извлекает файл из browser event, пока `DataTransfer` доступен.
 pub(crate) fn read_pasted_image(
     event: ClipboardEvent,
-    on_outcome: EventHandler<Result<PendingImageAttachment, String>>,
+    on_outcome: EventHandler<Result<PendingChatAttachment, String>>,
 ) -> bool {
     let Some(browser_event) = browser_clipboard_event(&event) else {
         warn!("text chat paste event did not contain a browser ClipboardEvent");
@@ -61,11 +61,11 @@ fn browser_clipboard_event(event: &ClipboardEvent) -> Option<web_sys::ClipboardE
 async fn read_image_file(
     file: web_sys::File,
     file_name: Option<String>,
-) -> Result<PendingImageAttachment, String> {
+) -> Result<PendingChatAttachment, String> {
     let buffer = JsFuture::from(file.array_buffer())
         .await
         .map_err(|_| "Не удалось прочитать изображение из буфера обмена.".to_owned())?;
-    pending_image_attachment(
+    pending_chat_attachment(
         file_name,
         js_sys::Uint8Array::new(&buffer).to_vec(),
         10 * 1024 * 1024,
@@ -5,8 +5,9 @@ use dioxus::prelude::*;
 
 use crate::features::realtime::RealtimeHandle;
 
+use super::api;
 use super::messages::append_message;
-use super::pending_attachment::PendingImageAttachment;
+use super::pending_attachment::PendingChatAttachment;
 use super::realtime;
 use super::scroll::ScrollCommand;
 
@@ -17,7 +18,8 @@ pub(super) struct ComposeState {
     pub(super) appearing_message_ids: Signal<Vec<String>>,
     pub(super) status: Signal<String>,
     pub(super) is_sending: Signal<bool>,
-    pub(super) pending_attachment: Signal<Option<PendingImageAttachment>>,
+    pub(super) pending_attachment: Signal<Option<PendingChatAttachment>>,
+    pub(super) reply_to: Signal<Option<TextChatMessage>>,
     pub(super) pending_scroll: Signal<Option<ScrollCommand>>,
 }
 
@@ -30,6 +32,7 @@ pub(super) fn send_current_message(
 ) {
     let body = (state.draft)().trim().to_owned();
     let attachment = (state.pending_attachment)();
+    let reply_to_message_id = (state.reply_to)().map(|message| message.id);
     if body.is_empty() && attachment.is_none() {
         return;
     }
@@ -40,25 +43,24 @@ pub(super) fn send_current_message(
         let attachment_id = match attachment {
             Some(attachment) => match attachment.uploaded_id {
                 Some(attachment_id) => Some(attachment_id),
-                None => match realtime::upload_chat_image(
-                    &realtime,
-                    server_id.clone(),
-                    room_id.clone(),
-                    attachment.file_name,
+                None => match api::upload_chat_attachment(
+                    &server_id,
+                    &room_id,
+                    attachment.file_name.as_deref(),
                     attachment.bytes,
                 )
                 .await
                 {
                     Ok(uploaded) => {
-                        info!(attachment_id = %uploaded.id, "uploaded pending text chat image");
+                        info!(attachment_id = %uploaded.id, kind = ?uploaded.kind, "uploaded pending text chat attachment");
                         if let Some(pending) = state.pending_attachment.write().as_mut() {
                             pending.uploaded_id = Some(uploaded.id.clone());
                         }
                         Some(uploaded.id)
                     }
                     Err(error) => {
-                        warn!(%error, "text chat image upload failed");
-                        state.status.set(error.to_string());
+                        warn!(%error, "text chat attachment upload failed");
+                        state.status.set(error);
                         state.is_sending.set(false);
                         on_complete.call(());
                         return;
@@ -67,7 +69,15 @@ pub(super) fn send_current_message(
             },
             None => None,
         };
-        match realtime::send_text_message(&realtime, server_id, room_id, body, attachment_id).await
+        match realtime::send_text_message(
+            &realtime,
+            server_id,
+            room_id,
+            body,
+            attachment_id,
+            reply_to_message_id,
+        )
+        .await
         {
             Ok(accepted) => {
                 let message_id = accepted.message.id.clone();
@@ -82,6 +92,7 @@ pub(super) fn send_current_message(
                 debug!(%message_id, "sent text chat message");
                 state.draft.set(String::new());
                 state.pending_attachment.set(None);
+                state.reply_to.set(None);
             }
             Err(error) => {
                 warn!(%error, "text chat message send failed");
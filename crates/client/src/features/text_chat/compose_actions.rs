@@ -1,12 +1,12 @@
 //! Локальные команды изменения состояния формы сообщения.
 
-use super::pending_attachment::PendingImageAttachment;
+use super::pending_attachment::PendingChatAttachment;
 use super::room_compose_state::RoomComposeState;
 use dioxus::prelude::*;
 
 pub(super) fn add_pending_image(
     mut state: RoomComposeState,
-    result: Result<PendingImageAttachment, String>,
+    result: Result<PendingChatAttachment, String>,
 ) {
     if (state.is_sending)() || (state.pending_attachment)().is_some() {
         return;
@@ -0,0 +1,272 @@
+//! Вид вложений-файлов, видео и аудио в текстовом чате и личных сообщениях.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use cheenhub_contracts::realtime::{TextChatAttachment, TextChatAttachmentKind};
+use dioxus::logger::tracing::warn;
+use dioxus::prelude::*;
+
+use super::api;
+use super::pending_attachment::format_attachment_size;
+use crate::features::social::api as social_api;
+
+/// Место, откуда загружаются байты вложения.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum AttachmentSource {
+    /// Вложение комнаты сервера с указанным идентификатором.
+    Server(String),
+    /// Вложение личного диалога с указанным идентификатором.
+    DirectMessage(String),
+}
+
+impl AttachmentSource {
+    async fn load(&self, attachment_id: &str) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Server(server_id) => api::load_chat_attachment(server_id, attachment_id).await,
+            Self::DirectMessage(conversation_id) => {
+                social_api::load_dm_attachment(conversation_id, attachment_id).await
+            }
+        }
+    }
+}
+
+/// Состояние загрузки байтов вложения.
+#[derive(Clone, PartialEq)]
+enum FileLoadState {
+    Idle,
+    Loading,
+    Failed(String),
+}
+
+/// Рендерит карточку вложения, которое не показывается как изображение.
+///
+/// Байты загружаются только по действию пользователя: для аудио и видео
+/// появляется встроенный плеер, остальные файлы сохраняются на устройство.
+#[component]
+pub(crate) fn ChatFileAttachment(
+    source: AttachmentSource,
+    attachment: TextChatAttachment,
+    is_own: bool,
+) -> Element {
+    let mut load_state = use_signal(|| FileLoadState::Idle);
+    let mut media_url = use_signal(|| None::<String>);
+    let name = attachment_display_name(&attachment);
+    let size = format_attachment_size(usize::try_from(attachment.byte_size).unwrap_or(0));
+    let is_media = matches!(
+        attachment.kind,
+        TextChatAttachmentKind::Audio | TextChatAttachmentKind::Video
+    );
+    let card_class = if is_own {
+        "flex w-full max-w-sm min-w-0 flex-col gap-2 rounded-[14px] border border-blue-500/20 bg-blue-950/20 p-2.5"
+    } else {
+        "flex w-full max-w-sm min-w-0 flex-col gap-2 rounded-[14px] border border-zinc-700/80 bg-zinc-950/70 p-2.5"
+    };
+    let busy = load_state() == FileLoadState::Loading;
+
+    let play_source = source.clone();
+    let play_attachment = attachment.clone();
+    let play = move |_| {
+        if busy || media_url().is_some() {
+            return;
+        }
+        let source = play_source.clone();
+        let attachment = play_attachment.clone();
+        load_state.set(FileLoadState::Loading);
+        spawn(async move {
+            match load_object_url(&source, &attachment).await {
+                Ok(url) => {
+                    media_url.set(Some(url));
+                    load_state.set(FileLoadState::Idle);
+                }
+                Err(error) => {
+                    warn!(attachment_id = %attachment.id, %error, "failed to load chat media attachment");
+                    load_state.set(FileLoadState::Failed(error));
+                }
+            }
+        });
+    };
+    let download_name = name.clone();
+    let download_attachment = attachment.clone();
+    let download = move |_| {
+        if busy {
+            return;
+        }
+        let source = source.clone();
+        let attachment = download_attachment.clone();
+        let file_name = download_name.clone();
+        load_state.set(FileLoadState::Loading);
+        spawn(async move {
+            match save_attachment(&source, &attachment, &file_name).await {
+                Ok(()) => load_state.set(FileLoadState::Idle),
+                Err(error) => {
+                    warn!(attachment_id = %attachment.id, %error, "failed to download chat attachment");
+                    load_state.set(FileLoadState::Failed(error));
+                }
+            }
+        });
+    };
+
+    rsx! {
+        div { class: card_class,
+            div { class: "flex min-w-0 items-center gap-3",
+                div { class: "flex size-10 shrink-0 items-center justify-center rounded-xl border border-white/5 bg-white/5 text-zinc-300",
+                    svg { class: "size-5", fill: "none", stroke: "currentColor", stroke_width: "1.8", view_box: "0 0 24 24", "aria-hidden": "true",
+                        path { stroke_linecap: "round", stroke_linejoin: "round", d: kind_icon_path(attachment.kind) }
+                    }
+                }
+                div { class: "min-w-0 flex-1",
+                    p { class: "truncate text-[12px] font-medium text-zinc-100", title: "{name}", "{name}" }
+                    p { class: "mt-0.5 truncate text-[11px] text-zinc-400", "{kind_label(attachment.kind)} · {size}" }
+                }
+                if is_media && media_url().is_none() {
+                    button {
+                        r#type: "button",
+                        disabled: busy,
+                        class: "flex size-9 shrink-0 items-center justify-center rounded-xl text-zinc-300 transition-colors hover:bg-white/10 hover:text-white disabled:cursor-wait disabled:opacity-50",
+                        "aria-label": "Воспроизвести",
+                        title: "Воспроизвести",
+                        onclick: play,
+                        svg { class: "size-4", fill: "currentColor", view_box: "0 0 24 24", "aria-hidden": "true",
+                            path { d: "M8 5.5v13a1 1 0 0 0 1.5.87l11-6.5a1 1 0 0 0 0-1.74l-11-6.5A1 1 0 0 0 8 5.5Z" }
+                        }
+                    }
+                }
+                button {
+                    r#type: "button",
+                    disabled: busy,
+                    class: "flex size-9 shrink-0 items-center justify-center rounded-xl text-zinc-300 transition-colors hover:bg-white/10 hover:text-white disabled:cursor-wait disabled:opacity-50",
+                    "aria-label": "Скачать {name}",
+                    title: "Скачать",
+                    onclick: download,
+                    if busy {
+                        span { class: "size-4 animate-spin rounded-full border-2 border-zinc-600 border-t-blue-300", "aria-hidden": "true" }
+                    } else {
+                        svg { class: "size-4", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24", "aria-hidden": "true",
+                            path { stroke_linecap: "round", stroke_linejoin: "round", d: "M12 4v11m0 0-4-4m4 4 4-4M5 20h14" }
+                        }
+                    }
+                }
+            }
+            if let Some(url) = media_url() {
+                if attachment.kind == TextChatAttachmentKind::Video {
+                    video { class: "max-h-80 w-full rounded-[10px] bg-black", src: "{url}", controls: true, autoplay: true }
+                } else {
+                    audio { class: "w-full", src: "{url}", controls: true, autoplay: true }
+                }
+            }
+            if let FileLoadState::Failed(error) = load_state() {
+                p { class: "text-[11px] text-red-200", role: "status", "{error}" }
+            }
+        }
+    }
+}
+
+fn attachment_display_name(attachment: &TextChatAttachment) -> String {
+    attachment
+        .original_filename
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| kind_label(attachment.kind).to_owned())
+}
+
+fn kind_label(kind: TextChatAttachmentKind) -> &'static str {
+    match kind {
+        TextChatAttachmentKind::Image => "Изображение",
+        TextChatAttachmentKind::Video => "Видео",
+        TextChatAttachmentKind::Audio => "Аудио",
+        TextChatAttachmentKind::File => "Файл",
+    }
+}
+
+fn kind_icon_path(kind: TextChatAttachmentKind) -> &'static str {
+    match kind {
+        TextChatAttachmentKind::Video => {
+            "M15 10.5 20 7v10l-5-3.5M5 18h8a2 2 0 0 0 2-2V8a2 2 0 0 0-2-2H5a2 2 0 0 0-2 2v8a2 2 0 0 0 2 2Z"
+        }
+        TextChatAttachmentKind::Audio => {
+            "M9 18V5l11-2v13M9 18a3 3 0 1 1-6 0 3 3 0 0 1 6 0Zm11-2a3 3 0 1 1-6 0 3 3 0 0 1 6 0Z"
+        }
+        TextChatAttachmentKind::Image | TextChatAttachmentKind::File => {
+            "M14 3H7a2 2 0 0 0-2 2v14a2 2 0 0 0 2 2h10a2 2 0 0 0 2-2V8l-5-5Zm0 0v5h5"
+        }
+    }
+}
+
+/// Загружает вложение и возвращает blob URL для встроенного плеера.
+async fn load_object_url(
+    source: &AttachmentSource,
+    attachment: &TextChatAttachment,
+) -> Result<String, String> {
+    let bytes = source.load(&attachment.id).await?;
+    let eval = document::eval(
+        r#"
+        const [contentType, data] = await dioxus.recv();
+        const bytes = Uint8Array.from(atob(data), (char) => char.charCodeAt(0));
+        return URL.createObjectURL(new Blob([bytes], { type: contentType }));
+        "#,
+    );
+    eval.send((attachment.content_type.clone(), BASE64.encode(bytes)))
+        .map_err(|_| "Не удалось подготовить файл.".to_owned())?;
+    eval.join::<String>()
+        .await
+        .map_err(|_| "Не удалось открыть файл.".to_owned())
+}
+
+/// Загружает вложение и сохраняет его через ссылку скачивания webview или браузера.
+async fn save_attachment(
+    source: &AttachmentSource,
+    attachment: &TextChatAttachment,
+    file_name: &str,
+) -> Result<(), String> {
+    let bytes = source.load(&attachment.id).await?;
+    let eval = document::eval(
+        r#"
+        const [fileName, contentType, data] = await dioxus.recv();
+        const bytes = Uint8Array.from(atob(data), (char) => char.charCodeAt(0));
+        const url = URL.createObjectURL(new Blob([bytes], { type: contentType }));
+        const link = document.createElement("a");
+        link.href = url;
+        link.download = fileName;
+        link.rel = "noopener";
+        document.body.appendChild(link);
+        link.click();
+        link.remove();
+        setTimeout(() => URL.revokeObjectURL(url), 60000);
+        return true;
+        "#,
+    );
+    eval.send((
+        file_name.to_owned(),
+        attachment.content_type.clone(),
+        BASE64.encode(bytes),
+    ))
+    .map_err(|_| "Не удалось подготовить файл.".to_owned())?;
+    eval.join::<bool>()
+        .await
+        .map(|_| ())
+        .map_err(|_| "Не удалось сохранить файл.".to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use cheenhub_contracts::realtime::{TextChatAttachment, TextChatAttachmentKind};
+
+    use super::attachment_display_name;
+
+    #[test]
+    fn display_name_falls_back_to_kind_label() {
+        let mut attachment = TextChatAttachment {
+            id: "attachment".to_owned(),
+            kind: TextChatAttachmentKind::Audio,
+            content_type: "audio/mpeg".to_owned(),
+            byte_size: 10,
+            width: None,
+            height: None,
+            original_filename: None,
+        };
+        assert_eq!(attachment_display_name(&attachment), "Аудио");
+
+        attachment.original_filename = Some("track.mp3".to_owned());
+        assert_eq!(attachment_display_name(&attachment), "track.mp3");
+    }
+}
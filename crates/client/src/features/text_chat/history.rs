@@ -12,8 +12,8 @@ use futures_util::future::{Either, select};
 use crate::features::realtime::{RealtimeConnectionStatus, RealtimeHandle};
 use crate::features::runtime::sleep_duration;
 
-use super::messages::prepend_messages;
-use super::realtime;
+use super::messages::{extend_messages, prepend_messages, replace_with_latest};
+use super::realtime::{self, HistoryCursor};
 use super::scroll::{ScrollCommand, capture_scroll_position};
 
 const INITIAL_HISTORY_TIMEOUT: Duration = Duration::from_secs(12);
@@ -29,11 +29,15 @@ pub(super) struct HistoryTarget {
 pub(super) struct HistoryState {
     pub(super) messages: Signal<Vec<TextChatMessage>>,
     pub(super) appearing_message_ids: Signal<Vec<String>>,
-    pub(super) has_more: Signal<bool>,
+    pub(super) has_more_before: Signal<bool>,
+    pub(super) has_more_after: Signal<bool>,
+    pub(super) last_read_message_id: Signal<Option<String>>,
     pub(super) initial_loading: Signal<bool>,
     pub(super) history_error: Signal<Option<String>>,
     pub(super) older_loading: Signal<bool>,
     pub(super) older_error: Signal<Option<String>>,
+    pub(super) newer_loading: Signal<bool>,
+    pub(super) newer_error: Signal<Option<String>>,
     pub(super) list_element: Signal<Option<Rc<MountedData>>>,
     pub(super) pending_scroll: Signal<Option<ScrollCommand>>,
 }
@@ -44,9 +48,13 @@ pub(super) fn load_initial_history(target: HistoryTarget, mut state: HistoryStat
     spawn(async move {
         let server_id = target.server_id;
         let room_id = target.room_id;
-        let history_request =
-            realtime::load_room_history(&target.realtime, server_id.clone(), room_id.clone(), None)
-                .boxed_local();
+        let history_request = realtime::load_room_history(
+            &target.realtime,
+            server_id.clone(),
+            room_id.clone(),
+            HistoryCursor::Latest,
+        )
+        .boxed_local();
         let timeout = sleep_duration(INITIAL_HISTORY_TIMEOUT).boxed_local();
 
         match select(history_request, timeout).await {
@@ -55,12 +63,17 @@ pub(super) fn load_initial_history(target: HistoryTarget, mut state: HistoryStat
                     server_id = %server_id,
                     room_id = %room_id,
                     messages = history.messages.len(),
-                    has_more = history.has_more,
+                    has_more_before = history.has_more_before,
                     "loaded initial text chat history"
                 );
-                state.messages.set(history.messages);
-                state.appearing_message_ids.set(Vec::new());
-                state.has_more.set(history.has_more);
+                replace_with_latest(
+                    &mut state.messages,
+                    &mut state.appearing_message_ids,
+                    history.messages,
+                );
+                state.has_more_before.set(history.has_more_before);
+                state.has_more_after.set(false);
+                state.last_read_message_id.set(history.last_read_message_id);
                 state.pending_scroll.set(Some(ScrollCommand::Bottom));
             }
             Either::Left((Err(error), _)) => {
@@ -136,7 +149,7 @@ pub(super) fn load_initial_history_when_connected(target: HistoryTarget, mut sta
 }
 
 pub(super) fn load_older_history(target: HistoryTarget, mut state: HistoryState) {
-    if (state.older_loading)() || !(state.has_more)() {
+    if (state.older_loading)() || !(state.has_more_before)() {
         return;
     }
     let Some(before_message_id) = (state.messages)().first().map(|message| message.id.clone())
@@ -156,13 +169,13 @@ pub(super) fn load_older_history(target: HistoryTarget, mut state: HistoryState)
             &target.realtime,
             target.server_id,
             target.room_id,
-            Some(before_message_id),
+            HistoryCursor::Before(before_message_id),
         )
         .await
         {
             Ok(history) => {
                 prepend_messages(&mut state.messages, history.messages);
-                state.has_more.set(history.has_more);
+                state.has_more_before.set(history.has_more_before);
                 if let Some((offset_y, height)) = before_scroll {
                     state
                         .pending_scroll
@@ -174,3 +187,81 @@ pub(super) fn load_older_history(target: HistoryTarget, mut state: HistoryState)
         state.older_loading.set(false);
     });
 }
+
+pub(super) fn load_newer_history(target: HistoryTarget, mut state: HistoryState) {
+    if (state.newer_loading)() || !(state.has_more_after)() {
+        return;
+    }
+    let Some(after_message_id) = (state.messages)().last().map(|message| message.id.clone()) else {
+        return;
+    };
+
+    state.newer_loading.set(true);
+    state.newer_error.set(None);
+    spawn(async move {
+        match realtime::load_room_history(
+            &target.realtime,
+            target.server_id,
+            target.room_id,
+            HistoryCursor::After(after_message_id),
+        )
+        .await
+        {
+            Ok(history) => {
+                extend_messages(&mut state.messages, history.messages);
+                state.has_more_after.set(history.has_more_after);
+            }
+            Err(error) => state.newer_error.set(Some(error.to_string())),
+        }
+        state.newer_loading.set(false);
+    });
+}
+
+/// Заменяет окно истории страницей вокруг сообщения, которого нет в текущем окне.
+pub(super) fn load_history_around(
+    target: HistoryTarget,
+    mut state: HistoryState,
+    message_id: String,
+) {
+    if (state.initial_loading)() {
+        return;
+    }
+
+    state.initial_loading.set(true);
+    spawn(async move {
+        match realtime::load_room_history(
+            &target.realtime,
+            target.server_id.clone(),
+            target.room_id.clone(),
+            HistoryCursor::Around(message_id.clone()),
+        )
+        .await
+        {
+            Ok(history) => {
+                debug!(
+                    %message_id,
+                    messages = history.messages.len(),
+                    has_more_before = history.has_more_before,
+                    has_more_after = history.has_more_after,
+                    "loaded text chat history around message"
+                );
+                state.messages.set(history.messages);
+                state.appearing_message_ids.set(Vec::new());
+                state.has_more_before.set(history.has_more_before);
+                state.has_more_after.set(history.has_more_after);
+                state.older_error.set(None);
+                state.newer_error.set(None);
+            }
+            Err(error) => {
+                warn!(
+                    %error,
+                    server_id = %target.server_id,
+                    room_id = %target.room_id,
+                    %message_id,
+                    "failed to load text chat history around message"
+                );
+            }
+        }
+        state.initial_loading.set(false);
+    });
+}
@@ -0,0 +1,54 @@
+//! Состояния загрузки истории в списке сообщений.
+
+use dioxus::prelude::*;
+
+/// Показывает индикатор загрузки или ошибку с повтором для соседней страницы истории.
+#[component]
+pub(super) fn ChatHistoryPageStatus(
+    loading: bool,
+    error: Option<String>,
+    on_retry: EventHandler<()>,
+) -> Element {
+    rsx! {
+        if loading {
+            div { class: "flex justify-center py-2",
+                div { class: "h-5 w-5 animate-spin rounded-full border-2 border-zinc-700 border-t-blue-400" }
+            }
+        } else if let Some(error) = error {
+            div { class: "rounded-xl border border-red-500/20 bg-red-500/10 px-3 py-2 text-center text-[12px] leading-5 text-red-200",
+                p { "{error}" }
+                button {
+                    r#type: "button",
+                    class: "mt-2 rounded-lg border border-red-300/20 px-3 py-1 text-[12px] font-medium text-red-100 transition-colors hover:border-red-200/40 hover:bg-red-400/10",
+                    onclick: move |_| on_retry.call(()),
+                    "Повторить"
+                }
+            }
+        }
+    }
+}
+
+/// Показывает заглушки сообщений, пока загружается первая страница истории.
+#[component]
+pub(super) fn ChatHistorySkeleton() -> Element {
+    rsx! {
+        div { class: "space-y-3",
+            div { class: "h-14 animate-pulse rounded-2xl bg-zinc-900/80" }
+            div { class: "h-14 animate-pulse rounded-2xl bg-zinc-900/60" }
+            div { class: "h-14 animate-pulse rounded-2xl bg-zinc-900/40" }
+        }
+    }
+}
+
+/// Показывает приглашение написать первое сообщение в пустой комнате.
+#[component]
+pub(super) fn ChatEmptyHistory() -> Element {
+    rsx! {
+        div { class: "rounded-[20px] border border-zinc-800 bg-zinc-900/60 p-6 text-center",
+            p { class: "text-[13px] font-medium text-zinc-100", "Сообщений пока нет" }
+            p { class: "mt-1 text-[12px] leading-5 text-zinc-500",
+                "Напиши первое сообщение в этой комнате."
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@
 
 use std::rc::Rc;
 
-use cheenhub_contracts::realtime::TextChatImageAttachment;
+use cheenhub_contracts::realtime::TextChatAttachment;
 use dioxus::logger::tracing::warn;
 use dioxus::prelude::*;
 use futures_util::FutureExt;
@@ -22,7 +22,7 @@ use super::realtime;
 pub(super) fn ChatImageAttachment(
     server_id: String,
     room_id: String,
-    attachment: TextChatImageAttachment,
+    attachment: TextChatAttachment,
     is_own: bool,
 ) -> Element {
     let realtime = use_context::<RealtimeHandle>();
@@ -31,7 +31,9 @@ pub(super) fn ChatImageAttachment(
     let mut thumbnail_element = use_signal(|| None::<Rc<MountedData>>);
     let mut reload_generation = use_signal(|| 0_u64);
     let content_type = attachment.content_type.clone();
-    let preview = preview_geometry(attachment.width, attachment.height);
+    let width = attachment.width.unwrap_or_default();
+    let height = attachment.height.unwrap_or_default();
+    let preview = preview_geometry(width, height);
     let shell_styles = image_shell_styles(preview);
     let wrapper_class = if is_own {
         "inline-block shrink-0 overflow-hidden rounded-[14px] border border-blue-500/20 bg-blue-950/20 shadow-[0_0_0_1px_rgba(255,255,255,0.035),0_12px_32px_rgba(0,0,0,0.28)]"
@@ -79,8 +81,8 @@ pub(super) fn ChatImageAttachment(
                         attachment_id: attachment.id.clone(),
                         content_type: loaded.content_type.clone(),
                         data_base64: loaded.data_base64.clone(),
-                        width: attachment.width,
-                        height: attachment.height,
+                        width,
+                        height,
                     };
                     rsx! {
                         button {
@@ -0,0 +1,24 @@
+//! Кнопка перехода к последнему сообщению текстового чата.
+
+use dioxus::prelude::*;
+
+/// Плавающая кнопка, которая возвращает список к последнему сообщению.
+#[component]
+pub(super) fn ChatJumpToLatestButton(on_click: EventHandler<()>) -> Element {
+    rsx! {
+        div { class: "pointer-events-none absolute bottom-3 right-4 z-20",
+            button {
+                r#type: "button",
+                class: "group pointer-events-auto relative flex h-10 w-10 items-center justify-center rounded-full border border-zinc-800 bg-zinc-950/85 text-blue-200 shadow-[0_8px_22px_rgba(0,0,0,0.35)] transition-[background,border-color,color,transform,opacity] duration-150 hover:-translate-y-px hover:border-white/15 hover:bg-zinc-900/90 hover:text-blue-100",
+                "aria-label": "Перейти к последнему сообщению",
+                onclick: move |_| on_click.call(()),
+                span { class: "pointer-events-none absolute bottom-[calc(100%+8px)] right-0 whitespace-nowrap rounded-lg border border-zinc-800 bg-zinc-950/95 px-2 py-1 text-[11px] font-medium text-zinc-300 opacity-0 shadow-[0_8px_22px_rgba(0,0,0,0.35)] transition-[opacity,transform] duration-150 group-hover:opacity-100",
+                    "К последнему сообщению"
+                }
+                svg { class: "h-5 w-5", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24",
+                    path { stroke_linecap: "round", stroke_linejoin: "round", d: "M12 5v14m0 0 6-6m-6 6-6-6" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Карточки ссылок под текстом сообщения.
+
+use cheenhub_contracts::rest::LinkPreviewSummary;
+use dioxus::prelude::*;
+
+/// Рендерит карточки ссылок сообщения: сайт, заголовок, описание и изображение.
+#[component]
+pub(crate) fn ChatLinkPreviews(previews: Vec<LinkPreviewSummary>, is_own: bool) -> Element {
+    let card_class = if is_own {
+        "flex w-[min(100%,26rem)] min-w-0 flex-col overflow-hidden rounded-2xl border-l-2 border-blue-300/60 bg-blue-500/5 text-left transition-colors hover:bg-blue-500/10"
+    } else {
+        "flex w-[min(100%,26rem)] min-w-0 flex-col overflow-hidden rounded-2xl border-l-2 border-zinc-500/70 bg-white/[0.03] text-left transition-colors hover:bg-white/[0.06]"
+    };
+
+    rsx! {
+        for preview in previews {
+            a {
+                key: "{preview.url}",
+                href: "{preview.url}",
+                target: "_blank",
+                rel: "noreferrer noopener",
+                class: card_class,
+                div { class: "flex min-w-0 flex-col gap-0.5 px-3 py-2",
+                    if let Some(site_name) = preview.site_name.as_ref() {
+                        span { class: "truncate text-[10px] font-medium uppercase tracking-wide text-zinc-500",
+                            "{site_name}"
+                        }
+                    }
+                    if let Some(title) = preview.title.as_ref() {
+                        span { class: "line-clamp-2 text-[13px] font-semibold leading-5 text-blue-200 [overflow-wrap:anywhere]",
+                            "{title}"
+                        }
+                    }
+                    if let Some(description) = preview.description.as_ref() {
+                        span { class: "line-clamp-3 text-[12px] leading-4 text-zinc-400 [overflow-wrap:anywhere]",
+                            "{description}"
+                        }
+                    }
+                }
+                if let Some(image_url) = preview.image_url.as_ref() {
+                    img {
+                        class: "block max-h-52 w-full object-cover",
+                        src: "{image_url}",
+                        alt: "",
+                        loading: "lazy",
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Удаление и редактирование сообщений открытой комнаты.
+
+use std::time::Duration;
+
+use cheenhub_contracts::realtime::TextChatMessage;
+use dioxus::prelude::*;
+
+use crate::features::realtime::RealtimeHandle;
+use crate::features::runtime::sleep_duration;
+
+use super::messages::{apply_message_edit, remove_message};
+use super::realtime;
+
+/// Обработчики удаления и редактирования сообщений комнаты.
+#[derive(Clone, Copy)]
+pub(super) struct RoomMessageActions {
+    /// Удаляет сообщение с анимацией исчезновения.
+    pub(super) delete: Callback<String>,
+    /// Сохраняет новый текст сообщения.
+    pub(super) edit: Callback<(String, String)>,
+}
+
+/// Создает обработчики удаления и редактирования сообщений комнаты.
+pub(super) fn use_room_message_actions(
+    realtime: RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    mut messages: Signal<Vec<TextChatMessage>>,
+    mut removing_message_ids: Signal<Vec<String>>,
+    mut status: Signal<String>,
+) -> RoomMessageActions {
+    let delete_realtime = realtime.clone();
+    let delete_server_id = server_id.clone();
+    let delete_room_id = room_id.clone();
+    let delete = use_callback(move |message_id: String| {
+        let realtime = delete_realtime.clone();
+        let server_id = delete_server_id.clone();
+        let room_id = delete_room_id.clone();
+        removing_message_ids.write().push(message_id.clone());
+        spawn(async move {
+            let _ =
+                realtime::delete_text_message(&realtime, server_id, room_id, message_id.clone())
+                    .await;
+            sleep_duration(Duration::from_millis(220)).await;
+            remove_message(&mut messages, &message_id);
+            removing_message_ids.write().retain(|id| id != &message_id);
+        });
+    });
+    let edit = use_callback(move |(message_id, body): (String, String)| {
+        let realtime = realtime.clone();
+        let server_id = server_id.clone();
+        let room_id = room_id.clone();
+        spawn(async move {
+            match realtime::edit_text_message(&realtime, server_id, room_id, message_id, body).await
+            {
+                Ok(accepted) => {
+                    let message = accepted.message;
+                    if let Some(edited_at) = message.edited_at {
+                        apply_message_edit(
+                            &mut messages,
+                            &message.id,
+                            message.body,
+                            edited_at,
+                            message.mentions,
+                        );
+                    }
+                }
+                Err(error) => status.set(error.to_string()),
+            }
+        });
+    });
+
+    RoomMessageActions { delete, edit }
+}
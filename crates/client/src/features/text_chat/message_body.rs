@@ -0,0 +1,125 @@
+//! Тело сообщения текстового чата с подсветкой упоминаний.
+
+use cheenhub_contracts::realtime::TextChatMention;
+use dioxus::prelude::*;
+
+/// Фрагмент тела сообщения.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BodySegment {
+    text: String,
+    is_mention: bool,
+}
+
+/// Рендерит тело сообщения, выделяя `@`-токены разрешенных упоминаний.
+#[component]
+pub(super) fn ChatMessageBody(body: String, mentions: Vec<TextChatMention>) -> Element {
+    let names = mentions
+        .iter()
+        .map(|mention| mention.name.to_lowercase())
+        .collect::<Vec<_>>();
+    let segments = mention_segments(&body, &names);
+
+    rsx! {
+        span { class: "[overflow-wrap:anywhere]",
+            for (index , segment) in segments.into_iter().enumerate() {
+                if segment.is_mention {
+                    span {
+                        key: "{index}",
+                        class: "rounded bg-sky-500/20 px-0.5 font-medium text-sky-200",
+                        "{segment.text}"
+                    }
+                } else {
+                    span { key: "{index}", "{segment.text}" }
+                }
+            }
+        }
+    }
+}
+
+/// Делит тело на обычный текст и упоминания.
+///
+/// Разбор совпадает с серверным: токен начинается с `@` в начале текста или
+/// после пробела и продолжается буквами, цифрами или `_`. Выделяются только
+/// токены, имя которых есть среди разрешенных сервером упоминаний.
+fn mention_segments(body: &str, names: &[String]) -> Vec<BodySegment> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut previous = None;
+    let mut chars = body.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        let at_token_start = ch == '@' && previous.is_none_or(char::is_whitespace);
+        previous = Some(ch);
+        if !at_token_start || names.is_empty() {
+            continue;
+        }
+
+        let mut end = index + ch.len_utf8();
+        while let Some(&(next_index, next)) = chars.peek() {
+            if !(next.is_alphanumeric() || next == '_') {
+                break;
+            }
+            end = next_index + next.len_utf8();
+            previous = Some(next);
+            chars.next();
+        }
+        let name = body[index + ch.len_utf8()..end].to_lowercase();
+        if !names.contains(&name) {
+            continue;
+        }
+        if text_start < index {
+            segments.push(BodySegment {
+                text: body[text_start..index].to_owned(),
+                is_mention: false,
+            });
+        }
+        segments.push(BodySegment {
+            text: body[index..end].to_owned(),
+            is_mention: true,
+        });
+        text_start = end;
+    }
+    if text_start < body.len() {
+        segments.push(BodySegment {
+            text: body[text_start..].to_owned(),
+            is_mention: false,
+        });
+    }
+
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BodySegment, mention_segments};
+
+    fn segment(text: &str, is_mention: bool) -> BodySegment {
+        BodySegment {
+            text: text.to_owned(),
+            is_mention,
+        }
+    }
+
+    #[test]
+    fn highlights_only_resolved_mentions() {
+        let segments = mention_segments(
+            "@Alice и @bob, пишите на me@example.com",
+            &["alice".to_owned()],
+        );
+
+        assert_eq!(
+            segments,
+            vec![
+                segment("@Alice", true),
+                segment(" и @bob, пишите на me@example.com", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_plain_body_as_single_segment() {
+        assert_eq!(
+            mention_segments("просто текст", &[]),
+            vec![segment("просто текст", false)]
+        );
+    }
+}
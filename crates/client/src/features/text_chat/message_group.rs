@@ -1,6 +1,6 @@
 //! Группа последовательных сообщений одного автора в текстовом чате.
 
-use cheenhub_contracts::realtime::TextChatMessage;
+use cheenhub_contracts::realtime::{TextChatAttachmentKind, TextChatMessage};
 use dioxus::prelude::*;
 
 use crate::features::app::components::avatar::{UserAvatar, use_avatar_seed};
@@ -17,6 +17,9 @@ pub(crate) fn ChatMessageGroup(
     removing_message_ids: Vec<String>,
     can_delete_messages: bool,
     on_delete: EventHandler<String>,
+    on_edit: EventHandler<(String, String)>,
+    on_reply: EventHandler<TextChatMessage>,
+    on_react: EventHandler<(String, String, bool)>,
     server_id: String,
     room_id: String,
 ) -> Element {
@@ -69,13 +72,27 @@ pub(crate) fn ChatMessageGroup(
                             removing: removing_message_ids.contains(&message.id),
                             can_delete_messages,
                             on_delete: move |id| on_delete.call(id),
+                            editable: true,
+                            on_edit: move |edit| on_edit.call(edit),
+                            detached: false,
+                            on_reply: move |message| on_reply.call(message),
+                            on_react: move |reaction| on_react.call(reaction),
                             for attachment in message.attachments.iter().cloned() {
-                                super::image_attachment::ChatImageAttachment {
-                                    key: "{super::image_attachment::ChatImageLoadKey::render_key(&server_id, &room_id, &attachment.id)}",
-                                    server_id: server_id.clone(),
-                                    room_id: room_id.clone(),
-                                    attachment,
-                                    is_own: message.author_user_id == current_user.id,
+                                if attachment.kind == TextChatAttachmentKind::Image {
+                                    super::image_attachment::ChatImageAttachment {
+                                        key: "{super::image_attachment::ChatImageLoadKey::render_key(&server_id, &room_id, &attachment.id)}",
+                                        server_id: server_id.clone(),
+                                        room_id: room_id.clone(),
+                                        attachment,
+                                        is_own: message.author_user_id == current_user.id,
+                                    }
+                                } else {
+                                    super::file_attachment::ChatFileAttachment {
+                                        key: "{attachment.id}",
+                                        source: super::file_attachment::AttachmentSource::Server(server_id.clone()),
+                                        attachment,
+                                        is_own: message.author_user_id == current_user.id,
+                                    }
                                 }
                             }
                         }
@@ -5,9 +5,19 @@ use dioxus::prelude::*;
 
 use crate::features::app::current_user::CurrentUserContext;
 
+use super::link_previews::ChatLinkPreviews;
+use super::message_body::ChatMessageBody;
 use super::message_date::full_message_datetime;
+use super::message_menu::{ChatMessageMenu, MessageMenuAction};
+use super::message_quote::ChatMessageQuote;
+use super::message_reactions::{ChatMessageReactions, ChatReactionPicker};
+use super::pins::RoomPins;
+use super::scroll::MessageAnchors;
 
 /// Рендерит одну строку сообщения текстового чата.
+///
+/// Отдельная (`detached`) строка показывается вне ленты, например в закрепленных,
+/// и не регистрируется как якорь перехода к сообщению.
 #[component]
 pub(crate) fn ChatMessageItem(
     message: TextChatMessage,
@@ -15,13 +25,45 @@ pub(crate) fn ChatMessageItem(
     removing: bool,
     can_delete_messages: bool,
     on_delete: EventHandler<String>,
+    editable: bool,
+    on_edit: EventHandler<(String, String)>,
+    detached: bool,
+    on_reply: Option<EventHandler<TextChatMessage>>,
+    on_react: Option<EventHandler<(String, String, bool)>>,
     children: Element,
 ) -> Element {
     let current_user = use_context::<CurrentUserContext>().require_user();
+    let anchors = try_use_context::<MessageAnchors>().filter(|_| !detached);
+    let pins = try_use_context::<RoomPins>().filter(|pins| pins.can_pin);
+    let is_pinned = pins.is_some_and(|pins| pins.is_pinned(&message.id));
     let is_own = message.author_user_id == current_user.id;
     let can_delete = is_own || can_delete_messages;
+    let can_edit = is_own && editable && !message.body.is_empty();
+    let menu_actions = [
+        (on_reply.is_some(), MessageMenuAction::Reply),
+        (on_react.is_some(), MessageMenuAction::React),
+        (can_edit, MessageMenuAction::Edit),
+        (pins.is_some() && !is_pinned, MessageMenuAction::Pin),
+        (is_pinned, MessageMenuAction::Unpin),
+        (can_delete, MessageMenuAction::Delete),
+    ]
+    .into_iter()
+    .filter_map(|(enabled, action)| enabled.then_some(action))
+    .collect::<Vec<_>>();
+    let has_menu_actions = !menu_actions.is_empty();
     let mut menu_pos = use_signal(|| None::<(f64, f64)>);
+    let mut picker_pos = use_signal(|| None::<(f64, f64)>);
+    let mut edit_draft = use_signal(|| None::<String>);
+    use_drop({
+        let message_id = message.id.clone();
+        move || {
+            if let Some(anchors) = anchors {
+                anchors.unregister(&message_id);
+            }
+        }
+    });
 
+    let highlighted = anchors.is_some_and(|anchors| anchors.is_highlighted(&message.id));
     let row_class = match (animate, removing, is_own) {
         (_, true, true) => "chat-message-removing flex w-full justify-end",
         (_, true, false) => "chat-message-removing flex w-full justify-start",
@@ -47,12 +89,31 @@ pub(crate) fn ChatMessageItem(
     };
     let sent_time = message_time(&message.created_at);
     let sent_datetime = full_message_datetime(&message.created_at);
+    let edited_datetime = message.edited_at.as_deref().map(full_message_datetime);
+    let edit_message_id = message.id.clone();
+    let edit_original_body = message.body.clone();
+    let anchor_message_id = message.id.clone();
+    let menu_message_id = message.id.clone();
+    let menu_body = message.body.clone();
+    let reply_message = message.clone();
+    let react_message_id = message.id.clone();
+    let toggle_message_id = message.id.clone();
+    let on_toggle_reaction = on_react.map(|on_react| {
+        EventHandler::new(move |(emoji, add): (String, bool)| {
+            on_react.call((toggle_message_id.clone(), emoji, add));
+        })
+    });
 
     rsx! {
         div {
             class: row_class,
+            onmounted: move |event| {
+                if let Some(anchors) = anchors {
+                    anchors.register(anchor_message_id.clone(), event.data.clone());
+                }
+            },
             oncontextmenu: move |event| {
-                if !can_delete {
+                if !has_menu_actions {
                     return;
                 }
                 event.prevent_default();
@@ -60,15 +121,67 @@ pub(crate) fn ChatMessageItem(
                 let p = event.client_coordinates();
                 menu_pos.set(Some((p.x, p.y)));
             },
-            div { class: content_stack_class,
-                if !message.body.is_empty() {
+            div {
+                class: if highlighted { format!("{content_stack_class} rounded-[20px] ring-2 ring-blue-400/50 ring-offset-4 ring-offset-zinc-950 transition-shadow") } else { content_stack_class.to_owned() },
+                if let Some(reply) = message.reply_to.clone() {
+                    ChatMessageQuote { reply, is_own }
+                }
+                if let Some(draft) = edit_draft() {
+                    div { class: "flex w-[min(100%,32rem)] flex-col gap-1",
+                        textarea {
+                            rows: "2",
+                            value: "{draft}",
+                            class: "max-h-40 min-h-16 w-full resize-y rounded-[16px] border border-blue-400/40 bg-zinc-950/70 px-3 py-2 text-[13px] leading-5 text-zinc-100 outline-none",
+                            onmounted: move |event| {
+                                let element = event.data.clone();
+                                spawn(async move {
+                                    let _ = element.set_focus(true).await;
+                                });
+                            },
+                            oninput: move |event| edit_draft.set(Some(event.value())),
+                            onkeydown: move |event| {
+                                if event.key() == Key::Escape {
+                                    event.prevent_default();
+                                    edit_draft.set(None);
+                                } else if event.key() == Key::Enter && !event.modifiers().shift() {
+                                    event.prevent_default();
+                                    let body = edit_draft().unwrap_or_default().trim().to_owned();
+                                    if body.is_empty() {
+                                        return;
+                                    }
+                                    edit_draft.set(None);
+                                    if body != edit_original_body {
+                                        on_edit.call((edit_message_id.clone(), body));
+                                    }
+                                }
+                            },
+                        }
+                        span { class: "px-1 text-[10px] leading-4 text-zinc-500",
+                            "Enter — сохранить, Esc — отменить"
+                        }
+                    }
+                } else if !message.body.is_empty() {
                     div { class: bubble_class,
-                        span { class: "[overflow-wrap:anywhere]", "{message.body}" }
+                        ChatMessageBody {
+                            body: message.body.clone(),
+                            mentions: message.mentions.clone(),
+                        }
                     }
                 }
+                if edit_draft().is_none() && !message.link_previews.is_empty() {
+                    ChatLinkPreviews { previews: message.link_previews.clone(), is_own }
+                }
                 {children}
+                ChatMessageReactions {
+                    reactions: message.reactions.clone(),
+                    is_own,
+                    on_toggle: on_toggle_reaction,
+                }
                 div { class: "group/message-time relative flex items-center gap-1.5",
                     span { class: time_class, "{sent_time}" }
+                    if edited_datetime.is_some() {
+                        span { class: time_class, "изменено" }
+                    }
                     if is_own {
                         if let Some(status) = message.delivery_status {
                             {delivery_status_marks(status)}
@@ -78,46 +191,52 @@ pub(crate) fn ChatMessageItem(
                         role: "tooltip",
                         class: "pointer-events-none absolute bottom-[calc(100%+8px)] right-0 z-30 w-max max-w-[min(18rem,calc(100vw-2rem))] rounded-lg border border-zinc-800 bg-zinc-950/95 px-2.5 py-1.5 text-[11px] font-medium leading-4 text-zinc-200 opacity-0 shadow-[0_8px_22px_rgba(0,0,0,0.35)] backdrop-blur-xl transition-[opacity,transform] duration-150 group-hover/message-time:opacity-100 group-focus-within/message-time:opacity-100",
                         "Отправлено {sent_datetime}"
+                        if let Some(edited_datetime) = edited_datetime.clone() {
+                            br {}
+                            "Изменено {edited_datetime}"
+                        }
                     }
                 }
             }
         }
 
         if let Some((x, y)) = menu_pos() {
-            {
-                let message_id = message.id.clone();
-                rsx! {
-                    div {
-                        class: "fixed inset-0 z-[999]",
-                        onclick: move |_| menu_pos.set(None),
+            ChatMessageMenu {
+                x,
+                y,
+                actions: menu_actions.clone(),
+                on_action: move |action| match action {
+                    MessageMenuAction::Reply => {
+                        if let Some(on_reply) = on_reply {
+                            on_reply.call(reply_message.clone());
+                        }
                     }
-                    div {
-                        class: "fixed z-[1000] min-w-[180px] overflow-hidden rounded-[16px] border border-zinc-800 bg-zinc-950/95 p-1.5 shadow-[0_20px_60px_rgba(0,0,0,.55)] backdrop-blur-xl",
-                        style: "left: clamp(12px, {x}px, calc(100vw - 200px)); top: clamp(12px, {y}px, calc(100vh - 80px));",
-                        onclick: move |event| event.stop_propagation(),
-                        button {
-                            r#type: "button",
-                            class: "flex w-full items-center gap-2.5 rounded-xl px-3 py-2.5 text-left text-[13px] text-red-300 transition-[background,color] duration-150 hover:bg-red-500/10 hover:text-red-200",
-                            onclick: move |_| {
-                                menu_pos.set(None);
-                                on_delete.call(message_id.clone());
-                            },
-                            svg {
-                                class: "h-4 w-4 shrink-0",
-                                fill: "none",
-                                stroke: "currentColor",
-                                stroke_width: "1.9",
-                                view_box: "0 0 24 24",
-                                path {
-                                    stroke_linecap: "round",
-                                    stroke_linejoin: "round",
-                                    d: "m14.74 9-.346 9m-4.788 0L9.26 9m9.968-3.21c.342.052.682.107 1.022.166m-1.022-.165L18.16 19.673a2.25 2.25 0 0 1-2.244 2.077H8.084a2.25 2.25 0 0 1-2.244-2.077L4.772 5.79m14.456 0a48.108 48.108 0 0 0-3.478-.397m-12 .562c.34-.059.68-.114 1.022-.165m0 0a48.11 48.11 0 0 1 3.478-.397m7.5 0v-.916c0-1.18-.91-2.164-2.09-2.201a51.964 51.964 0 0 0-3.32 0c-1.18.037-2.09 1.022-2.09 2.201v.916m7.5 0a48.667 48.667 0 0 0-7.5 0",
-                                }
-                            }
-                            "Удалить сообщение"
+                    MessageMenuAction::React => picker_pos.set(Some((x, y))),
+                    MessageMenuAction::Edit => edit_draft.set(Some(menu_body.clone())),
+                    MessageMenuAction::Pin | MessageMenuAction::Unpin => {
+                        if let Some(pins) = pins {
+                            pins.set_pinned(
+                                menu_message_id.clone(),
+                                action == MessageMenuAction::Pin,
+                            );
                         }
                     }
-                }
+                    MessageMenuAction::Delete => on_delete.call(menu_message_id.clone()),
+                },
+                on_close: move |_| menu_pos.set(None),
+            }
+        }
+
+        if let Some((x, y)) = picker_pos() {
+            ChatReactionPicker {
+                x,
+                y,
+                on_pick: move |emoji| {
+                    if let Some(on_react) = on_react {
+                        on_react.call((react_message_id.clone(), emoji, true));
+                    }
+                },
+                on_close: move |_| picker_pos.set(None),
             }
         }
     }
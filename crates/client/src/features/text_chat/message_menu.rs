@@ -0,0 +1,102 @@
+//! Контекстное меню сообщения текстового чата.
+
+use dioxus::prelude::*;
+
+const MENU_ITEM_HEIGHT: u32 = 44;
+const MENU_PADDING: u32 = 36;
+const MENU_ITEM_CLASS: &str = "flex w-full items-center gap-2.5 rounded-xl px-3 py-2.5 text-left text-[13px] text-zinc-200 transition-[background,color] duration-150 hover:bg-white/5 hover:text-white";
+const MENU_DANGER_ITEM_CLASS: &str = "flex w-full items-center gap-2.5 rounded-xl px-3 py-2.5 text-left text-[13px] text-red-300 transition-[background,color] duration-150 hover:bg-red-500/10 hover:text-red-200";
+
+/// Действие, выбранное в контекстном меню сообщения.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum MessageMenuAction {
+    /// Ответить на сообщение с цитатой.
+    Reply,
+    /// Поставить реакцию эмодзи.
+    React,
+    /// Изменить текст собственного сообщения.
+    Edit,
+    /// Закрепить сообщение в комнате.
+    Pin,
+    /// Открепить сообщение.
+    Unpin,
+    /// Удалить сообщение.
+    Delete,
+}
+
+/// Рендерит контекстное меню сообщения в точке клика.
+#[component]
+pub(super) fn ChatMessageMenu(
+    x: f64,
+    y: f64,
+    actions: Vec<MessageMenuAction>,
+    on_action: EventHandler<MessageMenuAction>,
+    on_close: EventHandler<()>,
+) -> Element {
+    let menu_height = MENU_PADDING + MENU_ITEM_HEIGHT * actions.len() as u32;
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[999]",
+            onclick: move |_| on_close.call(()),
+        }
+        div {
+            class: "fixed z-[1000] min-w-[180px] overflow-hidden rounded-[16px] border border-zinc-800 bg-zinc-950/95 p-1.5 shadow-[0_20px_60px_rgba(0,0,0,.55)] backdrop-blur-xl",
+            style: "left: clamp(12px, {x}px, calc(100vw - 200px)); top: clamp(12px, {y}px, calc(100vh - {menu_height}px));",
+            onclick: move |event| event.stop_propagation(),
+            for action in actions.iter().copied() {
+                button {
+                    key: "{action:?}",
+                    r#type: "button",
+                    class: if action == MessageMenuAction::Delete { MENU_DANGER_ITEM_CLASS } else { MENU_ITEM_CLASS },
+                    onclick: move |_| {
+                        on_close.call(());
+                        on_action.call(action);
+                    },
+                    svg {
+                        class: "h-4 w-4 shrink-0",
+                        fill: "none",
+                        stroke: "currentColor",
+                        stroke_width: "1.9",
+                        view_box: "0 0 24 24",
+                        path {
+                            stroke_linecap: "round",
+                            stroke_linejoin: "round",
+                            d: action_icon_path(action),
+                        }
+                    }
+                    {action_label(action)}
+                }
+            }
+        }
+    }
+}
+
+fn action_label(action: MessageMenuAction) -> &'static str {
+    match action {
+        MessageMenuAction::Reply => "Ответить",
+        MessageMenuAction::React => "Реакция",
+        MessageMenuAction::Edit => "Изменить сообщение",
+        MessageMenuAction::Pin => "Закрепить сообщение",
+        MessageMenuAction::Unpin => "Открепить сообщение",
+        MessageMenuAction::Delete => "Удалить сообщение",
+    }
+}
+
+fn action_icon_path(action: MessageMenuAction) -> &'static str {
+    match action {
+        MessageMenuAction::Reply => "M9 15 3 9m0 0 6-6M3 9h12a6 6 0 0 1 0 12h-3",
+        MessageMenuAction::React => {
+            "M15.182 15.182a4.5 4.5 0 0 1-6.364 0M21 12a9 9 0 1 1-18 0 9 9 0 0 1 18 0ZM9.75 9.75c0 .414-.168.75-.375.75S9 10.164 9 9.75 9.168 9 9.375 9s.375.336.375.75Zm-.375 0h.008v.015h-.008V9.75Zm5.625 0c0 .414-.168.75-.375.75s-.375-.336-.375-.75.168-.75.375-.75.375.336.375.75Zm-.375 0h.008v.015h-.008V9.75Z"
+        }
+        MessageMenuAction::Edit => {
+            "m16.862 4.487 1.687-1.688a1.875 1.875 0 1 1 2.652 2.652L10.582 16.07a4.5 4.5 0 0 1-1.897 1.13L6 18l.8-2.685a4.5 4.5 0 0 1 1.13-1.897l8.932-8.931Zm0 0L19.5 7.125"
+        }
+        MessageMenuAction::Pin | MessageMenuAction::Unpin => {
+            "M16.5 3.75 20.25 7.5l-3 1.5-3.75 3.75.75 4.5-1.5 1.5-3.75-3.75L4.5 19.5m4.5-9 4.5 4.5M9 10.5 6.75 8.25l1.5-1.5 4.5.75L16.5 3.75"
+        }
+        MessageMenuAction::Delete => {
+            "m14.74 9-.346 9m-4.788 0L9.26 9m9.968-3.21c.342.052.682.107 1.022.166m-1.022-.165L18.16 19.673a2.25 2.25 0 0 1-2.244 2.077H8.084a2.25 2.25 0 0 1-2.244-2.077L4.772 5.79m14.456 0a48.108 48.108 0 0 0-3.478-.397m-12 .562c.34-.059.68-.114 1.022-.165m0 0a48.11 48.11 0 0 1 3.478-.397m7.5 0v-.916c0-1.18-.91-2.164-2.09-2.201a51.964 51.964 0 0 0-3.32 0c-1.18.037-2.09 1.022-2.09 2.201v.916m7.5 0a48.667 48.667 0 0 0-7.5 0"
+        }
+    }
+}
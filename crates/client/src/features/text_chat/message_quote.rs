@@ -0,0 +1,73 @@
+//! Цитата сообщения, на которое отвечает сообщение текстового чата.
+
+use cheenhub_contracts::realtime::{TextChatMessage, TextChatReplySnapshot};
+use dioxus::prelude::*;
+
+use super::scroll::MessageAnchors;
+
+/// Рендерит кликабельную цитату, которая прокручивает список к исходному сообщению.
+#[component]
+pub(crate) fn ChatMessageQuote(reply: TextChatReplySnapshot, is_own: bool) -> Element {
+    let anchors = try_use_context::<MessageAnchors>();
+    let quote_class = if is_own {
+        "flex max-w-full min-w-0 flex-col items-start rounded-xl border-l-2 border-blue-300/60 bg-blue-500/5 px-2.5 py-1 text-left text-[11px] leading-4 transition-colors hover:bg-blue-500/10"
+    } else {
+        "flex max-w-full min-w-0 flex-col items-start rounded-xl border-l-2 border-zinc-500/70 bg-white/[0.03] px-2.5 py-1 text-left text-[11px] leading-4 transition-colors hover:bg-white/[0.06]"
+    };
+    let preview = quote_preview(&reply.body, reply.has_attachments, reply.deleted);
+    let message_id = reply.message_id.clone();
+
+    rsx! {
+        button {
+            r#type: "button",
+            class: quote_class,
+            title: "Перейти к сообщению",
+            disabled: reply.deleted,
+            onclick: move |event| {
+                event.stop_propagation();
+                if let Some(anchors) = anchors {
+                    anchors.jump_to(&message_id);
+                }
+            },
+            span { class: "truncate font-semibold text-zinc-200", "{reply.author_nickname}" }
+            span { class: "line-clamp-2 max-w-full text-zinc-400 [overflow-wrap:anywhere]",
+                "{preview}"
+            }
+        }
+    }
+}
+
+/// Рендерит цитату над полем ввода, пока пользователь пишет ответ.
+#[component]
+pub(crate) fn ChatReplyPreview(message: TextChatMessage, on_cancel: EventHandler<()>) -> Element {
+    let preview = quote_preview(&message.body, !message.attachments.is_empty(), false);
+
+    rsx! {
+        div { class: "flex min-w-0 items-center gap-3 rounded-2xl border border-blue-400/25 bg-blue-500/10 px-3 py-2 text-zinc-100",
+            div { class: "min-w-0 flex-1 border-l-2 border-blue-300/60 pl-2.5",
+                p { class: "truncate text-[12px] font-medium", "Ответ для {message.author_nickname}" }
+                p { class: "mt-0.5 truncate text-[11px] text-zinc-400", "{preview}" }
+            }
+            button {
+                r#type: "button",
+                class: "flex size-8 shrink-0 items-center justify-center rounded-xl text-zinc-400 transition-colors hover:bg-white/10 hover:text-white",
+                "aria-label": "Отменить ответ",
+                title: "Отменить ответ",
+                onclick: move |_| on_cancel.call(()),
+                svg { class: "size-4", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24", "aria-hidden": "true",
+                    path { stroke_linecap: "round", stroke_linejoin: "round", d: "m6 6 12 12M18 6 6 18" }
+                }
+            }
+        }
+    }
+}
+
+fn quote_preview(body: &str, has_attachments: bool, deleted: bool) -> String {
+    if deleted {
+        "Сообщение удалено".to_owned()
+    } else if body.is_empty() && has_attachments {
+        "Вложение".to_owned()
+    } else {
+        body.to_owned()
+    }
+}
@@ -0,0 +1,83 @@
+//! Реакции эмодзи под сообщением и быстрый выбор реакции.
+
+use cheenhub_contracts::rest::MessageReactionSummary;
+use dioxus::prelude::*;
+
+/// Эмодзи, доступные в быстром выборе реакции.
+const QUICK_REACTIONS: [&str; 8] = ["👍", "❤️", "😂", "😮", "😢", "🎉", "🔥", "👀"];
+
+/// Рендерит агрегированные реакции; клик по реакции ставит или снимает свою.
+#[component]
+pub(super) fn ChatMessageReactions(
+    reactions: Vec<MessageReactionSummary>,
+    is_own: bool,
+    on_toggle: Option<EventHandler<(String, bool)>>,
+) -> Element {
+    if reactions.is_empty() {
+        return rsx! {};
+    }
+    let row_class = if is_own {
+        "flex max-w-full flex-wrap justify-end gap-1"
+    } else {
+        "flex max-w-full flex-wrap justify-start gap-1"
+    };
+
+    rsx! {
+        div { class: row_class,
+            for reaction in reactions {
+                button {
+                    key: "{reaction.emoji}",
+                    r#type: "button",
+                    class: if reaction.reacted { "inline-flex h-6 items-center gap-1 rounded-full border border-blue-400/50 bg-blue-500/15 px-2 text-[12px] leading-none text-blue-100 transition-colors hover:bg-blue-500/25" } else { "inline-flex h-6 items-center gap-1 rounded-full border border-zinc-800 bg-zinc-900/80 px-2 text-[12px] leading-none text-zinc-300 transition-colors hover:border-white/15 hover:bg-zinc-800" },
+                    title: if reaction.reacted { "Убрать реакцию" } else { "Поставить реакцию" },
+                    disabled: on_toggle.is_none(),
+                    onclick: {
+                        let emoji = reaction.emoji.clone();
+                        let add = !reaction.reacted;
+                        move |event: MouseEvent| {
+                            event.stop_propagation();
+                            if let Some(on_toggle) = on_toggle {
+                                on_toggle.call((emoji.clone(), add));
+                            }
+                        }
+                    },
+                    span { "{reaction.emoji}" }
+                    span { class: "text-[11px] font-semibold tabular-nums", "{reaction.count}" }
+                }
+            }
+        }
+    }
+}
+
+/// Рендерит всплывающую панель быстрого выбора реакции в точке клика.
+#[component]
+pub(super) fn ChatReactionPicker(
+    x: f64,
+    y: f64,
+    on_pick: EventHandler<String>,
+    on_close: EventHandler<()>,
+) -> Element {
+    rsx! {
+        div {
+            class: "fixed inset-0 z-[999]",
+            onclick: move |_| on_close.call(()),
+        }
+        div {
+            class: "fixed z-[1000] flex gap-1 rounded-full border border-zinc-800 bg-zinc-950/95 p-1.5 shadow-[0_20px_60px_rgba(0,0,0,.55)] backdrop-blur-xl",
+            style: "left: clamp(12px, {x}px, calc(100vw - 340px)); top: clamp(12px, {y}px, calc(100vh - 60px));",
+            onclick: move |event| event.stop_propagation(),
+            for emoji in QUICK_REACTIONS {
+                button {
+                    key: "{emoji}",
+                    r#type: "button",
+                    class: "flex h-9 w-9 items-center justify-center rounded-full text-[18px] transition-[background,transform] duration-150 hover:scale-110 hover:bg-white/10",
+                    onclick: move |_| {
+                        on_close.call(());
+                        on_pick.call(emoji.to_owned());
+                    },
+                    "{emoji}"
+                }
+            }
+        }
+    }
+}
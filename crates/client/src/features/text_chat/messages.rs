@@ -1,6 +1,7 @@
 //! Вспомогательные функции списка сообщений текстового чата.
 
-use cheenhub_contracts::realtime::TextChatMessage;
+use cheenhub_contracts::realtime::{TextChatMention, TextChatMessage};
+use cheenhub_contracts::rest::LinkPreviewSummary;
 use dioxus::prelude::*;
 
 pub(super) fn append_message(
@@ -43,6 +44,51 @@ pub(super) fn prepend_messages(
     messages.set(next_messages);
 }
 
+/// Добавляет загруженную страницу более новых сообщений в конец списка без анимации появления.
+pub(super) fn extend_messages(
+    messages: &mut Signal<Vec<TextChatMessage>>,
+    incoming: Vec<TextChatMessage>,
+) {
+    let mut next_messages = messages();
+    for message in incoming {
+        if !next_messages
+            .iter()
+            .any(|saved_message| saved_message.id == message.id)
+        {
+            next_messages.push(message);
+        }
+    }
+    messages.set(next_messages);
+}
+
+/// Заменяет окно истории последней страницей, сохраняя сообщения, добавленные в панель вживую.
+///
+/// Сообщение сохраняется в фоне после отправки, поэтому только что
+/// отправленное сообщение может еще отсутствовать в загруженной странице.
+pub(super) fn replace_with_latest(
+    messages: &mut Signal<Vec<TextChatMessage>>,
+    appearing_message_ids: &mut Signal<Vec<String>>,
+    latest: Vec<TextChatMessage>,
+) {
+    let appearing = appearing_message_ids();
+    let live_messages = messages()
+        .into_iter()
+        .filter(|message| {
+            is_appearing_message(&message.id, &appearing)
+                && !latest.iter().any(|loaded| loaded.id == message.id)
+        })
+        .collect::<Vec<_>>();
+    appearing_message_ids.set(
+        live_messages
+            .iter()
+            .map(|message| message.id.clone())
+            .collect(),
+    );
+    let mut next_messages = latest;
+    next_messages.extend(live_messages);
+    messages.set(next_messages);
+}
+
 /// Удаляет сообщение из списка по идентификатору.
 pub(super) fn remove_message(messages: &mut Signal<Vec<TextChatMessage>>, message_id: &str) {
     let next = messages()
@@ -52,6 +98,38 @@ pub(super) fn remove_message(messages: &mut Signal<Vec<TextChatMessage>>, messag
     messages.set(next);
 }
 
+/// Применяет новый текст сообщения, если сообщение загружено в список.
+pub(super) fn apply_message_edit(
+    messages: &mut Signal<Vec<TextChatMessage>>,
+    message_id: &str,
+    body: String,
+    edited_at: String,
+    mentions: Vec<TextChatMention>,
+) {
+    let mut next = messages();
+    let Some(message) = next.iter_mut().find(|m| m.id == message_id) else {
+        return;
+    };
+    message.body = body;
+    message.edited_at = Some(edited_at);
+    message.mentions = mentions;
+    messages.set(next);
+}
+
+/// Подставляет полученные карточки ссылок, если сообщение загружено в список.
+pub(super) fn apply_link_previews(
+    messages: &mut Signal<Vec<TextChatMessage>>,
+    message_id: &str,
+    previews: Vec<LinkPreviewSummary>,
+) {
+    let mut next = messages();
+    let Some(message) = next.iter_mut().find(|m| m.id == message_id) else {
+        return;
+    };
+    message.link_previews = previews;
+    messages.set(next);
+}
+
 pub(crate) fn is_appearing_message(message_id: &str, appearing_message_ids: &[String]) -> bool {
     appearing_message_ids
         .iter()
@@ -81,3 +159,139 @@ pub(crate) fn group_consecutive_messages(
 
     groups
 }
+
+/// Группа сообщений списка вместе с разделителями, которые выводятся перед ней.
+#[derive(Clone, PartialEq)]
+pub(super) struct MessageGroupRow {
+    /// Стабильный ключ группы — идентификатор ее первого сообщения.
+    pub(super) key: String,
+    /// Подпись нового календарного дня, если группа его начинает.
+    pub(super) date_label: Option<String>,
+    /// Начинаются ли с этой группы непрочитанные сообщения.
+    pub(super) starts_unread: bool,
+    /// Сообщения группы.
+    pub(super) messages: Vec<TextChatMessage>,
+}
+
+/// Находит первое сообщение другого участника после последнего прочитанного.
+///
+/// Если отметка прочтения не попала в загруженное окно, разделитель не показывается.
+pub(super) fn first_unread_message_id(
+    messages: &[TextChatMessage],
+    last_read_message_id: Option<&str>,
+    current_user_id: &str,
+) -> Option<String> {
+    let last_read_index = messages
+        .iter()
+        .position(|message| Some(message.id.as_str()) == last_read_message_id)?;
+
+    messages[last_read_index + 1..]
+        .iter()
+        .find(|message| message.author_user_id != current_user_id)
+        .map(|message| message.id.clone())
+}
+
+/// Разбивает сообщения на группы с разделителями дней и начала непрочитанного.
+///
+/// Группа, внутри которой начинаются непрочитанные сообщения, делится на две,
+/// чтобы разделитель встал ровно перед первым непрочитанным сообщением.
+pub(super) fn message_group_rows(
+    messages: &[TextChatMessage],
+    first_unread_id: Option<&str>,
+) -> Vec<MessageGroupRow> {
+    let mut previous_day_key = None;
+    let mut rows = Vec::new();
+    for group in group_consecutive_messages(messages) {
+        let split = group
+            .iter()
+            .position(|message| Some(message.id.as_str()) == first_unread_id);
+        let parts = match split {
+            Some(index) if index > 0 => {
+                let mut head = group;
+                let tail = head.split_off(index);
+                vec![head, tail]
+            }
+            _ => vec![group],
+        };
+        for part in parts {
+            let Some(first_message) = part.first() else {
+                continue;
+            };
+            let day_key = super::message_date::message_day_key(&first_message.created_at);
+            let date_label = (previous_day_key.as_ref() != Some(&day_key))
+                .then(|| super::message_date::friendly_message_date(&first_message.created_at));
+            previous_day_key = Some(day_key);
+            rows.push(MessageGroupRow {
+                key: first_message.id.clone(),
+                date_label,
+                starts_unread: Some(first_message.id.as_str()) == first_unread_id,
+                messages: part,
+            });
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use cheenhub_contracts::realtime::TextChatMessage;
+
+    use super::{first_unread_message_id, message_group_rows};
+
+    fn message(id: &str, author_user_id: &str) -> TextChatMessage {
+        TextChatMessage {
+            id: id.to_owned(),
+            server_id: "server".to_owned(),
+            room_id: "room".to_owned(),
+            author_user_id: author_user_id.to_owned(),
+            author_nickname: author_user_id.to_owned(),
+            author_avatar_url: None,
+            body: id.to_owned(),
+            attachments: Vec::new(),
+            delivery_status: None,
+            created_at: "2026-10-16T12:00:00Z".to_owned(),
+            edited_at: None,
+            reply_to_message_id: None,
+            reply_to: None,
+            reactions: Vec::new(),
+            mentions: Vec::new(),
+            link_previews: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn first_unread_skips_own_messages_after_marker() {
+        let messages = vec![
+            message("a", "other"),
+            message("b", "me"),
+            message("c", "other"),
+        ];
+
+        assert_eq!(
+            first_unread_message_id(&messages, Some("a"), "me"),
+            Some("c".to_owned())
+        );
+        assert_eq!(first_unread_message_id(&messages, Some("c"), "me"), None);
+        assert_eq!(first_unread_message_id(&messages, Some("old"), "me"), None);
+    }
+
+    #[test]
+    fn unread_divider_splits_group_of_one_author() {
+        let messages = vec![
+            message("a", "other"),
+            message("b", "other"),
+            message("c", "other"),
+        ];
+
+        let rows = message_group_rows(&messages, Some("b"));
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].date_label.is_some());
+        assert!(!rows[0].starts_unread);
+        assert_eq!(rows[1].key, "b");
+        assert!(rows[1].date_label.is_none());
+        assert!(rows[1].starts_unread);
+        assert_eq!(rows[1].messages.len(), 2);
+    }
+}
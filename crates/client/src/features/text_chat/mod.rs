@@ -1,22 +1,39 @@
 //! Text chat client feature.
 
+mod api;
 mod attachment_preview;
 mod clipboard;
 mod compose;
 mod compose_actions;
+mod compose_focus;
+mod file_attachment;
 mod history;
+mod history_status;
 mod image_attachment;
+mod jump_to_latest;
+mod link_previews;
+mod message_actions;
+mod message_body;
 mod message_date;
 mod message_date_divider;
 mod message_group;
 mod message_item;
+mod message_menu;
+mod message_quote;
+mod message_reactions;
 mod messages;
 mod panel;
 mod pending_attachment;
+mod pinned_drawer;
+mod pins;
+mod reactions;
+mod read_marker;
 pub(crate) mod realtime;
 mod room_compose_state;
+mod room_events;
 mod scroll;
 mod surface;
+mod unread_divider;
 
 /// Общая ширина визуальной группы формы и ожидающего вложения.
 pub(crate) const CHAT_COMPOSER_GROUP_CLASS: &str = "mx-auto min-w-0 w-full max-w-5xl space-y-2";
@@ -29,11 +46,14 @@ pub(crate) const CHAT_COMPOSER_CLASS: &str = concat!(
 /// Общая ширина списка сообщений.
 pub(crate) const CHAT_CONTENT_CLASS: &str = "mx-auto flex w-full max-w-5xl flex-col gap-4";
 pub(crate) use attachment_preview::ChatAttachmentPreview;
+pub(crate) use file_attachment::{AttachmentSource, ChatFileAttachment};
 pub(crate) use message_date::{friendly_message_date, message_day_key};
 pub(crate) use message_date_divider::ChatMessageDateDivider;
 pub(crate) use message_group::ChatMessageGroup;
 pub(crate) use message_item::ChatMessageItem;
 pub(crate) use messages::{group_consecutive_messages, is_appearing_message};
+pub(crate) use pending_attachment::{PendingChatAttachment, pending_chat_attachment};
+pub(crate) use reactions::merge_broadcast_reactions;
 pub(crate) use room_compose_state::{RoomComposeState, use_room_compose_state};
 pub(crate) use scroll::{
     ScrollCommand, apply_scroll_command, capture_scroll_position, update_near_bottom_state,
@@ -1,38 +1,47 @@
 //! Компонент панели текстового чата комнаты.
 
-use std::time::Duration;
 use std::{cell::Cell, rc::Rc};
 
 use cheenhub_contracts::realtime::TextChatMessage;
 use dioxus::prelude::*;
-use futures_util::StreamExt;
 
+use crate::features::app::active_room::ActiveRoomContext;
 use crate::features::app::components::app_shell::ActiveRoom;
+use crate::features::app::current_user::CurrentUserContext;
 use crate::features::app::server_permissions::ServerPermissionsContext;
 use crate::features::image_picker::{ImagePickerButton, ImagePickerOutcome, PickedImage};
 use crate::features::realtime::RealtimeHandle;
-use crate::features::runtime::sleep_duration;
 
 use super::clipboard;
 use super::compose::{ComposeState, send_current_message};
 use super::compose_actions::add_pending_image;
+use super::compose_focus::restore_compose_input_focus;
 use super::history::{
-    HistoryState, HistoryTarget, load_initial_history, load_initial_history_when_connected,
-    load_older_history,
+    HistoryState, HistoryTarget, load_history_around, load_initial_history,
+    load_initial_history_when_connected, load_newer_history, load_older_history,
 };
-use super::messages::{append_message, group_consecutive_messages, remove_message};
-use super::pending_attachment::{
-    PendingImageAttachment, can_send_message, pending_image_attachment,
+use super::history_status::{ChatEmptyHistory, ChatHistoryPageStatus, ChatHistorySkeleton};
+use super::jump_to_latest::ChatJumpToLatestButton;
+use super::message_actions::use_room_message_actions;
+use super::message_quote::ChatReplyPreview;
+use super::messages::{first_unread_message_id, message_group_rows};
+use super::pending_attachment::{PendingChatAttachment, can_send_message, pending_chat_attachment};
+use super::pinned_drawer::ChatPinnedMessages;
+use super::pins::use_room_pins;
+use super::reactions::use_room_reaction_toggle;
+use super::read_marker::use_mark_room_read;
+use super::room_events::{RoomEventState, spawn_room_event_listener};
+use super::scroll::{
+    HistoryPaging, MessageAnchors, ScrollCommand, apply_scroll_command, update_scroll_state,
 };
-use super::realtime::{self, TextChatEvent};
-use super::scroll::{ScrollCommand, apply_scroll_command, update_scroll_state};
+use super::unread_divider::ChatUnreadDivider;
 use super::{
     CHAT_COMPOSER_CLASS, CHAT_COMPOSER_GROUP_CLASS, CHAT_CONTENT_CLASS, ChatAttachmentPreview,
-    ChatMessageDateDivider, ChatMessageGroup, RoomComposeState, friendly_message_date,
-    message_day_key,
+    ChatMessageDateDivider, ChatMessageGroup, RoomComposeState,
 };
 
-const MAX_CHAT_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+/// Самый большой лимит сервера; точный лимит зависит от вида файла и проверяется при загрузке.
+const MAX_CHAT_ATTACHMENT_BYTES: usize = 50 * 1024 * 1024;
 
 /// Рендерит панель realtime-текстового чата для одной комнаты.
 #[component]
@@ -40,9 +49,9 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
     let realtime = use_context::<RealtimeHandle>();
     let permissions = use_context::<ServerPermissionsContext>();
     let room_compose_state = use_context::<RoomComposeState>();
-    let mut messages = use_signal(Vec::<TextChatMessage>::new);
-    let mut appearing_message_ids = use_signal(Vec::<String>::new);
-    let mut removing_message_ids = use_signal(Vec::<String>::new);
+    let messages = use_signal(Vec::<TextChatMessage>::new);
+    let appearing_message_ids = use_signal(Vec::<String>::new);
+    let removing_message_ids = use_signal(Vec::<String>::new);
     let mut draft = room_compose_state.draft;
     let mut status = room_compose_state.status;
     let is_sending = room_compose_state.is_sending;
@@ -51,9 +60,13 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
     let mut pending_attachment = room_compose_state.pending_attachment;
     let initial_loading = use_signal(|| true);
     let older_loading = use_signal(|| false);
+    let newer_loading = use_signal(|| false);
     let history_error = use_signal(|| None::<String>);
     let older_error = use_signal(|| None::<String>);
-    let has_more = use_signal(|| false);
+    let newer_error = use_signal(|| None::<String>);
+    let has_more_before = use_signal(|| false);
+    let has_more_after = use_signal(|| false);
+    let last_read_message_id = use_signal(|| None::<String>);
     let is_near_bottom = use_signal(|| true);
     let mut list_element = use_signal(|| None::<Rc<MountedData>>);
     let mut compose_input_element = use_signal(|| None::<Rc<MountedData>>);
@@ -64,41 +77,89 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
         move || component_current.set(false)
     });
     let mut pending_scroll = use_signal(|| None::<ScrollCommand>);
+    let mut reply_to = room_compose_state.reply_to;
     let event_room_id = room.id.clone();
+    let current_user_id = use_context::<CurrentUserContext>().require_user().id;
+    let event_user_id = current_user_id.clone();
     let history_server_id = server_id.clone();
     let history_room_id = room.id.clone();
-    let older_server_id = server_id.clone();
-    let older_room_id = room.id.clone();
     let send_server_id = server_id.clone();
     let send_room_id = room.id.clone();
-    let delete_server_id = server_id.clone();
-    let delete_room_id = room.id.clone();
-    let delete_realtime = realtime.clone();
     let history_realtime = realtime.clone();
     let event_realtime = realtime.clone();
-    let older_realtime = realtime.clone();
     let send_realtime = realtime.clone();
     let history_target = HistoryTarget {
         realtime: history_realtime,
         server_id: history_server_id,
         room_id: history_room_id,
     };
-    let older_target = HistoryTarget {
-        realtime: older_realtime,
-        server_id: older_server_id,
-        room_id: older_room_id,
-    };
+    let older_target = history_target.clone();
+    let newer_target = history_target.clone();
+    let around_target = history_target.clone();
+    let latest_target = history_target.clone();
     let history_state = HistoryState {
         messages,
         appearing_message_ids,
-        has_more,
+        has_more_before,
+        has_more_after,
+        last_read_message_id,
         initial_loading,
         history_error,
         older_loading,
         older_error,
+        newer_loading,
+        newer_error,
         list_element,
         pending_scroll,
     };
+    let load_around = use_callback(move |message_id: String| {
+        load_history_around(around_target.clone(), history_state, message_id);
+    });
+    let anchors = use_context_provider(|| MessageAnchors::new(pending_scroll, load_around));
+    let active_room = use_context::<ActiveRoomContext>();
+    let focus_room_id = room.id.clone();
+    // Переход из уведомления ждет первой загрузки истории, иначе она заменит окно вокруг сообщения.
+    use_effect(move || {
+        if initial_loading() {
+            return;
+        }
+        if let Some(message_id) = active_room.take_message_focus(&focus_room_id) {
+            anchors.jump_to(&message_id);
+        }
+    });
+    let event_state = RoomEventState {
+        messages,
+        appearing_message_ids,
+        removing_message_ids,
+        is_near_bottom,
+        has_more_after,
+        pending_scroll,
+    };
+    let on_react = use_room_reaction_toggle(
+        realtime.clone(),
+        server_id.clone(),
+        room.id.clone(),
+        messages,
+        status,
+    );
+    let message_actions = use_room_message_actions(
+        realtime.clone(),
+        server_id.clone(),
+        room.id.clone(),
+        messages,
+        removing_message_ids,
+        status,
+    );
+    let on_delete_message = message_actions.delete;
+    let on_edit_message = message_actions.edit;
+    let pins = use_room_pins(
+        realtime.clone(),
+        server_id.clone(),
+        room.id.clone(),
+        permissions.can_pin_messages,
+        status,
+    );
+    use_context_provider(|| pins);
     let compose_state = ComposeState {
         draft,
         messages,
@@ -106,6 +167,7 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
         status,
         is_sending,
         pending_attachment,
+        reply_to,
         pending_scroll,
     };
     let placeholder_prefix = if compact { "&" } else { "#" };
@@ -133,51 +195,30 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
     let removing_message_ids_list = removing_message_ids();
     let rendered_messages = messages();
     let has_messages = !rendered_messages.is_empty();
-    let mut previous_day_key = None;
-    let message_groups = group_consecutive_messages(&rendered_messages)
-        .into_iter()
-        .filter_map(|group| {
-            let first_message = group.first()?;
-            let day_key = message_day_key(&first_message.created_at);
-            let date_label = (previous_day_key.as_ref() != Some(&day_key))
-                .then(|| friendly_message_date(&first_message.created_at));
-            previous_day_key = Some(day_key);
-            Some((first_message.id.clone(), date_label, group))
-        })
-        .collect::<Vec<_>>();
+    let first_unread_id = first_unread_message_id(
+        &rendered_messages,
+        last_read_message_id().as_deref(),
+        &current_user_id,
+    );
+    let message_groups = message_group_rows(&rendered_messages, first_unread_id.as_deref());
+    use_mark_room_read(
+        history_target.clone(),
+        current_user_id,
+        history_state,
+        is_near_bottom,
+    );
 
     use_hook(move || {
         load_initial_history_when_connected(history_target, history_state);
     });
 
     use_hook(move || {
-        let realtime = event_realtime.clone();
-        spawn(async move {
-            let mut receiver = realtime::subscribe_text_chat(&realtime);
-            while let Some(event) = receiver.next().await {
-                match event {
-                    TextChatEvent::MessageCreated(message) => {
-                        if message.room_id == event_room_id
-                            && append_message(&mut messages, &mut appearing_message_ids, message)
-                            && is_near_bottom()
-                        {
-                            pending_scroll.set(Some(ScrollCommand::Bottom));
-                        }
-                    }
-                    TextChatEvent::MessageDeleted(payload) => {
-                        if payload.room_id == event_room_id {
-                            let message_id = payload.message_id.clone();
-                            removing_message_ids.write().push(message_id.clone());
-                            spawn(async move {
-                                sleep_duration(Duration::from_millis(220)).await;
-                                remove_message(&mut messages, &message_id);
-                                removing_message_ids.write().retain(|id| id != &message_id);
-                            });
-                        }
-                    }
-                }
-            }
-        });
+        spawn_room_event_listener(
+            event_realtime.clone(),
+            event_room_id.clone(),
+            event_user_id.clone(),
+            event_state,
+        );
     });
 
     use_effect(move || {
@@ -200,6 +241,31 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
         pending_attachment().is_some(),
         is_sending() || is_selecting_image() || is_reading_clipboard(),
     );
+    let load_older = use_callback(move |_| {
+        load_older_history(older_target.clone(), history_state);
+    });
+    let load_newer = use_callback(move |_| {
+        load_newer_history(newer_target.clone(), history_state);
+    });
+    let reload_latest = use_callback(move |_| {
+        load_initial_history(latest_target.clone(), history_state);
+    });
+    let jump_to_latest = use_callback(move |_| {
+        if has_more_after() {
+            reload_latest.call(());
+        } else {
+            pending_scroll.set(Some(ScrollCommand::SmoothBottom));
+        }
+    });
+    let paging = HistoryPaging {
+        has_more_before,
+        has_more_after,
+        older_loading,
+        newer_loading,
+        initial_loading,
+        load_older,
+        load_newer,
+    };
     let submit_realtime = send_realtime.clone();
     let submit_server_id = send_server_id.clone();
     let submit_room_id = send_room_id.clone();
@@ -211,6 +277,9 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
             is_sending() || is_selecting_image() || is_reading_clipboard(),
         ) {
             refocus_requested.set(true);
+            if has_more_after() {
+                jump_to_latest.call(());
+            }
             send_current_message(
                 submit_realtime.clone(),
                 submit_server_id.clone(),
@@ -229,144 +298,96 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
             );
         }
     });
-    let load_older = use_callback(move |_| {
-        load_older_history(older_target.clone(), history_state);
-    });
-    let on_delete_message = use_callback(move |message_id: String| {
-        let realtime = delete_realtime.clone();
-        let server_id = delete_server_id.clone();
-        let room_id = delete_room_id.clone();
-        removing_message_ids.write().push(message_id.clone());
-        spawn(async move {
-            let _ =
-                realtime::delete_text_message(&realtime, server_id, room_id, message_id.clone())
-                    .await;
-            sleep_duration(Duration::from_millis(220)).await;
-            remove_message(&mut messages, &message_id);
-            removing_message_ids.write().retain(|id| id != &message_id);
-        });
-    });
-    let add_pending_image = use_callback(move |result: Result<PendingImageAttachment, String>| {
+    let add_pending_image = use_callback(move |result: Result<PendingChatAttachment, String>| {
         add_pending_image(room_compose_state, result);
     });
     let select_pending_image = use_callback(move |outcome: ImagePickerOutcome| {
         let result = match outcome {
             ImagePickerOutcome::Selected(PickedImage { file_name, bytes }) => {
-                pending_image_attachment(file_name, bytes, MAX_CHAT_IMAGE_BYTES)
+                pending_chat_attachment(file_name, bytes, MAX_CHAT_ATTACHMENT_BYTES)
             }
             ImagePickerOutcome::Failed(error) => Err(error),
         };
         add_pending_image.call(result);
     });
-    let clipboard_outcome = use_callback(move |result: Result<PendingImageAttachment, String>| {
+    let clipboard_outcome = use_callback(move |result: Result<PendingChatAttachment, String>| {
         is_reading_clipboard.set(false);
         add_pending_image.call(result);
     });
 
     rsx! {
-        div { class: "flex h-full min-h-0 flex-col",
+        div { class: "relative flex h-full min-h-0 flex-col",
+            ChatPinnedMessages { pins, on_delete: move |id| on_delete_message.call(id) }
             div {
                 class: list_class,
                 onmounted: move |event| list_element.set(Some(event.data.clone())),
                 onscroll: move |_| {
                     if let Some(element) = list_element.cloned() {
                         spawn(async move {
-                            update_scroll_state(
-                                element,
-                                is_near_bottom,
-                                has_more,
-                                older_loading,
-                                initial_loading,
-                                load_older,
-                            ).await;
+                            update_scroll_state(element, is_near_bottom, paging).await;
                         });
                     }
                 },
                 div { class: inner_class,
-                    if older_loading() {
-                        div { class: "flex justify-center py-2",
-                            div { class: "h-5 w-5 animate-spin rounded-full border-2 border-zinc-700 border-t-blue-400" }
-                        }
-                    } else if let Some(error) = older_error() {
-                        div { class: "rounded-xl border border-red-500/20 bg-red-500/10 px-3 py-2 text-center text-[12px] leading-5 text-red-200",
-                            p { "{error}" }
-                            button {
-                                r#type: "button",
-                                class: "mt-2 rounded-lg border border-red-300/20 px-3 py-1 text-[12px] font-medium text-red-100 transition-colors hover:border-red-200/40 hover:bg-red-400/10",
-                                onclick: move |_| load_older.call(()),
-                                "Повторить"
-                            }
-                        }
+                    ChatHistoryPageStatus {
+                        loading: older_loading(),
+                        error: older_error(),
+                        on_retry: move |_| load_older.call(()),
                     }
                     if initial_loading() && !has_messages {
-                        div { class: "space-y-3",
-                            div { class: "h-14 animate-pulse rounded-2xl bg-zinc-900/80" }
-                            div { class: "h-14 animate-pulse rounded-2xl bg-zinc-900/60" }
-                            div { class: "h-14 animate-pulse rounded-2xl bg-zinc-900/40" }
-                        }
-                    } else if let Some(error) = history_error() {
-                        div { class: "rounded-xl border border-red-500/20 bg-red-500/10 px-3 py-3 text-center text-[12px] leading-5 text-red-200",
-                            p { "{error}" }
-                            button {
-                                r#type: "button",
-                                class: "mt-2 rounded-lg border border-red-300/20 px-3 py-1 text-[12px] font-medium text-red-100 transition-colors hover:border-red-200/40 hover:bg-red-400/10",
-                                onclick: move |_| {
-                                    load_initial_history(
-                                        HistoryTarget {
-                                            realtime: realtime.clone(),
-                                            server_id: server_id.clone(),
-                                            room_id: room.id.clone(),
-                                        },
-                                        history_state,
-                                    );
-                                },
-                                "Повторить"
-                            }
+                        ChatHistorySkeleton {}
+                    } else if history_error().is_some() {
+                        ChatHistoryPageStatus {
+                            loading: false,
+                            error: history_error(),
+                            on_retry: move |_| reload_latest.call(()),
                         }
                     } else if !has_messages {
-                        div { class: "rounded-[20px] border border-zinc-800 bg-zinc-900/60 p-6 text-center",
-                            p { class: "text-[13px] font-medium text-zinc-100", "Сообщений пока нет" }
-                            p { class: "mt-1 text-[12px] leading-5 text-zinc-500",
-                                "Напиши первое сообщение в этой комнате."
-                            }
-                        }
+                        ChatEmptyHistory {}
                     } else {
-                        for (group_key, date_label, group) in message_groups.iter().cloned() {
-                            div { key: "{group_key}", class: "contents",
-                                if let Some(label) = date_label {
+                        for row in message_groups.iter().cloned() {
+                            div { key: "{row.key}", class: "contents",
+                                if let Some(label) = row.date_label {
                                     ChatMessageDateDivider { label }
                                 }
+                                if row.starts_unread {
+                                    ChatUnreadDivider {}
+                                }
                                 ChatMessageGroup {
-                                    messages: group,
+                                    messages: row.messages,
                                     appearing_message_ids: appearing_message_ids_list.clone(),
                                     removing_message_ids: removing_message_ids_list.clone(),
                                     can_delete_messages: permissions.can_delete_messages,
                                     on_delete: move |id| on_delete_message.call(id),
+                                    on_edit: move |edit| on_edit_message.call(edit),
+                                    on_react: move |reaction| on_react.call(reaction),
+                                    on_reply: move |message| {
+                                        reply_to.set(Some(message));
+                                        if let Some(element) = compose_input_element.cloned() {
+                                            spawn(async move {
+                                                let _ = element.set_focus(true).await;
+                                            });
+                                        }
+                                    },
                                     server_id: server_id.clone(),
                                     room_id: room.id.clone(),
                                 }
                             }
                         }
                     }
+                    ChatHistoryPageStatus {
+                        loading: newer_loading(),
+                        error: newer_error(),
+                        on_retry: move |_| load_newer.call(()),
+                    }
                 }
             }
             div { class: "relative",
-                if !is_near_bottom() && has_messages {
-                    div { class: "pointer-events-none absolute bottom-3 right-4 z-20",
-                    button {
-                        r#type: "button",
-                        class: "group pointer-events-auto relative flex h-10 w-10 items-center justify-center rounded-full border border-zinc-800 bg-zinc-950/85 text-blue-200 shadow-[0_8px_22px_rgba(0,0,0,0.35)] transition-[background,border-color,color,transform,opacity] duration-150 hover:-translate-y-px hover:border-white/15 hover:bg-zinc-900/90 hover:text-blue-100",
-                        "aria-label": "Перейти к последнему сообщению",
-                        onclick: move |_| pending_scroll.set(Some(ScrollCommand::SmoothBottom)),
-                        span { class: "pointer-events-none absolute bottom-[calc(100%+8px)] right-0 whitespace-nowrap rounded-lg border border-zinc-800 bg-zinc-950/95 px-2 py-1 text-[11px] font-medium text-zinc-300 opacity-0 shadow-[0_8px_22px_rgba(0,0,0,0.35)] transition-[opacity,transform] duration-150 group-hover:opacity-100",
-                            "К последнему сообщению"
-                        }
-                        svg { class: "h-5 w-5", fill: "none", stroke: "currentColor", stroke_width: "2", view_box: "0 0 24 24",
-                            path { stroke_linecap: "round", stroke_linejoin: "round", d: "M12 5v14m0 0 6-6m-6 6-6-6" }
-                        }
+                if (!is_near_bottom() || has_more_after()) && has_messages {
+                    ChatJumpToLatestButton {
+                        on_click: move |_| jump_to_latest.call(()),
                     }
                 }
-                }
             }
             div { class: input_outer_class,
                 div { class: CHAT_COMPOSER_GROUP_CLASS,
@@ -376,13 +397,19 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
                         "Получаем изображение из буфера обмена…"
                         }
                     }
+                    if let Some(message) = reply_to() {
+                        ChatReplyPreview {
+                            message,
+                            on_cancel: move |_| reply_to.set(None),
+                        }
+                    }
                     if let Some(attachment) = pending_attachment() {
                         ChatAttachmentPreview {
                             attachment,
                             busy: is_sending(),
                             on_remove: move |_| {
                                 if !is_sending() {
-                                    info!("removed pending text chat image");
+                                    info!("removed pending text chat attachment");
                                     pending_attachment.set(None);
                                     status.set(String::new());
                                 }
@@ -393,7 +420,8 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
                     ImagePickerButton {
                         disabled: is_sending() || is_reading_clipboard() || pending_attachment().is_some(),
                         busy: is_selecting_image() || is_reading_clipboard(),
-                        max_bytes: MAX_CHAT_IMAGE_BYTES,
+                        max_bytes: MAX_CHAT_ATTACHMENT_BYTES,
+                        accept_any_file: true,
                         on_outcome: move |outcome| select_pending_image.call(outcome),
                         on_active_change: move |active| is_selecting_image.set(active),
                     }
@@ -452,43 +480,3 @@ pub(crate) fn ChatRoomPanel(server_id: String, room: ActiveRoom, compact: bool)
         }
     }
 }
-
-fn restore_compose_input_focus(
-    input_element: Signal<Option<Rc<MountedData>>>,
-    refocus_requested: Signal<bool>,
-    component_current: Rc<Cell<bool>>,
-) {
-    if !should_refocus(component_current.get(), refocus_requested()) {
-        return;
-    }
-
-    let Some(element) = input_element.cloned() else {
-        return;
-    };
-
-    spawn(async move {
-        if !should_refocus(component_current.get(), refocus_requested()) {
-            return;
-        }
-
-        if let Err(error) = element.set_focus(true).await {
-            debug!(?error, "failed to restore text chat input focus");
-        }
-    });
-}
-
-fn should_refocus(component_current: bool, refocus_requested: bool) -> bool {
-    component_current && refocus_requested
-}
-
-#[cfg(test)]
-mod tests {
-    use super::should_refocus;
-
-    #[test]
-    fn refocus_requires_an_active_component_and_submit_intent() {
-        assert!(should_refocus(true, true));
-        assert!(!should_refocus(false, true));
-        assert!(!should_refocus(true, false));
-    }
-}
@@ -3,35 +3,35 @@
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 
-/// Изображение, ожидающее отправки вместе с черновиком.
+/// Файл, ожидающий отправки вместе с черновиком.
 #[derive(Clone, Debug, Eq, PartialEq)]
-pub(crate) struct PendingImageAttachment {
+pub(crate) struct PendingChatAttachment {
     /// Имя, показываемое пользователю.
     pub(crate) display_name: String,
     /// Исходное имя файла, если оно известно платформе.
     pub(crate) file_name: Option<String>,
     /// Размер содержимого в байтах.
     pub(crate) byte_size: usize,
-    /// Содержимое изображения до первой успешной загрузки.
+    /// Содержимое файла до первой успешной загрузки.
     pub(crate) bytes: Vec<u8>,
-    /// Идентификатор уже загруженного изображения для повторной отправки.
+    /// Идентификатор уже загруженного вложения для повторной отправки.
     pub(crate) uploaded_id: Option<String>,
-    /// Безопасный data URL для локального thumbnail без platform API.
+    /// Безопасный data URL для локального thumbnail; есть только у изображений.
     pub(crate) preview_data_url: Option<String>,
 }
 
 /// Создаёт ожидающее вложение после локальной проверки размера.
-pub(crate) fn pending_image_attachment(
+pub(crate) fn pending_chat_attachment(
     file_name: Option<String>,
     bytes: Vec<u8>,
     max_bytes: usize,
-) -> Result<PendingImageAttachment, String> {
+) -> Result<PendingChatAttachment, String> {
     if bytes.is_empty() {
-        return Err("Выбранное изображение пустое.".to_owned());
+        return Err("Выбранный файл пустой.".to_owned());
     }
     if bytes.len() > max_bytes {
         return Err(format!(
-            "Изображение слишком большое. Максимум — {} МБ.",
+            "Файл слишком большой. Максимум — {} МБ.",
             max_bytes / (1024 * 1024)
         ));
     }
@@ -43,7 +43,7 @@ pub(crate) fn pending_image_attachment(
     let preview_data_url = image_content_type(&bytes)
         .filter(|content_type| is_supported_image_mime(content_type))
         .map(|content_type| format!("data:{content_type};base64,{}", BASE64.encode(&bytes)));
-    Ok(PendingImageAttachment {
+    Ok(PendingChatAttachment {
         display_name,
         file_name,
         byte_size: bytes.len(),
@@ -96,7 +96,7 @@ pub(crate) fn format_attachment_size(byte_size: usize) -> String {
 mod tests {
     use super::{
         can_send_message, format_attachment_size, image_content_type, is_supported_image_mime,
-        pending_image_attachment,
+        pending_chat_attachment,
     };
     #[test]
     fn formats_compact_attachment_sizes() {
@@ -104,14 +104,14 @@ mod tests {
     }
     #[test]
     fn rejects_empty_and_oversized_attachments() {
-        assert!(pending_image_attachment(None, Vec::new(), 10).is_err());
-        assert!(pending_image_attachment(None, vec![0; 11], 10).is_err());
+        assert!(pending_chat_attachment(None, Vec::new(), 10).is_err());
+        assert!(pending_chat_attachment(None, vec![0; 11], 10).is_err());
     }
 
     #[test]
     fn creates_png_data_url_for_supported_thumbnail() {
         let bytes = b"\x89PNG\r\n\x1a\nimage".to_vec();
-        let attachment = pending_image_attachment(None, bytes, 1024).unwrap();
+        let attachment = pending_chat_attachment(None, bytes, 1024).unwrap();
         assert_eq!(image_content_type(&attachment.bytes), Some("image/png"));
         assert!(
             attachment
@@ -0,0 +1,95 @@
+//! Панель закрепленных сообщений комнаты.
+
+use dioxus::prelude::*;
+
+use super::message_date::full_message_datetime;
+use super::message_item::ChatMessageItem;
+use super::pins::RoomPins;
+use super::scroll::MessageAnchors;
+
+const PIN_ICON_PATH: &str = "M16.5 3.75 20.25 7.5l-3 1.5-3.75 3.75.75 4.5-1.5 1.5-3.75-3.75L4.5 19.5m4.5-9 4.5 4.5M9 10.5 6.75 8.25l1.5-1.5 4.5.75L16.5 3.75";
+
+/// Рендерит кнопку закрепленных сообщений и выезжающую панель со списком.
+///
+/// Кнопка появляется, только когда в комнате есть закрепленные сообщения.
+#[component]
+pub(super) fn ChatPinnedMessages(pins: RoomPins, on_delete: EventHandler<String>) -> Element {
+    let anchors = try_use_context::<MessageAnchors>();
+    let mut drawer_open = pins.drawer_open;
+    let pinned = (pins.pins)();
+    let count = pinned.len();
+
+    rsx! {
+        if count > 0 {
+            div { class: "shrink-0 border-b border-zinc-800/80 px-4 py-1.5",
+                button {
+                    r#type: "button",
+                    class: "flex items-center gap-2 rounded-lg px-2 py-1 text-[12px] font-medium text-zinc-400 transition-colors hover:bg-white/5 hover:text-zinc-100",
+                    "aria-expanded": if drawer_open() { "true" } else { "false" },
+                    onclick: move |_| drawer_open.toggle(),
+                    svg { class: "h-3.5 w-3.5", fill: "none", stroke: "currentColor", stroke_width: "1.9", view_box: "0 0 24 24",
+                        path { stroke_linecap: "round", stroke_linejoin: "round", d: PIN_ICON_PATH }
+                    }
+                    "Закреплено: {count}"
+                }
+            }
+        }
+        if drawer_open() {
+            aside {
+                class: "absolute inset-y-0 right-0 z-40 flex w-[min(100%,24rem)] flex-col border-l border-zinc-800 bg-zinc-950/95 shadow-[-20px_0_60px_rgba(0,0,0,.45)] backdrop-blur-xl",
+                "aria-label": "Закрепленные сообщения",
+                div { class: "flex shrink-0 items-center justify-between border-b border-zinc-800/80 px-4 py-3",
+                    h2 { class: "text-[13px] font-semibold text-zinc-100", "Закрепленные сообщения" }
+                    button {
+                        r#type: "button",
+                        class: "rounded-lg px-2 py-1 text-[12px] text-zinc-400 transition-colors hover:bg-white/5 hover:text-zinc-100",
+                        "aria-label": "Закрыть закрепленные сообщения",
+                        onclick: move |_| drawer_open.set(false),
+                        "✕"
+                    }
+                }
+                div { class: "min-h-0 flex-1 space-y-4 overflow-y-auto p-4",
+                    if (pins.loading)() {
+                        p { class: "text-[12px] text-zinc-500", "Загружаем закрепленные сообщения…" }
+                    } else if let Some(error) = (pins.error)() {
+                        p { class: "text-[12px] text-red-200", "{error}" }
+                    } else if pinned.is_empty() {
+                        p { class: "text-[12px] text-zinc-500", "В комнате нет закрепленных сообщений." }
+                    }
+                    for pin in pinned {
+                        div { key: "{pin.message.id}", class: "flex flex-col gap-1.5",
+                            div { class: "flex items-center justify-between gap-2 text-[11px]",
+                                span { class: "truncate font-semibold text-zinc-200", "{pin.message.author_nickname}" }
+                                button {
+                                    r#type: "button",
+                                    class: "shrink-0 text-zinc-500 transition-colors hover:text-blue-300",
+                                    title: "Закреплено {full_message_datetime(&pin.pinned_at)}",
+                                    onclick: {
+                                        let message_id = pin.message.id.clone();
+                                        move |_| {
+                                            drawer_open.set(false);
+                                            if let Some(anchors) = anchors {
+                                                anchors.jump_to(&message_id);
+                                            }
+                                        }
+                                    },
+                                    "Перейти"
+                                }
+                            }
+                            ChatMessageItem {
+                                message: pin.message.clone(),
+                                animate: false,
+                                removing: false,
+                                can_delete_messages: false,
+                                on_delete: move |id| on_delete.call(id),
+                                editable: false,
+                                on_edit: move |_| {},
+                                detached: true,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,220 @@
+//! Закрепленные сообщения комнаты на клиенте.
+
+use cheenhub_contracts::realtime::{MessagePinnedPayload, TextChatPinnedMessage};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::features::realtime::{RealtimeConnectionStatus, RealtimeHandle};
+
+use super::realtime::{self, TextChatEvent};
+
+/// Закрепленные сообщения открытой комнаты и действия с ними.
+///
+/// Предоставляется панелью через контекст, чтобы строки сообщений могли
+/// показать пункт «Закрепить» или «Открепить» без передачи свойств через группы.
+#[derive(Clone, Copy, PartialEq)]
+pub(super) struct RoomPins {
+    pub(super) pins: Signal<Vec<TextChatPinnedMessage>>,
+    pub(super) loading: Signal<bool>,
+    pub(super) error: Signal<Option<String>>,
+    pub(super) drawer_open: Signal<bool>,
+    pub(super) can_pin: bool,
+    toggle: Callback<(String, bool)>,
+}
+
+impl RoomPins {
+    /// Закреплено ли сообщение в комнате.
+    pub(super) fn is_pinned(&self, message_id: &str) -> bool {
+        self.pins
+            .read()
+            .iter()
+            .any(|pin| pin.message.id == message_id)
+    }
+
+    /// Закрепляет или открепляет сообщение.
+    pub(super) fn set_pinned(&self, message_id: String, pinned: bool) {
+        self.toggle.call((message_id, pinned));
+    }
+}
+
+/// Загружает закрепленные сообщения комнаты и поддерживает их актуальными по событиям.
+pub(super) fn use_room_pins(
+    realtime: RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    can_pin: bool,
+    mut status: Signal<String>,
+) -> RoomPins {
+    let mut pins = use_signal(Vec::<TextChatPinnedMessage>::new);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+    let drawer_open = use_signal(|| false);
+    let toggle_realtime = realtime.clone();
+    let toggle_server_id = server_id.clone();
+    let toggle_room_id = room_id.clone();
+    let toggle = use_callback(move |(message_id, pinned): (String, bool)| {
+        let realtime = toggle_realtime.clone();
+        let server_id = toggle_server_id.clone();
+        let room_id = toggle_room_id.clone();
+        spawn(async move {
+            match realtime::set_message_pinned(&realtime, server_id, room_id, message_id, pinned)
+                .await
+            {
+                Ok(payload) => apply_pin_event(&mut pins, payload),
+                Err(failure) => status.set(failure.to_string()),
+            }
+        });
+    });
+
+    use_hook(move || {
+        let mut statuses = realtime.subscribe_connection_status();
+        let mut events = realtime::subscribe_text_chat(&realtime);
+        let load_realtime = realtime.clone();
+        let load_room_id = room_id.clone();
+        spawn(async move {
+            while let Some(connection) = statuses.next().await {
+                if !matches!(connection, RealtimeConnectionStatus::Connected(_)) {
+                    continue;
+                }
+                match realtime::list_pinned_messages(&load_realtime, server_id, load_room_id).await
+                {
+                    Ok(list) => pins.set(list.pins),
+                    Err(failure) => error.set(Some(failure.to_string())),
+                }
+                loading.set(false);
+                return;
+            }
+        });
+        spawn(async move {
+            while let Some(event) = events.next().await {
+                match event {
+                    TextChatEvent::Pinned(payload) if payload.room_id == room_id => {
+                        apply_pin_event(&mut pins, *payload);
+                    }
+                    TextChatEvent::Deleted(payload) if payload.room_id == room_id => {
+                        pins.write()
+                            .retain(|pin| pin.message.id != payload.message_id);
+                    }
+                    TextChatEvent::Edited(payload) if payload.room_id == room_id => {
+                        if let Some(pin) = pins
+                            .write()
+                            .iter_mut()
+                            .find(|pin| pin.message.id == payload.message_id)
+                        {
+                            pin.message.body = payload.body;
+                            pin.message.edited_at = Some(payload.edited_at);
+                            pin.message.mentions = payload.mentions;
+                        }
+                    }
+                    TextChatEvent::PreviewReady(payload) if payload.room_id == room_id => {
+                        if let Some(pin) = pins
+                            .write()
+                            .iter_mut()
+                            .find(|pin| pin.message.id == payload.message_id)
+                        {
+                            pin.message.link_previews = payload.previews;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        });
+    });
+
+    RoomPins {
+        pins,
+        loading,
+        error,
+        drawer_open,
+        can_pin,
+        toggle,
+    }
+}
+
+/// Применяет закрепление или открепление к списку, сохраняя порядок от недавно закрепленных.
+fn apply_pin_event(pins: &mut Signal<Vec<TextChatPinnedMessage>>, payload: MessagePinnedPayload) {
+    let mut next = pins();
+    upsert_pin(&mut next, payload);
+    pins.set(next);
+}
+
+fn upsert_pin(pins: &mut Vec<TextChatPinnedMessage>, payload: MessagePinnedPayload) {
+    let position = pins
+        .iter()
+        .position(|pin| pin.message.id == payload.message_id);
+    match (payload.pin.filter(|_| payload.pinned), position) {
+        (Some(pin), Some(index)) => pins[index] = pin,
+        (Some(pin), None) => pins.insert(0, pin),
+        (None, Some(index)) => {
+            pins.remove(index);
+        }
+        (None, None) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cheenhub_contracts::realtime::{
+        MessagePinnedPayload, TextChatMessage, TextChatPinnedMessage,
+    };
+
+    use super::upsert_pin;
+
+    fn pin(id: &str) -> TextChatPinnedMessage {
+        TextChatPinnedMessage {
+            message: TextChatMessage {
+                id: id.to_owned(),
+                server_id: "server".to_owned(),
+                room_id: "room".to_owned(),
+                author_user_id: "author".to_owned(),
+                author_nickname: "author".to_owned(),
+                author_avatar_url: None,
+                body: format!("message {id}"),
+                attachments: Vec::new(),
+                delivery_status: None,
+                created_at: "2026-10-16T10:00:00Z".to_owned(),
+                edited_at: None,
+                reply_to_message_id: None,
+                reply_to: None,
+                reactions: Vec::new(),
+                mentions: Vec::new(),
+                link_previews: Vec::new(),
+            },
+            pinned_by_user_id: "moderator".to_owned(),
+            pinned_at: "2026-10-16T10:05:00Z".to_owned(),
+        }
+    }
+
+    fn payload(id: &str, pinned: bool) -> MessagePinnedPayload {
+        MessagePinnedPayload {
+            server_id: "server".to_owned(),
+            room_id: "room".to_owned(),
+            message_id: id.to_owned(),
+            pinned,
+            pin: pinned.then(|| pin(id)),
+        }
+    }
+
+    fn ids(pins: &[TextChatPinnedMessage]) -> Vec<&str> {
+        pins.iter().map(|pin| pin.message.id.as_str()).collect()
+    }
+
+    #[test]
+    fn new_pin_goes_first_and_repeated_pin_keeps_its_place() {
+        let mut pins = vec![pin("a"), pin("b")];
+
+        upsert_pin(&mut pins, payload("c", true));
+        upsert_pin(&mut pins, payload("b", true));
+
+        assert_eq!(ids(&pins), vec!["c", "a", "b"]);
+    }
+
+    #[test]
+    fn unpinned_message_is_removed() {
+        let mut pins = vec![pin("a"), pin("b")];
+
+        upsert_pin(&mut pins, payload("a", false));
+
+        assert_eq!(ids(&pins), vec!["b"]);
+    }
+}
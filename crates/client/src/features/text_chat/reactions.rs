@@ -0,0 +1,125 @@
+//! Применение изменений реакций к загруженным сообщениям.
+
+use cheenhub_contracts::realtime::{ReactionsChangedPayload, TextChatMessage};
+use cheenhub_contracts::rest::MessageReactionSummary;
+use dioxus::prelude::*;
+
+use crate::features::realtime::RealtimeHandle;
+
+use super::realtime;
+
+/// Сливает широковещательные реакции с уже известными флагами текущего пользователя.
+///
+/// Сервер не персонализирует `reacted` в рассылке, поэтому флаг берется из
+/// прежнего состояния и меняется, только если реакцию изменил сам пользователь.
+pub(crate) fn merge_broadcast_reactions(
+    current: &[MessageReactionSummary],
+    incoming: Vec<MessageReactionSummary>,
+    own_change: Option<(&str, bool)>,
+) -> Vec<MessageReactionSummary> {
+    incoming
+        .into_iter()
+        .map(|mut reaction| {
+            reaction.reacted = match own_change {
+                Some((emoji, added)) if emoji == reaction.emoji => added,
+                _ => current
+                    .iter()
+                    .any(|saved| saved.emoji == reaction.emoji && saved.reacted),
+            };
+            reaction
+        })
+        .collect()
+}
+
+/// Применяет событие изменения реакций комнаты к загруженному сообщению.
+pub(super) fn apply_reactions_event(
+    messages: &mut Signal<Vec<TextChatMessage>>,
+    payload: ReactionsChangedPayload,
+    current_user_id: &str,
+) {
+    let mut next = messages();
+    let Some(message) = next.iter_mut().find(|m| m.id == payload.message_id) else {
+        return;
+    };
+    let own_change =
+        (payload.user_id == current_user_id).then_some((payload.emoji.as_str(), payload.added));
+    message.reactions =
+        merge_broadcast_reactions(&message.reactions, payload.reactions, own_change);
+    messages.set(next);
+}
+
+/// Создает обработчик, который ставит или снимает реакцию на сообщение комнаты.
+pub(super) fn use_room_reaction_toggle(
+    realtime: RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    mut messages: Signal<Vec<TextChatMessage>>,
+    mut status: Signal<String>,
+) -> Callback<(String, String, bool)> {
+    use_callback(move |(message_id, emoji, add): (String, String, bool)| {
+        let realtime = realtime.clone();
+        let server_id = server_id.clone();
+        let room_id = room_id.clone();
+        spawn(async move {
+            let result = if add {
+                realtime::add_text_reaction(&realtime, server_id, room_id, message_id, emoji).await
+            } else {
+                realtime::remove_text_reaction(&realtime, server_id, room_id, message_id, emoji)
+                    .await
+            };
+            match result {
+                Ok(payload) => {
+                    let mut next = messages();
+                    if let Some(message) = next.iter_mut().find(|m| m.id == payload.message_id) {
+                        message.reactions = payload.reactions;
+                        messages.set(next);
+                    }
+                }
+                Err(error) => status.set(error.to_string()),
+            }
+        });
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cheenhub_contracts::rest::MessageReactionSummary;
+
+    use super::merge_broadcast_reactions;
+
+    fn reaction(emoji: &str, count: i64, reacted: bool) -> MessageReactionSummary {
+        MessageReactionSummary {
+            emoji: emoji.to_owned(),
+            count,
+            reacted,
+        }
+    }
+
+    #[test]
+    fn keeps_own_flags_for_foreign_changes() {
+        let merged = merge_broadcast_reactions(
+            &[reaction("👍", 1, true)],
+            vec![reaction("👍", 2, false), reaction("🔥", 1, false)],
+            None,
+        );
+
+        assert_eq!(
+            merged,
+            vec![reaction("👍", 2, true), reaction("🔥", 1, false)]
+        );
+    }
+
+    #[test]
+    fn applies_own_change_to_matching_emoji() {
+        let merged = merge_broadcast_reactions(
+            &[reaction("👍", 1, true)],
+            vec![reaction("👍", 1, false), reaction("🔥", 1, false)],
+            Some(("🔥", true)),
+        );
+
+        assert_eq!(
+            merged,
+            vec![reaction("👍", 1, true), reaction("🔥", 1, true)]
+        );
+    }
+}
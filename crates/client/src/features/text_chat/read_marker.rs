@@ -0,0 +1,80 @@
+//! Отметка прочтения комнаты, пока пользователь видит последние сообщения.
+
+use std::time::Duration;
+
+use cheenhub_contracts::realtime::TextChatMessage;
+use dioxus::prelude::*;
+
+use crate::features::runtime::sleep_duration;
+
+use super::history::{HistoryState, HistoryTarget};
+use super::realtime;
+
+/// Задержка перед отметкой, чтобы не отправлять запрос на каждое событие прокрутки.
+const MARK_READ_DELAY: Duration = Duration::from_millis(600);
+
+/// Отмечает комнату прочитанной до последнего сообщения, когда список прокручен вниз.
+///
+/// Собственные сообщения не считаются непрочитанными, поэтому отметка ставится
+/// на последнее сообщение другого участника. Отметка, пришедшая с последней
+/// страницей истории, повторно не отправляется.
+pub(super) fn use_mark_room_read(
+    target: HistoryTarget,
+    current_user_id: String,
+    state: HistoryState,
+    is_near_bottom: Signal<bool>,
+) {
+    let mut marked_message_id = use_signal(|| None::<String>);
+    use_effect(move || {
+        if (state.initial_loading)() || (state.has_more_after)() || !is_near_bottom() {
+            return;
+        }
+        let Some(message_id) = latest_foreign_message_id(&(state.messages)(), &current_user_id)
+        else {
+            return;
+        };
+        if marked_message_id.peek().as_ref() == Some(&message_id)
+            || state.last_read_message_id.peek().as_ref() == Some(&message_id)
+        {
+            return;
+        }
+
+        let target = target.clone();
+        let current_user_id = current_user_id.clone();
+        spawn(async move {
+            sleep_duration(MARK_READ_DELAY).await;
+            if latest_foreign_message_id(&state.messages.peek(), &current_user_id).as_ref()
+                != Some(&message_id)
+            {
+                return;
+            }
+            match realtime::mark_room_read(
+                &target.realtime,
+                target.server_id.clone(),
+                target.room_id.clone(),
+                message_id.clone(),
+            )
+            .await
+            {
+                Ok(_) => marked_message_id.set(Some(message_id)),
+                Err(error) => debug!(
+                    %error,
+                    room_id = %target.room_id,
+                    %message_id,
+                    "failed to mark text chat room read"
+                ),
+            }
+        });
+    });
+}
+
+fn latest_foreign_message_id(
+    messages: &[TextChatMessage],
+    current_user_id: &str,
+) -> Option<String> {
+    messages
+        .iter()
+        .rev()
+        .find(|message| message.author_user_id != current_user_id)
+        .map(|message| message.id.clone())
+}
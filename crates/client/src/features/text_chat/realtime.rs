@@ -1,12 +1,13 @@
 //! Text chat realtime helpers.
 
-use base64::Engine;
-use base64::engine::general_purpose::STANDARD as BASE64;
 use cheenhub_contracts::realtime::{
-    ChatImageLoadedResponse, ChatImageUploadResponse, DeleteMessage, DeleteMessageAccepted,
-    LoadChatImage, LoadRoomHistory, MessageDeletedPayload, RealtimeEnvelope, RealtimeKind,
-    RealtimeModule, RoomHistory, SendMessage, SendMessageAccepted, TextChatKind, TextChatMessage,
-    UploadChatImage,
+    AddReaction, ChatImageLoadedResponse, DeleteMessage, DeleteMessageAccepted, EditMessage,
+    EditMessageAccepted, ListPinnedMessages, LoadChatImage, LoadRoomHistory, MarkRoomRead,
+    MentionReceivedPayload, MessageDeletedPayload, MessageEditedPayload, MessagePinnedPayload,
+    MessagePreviewReadyPayload, PinMessage, PinnedMessagesList, ReactionsChangedPayload,
+    RealtimeEnvelope, RealtimeKind, RealtimeModule, RemoveReaction, RoomHistory,
+    RoomReadUpdatedPayload, SendMessage, SendMessageAccepted, TextChatKind, TextChatMessage,
+    UnpinMessage,
 };
 use futures_channel::mpsc;
 use futures_util::StreamExt;
@@ -17,18 +18,49 @@ use crate::features::realtime::{RealtimeError, RealtimeHandle};
 /// Inbound text chat event delivered via WebSocket subscription.
 pub(crate) enum TextChatEvent {
     /// A new message was created.
-    MessageCreated(TextChatMessage),
+    Created(Box<TextChatMessage>),
     /// A message was removed by its author.
-    MessageDeleted(MessageDeletedPayload),
+    Deleted(MessageDeletedPayload),
+    /// A message body was changed by its author.
+    Edited(MessageEditedPayload),
+    /// Reactions on a message changed.
+    ReactionsChanged(ReactionsChangedPayload),
+    /// The current user was mentioned in a message.
+    Mentioned(Box<TextChatMessage>),
+    /// The current user's read marker in a room moved, possibly from another session.
+    ReadUpdated(RoomReadUpdatedPayload),
+    /// A message was pinned or unpinned in a room.
+    Pinned(Box<MessagePinnedPayload>),
+    /// Link previews for a message finished loading.
+    PreviewReady(MessagePreviewReadyPayload),
 }
 
-/// Loads latest text chat history for a room.
+/// Position of a requested history page relative to a loaded message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum HistoryCursor {
+    /// The latest messages of the room.
+    Latest,
+    /// Messages strictly older than the given message.
+    Before(String),
+    /// Messages strictly newer than the given message.
+    After(String),
+    /// A window centred on the given message.
+    Around(String),
+}
+
+/// Loads one page of text chat history for a room.
 pub(crate) async fn load_room_history(
     realtime: &RealtimeHandle,
     server_id: String,
     room_id: String,
-    before_message_id: Option<String>,
+    cursor: HistoryCursor,
 ) -> Result<RoomHistory, RealtimeError> {
+    let (before_message_id, after_message_id, around_message_id) = match cursor {
+        HistoryCursor::Latest => (None, None, None),
+        HistoryCursor::Before(message_id) => (Some(message_id), None, None),
+        HistoryCursor::After(message_id) => (None, Some(message_id), None),
+        HistoryCursor::Around(message_id) => (None, None, Some(message_id)),
+    };
     realtime
         .request_one_shot(
             RealtimeModule::TextChat,
@@ -37,6 +69,8 @@ pub(crate) async fn load_room_history(
                 server_id,
                 room_id,
                 before_message_id,
+                after_message_id,
+                around_message_id,
             },
         )
         .await
@@ -49,6 +83,7 @@ pub(crate) async fn send_text_message(
     room_id: String,
     body: String,
     attachment_id: Option<String>,
+    reply_to_message_id: Option<String>,
 ) -> Result<SendMessageAccepted, RealtimeError> {
     realtime
         .request(
@@ -59,28 +94,7 @@ pub(crate) async fn send_text_message(
                 room_id,
                 body,
                 attachment_ids: attachment_id.into_iter().collect(),
-            },
-        )
-        .await
-}
-
-/// Uploads one image attachment over realtime.
-pub(crate) async fn upload_chat_image(
-    realtime: &RealtimeHandle,
-    server_id: String,
-    room_id: String,
-    original_filename: Option<String>,
-    bytes: Vec<u8>,
-) -> Result<ChatImageUploadResponse, RealtimeError> {
-    realtime
-        .request(
-            RealtimeModule::TextChat,
-            RealtimeKind::TextChat(TextChatKind::UploadImage),
-            UploadChatImage {
-                server_id,
-                room_id,
-                original_filename,
-                data_base64: BASE64.encode(bytes),
+                reply_to_message_id,
             },
         )
         .await
@@ -122,7 +136,143 @@ pub(crate) async fn delete_text_message(
         .await
 }
 
-/// Subscribes to inbound text chat events (creates and deletes) for this tab.
+/// Replaces the body of one of the user's own messages.
+pub(crate) async fn edit_text_message(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    message_id: String,
+    body: String,
+) -> Result<EditMessageAccepted, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::EditMessage),
+            EditMessage {
+                server_id,
+                room_id,
+                message_id,
+                body,
+            },
+        )
+        .await
+}
+
+/// Adds the user's emoji reaction to a room message.
+pub(crate) async fn add_text_reaction(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    message_id: String,
+    emoji: String,
+) -> Result<ReactionsChangedPayload, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::AddReaction),
+            AddReaction {
+                server_id,
+                room_id,
+                message_id,
+                emoji,
+            },
+        )
+        .await
+}
+
+/// Removes the user's emoji reaction from a room message.
+pub(crate) async fn remove_text_reaction(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    message_id: String,
+    emoji: String,
+) -> Result<ReactionsChangedPayload, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::RemoveReaction),
+            RemoveReaction {
+                server_id,
+                room_id,
+                message_id,
+                emoji,
+            },
+        )
+        .await
+}
+
+/// Moves the user's read marker in a room up to the given message.
+pub(crate) async fn mark_room_read(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    message_id: String,
+) -> Result<RoomReadUpdatedPayload, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::MarkRoomRead),
+            MarkRoomRead {
+                server_id,
+                room_id,
+                message_id,
+            },
+        )
+        .await
+}
+
+/// Pins or unpins a room message.
+pub(crate) async fn set_message_pinned(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    message_id: String,
+    pinned: bool,
+) -> Result<MessagePinnedPayload, RealtimeError> {
+    if pinned {
+        realtime
+            .request(
+                RealtimeModule::TextChat,
+                RealtimeKind::TextChat(TextChatKind::PinMessage),
+                PinMessage {
+                    server_id,
+                    room_id,
+                    message_id,
+                },
+            )
+            .await
+    } else {
+        realtime
+            .request(
+                RealtimeModule::TextChat,
+                RealtimeKind::TextChat(TextChatKind::UnpinMessage),
+                UnpinMessage {
+                    server_id,
+                    room_id,
+                    message_id,
+                },
+            )
+            .await
+    }
+}
+
+/// Loads pinned messages of a room, most recently pinned first.
+pub(crate) async fn list_pinned_messages(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+) -> Result<PinnedMessagesList, RealtimeError> {
+    realtime
+        .request_one_shot(
+            RealtimeModule::TextChat,
+            RealtimeKind::TextChat(TextChatKind::ListPinnedMessages),
+            ListPinnedMessages { server_id, room_id },
+        )
+        .await
+}
+
+/// Subscribes to inbound text chat events (messages, reactions and read markers) for this tab.
 pub(crate) fn subscribe_text_chat(
     realtime: &RealtimeHandle,
 ) -> mpsc::UnboundedReceiver<TextChatEvent> {
@@ -151,11 +301,39 @@ fn decode_text_chat_event(envelope: RealtimeEnvelope) -> Option<TextChatEvent> {
     match envelope.kind {
         RealtimeKind::TextChat(TextChatKind::MessageCreated) => {
             let message = serde_json::from_value::<TextChatMessage>(envelope.payload).ok()?;
-            Some(TextChatEvent::MessageCreated(message))
+            Some(TextChatEvent::Created(Box::new(message)))
         }
         RealtimeKind::TextChat(TextChatKind::MessageDeleted) => {
             let payload = serde_json::from_value::<MessageDeletedPayload>(envelope.payload).ok()?;
-            Some(TextChatEvent::MessageDeleted(payload))
+            Some(TextChatEvent::Deleted(payload))
+        }
+        RealtimeKind::TextChat(TextChatKind::MessageEdited) => {
+            let payload = serde_json::from_value::<MessageEditedPayload>(envelope.payload).ok()?;
+            Some(TextChatEvent::Edited(payload))
+        }
+        RealtimeKind::TextChat(TextChatKind::ReactionsChanged) => {
+            let payload =
+                serde_json::from_value::<ReactionsChangedPayload>(envelope.payload).ok()?;
+            Some(TextChatEvent::ReactionsChanged(payload))
+        }
+        RealtimeKind::TextChat(TextChatKind::MentionReceived) => {
+            let payload =
+                serde_json::from_value::<MentionReceivedPayload>(envelope.payload).ok()?;
+            Some(TextChatEvent::Mentioned(Box::new(payload.message)))
+        }
+        RealtimeKind::TextChat(TextChatKind::RoomReadUpdated) => {
+            let payload =
+                serde_json::from_value::<RoomReadUpdatedPayload>(envelope.payload).ok()?;
+            Some(TextChatEvent::ReadUpdated(payload))
+        }
+        RealtimeKind::TextChat(TextChatKind::MessagePinned) => {
+            let payload = serde_json::from_value::<MessagePinnedPayload>(envelope.payload).ok()?;
+            Some(TextChatEvent::Pinned(Box::new(payload)))
+        }
+        RealtimeKind::TextChat(TextChatKind::MessagePreviewReady) => {
+            let payload =
+                serde_json::from_value::<MessagePreviewReadyPayload>(envelope.payload).ok()?;
+            Some(TextChatEvent::PreviewReady(payload))
         }
         _ => None,
     }
@@ -1,8 +1,9 @@
 //! Состояние формы сообщения, ограниченное одной комнатой.
 
+use cheenhub_contracts::realtime::TextChatMessage;
 use dioxus::prelude::*;
 
-use super::pending_attachment::PendingImageAttachment;
+use super::pending_attachment::PendingChatAttachment;
 
 /// Общее состояние всех представлений формы одной комнаты.
 #[derive(Clone, Copy)]
@@ -18,7 +19,9 @@ pub(crate) struct RoomComposeState {
     /// Признак асинхронного чтения изображения из буфера обмена.
     pub(crate) is_reading_clipboard: Signal<bool>,
     /// Изображение, ожидающее отправки.
-    pub(crate) pending_attachment: Signal<Option<PendingImageAttachment>>,
+    pub(crate) pending_attachment: Signal<Option<PendingChatAttachment>>,
+    /// Сообщение, на которое пользователь сейчас отвечает.
+    pub(crate) reply_to: Signal<Option<TextChatMessage>>,
 }
 
 /// Создаёт состояние формы, живущее в keyed-экземпляре комнаты.
@@ -30,5 +33,6 @@ pub(crate) fn use_room_compose_state() -> RoomComposeState {
         is_selecting_image: use_signal(|| false),
         is_reading_clipboard: use_signal(|| false),
         pending_attachment: use_signal(|| None),
+        reply_to: use_signal(|| None),
     }
 }
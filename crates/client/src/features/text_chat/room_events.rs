@@ -0,0 +1,95 @@
+//! Подписка панели комнаты на realtime-события текстового чата.
+
+use std::time::Duration;
+
+use cheenhub_contracts::realtime::TextChatMessage;
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::features::realtime::RealtimeHandle;
+use crate::features::runtime::sleep_duration;
+
+use super::messages::{append_message, apply_link_previews, apply_message_edit, remove_message};
+use super::reactions::apply_reactions_event;
+use super::realtime::{self, TextChatEvent};
+use super::scroll::ScrollCommand;
+
+/// Сигналы панели, которые обновляются входящими событиями комнаты.
+#[derive(Clone, Copy)]
+pub(super) struct RoomEventState {
+    pub(super) messages: Signal<Vec<TextChatMessage>>,
+    pub(super) appearing_message_ids: Signal<Vec<String>>,
+    pub(super) removing_message_ids: Signal<Vec<String>>,
+    pub(super) is_near_bottom: Signal<bool>,
+    pub(super) has_more_after: Signal<bool>,
+    pub(super) pending_scroll: Signal<Option<ScrollCommand>>,
+}
+
+/// Применяет события текстового чата, относящиеся к комнате `room_id`.
+pub(super) fn spawn_room_event_listener(
+    realtime: RealtimeHandle,
+    room_id: String,
+    current_user_id: String,
+    state: RoomEventState,
+) {
+    let RoomEventState {
+        mut messages,
+        mut appearing_message_ids,
+        mut removing_message_ids,
+        is_near_bottom,
+        has_more_after,
+        mut pending_scroll,
+    } = state;
+    spawn(async move {
+        let mut receiver = realtime::subscribe_text_chat(&realtime);
+        while let Some(event) = receiver.next().await {
+            match event {
+                TextChatEvent::Created(message) => {
+                    // Окно в середине истории догрузит новое сообщение при прокрутке вниз.
+                    if message.room_id == room_id
+                        && !has_more_after()
+                        && append_message(&mut messages, &mut appearing_message_ids, *message)
+                        && is_near_bottom()
+                    {
+                        pending_scroll.set(Some(ScrollCommand::Bottom));
+                    }
+                }
+                TextChatEvent::Edited(payload) => {
+                    if payload.room_id == room_id {
+                        apply_message_edit(
+                            &mut messages,
+                            &payload.message_id,
+                            payload.body,
+                            payload.edited_at,
+                            payload.mentions,
+                        );
+                    }
+                }
+                TextChatEvent::Deleted(payload) => {
+                    if payload.room_id == room_id {
+                        let message_id = payload.message_id.clone();
+                        removing_message_ids.write().push(message_id.clone());
+                        spawn(async move {
+                            sleep_duration(Duration::from_millis(220)).await;
+                            remove_message(&mut messages, &message_id);
+                            removing_message_ids.write().retain(|id| id != &message_id);
+                        });
+                    }
+                }
+                TextChatEvent::PreviewReady(payload) => {
+                    if payload.room_id == room_id {
+                        apply_link_previews(&mut messages, &payload.message_id, payload.previews);
+                    }
+                }
+                TextChatEvent::Mentioned(_)
+                | TextChatEvent::ReadUpdated(_)
+                | TextChatEvent::Pinned(_) => {}
+                TextChatEvent::ReactionsChanged(payload) => {
+                    if payload.room_id == room_id {
+                        apply_reactions_event(&mut messages, payload, &current_user_id);
+                    }
+                }
+            }
+        }
+    });
+}
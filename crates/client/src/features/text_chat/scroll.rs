@@ -1,14 +1,20 @@
 //! Поведение прокрутки текстового чата.
 
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::Duration;
 
 use dioxus::prelude::dioxus_elements::geometry::PixelsVector2D;
 use dioxus::prelude::*;
 
+use crate::features::runtime::sleep_duration;
+
 const BOTTOM_SCROLL_THRESHOLD: f64 = 24.0;
 const OLDER_PAGE_SCROLL_THRESHOLD: f64 = 48.0;
+const NEWER_PAGE_SCROLL_THRESHOLD: f64 = 48.0;
+const MESSAGE_HIGHLIGHT_DURATION: Duration = Duration::from_millis(1600);
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub(crate) enum ScrollCommand {
     /// Мгновенно прокручивает список к последнему сообщению.
     Bottom,
@@ -16,26 +22,120 @@ pub(crate) enum ScrollCommand {
     SmoothBottom,
     /// Сохраняет видимую позицию после добавления сообщений выше текущего окна.
     Preserve { offset_y: f64, height: f64 },
+    /// Плавно прокручивает список так, чтобы сообщение оказалось по центру.
+    Message(Rc<MountedData>),
+}
+
+/// Смонтированные строки сообщений комнаты, к которым можно перейти по цитате.
+#[derive(Clone, Copy)]
+pub(super) struct MessageAnchors {
+    elements: Signal<HashMap<String, Rc<MountedData>>>,
+    highlighted: Signal<Option<String>>,
+    pending_jump: Signal<Option<String>>,
+    pending_scroll: Signal<Option<ScrollCommand>>,
+    load_around: Callback<String>,
+}
+
+impl MessageAnchors {
+    /// Создает реестр строк, который отправляет команды прокрутки в `pending_scroll`.
+    ///
+    /// `load_around` вызывается для сообщений вне текущего окна истории.
+    pub(super) fn new(
+        pending_scroll: Signal<Option<ScrollCommand>>,
+        load_around: Callback<String>,
+    ) -> Self {
+        Self {
+            elements: Signal::new(HashMap::new()),
+            highlighted: Signal::new(None),
+            pending_jump: Signal::new(None),
+            pending_scroll,
+            load_around,
+        }
+    }
+
+    /// Запоминает смонтированную строку сообщения и завершает ожидающий переход к ней.
+    pub(super) fn register(mut self, message_id: String, element: Rc<MountedData>) {
+        self.elements.write().insert(message_id.clone(), element);
+        if self.pending_jump.peek().as_deref() == Some(message_id.as_str()) {
+            self.pending_jump.set(None);
+            self.jump_to(&message_id);
+        }
+    }
+
+    /// Забывает строку сообщения после размонтирования.
+    pub(super) fn unregister(mut self, message_id: &str) {
+        self.elements.write().remove(message_id);
+    }
+
+    /// Подсвечено ли сообщение после перехода по цитате.
+    pub(super) fn is_highlighted(&self, message_id: &str) -> bool {
+        (self.highlighted)().as_deref() == Some(message_id)
+    }
+
+    /// Прокручивает список к сообщению и ненадолго подсвечивает его.
+    ///
+    /// Если сообщение не загружено в текущее окно истории, загружает окно
+    /// вокруг него и выполняет переход, когда строка смонтируется.
+    pub(super) fn jump_to(mut self, message_id: &str) {
+        let Some(element) = self.elements.peek().get(message_id).cloned() else {
+            self.pending_jump.set(Some(message_id.to_owned()));
+            self.load_around.call(message_id.to_owned());
+            return;
+        };
+        self.pending_scroll
+            .set(Some(ScrollCommand::Message(element)));
+        self.highlighted.set(Some(message_id.to_owned()));
+        let message_id = message_id.to_owned();
+        spawn(async move {
+            sleep_duration(MESSAGE_HIGHLIGHT_DURATION).await;
+            if self.highlighted.peek().as_deref() == Some(message_id.as_str()) {
+                self.highlighted.set(None);
+            }
+        });
+    }
+}
+
+/// Состояние двунаправленной подгрузки окна истории при прокрутке к его краям.
+#[derive(Clone, Copy)]
+pub(super) struct HistoryPaging {
+    pub(super) has_more_before: Signal<bool>,
+    pub(super) has_more_after: Signal<bool>,
+    pub(super) older_loading: Signal<bool>,
+    pub(super) newer_loading: Signal<bool>,
+    pub(super) initial_loading: Signal<bool>,
+    pub(super) load_older: Callback,
+    pub(super) load_newer: Callback,
 }
 
 pub(super) async fn update_scroll_state(
     element: Rc<MountedData>,
     is_near_bottom: Signal<bool>,
-    has_more: Signal<bool>,
-    older_loading: Signal<bool>,
-    initial_loading: Signal<bool>,
-    load_older: Callback,
+    paging: HistoryPaging,
 ) {
     update_near_bottom_state(element.clone(), is_near_bottom).await;
+    if (paging.initial_loading)() {
+        return;
+    }
     let Ok(offset) = element.get_scroll_offset().await else {
         return;
     };
     if offset.y <= OLDER_PAGE_SCROLL_THRESHOLD
-        && has_more()
-        && !older_loading()
-        && !initial_loading()
+        && (paging.has_more_before)()
+        && !(paging.older_loading)()
     {
-        load_older.call(());
+        paging.load_older.call(());
+    }
+    if !(paging.has_more_after)() || (paging.newer_loading)() {
+        return;
+    }
+    let Ok(scroll_size) = element.get_scroll_size().await else {
+        return;
+    };
+    let Ok(rect) = element.get_client_rect().await else {
+        return;
+    };
+    if scroll_size.height - rect.size.height - offset.y <= NEWER_PAGE_SCROLL_THRESHOLD {
+        paging.load_newer.call(());
     }
 }
 
@@ -102,5 +202,24 @@ pub(crate) async fn apply_scroll_command(element: Rc<MountedData>, command: Scro
                 )
                 .await;
         }
+        ScrollCommand::Message(target) => {
+            let Ok(offset) = element.get_scroll_offset().await else {
+                return;
+            };
+            let Ok(list_rect) = element.get_client_rect().await else {
+                return;
+            };
+            let Ok(target_rect) = target.get_client_rect().await else {
+                return;
+            };
+            let next_offset = offset.y + (target_rect.origin.y - list_rect.origin.y)
+                - (list_rect.size.height - target_rect.size.height) / 2.0;
+            let _ = element
+                .scroll(
+                    PixelsVector2D::new(0.0, next_offset.max(0.0)),
+                    ScrollBehavior::Smooth,
+                )
+                .await;
+        }
     }
 }
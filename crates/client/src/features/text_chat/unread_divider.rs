@@ -0,0 +1,17 @@
+//! Визуальный разделитель начала непрочитанных сообщений.
+
+use dioxus::prelude::*;
+
+/// Рендерит линию «Новые сообщения» перед первым непрочитанным сообщением.
+#[component]
+pub(super) fn ChatUnreadDivider() -> Element {
+    rsx! {
+        div { class: "flex items-center gap-3", role: "separator", "aria-label": "Новые сообщения",
+            div { class: "h-px flex-1 bg-red-400/40" }
+            span { class: "text-[11px] font-semibold uppercase tracking-[0.12em] text-red-300",
+                "Новые сообщения"
+            }
+            div { class: "h-px flex-1 bg-red-400/40" }
+        }
+    }
+}
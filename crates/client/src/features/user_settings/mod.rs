@@ -6,7 +6,9 @@ mod password_section;
 mod profile_section;
 mod scope;
 mod security_section;
+mod sound_activation;
 mod sound_devices;
+mod sound_processing;
 mod sound_section;
 mod styles;
 mod system_section;
@@ -0,0 +1,162 @@
+//! Выбор режима активации микрофона и настройки push-to-talk.
+
+use dioxus::prelude::*;
+
+use crate::features::microphone::{
+    MAX_PUSH_TO_TALK_RELEASE_DELAY_MS, MicrophoneActivationMode, MicrophoneHandle,
+    PUSH_TO_TALK_KEYS_HINT, is_supported_push_to_talk_key, push_to_talk_key_label,
+};
+
+use super::styles::parse_u32_range;
+
+const ACTIVATION_MODES: [(MicrophoneActivationMode, &str, &str); 4] = [
+    (
+        MicrophoneActivationMode::AlwaysActive,
+        "Всегда включен",
+        "Микрофон активен постоянно.",
+    ),
+    (
+        MicrophoneActivationMode::VoiceActivated,
+        "Активация по голосу",
+        "Включение при превышении порога.",
+    ),
+    (
+        MicrophoneActivationMode::PushToTalk,
+        "Push-to-talk",
+        "Активация по удержанию клавиши.",
+    ),
+    (
+        MicrophoneActivationMode::PushToMute,
+        "Push-to-mute",
+        "Отключение по удержанию клавиши.",
+    ),
+];
+
+/// Кнопки выбора режима активации микрофона.
+#[component]
+pub(super) fn ActivationModeSelector() -> Element {
+    let mic = use_context::<MicrophoneHandle>();
+    let activation_mode = mic.activation_mode();
+
+    rsx! {
+        div {
+            label { class: "mb-3 block text-[13px] font-medium text-zinc-300", "Режим активации" }
+            div { class: "grid gap-3 sm:grid-cols-2 xl:grid-cols-4",
+                for (mode , title , description) in ACTIVATION_MODES {
+                    button {
+                        key: "{title}",
+                        r#type: "button",
+                        class: activation_button_class(activation_mode == mode),
+                        onclick: {
+                            let mic = mic.clone();
+                            move |_| mic.set_activation_mode(mode)
+                        },
+                        div { class: "font-medium", "{title}" }
+                        div { class: "mt-1 text-[12px] leading-4 text-zinc-400", "{description}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Назначение клавиши и release tail для push-to-talk и push-to-mute.
+#[component]
+pub(super) fn PushToTalkSettings() -> Element {
+    let mic = use_context::<MicrophoneHandle>();
+    let mut capturing = use_signal(|| false);
+    let mut rejected_key = use_signal(|| None::<String>);
+    let activation_mode = mic.activation_mode();
+    let open = matches!(
+        activation_mode,
+        MicrophoneActivationMode::PushToTalk | MicrophoneActivationMode::PushToMute
+    );
+    let key_label = push_to_talk_key_label(&mic.push_to_talk_key());
+    let release_delay_ms = mic.push_to_talk_release_delay_ms();
+    let key_mic = mic.clone();
+
+    rsx! {
+        div {
+            "data-open": if open { "true" } else { "false" },
+            class: "mt-0 max-h-0 overflow-hidden opacity-0 transition-[max-height,opacity,transform,margin] duration-300 ease-out -translate-y-2 pointer-events-none data-[open=true]:mt-4 data-[open=true]:max-h-[320px] data-[open=true]:translate-y-0 data-[open=true]:opacity-100 data-[open=true]:pointer-events-auto",
+            div { class: "rounded-2xl border border-zinc-800 bg-zinc-900/45 p-4",
+                div { class: "mb-4",
+                    h4 { class: "text-[14px] font-semibold text-zinc-100", "Клавиша активации" }
+                    p { class: "mt-1 text-[12px] leading-5 text-zinc-500",
+                        "В desktop-клиенте клавиша работает и когда окно не в фокусе."
+                    }
+                }
+
+                div { class: "space-y-4",
+                    div { class: "flex flex-wrap items-center gap-3",
+                        button {
+                            r#type: "button",
+                            class: key_button_class(capturing()),
+                            onclick: move |_| {
+                                rejected_key.set(None);
+                                capturing.set(true);
+                            },
+                            onblur: move |_| capturing.set(false),
+                            onkeydown: move |event: Event<KeyboardData>| {
+                                if !capturing() {
+                                    return;
+                                }
+                                event.prevent_default();
+                                let code = event.code().to_string();
+                                if code == "Escape" {
+                                    capturing.set(false);
+                                } else if is_supported_push_to_talk_key(&code) {
+                                    key_mic.set_push_to_talk_key(&code);
+                                    capturing.set(false);
+                                } else {
+                                    rejected_key.set(Some(code));
+                                }
+                            },
+                            if capturing() { "Нажмите клавишу…" } else { "{key_label}" }
+                        }
+                        span { class: "text-[12px] text-zinc-500",
+                            if capturing() { "Esc — отмена" } else { "Нажмите, чтобы назначить другую клавишу." }
+                        }
+                    }
+                    if let Some(code) = rejected_key() {
+                        p { class: "text-[12px] leading-5 text-amber-200",
+                            "Клавишу {code} нельзя назначить: используйте {PUSH_TO_TALK_KEYS_HINT}."
+                        }
+                    }
+
+                    div {
+                        div { class: "mb-2 flex items-center justify-between gap-3",
+                            label { class: "text-[12px] font-medium text-zinc-300", "Задержка отключения после отпускания" }
+                            span { class: "shrink-0 text-[12px] text-zinc-500", "{release_delay_ms} мс" }
+                        }
+                        input {
+                            r#type: "range",
+                            min: "0",
+                            max: "{MAX_PUSH_TO_TALK_RELEASE_DELAY_MS}",
+                            step: "10",
+                            value: release_delay_ms,
+                            oninput: move |event| mic.set_push_to_talk_release_delay_ms(parse_u32_range(&event.value(), release_delay_ms, MAX_PUSH_TO_TALK_RELEASE_DELAY_MS)),
+                            class: "w-full cursor-pointer accent-blue-500",
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn activation_button_class(active: bool) -> &'static str {
+    if active {
+        "relative rounded-2xl border border-accent/30 bg-accent/10 px-4 py-4 text-left transition hover:border-blue-400/45"
+    } else {
+        "relative rounded-2xl border border-zinc-700 bg-zinc-950 px-4 py-4 text-left transition hover:border-zinc-500"
+    }
+}
+
+fn key_button_class(capturing: bool) -> &'static str {
+    if capturing {
+        "h-10 min-w-[120px] rounded-xl border border-accent/60 bg-accent/10 px-4 text-[13px] font-semibold text-blue-100 outline-none ring-4 ring-accent/10 transition"
+    } else {
+        "h-10 min-w-[120px] rounded-xl border border-zinc-700 bg-zinc-950 px-4 text-[13px] font-semibold text-zinc-100 outline-none transition hover:border-zinc-500"
+    }
+}
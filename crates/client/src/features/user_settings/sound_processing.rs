@@ -0,0 +1,104 @@
+//! Переключатели обработки звука микрофона.
+
+use dioxus::prelude::*;
+
+use crate::features::microphone::{MicrophoneHandle, MicrophoneProcessingConfig};
+
+type ProcessingToggle = (
+    &'static str,
+    &'static str,
+    fn(&MicrophoneProcessingConfig) -> bool,
+    fn(&mut MicrophoneProcessingConfig, bool),
+);
+
+const PROCESSING_TOGGLES: [ProcessingToggle; 5] = [
+    (
+        "Шумоподавление",
+        "Приглушает постоянный фон: вентилятор, гул, шум улицы.",
+        |processing| processing.noise_suppression,
+        |processing, enabled| processing.noise_suppression = enabled,
+    ),
+    (
+        "Подавление эха",
+        "Приглушает микрофон, пока говорят собеседники, а вы молчите. Когда говорите одновременно, эхо из динамиков может быть слышно.",
+        |processing| processing.echo_suppression,
+        |processing, enabled| processing.echo_suppression = enabled,
+    ),
+    (
+        "Автоматическая регулировка громкости",
+        "Выравнивает громкость речи, если вы отходите от микрофона.",
+        |processing| processing.auto_gain_control,
+        |processing, enabled| processing.auto_gain_control = enabled,
+    ),
+    (
+        "Срез низких частот (80 Гц)",
+        "Срезает гул и удары по столу ниже голосового диапазона.",
+        |processing| processing.high_pass_filter,
+        |processing, enabled| processing.high_pass_filter = enabled,
+    ),
+    (
+        "Шумовой порог",
+        "Полностью глушит тихие звуки между фразами.",
+        |processing| processing.noise_gate,
+        |processing, enabled| processing.noise_gate = enabled,
+    ),
+];
+
+/// Настройки стадий обработки звука микрофона.
+#[component]
+pub(super) fn AudioProcessingSettings() -> Element {
+    let mic = use_context::<MicrophoneHandle>();
+    let processing = mic.processing();
+
+    rsx! {
+        div { class: "mt-4",
+            label { class: "mb-3 block text-[13px] font-medium text-zinc-300", "Обработка звука" }
+            div { class: "grid gap-3 md:grid-cols-2",
+                for (title , description , enabled , update) in PROCESSING_TOGGLES {
+                    label {
+                        key: "{title}",
+                        class: "flex cursor-pointer items-center justify-between gap-4 rounded-2xl border border-zinc-800 bg-zinc-900/45 p-4",
+                        span { class: "min-w-0",
+                            span { class: "block text-[14px] font-medium text-zinc-100", "{title}" }
+                            span { class: "mt-1 block text-[12px] leading-5 text-zinc-500", "{description}" }
+                        }
+                        input {
+                            r#type: "checkbox",
+                            class: "peer sr-only",
+                            checked: enabled(&processing),
+                            onchange: {
+                                let mic = mic.clone();
+                                move |event: Event<FormData>| {
+                                    let mut next = processing;
+                                    update(&mut next, event.checked());
+                                    mic.set_processing(next);
+                                }
+                            },
+                        }
+                        span {
+                            "aria-hidden": "true",
+                            class: toggle_class(enabled(&processing)),
+                            span { class: knob_class(enabled(&processing)) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn toggle_class(enabled: bool) -> &'static str {
+    if enabled {
+        "relative inline-flex h-6 w-11 shrink-0 items-center rounded-full bg-blue-500 transition"
+    } else {
+        "relative inline-flex h-6 w-11 shrink-0 items-center rounded-full bg-zinc-700 transition"
+    }
+}
+
+fn knob_class(enabled: bool) -> &'static str {
+    if enabled {
+        "absolute left-6 h-4 w-4 rounded-full bg-white shadow-sm transition"
+    } else {
+        "absolute left-1 h-4 w-4 rounded-full bg-white shadow-sm transition"
+    }
+}
@@ -13,7 +13,9 @@ use crate::features::microphone::{
     MicrophoneStatus, enumerate_audio_input_devices, request_microphone_permission,
 };
 
+use super::sound_activation::{ActivationModeSelector, PushToTalkSettings};
 use super::sound_devices::{input_device_widget, output_device_widget};
+use super::sound_processing::AudioProcessingSettings;
 use super::styles::{parse_percent, parse_percent_range};
 
 /// Renders sound input, output, and voice activation controls.
@@ -35,6 +37,7 @@ pub(crate) fn SoundSettingsSection() -> Element {
 
     let input_volume = mic.input_volume_percent();
     let output_volume = playback.output_volume_percent();
+    let jitter_buffer_min_us = playback.jitter_buffer_min_us();
     let jitter_buffer_us = playback.jitter_buffer_us();
     let activation_mode = mic.activation_mode();
     let activation_level = mic.vad_threshold_percent();
@@ -121,9 +124,8 @@ pub(crate) fn SoundSettingsSection() -> Element {
     };
 
     let mic_volume_change = mic.clone();
+    let playback_jitter_min_change = playback.clone();
     let playback_jitter_change = playback.clone();
-    let mic_always_active = mic.clone();
-    let mic_voice_activation = mic.clone();
     let mic_threshold_change = mic.clone();
 
     rsx! {
@@ -165,44 +167,13 @@ pub(crate) fn SoundSettingsSection() -> Element {
                         )}
                     }
                     {volume_slider("Громкость вывода", output_volume, move |value| playback.set_output_volume_percent(value))}
-                    {jitter_buffer_slider(jitter_buffer_us, move |value| playback_jitter_change.set_jitter_buffer_us(value))}
+                    {jitter_buffer_slider("Минимальный буфер входящего звука", jitter_buffer_min_us, move |value| playback_jitter_min_change.set_jitter_buffer_min_us(value))}
+                    {jitter_buffer_slider("Максимальный буфер входящего звука", jitter_buffer_us, move |value| playback_jitter_change.set_jitter_buffer_us(value))}
                 }
             }
 
             div { class: "mt-4",
-                div {
-                    label { class: "mb-3 block text-[13px] font-medium text-zinc-300", "Режим активации" }
-                    div { class: "grid gap-3 md:grid-cols-3",
-                        button {
-                            r#type: "button",
-                            class: activation_button_class(activation_mode == MicrophoneActivationMode::AlwaysActive),
-                            onclick: move |_| mic_always_active.set_activation_mode(MicrophoneActivationMode::AlwaysActive),
-                            div { class: "font-medium", "Всегда включен" }
-                            div { class: "mt-1 text-[12px] leading-4 text-zinc-400", "Микрофон активен постоянно." }
-                        }
-
-                        button {
-                            r#type: "button",
-                            class: activation_button_class(activation_mode == MicrophoneActivationMode::VoiceActivated),
-                            onclick: move |_| mic_voice_activation.set_activation_mode(MicrophoneActivationMode::VoiceActivated),
-                            div { class: "font-medium", "Активация по голосу" }
-                            div { class: "mt-1 text-[12px] leading-4 text-zinc-400", "Включение при превышении порога." }
-                        }
-
-                        div { class: "group relative",
-                            button {
-                                r#type: "button",
-                                disabled: true,
-                                class: "w-full cursor-not-allowed rounded-2xl border border-zinc-800 bg-zinc-900/35 px-4 py-4 text-left opacity-60",
-                                div { class: "font-medium", "Push-to-talk" }
-                                div { class: "mt-1 text-[12px] leading-4 text-zinc-500", "Активация по удержанию клавиши." }
-                            }
-                            div { class: "pointer-events-none absolute left-1/2 top-full z-10 mt-2 w-[calc(100vw-32px)] max-w-xs -translate-x-1/2 rounded-xl border border-zinc-800 bg-zinc-950/95 px-3 py-2 text-center text-[12px] text-zinc-200 opacity-0 shadow-[0_16px_40px_rgba(0,0,0,.45)] backdrop-blur-xl transition-opacity duration-200 group-hover:opacity-100 sm:w-max sm:max-w-none",
-                                "в разработке"
-                            }
-                        }
-                    }
-                }
+                ActivationModeSelector {}
 
                 div {
                     "data-open": if activation_mode == MicrophoneActivationMode::VoiceActivated { "true" } else { "false" },
@@ -253,7 +224,11 @@ pub(crate) fn SoundSettingsSection() -> Element {
                         }
                     }
                 }
+
+                PushToTalkSettings {}
             }
+
+            AudioProcessingSettings {}
         }
     }
 }
@@ -386,7 +361,11 @@ fn volume_slider(
     }
 }
 
-fn jitter_buffer_slider(value_us: u32, mut on_change: impl FnMut(u32) + 'static) -> Element {
+fn jitter_buffer_slider(
+    label: &'static str,
+    value_us: u32,
+    mut on_change: impl FnMut(u32) + 'static,
+) -> Element {
     let value_ms = f64::from(value_us) / 1_000.0;
     let value_label = format_jitter_buffer_ms(value_us);
     let min_ms = f64::from(MIN_JITTER_BUFFER_US) / 1_000.0;
@@ -396,7 +375,7 @@ fn jitter_buffer_slider(value_us: u32, mut on_change: impl FnMut(u32) + 'static)
     rsx! {
         div {
             div { class: "mb-2 flex items-center justify-between gap-3",
-                label { class: "text-[13px] font-medium text-zinc-300", "Буфер входящего звука" }
+                label { class: "text-[13px] font-medium text-zinc-300", "{label}" }
                 span { class: "shrink-0 text-[12px] text-zinc-500", "{value_label} мс" }
             }
             input {
@@ -438,14 +417,6 @@ fn format_jitter_buffer_ms(value_us: u32) -> String {
     }
 }
 
-fn activation_button_class(active: bool) -> &'static str {
-    if active {
-        "relative rounded-2xl border border-accent/30 bg-accent/10 px-4 py-4 text-left transition hover:border-blue-400/45"
-    } else {
-        "relative rounded-2xl border border-zinc-700 bg-zinc-950 px-4 py-4 text-left transition hover:border-zinc-500"
-    }
-}
-
 fn level_percent(rms: f32) -> u32 {
     ((rms.max(0.0) * 1000.0).round() as u32).min(100)
 }
@@ -22,5 +22,10 @@ pub(crate) fn parse_percent(value: &str, fallback: i32) -> i32 {
 
 /// Parses a percentage-like value and clamps it to a custom slider range.
 pub(crate) fn parse_percent_range(value: &str, fallback: u32, max: u32) -> u32 {
+    parse_u32_range(value, fallback, max)
+}
+
+/// Parses an unsigned slider value and caps it at `max`.
+pub(crate) fn parse_u32_range(value: &str, fallback: u32, max: u32) -> u32 {
     value.parse::<u32>().unwrap_or(fallback).min(max)
 }
@@ -18,6 +18,9 @@ const MIME_VP9: &str = "video/x-vnd.on2.vp9";
 const CONFIGURE_FLAG_ENCODE: i32 = 1;
 const BUFFER_FLAG_KEY_FRAME: i32 = 1;
 const BUFFER_FLAG_CODEC_CONFIG: i32 = 2;
+/// Резервный интервал ключевых кадров; обычно их запрашивают получатели.
+const FALLBACK_KEY_FRAME_INTERVAL_SECONDS: i32 = 10;
+const PARAMETER_KEY_REQUEST_SYNC_FRAME: &str = "request-sync";
 
 /// Ссылка на входной `Surface` Android-кодировщика.
 #[derive(Clone)]
@@ -89,7 +92,12 @@ impl AndroidSurfaceVideoEncoder {
             .map_err(|error| media_error("Не удалось создать MediaFormat для VP9", error))?;
         set_integer(&mut env, &format, "bitrate", config.bitrate_bps as i32)?;
         set_integer(&mut env, &format, "frame-rate", config.frame_rate as i32)?;
-        set_integer(&mut env, &format, "i-frame-interval", 2)?;
+        set_integer(
+            &mut env,
+            &format,
+            "i-frame-interval",
+            FALLBACK_KEY_FRAME_INTERVAL_SECONDS,
+        )?;
         set_integer(&mut env, &format, "color-format", 0x7F00_0789)?; // COLOR_FormatSurface
 
         env.call_method(
@@ -144,6 +152,37 @@ impl AndroidSurfaceVideoEncoder {
         self.surface.clone()
     }
 
+    /// Просит MediaCodec закодировать следующий кадр как ключевой.
+    pub(crate) fn request_key_frame(&self) -> Result<(), VideoEncodingError> {
+        if self.closed.get() {
+            return Ok(());
+        }
+        let mut env = self.vm.attach_current_thread().map_err(|error| {
+            media_error("Не удалось подключить поток запроса ключевого кадра", error)
+        })?;
+        let parameters = env
+            .new_object("android/os/Bundle", "()V", &[])
+            .map_err(|error| media_error("Не удалось создать параметры MediaCodec", error))?;
+        let key = env
+            .new_string(PARAMETER_KEY_REQUEST_SYNC_FRAME)
+            .map_err(|error| media_error("Не удалось создать ключ параметра MediaCodec", error))?;
+        env.call_method(
+            &parameters,
+            "putInt",
+            "(Ljava/lang/String;I)V",
+            &[JValue::Object(&key), JValue::Int(0)],
+        )
+        .map_err(|error| media_error("Не удалось заполнить параметры MediaCodec", error))?;
+        env.call_method(
+            self.codec.as_obj(),
+            "setParameters",
+            "(Landroid/os/Bundle;)V",
+            &[JValue::Object(&parameters)],
+        )
+        .map_err(|error| media_error("VP9 MediaCodec отклонил запрос ключевого кадра", error))?;
+        Ok(())
+    }
+
     /// Извлекает все готовые encoded buffers без блокировки вызывающего потока.
     pub(crate) fn drain(&self) -> Result<(), VideoEncodingError> {
         if self.closed.get() {
@@ -0,0 +1,47 @@
+//! Говорящие участники, которых relay оценивает по уровням звука голосовых кадров.
+
+use cheenhub_contracts::realtime::{
+    RealtimeEnvelope, RealtimeKind, RealtimeModule, VoiceActiveSpeakersChanged, VoiceChatKind,
+};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+
+use crate::features::realtime::RealtimeHandle;
+
+use super::speaking::SpeakingIndicators;
+use super::state::VoiceConnectionState;
+
+/// Применяет события о говорящих в активной комнате к индикаторам речи.
+pub(super) fn use_active_speakers(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+    speaking: SpeakingIndicators,
+) {
+    use_hook(move || {
+        spawn(async move {
+            let mut events = realtime.subscribe_events();
+            while let Some(envelope) = events.next().await {
+                let Some(event) = active_speakers_changed(envelope) else {
+                    continue;
+                };
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                if event.server_id != target.server_id || event.room_id != target.room_id {
+                    continue;
+                }
+                speaking.apply_server_speakers(event.speaker_user_ids, event.dominant_user_id);
+            }
+        })
+    });
+}
+
+fn active_speakers_changed(envelope: RealtimeEnvelope) -> Option<VoiceActiveSpeakersChanged> {
+    if envelope.module != RealtimeModule::VoiceChat
+        || envelope.kind != RealtimeKind::VoiceChat(VoiceChatKind::ActiveSpeakersChanged)
+    {
+        return None;
+    }
+
+    serde_json::from_value(envelope.payload).ok()
+}
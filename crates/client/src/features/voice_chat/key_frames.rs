@@ -0,0 +1,149 @@
+//! Запросы ключевых кадров между зрителями и отправителями видео.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use cheenhub_contracts::realtime::{
+    RealtimeEnvelope, RealtimeKind, RealtimeModule, RequestVoiceKeyFrame, VoiceChatKind,
+    VoiceKeyFrameRequested, VoiceVideoStreamSource,
+};
+use dioxus::prelude::*;
+use futures_channel::mpsc;
+use futures_util::StreamExt;
+use web_time::Instant;
+
+use crate::features::camera::CameraHandle;
+use crate::features::realtime::RealtimeHandle;
+use crate::features::screen_share::ScreenShareHandle;
+
+use super::state::VoiceConnectionState;
+use super::video_streams::ParticipantVideoSource;
+
+/// Повторный запрос одного потока не чаще этого интервала, пока ключевой кадр не пришел.
+const KEY_FRAME_REQUEST_RETRY: Duration = Duration::from_secs(1);
+
+type KeyFrameRequestTimes = Rc<RefCell<HashMap<(ParticipantVideoSource, String), Instant>>>;
+
+/// Очередь запросов ключевых кадров у отправителей видео активной комнаты.
+#[derive(Clone)]
+pub(crate) struct KeyFrameRequester {
+    sender: mpsc::UnboundedSender<(ParticipantVideoSource, String)>,
+    current_user_id: String,
+    last_requests: KeyFrameRequestTimes,
+}
+
+impl KeyFrameRequester {
+    /// Просит отправителя закодировать ключевой кадр, если недавно об этом не просили.
+    pub(super) fn request(&self, source: ParticipantVideoSource, user_id: &str) {
+        if user_id == self.current_user_id {
+            return;
+        }
+        let now = Instant::now();
+        let key = (source, user_id.to_owned());
+        {
+            let mut last_requests = self.last_requests.borrow_mut();
+            if last_requests.get(&key).is_some_and(|requested_at| {
+                now.duration_since(*requested_at) < KEY_FRAME_REQUEST_RETRY
+            }) {
+                return;
+            }
+            last_requests.insert(key.clone(), now);
+        }
+        let _ = self.sender.unbounded_send(key);
+    }
+}
+
+/// Отправляет запросы ключевых кадров отправителям и выполняет запросы, адресованные
+/// локальной камере и демонстрации экрана.
+pub(super) fn use_key_frame_requests(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+    current_user_id: String,
+    camera: CameraHandle,
+    screen_share: ScreenShareHandle,
+) -> KeyFrameRequester {
+    let request_realtime = realtime.clone();
+    let requester = use_hook(move || {
+        let (sender, mut requests) = mpsc::unbounded::<(ParticipantVideoSource, String)>();
+        spawn(async move {
+            while let Some((source, publisher_user_id)) = requests.next().await {
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                let result = request_realtime
+                    .send_reliable(
+                        RealtimeModule::VoiceChat,
+                        RealtimeKind::VoiceChat(VoiceChatKind::RequestKeyFrame),
+                        RequestVoiceKeyFrame {
+                            server_id: target.server_id,
+                            room_id: target.room_id,
+                            publisher_user_id: publisher_user_id.clone(),
+                            source: contract_source(source),
+                        },
+                    )
+                    .await;
+                match result {
+                    Ok(()) => debug!(
+                        %publisher_user_id,
+                        source = source.label(),
+                        "requested participant video key frame"
+                    ),
+                    Err(error) => warn!(
+                        %error,
+                        %publisher_user_id,
+                        source = source.label(),
+                        "failed to request participant video key frame"
+                    ),
+                }
+            }
+        });
+        KeyFrameRequester {
+            sender,
+            current_user_id,
+            last_requests: Rc::new(RefCell::new(HashMap::new())),
+        }
+    });
+
+    use_hook(move || {
+        spawn(async move {
+            let mut events = realtime.subscribe_events();
+            while let Some(envelope) = events.next().await {
+                let Some(event) = key_frame_requested(envelope) else {
+                    continue;
+                };
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                if event.server_id != target.server_id || event.room_id != target.room_id {
+                    continue;
+                }
+                debug!(source = ?event.source, "received local video key frame request");
+                match event.source {
+                    VoiceVideoStreamSource::Camera => camera.request_key_frame(),
+                    VoiceVideoStreamSource::ScreenShare => screen_share.request_key_frame(),
+                }
+            }
+        })
+    });
+
+    requester
+}
+
+fn key_frame_requested(envelope: RealtimeEnvelope) -> Option<VoiceKeyFrameRequested> {
+    if envelope.module != RealtimeModule::VoiceChat
+        || envelope.kind != RealtimeKind::VoiceChat(VoiceChatKind::KeyFrameRequested)
+    {
+        return None;
+    }
+
+    serde_json::from_value(envelope.payload).ok()
+}
+
+pub(super) fn contract_source(source: ParticipantVideoSource) -> VoiceVideoStreamSource {
+    match source {
+        ParticipantVideoSource::Camera => VoiceVideoStreamSource::Camera,
+        ParticipantVideoSource::ScreenShare => VoiceVideoStreamSource::ScreenShare,
+    }
+}
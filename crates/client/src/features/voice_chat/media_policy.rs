@@ -0,0 +1,98 @@
+//! Применение настроек голоса и видео сервера к локальному захвату.
+
+use cheenhub_contracts::realtime::{
+    LoadServerMediaSettings, RealtimeKind, RealtimeModule, ServerKind, ServerMediaSettings,
+};
+use cheenhub_contracts::video_presets::VideoStreamSource;
+use dioxus::prelude::*;
+
+use crate::features::camera::CameraHandle;
+use crate::features::microphone::MicrophoneHandle;
+use crate::features::realtime::{RealtimeError, RealtimeHandle};
+use crate::features::screen_share::ScreenShareHandle;
+
+use super::state::{VoiceConnectionState, VoiceRoomTargetKind};
+
+/// Загружает ограничения сервера активной голосовой комнаты и передает их
+/// микрофону, камере и демонстрации экрана.
+///
+/// Для личных звонков и без активной комнаты действуют ограничения по умолчанию.
+pub(super) fn use_server_media_policy(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+    microphone: MicrophoneHandle,
+    camera: CameraHandle,
+    screen_share: ScreenShareHandle,
+) {
+    let mut applied_server_id = use_signal(|| None::<String>);
+    let mut generation = use_signal(|| 0_u64);
+    use_effect(move || {
+        let server_id = state()
+            .active_target()
+            .filter(|target| target.kind == VoiceRoomTargetKind::Server)
+            .map(|target| target.server_id);
+        if *applied_server_id.peek() == server_id {
+            return;
+        }
+        applied_server_id.set(server_id.clone());
+        let request_generation = *generation.peek() + 1;
+        generation.set(request_generation);
+
+        let Some(server_id) = server_id else {
+            apply_settings(
+                &ServerMediaSettings::defaults(String::new()),
+                &microphone,
+                &camera,
+                &screen_share,
+            );
+            return;
+        };
+        let realtime = realtime.clone();
+        let microphone = microphone.clone();
+        let camera = camera.clone();
+        let screen_share = screen_share.clone();
+        spawn(async move {
+            let settings = match load_server_media_settings(&realtime, server_id.clone()).await {
+                Ok(settings) => settings,
+                Err(error) => {
+                    warn!(%server_id, %error, "failed to load server media settings for voice room");
+                    ServerMediaSettings::defaults(server_id)
+                }
+            };
+            if *generation.peek() != request_generation {
+                return;
+            }
+            info!(
+                server_id = %settings.server_id,
+                allowed_video_presets = ?settings.allowed_video_presets,
+                audio_bitrate_bps = settings.audio_bitrate_bps,
+                "applying server media settings to local capture"
+            );
+            apply_settings(&settings, &microphone, &camera, &screen_share);
+        });
+    });
+}
+
+fn apply_settings(
+    settings: &ServerMediaSettings,
+    microphone: &MicrophoneHandle,
+    camera: &CameraHandle,
+    screen_share: &ScreenShareHandle,
+) {
+    microphone.set_bitrate_bps(settings.audio_bitrate_bps);
+    camera.set_allowed_presets(settings.presets_for(VideoStreamSource::Camera));
+    screen_share.set_allowed_presets(settings.presets_for(VideoStreamSource::ScreenShare));
+}
+
+async fn load_server_media_settings(
+    realtime: &RealtimeHandle,
+    server_id: String,
+) -> Result<ServerMediaSettings, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::Server,
+            RealtimeKind::Server(ServerKind::LoadServerMediaSettings),
+            LoadServerMediaSettings { server_id },
+        )
+        .await
+}
@@ -1,5 +1,6 @@
 //! Voice chat client feature.
 
+mod active_speakers;
 mod active_voice_notification_controls;
 mod android_output_route_button;
 mod direct_call_controls;
@@ -11,8 +12,10 @@ mod direct_call_provider;
 mod direct_call_realtime;
 mod direct_call_stage;
 mod direct_call_state;
+mod key_frames;
 mod kicked_modal;
 mod local_video;
+mod media_policy;
 mod microphone_uplink;
 mod microphone_uplink_platform;
 mod notification_sounds;
@@ -22,13 +25,16 @@ mod participant_grid_data;
 mod participant_tile;
 mod provider;
 mod realtime;
+mod receiver_reports;
 mod room_presence;
 mod sidebar_controls;
 mod speaking;
 mod state;
 mod surface;
+mod video_codecs;
 mod video_fragments;
 mod video_streams;
+mod video_subscriptions;
 mod voice_call_platform;
 mod voice_controls;
 mod voice_frame_sender;
@@ -63,6 +63,9 @@ pub(super) fn ParticipantFocusStrip(
                                 class: "flex h-9 w-9 items-center justify-center rounded-xl border border-white/10 bg-zinc-900 text-[14px] font-bold text-zinc-100".to_owned(),
                                 avatar_seed: Some(item.user_id.clone()),
                             }
+                            if item.dominant {
+                                span { class: "pointer-events-none absolute -inset-0.5 rounded-[14px] ring-2 ring-emerald-400/70" }
+                            }
                             if item.speaking {
                                 span { class: "absolute -bottom-0.5 -right-0.5 h-3 w-3 rounded-full border-2 border-zinc-950 bg-emerald-400" }
                             }
@@ -12,9 +12,10 @@ use crate::features::camera::{CameraHandle, CameraStatus};
 
 use super::participant_focus_strip::{FocusStripSelection, ParticipantFocusStrip};
 use super::participant_grid_data::{
-    focus_strip_tiles, participant_grid_layout, participant_tiles, preferred_focus_tile_key,
+    dominant_focus_tile_key, focus_strip_tiles, participant_grid_layout, participant_tiles,
+    preferred_focus_tile_key,
 };
-use super::participant_tile::VoiceParticipantTile;
+use super::participant_tile::{VoiceParticipantTile, VoiceParticipantTileMedia};
 use super::state::VoiceConnectionHandle;
 use super::video_streams::{ParticipantVideoHandle, ParticipantVideoSource};
 
@@ -47,12 +48,15 @@ pub(crate) fn VoiceParticipantGrid(
     room_id: String,
     participants: Vec<VoiceRoomParticipant>,
     speaking_user_ids: Vec<String>,
+    dominant_speaker_user_id: Option<String>,
     status: VoiceParticipantGridStatus,
     can_kick_voice: bool,
+    can_moderate_voice: bool,
     on_retry: EventHandler<()>,
 ) -> Element {
     let mut open_user_menu = use_signal(|| None::<UserMenuState>);
     let mut focused_tile_key = use_signal(|| None::<String>);
+    let mut follow_dominant_speaker = use_signal(|| false);
     let mut user_volumes = use_signal(HashMap::<String, u32>::new);
     let playback = use_context::<AudioPlaybackHandle>();
     let voice = use_context::<VoiceConnectionHandle>();
@@ -82,6 +86,26 @@ pub(crate) fn VoiceParticipantGrid(
     let kick_user_id = open_user_menu().map(|m| m.user_id.clone());
     let kick_server_id = server_id.clone();
     let kick_room_id = room_id.clone();
+    let mute_user_id = kick_user_id.clone();
+    let mute_server_id = server_id.clone();
+    let mute_room_id = room_id.clone();
+    let deafen_user_id = kick_user_id.clone();
+    let deafen_server_id = server_id.clone();
+    let deafen_room_id = room_id.clone();
+    let mute_voice = voice.clone();
+    let deafen_voice = voice.clone();
+    let menu_participant = open_user_menu().and_then(|menu| {
+        participants
+            .iter()
+            .find(|participant| participant.user_id == menu.user_id)
+            .cloned()
+    });
+    let server_muted = menu_participant
+        .as_ref()
+        .is_some_and(|participant| participant.server_muted);
+    let server_deafened = menu_participant
+        .as_ref()
+        .is_some_and(|participant| participant.server_deafened);
     let camera_live = matches!(camera.status(), CameraStatus::Live);
     let camera_user_ids = participant_video.live_user_ids(ParticipantVideoSource::Camera);
     let screen_user_ids = participant_video.live_user_ids(ParticipantVideoSource::ScreenShare);
@@ -94,13 +118,32 @@ pub(crate) fn VoiceParticipantGrid(
         camera_live,
     );
     let grid_layout = participant_grid_layout(participant_tiles.len());
-    let focus_strip_items = focus_strip_tiles(&participants, &participant_tiles, &current_user_id);
-    let active_focus_tile_key = focused_tile_key().and_then(|key| {
+    let focus_strip_items = focus_strip_tiles(
+        &participants,
+        &participant_tiles,
+        &current_user_id,
+        dominant_speaker_user_id.as_deref(),
+    );
+    let selected_focus_tile_key = focused_tile_key().and_then(|key| {
         participant_tiles
             .iter()
-            .any(|tile| tile.key == key)
-            .then_some(key)
+            .find(|tile| tile.key == key)
+            .map(|tile| (key, tile.media))
     });
+    // Until the user picks a tile manually, focus follows the dominant speaker, but never away from a screen share.
+    let active_focus_tile_key = match selected_focus_tile_key {
+        Some((key, media))
+            if follow_dominant_speaker() && media != VoiceParticipantTileMedia::ScreenShare =>
+        {
+            dominant_focus_tile_key(
+                &participant_tiles,
+                dominant_speaker_user_id.as_deref(),
+                &current_user_id,
+            )
+            .or(Some(key))
+        }
+        selected => selected.map(|(key, _)| key),
+    };
     let focused = active_focus_tile_key.is_some();
     let preferred_focus_tile_key = preferred_focus_tile_key(&participant_tiles);
     let display_mode_label = if focused {
@@ -150,6 +193,8 @@ pub(crate) fn VoiceParticipantGrid(
                         VoiceParticipantTile {
                             key: "{tile.key}",
                             focused: active_focus_tile_key.as_deref() == Some(tile.key.as_str()),
+                            // Focus mode hides the other tiles with CSS, so only the focused one keeps its video subscription.
+                            video_visible: !focused || active_focus_tile_key.as_deref() == Some(tile.key.as_str()),
                             speaking: tile.speaking,
                             media: tile.media,
                             participant: tile.participant.clone(),
@@ -168,6 +213,7 @@ pub(crate) fn VoiceParticipantGrid(
                                         );
                                         focused_tile_key.set(None);
                                     } else {
+                                        follow_dominant_speaker.set(false);
                                         info!(
                                             user_id = %focus_user_id,
                                             tile_key = %focus_tile_key,
@@ -195,6 +241,7 @@ pub(crate) fn VoiceParticipantGrid(
                             let target_tile_key = selection.tile_key;
                             let target_user_id = selection.user_id;
                             let target_media = selection.media;
+                            follow_dominant_speaker.set(false);
                             if focused_tile_key().as_deref() != Some(target_tile_key.as_str()) {
                                 info!(
                                     user_id = %target_user_id,
@@ -224,6 +271,7 @@ pub(crate) fn VoiceParticipantGrid(
                                     tile_key = %tile_key,
                                     "opened focused voice participant tile from display mode button"
                                 );
+                                follow_dominant_speaker.set(true);
                                 focused_tile_key.set(Some(tile_key));
                             }
                         }
@@ -245,6 +293,9 @@ pub(crate) fn VoiceParticipantGrid(
                     name: menu.name,
                     is_self: menu.user_id == current_user_id,
                     can_kick_voice,
+                    can_moderate_voice,
+                    server_muted,
+                    server_deafened,
                     volume: user_volumes().get(&menu.user_id).copied().unwrap_or(100),
                     x: menu.x,
                     y: menu.y,
@@ -262,6 +313,28 @@ pub(crate) fn VoiceParticipantGrid(
                             );
                         }
                     },
+                    on_server_mute: move |muted: bool| {
+                        if let Some(ref uid) = mute_user_id {
+                            open_user_menu.set(None);
+                            mute_voice.server_mute_member(
+                                mute_server_id.clone(),
+                                mute_room_id.clone(),
+                                uid.clone(),
+                                muted,
+                            );
+                        }
+                    },
+                    on_server_deafen: move |deafened: bool| {
+                        if let Some(ref uid) = deafen_user_id {
+                            open_user_menu.set(None);
+                            deafen_voice.server_deafen_member(
+                                deafen_server_id.clone(),
+                                deafen_room_id.clone(),
+                                uid.clone(),
+                                deafened,
+                            );
+                        }
+                    },
                 }
             }
         }
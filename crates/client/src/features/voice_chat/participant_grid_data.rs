@@ -33,6 +33,8 @@ pub(super) struct FocusStripTile {
     pub(super) tile_key: String,
     /// Признак активной речи участника.
     pub(super) speaking: bool,
+    /// Признак доминирующего говорящего по данным сервера.
+    pub(super) dominant: bool,
     /// Тип медиа выбранного тайла.
     pub(super) media: VoiceParticipantTileMedia,
     /// Признак текущего пользователя.
@@ -75,6 +77,7 @@ pub(super) fn focus_strip_tiles(
     participants: &[VoiceRoomParticipant],
     tiles: &[ParticipantTileEntry],
     current_user_id: &str,
+    dominant_user_id: Option<&str>,
 ) -> Vec<FocusStripTile> {
     let mut items = Vec::with_capacity(tiles.len());
 
@@ -82,18 +85,52 @@ pub(super) fn focus_strip_tiles(
         .iter()
         .find(|participant| participant.user_id == current_user_id)
     {
-        push_focus_strip_tiles(&mut items, participant, tiles, current_user_id);
+        push_focus_strip_tiles(
+            &mut items,
+            participant,
+            tiles,
+            current_user_id,
+            dominant_user_id,
+        );
     }
 
     for participant in participants {
         if participant.user_id != current_user_id {
-            push_focus_strip_tiles(&mut items, participant, tiles, current_user_id);
+            push_focus_strip_tiles(
+                &mut items,
+                participant,
+                tiles,
+                current_user_id,
+                dominant_user_id,
+            );
         }
     }
 
     items
 }
 
+/// Выбирает тайл доминирующего говорящего для автоматического фокуса.
+///
+/// Предпочитает камеру, затем аватар: демонстрация экрана показывает контент,
+/// а не говорящего. Текущий пользователь в фокус автоматически не попадает.
+pub(super) fn dominant_focus_tile_key(
+    tiles: &[ParticipantTileEntry],
+    dominant_user_id: Option<&str>,
+    current_user_id: &str,
+) -> Option<String> {
+    let dominant_user_id = dominant_user_id.filter(|user_id| *user_id != current_user_id)?;
+    let speaker_tiles = || {
+        tiles
+            .iter()
+            .filter(move |tile| tile.participant.user_id == dominant_user_id)
+    };
+    speaker_tiles()
+        .find(|tile| tile.media == VoiceParticipantTileMedia::Camera)
+        .or_else(|| speaker_tiles().find(|tile| tile.media == VoiceParticipantTileMedia::Avatar))
+        .or_else(|| speaker_tiles().next())
+        .map(|tile| tile.key.clone())
+}
+
 /// Выбирает лучший тайл для глобальной кнопки режима отображения.
 pub(super) fn preferred_focus_tile_key(tiles: &[ParticipantTileEntry]) -> Option<String> {
     tiles
@@ -165,6 +202,7 @@ fn push_focus_strip_tiles(
     participant: &VoiceRoomParticipant,
     tiles: &[ParticipantTileEntry],
     current_user_id: &str,
+    dominant_user_id: Option<&str>,
 ) {
     for tile in tiles
         .iter()
@@ -176,6 +214,7 @@ fn push_focus_strip_tiles(
             avatar_url: participant.avatar_url.clone(),
             tile_key: tile.key.clone(),
             speaking: tile.speaking,
+            dominant: dominant_user_id == Some(participant.user_id.as_str()),
             media: tile.media,
             is_self: participant.user_id == current_user_id,
         });
@@ -247,7 +286,7 @@ mod tests {
             true,
         );
 
-        let focus_tiles = focus_strip_tiles(&participants, &tiles, "current-user");
+        let focus_tiles = focus_strip_tiles(&participants, &tiles, "current-user", None);
 
         assert_eq!(focus_tiles.len(), 3);
         assert_eq!(focus_tiles[0].user_id, "current-user");
@@ -261,12 +300,45 @@ mod tests {
         assert_eq!(focus_tiles[2].media, VoiceParticipantTileMedia::Avatar);
     }
 
+    #[test]
+    fn dominant_speaker_focus_prefers_camera_and_skips_current_user() {
+        let participants = vec![
+            participant("current-user", "Текущий"),
+            participant("other-user", "Сосед"),
+        ];
+        let tiles = participant_tiles(
+            &participants,
+            &[],
+            &["other-user".to_owned()],
+            &["other-user".to_owned()],
+            "current-user",
+            false,
+        );
+
+        assert_eq!(
+            dominant_focus_tile_key(&tiles, Some("other-user"), "current-user").as_deref(),
+            Some("other-user-camera")
+        );
+        assert_eq!(
+            dominant_focus_tile_key(&tiles, Some("current-user"), "current-user"),
+            None
+        );
+
+        let focus_tiles =
+            focus_strip_tiles(&participants, &tiles, "current-user", Some("other-user"));
+        assert!(!focus_tiles[0].dominant);
+        assert!(focus_tiles[1..].iter().all(|tile| tile.dominant));
+    }
+
     fn participant(user_id: &str, nickname: &str) -> VoiceRoomParticipant {
         VoiceRoomParticipant {
             user_id: user_id.to_owned(),
             nickname: nickname.to_owned(),
             avatar_url: None,
             joined_at: "2026-06-19T00:00:00Z".to_owned(),
+            connection_quality: None,
+            server_muted: false,
+            server_deafened: false,
         }
     }
 }
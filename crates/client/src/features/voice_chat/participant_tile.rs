@@ -7,6 +7,9 @@ use crate::features::app::components::avatar::{UserAvatar, use_avatar_seed};
 
 use super::video_streams::{ParticipantVideoCanvas, ParticipantVideoSource};
 
+/// Оценка соединения, ниже которой на плитке показывается предупреждение.
+const WEAK_CONNECTION_QUALITY: u8 = 70;
+
 /// Видеороль или fallback-содержимое плитки участника.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub(crate) enum VoiceParticipantTileMedia {
@@ -34,6 +37,7 @@ impl VoiceParticipantTileMedia {
 pub(crate) fn VoiceParticipantTile(
     participant: VoiceRoomParticipant,
     focused: bool,
+    video_visible: bool,
     speaking: bool,
     media: VoiceParticipantTileMedia,
     on_toggle_focus: EventHandler<()>,
@@ -45,6 +49,9 @@ pub(crate) fn VoiceParticipantTile(
     let camera_on = matches!(media, VoiceParticipantTileMedia::Camera);
     let video_on = screen_sharing || camera_on;
     let focused_video = focused && video_on;
+    let weak_connection = participant
+        .connection_quality
+        .is_some_and(|quality| quality < WEAK_CONNECTION_QUALITY);
     let tile_class = if focused_video && speaking {
         "user-tile relative overflow-hidden rounded-lg border border-emerald-400/55 bg-zinc-950 p-0 shadow-none transition-[border-color,background,transform,box-shadow] duration-200 ease-in-out hover:border-emerald-300/65"
     } else if focused_video && screen_sharing {
@@ -84,7 +91,7 @@ pub(crate) fn VoiceParticipantTile(
                     on_open_user_menu.call((nickname.clone(), user_id.clone(), point.x, point.y));
                 }
             },
-            if screen_sharing {
+            if screen_sharing && video_visible {
                 ParticipantVideoCanvas {
                     user_id: participant.user_id.clone(),
                     source: ParticipantVideoSource::ScreenShare,
@@ -92,7 +99,7 @@ pub(crate) fn VoiceParticipantTile(
                 if !focused {
                     div { class: "pointer-events-none absolute inset-0 z-[1] bg-gradient-to-t from-zinc-950/65 via-transparent to-zinc-950/20" }
                 }
-            } else if camera_on {
+            } else if camera_on && video_visible {
                 ParticipantVideoCanvas {
                     user_id: participant.user_id.clone(),
                     source: ParticipantVideoSource::Camera,
@@ -157,6 +164,22 @@ pub(crate) fn VoiceParticipantTile(
                             }
                         }
                         div { class: "truncate", "{participant.nickname}" }
+                        if participant.server_muted {
+                            svg { class: "h-3.5 w-3.5 shrink-0 text-rose-400", fill: "none", stroke: "currentColor", stroke_width: "1.9", view_box: "0 0 24 24", "aria-hidden": "true",
+                                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M15 9.34V7a3 3 0 0 0-5.68-1.34M9 9v2a3 3 0 0 0 5.12 2.12M19 11a7 7 0 0 1-7 7m0 0v3m0-3a7 7 0 0 1-7-7m3 10h8M3 3l18 18" }
+                            }
+                            span { class: "sr-only", "Микрофон отключен модератором" }
+                        }
+                        if participant.server_deafened {
+                            svg { class: "h-3.5 w-3.5 shrink-0 text-rose-400", fill: "none", stroke: "currentColor", stroke_width: "1.9", view_box: "0 0 24 24", "aria-hidden": "true",
+                                path { stroke_linecap: "round", stroke_linejoin: "round", d: "M3 14v-2a9 9 0 0 1 15.36-6.36M21 12v2M3 14a2 2 0 0 1 2-2h1v7H5a2 2 0 0 1-2-2v-3Zm18 0a2 2 0 0 0-2-2h-1v7h1a2 2 0 0 0 2-2v-3ZM3 3l18 18" }
+                            }
+                            span { class: "sr-only", "Звук отключен модератором" }
+                        }
+                        if weak_connection {
+                            span { class: "h-2 w-2 shrink-0 rounded-full bg-amber-400", title: "Слабое соединение" }
+                            span { class: "sr-only", "Слабое соединение" }
+                        }
                     }
                 }
             }
@@ -4,6 +4,7 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use cheenhub_contracts::media::MIXED_VOICE_SENDER_USER_IDS;
 use dioxus::prelude::*;
 use futures_util::StreamExt;
 
@@ -17,18 +18,24 @@ use crate::features::realtime::{RealtimeConnectionStatus, RealtimeHandle};
 use crate::features::screen_share::{ScreenShareHandle, ScreenShareStatus};
 
 use super::direct_call_provider::DirectCallProvider;
+use super::key_frames::use_key_frame_requests;
 use super::kicked_modal::KickedFromVoiceModal;
 use super::local_video::{
     LocalVideoRuntime, LocalVideoTarget, participant_source_from_contract, reconcile_camera_target,
     reconcile_screen_share_target, release_local_video_target,
 };
+use super::media_policy::use_server_media_policy;
 use super::microphone_uplink;
 use super::notification_sounds::{
     ConnectionNotificationSoundState, ToggleNotificationSoundState, VoiceNotificationSoundState,
 };
 use super::realtime;
+use super::receiver_reports::use_receiver_reports;
+use super::speaking::use_speaking_indicators;
 use super::state::{VoiceConnectionHandle, VoiceConnectionState};
+use super::video_codecs::use_video_codec_advertisement;
 use super::video_streams::{ParticipantVideoHandle, ParticipantVideoSource};
+use super::video_subscriptions::{use_video_stream_activity, use_video_subscriptions};
 use super::voice_call_platform::{self, VoiceAudioFocusEvent};
 
 /// Предоставляет состояние голосового соединения аутентифицированным компонентам приложения.
@@ -45,18 +52,40 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
     let mut platform_call_active = use_signal(|| false);
     let mut voice_audio_focused = use_signal(|| true);
     let kicked_from_room = use_signal(|| None::<String>);
-    let speaking_users = use_signal(Vec::new);
+    let speaking = use_speaking_indicators(state, realtime.clone());
     let room_snapshots = use_signal(Vec::new);
-    let speaking_generations = use_hook(|| Rc::new(RefCell::new(HashMap::<String, u64>::new())));
     let participant_video_streams = use_signal(Vec::new);
     let participant_video_subscribers = use_hook(|| Rc::new(RefCell::new(HashMap::new())));
     let participant_video_generations = use_hook(|| Rc::new(RefCell::new(HashMap::new())));
     let participant_video_blocked_streams = use_hook(|| Rc::new(RefCell::new(HashSet::new())));
+    let key_frames = use_key_frame_requests(
+        state,
+        realtime.clone(),
+        current_user.id.clone(),
+        camera.clone(),
+        screen_share.clone(),
+    );
+    let video_subscriptions =
+        use_video_subscriptions(state, realtime.clone(), current_user.id.clone());
     let participant_video = ParticipantVideoHandle::new(
         participant_video_streams,
         participant_video_subscribers,
         participant_video_generations,
         participant_video_blocked_streams,
+        key_frames,
+        video_subscriptions,
+    );
+    use_video_stream_activity(
+        state,
+        realtime.clone(),
+        current_user.id.clone(),
+        participant_video.clone(),
+    );
+    let video_receive_stats = use_receiver_reports(
+        state,
+        realtime.clone(),
+        playback.clone(),
+        microphone.clone(),
     );
     let participant_video_context = participant_video.clone();
     use_context_provider(move || participant_video_context.clone());
@@ -75,9 +104,8 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
     let handle = VoiceConnectionHandle::new(
         state,
         kicked_from_room,
-        speaking_users,
+        speaking,
         room_snapshots,
-        speaking_generations,
         realtime.clone(),
         current_user.clone(),
     );
@@ -120,6 +148,7 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
     let datagram_handle = handle.clone();
     use_hook(move || {
         spawn(async move {
+            let mixed_sender_user_ids = MIXED_VOICE_SENDER_USER_IDS.map(|id| id.to_string());
             let mut frames = realtime::subscribe_voice_frames(&datagram_realtime);
             while let Some(frame) = frames.next().await {
                 if !voice_audio_focused() {
@@ -134,7 +163,9 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
                 {
                     continue;
                 }
-                datagram_handle.mark_user_speaking(frame.sender_user_id.clone());
+                if !mixed_sender_user_ids.contains(&frame.sender_user_id) {
+                    datagram_handle.mark_user_speaking(frame.sender_user_id.clone());
+                }
                 datagram_playback.play_voice_frame(VoiceFrame {
                     sender_user_id: frame.sender_user_id,
                     sequence: frame.sequence,
@@ -149,9 +180,11 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
     let screen_datagram_realtime = realtime.clone();
     let screen_datagram_current_user_id = current_user.id.clone();
     let screen_datagram_video = participant_video.clone();
+    let screen_receive_stats = video_receive_stats.clone();
     use_hook(move || {
         spawn(async move {
-            let mut frames = realtime::subscribe_screen_frames(&screen_datagram_realtime);
+            let mut frames =
+                realtime::subscribe_screen_frames(&screen_datagram_realtime, screen_receive_stats);
             while let Some(frame) = frames.next().await {
                 let current = state();
                 let Some(target) = current.active_target() else {
@@ -170,9 +203,11 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
     let camera_datagram_realtime = realtime.clone();
     let camera_datagram_current_user_id = current_user.id.clone();
     let camera_datagram_video = participant_video.clone();
+    let camera_receive_stats = video_receive_stats;
     use_hook(move || {
         spawn(async move {
-            let mut frames = realtime::subscribe_camera_frames(&camera_datagram_realtime);
+            let mut frames =
+                realtime::subscribe_camera_frames(&camera_datagram_realtime, camera_receive_stats);
             while let Some(frame) = frames.next().await {
                 let current = state();
                 let Some(target) = current.active_target() else {
@@ -237,6 +272,14 @@ pub(crate) fn VoiceConnectionProvider(children: Element) -> Element {
             }
         })
     });
+    use_server_media_policy(
+        state,
+        realtime.clone(),
+        microphone.clone(),
+        camera.clone(),
+        screen_share.clone(),
+    );
+    use_video_codec_advertisement(state, realtime.clone());
     let camera_sound_playback = playback.clone();
     let camera_sound_handle = camera.clone();
     let camera_sound_state = camera_notification_sounds.clone();
@@ -4,11 +4,12 @@ use bytes::Bytes;
 use cheenhub_contracts::media::{MediaCodec, MediaDatagram, MediaDatagramKind};
 use cheenhub_contracts::realtime::{
     DirectMessageVoiceRoomsSnapshot, IssueMicrophoneUplinkGrant, JoinDirectMessageVoiceRoom,
-    JoinVoiceRoom, KickVoiceMember, LeaveDirectMessageVoiceRoom, LeaveVoiceRoom,
-    ListDirectMessageVoiceRooms, ListServerVoiceRooms, MicrophoneUplinkGrantIssued, RealtimeKind,
-    RealtimeModule, ServerVoiceRoomsSnapshot, StopVoiceVideoStream, VoiceChatKind,
-    VoiceRoomSnapshot, VoiceVideoStreamSource,
+    JoinVoiceRoom, LeaveDirectMessageVoiceRoom, LeaveVoiceRoom, ListDirectMessageVoiceRooms,
+    ListServerVoiceRooms, MicrophoneUplinkGrantIssued, RealtimeKind, RealtimeModule,
+    ServerVoiceRoomsSnapshot, StopVoiceVideoStream, VoiceChatKind, VoiceRoomSnapshot,
+    VoiceVideoStreamSource,
 };
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
 use futures_channel::mpsc;
 use futures_util::StreamExt;
 use uuid::Uuid;
@@ -19,10 +20,17 @@ use crate::features::screen_share::{EncodedScreenShareFrame, ScreenShareCodec};
 
 use crate::features::camera::{CameraCodec, EncodedCameraFrame};
 
+use super::receiver_reports::VideoReceiveCounters;
 use super::video_fragments::{self, OutboundVideoFrame};
 
 #[path = "realtime_decode.rs"]
 mod realtime_decode;
+#[path = "realtime_moderation.rs"]
+mod realtime_moderation;
+
+pub(crate) use realtime_moderation::{
+    kick_voice_member, server_deafen_voice_member, server_mute_voice_member,
+};
 
 /// Inbound relayed voice frame.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -41,7 +49,7 @@ pub(crate) struct InboundVoiceFrame {
     pub(crate) bytes: Vec<u8>,
 }
 
-/// Входящий ретранслированный VP9 кадр видео.
+/// Входящий ретранслированный кадр видео.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct InboundVideoFrame {
     /// Идентификатор целевой комнаты.
@@ -54,10 +62,14 @@ pub(crate) struct InboundVideoFrame {
     pub(crate) timestamp_us: u64,
     /// Длительность кадра в микросекундах.
     pub(crate) duration_us: u32,
-    /// Сырые байты закодированного VP9 кадра.
+    /// Видеокодек кадра.
+    pub(crate) codec: MediaCodec,
+    /// Сырые байты закодированного кадра.
     pub(crate) bytes: Vec<u8>,
     /// Может ли этот кадр открыть поток декодера.
     pub(crate) key_frame: bool,
+    /// Потерялись ли предыдущие кадры этого потока.
+    pub(crate) follows_frame_loss: bool,
 }
 
 /// Входящее событие остановки видеопотока участника.
@@ -146,26 +158,6 @@ pub(crate) async fn issue_microphone_uplink_grant(
         .await
 }
 
-/// Kicks one participant from a voice room.
-pub(crate) async fn kick_voice_member(
-    realtime: &RealtimeHandle,
-    server_id: String,
-    room_id: String,
-    user_id: String,
-) -> Result<VoiceRoomSnapshot, RealtimeError> {
-    realtime
-        .request(
-            RealtimeModule::VoiceChat,
-            RealtimeKind::VoiceChat(VoiceChatKind::KickVoiceMember),
-            KickVoiceMember {
-                server_id,
-                room_id,
-                user_id,
-            },
-        )
-        .await
-}
-
 /// Loads active voice room participant snapshots for one server.
 pub(crate) async fn list_server_voice_rooms(
     realtime: &RealtimeHandle,
@@ -262,6 +254,7 @@ pub(crate) fn subscribe_voice_frames(
 /// Подписывает текущую вкладку на входящие ретранслированные кадры демонстрации экрана.
 pub(crate) fn subscribe_screen_frames(
     realtime: &RealtimeHandle,
+    receive_stats: VideoReceiveCounters,
 ) -> mpsc::UnboundedReceiver<InboundVideoFrame> {
     let datagrams = realtime.subscribe_datagrams();
     let (sender, receiver) = mpsc::unbounded();
@@ -269,20 +262,21 @@ pub(crate) fn subscribe_screen_frames(
     dioxus::prelude::spawn(async move {
         let mut datagrams = datagrams;
         let mut reassembler = video_fragments::VideoFrameReassembler::default();
+        let mut losses = video_fragments::VideoFrameLossDetector::with_receive_stats(receive_stats);
         while let Some(bytes) = datagrams.next().await {
             let Some(datagram) = realtime_decode::screen_datagram(&bytes) else {
                 continue;
             };
-            let frame = if video_fragments::is_fragmented(&datagram) {
-                reassembler
-                    .push(datagram)
-                    .map(realtime_decode::video_frame_from_datagram)
+            let datagram = if video_fragments::is_fragmented(&datagram) {
+                reassembler.push(datagram)
             } else {
-                Some(realtime_decode::video_frame_from_datagram(datagram))
+                Some(datagram)
             };
-            let Some(frame) = frame else {
+            let Some(datagram) = datagram else {
                 continue;
             };
+            let follows_frame_loss = losses.observe(&datagram);
+            let frame = realtime_decode::video_frame_from_datagram(datagram, follows_frame_loss);
             if sender.unbounded_send(frame).is_err() {
                 break;
             }
@@ -295,6 +289,7 @@ pub(crate) fn subscribe_screen_frames(
 /// Подписывает текущую вкладку на входящие ретранслированные кадры камеры.
 pub(crate) fn subscribe_camera_frames(
     realtime: &RealtimeHandle,
+    receive_stats: VideoReceiveCounters,
 ) -> mpsc::UnboundedReceiver<InboundVideoFrame> {
     let datagrams = realtime.subscribe_datagrams();
     let (sender, receiver) = mpsc::unbounded();
@@ -302,20 +297,21 @@ pub(crate) fn subscribe_camera_frames(
     dioxus::prelude::spawn(async move {
         let mut datagrams = datagrams;
         let mut reassembler = video_fragments::VideoFrameReassembler::default();
+        let mut losses = video_fragments::VideoFrameLossDetector::with_receive_stats(receive_stats);
         while let Some(bytes) = datagrams.next().await {
             let Some(datagram) = realtime_decode::camera_datagram(&bytes) else {
                 continue;
             };
-            let frame = if video_fragments::is_fragmented(&datagram) {
-                reassembler
-                    .push(datagram)
-                    .map(realtime_decode::video_frame_from_datagram)
+            let datagram = if video_fragments::is_fragmented(&datagram) {
+                reassembler.push(datagram)
             } else {
-                Some(realtime_decode::video_frame_from_datagram(datagram))
+                Some(datagram)
             };
-            let Some(frame) = frame else {
+            let Some(datagram) = datagram else {
                 continue;
             };
+            let follows_frame_loss = losses.observe(&datagram);
+            let frame = realtime_decode::video_frame_from_datagram(datagram, follows_frame_loss);
             if sender.unbounded_send(frame).is_err() {
                 break;
             }
@@ -346,6 +342,8 @@ pub(crate) async fn send_voice_frame(
         duration_us: frame.duration_us,
         room_id,
         sender_user_id: Uuid::nil(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: frame.audio_level,
         payload: frame.bytes,
     };
     let bytes = datagram
@@ -370,6 +368,7 @@ pub(crate) async fn send_screen_frame(
         timestamp_us: frame.timestamp_us,
         duration_us: frame.duration_us,
         key_frame: frame.key_frame,
+        layer: frame.layer,
         bytes: frame.bytes,
     };
     send_video_frame(
@@ -398,6 +397,7 @@ pub(crate) async fn send_camera_frame(
         timestamp_us: frame.timestamp_us,
         duration_us: frame.duration_us,
         key_frame: frame.key_frame,
+        layer: frame.layer,
         bytes: frame.bytes,
     };
     send_video_frame(
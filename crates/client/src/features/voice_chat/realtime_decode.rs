@@ -54,7 +54,7 @@ pub(super) fn voice_frame(bytes: &[u8]) -> Option<InboundVoiceFrame> {
 
 pub(super) fn screen_datagram(bytes: &[u8]) -> Option<MediaDatagram> {
     let datagram = MediaDatagram::decode(bytes).ok()?;
-    if datagram.kind != MediaDatagramKind::ScreenFrame || datagram.codec != MediaCodec::Vp9 {
+    if datagram.kind != MediaDatagramKind::ScreenFrame || !datagram.codec.is_video() {
         return None;
     }
 
@@ -63,21 +63,26 @@ pub(super) fn screen_datagram(bytes: &[u8]) -> Option<MediaDatagram> {
 
 pub(super) fn camera_datagram(bytes: &[u8]) -> Option<MediaDatagram> {
     let datagram = MediaDatagram::decode(bytes).ok()?;
-    if datagram.kind != MediaDatagramKind::CameraFrame || datagram.codec != MediaCodec::Vp9 {
+    if datagram.kind != MediaDatagramKind::CameraFrame || !datagram.codec.is_video() {
         return None;
     }
 
     Some(datagram)
 }
 
-pub(super) fn video_frame_from_datagram(datagram: MediaDatagram) -> InboundVideoFrame {
+pub(super) fn video_frame_from_datagram(
+    datagram: MediaDatagram,
+    follows_frame_loss: bool,
+) -> InboundVideoFrame {
     InboundVideoFrame {
         room_id: datagram.room_id.to_string(),
         sender_user_id: datagram.sender_user_id.to_string(),
         sequence: datagram.sequence,
         timestamp_us: datagram.timestamp_us,
         duration_us: datagram.duration_us,
+        codec: datagram.codec,
         bytes: datagram.payload,
         key_frame: datagram.flags & MEDIA_DATAGRAM_FLAG_KEY_FRAME != 0,
+        follows_frame_loss,
     }
 }
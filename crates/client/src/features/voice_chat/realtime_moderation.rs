@@ -0,0 +1,72 @@
+//! Realtime-запросы модерации участников голосовой комнаты.
+
+use cheenhub_contracts::realtime::{
+    KickVoiceMember, RealtimeKind, RealtimeModule, ServerDeafenVoiceMember, ServerMuteVoiceMember,
+    VoiceChatKind, VoiceRoomSnapshot,
+};
+
+use crate::features::realtime::{RealtimeError, RealtimeHandle};
+
+/// Kicks one participant from a voice room.
+pub(crate) async fn kick_voice_member(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    user_id: String,
+) -> Result<VoiceRoomSnapshot, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::VoiceChat,
+            RealtimeKind::VoiceChat(VoiceChatKind::KickVoiceMember),
+            KickVoiceMember {
+                server_id,
+                room_id,
+                user_id,
+            },
+        )
+        .await
+}
+
+/// Server-mutes or unmutes one participant of a voice room.
+pub(crate) async fn server_mute_voice_member(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    user_id: String,
+    muted: bool,
+) -> Result<VoiceRoomSnapshot, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::VoiceChat,
+            RealtimeKind::VoiceChat(VoiceChatKind::ServerMuteVoiceMember),
+            ServerMuteVoiceMember {
+                server_id,
+                room_id,
+                user_id,
+                muted,
+            },
+        )
+        .await
+}
+
+/// Server-deafens or undeafens one participant of a voice room.
+pub(crate) async fn server_deafen_voice_member(
+    realtime: &RealtimeHandle,
+    server_id: String,
+    room_id: String,
+    user_id: String,
+    deafened: bool,
+) -> Result<VoiceRoomSnapshot, RealtimeError> {
+    realtime
+        .request(
+            RealtimeModule::VoiceChat,
+            RealtimeKind::VoiceChat(VoiceChatKind::ServerDeafenVoiceMember),
+            ServerDeafenVoiceMember {
+                server_id,
+                room_id,
+                user_id,
+                deafened,
+            },
+        )
+        .await
+}
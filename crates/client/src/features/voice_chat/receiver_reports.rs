@@ -0,0 +1,260 @@
+//! Периодические отчеты получателя о приеме медиа участников комнаты.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::rc::Rc;
+
+use cheenhub_contracts::realtime::{
+    NetworkKind, RealtimeEnvelope, RealtimeKind, RealtimeModule, ReceiverReport,
+    ReceiverSenderStats, VoiceChatKind, VoiceUplinkLossReported,
+};
+use dioxus::prelude::*;
+use futures_util::StreamExt;
+use uuid::Uuid;
+
+use crate::features::audio_playback::{AudioPlaybackHandle, VoiceReceiveStats};
+use crate::features::microphone::MicrophoneHandle;
+use crate::features::realtime::RealtimeHandle;
+use crate::features::runtime::sleep_ms;
+
+use super::state::VoiceConnectionState;
+
+/// Как часто получатель отправляет статистику приема на сервер.
+const RECEIVER_REPORT_INTERVAL_MS: u32 = 5_000;
+/// Минимальная ожидаемая потеря, под которую кодер держит FEC даже на чистом канале.
+const MIN_UPLINK_FEC_LOSS_PERCENT: u8 = 5;
+/// Предел ожидаемой потери: дальше FEC съедает битрейт голоса без заметной пользы.
+const MAX_UPLINK_FEC_LOSS_PERCENT: u8 = 40;
+
+/// Счетчики приема видеокадров одного отправителя с прошлого отчета.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) struct VideoReceiveStats {
+    /// Число собранных кадров.
+    pub(super) received_frames: u64,
+    /// Число номеров кадров, пропущенных перед собранными.
+    pub(super) lost_frames: u64,
+    /// Число кадров, пришедших после более новых.
+    pub(super) late_frames: u64,
+}
+
+/// Общие для входящих видеопотоков счетчики приема по отправителям.
+#[derive(Clone, Default)]
+pub(crate) struct VideoReceiveCounters(Rc<RefCell<HashMap<Uuid, VideoReceiveStats>>>);
+
+impl VideoReceiveCounters {
+    /// Учитывает собранный кадр отправителя.
+    pub(super) fn record(&self, sender_user_id: Uuid, lost_frames: u64, late: bool) {
+        let mut counters = self.0.borrow_mut();
+        let stats = counters.entry(sender_user_id).or_default();
+        stats.received_frames = stats.received_frames.saturating_add(1);
+        stats.lost_frames = stats.lost_frames.saturating_add(lost_frames);
+        if late {
+            stats.late_frames = stats.late_frames.saturating_add(1);
+        }
+    }
+
+    fn take(&self) -> HashMap<Uuid, VideoReceiveStats> {
+        std::mem::take(&mut *self.0.borrow_mut())
+    }
+}
+
+/// Раз в несколько секунд отправляет серверу статистику приема голоса и видео
+/// от участников активной комнаты и подстраивает FEC микрофона под потери,
+/// о которых сервер сообщает по отчетам других получателей.
+pub(super) fn use_receiver_reports(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+    playback: AudioPlaybackHandle,
+    microphone: MicrophoneHandle,
+) -> VideoReceiveCounters {
+    let video = use_hook(VideoReceiveCounters::default);
+    let report_video = video.clone();
+    let loss_realtime = realtime.clone();
+    use_hook(move || {
+        spawn(async move {
+            let mut events = loss_realtime.subscribe_events();
+            while let Some(envelope) = events.next().await {
+                let Some(event) = uplink_loss_reported(envelope) else {
+                    continue;
+                };
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                if event.server_id != target.server_id || event.room_id != target.room_id {
+                    continue;
+                }
+                debug!(
+                    loss_percent = event.loss_percent,
+                    "received voice uplink loss"
+                );
+                microphone.set_packet_loss_percent(fec_loss_percent(event.loss_percent));
+            }
+        })
+    });
+    use_hook(move || {
+        spawn(async move {
+            loop {
+                sleep_ms(RECEIVER_REPORT_INTERVAL_MS).await;
+                let audio = playback.take_receive_stats();
+                let video = report_video.take();
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                let senders = sender_stats(audio, video);
+                if senders.is_empty() {
+                    continue;
+                }
+                let sender_count = senders.len();
+                let result = realtime
+                    .send_reliable(
+                        RealtimeModule::Network,
+                        RealtimeKind::Network(NetworkKind::ReceiverReport),
+                        ReceiverReport {
+                            server_id: target.server_id,
+                            room_id: target.room_id,
+                            senders,
+                        },
+                    )
+                    .await;
+                match result {
+                    Ok(()) => trace!(senders = sender_count, "sent media receiver report"),
+                    Err(error) => warn!(%error, "failed to send media receiver report"),
+                }
+            }
+        })
+    });
+
+    video
+}
+
+fn uplink_loss_reported(envelope: RealtimeEnvelope) -> Option<VoiceUplinkLossReported> {
+    if envelope.module != RealtimeModule::VoiceChat
+        || envelope.kind != RealtimeKind::VoiceChat(VoiceChatKind::UplinkLossReported)
+    {
+        return None;
+    }
+
+    serde_json::from_value(envelope.payload).ok()
+}
+
+fn fec_loss_percent(loss_percent: u8) -> u8 {
+    loss_percent.clamp(MIN_UPLINK_FEC_LOSS_PERCENT, MAX_UPLINK_FEC_LOSS_PERCENT)
+}
+
+fn sender_stats(
+    audio: Vec<(String, VoiceReceiveStats)>,
+    video: HashMap<Uuid, VideoReceiveStats>,
+) -> Vec<ReceiverSenderStats> {
+    let mut senders = BTreeMap::<String, ReceiverSenderStats>::new();
+    for (user_id, stats) in audio {
+        trace!(
+            %user_id,
+            jitter_us = stats.jitter_us,
+            playout_delay_us = stats.playout_delay_us,
+            "inbound voice playout delay"
+        );
+        let sender = senders
+            .entry(user_id.clone())
+            .or_insert_with(|| empty_sender_stats(user_id));
+        sender.received_frames = sender.received_frames.saturating_add(stats.received_frames);
+        sender.lost_frames = sender.lost_frames.saturating_add(stats.lost_frames);
+        sender.late_frames = sender.late_frames.saturating_add(stats.late_frames);
+        sender.jitter_ms = stats.jitter_us / 1_000;
+    }
+    for (user_id, stats) in video {
+        let user_id = user_id.to_string();
+        let sender = senders
+            .entry(user_id.clone())
+            .or_insert_with(|| empty_sender_stats(user_id));
+        sender.received_frames = sender.received_frames.saturating_add(stats.received_frames);
+        sender.lost_frames = sender.lost_frames.saturating_add(stats.lost_frames);
+        sender.late_frames = sender.late_frames.saturating_add(stats.late_frames);
+    }
+
+    senders
+        .into_values()
+        .filter(|sender| sender.received_frames > 0 || sender.lost_frames > 0)
+        .collect()
+}
+
+fn empty_sender_stats(user_id: String) -> ReceiverSenderStats {
+    ReceiverSenderStats {
+        user_id,
+        received_frames: 0,
+        lost_frames: 0,
+        late_frames: 0,
+        jitter_ms: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_voice_and_video_stats_per_sender() {
+        let video_only = Uuid::new_v4();
+        let both = Uuid::new_v4();
+        let audio = vec![
+            (
+                both.to_string(),
+                VoiceReceiveStats {
+                    received_frames: 48,
+                    lost_frames: 2,
+                    late_frames: 1,
+                    jitter_us: 12_600,
+                    playout_delay_us: 50_000,
+                },
+            ),
+            ("idle".to_owned(), VoiceReceiveStats::default()),
+        ];
+        let video = HashMap::from([
+            (
+                both,
+                VideoReceiveStats {
+                    received_frames: 30,
+                    lost_frames: 1,
+                    late_frames: 0,
+                },
+            ),
+            (
+                video_only,
+                VideoReceiveStats {
+                    received_frames: 15,
+                    lost_frames: 0,
+                    late_frames: 2,
+                },
+            ),
+        ]);
+
+        let senders = sender_stats(audio, video);
+
+        assert_eq!(senders.len(), 2);
+        let merged = senders
+            .iter()
+            .find(|sender| sender.user_id == both.to_string())
+            .expect("sender with voice and video");
+        assert_eq!(
+            (
+                merged.received_frames,
+                merged.lost_frames,
+                merged.late_frames
+            ),
+            (78, 3, 1)
+        );
+        assert_eq!(merged.jitter_ms, 12);
+        let video_sender = senders
+            .iter()
+            .find(|sender| sender.user_id == video_only.to_string())
+            .expect("video-only sender");
+        assert_eq!(video_sender.late_frames, 2);
+        assert_eq!(video_sender.jitter_ms, 0);
+    }
+
+    #[test]
+    fn keeps_fec_loss_within_useful_range() {
+        assert_eq!(fec_loss_percent(0), MIN_UPLINK_FEC_LOSS_PERCENT);
+        assert_eq!(fec_loss_percent(17), 17);
+        assert_eq!(fec_loss_percent(100), MAX_UPLINK_FEC_LOSS_PERCENT);
+    }
+}
@@ -6,82 +6,137 @@ use std::rc::Rc;
 
 use dioxus::prelude::*;
 
+use crate::features::realtime::RealtimeHandle;
 use crate::features::runtime::sleep_ms;
 
+use super::active_speakers::use_active_speakers;
+use super::state::VoiceConnectionState;
+
 const SPEAKING_RELEASE_TIMEOUT_MS: u32 = 450;
 
 /// Активность одного говорящего участника.
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub(super) struct SpeakingUserActivity {
+struct SpeakingUserActivity {
     /// Идентификатор участника.
     user_id: String,
 }
 
-/// Возвращает идентификаторы участников, сейчас помеченных говорящими.
-pub(super) fn user_ids(speaking_users: Signal<Vec<SpeakingUserActivity>>) -> Vec<String> {
-    speaking_users()
-        .into_iter()
-        .map(|activity| activity.user_id)
-        .collect()
+/// Индикаторы говорящих: по принятым голосовым кадрам и по событиям сервера.
+#[derive(Clone)]
+pub(super) struct SpeakingIndicators {
+    users: Signal<Vec<SpeakingUserActivity>>,
+    generations: Rc<RefCell<HashMap<String, u64>>>,
+    server_speakers: Signal<Vec<String>>,
+    dominant: Signal<Option<String>>,
 }
 
-/// Помечает одного участника говорящим до истечения окна без новых frame.
-pub(super) fn mark_user_speaking(
-    speaking_users: Signal<Vec<SpeakingUserActivity>>,
-    speaking_generations: Rc<RefCell<HashMap<String, u64>>>,
-    user_id: String,
-) {
-    let generation = {
-        let mut generations = speaking_generations.borrow_mut();
-        let generation = generations.entry(user_id.clone()).or_insert(0);
-        *generation = generation.saturating_add(1);
-        *generation
+/// Создает индикаторы говорящих и подписывает их на события сервера об активной комнате.
+pub(super) fn use_speaking_indicators(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+) -> SpeakingIndicators {
+    let speaking = SpeakingIndicators {
+        users: use_signal(Vec::new),
+        generations: use_hook(|| Rc::new(RefCell::new(HashMap::new()))),
+        server_speakers: use_signal(Vec::new),
+        dominant: use_signal(|| None),
     };
+    use_active_speakers(state, realtime, speaking.clone());
+    speaking
+}
+
+impl SpeakingIndicators {
+    /// Возвращает идентификаторы участников, сейчас помеченных говорящими.
+    pub(super) fn user_ids(&self) -> Vec<String> {
+        let mut user_ids = (self.server_speakers)();
+        for activity in (self.users)() {
+            if !user_ids.contains(&activity.user_id) {
+                user_ids.push(activity.user_id);
+            }
+        }
+        user_ids
+    }
 
-    let mut next_users = speaking_users();
-    if next_users
-        .iter()
-        .any(|activity| activity.user_id == user_id)
-    {
-        return;
+    /// Возвращает последнего доминирующего говорящего по данным сервера.
+    ///
+    /// В паузах значение не сбрасывается, чтобы фокус оставался на последнем
+    /// говорившем, пока не заговорит кто-то другой.
+    pub(super) fn dominant_user_id(&self) -> Option<String> {
+        (self.dominant)()
     }
-    next_users.push(SpeakingUserActivity {
-        user_id: user_id.clone(),
-    });
-    let mut speaking_users = speaking_users;
-    speaking_users.set(next_users);
 
-    spawn(async move {
-        let mut observed_generation = generation;
-        loop {
-            sleep_ms(SPEAKING_RELEASE_TIMEOUT_MS).await;
-            let latest_generation = speaking_generations.borrow().get(&user_id).copied();
-            match latest_generation {
-                Some(latest_generation) if latest_generation != observed_generation => {
-                    observed_generation = latest_generation;
-                }
-                Some(_) => {
-                    speaking_generations.borrow_mut().remove(&user_id);
-                    let mut next_users = speaking_users();
-                    let previous_len = next_users.len();
-                    next_users.retain(|activity| activity.user_id != user_id);
-                    if next_users.len() != previous_len {
-                        speaking_users.set(next_users);
+    /// Применяет список говорящих, рассчитанный сервером по уровням звука.
+    pub(super) fn apply_server_speakers(
+        &self,
+        speaker_user_ids: Vec<String>,
+        dominant_user_id: Option<String>,
+    ) {
+        let mut server_speakers = self.server_speakers;
+        if *server_speakers.peek() != speaker_user_ids {
+            server_speakers.set(speaker_user_ids);
+        }
+        if dominant_user_id.is_some() && *self.dominant.peek() != dominant_user_id {
+            let mut dominant = self.dominant;
+            dominant.set(dominant_user_id);
+        }
+    }
+
+    /// Помечает одного участника говорящим до истечения окна без новых frame.
+    pub(super) fn mark_user_speaking(&self, user_id: String) {
+        let speaking_generations = self.generations.clone();
+        let generation = {
+            let mut generations = speaking_generations.borrow_mut();
+            let generation = generations.entry(user_id.clone()).or_insert(0);
+            *generation = generation.saturating_add(1);
+            *generation
+        };
+
+        let mut speaking_users = self.users;
+        let mut next_users = speaking_users();
+        if next_users
+            .iter()
+            .any(|activity| activity.user_id == user_id)
+        {
+            return;
+        }
+        next_users.push(SpeakingUserActivity {
+            user_id: user_id.clone(),
+        });
+        speaking_users.set(next_users);
+
+        spawn(async move {
+            let mut observed_generation = generation;
+            loop {
+                sleep_ms(SPEAKING_RELEASE_TIMEOUT_MS).await;
+                let latest_generation = speaking_generations.borrow().get(&user_id).copied();
+                match latest_generation {
+                    Some(latest_generation) if latest_generation != observed_generation => {
+                        observed_generation = latest_generation;
                     }
-                    break;
+                    Some(_) => {
+                        speaking_generations.borrow_mut().remove(&user_id);
+                        let mut next_users = speaking_users();
+                        let previous_len = next_users.len();
+                        next_users.retain(|activity| activity.user_id != user_id);
+                        if next_users.len() != previous_len {
+                            speaking_users.set(next_users);
+                        }
+                        break;
+                    }
+                    None => break,
                 }
-                None => break,
             }
-        }
-    });
-}
+        });
+    }
 
-/// Очищает все удаленные индикаторы речи.
-pub(super) fn clear_speaking_users(
-    speaking_users: Signal<Vec<SpeakingUserActivity>>,
-    speaking_generations: Rc<RefCell<HashMap<String, u64>>>,
-) {
-    speaking_generations.borrow_mut().clear();
-    let mut speaking_users = speaking_users;
-    speaking_users.set(Vec::new());
+    /// Очищает все удаленные индикаторы речи.
+    pub(super) fn clear(&self) {
+        self.generations.borrow_mut().clear();
+        let mut speaking_users = self.users;
+        speaking_users.set(Vec::new());
+        let mut server_speakers = self.server_speakers;
+        server_speakers.set(Vec::new());
+        let mut dominant = self.dominant;
+        dominant.set(None);
+    }
 }
@@ -1,9 +1,5 @@
 //! Shared voice connection state.
 
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::rc::Rc;
-
 use cheenhub_contracts::realtime::VoiceRoomParticipant;
 use cheenhub_contracts::rest::AuthUser;
 use dioxus::prelude::*;
@@ -15,9 +11,10 @@ use crate::features::runtime::sleep_ms;
 
 use super::realtime;
 use super::room_presence::{self, VoiceRoomParticipants};
-use super::speaking::{self, SpeakingUserActivity};
+use super::speaking::SpeakingIndicators;
 
 mod actions;
+mod moderation;
 mod status;
 mod target;
 
@@ -66,9 +63,8 @@ pub(crate) struct VoiceConnectionHandle {
     pub(crate) state: Signal<VoiceConnectionState>,
     /// Room name the user was kicked from, set when a kick is detected.
     pub(crate) kicked_from_room: Signal<Option<String>>,
-    speaking_users: Signal<Vec<SpeakingUserActivity>>,
+    speaking: SpeakingIndicators,
     room_snapshots: Signal<Vec<VoiceRoomParticipants>>,
-    speaking_generations: Rc<RefCell<HashMap<String, u64>>>,
     realtime: RealtimeHandle,
     current_user: AuthUser,
 }
@@ -78,18 +74,16 @@ impl VoiceConnectionHandle {
     pub(super) fn new(
         state: Signal<VoiceConnectionState>,
         kicked_from_room: Signal<Option<String>>,
-        speaking_users: Signal<Vec<SpeakingUserActivity>>,
+        speaking: SpeakingIndicators,
         room_snapshots: Signal<Vec<VoiceRoomParticipants>>,
-        speaking_generations: Rc<RefCell<HashMap<String, u64>>>,
         realtime: RealtimeHandle,
         current_user: AuthUser,
     ) -> Self {
         Self {
             state,
             kicked_from_room,
-            speaking_users,
+            speaking,
             room_snapshots,
-            speaking_generations,
             realtime,
             current_user,
         }
@@ -113,7 +107,12 @@ impl VoiceConnectionHandle {
 
     /// Returns user identifiers currently marked as speaking.
     pub(crate) fn speaking_user_ids(&self) -> Vec<String> {
-        speaking::user_ids(self.speaking_users)
+        self.speaking.user_ids()
+    }
+
+    /// Returns the last dominant speaker reported by the server.
+    pub(crate) fn dominant_speaker_user_id(&self) -> Option<String> {
+        self.speaking.dominant_user_id()
     }
 
     /// Returns the latest known participant list for one voice-capable room.
@@ -195,16 +194,12 @@ impl VoiceConnectionHandle {
 
     /// Marks one user as speaking until no new voice frame refreshes the marker.
     pub(crate) fn mark_user_speaking(&self, user_id: String) {
-        speaking::mark_user_speaking(
-            self.speaking_users,
-            self.speaking_generations.clone(),
-            user_id,
-        );
+        self.speaking.mark_user_speaking(user_id);
     }
 
     /// Clears all remote speaking indicators.
     pub(crate) fn clear_speaking_users(&self) {
-        speaking::clear_speaking_users(self.speaking_users, self.speaking_generations.clone());
+        self.speaking.clear();
     }
 
     /// Joins one room, leaving the previous room first when needed.
@@ -354,18 +349,6 @@ impl VoiceConnectionHandle {
         });
     }
 
-    /// Kicks one participant from the active voice room.
-    pub(crate) fn kick_member(&self, server_id: String, room_id: String, user_id: String) {
-        let realtime = self.realtime.clone();
-        spawn(async move {
-            if let Err(error) =
-                realtime::kick_voice_member(&realtime, server_id, room_id, user_id).await
-            {
-                warn!(%error, "failed to kick voice member");
-            }
-        });
-    }
-
     /// Leaves the active voice room.
     pub(crate) fn leave(&self) {
         let current = self.state();
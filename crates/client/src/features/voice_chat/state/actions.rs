@@ -52,5 +52,8 @@ pub(super) fn ensure_current_user_present(
         nickname: user.nickname.clone(),
         avatar_url: user.avatar_url.clone(),
         joined_at: String::new(),
+        connection_quality: None,
+        server_muted: false,
+        server_deafened: false,
     });
 }
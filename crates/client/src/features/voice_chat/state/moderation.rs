@@ -0,0 +1,59 @@
+//! Модерация участников активной голосовой комнаты.
+
+use dioxus::prelude::*;
+
+use super::VoiceConnectionHandle;
+use crate::features::voice_chat::realtime;
+
+impl VoiceConnectionHandle {
+    /// Kicks one participant from the active voice room.
+    pub(crate) fn kick_member(&self, server_id: String, room_id: String, user_id: String) {
+        let realtime = self.realtime.clone();
+        spawn(async move {
+            if let Err(error) =
+                realtime::kick_voice_member(&realtime, server_id, room_id, user_id).await
+            {
+                warn!(%error, "failed to kick voice member");
+            }
+        });
+    }
+
+    /// Server-mutes or unmutes one participant of the active voice room.
+    pub(crate) fn server_mute_member(
+        &self,
+        server_id: String,
+        room_id: String,
+        user_id: String,
+        muted: bool,
+    ) {
+        let realtime = self.realtime.clone();
+        spawn(async move {
+            if let Err(error) =
+                realtime::server_mute_voice_member(&realtime, server_id, room_id, user_id, muted)
+                    .await
+            {
+                warn!(%error, "failed to server-mute voice member");
+            }
+        });
+    }
+
+    /// Server-deafens or undeafens one participant of the active voice room.
+    pub(crate) fn server_deafen_member(
+        &self,
+        server_id: String,
+        room_id: String,
+        user_id: String,
+        deafened: bool,
+    ) {
+        let realtime = self.realtime.clone();
+        spawn(async move {
+            if let Err(error) = realtime::server_deafen_voice_member(
+                &realtime, server_id, room_id, user_id, deafened,
+            )
+            .await
+            {
+                warn!(%error, "failed to server-deafen voice member");
+            }
+        });
+    }
+}
@@ -40,6 +40,9 @@ pub(crate) fn VoiceRoomSurface(server_id: String, room: ActiveRoom) -> Element {
     } else {
         Vec::new()
     };
+    let dominant_speaker_user_id = is_active_room
+        .then(|| voice.dominant_speaker_user_id())
+        .flatten();
     let microphone_live = matches!(microphone.status(), MicrophoneStatus::Live);
     if is_active_room && microphone_live && microphone.level_active() {
         let current_user_id = voice.current_user_id().to_owned();
@@ -72,8 +75,10 @@ pub(crate) fn VoiceRoomSurface(server_id: String, room: ActiveRoom) -> Element {
                     room_id: room.id.clone(),
                     participants,
                     speaking_user_ids,
+                    dominant_speaker_user_id,
                     status: grid_status,
                     can_kick_voice: permissions.can_kick_voice,
+                    can_moderate_voice: permissions.can_moderate_voice,
                     on_retry: move |_| voice.join(retry_target.clone()),
                 }
             } else {
@@ -0,0 +1,82 @@
+//! Объявление видеокодеков клиента в активной голосовой комнате.
+
+use cheenhub_contracts::realtime::{
+    AdvertiseVoiceCodecCapabilities, RealtimeKind, RealtimeModule, VoiceChatKind, VoiceVideoCodec,
+    VoiceVideoCodecSelected,
+};
+use dioxus::prelude::*;
+
+use crate::features::realtime::{RealtimeError, RealtimeHandle};
+
+use super::state::{VoiceConnectionState, VoiceRoomTarget};
+use super::video_streams;
+
+/// Кодеки, которыми публикуют камера и демонстрация экрана на всех платформах.
+///
+/// Кодировщики умеют только VP9, поэтому клиент объявляет только его и комната
+/// с такими клиентами всегда выбирает VP9. Декодеры объявляются полностью:
+/// по ним сервер не пересылает видео участникам, которые не смогут его показать.
+const LOCAL_ENCODER_CODECS: [VoiceVideoCodec; 1] = [VoiceVideoCodec::Vp9];
+
+/// После входа в комнату сообщает серверу видеокодеки клиента.
+pub(super) fn use_video_codec_advertisement(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+) {
+    let mut advertised_target = use_signal(|| None::<VoiceRoomTarget>);
+    use_effect(move || {
+        let target = match state() {
+            VoiceConnectionState::Connected { target, .. } => Some(target),
+            _ => None,
+        };
+        if *advertised_target.peek() == target {
+            return;
+        }
+        advertised_target.set(target.clone());
+        let Some(target) = target else {
+            return;
+        };
+        let realtime = realtime.clone();
+        spawn(async move {
+            match advertise_capabilities(&realtime, &target).await {
+                Ok(selected) => log_selected_codec(&selected),
+                Err(error) => warn!(%error, "failed to advertise voice video codecs"),
+            }
+        });
+    });
+}
+
+async fn advertise_capabilities(
+    realtime: &RealtimeHandle,
+    target: &VoiceRoomTarget,
+) -> Result<VoiceVideoCodecSelected, RealtimeError> {
+    let decoders = video_streams::supported_decoder_codecs()
+        .await
+        .into_iter()
+        .filter_map(VoiceVideoCodec::from_media_codec)
+        .collect::<Vec<_>>();
+    debug!(?decoders, "advertising voice video codecs");
+    realtime
+        .request(
+            RealtimeModule::VoiceChat,
+            RealtimeKind::VoiceChat(VoiceChatKind::AdvertiseCodecCapabilities),
+            AdvertiseVoiceCodecCapabilities {
+                server_id: target.server_id.clone(),
+                room_id: target.room_id.clone(),
+                decoders,
+                encoders: LOCAL_ENCODER_CODECS.to_vec(),
+            },
+        )
+        .await
+}
+
+fn log_selected_codec(selected: &VoiceVideoCodecSelected) {
+    if LOCAL_ENCODER_CODECS.contains(&selected.codec) {
+        debug!(codec = ?selected.codec, "voice room video codec selected");
+    } else {
+        warn!(
+            codec = ?selected.codec,
+            "voice room selected a video codec the local encoder cannot produce; publishing VP9"
+        );
+    }
+}
@@ -6,11 +6,14 @@ use cheenhub_contracts::media::{
     MEDIA_DATAGRAM_FLAG_FRAGMENTED, MEDIA_DATAGRAM_FLAG_KEY_FRAME, MediaCodec, MediaDatagram,
     MediaDatagramKind,
 };
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
 use dioxus::prelude::{debug, warn};
 use uuid::Uuid;
 
 use crate::features::realtime::RealtimeError;
 
+use super::receiver_reports::VideoReceiveCounters;
+
 const VIDEO_FRAME_FRAGMENT_BYTES: usize = 900;
 const VIDEO_FRAME_FRAGMENT_HEADER_LEN: usize = 8;
 const VIDEO_FRAME_FRAGMENT_LOG_INTERVAL: u64 = 300;
@@ -27,6 +30,8 @@ pub(super) struct OutboundVideoFrame {
     pub(super) duration_us: u32,
     /// Может ли этот кадр открыть поток декодера.
     pub(super) key_frame: bool,
+    /// Слой simulcast, к которому относится кадр.
+    pub(super) layer: VideoSpatialLayer,
     /// Сырые байты закодированного кадра.
     pub(super) bytes: Vec<u8>,
 }
@@ -58,6 +63,8 @@ pub(super) fn video_frame_datagrams(
             duration_us: frame.duration_us,
             room_id,
             sender_user_id: Uuid::nil(),
+            spatial_layer: frame.layer,
+            audio_level: None,
             payload: frame.bytes,
         }]);
     }
@@ -111,6 +118,8 @@ pub(super) fn video_frame_datagrams(
                 duration_us: frame.duration_us,
                 room_id,
                 sender_user_id: Uuid::nil(),
+                spatial_layer: frame.layer,
+                audio_level: None,
                 payload,
             })
         })
@@ -153,6 +162,7 @@ impl VideoFrameReassembler {
             room_id: datagram.room_id,
             sender_user_id: datagram.sender_user_id,
             kind: datagram.kind,
+            spatial_layer: datagram.spatial_layer,
             sequence: datagram.sequence,
         };
         let pending = self.pending.entry(key.clone()).or_insert_with(|| {
@@ -231,20 +241,89 @@ impl VideoFrameReassembler {
 
         let sender_user_id = datagram.sender_user_id;
         let kind = datagram.kind;
+        let spatial_layer = datagram.spatial_layer;
         let sequence = datagram.sequence;
         self.pending.retain(|key, _| {
             key.sender_user_id != sender_user_id
                 || key.kind != kind
+                || key.spatial_layer != spatial_layer
                 || key.sequence.saturating_add(8) >= sequence
         });
     }
 }
 
+/// Замечает пропуски в последовательности собранных видеокадров отправителей.
+///
+/// Недособранный фрагментированный кадр никогда не доходит до декодера, поэтому
+/// пропуск номера означает, что следующие дельта-кадры ссылаются на потерянный.
+/// Смена слоя simulcast без ключевого кадра тоже оставляет декодер без опорного кадра.
+#[derive(Default)]
+pub(super) struct VideoFrameLossDetector {
+    last_frames: HashMap<VideoStreamKey, (VideoSpatialLayer, u64)>,
+    receive_stats: Option<VideoReceiveCounters>,
+}
+
+impl VideoFrameLossDetector {
+    /// Создает детектор, который дополнительно ведет счетчики для отчетов получателя.
+    pub(super) fn with_receive_stats(receive_stats: VideoReceiveCounters) -> Self {
+        Self {
+            last_frames: HashMap::new(),
+            receive_stats: Some(receive_stats),
+        }
+    }
+
+    /// Запоминает собранный кадр и возвращает, что перед ним потерялись кадры потока.
+    pub(super) fn observe(&mut self, datagram: &MediaDatagram) -> bool {
+        let key = VideoStreamKey {
+            room_id: datagram.room_id,
+            sender_user_id: datagram.sender_user_id,
+            kind: datagram.kind,
+        };
+        let key_frame = datagram.flags & MEDIA_DATAGRAM_FLAG_KEY_FRAME != 0;
+        let (lost_frames, late, follows_loss) = match self.last_frames.get_mut(&key) {
+            None => {
+                self.last_frames
+                    .insert(key, (datagram.spatial_layer, datagram.sequence));
+                (0, false, false)
+            }
+            Some((last_layer, last_sequence)) if *last_layer != datagram.spatial_layer => {
+                *last_layer = datagram.spatial_layer;
+                *last_sequence = datagram.sequence;
+                (0, false, !key_frame)
+            }
+            // Ключевой кадр с меньшим номером означает перезапуск захвата у отправителя.
+            Some((_, last_sequence)) if datagram.sequence <= *last_sequence => {
+                if key_frame {
+                    *last_sequence = datagram.sequence;
+                }
+                (0, !key_frame, false)
+            }
+            Some((_, last_sequence)) => {
+                let lost_frames = datagram.sequence - *last_sequence - 1;
+                *last_sequence = datagram.sequence;
+                (lost_frames, false, lost_frames > 0)
+            }
+        };
+        if let Some(receive_stats) = &self.receive_stats {
+            receive_stats.record(datagram.sender_user_id, lost_frames, late);
+        }
+        follows_loss
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct VideoStreamKey {
+    room_id: Uuid,
+    sender_user_id: Uuid,
+    kind: MediaDatagramKind,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct VideoFrameKey {
     room_id: Uuid,
     sender_user_id: Uuid,
     kind: MediaDatagramKind,
+    spatial_layer: VideoSpatialLayer,
     sequence: u64,
 }
 
@@ -285,77 +364,4 @@ impl VideoFrameFragment {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn reassembles_fragmented_camera_key_frame() {
-        let room_id = Uuid::new_v4();
-        let sender_user_id = Uuid::new_v4();
-        let mut reassembler = VideoFrameReassembler::default();
-        let second = fragmented_video_datagram(FragmentFixture {
-            room_id,
-            sender_user_id,
-            kind: MediaDatagramKind::CameraFrame,
-            sequence: 7,
-            flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
-            total_len: 5,
-            fragment_index: 1,
-            fragment_count: 2,
-            bytes: &[4, 5],
-        });
-        let first = fragmented_video_datagram(FragmentFixture {
-            room_id,
-            sender_user_id,
-            kind: MediaDatagramKind::CameraFrame,
-            sequence: 7,
-            flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
-            total_len: 5,
-            fragment_index: 0,
-            fragment_count: 2,
-            bytes: &[1, 2, 3],
-        });
-
-        assert!(reassembler.push(second).is_none());
-        let datagram = reassembler.push(first).expect("frame reassembles");
-
-        assert_eq!(datagram.room_id, room_id);
-        assert_eq!(datagram.sender_user_id, sender_user_id);
-        assert_eq!(datagram.kind, MediaDatagramKind::CameraFrame);
-        assert_eq!(datagram.sequence, 7);
-        assert_eq!(datagram.flags, MEDIA_DATAGRAM_FLAG_KEY_FRAME);
-        assert_eq!(datagram.payload, vec![1, 2, 3, 4, 5]);
-    }
-
-    struct FragmentFixture<'a> {
-        room_id: Uuid,
-        sender_user_id: Uuid,
-        kind: MediaDatagramKind,
-        sequence: u64,
-        flags: u8,
-        total_len: u32,
-        fragment_index: u16,
-        fragment_count: u16,
-        bytes: &'a [u8],
-    }
-
-    fn fragmented_video_datagram(fragment: FragmentFixture<'_>) -> MediaDatagram {
-        let mut payload = Vec::new();
-        payload.extend_from_slice(&fragment.total_len.to_be_bytes());
-        payload.extend_from_slice(&fragment.fragment_index.to_be_bytes());
-        payload.extend_from_slice(&fragment.fragment_count.to_be_bytes());
-        payload.extend_from_slice(fragment.bytes);
-
-        MediaDatagram {
-            kind: fragment.kind,
-            codec: MediaCodec::Vp9,
-            flags: fragment.flags | MEDIA_DATAGRAM_FLAG_FRAGMENTED,
-            sequence: fragment.sequence,
-            timestamp_us: 100,
-            duration_us: 33_333,
-            room_id: fragment.room_id,
-            sender_user_id: fragment.sender_user_id,
-            payload,
-        }
-    }
-}
+mod tests;
@@ -0,0 +1,136 @@
+use super::*;
+
+#[test]
+fn reassembles_fragmented_camera_key_frame() {
+    let room_id = Uuid::new_v4();
+    let sender_user_id = Uuid::new_v4();
+    let mut reassembler = VideoFrameReassembler::default();
+    let second = fragmented_video_datagram(FragmentFixture {
+        room_id,
+        sender_user_id,
+        kind: MediaDatagramKind::CameraFrame,
+        sequence: 7,
+        flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
+        total_len: 5,
+        fragment_index: 1,
+        fragment_count: 2,
+        bytes: &[4, 5],
+    });
+    let first = fragmented_video_datagram(FragmentFixture {
+        room_id,
+        sender_user_id,
+        kind: MediaDatagramKind::CameraFrame,
+        sequence: 7,
+        flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
+        total_len: 5,
+        fragment_index: 0,
+        fragment_count: 2,
+        bytes: &[1, 2, 3],
+    });
+
+    assert!(reassembler.push(second).is_none());
+    let datagram = reassembler.push(first).expect("frame reassembles");
+
+    assert_eq!(datagram.room_id, room_id);
+    assert_eq!(datagram.sender_user_id, sender_user_id);
+    assert_eq!(datagram.kind, MediaDatagramKind::CameraFrame);
+    assert_eq!(datagram.sequence, 7);
+    assert_eq!(datagram.flags, MEDIA_DATAGRAM_FLAG_KEY_FRAME);
+    assert_eq!(datagram.payload, vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn loss_detector_reports_sequence_gaps_per_stream() {
+    let room_id = Uuid::new_v4();
+    let sender_user_id = Uuid::new_v4();
+    let mut detector = VideoFrameLossDetector::default();
+    let frame = |kind, sequence, flags| MediaDatagram {
+        kind,
+        codec: MediaCodec::Vp9,
+        flags,
+        sequence,
+        timestamp_us: 100,
+        duration_us: 33_333,
+        room_id,
+        sender_user_id,
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload: vec![1],
+    };
+
+    assert!(!detector.observe(&frame(MediaDatagramKind::CameraFrame, 4, 0)));
+    assert!(!detector.observe(&frame(MediaDatagramKind::CameraFrame, 5, 0)));
+    assert!(!detector.observe(&frame(MediaDatagramKind::ScreenFrame, 9, 0)));
+    assert!(detector.observe(&frame(MediaDatagramKind::CameraFrame, 7, 0)));
+    assert!(!detector.observe(&frame(MediaDatagramKind::CameraFrame, 6, 0)));
+    assert!(!detector.observe(&frame(
+        MediaDatagramKind::CameraFrame,
+        0,
+        MEDIA_DATAGRAM_FLAG_KEY_FRAME
+    )));
+    assert!(!detector.observe(&frame(MediaDatagramKind::CameraFrame, 1, 0)));
+}
+
+#[test]
+fn loss_detector_requires_key_frame_after_layer_switch() {
+    let room_id = Uuid::new_v4();
+    let sender_user_id = Uuid::new_v4();
+    let mut detector = VideoFrameLossDetector::default();
+    let frame = |spatial_layer, sequence, flags| MediaDatagram {
+        kind: MediaDatagramKind::CameraFrame,
+        codec: MediaCodec::Vp9,
+        flags,
+        sequence,
+        timestamp_us: 100,
+        duration_us: 33_333,
+        room_id,
+        sender_user_id,
+        spatial_layer,
+        audio_level: None,
+        payload: vec![1],
+    };
+
+    assert!(!detector.observe(&frame(VideoSpatialLayer::High, 10, 0)));
+    assert!(detector.observe(&frame(VideoSpatialLayer::Low, 11, 0)));
+    assert!(!detector.observe(&frame(VideoSpatialLayer::Low, 12, 0)));
+    assert!(!detector.observe(&frame(
+        VideoSpatialLayer::High,
+        3,
+        MEDIA_DATAGRAM_FLAG_KEY_FRAME
+    )));
+    assert!(!detector.observe(&frame(VideoSpatialLayer::High, 4, 0)));
+}
+
+struct FragmentFixture<'a> {
+    room_id: Uuid,
+    sender_user_id: Uuid,
+    kind: MediaDatagramKind,
+    sequence: u64,
+    flags: u8,
+    total_len: u32,
+    fragment_index: u16,
+    fragment_count: u16,
+    bytes: &'a [u8],
+}
+
+fn fragmented_video_datagram(fragment: FragmentFixture<'_>) -> MediaDatagram {
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&fragment.total_len.to_be_bytes());
+    payload.extend_from_slice(&fragment.fragment_index.to_be_bytes());
+    payload.extend_from_slice(&fragment.fragment_count.to_be_bytes());
+    payload.extend_from_slice(fragment.bytes);
+
+    MediaDatagram {
+        kind: fragment.kind,
+        codec: MediaCodec::Vp9,
+        flags: fragment.flags | MEDIA_DATAGRAM_FLAG_FRAGMENTED,
+        sequence: fragment.sequence,
+        timestamp_us: 100,
+        duration_us: 33_333,
+        room_id: fragment.room_id,
+        sender_user_id: fragment.sender_user_id,
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload,
+    }
+}
@@ -9,17 +9,20 @@ use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use cheenhub_contracts::media::MediaCodec;
 use dioxus::dioxus_core::spawn_forever;
 use dioxus::prelude::*;
 use futures_channel::mpsc;
 use futures_util::StreamExt;
 use uuid::Uuid;
 
-use crate::features::camera::EncodedCameraFrame;
+use crate::features::camera::{CameraCodec, EncodedCameraFrame};
 use crate::features::runtime::sleep_ms;
 
 use self::backend::{ParticipantVideoBackend, ParticipantVideoRenderer};
+use super::key_frames::KeyFrameRequester;
 use super::realtime::InboundVideoFrame;
+use super::video_subscriptions::VideoSubscriptionRequester;
 
 const PARTICIPANT_VIDEO_RELEASE_TIMEOUT_MS: u32 = 1_500;
 
@@ -28,6 +31,11 @@ type ParticipantVideoSubscribers =
 type ParticipantVideoGenerations = Rc<RefCell<HashMap<ParticipantVideoKey, u64>>>;
 type ParticipantVideoBlockedStreams = Rc<RefCell<HashSet<ParticipantVideoKey>>>;
 
+/// Возвращает видеокодеки, которые умеет декодировать renderer текущей платформы.
+pub(super) async fn supported_decoder_codecs() -> Vec<MediaCodec> {
+    native::supported_decoder_codecs().await
+}
+
 /// Тип видеопотока внутри плитки участника.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum ParticipantVideoSource {
@@ -67,10 +75,14 @@ pub(crate) struct ParticipantVideoFrame {
     pub(crate) timestamp_us: u64,
     /// Длительность кадра в микросекундах.
     pub(crate) duration_us: u32,
-    /// Сырые байты закодированного VP9 кадра.
+    /// Видеокодек кадра.
+    pub(crate) codec: MediaCodec,
+    /// Сырые байты закодированного кадра.
     pub(crate) bytes: Vec<u8>,
     /// Может ли этот кадр открыть поток декодера.
     pub(crate) key_frame: bool,
+    /// Потерялись ли предыдущие кадры этого потока по дороге.
+    pub(crate) follows_frame_loss: bool,
 }
 
 impl ParticipantVideoFrame {
@@ -86,8 +98,12 @@ impl ParticipantVideoFrame {
             sequence: frame.sequence,
             timestamp_us: frame.timestamp_us,
             duration_us: frame.duration_us,
+            codec: match frame.codec {
+                CameraCodec::Vp9 => MediaCodec::Vp9,
+            },
             bytes: frame.bytes,
             key_frame: frame.key_frame,
+            follows_frame_loss: false,
         }
     }
 }
@@ -100,8 +116,10 @@ impl From<InboundVideoFrame> for ParticipantVideoFrame {
             sequence: frame.sequence,
             timestamp_us: frame.timestamp_us,
             duration_us: frame.duration_us,
+            codec: frame.codec,
             bytes: frame.bytes,
             key_frame: frame.key_frame,
+            follows_frame_loss: frame.follows_frame_loss,
         }
     }
 }
@@ -120,6 +138,8 @@ pub(crate) struct ParticipantVideoHandle {
     subscribers: ParticipantVideoSubscribers,
     generations: ParticipantVideoGenerations,
     blocked_streams: ParticipantVideoBlockedStreams,
+    key_frames: KeyFrameRequester,
+    subscriptions: VideoSubscriptionRequester,
     backend: Rc<dyn ParticipantVideoBackend>,
 }
 
@@ -130,12 +150,16 @@ impl ParticipantVideoHandle {
         subscribers: ParticipantVideoSubscribers,
         generations: ParticipantVideoGenerations,
         blocked_streams: ParticipantVideoBlockedStreams,
+        key_frames: KeyFrameRequester,
+        subscriptions: VideoSubscriptionRequester,
     ) -> Self {
         Self {
             live_streams,
             subscribers,
             generations,
             blocked_streams,
+            key_frames,
+            subscriptions,
             backend: native::default_backend(),
         }
     }
@@ -156,7 +180,17 @@ impl ParticipantVideoHandle {
         frame: ParticipantVideoFrame,
     ) {
         let key = ParticipantVideoKey::new(source, frame.sender_user_id.clone());
+        if frame.follows_frame_loss && !frame.key_frame {
+            debug!(
+                user_id = %key.user_id,
+                source = key.source.label(),
+                sequence = frame.sequence,
+                "participant video frames were lost, waiting for key frame"
+            );
+            self.blocked_streams.borrow_mut().insert(key.clone());
+        }
         if self.should_drop_blocked_frame(&key, frame.key_frame) {
+            self.key_frames.request(source, &key.user_id);
             debug!(
                 user_id = %key.user_id,
                 source = key.source.label(),
@@ -173,6 +207,11 @@ impl ParticipantVideoHandle {
         stream_subscribers.retain(|subscriber| subscriber.unbounded_send(frame.clone()).is_ok());
     }
 
+    /// Продлевает индикатор активности потока, кадры которого relay сейчас не пересылает.
+    pub(crate) fn mark_stream_active(&self, source: ParticipantVideoSource, user_id: String) {
+        self.mark_live(ParticipantVideoKey::new(source, user_id));
+    }
+
     /// Немедленно освобождает индикатор активности одного видеопотока.
     pub(crate) fn release_stream(&self, source: ParticipantVideoSource, user_id: &str) {
         let key = ParticipantVideoKey::new(source, user_id.to_owned());
@@ -196,11 +235,21 @@ impl ParticipantVideoHandle {
     }
 
     /// Подписывает плитку участника на один видеопоток.
+    ///
+    /// Первая видимая плитка потока подписывает сессию на кадры relay. Новому
+    /// зрителю нужен ключевой кадр, поэтому подписка сразу запрашивает его у
+    /// отправителя, не дожидаясь резервного интервала кодировщика.
     pub(crate) fn subscribe_stream(
         &self,
         source: ParticipantVideoSource,
         user_id: String,
     ) -> mpsc::UnboundedReceiver<ParticipantVideoFrame> {
+        if self.subscriptions.acquire(source, &user_id) {
+            self.blocked_streams
+                .borrow_mut()
+                .insert(ParticipantVideoKey::new(source, user_id.clone()));
+        }
+        self.key_frames.request(source, &user_id);
         let (sender, receiver) = mpsc::unbounded();
         self.subscribers
             .borrow_mut()
@@ -211,6 +260,11 @@ impl ParticipantVideoHandle {
         receiver
     }
 
+    /// Отписывает скрытую плитку участника от видеопотока.
+    pub(crate) fn unsubscribe_stream(&self, source: ParticipantVideoSource, user_id: &str) {
+        self.subscriptions.release(source, user_id);
+    }
+
     /// Очищает индикаторы активных видеоисточников.
     pub(crate) fn clear(&self) {
         self.generations.borrow_mut().clear();
@@ -326,6 +380,11 @@ pub(crate) fn ParticipantVideoCanvas(user_id: String, source: ParticipantVideoSo
         move || format!("{}-{}", source.id_prefix(), Uuid::new_v4().simple())
     });
     let mut waiting_for_key_frame = use_signal(|| true);
+    use_drop({
+        let video = video.clone();
+        let user_id = user_id.clone();
+        move || video.unsubscribe_stream(source, &user_id)
+    });
 
     use_hook({
         let target_id = target_id.clone();
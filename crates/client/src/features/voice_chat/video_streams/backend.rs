@@ -19,7 +19,7 @@ pub(crate) trait ParticipantVideoBackend {
 
 /// Платформенный renderer одного видеопотока участника.
 pub(crate) trait ParticipantVideoRenderer {
-    /// Декодирует и рендерит один входящий закодированный VP9 кадр.
+    /// Декодирует и рендерит один входящий закодированный кадр, перенастраивая decoder под его кодек.
     fn decode(&self, frame: &ParticipantVideoFrame) -> Result<(), ParticipantVideoRenderError>;
 
     /// Освобождает платформенный decoder и ресурсы рендеринга.
@@ -2,6 +2,8 @@
 
 use std::rc::Rc;
 
+use cheenhub_contracts::media::MediaCodec;
+
 use super::backend::ParticipantVideoBackend;
 
 /// Возвращает backend renderer'а видео участников для текущей платформы.
@@ -18,3 +20,18 @@ fn default_backend_platform() -> Rc<dyn ParticipantVideoBackend> {
 fn default_backend_platform() -> Rc<dyn ParticipantVideoBackend> {
     Rc::new(super::unsupported::UnavailableParticipantVideoBackend)
 }
+
+/// Возвращает видеокодеки renderer'а текущей платформы; без renderer'а список пуст.
+pub(super) async fn supported_decoder_codecs() -> Vec<MediaCodec> {
+    supported_decoder_codecs_platform().await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn supported_decoder_codecs_platform() -> Vec<MediaCodec> {
+    super::web::supported_decoder_codecs().await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn supported_decoder_codecs_platform() -> Vec<MediaCodec> {
+    Vec::new()
+}
@@ -4,6 +4,7 @@
 use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+use cheenhub_contracts::media::MediaCodec;
 use dioxus::prelude::{debug, warn};
 use gloo_timers::future::TimeoutFuture;
 use js_sys::{Array, Function, Object, Reflect, Uint8Array};
@@ -41,6 +42,7 @@ struct BrowserParticipantVideoRenderer {
     canvas: Rc<RefCell<Option<JsValue>>>,
     source_label: &'static str,
     closed: Rc<Cell<bool>>,
+    configured_codec: Cell<Option<MediaCodec>>,
     received_key_frame: Rc<Cell<bool>>,
     waiting_key_frame_logged: Rc<Cell<bool>>,
     output_closure: RefCell<Option<VideoOutputClosure>>,
@@ -108,13 +110,13 @@ impl BrowserParticipantVideoRenderer {
         Reflect::set(&init, &JsValue::from_str("error"), error_closure.as_ref())
             .map_err(render_error)?;
         let decoder = VideoDecoder::new(&init.into()).map_err(render_error)?;
-        decoder.configure(&decoder_config()).map_err(render_error)?;
 
         Ok(Self {
             decoder,
             canvas,
             source_label,
             closed,
+            configured_codec: Cell::new(None),
             received_key_frame: Rc::new(Cell::new(false)),
             waiting_key_frame_logged: Rc::new(Cell::new(false)),
             output_closure: RefCell::new(Some(output_closure)),
@@ -131,7 +133,8 @@ impl ParticipantVideoRenderer for BrowserParticipantVideoRenderer {
         if frame.bytes.is_empty() {
             return Ok(());
         }
-        if !self.received_key_frame.get() && !frame.key_frame {
+        let codec_matches = self.configured_codec.get() == Some(frame.codec);
+        if (!self.received_key_frame.get() || !codec_matches) && !frame.key_frame {
             if !self.waiting_key_frame_logged.replace(true) {
                 debug!(
                     sender_user_id = %frame.sender_user_id,
@@ -142,6 +145,20 @@ impl ParticipantVideoRenderer for BrowserParticipantVideoRenderer {
             }
             return Ok(());
         }
+        if !codec_matches {
+            // Смена кодека комнаты приходит key frame'ом; configure сбрасывает очередь decoder'а.
+            self.decoder
+                .configure(&decoder_config(frame.codec))
+                .map_err(render_error)?;
+            self.configured_codec.set(Some(frame.codec));
+            self.received_key_frame.set(false);
+            debug!(
+                sender_user_id = %frame.sender_user_id,
+                codec = ?frame.codec,
+                source = self.source_label,
+                "configured participant video decoder"
+            );
+        }
         if frame.key_frame && !self.received_key_frame.replace(true) {
             debug!(
                 sender_user_id = %frame.sender_user_id,
@@ -204,12 +221,56 @@ fn defer_video_callbacks_drop(
     });
 }
 
-fn decoder_config() -> JsValue {
+fn decoder_config(codec: MediaCodec) -> JsValue {
     let object = Object::new();
-    set_property(&object, "codec", &JsValue::from_str("vp09.00.10.08"));
+    set_property(
+        &object,
+        "codec",
+        &JsValue::from_str(web_codecs_codec(codec)),
+    );
     object.into()
 }
 
+/// Строка кодека WebCodecs; H.264 без `description` декодируется как Annex B.
+fn web_codecs_codec(codec: MediaCodec) -> &'static str {
+    match codec {
+        MediaCodec::Av1 => "av01.0.08M.08",
+        MediaCodec::H264 => "avc1.640028",
+        MediaCodec::Vp9 | MediaCodec::Opus => "vp09.00.10.08",
+    }
+}
+
+/// Проверяет через `VideoDecoder.isConfigSupported`, какие видеокодеки декодирует браузер.
+pub(super) async fn supported_decoder_codecs() -> Vec<MediaCodec> {
+    let mut supported = Vec::new();
+    for codec in [MediaCodec::Av1, MediaCodec::Vp9, MediaCodec::H264] {
+        match is_decoder_config_supported(codec).await {
+            Ok(true) => supported.push(codec),
+            Ok(false) => {}
+            Err(error) => warn!(
+                error = %js_error_message(error),
+                ?codec,
+                "failed to probe participant video decoder support"
+            ),
+        }
+    }
+    supported
+}
+
+async fn is_decoder_config_supported(codec: MediaCodec) -> Result<bool, JsValue> {
+    let constructor = Reflect::get(&js_sys::global(), &JsValue::from_str("VideoDecoder"))?;
+    if constructor.is_undefined() {
+        return Ok(false);
+    }
+    let is_config_supported = Reflect::get(&constructor, &JsValue::from_str("isConfigSupported"))?
+        .dyn_into::<Function>()?;
+    let promise = is_config_supported
+        .call1(&constructor, &decoder_config(codec))?
+        .dyn_into::<js_sys::Promise>()?;
+    let support = wasm_bindgen_futures::JsFuture::from(promise).await?;
+    Ok(Reflect::get(&support, &JsValue::from_str("supported"))?.is_truthy())
+}
+
 fn encoded_video_chunk(frame: &ParticipantVideoFrame) -> Result<EncodedVideoChunk, JsValue> {
     let data = Uint8Array::from(frame.bytes.as_slice());
     let init = Object::new();
@@ -0,0 +1,328 @@
+//! Подписки на видеопотоки участников, плитки которых сейчас видны.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use cheenhub_contracts::realtime::{
+    RealtimeEnvelope, RealtimeKind, RealtimeModule, SubscribeVoiceVideo, UnsubscribeVoiceVideo,
+    VoiceChatKind, VoiceVideoStreamActive,
+};
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
+use dioxus::prelude::*;
+use futures_channel::mpsc;
+use futures_util::StreamExt;
+
+use crate::features::realtime::RealtimeHandle;
+
+use super::key_frames::contract_source;
+use super::local_video::participant_source_from_contract;
+use super::state::VoiceConnectionState;
+use super::video_streams::{ParticipantVideoHandle, ParticipantVideoSource};
+
+type VideoSubscriptionCounts = Rc<RefCell<HashMap<(ParticipantVideoSource, String), usize>>>;
+
+/// Сколько чужих камер может быть на экране, пока relay пересылает их в полном разрешении.
+const MAX_HIGH_LAYER_CAMERAS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VideoSubscriptionChange {
+    Subscribe(VideoSpatialLayer),
+    Unsubscribe,
+}
+
+/// Очередь подписок текущей сессии на видеопотоки участников активной комнаты.
+///
+/// Relay пересылает кадры только подписанным сессиям, поэтому подписка живет,
+/// пока на экране есть хотя бы одна плитка с этим потоком. Когда в сетке много
+/// камер, подписки переключаются на уменьшенный simulcast-слой.
+#[derive(Clone)]
+pub(crate) struct VideoSubscriptionRequester {
+    sender: mpsc::UnboundedSender<(ParticipantVideoSource, String, VideoSubscriptionChange)>,
+    current_user_id: String,
+    counts: VideoSubscriptionCounts,
+}
+
+impl VideoSubscriptionRequester {
+    /// Учитывает видимую плитку потока и возвращает, подписала ли она сессию на кадры.
+    pub(super) fn acquire(&self, source: ParticipantVideoSource, user_id: &str) -> bool {
+        if user_id == self.current_user_id {
+            return false;
+        }
+        let mut counts = self.counts.borrow_mut();
+        let previous_layer = camera_layer(&counts);
+        let count = counts.entry((source, user_id.to_owned())).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            return false;
+        }
+        let layer = self.resubscribe_cameras(&counts, previous_layer, Some(user_id));
+        let layer = match source {
+            ParticipantVideoSource::Camera => layer,
+            ParticipantVideoSource::ScreenShare => VideoSpatialLayer::High,
+        };
+        let _ = self.sender.unbounded_send((
+            source,
+            user_id.to_owned(),
+            VideoSubscriptionChange::Subscribe(layer),
+        ));
+        true
+    }
+
+    /// Снимает учет скрытой плитки; последняя плитка потока отписывает сессию.
+    pub(super) fn release(&self, source: ParticipantVideoSource, user_id: &str) {
+        if user_id == self.current_user_id {
+            return;
+        }
+        let key = (source, user_id.to_owned());
+        let mut counts = self.counts.borrow_mut();
+        let Some(count) = counts.get_mut(&key) else {
+            return;
+        };
+        *count = count.saturating_sub(1);
+        if *count > 0 {
+            return;
+        }
+        let previous_layer = camera_layer(&counts);
+        counts.remove(&key);
+        let _ = self.sender.unbounded_send((
+            source,
+            user_id.to_owned(),
+            VideoSubscriptionChange::Unsubscribe,
+        ));
+        self.resubscribe_cameras(&counts, previous_layer, None);
+    }
+
+    /// Переподписывает видимые камеры, если их число пересекло порог полного слоя.
+    fn resubscribe_cameras(
+        &self,
+        counts: &HashMap<(ParticipantVideoSource, String), usize>,
+        previous_layer: VideoSpatialLayer,
+        skipped_user_id: Option<&str>,
+    ) -> VideoSpatialLayer {
+        let layer = camera_layer(counts);
+        if layer == previous_layer {
+            return layer;
+        }
+        for (source, user_id) in counts.keys() {
+            if *source != ParticipantVideoSource::Camera
+                || Some(user_id.as_str()) == skipped_user_id
+            {
+                continue;
+            }
+            let _ = self.sender.unbounded_send((
+                *source,
+                user_id.clone(),
+                VideoSubscriptionChange::Subscribe(layer),
+            ));
+        }
+        layer
+    }
+}
+
+fn camera_layer(counts: &HashMap<(ParticipantVideoSource, String), usize>) -> VideoSpatialLayer {
+    let cameras = counts
+        .keys()
+        .filter(|(source, _)| *source == ParticipantVideoSource::Camera)
+        .count();
+    if cameras > MAX_HIGH_LAYER_CAMERAS {
+        VideoSpatialLayer::Low
+    } else {
+        VideoSpatialLayer::High
+    }
+}
+
+/// Отправляет на relay подписки и отписки видимых видеоплиток.
+pub(super) fn use_video_subscriptions(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+    current_user_id: String,
+) -> VideoSubscriptionRequester {
+    use_hook(move || {
+        let (sender, mut changes) =
+            mpsc::unbounded::<(ParticipantVideoSource, String, VideoSubscriptionChange)>();
+        spawn(async move {
+            while let Some((source, user_id, change)) = changes.next().await {
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                let result = match change {
+                    VideoSubscriptionChange::Subscribe(layer) => {
+                        realtime
+                            .send_reliable(
+                                RealtimeModule::VoiceChat,
+                                RealtimeKind::VoiceChat(VoiceChatKind::SubscribeVideo),
+                                SubscribeVoiceVideo {
+                                    server_id: target.server_id,
+                                    room_id: target.room_id,
+                                    user_id: user_id.clone(),
+                                    source: contract_source(source),
+                                    layer,
+                                },
+                            )
+                            .await
+                    }
+                    VideoSubscriptionChange::Unsubscribe => {
+                        realtime
+                            .send_reliable(
+                                RealtimeModule::VoiceChat,
+                                RealtimeKind::VoiceChat(VoiceChatKind::UnsubscribeVideo),
+                                UnsubscribeVoiceVideo {
+                                    server_id: target.server_id,
+                                    room_id: target.room_id,
+                                    user_id: user_id.clone(),
+                                    source: contract_source(source),
+                                },
+                            )
+                            .await
+                    }
+                };
+                match result {
+                    Ok(()) => debug!(
+                        %user_id,
+                        source = source.label(),
+                        change = ?change,
+                        "updated participant video subscription"
+                    ),
+                    Err(error) => warn!(
+                        %error,
+                        %user_id,
+                        source = source.label(),
+                        change = ?change,
+                        "failed to update participant video subscription"
+                    ),
+                }
+            }
+        });
+        VideoSubscriptionRequester {
+            sender,
+            current_user_id,
+            counts: Rc::new(RefCell::new(HashMap::new())),
+        }
+    })
+}
+
+/// Отмечает активными видеопотоки, о которых relay сообщает неподписанным зрителям.
+pub(super) fn use_video_stream_activity(
+    state: Signal<VoiceConnectionState>,
+    realtime: RealtimeHandle,
+    current_user_id: String,
+    participant_video: ParticipantVideoHandle,
+) {
+    use_hook(move || {
+        spawn(async move {
+            let mut events = realtime.subscribe_events();
+            while let Some(envelope) = events.next().await {
+                let Some(event) = video_stream_active(envelope) else {
+                    continue;
+                };
+                let Some(target) = state.peek().active_target() else {
+                    continue;
+                };
+                if event.server_id != target.server_id
+                    || event.room_id != target.room_id
+                    || event.user_id == current_user_id
+                {
+                    continue;
+                }
+                participant_video.mark_stream_active(
+                    participant_source_from_contract(event.source),
+                    event.user_id,
+                );
+            }
+        })
+    });
+}
+
+fn video_stream_active(envelope: RealtimeEnvelope) -> Option<VoiceVideoStreamActive> {
+    if envelope.module != RealtimeModule::VoiceChat
+        || envelope.kind != RealtimeKind::VoiceChat(VoiceChatKind::VideoStreamActive)
+    {
+        return None;
+    }
+
+    serde_json::from_value(envelope.payload).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stream_subscription_follows_first_and_last_visible_tile() {
+        let (sender, mut changes) = mpsc::unbounded();
+        let requester = VideoSubscriptionRequester {
+            sender,
+            current_user_id: "self".to_owned(),
+            counts: Rc::new(RefCell::new(HashMap::new())),
+        };
+
+        assert!(!requester.acquire(ParticipantVideoSource::Camera, "self"));
+        assert!(requester.acquire(ParticipantVideoSource::Camera, "peer"));
+        assert!(!requester.acquire(ParticipantVideoSource::Camera, "peer"));
+        requester.release(ParticipantVideoSource::Camera, "peer");
+        requester.release(ParticipantVideoSource::Camera, "peer");
+        requester.release(ParticipantVideoSource::Camera, "self");
+
+        let mut sent = Vec::new();
+        while let Ok(change) = changes.try_recv() {
+            sent.push(change);
+        }
+        assert_eq!(
+            sent,
+            vec![
+                (
+                    ParticipantVideoSource::Camera,
+                    "peer".to_owned(),
+                    VideoSubscriptionChange::Subscribe(VideoSpatialLayer::High)
+                ),
+                (
+                    ParticipantVideoSource::Camera,
+                    "peer".to_owned(),
+                    VideoSubscriptionChange::Unsubscribe
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn crowded_camera_grid_switches_to_low_layer() {
+        let (sender, mut changes) = mpsc::unbounded();
+        let requester = VideoSubscriptionRequester {
+            sender,
+            current_user_id: "self".to_owned(),
+            counts: Rc::new(RefCell::new(HashMap::new())),
+        };
+
+        assert!(requester.acquire(ParticipantVideoSource::Camera, "a"));
+        assert!(requester.acquire(ParticipantVideoSource::Camera, "b"));
+        assert!(requester.acquire(ParticipantVideoSource::ScreenShare, "a"));
+        assert!(requester.acquire(ParticipantVideoSource::Camera, "c"));
+        requester.release(ParticipantVideoSource::Camera, "c");
+
+        let mut sent = Vec::new();
+        while let Ok(change) = changes.try_recv() {
+            sent.push(change);
+        }
+        let layers_of = |user_id: &str| {
+            sent.iter()
+                .filter(|(source, id, _)| {
+                    *source == ParticipantVideoSource::Camera && id == user_id
+                })
+                .map(|(_, _, change)| *change)
+                .collect::<Vec<_>>()
+        };
+        use VideoSpatialLayer::{High, Low};
+        use VideoSubscriptionChange::{Subscribe, Unsubscribe};
+        assert_eq!(
+            layers_of("a"),
+            vec![Subscribe(High), Subscribe(Low), Subscribe(High)]
+        );
+        assert_eq!(layers_of("c"), vec![Subscribe(Low), Unsubscribe]);
+        assert!(sent.contains(&(
+            ParticipantVideoSource::ScreenShare,
+            "a".to_owned(),
+            Subscribe(High)
+        )));
+    }
+}
@@ -220,6 +220,7 @@ mod tests {
             sample_rate_hz: 48_000,
             channels: 1,
             bytes: vec![1, 2, 3],
+            audio_level: None,
         }
     }
 }
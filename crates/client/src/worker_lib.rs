@@ -1,7 +1,7 @@
 #![warn(missing_docs)]
 //! Библиотечная точка входа для wasm-воркеров web-клиента.
 
-#[path = "features/microphone/core.rs"]
+#[path = "features/microphone/core/mod.rs"]
 mod microphone_core;
 
 pub mod workers;
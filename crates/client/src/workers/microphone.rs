@@ -1,20 +1,23 @@
 //! wasm API worker микрофона для обработки PCM и кодирования protocol frames.
 
-use cheenhub_contracts::media::{MediaCodec, MediaDatagram, MediaDatagramKind};
+use cheenhub_contracts::media::{
+    MediaCodec, MediaDatagram, MediaDatagramKind, audio_level_from_rms,
+};
 use cheenhub_contracts::realtime::{
     Authenticate, BindMicrophoneUplink, ControlKind, RealtimeEnvelope, RealtimeKind,
     RealtimeModule, VoiceChatKind,
 };
+use cheenhub_contracts::video_presets::VideoSpatialLayer;
 use js_sys::{Float32Array, Object, Reflect, Uint8Array};
 use uuid::Uuid;
 use wasm_bindgen::prelude::*;
 
 use crate::microphone_core::{
-    CoreActivationMode, VoiceActivationConfig, VoiceActivityDetector, apply_input_gain,
-    duration_us, rms_level,
+    AudioProcessingChain, AudioProcessingConfig, CoreActivationMode, VoiceActivationConfig,
+    VoiceActivityDetector, apply_input_gain, duration_us, rms_level,
 };
 
-const MICROPHONE_WORKER_ABI_VERSION: u32 = 3;
+const MICROPHONE_WORKER_ABI_VERSION: u32 = 5;
 
 /// Возвращает версию ABI worker микрофона.
 #[wasm_bindgen]
@@ -56,10 +59,12 @@ pub fn microphone_worker_bind_uplink_websocket_message(grant: &str) -> Result<St
 #[wasm_bindgen]
 pub struct MicrophoneWorkerProcessor {
     detector: VoiceActivityDetector,
+    processing: AudioProcessingChain,
     sample_rate_hz: u32,
     input_gain: f32,
     room_id: Uuid,
     sequence: u64,
+    audio_level: Option<u8>,
 }
 
 #[wasm_bindgen]
@@ -74,6 +79,7 @@ impl MicrophoneWorkerProcessor {
         vad_threshold: f32,
         vad_activation_delay_us: u32,
         vad_release_delay_us: u32,
+        push_to_talk_release_delay_us: u32,
         input_gain: f32,
         room_id: &str,
     ) -> Result<MicrophoneWorkerProcessor, JsValue> {
@@ -85,6 +91,8 @@ impl MicrophoneWorkerProcessor {
         let mode = match activation_mode {
             "always_active" => CoreActivationMode::AlwaysActive,
             "voice_activated" => CoreActivationMode::VoiceActivated,
+            "push_to_talk" => CoreActivationMode::PushToTalk,
+            "push_to_mute" => CoreActivationMode::PushToMute,
             value => {
                 return Err(js_error(format!(
                     "unsupported microphone activation mode: {value}"
@@ -96,16 +104,45 @@ impl MicrophoneWorkerProcessor {
             threshold: vad_threshold,
             activation_delay_us: vad_activation_delay_us,
             release_delay_us: vad_release_delay_us,
+            key_release_delay_us: push_to_talk_release_delay_us,
         });
         Ok(Self {
             detector,
+            processing: AudioProcessingChain::new(AudioProcessingConfig::default(), sample_rate_hz),
             sample_rate_hz,
             input_gain,
             room_id,
             sequence: 0,
+            audio_level: None,
         })
     }
 
+    /// Обновляет состояние клавиши push-to-talk.
+    pub fn set_push_to_talk_pressed(&mut self, pressed: bool) {
+        self.detector.set_key_pressed(pressed);
+    }
+
+    /// Включает стадии обработки PCM перед voice activation.
+    pub fn set_processing(
+        &mut self,
+        high_pass: bool,
+        echo_suppression: bool,
+        noise_suppression: bool,
+        noise_gate: bool,
+        auto_gain: bool,
+    ) {
+        self.processing = AudioProcessingChain::new(
+            AudioProcessingConfig {
+                high_pass,
+                echo_suppression,
+                noise_suppression,
+                noise_gate,
+                auto_gain,
+            },
+            self.sample_rate_hz,
+        );
+    }
+
     /// Обрабатывает PCM chunk и возвращает level/VAD результат.
     pub fn process_pcm(
         &mut self,
@@ -114,9 +151,11 @@ impl MicrophoneWorkerProcessor {
     ) -> Result<JsValue, JsValue> {
         let mut samples = samples.to_vec();
         apply_input_gain(&mut samples, self.input_gain);
+        self.processing.process(&mut samples, 0.0);
         let duration_us = duration_us(samples.len(), self.sample_rate_hz);
         let rms = rms_level(&samples);
         let active = self.detector.update(rms, duration_us);
+        self.audio_level = Some(audio_level_from_rms(rms));
         let output = Object::new();
         set_property(&output, "rms", JsValue::from_f64(f64::from(rms)))?;
         set_property(&output, "active", JsValue::from_bool(active))?;
@@ -163,6 +202,8 @@ impl MicrophoneWorkerProcessor {
             duration_us: duration_us.max(0.0).min(f64::from(u32::MAX)) as u32,
             room_id: self.room_id,
             sender_user_id: Uuid::nil(),
+            spatial_layer: VideoSpatialLayer::High,
+            audio_level: self.audio_level,
             payload: payload.to_vec(),
         };
         self.sequence = self.sequence.saturating_add(1);
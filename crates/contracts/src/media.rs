@@ -2,9 +2,14 @@
 
 use uuid::Uuid;
 
+use crate::video_presets::VideoSpatialLayer;
+
 const MAGIC: &[u8; 4] = b"CHUB";
-const VERSION: u8 = 1;
-const HEADER_LEN: usize = 64;
+/// Версия без идентификатора слоя: такие датаграммы несут единственный слой `High`.
+const VERSION_SINGLE_LAYER: u8 = 1;
+const VERSION: u8 = 2;
+const SINGLE_LAYER_HEADER_LEN: usize = 64;
+const HEADER_LEN: usize = 65;
 
 /// Флаг медиадатаграммы, когда закодированная полезная нагрузка является независимо декодируемым ключевым кадром.
 pub const MEDIA_DATAGRAM_FLAG_KEY_FRAME: u8 = 0b0000_0001;
@@ -12,6 +17,29 @@ pub const MEDIA_DATAGRAM_FLAG_KEY_FRAME: u8 = 0b0000_0001;
 /// Флаг медиадатаграммы, когда полезная нагрузка несет один фрагмент более крупного медиакадра.
 pub const MEDIA_DATAGRAM_FLAG_FRAGMENTED: u8 = 0b0000_0010;
 
+/// Флаг голосовой датаграммы, когда за заголовком следует байт уровня звука отправителя.
+pub const MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL: u8 = 0b0000_0100;
+
+/// Уровень звука тишины: 127 дБ ниже полной шкалы, как в RFC 6464.
+pub const AUDIO_LEVEL_SILENCE: u8 = 127;
+
+/// Отправитель голосовых датаграмм, которые сервер свел из нескольких говорящих.
+///
+/// Этим отправителем приходит общая смесь, одна для всех слушателей.
+pub const MIXED_VOICE_SENDER_USER_ID: Uuid = Uuid::from_u128(u128::MAX);
+
+/// Отправитель личной смеси говорящего без его собственного голоса.
+///
+/// Личная смесь кодируется отдельным кодировщиком, поэтому клиент декодирует
+/// ее отдельным декодером, а не продолжает поток общей смеси.
+pub const MIXED_PERSONAL_VOICE_SENDER_USER_ID: Uuid = Uuid::from_u128(u128::MAX - 1);
+
+/// Все отправители смесей сервера: они не подсвечиваются как говорящие участники.
+pub const MIXED_VOICE_SENDER_USER_IDS: [Uuid; 2] = [
+    MIXED_VOICE_SENDER_USER_ID,
+    MIXED_PERSONAL_VOICE_SENDER_USER_ID,
+];
+
 /// Вид медиадатаграммы.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MediaDatagramKind {
@@ -41,13 +69,24 @@ pub enum MediaCodec {
     Opus = 1,
     /// Видео VP9.
     Vp9 = 2,
+    /// Видео AV1 в виде последовательности OBU low-overhead формата.
+    Av1 = 3,
+    /// Видео H.264 в виде Annex B потока NAL units.
+    H264 = 4,
 }
 
 impl MediaCodec {
+    /// Является ли кодек видеокодеком камеры и демонстрации экрана.
+    pub const fn is_video(self) -> bool {
+        matches!(self, Self::Vp9 | Self::Av1 | Self::H264)
+    }
+
     fn from_u8(value: u8) -> Result<Self, MediaDatagramError> {
         match value {
             1 => Ok(Self::Opus),
             2 => Ok(Self::Vp9),
+            3 => Ok(Self::Av1),
+            4 => Ok(Self::H264),
             _ => Err(MediaDatagramError::UnknownCodec(value)),
         }
     }
@@ -72,6 +111,13 @@ pub struct MediaDatagram {
     pub room_id: Uuid,
     /// Идентификатор аутентифицированного отправителя, назначенный сервером для ретранслируемых кадров.
     pub sender_user_id: Uuid,
+    /// Пространственный слой simulcast-видео; аудио и видео без simulcast используют `High`.
+    pub spatial_layer: VideoSpatialLayer,
+    /// Уровень звука отправителя в дБ ниже полной шкалы, если голосовая датаграмма его несет.
+    ///
+    /// Передается байтом после заголовка под флагом [`MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL`],
+    /// поэтому в [`MediaDatagram::flags`] декодированной датаграммы этот флаг не попадает.
+    pub audio_level: Option<u8>,
     /// Сырая закодированная медиа-полезная нагрузка.
     pub payload: Vec<u8>,
 }
@@ -81,18 +127,25 @@ impl MediaDatagram {
     pub fn encode(&self) -> Result<Vec<u8>, MediaDatagramError> {
         let payload_len = u32::try_from(self.payload.len())
             .map_err(|_| MediaDatagramError::PayloadTooLarge(self.payload.len()))?;
-        let mut bytes = Vec::with_capacity(HEADER_LEN + self.payload.len());
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 1 + self.payload.len());
         bytes.extend_from_slice(MAGIC);
         bytes.push(VERSION);
         bytes.push(self.kind as u8);
         bytes.push(self.codec as u8);
-        bytes.push(self.flags);
+        bytes.push(match self.audio_level {
+            Some(_) => self.flags | MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL,
+            None => self.flags & !MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL,
+        });
         bytes.extend_from_slice(&self.sequence.to_be_bytes());
         bytes.extend_from_slice(&self.timestamp_us.to_be_bytes());
         bytes.extend_from_slice(&self.duration_us.to_be_bytes());
         bytes.extend_from_slice(self.room_id.as_bytes());
         bytes.extend_from_slice(self.sender_user_id.as_bytes());
         bytes.extend_from_slice(&payload_len.to_be_bytes());
+        bytes.push(self.spatial_layer.id());
+        if let Some(level) = self.audio_level {
+            bytes.push(level.min(AUDIO_LEVEL_SILENCE));
+        }
         bytes.extend_from_slice(&self.payload);
 
         Ok(bytes)
@@ -100,31 +153,49 @@ impl MediaDatagram {
 
     /// Декодирует одну бинарную медиадатаграмму.
     pub fn decode(bytes: &[u8]) -> Result<Self, MediaDatagramError> {
-        if bytes.len() < HEADER_LEN {
+        if bytes.len() < SINGLE_LAYER_HEADER_LEN {
             return Err(MediaDatagramError::Truncated);
         }
         if &bytes[..4] != MAGIC {
             return Err(MediaDatagramError::BadMagic);
         }
-        if bytes[4] != VERSION {
-            return Err(MediaDatagramError::UnknownVersion(bytes[4]));
+        let mut header_len = match bytes[4] {
+            VERSION_SINGLE_LAYER => SINGLE_LAYER_HEADER_LEN,
+            VERSION => HEADER_LEN,
+            version => return Err(MediaDatagramError::UnknownVersion(version)),
+        };
+        let carries_audio_level =
+            header_len == HEADER_LEN && bytes[7] & MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL != 0;
+        if carries_audio_level {
+            header_len += 1;
+        }
+        if bytes.len() < header_len {
+            return Err(MediaDatagramError::Truncated);
         }
         let kind = MediaDatagramKind::from_u8(bytes[5])?;
         let codec = MediaCodec::from_u8(bytes[6])?;
-        let flags = bytes[7];
+        let flags = bytes[7] & !MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL;
         let sequence = u64::from_be_bytes(copy_array(&bytes[8..16]));
         let timestamp_us = u64::from_be_bytes(copy_array(&bytes[16..24]));
         let duration_us = u32::from_be_bytes(copy_array(&bytes[24..28]));
         let room_id = Uuid::from_bytes(copy_array(&bytes[28..44]));
         let sender_user_id = Uuid::from_bytes(copy_array(&bytes[44..60]));
         let payload_len = u32::from_be_bytes(copy_array(&bytes[60..64])) as usize;
-        let expected_len = HEADER_LEN
+        let spatial_layer = if header_len >= HEADER_LEN {
+            VideoSpatialLayer::from_id(bytes[64])
+                .ok_or(MediaDatagramError::UnknownSpatialLayer(bytes[64]))?
+        } else {
+            VideoSpatialLayer::High
+        };
+        let expected_len = header_len
             .checked_add(payload_len)
             .ok_or(MediaDatagramError::PayloadTooLarge(payload_len))?;
         if bytes.len() < expected_len {
             return Err(MediaDatagramError::Truncated);
         }
 
+        let audio_level = carries_audio_level.then(|| bytes[HEADER_LEN].min(AUDIO_LEVEL_SILENCE));
+
         Ok(Self {
             kind,
             codec,
@@ -134,11 +205,25 @@ impl MediaDatagram {
             duration_us,
             room_id,
             sender_user_id,
-            payload: bytes[HEADER_LEN..expected_len].to_vec(),
+            spatial_layer,
+            audio_level,
+            payload: bytes[header_len..expected_len].to_vec(),
         })
     }
 }
 
+/// Переводит нормализованный RMS кадра в уровень звука датаграммы.
+///
+/// Уровень равен громкости в дБ ниже полной шкалы, округленной до целого и
+/// ограниченной [`AUDIO_LEVEL_SILENCE`].
+pub fn audio_level_from_rms(rms: f32) -> u8 {
+    if !rms.is_finite() || rms <= 0.0 {
+        return AUDIO_LEVEL_SILENCE;
+    }
+    let dbov = 20.0 * rms.min(1.0).log10();
+    (-dbov).round().clamp(0.0, f32::from(AUDIO_LEVEL_SILENCE)) as u8
+}
+
 /// Ошибка кодирования/декодирования медиадатаграммы.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MediaDatagramError {
@@ -152,6 +237,8 @@ pub enum MediaDatagramError {
     UnknownKind(u8),
     /// Кодек датаграммы не поддерживается.
     UnknownCodec(u8),
+    /// Пространственный слой датаграммы не поддерживается.
+    UnknownSpatialLayer(u8),
     /// Длина полезной нагрузки не помещается в wire-формат или локальное выделение.
     PayloadTooLarge(usize),
 }
@@ -171,6 +258,9 @@ impl std::fmt::Display for MediaDatagramError {
             Self::UnknownCodec(codec) => {
                 write!(formatter, "media datagram codec {codec} is unknown")
             }
+            Self::UnknownSpatialLayer(layer) => {
+                write!(formatter, "media datagram spatial layer {layer} is unknown")
+            }
             Self::PayloadTooLarge(size) => {
                 write!(
                     formatter,
@@ -190,125 +280,4 @@ fn copy_array<const N: usize>(slice: &[u8]) -> [u8; N] {
 }
 
 #[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn media_datagram_round_trips() {
-        let datagram = MediaDatagram {
-            kind: MediaDatagramKind::VoiceFrame,
-            codec: MediaCodec::Opus,
-            flags: 0,
-            sequence: 42,
-            timestamp_us: 123_456,
-            duration_us: 20_000,
-            room_id: Uuid::new_v4(),
-            sender_user_id: Uuid::new_v4(),
-            payload: vec![1, 2, 3, 4],
-        };
-
-        let encoded = datagram.encode().expect("datagram encodes");
-        let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
-
-        assert_eq!(decoded, datagram);
-    }
-
-    #[test]
-    fn screen_media_datagram_round_trips() {
-        let datagram = MediaDatagram {
-            kind: MediaDatagramKind::ScreenFrame,
-            codec: MediaCodec::Vp9,
-            flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
-            sequence: 84,
-            timestamp_us: 654_321,
-            duration_us: 33_333,
-            room_id: Uuid::new_v4(),
-            sender_user_id: Uuid::new_v4(),
-            payload: vec![9, 8, 7, 6],
-        };
-
-        let encoded = datagram.encode().expect("datagram encodes");
-        let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
-
-        assert_eq!(decoded, datagram);
-    }
-
-    #[test]
-    fn camera_media_datagram_round_trips() {
-        let datagram = MediaDatagram {
-            kind: MediaDatagramKind::CameraFrame,
-            codec: MediaCodec::Vp9,
-            flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
-            sequence: 21,
-            timestamp_us: 456_123,
-            duration_us: 41_667,
-            room_id: Uuid::new_v4(),
-            sender_user_id: Uuid::new_v4(),
-            payload: vec![5, 6, 7, 8],
-        };
-
-        let encoded = datagram.encode().expect("datagram encodes");
-        let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
-
-        assert_eq!(decoded, datagram);
-    }
-
-    #[test]
-    fn media_datagram_rejects_truncated_payload() {
-        let datagram = MediaDatagram {
-            kind: MediaDatagramKind::VoiceFrame,
-            codec: MediaCodec::Opus,
-            flags: 0,
-            sequence: 1,
-            timestamp_us: 1,
-            duration_us: 20_000,
-            room_id: Uuid::new_v4(),
-            sender_user_id: Uuid::nil(),
-            payload: vec![1, 2, 3],
-        };
-        let mut encoded = datagram.encode().expect("datagram encodes");
-        encoded.pop();
-
-        assert_eq!(
-            MediaDatagram::decode(&encoded),
-            Err(MediaDatagramError::Truncated)
-        );
-    }
-
-    #[test]
-    fn media_datagram_rejects_unknown_version_kind_and_codec() {
-        let datagram = MediaDatagram {
-            kind: MediaDatagramKind::VoiceFrame,
-            codec: MediaCodec::Opus,
-            flags: 0,
-            sequence: 1,
-            timestamp_us: 1,
-            duration_us: 20_000,
-            room_id: Uuid::new_v4(),
-            sender_user_id: Uuid::nil(),
-            payload: vec![],
-        };
-        let encoded = datagram.encode().expect("datagram encodes");
-
-        let mut unknown_version = encoded.clone();
-        unknown_version[4] = 2;
-        assert_eq!(
-            MediaDatagram::decode(&unknown_version),
-            Err(MediaDatagramError::UnknownVersion(2))
-        );
-
-        let mut unknown_kind = encoded.clone();
-        unknown_kind[5] = 9;
-        assert_eq!(
-            MediaDatagram::decode(&unknown_kind),
-            Err(MediaDatagramError::UnknownKind(9))
-        );
-
-        let mut unknown_codec = encoded;
-        unknown_codec[6] = 9;
-        assert_eq!(
-            MediaDatagram::decode(&unknown_codec),
-            Err(MediaDatagramError::UnknownCodec(9))
-        );
-    }
-}
+mod tests;
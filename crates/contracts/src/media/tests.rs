@@ -0,0 +1,266 @@
+use super::*;
+
+#[test]
+fn media_datagram_round_trips() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::VoiceFrame,
+        codec: MediaCodec::Opus,
+        flags: 0,
+        sequence: 42,
+        timestamp_us: 123_456,
+        duration_us: 20_000,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::new_v4(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload: vec![1, 2, 3, 4],
+    };
+
+    let encoded = datagram.encode().expect("datagram encodes");
+    let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
+
+    assert_eq!(decoded, datagram);
+}
+
+#[test]
+fn screen_media_datagram_round_trips() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::ScreenFrame,
+        codec: MediaCodec::Vp9,
+        flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
+        sequence: 84,
+        timestamp_us: 654_321,
+        duration_us: 33_333,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::new_v4(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload: vec![9, 8, 7, 6],
+    };
+
+    let encoded = datagram.encode().expect("datagram encodes");
+    let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
+
+    assert_eq!(decoded, datagram);
+}
+
+#[test]
+fn camera_media_datagram_round_trips() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::CameraFrame,
+        codec: MediaCodec::Vp9,
+        flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
+        sequence: 21,
+        timestamp_us: 456_123,
+        duration_us: 41_667,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::new_v4(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload: vec![5, 6, 7, 8],
+    };
+
+    let encoded = datagram.encode().expect("datagram encodes");
+    let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
+
+    assert_eq!(decoded, datagram);
+}
+
+#[test]
+fn av1_and_h264_camera_datagrams_round_trip() {
+    for codec in [MediaCodec::Av1, MediaCodec::H264] {
+        let datagram = MediaDatagram {
+            kind: MediaDatagramKind::CameraFrame,
+            codec,
+            flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
+            sequence: 3,
+            timestamp_us: 12_345,
+            duration_us: 33_333,
+            room_id: Uuid::new_v4(),
+            sender_user_id: Uuid::new_v4(),
+            spatial_layer: VideoSpatialLayer::High,
+            audio_level: None,
+            payload: vec![0, 0, 0, 1, 0x67],
+        };
+
+        let encoded = datagram.encode().expect("datagram encodes");
+        let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
+
+        assert_eq!(decoded, datagram);
+        assert!(decoded.codec.is_video());
+    }
+    assert!(!MediaCodec::Opus.is_video());
+}
+
+#[test]
+fn media_datagram_rejects_truncated_payload() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::VoiceFrame,
+        codec: MediaCodec::Opus,
+        flags: 0,
+        sequence: 1,
+        timestamp_us: 1,
+        duration_us: 20_000,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::nil(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload: vec![1, 2, 3],
+    };
+    let mut encoded = datagram.encode().expect("datagram encodes");
+    encoded.pop();
+
+    assert_eq!(
+        MediaDatagram::decode(&encoded),
+        Err(MediaDatagramError::Truncated)
+    );
+}
+
+#[test]
+fn media_datagram_rejects_unknown_version_kind_and_codec() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::VoiceFrame,
+        codec: MediaCodec::Opus,
+        flags: 0,
+        sequence: 1,
+        timestamp_us: 1,
+        duration_us: 20_000,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::nil(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: None,
+        payload: vec![],
+    };
+    let encoded = datagram.encode().expect("datagram encodes");
+
+    let mut unknown_version = encoded.clone();
+    unknown_version[4] = 3;
+    assert_eq!(
+        MediaDatagram::decode(&unknown_version),
+        Err(MediaDatagramError::UnknownVersion(3))
+    );
+
+    let mut unknown_kind = encoded.clone();
+    unknown_kind[5] = 9;
+    assert_eq!(
+        MediaDatagram::decode(&unknown_kind),
+        Err(MediaDatagramError::UnknownKind(9))
+    );
+
+    let mut unknown_codec = encoded.clone();
+    unknown_codec[6] = 9;
+    assert_eq!(
+        MediaDatagram::decode(&unknown_codec),
+        Err(MediaDatagramError::UnknownCodec(9))
+    );
+
+    let mut unknown_layer = encoded;
+    unknown_layer[64] = 9;
+    assert_eq!(
+        MediaDatagram::decode(&unknown_layer),
+        Err(MediaDatagramError::UnknownSpatialLayer(9))
+    );
+}
+
+#[test]
+fn single_layer_datagram_decodes_as_high_layer() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::CameraFrame,
+        codec: MediaCodec::Vp9,
+        flags: MEDIA_DATAGRAM_FLAG_KEY_FRAME,
+        sequence: 7,
+        timestamp_us: 1,
+        duration_us: 41_667,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::nil(),
+        spatial_layer: VideoSpatialLayer::Low,
+        audio_level: None,
+        payload: vec![1, 2, 3],
+    };
+    let encoded = datagram.encode().expect("datagram encodes");
+    assert_eq!(
+        MediaDatagram::decode(&encoded).expect("datagram decodes"),
+        datagram
+    );
+
+    let mut single_layer = encoded[..SINGLE_LAYER_HEADER_LEN].to_vec();
+    single_layer[4] = VERSION_SINGLE_LAYER;
+    single_layer.extend_from_slice(&datagram.payload);
+    let decoded = MediaDatagram::decode(&single_layer).expect("version 1 datagram decodes");
+
+    assert_eq!(decoded.spatial_layer, VideoSpatialLayer::High);
+    assert_eq!(decoded.payload, datagram.payload);
+}
+
+#[test]
+fn voice_datagram_carries_audio_level_after_header() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::VoiceFrame,
+        codec: MediaCodec::Opus,
+        flags: 0,
+        sequence: 3,
+        timestamp_us: 60_000,
+        duration_us: 20_000,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::nil(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: Some(30),
+        payload: vec![0x78, 0x01],
+    };
+
+    let encoded = datagram.encode().expect("datagram encodes");
+    assert_eq!(encoded[7], MEDIA_DATAGRAM_FLAG_AUDIO_LEVEL);
+    assert_eq!(encoded[HEADER_LEN], 30);
+    assert_eq!(encoded.len(), HEADER_LEN + 1 + datagram.payload.len());
+    let decoded = MediaDatagram::decode(&encoded).expect("datagram decodes");
+    assert_eq!(decoded, datagram);
+
+    let stripped = MediaDatagram {
+        audio_level: None,
+        ..decoded
+    }
+    .encode()
+    .expect("datagram encodes");
+    assert_eq!(stripped[7], 0);
+    assert_eq!(&stripped[HEADER_LEN..], datagram.payload.as_slice());
+}
+
+#[test]
+fn audio_level_is_clamped_and_required_by_flag() {
+    let datagram = MediaDatagram {
+        kind: MediaDatagramKind::VoiceFrame,
+        codec: MediaCodec::Opus,
+        flags: 0,
+        sequence: 1,
+        timestamp_us: 0,
+        duration_us: 20_000,
+        room_id: Uuid::new_v4(),
+        sender_user_id: Uuid::nil(),
+        spatial_layer: VideoSpatialLayer::High,
+        audio_level: Some(200),
+        payload: Vec::new(),
+    };
+    let encoded = datagram.encode().expect("datagram encodes");
+    assert_eq!(
+        MediaDatagram::decode(&encoded)
+            .expect("datagram decodes")
+            .audio_level,
+        Some(AUDIO_LEVEL_SILENCE)
+    );
+
+    assert_eq!(
+        MediaDatagram::decode(&encoded[..HEADER_LEN]),
+        Err(MediaDatagramError::Truncated)
+    );
+}
+
+#[test]
+fn rms_maps_to_decibels_below_full_scale() {
+    assert_eq!(audio_level_from_rms(1.0), 0);
+    assert_eq!(audio_level_from_rms(2.0), 0);
+    assert_eq!(audio_level_from_rms(0.1), 20);
+    assert_eq!(audio_level_from_rms(0.01), 40);
+    assert_eq!(audio_level_from_rms(0.0), AUDIO_LEVEL_SILENCE);
+    assert_eq!(audio_level_from_rms(1e-9), AUDIO_LEVEL_SILENCE);
+    assert_eq!(audio_level_from_rms(f32::NAN), AUDIO_LEVEL_SILENCE);
+}
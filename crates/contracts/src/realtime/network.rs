@@ -10,6 +10,8 @@ pub enum NetworkKind {
     Ping,
     /// Надежный pong-ответ.
     Pong,
+    /// Периодический отчет получателя о приеме медиа от участников голосовой комнаты.
+    ReceiverReport,
 }
 
 /// Полезная нагрузка надежного ping-запроса.
@@ -29,3 +31,32 @@ pub struct Pong {
     /// Временная метка отправки на стороне сервера в миллисекундах.
     pub server_sent_at_ms: u64,
 }
+
+/// Полезная нагрузка периодического отчета получателя медиа.
+///
+/// Счетчики покрывают только интервал с предыдущего отчета, поэтому сервер
+/// может складывать их без знания истории клиента.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiverReport {
+    /// Идентификатор сервера или личного диалога голосовой комнаты.
+    pub server_id: String,
+    /// Идентификатор голосовой комнаты.
+    pub room_id: String,
+    /// Статистика приема по каждому отправителю, от которого приходили кадры.
+    pub senders: Vec<ReceiverSenderStats>,
+}
+
+/// Статистика приема медиа от одного отправителя за интервал отчета.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceiverSenderStats {
+    /// Идентификатор пользователя-отправителя.
+    pub user_id: String,
+    /// Число кадров, дошедших до получателя.
+    pub received_frames: u64,
+    /// Число кадров, которые так и не пришли.
+    pub lost_frames: u64,
+    /// Число кадров, пришедших после того, как их место воспроизведения прошло.
+    pub late_frames: u64,
+    /// Оценка межпакетного джиттера голосовых кадров в миллисекундах.
+    pub jitter_ms: u32,
+}
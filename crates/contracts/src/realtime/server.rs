@@ -2,6 +2,14 @@
 
 use serde::{Deserialize, Serialize};
 
+mod media_settings;
+
+pub use media_settings::{
+    DEFAULT_AUDIO_BITRATE_BPS, DEFAULT_MAX_VIDEO_PUBLISHERS, LoadServerMediaSettings,
+    MAX_AUDIO_BITRATE_BPS, MAX_VIDEO_PUBLISHERS, MIN_AUDIO_BITRATE_BPS, SaveServerMediaSettings,
+    ServerMediaSettings,
+};
+
 /// Виды сообщений realtime-модуля управления сервером.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -42,6 +50,14 @@ pub enum ServerKind {
     RevokeServerMemberRole,
     /// Подтверждает, что роль была отозвана.
     ServerMemberRoleRevoked,
+    /// Загрузить настройки качества голоса и видео сервера.
+    LoadServerMediaSettings,
+    /// Ответ с настройками качества голоса и видео сервера.
+    ServerMediaSettings,
+    /// Сохранить настройки качества голоса и видео сервера.
+    SaveServerMediaSettings,
+    /// Подтверждает, что настройки качества голоса и видео были сохранены.
+    ServerMediaSettingsSaved,
 }
 
 /// Полезная нагрузка запроса для загрузки участников сервера.
@@ -237,6 +253,14 @@ pub enum ServerRolePermission {
     KickVoiceMembers,
     /// Разрешает удалять любые сообщения в текстовых комнатах.
     DeleteMessages,
+    /// Разрешает закреплять и откреплять сообщения в текстовых комнатах.
+    PinMessages,
+    /// Разрешает менять настройки качества голоса и видео сервера.
+    ManageMediaSettings,
+    /// Разрешает запускать и останавливать запись голосовых комнат сервера.
+    RecordVoiceRoom,
+    /// Разрешает отключать микрофон и звук участникам голосовых комнат.
+    ModerateVoiceMembers,
 }
 
 /// Краткая сводка роли сервера, встроенная в серверные ответы для проверки прав на клиенте.
@@ -0,0 +1,96 @@
+//! Контракты настроек качества голоса и видео сервера.
+
+use serde::{Deserialize, Serialize};
+
+use crate::video_presets::{ALL_VIDEO_PRESETS, VideoPresetId, VideoStreamSource};
+
+/// Битрейт микрофона, который получает сервер без сохраненных настроек.
+pub const DEFAULT_AUDIO_BITRATE_BPS: u32 = 32_000;
+
+/// Нижняя граница битрейта микрофона, которую можно выбрать в настройках.
+pub const MIN_AUDIO_BITRATE_BPS: u32 = 16_000;
+
+/// Верхняя граница битрейта микрофона, которую можно выбрать в настройках.
+pub const MAX_AUDIO_BITRATE_BPS: u32 = 64_000;
+
+/// Число одновременных видеопубликаций в комнате для сервера без сохраненных настроек.
+pub const DEFAULT_MAX_VIDEO_PUBLISHERS: u32 = 16;
+
+/// Верхняя граница одновременных видеопубликаций в одной комнате.
+pub const MAX_VIDEO_PUBLISHERS: u32 = 25;
+
+/// Полезная нагрузка запроса для загрузки настроек голоса и видео сервера.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadServerMediaSettings {
+    /// Идентификатор сервера.
+    pub server_id: String,
+}
+
+/// Настройки качества голоса и видео, действующие в голосовых комнатах сервера.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerMediaSettings {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Пресеты камеры и демонстрации экрана, разрешенные на сервере.
+    pub allowed_video_presets: Vec<VideoPresetId>,
+    /// Верхняя граница битрейта микрофона в битах в секунду.
+    pub audio_bitrate_bps: u32,
+    /// Сколько участников одной комнаты могут одновременно публиковать видео.
+    pub max_video_publishers: u32,
+}
+
+impl ServerMediaSettings {
+    /// Возвращает настройки сервера, для которого ничего не сохранено.
+    pub fn defaults(server_id: String) -> Self {
+        Self {
+            server_id,
+            allowed_video_presets: ALL_VIDEO_PRESETS.to_vec(),
+            audio_bitrate_bps: DEFAULT_AUDIO_BITRATE_BPS,
+            max_video_publishers: DEFAULT_MAX_VIDEO_PUBLISHERS,
+        }
+    }
+
+    /// Возвращает разрешенные пресеты одного источника видео.
+    pub fn presets_for(&self, source: VideoStreamSource) -> Vec<VideoPresetId> {
+        self.allowed_video_presets
+            .iter()
+            .copied()
+            .filter(|preset| preset.spec().source == source)
+            .collect()
+    }
+}
+
+/// Полезная нагрузка запроса для сохранения настроек голоса и видео сервера.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveServerMediaSettings {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Пресеты камеры и демонстрации экрана, разрешенные на сервере.
+    pub allowed_video_presets: Vec<VideoPresetId>,
+    /// Верхняя граница битрейта микрофона в битах в секунду.
+    pub audio_bitrate_bps: u32,
+    /// Сколько участников одной комнаты могут одновременно публиковать видео.
+    pub max_video_publishers: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_allow_every_preset_of_each_source() {
+        let settings = ServerMediaSettings::defaults("server".to_owned());
+
+        assert_eq!(
+            settings.presets_for(VideoStreamSource::Camera),
+            vec![VideoPresetId::Camera720p24]
+        );
+        assert_eq!(
+            settings.presets_for(VideoStreamSource::ScreenShare),
+            vec![VideoPresetId::Screen720p30, VideoPresetId::Screen1080p15]
+        );
+        assert!(
+            (MIN_AUDIO_BITRATE_BPS..=MAX_AUDIO_BITRATE_BPS).contains(&settings.audio_bitrate_bps)
+        );
+    }
+}
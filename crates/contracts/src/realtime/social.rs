@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::rest::{LinkPreviewSummary, MessageReactionSummary};
+
 /// Тип realtime-сообщения social-модуля.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -16,6 +18,14 @@ pub enum SocialKind {
     DirectMessageCreated,
     /// Участник подтвердил прочтение личного диалога.
     ConversationReadCheckpoint,
+    /// Поставить реакцию на личное сообщение.
+    AddDirectMessageReaction,
+    /// Снять свою реакцию с личного сообщения.
+    RemoveDirectMessageReaction,
+    /// Реакции на личное сообщение изменились; также служит ответом на запросы реакций.
+    DirectMessageReactionsChanged,
+    /// Для ссылок из личного сообщения готовы карточки предпросмотра.
+    DirectMessagePreviewReady,
 }
 
 /// Пустой запрос подписки на social-события.
@@ -69,6 +79,56 @@ pub struct ConversationReadCheckpoint {
     pub read_at: String,
 }
 
+/// Запрос установки реакции на личное сообщение.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddDirectMessageReaction {
+    /// Идентификатор личного диалога.
+    pub conversation_id: String,
+    /// Идентификатор сообщения.
+    pub message_id: String,
+    /// Эмодзи реакции.
+    pub emoji: String,
+}
+
+/// Запрос снятия своей реакции с личного сообщения.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RemoveDirectMessageReaction {
+    /// Идентификатор личного диалога.
+    pub conversation_id: String,
+    /// Идентификатор сообщения.
+    pub message_id: String,
+    /// Эмодзи реакции.
+    pub emoji: String,
+}
+
+/// Realtime-событие изменения реакций на личное сообщение для обоих участников.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectMessageReactionsChanged {
+    /// Идентификатор личного диалога.
+    pub conversation_id: String,
+    /// Идентификатор сообщения.
+    pub message_id: String,
+    /// Пользователь, изменивший реакцию.
+    pub user_id: String,
+    /// Эмодзи, который был поставлен или снят.
+    pub emoji: String,
+    /// Была ли реакция поставлена (`true`) или снята (`false`).
+    pub added: bool,
+    /// Актуальные агрегированные реакции сообщения.
+    pub reactions: Vec<MessageReactionSummary>,
+}
+
+/// Realtime-событие готовности карточек ссылок личного сообщения для обоих участников.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectMessagePreviewReady {
+    /// Идентификатор личного диалога.
+    pub conversation_id: String,
+    /// Идентификатор сообщения.
+    pub message_id: String,
+    /// Карточки ссылок в порядке их появления в тексте.
+    pub previews: Vec<LinkPreviewSummary>,
+}
+
 /// Причина изменения social-состояния.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
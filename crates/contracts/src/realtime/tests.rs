@@ -0,0 +1,302 @@
+use super::*;
+use uuid::Uuid;
+
+mod voice_chat;
+
+#[test]
+fn envelope_round_trips_uuid_and_typed_kind() {
+    let request_id = Uuid::new_v4();
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::Network,
+        RealtimeKind::Network(NetworkKind::Ping),
+        Some(request_id),
+        Ping { sent_at_ms: 42 },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"module\":\"network\""));
+    assert!(json.contains("\"kind\":\"ping\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+
+    assert_eq!(decoded.request_id, Some(request_id));
+    assert_eq!(decoded.kind, RealtimeKind::Network(NetworkKind::Ping));
+    assert!(decoded.has_matching_module_kind());
+}
+
+#[test]
+fn receiver_report_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::Network,
+        RealtimeKind::Network(NetworkKind::ReceiverReport),
+        None,
+        ReceiverReport {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            senders: vec![ReceiverSenderStats {
+                user_id: Uuid::new_v4().to_string(),
+                received_frames: 240,
+                lost_frames: 6,
+                late_frames: 2,
+                jitter_ms: 14,
+            }],
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"receiver_report\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: ReceiverReport = serde_json::from_value(decoded.payload).expect("payload decodes");
+
+    assert_eq!(payload.senders[0].lost_frames, 6);
+}
+
+#[test]
+fn module_kind_mismatch_is_detected() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::Control,
+        RealtimeKind::Network(NetworkKind::Ping),
+        None,
+        Ping { sent_at_ms: 42 },
+    )
+    .expect("payload serializes");
+
+    assert!(!envelope.has_matching_module_kind());
+}
+
+#[test]
+fn text_chat_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::TextChat,
+        RealtimeKind::TextChat(TextChatKind::LoadRoomHistory),
+        Some(Uuid::new_v4()),
+        LoadRoomHistory {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            before_message_id: None,
+            after_message_id: None,
+            around_message_id: None,
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"module\":\"text_chat\""));
+    assert!(json.contains("\"kind\":\"load_room_history\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+
+    assert_eq!(
+        decoded.kind,
+        RealtimeKind::TextChat(TextChatKind::LoadRoomHistory)
+    );
+    assert!(decoded.has_matching_module_kind());
+}
+
+#[test]
+fn text_chat_message_edited_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::TextChat,
+        RealtimeKind::TextChat(TextChatKind::MessageEdited),
+        None,
+        MessageEditedPayload {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            message_id: Uuid::new_v4().to_string(),
+            body: "fixed typo".to_owned(),
+            edited_at: "2026-10-16T00:00:00Z".to_owned(),
+            mentions: Vec::new(),
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"message_edited\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+
+    assert_eq!(
+        decoded.kind,
+        RealtimeKind::TextChat(TextChatKind::MessageEdited)
+    );
+    assert!(decoded.has_matching_module_kind());
+}
+
+#[test]
+fn reaction_kinds_stay_in_their_modules() {
+    for (kind, name) in [
+        (
+            RealtimeKind::TextChat(TextChatKind::ReactionsChanged),
+            "reactions_changed",
+        ),
+        (
+            RealtimeKind::Social(SocialKind::DirectMessageReactionsChanged),
+            "direct_message_reactions_changed",
+        ),
+        (
+            RealtimeKind::Social(SocialKind::AddDirectMessageReaction),
+            "add_direct_message_reaction",
+        ),
+    ] {
+        let json = serde_json::to_string(&kind).expect("kind serializes");
+        assert_eq!(json, format!("\"{name}\""));
+        let decoded: RealtimeKind = serde_json::from_str(&json).expect("kind decodes");
+        assert_eq!(decoded, kind);
+    }
+}
+
+#[test]
+fn server_invites_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::Server,
+        RealtimeKind::Server(ServerKind::ListServerInvites),
+        Some(Uuid::new_v4()),
+        ListServerInvites {
+            server_id: Uuid::new_v4().to_string(),
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"module\":\"server\""));
+    assert!(json.contains("\"kind\":\"list_server_invites\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+
+    assert_eq!(
+        decoded.kind,
+        RealtimeKind::Server(ServerKind::ListServerInvites)
+    );
+    assert!(decoded.has_matching_module_kind());
+}
+
+#[test]
+fn social_changed_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::Social,
+        RealtimeKind::Social(SocialKind::Changed),
+        None,
+        SocialChanged {
+            reason: SocialChangeReason::DirectMessages,
+            conversation_id: Some(Uuid::new_v4().to_string()),
+        },
+    )
+    .expect("envelope serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"module\":\"social\""));
+    assert!(json.contains("\"kind\":\"changed\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+
+    assert_eq!(decoded.kind, RealtimeKind::Social(SocialKind::Changed));
+    assert!(decoded.has_matching_module_kind());
+}
+
+#[test]
+fn direct_message_created_envelope_round_trips() {
+    let message_id = Uuid::new_v4().to_string();
+    let conversation_id = Uuid::new_v4().to_string();
+    let sender_user_id = Uuid::new_v4().to_string();
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::Social,
+        RealtimeKind::Social(SocialKind::DirectMessageCreated),
+        None,
+        DirectMessageCreated {
+            message_id: message_id.clone(),
+            conversation_id: conversation_id.clone(),
+            message_seq: 42,
+            sender_user_id: sender_user_id.clone(),
+            sender_nickname: "alice".to_owned(),
+            body: "Привет".to_owned(),
+            created_at: "2026-07-13T00:00:00Z".to_owned(),
+        },
+    )
+    .expect("envelope serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    let payload: DirectMessageCreated =
+        serde_json::from_value(decoded.payload).expect("payload decodes");
+
+    assert_eq!(
+        decoded.kind,
+        RealtimeKind::Social(SocialKind::DirectMessageCreated)
+    );
+    assert_eq!(payload.message_id, message_id);
+    assert_eq!(payload.conversation_id, conversation_id);
+    assert_eq!(payload.sender_user_id, sender_user_id);
+    assert_eq!(payload.message_seq, 42);
+}
+
+#[test]
+fn avatar_fields_round_trip_in_realtime_payloads() {
+    let message = TextChatMessage {
+        id: Uuid::new_v4().to_string(),
+        server_id: Uuid::new_v4().to_string(),
+        room_id: Uuid::new_v4().to_string(),
+        author_user_id: Uuid::new_v4().to_string(),
+        author_nickname: "avatar_user".to_owned(),
+        author_avatar_url: Some("http://localhost/api/images/avatar".to_owned()),
+        body: "hello".to_owned(),
+        attachments: Vec::new(),
+        delivery_status: None,
+        created_at: "2026-05-13T00:00:00Z".to_owned(),
+        edited_at: None,
+        reply_to_message_id: None,
+        reply_to: None,
+        reactions: Vec::new(),
+        mentions: vec![TextChatMention {
+            kind: TextChatMentionKind::Role,
+            target_id: Uuid::new_v4().to_string(),
+            name: "moderators".to_owned(),
+        }],
+        link_previews: Vec::new(),
+    };
+    let decoded: TextChatMessage =
+        serde_json::from_str(&serde_json::to_string(&message).expect("message serializes"))
+            .expect("message decodes");
+    assert_eq!(decoded.author_avatar_url, message.author_avatar_url);
+    assert_eq!(decoded.mentions, message.mentions);
+
+    let participant = VoiceRoomParticipant {
+        user_id: Uuid::new_v4().to_string(),
+        nickname: "voice_user".to_owned(),
+        avatar_url: Some("http://localhost/api/images/avatar".to_owned()),
+        joined_at: "2026-05-13T00:00:00Z".to_owned(),
+        connection_quality: Some(80),
+        server_muted: true,
+        server_deafened: false,
+    };
+    let decoded: VoiceRoomParticipant =
+        serde_json::from_str(&serde_json::to_string(&participant).expect("participant serializes"))
+            .expect("participant decodes");
+    assert_eq!(decoded.avatar_url, participant.avatar_url);
+    assert_eq!(decoded.connection_quality, Some(80));
+    assert!(decoded.server_muted);
+    let legacy: VoiceRoomParticipant = serde_json::from_str(
+        r#"{"user_id":"u","nickname":"n","avatar_url":null,"joined_at":"2026-05-13T00:00:00Z"}"#,
+    )
+    .expect("legacy participant decodes");
+    assert_eq!(legacy.connection_quality, None);
+    assert!(!legacy.server_muted && !legacy.server_deafened);
+}
+
+#[test]
+fn legacy_image_attachment_decodes_as_image_kind() {
+    let attachment: TextChatAttachment = serde_json::from_str(
+        r#"{"id":"a","content_type":"image/png","byte_size":3,"width":1,"height":2}"#,
+    )
+    .expect("legacy attachment decodes");
+    assert_eq!(attachment.kind, TextChatAttachmentKind::Image);
+    assert_eq!(attachment.width, Some(1));
+
+    let file = TextChatAttachment {
+        kind: TextChatAttachmentKind::File,
+        width: None,
+        height: None,
+        original_filename: Some("build.log".to_owned()),
+        ..attachment
+    };
+    let json = serde_json::to_string(&file).expect("attachment serializes");
+    assert!(json.contains(r#""kind":"file""#));
+    assert!(!json.contains("width"));
+}
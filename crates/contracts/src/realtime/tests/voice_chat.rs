@@ -0,0 +1,264 @@
+//! Voice chat envelope tests.
+
+use super::super::*;
+use crate::video_presets::VideoSpatialLayer;
+use uuid::Uuid;
+
+#[test]
+fn voice_video_stream_ended_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::VideoStreamEnded),
+        None,
+        VoiceVideoStreamEnded {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            user_id: Uuid::new_v4().to_string(),
+            source: VoiceVideoStreamSource::ScreenShare,
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"module\":\"voice_chat\""));
+    assert!(json.contains("\"kind\":\"video_stream_ended\""));
+    assert!(json.contains("\"source\":\"screen_share\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+
+    assert_eq!(
+        decoded.kind,
+        RealtimeKind::VoiceChat(VoiceChatKind::VideoStreamEnded)
+    );
+    assert!(decoded.has_matching_module_kind());
+}
+
+#[test]
+fn key_frame_request_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::RequestKeyFrame),
+        None,
+        RequestVoiceKeyFrame {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            publisher_user_id: Uuid::new_v4().to_string(),
+            source: VoiceVideoStreamSource::Camera,
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"request_key_frame\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: RequestVoiceKeyFrame =
+        serde_json::from_value(decoded.payload).expect("payload decodes");
+
+    assert_eq!(payload.source, VoiceVideoStreamSource::Camera);
+}
+
+#[test]
+fn codec_capabilities_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::AdvertiseCodecCapabilities),
+        Some(Uuid::new_v4()),
+        AdvertiseVoiceCodecCapabilities {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            decoders: vec![VoiceVideoCodec::Av1, VoiceVideoCodec::H264],
+            encoders: vec![VoiceVideoCodec::Vp9],
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"advertise_codec_capabilities\""));
+    assert!(json.contains("\"decoders\":[\"av1\",\"h264\"]"));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: AdvertiseVoiceCodecCapabilities =
+        serde_json::from_value(decoded.payload).expect("payload decodes");
+
+    assert_eq!(payload.encoders, vec![VoiceVideoCodec::Vp9]);
+}
+
+#[test]
+fn voice_room_snapshot_carries_recording_status() {
+    let legacy: VoiceRoomSnapshot =
+        serde_json::from_str(r#"{"server_id":"s","room_id":"r","participants":[]}"#)
+            .expect("snapshot without recording decodes");
+    assert_eq!(legacy.recording, None);
+    assert!(!legacy.audio_mixing);
+
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::ParticipantsChanged),
+        None,
+        VoiceRoomSnapshot {
+            recording: Some(VoiceRoomRecordingStatus {
+                recording_id: Uuid::new_v4().to_string(),
+                started_by_user_id: Uuid::new_v4().to_string(),
+                started_at: "2026-10-17T09:00:00Z".to_owned(),
+            }),
+            ..legacy
+        },
+    )
+    .expect("snapshot envelope serializes");
+    let json = serde_json::to_string(&envelope).expect("snapshot envelope encodes");
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("snapshot envelope decodes");
+    let snapshot: VoiceRoomSnapshot =
+        serde_json::from_value(decoded.payload).expect("snapshot payload decodes");
+
+    assert!(snapshot.recording.is_some());
+}
+
+#[test]
+fn audio_mixing_switch_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::SetAudioMixing),
+        Some(Uuid::new_v4()),
+        SetVoiceRoomAudioMixing {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            enabled: true,
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"set_audio_mixing\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: SetVoiceRoomAudioMixing =
+        serde_json::from_value(decoded.payload).expect("payload decodes");
+    assert!(payload.enabled);
+}
+
+#[test]
+fn active_speakers_event_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::ActiveSpeakersChanged),
+        None,
+        VoiceActiveSpeakersChanged {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            speaker_user_ids: vec!["loud".to_owned(), "quiet".to_owned()],
+            dominant_user_id: Some("loud".to_owned()),
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"active_speakers_changed\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: VoiceActiveSpeakersChanged =
+        serde_json::from_value(decoded.payload).expect("payload decodes");
+    assert_eq!(payload.speaker_user_ids, ["loud", "quiet"]);
+    assert_eq!(payload.dominant_user_id.as_deref(), Some("loud"));
+}
+
+#[test]
+fn video_subscription_envelope_round_trips() {
+    let envelope = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::SubscribeVideo),
+        None,
+        SubscribeVoiceVideo {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            user_id: Uuid::new_v4().to_string(),
+            source: VoiceVideoStreamSource::ScreenShare,
+            layer: VideoSpatialLayer::Low,
+        },
+    )
+    .expect("payload serializes");
+
+    let json = serde_json::to_string(&envelope).expect("envelope serializes");
+    assert!(json.contains("\"kind\":\"subscribe_video\""));
+    assert!(json.contains("\"layer\":\"low\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: SubscribeVoiceVideo =
+        serde_json::from_value(decoded.payload).expect("payload decodes");
+
+    assert_eq!(payload.source, VoiceVideoStreamSource::ScreenShare);
+    assert_eq!(payload.layer, VideoSpatialLayer::Low);
+}
+
+#[test]
+fn microphone_uplink_grant_envelopes_round_trip() {
+    let grant = Uuid::new_v4();
+    let issued = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::MicrophoneUplinkGrantIssued),
+        Some(Uuid::new_v4()),
+        MicrophoneUplinkGrantIssued {
+            grant: grant.to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            expires_at: "2026-07-14T10:00:00Z".to_owned(),
+        },
+    )
+    .expect("grant envelope serializes");
+    let json = serde_json::to_string(&issued).expect("grant envelope encodes");
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("grant envelope decodes");
+
+    assert_eq!(
+        decoded.kind,
+        RealtimeKind::VoiceChat(VoiceChatKind::MicrophoneUplinkGrantIssued)
+    );
+    assert!(decoded.has_matching_module_kind());
+
+    let bind = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::BindMicrophoneUplink),
+        Some(Uuid::new_v4()),
+        BindMicrophoneUplink {
+            grant: grant.to_string(),
+        },
+    )
+    .expect("bind envelope serializes");
+    assert!(bind.has_matching_module_kind());
+}
+
+#[test]
+fn server_moderation_envelopes_round_trip() {
+    let mute = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::ServerMuteVoiceMember),
+        Some(Uuid::new_v4()),
+        ServerMuteVoiceMember {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            user_id: Uuid::new_v4().to_string(),
+            muted: true,
+        },
+    )
+    .expect("mute envelope serializes");
+    let json = serde_json::to_string(&mute).expect("mute envelope encodes");
+    assert!(json.contains("\"kind\":\"server_mute_voice_member\""));
+    let decoded: RealtimeEnvelope = serde_json::from_str(&json).expect("mute envelope decodes");
+    assert!(decoded.has_matching_module_kind());
+    let payload: ServerMuteVoiceMember =
+        serde_json::from_value(decoded.payload).expect("mute payload decodes");
+    assert!(payload.muted);
+
+    let deafen = RealtimeEnvelope::new(
+        RealtimeModule::VoiceChat,
+        RealtimeKind::VoiceChat(VoiceChatKind::ServerDeafenVoiceMember),
+        Some(Uuid::new_v4()),
+        ServerDeafenVoiceMember {
+            server_id: Uuid::new_v4().to_string(),
+            room_id: Uuid::new_v4().to_string(),
+            user_id: Uuid::new_v4().to_string(),
+            deafened: false,
+        },
+    )
+    .expect("deafen envelope serializes");
+    let json = serde_json::to_string(&deafen).expect("deafen envelope encodes");
+    assert!(json.contains("\"kind\":\"server_deafen_voice_member\""));
+    assert!(json.contains("\"deafened\":false"));
+}
@@ -2,7 +2,19 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::rest::DmMessageDeliveryStatus;
+use crate::rest::{DmMessageDeliveryStatus, LinkPreviewSummary, MessageReactionSummary};
+
+mod attachments;
+mod pins;
+
+pub use attachments::{
+    ChatAttachmentUploadResponse, ChatImageLoadedResponse, LoadChatImage, TextChatAttachment,
+    TextChatAttachmentKind, UploadChatAttachment,
+};
+pub use pins::{
+    ListPinnedMessages, MessagePinnedPayload, PinMessage, PinnedMessagesList,
+    TextChatPinnedMessage, UnpinMessage,
+};
 
 /// Виды сообщений модуля текстового чата.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -16,9 +28,9 @@ pub enum TextChatKind {
     SendMessage,
     /// Подтверждает, что сообщение принято для рассылки и сохранения.
     SendMessageAccepted,
-    /// Загрузить вложение-изображение чата.
+    /// Загрузить вложение чата; размер ограничен realtime-кадром.
     UploadImage,
-    /// Подтверждает, что изображение чата загружено.
+    /// Подтверждает, что вложение чата загружено.
     UploadImageAccepted,
     /// Загрузить вложение-изображение чата через realtime.
     LoadImage,
@@ -32,6 +44,36 @@ pub enum TextChatKind {
     DeleteMessageAccepted,
     /// Сообщение удалено автором; получатели должны убрать его.
     MessageDeleted,
+    /// Изменить текст одного из собственных сообщений пользователя.
+    EditMessage,
+    /// Подтверждает, что изменение сообщения сохранено.
+    EditMessageAccepted,
+    /// Сообщение изменено автором; получатели должны обновить его текст.
+    MessageEdited,
+    /// Поставить реакцию на сообщение комнаты.
+    AddReaction,
+    /// Снять свою реакцию с сообщения комнаты.
+    RemoveReaction,
+    /// Реакции на сообщение изменились; также служит ответом на запросы реакций.
+    ReactionsChanged,
+    /// Текущего пользователя упомянули в сообщении; отправляется только адресатам упоминания.
+    MentionReceived,
+    /// Отметить сообщения комнаты прочитанными до указанного сообщения.
+    MarkRoomRead,
+    /// Отметка прочтения комнаты изменилась; также служит ответом на `MarkRoomRead`.
+    RoomReadUpdated,
+    /// Закрепить сообщение комнаты.
+    PinMessage,
+    /// Открепить сообщение комнаты.
+    UnpinMessage,
+    /// Закрепление сообщения изменилось; также служит ответом на `PinMessage` и `UnpinMessage`.
+    MessagePinned,
+    /// Загрузить закрепленные сообщения комнаты.
+    ListPinnedMessages,
+    /// Ответ с закрепленными сообщениями комнаты.
+    PinnedMessages,
+    /// Для ссылок из сообщения комнаты готовы карточки предпросмотра.
+    MessagePreviewReady,
 }
 
 /// Полезная нагрузка запроса для загрузки истории комнаты.
@@ -43,6 +85,12 @@ pub struct LoadRoomHistory {
     pub room_id: String,
     /// Идентификатор сообщения, перед которым нужно загрузить сообщения.
     pub before_message_id: Option<String>,
+    /// Идентификатор сообщения, после которого нужно загрузить сообщения.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_message_id: Option<String>,
+    /// Идентификатор сообщения, вокруг которого нужно загрузить окно истории.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub around_message_id: Option<String>,
 }
 
 /// Полезная нагрузка ответа с последними сообщениями комнаты.
@@ -52,10 +100,15 @@ pub struct RoomHistory {
     pub server_id: String,
     /// Идентификатор комнаты.
     pub room_id: String,
-    /// Последние сохраненные сообщения комнаты.
+    /// Сообщения запрошенного окна истории в порядке от старых к новым.
     pub messages: Vec<TextChatMessage>,
     /// Доступны ли более старые сообщения до этой страницы.
-    pub has_more: bool,
+    pub has_more_before: bool,
+    /// Доступны ли более новые сообщения после этой страницы.
+    pub has_more_after: bool,
+    /// Последнее прочитанное текущим пользователем сообщение комнаты на момент загрузки.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_read_message_id: Option<String>,
 }
 
 /// Полезная нагрузка запроса для отправки сообщения в комнату.
@@ -70,6 +123,9 @@ pub struct SendMessage {
     /// Идентификаторы загруженных вложений-изображений, которые нужно включить в сообщение.
     #[serde(default)]
     pub attachment_ids: Vec<String>,
+    /// Идентификатор сообщения той же комнаты, на которое отвечает новое сообщение.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to_message_id: Option<String>,
 }
 
 /// Полезная нагрузка ответа после принятия отправки сообщения.
@@ -79,98 +135,116 @@ pub struct SendMessageAccepted {
     pub message: TextChatMessage,
 }
 
-/// Полезная нагрузка запроса для загрузки одного вложения-изображения чата.
+/// Полезная нагрузка запроса для мягкого удаления одного из собственных сообщений пользователя.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct UploadChatImage {
+pub struct DeleteMessage {
     /// Идентификатор сервера.
     pub server_id: String,
     /// Идентификатор комнаты.
     pub room_id: String,
-    /// Необязательное исходное имя файла.
-    pub original_filename: Option<String>,
-    /// Байты изображения в Base64.
-    pub data_base64: String,
+    /// Идентификатор сообщения для удаления.
+    pub message_id: String,
 }
 
-/// Ответ после загрузки изображения чата.
+/// Полезная нагрузка ответа после принятия удаления сообщения.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ChatImageUploadResponse {
-    /// Стабильный идентификатор вложения.
-    pub id: String,
+pub struct DeleteMessageAccepted {
+    /// Идентификатор удаленного сообщения.
+    pub message_id: String,
+}
+
+/// Полезная нагрузка широковещания, уведомляющая участников комнаты об удалении сообщения.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageDeletedPayload {
     /// Идентификатор сервера.
     pub server_id: String,
     /// Идентификатор комнаты.
     pub room_id: String,
-    /// Проверенный MIME-тип изображения.
-    pub content_type: String,
-    /// Загруженный размер в байтах.
-    pub byte_size: i64,
-    /// Ширина изображения в пикселях.
-    pub width: i32,
-    /// Высота изображения в пикселях.
-    pub height: i32,
+    /// Идентификатор удаленного сообщения.
+    pub message_id: String,
 }
 
-/// Метаданные вложения-изображения, включаемые в сообщение текстового чата.
+/// Полезная нагрузка запроса для изменения текста одного из собственных сообщений пользователя.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct TextChatImageAttachment {
-    /// Стабильный идентификатор вложения.
-    pub id: String,
-    /// Проверенный MIME-тип изображения.
-    pub content_type: String,
-    /// Загруженный размер в байтах.
-    pub byte_size: i64,
-    /// Ширина изображения в пикселях.
-    pub width: i32,
-    /// Высота изображения в пикселях.
-    pub height: i32,
+pub struct EditMessage {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор изменяемого сообщения.
+    pub message_id: String,
+    /// Новое тело сообщения.
+    pub body: String,
 }
 
-/// Полезная нагрузка запроса для загрузки одного вложения-изображения чата.
+/// Полезная нагрузка ответа после сохранения изменения сообщения.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct LoadChatImage {
-    /// Стабильный идентификатор вложения.
-    pub attachment_id: String,
+pub struct EditMessageAccepted {
+    /// Сообщение после изменения.
+    pub message: TextChatMessage,
 }
 
-/// Полезная нагрузка ответа с одним вложением-изображением чата.
+/// Полезная нагрузка широковещания, уведомляющая участников комнаты об изменении сообщения.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct ChatImageLoadedResponse {
-    /// Стабильный идентификатор вложения.
-    pub id: String,
-    /// Проверенный MIME-тип изображения.
-    pub content_type: String,
-    /// Байты изображения в Base64.
-    pub data_base64: String,
+pub struct MessageEditedPayload {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор измененного сообщения.
+    pub message_id: String,
+    /// Новое тело сообщения.
+    pub body: String,
+    /// Временная метка изменения в формате RFC3339.
+    pub edited_at: String,
+    /// Упоминания, разобранные из нового тела сообщения.
+    #[serde(default)]
+    pub mentions: Vec<TextChatMention>,
 }
 
-/// Полезная нагрузка запроса для мягкого удаления одного из собственных сообщений пользователя.
+/// Полезная нагрузка запроса для установки реакции на сообщение комнаты.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DeleteMessage {
+pub struct AddReaction {
     /// Идентификатор сервера.
     pub server_id: String,
     /// Идентификатор комнаты.
     pub room_id: String,
-    /// Идентификатор сообщения для удаления.
+    /// Идентификатор сообщения.
     pub message_id: String,
+    /// Эмодзи реакции.
+    pub emoji: String,
 }
 
-/// Полезная нагрузка ответа после принятия удаления сообщения.
+/// Полезная нагрузка запроса для снятия своей реакции с сообщения комнаты.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct DeleteMessageAccepted {
-    /// Идентификатор удаленного сообщения.
+pub struct RemoveReaction {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор сообщения.
     pub message_id: String,
+    /// Эмодзи реакции.
+    pub emoji: String,
 }
 
-/// Полезная нагрузка широковещания, уведомляющая участников комнаты об удалении сообщения.
+/// Полезная нагрузка широковещания об изменении реакций на сообщение комнаты.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct MessageDeletedPayload {
+pub struct ReactionsChangedPayload {
     /// Идентификатор сервера.
     pub server_id: String,
     /// Идентификатор комнаты.
     pub room_id: String,
-    /// Идентификатор удаленного сообщения.
+    /// Идентификатор сообщения.
     pub message_id: String,
+    /// Пользователь, изменивший реакцию.
+    pub user_id: String,
+    /// Эмодзи, который был поставлен или снят.
+    pub emoji: String,
+    /// Была ли реакция поставлена (`true`) или снята (`false`).
+    pub added: bool,
+    /// Актуальные агрегированные реакции сообщения.
+    pub reactions: Vec<MessageReactionSummary>,
 }
 
 /// Полезная нагрузка сообщения текстового чата.
@@ -190,12 +264,119 @@ pub struct TextChatMessage {
     pub author_avatar_url: Option<String>,
     /// Тело сообщения.
     pub body: String,
-    /// Вложения-изображения, включенные в сообщение.
+    /// Вложения, включенные в сообщение.
     #[serde(default)]
-    pub attachments: Vec<TextChatImageAttachment>,
+    pub attachments: Vec<TextChatAttachment>,
     /// Статус доставки для личных сообщений.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub delivery_status: Option<DmMessageDeliveryStatus>,
     /// Временная метка создания сообщения в формате RFC3339.
     pub created_at: String,
+    /// Временная метка последнего изменения в формате RFC3339, если сообщение изменялось.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<String>,
+    /// Идентификатор сообщения, на которое отвечает это сообщение.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to_message_id: Option<String>,
+    /// Компактный снимок цитируемого сообщения для отображения над ответом.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_to: Option<TextChatReplySnapshot>,
+    /// Реакции на сообщение, сгруппированные по эмодзи.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reactions: Vec<MessageReactionSummary>,
+    /// Пользователи и роли, упомянутые в теле сообщения через `@`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<TextChatMention>,
+    /// Карточки ссылок из тела сообщения, если они уже получены.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_previews: Vec<LinkPreviewSummary>,
+}
+
+/// Вид цели упоминания в сообщении.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextChatMentionKind {
+    /// Упомянут участник сервера по никнейму.
+    User,
+    /// Упомянута роль сервера по названию.
+    Role,
+}
+
+/// Разрешенное упоминание пользователя или роли в сообщении.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChatMention {
+    /// Вид цели упоминания.
+    pub kind: TextChatMentionKind,
+    /// Идентификатор пользователя или роли.
+    pub target_id: String,
+    /// Снимок никнейма или названия роли на момент отправки сообщения.
+    pub name: String,
+}
+
+/// Полезная нагрузка запроса на отметку комнаты прочитанной.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkRoomRead {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Последнее сообщение комнаты, которое увидел пользователь.
+    pub message_id: String,
+}
+
+/// Персональное событие об изменении отметки прочтения комнаты.
+///
+/// Рассылается всем сессиям пользователя, чтобы счетчики непрочитанного
+/// совпадали на всех устройствах.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomReadUpdatedPayload {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Последнее прочитанное сообщение комнаты.
+    pub last_read_message_id: Option<String>,
+    /// Количество непрочитанных сообщений других участников после отметки.
+    pub unread_count: u32,
+    /// Количество непрочитанных сообщений с упоминанием пользователя или его ролей.
+    pub mention_count: u32,
+}
+
+/// Событие готовности карточек ссылок для сообщения комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessagePreviewReadyPayload {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор сообщения.
+    pub message_id: String,
+    /// Карточки ссылок в порядке их появления в тексте.
+    pub previews: Vec<LinkPreviewSummary>,
+}
+
+/// Персональное уведомление об упоминании текущего пользователя в сообщении комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MentionReceivedPayload {
+    /// Сообщение, в котором упомянут пользователь.
+    pub message: TextChatMessage,
+}
+
+/// Компактный снимок цитируемого сообщения текстового чата.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChatReplySnapshot {
+    /// Идентификатор цитируемого сообщения.
+    pub message_id: String,
+    /// Идентификатор пользователя-автора цитируемого сообщения.
+    pub author_user_id: String,
+    /// Снимок ника автора цитируемого сообщения.
+    pub author_nickname: String,
+    /// Укороченное тело цитируемого сообщения; пустое для удаленных сообщений.
+    pub body: String,
+    /// Есть ли в цитируемом сообщении вложения.
+    #[serde(default)]
+    pub has_attachments: bool,
+    /// Было ли цитируемое сообщение удалено.
+    #[serde(default)]
+    pub deleted: bool,
 }
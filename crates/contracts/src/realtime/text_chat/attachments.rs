@@ -0,0 +1,101 @@
+//! Realtime-контракты вложений сообщений комнат.
+
+use serde::{Deserialize, Serialize};
+
+/// Вид вложения, определенный сервером по содержимому файла.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TextChatAttachmentKind {
+    /// Изображение, которое показывается в ленте.
+    #[default]
+    Image,
+    /// Видеофайл.
+    Video,
+    /// Аудиофайл.
+    Audio,
+    /// Любой другой файл, доступный только для скачивания.
+    File,
+}
+
+/// Полезная нагрузка запроса для загрузки одного вложения чата через realtime.
+///
+/// Размер ограничен realtime-кадром; крупные файлы загружаются по HTTP.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UploadChatAttachment {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Необязательное исходное имя файла.
+    pub original_filename: Option<String>,
+    /// Байты файла в Base64.
+    pub data_base64: String,
+}
+
+/// Ответ после загрузки вложения чата.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatAttachmentUploadResponse {
+    /// Стабильный идентификатор вложения.
+    pub id: String,
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Вид вложения.
+    #[serde(default)]
+    pub kind: TextChatAttachmentKind,
+    /// MIME-тип, определенный по содержимому.
+    pub content_type: String,
+    /// Загруженный размер в байтах.
+    pub byte_size: i64,
+    /// Ширина изображения в пикселях.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    /// Высота изображения в пикселях.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    /// Очищенное исходное имя файла.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_filename: Option<String>,
+}
+
+/// Метаданные вложения, включаемые в сообщение текстового чата.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChatAttachment {
+    /// Стабильный идентификатор вложения.
+    pub id: String,
+    /// Вид вложения.
+    #[serde(default)]
+    pub kind: TextChatAttachmentKind,
+    /// MIME-тип, определенный по содержимому.
+    pub content_type: String,
+    /// Загруженный размер в байтах.
+    pub byte_size: i64,
+    /// Ширина изображения в пикселях.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub width: Option<i32>,
+    /// Высота изображения в пикселях.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub height: Option<i32>,
+    /// Очищенное исходное имя файла.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub original_filename: Option<String>,
+}
+
+/// Полезная нагрузка запроса для загрузки одного вложения-изображения чата.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoadChatImage {
+    /// Стабильный идентификатор вложения.
+    pub attachment_id: String,
+}
+
+/// Полезная нагрузка ответа с одним вложением-изображением чата.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChatImageLoadedResponse {
+    /// Стабильный идентификатор вложения.
+    pub id: String,
+    /// Проверенный MIME-тип изображения.
+    pub content_type: String,
+    /// Байты изображения в Base64.
+    pub data_base64: String,
+}
@@ -0,0 +1,74 @@
+//! Realtime-контракты закрепленных сообщений комнат.
+
+use serde::{Deserialize, Serialize};
+
+use super::TextChatMessage;
+
+/// Полезная нагрузка запроса для закрепления сообщения комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinMessage {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор закрепляемого сообщения.
+    pub message_id: String,
+}
+
+/// Полезная нагрузка запроса для открепления сообщения комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnpinMessage {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор открепляемого сообщения.
+    pub message_id: String,
+}
+
+/// Полезная нагрузка запроса для загрузки закрепленных сообщений комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListPinnedMessages {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+}
+
+/// Закрепленное сообщение комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextChatPinnedMessage {
+    /// Закрепленное сообщение.
+    pub message: TextChatMessage,
+    /// Пользователь, закрепивший сообщение.
+    pub pinned_by_user_id: String,
+    /// Временная метка закрепления в формате RFC3339.
+    pub pinned_at: String,
+}
+
+/// Полезная нагрузка ответа с закрепленными сообщениями комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedMessagesList {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Закрепленные сообщения от недавно закрепленных к давно закрепленным.
+    pub pins: Vec<TextChatPinnedMessage>,
+}
+
+/// Полезная нагрузка широковещания о закреплении или откреплении сообщения комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessagePinnedPayload {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор сообщения.
+    pub message_id: String,
+    /// Закреплено ли сообщение после изменения.
+    pub pinned: bool,
+    /// Закрепление с сообщением; отсутствует, когда сообщение откреплено.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin: Option<TextChatPinnedMessage>,
+}
@@ -2,6 +2,20 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::video_presets::VideoSpatialLayer;
+
+mod active_speakers;
+mod audio_mixing;
+mod moderation;
+mod recording;
+mod video_codecs;
+
+pub use active_speakers::VoiceActiveSpeakersChanged;
+pub use audio_mixing::SetVoiceRoomAudioMixing;
+pub use moderation::{ServerDeafenVoiceMember, ServerMuteVoiceMember};
+pub use recording::{StartVoiceRoomRecording, StopVoiceRoomRecording, VoiceRoomRecordingStatus};
+pub use video_codecs::{AdvertiseVoiceCodecCapabilities, VoiceVideoCodec, VoiceVideoCodecSelected};
+
 /// Виды сообщений модуля присутствия в голосовом чате.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -36,6 +50,12 @@ pub enum VoiceChatKind {
     ListDirectMessageVoiceRooms,
     /// Сообщить об остановке локального видеопотока в голосовой комнате.
     StopVideoStream,
+    /// Попросить участника комнаты закодировать ключевой кадр видеопотока.
+    RequestKeyFrame,
+    /// Начать получать видеопоток участника голосовой комнаты.
+    SubscribeVideo,
+    /// Перестать получать видеопоток участника голосовой комнаты.
+    UnsubscribeVideo,
     /// Выдать одноразовый grant для отдельной сессии отправки микрофона.
     IssueMicrophoneUplinkGrant,
     /// Одноразовый grant для отдельной сессии отправки микрофона выдан.
@@ -54,6 +74,28 @@ pub enum VoiceChatKind {
     ParticipantsChanged,
     /// Событие остановки видеопотока участника голосовой комнаты.
     VideoStreamEnded,
+    /// Событие активности видеопотока участника для неподписанных зрителей.
+    VideoStreamActive,
+    /// Адресное событие с просьбой закодировать ключевой кадр видеопотока.
+    KeyFrameRequested,
+    /// Адресное событие с долей потерь голоса отправителя по отчетам получателей.
+    UplinkLossReported,
+    /// Сообщить видеокодеки, которые клиент умеет декодировать и кодировать.
+    AdvertiseCodecCapabilities,
+    /// Видеокодек публикаций комнаты, выбранный сервером.
+    VideoCodecSelected,
+    /// Запустить запись голосовой комнаты сервера.
+    StartRecording,
+    /// Остановить запись голосовой комнаты сервера.
+    StopRecording,
+    /// Включить или выключить серверное сведение звука комнаты.
+    SetAudioMixing,
+    /// Событие изменения говорящих участников голосовой комнаты.
+    ActiveSpeakersChanged,
+    /// Отключить или вернуть микрофон участнику голосовой комнаты на стороне сервера.
+    ServerMuteVoiceMember,
+    /// Отключить или вернуть звук участнику голосовой комнаты на стороне сервера.
+    ServerDeafenVoiceMember,
 }
 
 /// Полезная нагрузка запроса на присоединение к комнате с поддержкой голоса.
@@ -246,6 +288,12 @@ pub struct VoiceRoomSnapshot {
     pub room_id: String,
     /// Участники, присутствующие в комнате.
     pub participants: Vec<VoiceRoomParticipant>,
+    /// Идущая запись комнаты; `None`, когда комнату не записывают.
+    #[serde(default)]
+    pub recording: Option<VoiceRoomRecordingStatus>,
+    /// Сводит ли сервер голоса комнаты в один поток для каждого участника.
+    #[serde(default)]
+    pub audio_mixing: bool,
 }
 
 /// Полезная нагрузка запроса на исключение участника из голосовой комнаты.
@@ -304,6 +352,86 @@ pub struct VoiceVideoStreamEnded {
     pub source: VoiceVideoStreamSource,
 }
 
+/// Событие активности видеопотока, на который получатель не подписан.
+///
+/// Relay повторяет событие, пока отправитель публикует кадры, чтобы клиент
+/// показывал плитку потока без получения самого видео.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceVideoStreamActive {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Пользователь, публикующий видеопоток.
+    pub user_id: String,
+    /// Активный источник видео.
+    pub source: VoiceVideoStreamSource,
+}
+
+/// Полезная нагрузка подписки на видеопоток участника голосовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubscribeVoiceVideo {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Пользователь, который публикует видеопоток.
+    pub user_id: String,
+    /// Источник видео, кадры которого нужно получать.
+    pub source: VoiceVideoStreamSource,
+    /// Simulcast-слой, который нужен получателю; повторная подписка меняет слой.
+    #[serde(default)]
+    pub layer: VideoSpatialLayer,
+}
+
+/// Полезная нагрузка отписки от видеопотока участника голосовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnsubscribeVoiceVideo {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Пользователь, который публикует видеопоток.
+    pub user_id: String,
+    /// Источник видео, кадры которого больше не нужны.
+    pub source: VoiceVideoStreamSource,
+}
+
+/// Полезная нагрузка запроса ключевого кадра у отправителя видеопотока.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestVoiceKeyFrame {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Пользователь, который отправляет видеопоток.
+    pub publisher_user_id: String,
+    /// Источник видео, для которого нужен ключевой кадр.
+    pub source: VoiceVideoStreamSource,
+}
+
+/// Адресное событие с просьбой закодировать ключевой кадр видеопотока.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceKeyFrameRequested {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Источник видео, для которого нужен ключевой кадр.
+    pub source: VoiceVideoStreamSource,
+}
+
+/// Адресное событие с долей потерь голоса отправителя по отчетам получателей.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceUplinkLossReported {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Процент кадров отправителя, не дошедших даже до лучшего получателя.
+    pub loss_percent: u8,
+}
+
 /// Снимки активных голосовых комнат одного сервера.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ServerVoiceRoomsSnapshot {
@@ -331,4 +459,13 @@ pub struct VoiceRoomParticipant {
     pub avatar_url: Option<String>,
     /// Метка времени RFC3339, когда этот участник присоединился.
     pub joined_at: String,
+    /// Оценка качества соединения от 0 до 100 по отчетам получателей; `None`, пока отчетов нет.
+    #[serde(default)]
+    pub connection_quality: Option<u8>,
+    /// Микрофон участника отключен модератором сервера.
+    #[serde(default)]
+    pub server_muted: bool,
+    /// Звук участнику отключен модератором сервера.
+    #[serde(default)]
+    pub server_deafened: bool,
 }
@@ -0,0 +1,20 @@
+//! Контракты активных говорящих голосовой комнаты.
+
+use serde::{Deserialize, Serialize};
+
+/// Событие изменения говорящих участников голосовой комнаты.
+///
+/// Сервер оценивает громкость по уровню звука из голосовых датаграмм и
+/// рассылает событие не чаще нескольких раз в секунду, поэтому клиенту не нужно
+/// декодировать каждый поток, чтобы подсветить говорящих.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceActiveSpeakersChanged {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Говорящие участники: сначала доминирующий, затем по убыванию громкости.
+    pub speaker_user_ids: Vec<String>,
+    /// Доминирующий говорящий, если кто-то говорит.
+    pub dominant_user_id: Option<String>,
+}
@@ -0,0 +1,17 @@
+//! Контракты режима серверного сведения звука голосовой комнаты.
+
+use serde::{Deserialize, Serialize};
+
+/// Полезная нагрузка запроса на переключение серверного сведения звука комнаты.
+///
+/// В режиме сведения сервер присылает каждому участнику один голосовой поток
+/// с самыми громкими говорящими вместо отдельного потока от каждого.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SetVoiceRoomAudioMixing {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Включить ли сведение.
+    pub enabled: bool,
+}
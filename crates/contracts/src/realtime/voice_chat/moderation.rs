@@ -0,0 +1,33 @@
+//! Контракты серверной модерации участников голосовой комнаты.
+
+use serde::{Deserialize, Serialize};
+
+/// Полезная нагрузка запроса на серверное отключение микрофона участника.
+///
+/// Пока флаг установлен, сервер не пересылает голос участника остальным.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerMuteVoiceMember {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор пользователя.
+    pub user_id: String,
+    /// Отключить ли микрофон; `false` снимает ограничение.
+    pub muted: bool,
+}
+
+/// Полезная нагрузка запроса на серверное отключение звука участнику.
+///
+/// Пока флаг установлен, сервер не пересылает участнику голос комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerDeafenVoiceMember {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Идентификатор пользователя.
+    pub user_id: String,
+    /// Отключить ли звук; `false` снимает ограничение.
+    pub deafened: bool,
+}
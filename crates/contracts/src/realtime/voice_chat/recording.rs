@@ -0,0 +1,32 @@
+//! Контракты записи голосовой комнаты сервера.
+
+use serde::{Deserialize, Serialize};
+
+/// Полезная нагрузка запроса на запуск записи голосовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StartVoiceRoomRecording {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+}
+
+/// Полезная нагрузка запроса на остановку записи голосовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StopVoiceRoomRecording {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+}
+
+/// Идущая запись голосовой комнаты, видимая всем участникам.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceRoomRecordingStatus {
+    /// Стабильный идентификатор записи.
+    pub recording_id: String,
+    /// Пользователь, запустивший запись.
+    pub started_by_user_id: String,
+    /// Метка времени RFC3339 начала записи.
+    pub started_at: String,
+}
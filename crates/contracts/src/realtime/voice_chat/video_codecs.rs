@@ -0,0 +1,65 @@
+//! Контракты согласования видеокодека голосовой комнаты.
+
+use serde::{Deserialize, Serialize};
+
+use crate::media::MediaCodec;
+
+/// Видеокодек камеры и демонстрации экрана.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceVideoCodec {
+    /// Видео VP9.
+    Vp9,
+    /// Видео AV1.
+    Av1,
+    /// Видео H.264.
+    H264,
+}
+
+impl VoiceVideoCodec {
+    /// Возвращает кодек, которым помечаются медиадатаграммы этого видео.
+    pub const fn media_codec(self) -> MediaCodec {
+        match self {
+            Self::Vp9 => MediaCodec::Vp9,
+            Self::Av1 => MediaCodec::Av1,
+            Self::H264 => MediaCodec::H264,
+        }
+    }
+
+    /// Возвращает видеокодек медиадатаграммы или `None` для аудиокодеков.
+    pub const fn from_media_codec(codec: MediaCodec) -> Option<Self> {
+        match codec {
+            MediaCodec::Vp9 => Some(Self::Vp9),
+            MediaCodec::Av1 => Some(Self::Av1),
+            MediaCodec::H264 => Some(Self::H264),
+            MediaCodec::Opus => None,
+        }
+    }
+}
+
+/// Видеокодеки, которые клиент умеет декодировать и кодировать.
+///
+/// Клиент отправляет сообщение после входа в комнату; до этого сервер считает,
+/// что участник работает только с VP9.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdvertiseVoiceCodecCapabilities {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Кодеки, которые клиент умеет декодировать; пустой список — клиент не показывает видео.
+    pub decoders: Vec<VoiceVideoCodec>,
+    /// Кодеки, которыми клиент умеет публиковать камеру и экран.
+    pub encoders: Vec<VoiceVideoCodec>,
+}
+
+/// Видеокодек, выбранный сервером для публикаций комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceVideoCodecSelected {
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор комнаты.
+    pub room_id: String,
+    /// Кодек, который декодируют все участники комнаты, показывающие видео.
+    pub codec: VoiceVideoCodec,
+}
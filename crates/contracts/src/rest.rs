@@ -5,6 +5,7 @@ pub mod error;
 pub mod push_notifications;
 pub mod servers;
 pub mod social;
+pub mod voice_chat;
 
 pub use auth::{
     ActiveSession, ActiveSessionsResponse, AuthResponse, AuthUser,
@@ -20,18 +21,24 @@ pub use push_notifications::{PushPlatform, UpsertPushInstallationRequest};
 pub use servers::{
     AcceptServerInviteResponse, CreateServerInviteRequest, CreateServerInviteResponse,
     CreateServerRequest, CreateServerResponse, CreateServerRoomRequest, CreateServerRoomResponse,
-    ListServerRoomsResponse, ListServersResponse, ServerInviteInfoResponse, ServerInviteSummary,
-    ServerRoomKind, ServerRoomSummary, ServerSummary, UpdateServerAvatarResponse,
-    UpdateServerRequest, UpdateServerResponse, UpdateServerRoomRequest, UpdateServerRoomResponse,
+    ListServerRoomsResponse, ListServersResponse, SearchServerMessagesResponse,
+    ServerInviteInfoResponse, ServerInviteSummary, ServerRoomKind, ServerRoomSummary,
+    ServerSummary, UpdateServerAvatarResponse, UpdateServerRequest, UpdateServerResponse,
+    UpdateServerRoomRequest, UpdateServerRoomResponse,
 };
 pub use social::{
     DmConversationSummary, DmImageAttachmentSummary, DmMessageDeliveryStatus, DmMessageSummary,
-    FriendRequestStatus, FriendRequestSummary, FriendSummary, ListDmConversationsResponse,
-    ListDmMessagesResponse, ListFriendRequestsResponse, ListFriendsResponse,
-    MarkDmConversationReadRequest, MarkDmConversationReadResponse, OpenDmConversationRequest,
-    OpenDmConversationResponse, SearchUsersResponse, SendDmMessageRequest, SendDmMessageResponse,
-    SendFriendRequestRequest, SendFriendRequestResponse, UploadDmImageResponse, UserRelationStatus,
-    UserSearchResult,
+    FriendRequestStatus, FriendRequestSummary, FriendSummary, LinkPreviewSummary,
+    ListDmConversationsResponse, ListDmMessagesResponse, ListFriendRequestsResponse,
+    ListFriendsResponse, MarkDmConversationReadRequest, MarkDmConversationReadResponse,
+    MessageReactionSummary, OpenDmConversationRequest, OpenDmConversationResponse,
+    SearchDmMessagesResponse, SearchUsersResponse, SendDmMessageRequest, SendDmMessageResponse,
+    SendFriendRequestRequest, SendFriendRequestResponse, UploadDmAttachmentResponse,
+    UserRelationStatus, UserSearchResult,
+};
+pub use voice_chat::{
+    ListVoiceRecordingsResponse, VoiceRecordingSummary, VoiceRecordingTrack,
+    VoiceRecordingTrackKind,
 };
 
 #[cfg(test)]
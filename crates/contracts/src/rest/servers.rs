@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::realtime::ServerRoleSummary;
+use crate::realtime::{ServerRoleSummary, TextChatMessage};
 
 /// Тело запроса для создания нового сервера.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -82,6 +82,12 @@ pub struct ServerRoomSummary {
     pub kind: ServerRoomKind,
     /// Позиция комнаты в порядке добавления внутри сервера.
     pub position: u32,
+    /// Количество непрочитанных текущим пользователем сообщений комнаты.
+    #[serde(default)]
+    pub unread_count: u32,
+    /// Количество непрочитанных сообщений с упоминанием пользователя или его ролей.
+    #[serde(default)]
+    pub mention_count: u32,
 }
 
 /// Тело запроса для создания комнаты сервера.
@@ -176,3 +182,12 @@ pub struct ListServersResponse {
     /// Серверы, доступные текущему пользователю.
     pub servers: Vec<ServerSummary>,
 }
+
+/// Ответ на поиск сообщений по текстовым комнатам сервера.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchServerMessagesResponse {
+    /// Найденные сообщения в порядке от старых к новым.
+    pub messages: Vec<TextChatMessage>,
+    /// Есть ли более старые совпадения перед этой страницей.
+    pub has_more: bool,
+}
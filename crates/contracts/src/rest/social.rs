@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::realtime::TextChatAttachment;
+
 /// Отношение найденного пользователя к текущему пользователю.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -166,12 +168,55 @@ pub struct DmMessageSummary {
     pub sender_avatar_url: Option<String>,
     /// Текст сообщения.
     pub body: String,
-    /// Изображение, прикреплённое к сообщению.
+    /// Изображение старого формата, прикреплённое до появления типизированных вложений.
     pub image: Option<DmImageAttachmentSummary>,
+    /// Вложение любого вида, прикреплённое к сообщению.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<TextChatAttachment>,
     /// Статус доставки для текущего пользователя, если сообщение исходящее.
     pub delivery_status: Option<DmMessageDeliveryStatus>,
     /// Временная метка создания в формате RFC3339.
     pub created_at: String,
+    /// Реакции на сообщение, сгруппированные по эмодзи.
+    #[serde(default)]
+    pub reactions: Vec<MessageReactionSummary>,
+    /// Карточки ссылок из текста сообщения, если они уже получены.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_previews: Vec<LinkPreviewSummary>,
+}
+
+/// Агрегированная реакция одним эмодзи на сообщение комнаты или личного диалога.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessageReactionSummary {
+    /// Эмодзи реакции.
+    pub emoji: String,
+    /// Количество пользователей, поставивших реакцию.
+    pub count: i64,
+    /// Поставил ли реакцию текущий пользователь.
+    ///
+    /// В широковещательных событиях поле не персонализируется и всегда `false`;
+    /// клиент сохраняет собственный флаг и обновляет его по автору изменения.
+    #[serde(default)]
+    pub reacted: bool,
+}
+
+/// Карточка ссылки из текста сообщения комнаты или личного диалога.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPreviewSummary {
+    /// Ссылка в том виде, в котором она встречается в тексте сообщения.
+    pub url: String,
+    /// Заголовок страницы.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Краткое описание страницы.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Название сайта.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site_name: Option<String>,
+    /// Публичный URL сохраненной копии изображения страницы.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_url: Option<String>,
 }
 
 /// Метаданные изображения в личном сообщении.
@@ -219,6 +264,15 @@ pub struct ListDmMessagesResponse {
     pub has_more: bool,
 }
 
+/// Ответ на поиск сообщений по личным диалогам пользователя.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchDmMessagesResponse {
+    /// Найденные сообщения в порядке от старых к новым.
+    pub messages: Vec<DmMessageSummary>,
+    /// Есть ли более старые совпадения перед этой страницей.
+    pub has_more: bool,
+}
+
 /// Запрос на отметку личного диалога прочитанным.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MarkDmConversationReadRequest {
@@ -248,15 +302,16 @@ pub struct MarkDmConversationReadResponse {
 pub struct SendDmMessageRequest {
     /// Текст сообщения.
     pub body: String,
-    /// Ранее загруженное изображение, которое нужно прикрепить.
-    pub image_id: Option<String>,
+    /// Ранее загруженное вложение, которое нужно прикрепить.
+    #[serde(default)]
+    pub attachment_id: Option<String>,
 }
 
-/// Ответ после загрузки изображения для личного сообщения.
+/// Ответ после загрузки вложения для личного сообщения.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-pub struct UploadDmImageResponse {
-    /// Метаданные загруженного изображения.
-    pub image: DmImageAttachmentSummary,
+pub struct UploadDmAttachmentResponse {
+    /// Метаданные загруженного вложения.
+    pub attachment: TextChatAttachment,
 }
 
 /// Ответ на отправку личного сообщения.
@@ -0,0 +1,63 @@
+//! Контракты REST для записей голосовых комнат.
+
+use serde::{Deserialize, Serialize};
+
+/// Вид дорожки записи голосовой комнаты.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoiceRecordingTrackKind {
+    /// Голос участника в Ogg Opus.
+    Voice,
+    /// Камера участника в WebM VP9.
+    Camera,
+    /// Демонстрация экрана участника в WebM VP9.
+    ScreenShare,
+}
+
+/// Один файл завершенной записи голосовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceRecordingTrack {
+    /// Участник, чье медиа содержит дорожка.
+    pub user_id: String,
+    /// Снимок ника участника на момент записи.
+    pub nickname: String,
+    /// Вид дорожки.
+    pub kind: VoiceRecordingTrackKind,
+    /// Имя файла дорожки внутри записи.
+    pub file_name: String,
+    /// MIME-тип файла.
+    pub content_type: String,
+    /// Размер файла в байтах.
+    pub byte_size: u64,
+    /// URL скачивания файла.
+    pub url: String,
+}
+
+/// Завершенная запись голосовой комнаты.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VoiceRecordingSummary {
+    /// Стабильный идентификатор записи.
+    pub id: String,
+    /// Идентификатор сервера.
+    pub server_id: String,
+    /// Идентификатор записанной комнаты.
+    pub room_id: String,
+    /// Пользователь, запустивший запись.
+    pub started_by_user_id: String,
+    /// Метка времени RFC3339 начала записи.
+    pub started_at: String,
+    /// Метка времени RFC3339 окончания записи.
+    pub finished_at: String,
+    /// Дорожки участников; все они начинаются в момент `started_at`.
+    pub tracks: Vec<VoiceRecordingTrack>,
+}
+
+/// Успешный ответ со списком записей голосовых комнат сервера.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ListVoiceRecordingsResponse {
+    /// Записи от новых к старым.
+    pub recordings: Vec<VoiceRecordingSummary>,
+    /// Есть ли записи старше последней в этой странице.
+    #[serde(default)]
+    pub has_more: bool,
+}
@@ -27,6 +27,44 @@ pub enum VideoPresetId {
     Screen1080p15,
 }
 
+/// Пространственный слой simulcast-публикации видео.
+///
+/// Отправитель кодирует один источник в нескольких разрешениях, а relay
+/// пересылает каждому получателю только один слой.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum VideoSpatialLayer {
+    /// Уменьшенный слой для маленьких плиток и слабых каналов.
+    Low,
+    /// Слой в полном разрешении пресета.
+    #[default]
+    High,
+}
+
+impl VideoSpatialLayer {
+    /// Все слои в порядке возрастания качества.
+    pub const ALL: [Self; 2] = [Self::Low, Self::High];
+
+    /// Возвращает идентификатор слоя в медиадатаграмме.
+    pub const fn id(self) -> u8 {
+        match self {
+            Self::Low => 0,
+            Self::High => 1,
+        }
+    }
+
+    /// Восстанавливает слой по идентификатору из медиадатаграммы.
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::Low),
+            1 => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
 /// Числовые параметры пресета исходящего видео.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VideoPresetSpec {
@@ -43,7 +81,41 @@ pub struct VideoPresetSpec {
 }
 
 impl VideoPresetId {
-    /// Возвращает числовые параметры пресета.
+    /// Возвращает числовые параметры одного simulcast-слоя пресета.
+    pub const fn layer_spec(self, layer: VideoSpatialLayer) -> VideoPresetSpec {
+        match layer {
+            VideoSpatialLayer::High => self.spec(),
+            VideoSpatialLayer::Low => self.low_layer_spec(),
+        }
+    }
+
+    const fn low_layer_spec(self) -> VideoPresetSpec {
+        match self {
+            Self::Camera720p24 => VideoPresetSpec {
+                source: VideoStreamSource::Camera,
+                width: 640,
+                height: 360,
+                max_fps: 24,
+                bitrate_bps: 250_000,
+            },
+            Self::Screen720p30 => VideoPresetSpec {
+                source: VideoStreamSource::ScreenShare,
+                width: 640,
+                height: 360,
+                max_fps: 30,
+                bitrate_bps: 450_000,
+            },
+            Self::Screen1080p15 => VideoPresetSpec {
+                source: VideoStreamSource::ScreenShare,
+                width: 960,
+                height: 540,
+                max_fps: 15,
+                bitrate_bps: 450_000,
+            },
+        }
+    }
+
+    /// Возвращает числовые параметры пресета в полном разрешении.
     pub const fn spec(self) -> VideoPresetSpec {
         match self {
             Self::Camera720p24 => VideoPresetSpec {
@@ -71,6 +143,13 @@ impl VideoPresetId {
     }
 }
 
+/// Все пресеты исходящего видео, которые понимают клиент и сервер.
+pub const ALL_VIDEO_PRESETS: &[VideoPresetId] = &[
+    VideoPresetId::Camera720p24,
+    VideoPresetId::Screen720p30,
+    VideoPresetId::Screen1080p15,
+];
+
 /// Базовые пресеты камеры без расширений подписки.
 pub const BASE_CAMERA_VIDEO_PRESETS: &[VideoPresetId] = &[VideoPresetId::Camera720p24];
 
@@ -96,6 +175,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn low_layers_are_smaller_than_high_layers() {
+        for preset in ALL_VIDEO_PRESETS {
+            let low = preset.layer_spec(VideoSpatialLayer::Low);
+            let high = preset.layer_spec(VideoSpatialLayer::High);
+
+            assert_eq!(low.source, high.source);
+            assert!(low.width < high.width && low.height < high.height);
+            assert!(low.bitrate_bps < high.bitrate_bps);
+        }
+        for layer in VideoSpatialLayer::ALL {
+            assert_eq!(VideoSpatialLayer::from_id(layer.id()), Some(layer));
+        }
+    }
+
     #[test]
     fn preset_ids_round_trip_through_json() {
         let encoded =
@@ -30,6 +30,18 @@ mod m20260630_000026_add_dm_read_state;
 mod m20260713_000027_create_push_notifications;
 mod m20260718_000028_add_dm_message_images;
 mod m20260811_000029_create_legal_acceptances;
+mod m20261016_000030_add_text_message_edits;
+mod m20261016_000031_add_text_message_replies;
+mod m20261016_000032_create_message_reactions;
+mod m20261016_000033_create_text_message_mentions;
+mod m20261016_000034_add_message_search_indexes;
+mod m20261016_000035_create_text_room_read_states;
+mod m20261016_000036_create_text_message_pins;
+mod m20261017_000037_create_link_previews;
+mod m20261017_000038_add_text_chat_attachment_kinds;
+mod m20261017_000039_create_server_media_settings;
+mod m20261017_000040_seed_text_room_read_states;
+mod m20261017_000041_create_dm_attachments;
 
 pub use sea_orm_migration::prelude::*;
 
@@ -69,6 +81,18 @@ impl MigratorTrait for Migrator {
             Box::new(m20260713_000027_create_push_notifications::Migration),
             Box::new(m20260718_000028_add_dm_message_images::Migration),
             Box::new(m20260811_000029_create_legal_acceptances::Migration),
+            Box::new(m20261016_000030_add_text_message_edits::Migration),
+            Box::new(m20261016_000031_add_text_message_replies::Migration),
+            Box::new(m20261016_000032_create_message_reactions::Migration),
+            Box::new(m20261016_000033_create_text_message_mentions::Migration),
+            Box::new(m20261016_000034_add_message_search_indexes::Migration),
+            Box::new(m20261016_000035_create_text_room_read_states::Migration),
+            Box::new(m20261016_000036_create_text_message_pins::Migration),
+            Box::new(m20261017_000037_create_link_previews::Migration),
+            Box::new(m20261017_000038_add_text_chat_attachment_kinds::Migration),
+            Box::new(m20261017_000039_create_server_media_settings::Migration),
+            Box::new(m20261017_000040_seed_text_room_read_states::Migration),
+            Box::new(m20261017_000041_create_dm_attachments::Migration),
         ]
     }
 }
@@ -0,0 +1,129 @@
+//! Adds message editing support for text messages.
+
+use sea_orm_migration::prelude::*;
+
+/// Adds `edited_at` to `text_messages` and creates the `text_message_edits` history table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TextMessages::Table)
+                    .add_column(
+                        ColumnDef::new(TextMessages::EditedAt)
+                            .timestamp_with_time_zone()
+                            .null(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_table(
+                Table::create()
+                    .table(TextMessageEdits::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TextMessageEdits::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageEdits::MessageId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageEdits::EditorUserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageEdits::PreviousBody)
+                            .string_len(2000)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageEdits::EditedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_message_edits_message")
+                            .from(TextMessageEdits::Table, TextMessageEdits::MessageId)
+                            .to(TextMessages::Table, TextMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_message_edits_editor")
+                            .from(TextMessageEdits::Table, TextMessageEdits::EditorUserId)
+                            .to(Users::Table, Users::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_text_message_edits_message_edited")
+                    .table(TextMessageEdits::Table)
+                    .col(TextMessageEdits::MessageId)
+                    .col(TextMessageEdits::EditedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_text_message_edits_message_edited")
+                    .table(TextMessageEdits::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(TextMessageEdits::Table).to_owned())
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TextMessages::Table)
+                    .drop_column(TextMessages::EditedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TextMessages {
+    Table,
+    Id,
+    EditedAt,
+}
+
+#[derive(DeriveIden)]
+enum TextMessageEdits {
+    Table,
+    Id,
+    MessageId,
+    EditorUserId,
+    PreviousBody,
+    EditedAt,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,50 @@
+//! Links text messages to the earlier message they reply to.
+
+use sea_orm_migration::prelude::*;
+
+/// Adds a nullable self-reference from `text_messages` to the quoted message.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let mut reply_foreign_key = TableForeignKey::new();
+        reply_foreign_key
+            .name("fk_text_messages_reply_to_message")
+            .from_tbl(TextMessages::Table)
+            .from_col(TextMessages::ReplyToMessageId)
+            .to_tbl(TextMessages::Table)
+            .to_col(TextMessages::Id)
+            .on_delete(ForeignKeyAction::SetNull);
+
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TextMessages::Table)
+                    .add_column(ColumnDef::new(TextMessages::ReplyToMessageId).uuid())
+                    .add_foreign_key(&reply_foreign_key)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TextMessages::Table)
+                    .drop_foreign_key(Alias::new("fk_text_messages_reply_to_message"))
+                    .drop_column(TextMessages::ReplyToMessageId)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TextMessages {
+    Table,
+    Id,
+    ReplyToMessageId,
+}
@@ -0,0 +1,129 @@
+//! Creates emoji reactions for text room and direct messages.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates the `message_reactions` table shared by room and direct messages.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MessageReactions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MessageReactions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageReactions::TextMessageId)
+                            .uuid()
+                            .null(),
+                    )
+                    .col(ColumnDef::new(MessageReactions::DmMessageId).uuid().null())
+                    .col(ColumnDef::new(MessageReactions::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(MessageReactions::Emoji)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MessageReactions::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .check(Expr::cust(
+                        "(text_message_id IS NULL) <> (dm_message_id IS NULL)",
+                    ))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_reactions_text_message")
+                            .from(MessageReactions::Table, MessageReactions::TextMessageId)
+                            .to(TextMessages::Table, TextMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_reactions_dm_message")
+                            .from(MessageReactions::Table, MessageReactions::DmMessageId)
+                            .to(DmMessages::Table, DmMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_message_reactions_user")
+                            .from(MessageReactions::Table, MessageReactions::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_message_reactions_text_message_user_emoji")
+                    .table(MessageReactions::Table)
+                    .col(MessageReactions::TextMessageId)
+                    .col(MessageReactions::UserId)
+                    .col(MessageReactions::Emoji)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_message_reactions_dm_message_user_emoji")
+                    .table(MessageReactions::Table)
+                    .col(MessageReactions::DmMessageId)
+                    .col(MessageReactions::UserId)
+                    .col(MessageReactions::Emoji)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MessageReactions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MessageReactions {
+    Table,
+    Id,
+    TextMessageId,
+    DmMessageId,
+    UserId,
+    Emoji,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum TextMessages {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum DmMessages {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,90 @@
+//! Creates resolved `@` mention targets for text room messages.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates the `text_message_mentions` table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TextMessageMentions::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TextMessageMentions::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageMentions::MessageId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageMentions::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageMentions::TargetId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessageMentions::Name)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .check(Expr::cust("kind IN ('user', 'role')"))
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_message_mentions_message")
+                            .from(TextMessageMentions::Table, TextMessageMentions::MessageId)
+                            .to(TextMessages::Table, TextMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_text_message_mentions_message_kind_target")
+                    .table(TextMessageMentions::Table)
+                    .col(TextMessageMentions::MessageId)
+                    .col(TextMessageMentions::Kind)
+                    .col(TextMessageMentions::TargetId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TextMessageMentions::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TextMessageMentions {
+    Table,
+    Id,
+    MessageId,
+    Kind,
+    TargetId,
+    Name,
+}
+
+#[derive(DeriveIden)]
+enum TextMessages {
+    Table,
+    Id,
+}
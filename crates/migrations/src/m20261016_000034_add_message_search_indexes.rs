@@ -0,0 +1,54 @@
+//! Adds full-text search indexes for server room and direct message bodies.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates GIN indexes that back message search in rooms and direct messages.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Search queries repeat the exact `to_tsvector('simple', body)` expression,
+        // so Postgres can match them against these expression indexes.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_text_messages_body_search
+                ON text_messages
+                USING GIN (to_tsvector('simple', body))
+                WHERE deleted_at IS NULL
+                "#,
+            )
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                CREATE INDEX IF NOT EXISTS idx_dm_messages_body_search
+                ON dm_messages
+                USING GIN (to_tsvector('simple', body))
+                WHERE deleted_at IS NULL
+                "#,
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_dm_messages_body_search")
+            .await?;
+
+        manager
+            .get_connection()
+            .execute_unprepared("DROP INDEX IF EXISTS idx_text_messages_body_search")
+            .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+//! Creates per-member read markers for server text rooms.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates the `text_room_read_states` table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TextRoomReadStates::Table)
+                    .if_not_exists()
+                    .col(ColumnDef::new(TextRoomReadStates::RoomId).uuid().not_null())
+                    .col(ColumnDef::new(TextRoomReadStates::UserId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(TextRoomReadStates::LastReadMessageId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextRoomReadStates::LastReadMessageCreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextRoomReadStates::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .col(TextRoomReadStates::RoomId)
+                            .col(TextRoomReadStates::UserId),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_room_read_states_room")
+                            .from(TextRoomReadStates::Table, TextRoomReadStates::RoomId)
+                            .to(ServerRooms::Table, ServerRooms::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_room_read_states_user")
+                            .from(TextRoomReadStates::Table, TextRoomReadStates::UserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_room_read_states_last_read_message")
+                            .from(
+                                TextRoomReadStates::Table,
+                                TextRoomReadStates::LastReadMessageId,
+                            )
+                            .to(TextMessages::Table, TextMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TextRoomReadStates::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TextRoomReadStates {
+    Table,
+    RoomId,
+    UserId,
+    LastReadMessageId,
+    LastReadMessageCreatedAt,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum ServerRooms {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum TextMessages {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
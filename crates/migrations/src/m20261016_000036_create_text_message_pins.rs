@@ -0,0 +1,103 @@
+//! Creates pinned messages of server text rooms.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates the `text_message_pins` table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TextMessagePins::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TextMessagePins::MessageId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(TextMessagePins::RoomId).uuid().not_null())
+                    .col(
+                        ColumnDef::new(TextMessagePins::PinnedByUserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(TextMessagePins::PinnedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_message_pins_message")
+                            .from(TextMessagePins::Table, TextMessagePins::MessageId)
+                            .to(TextMessages::Table, TextMessages::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_message_pins_room")
+                            .from(TextMessagePins::Table, TextMessagePins::RoomId)
+                            .to(ServerRooms::Table, ServerRooms::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_text_message_pins_pinned_by_user")
+                            .from(TextMessagePins::Table, TextMessagePins::PinnedByUserId)
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_text_message_pins_room_pinned_at")
+                    .table(TextMessagePins::Table)
+                    .col(TextMessagePins::RoomId)
+                    .col(TextMessagePins::PinnedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TextMessagePins::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TextMessagePins {
+    Table,
+    MessageId,
+    RoomId,
+    PinnedByUserId,
+    PinnedAt,
+}
+
+#[derive(DeriveIden)]
+enum TextMessages {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum ServerRooms {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
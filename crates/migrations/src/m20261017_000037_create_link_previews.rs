@@ -0,0 +1,73 @@
+//! Creates the cache of link previews for chat messages.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates the `link_previews` table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LinkPreviews::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LinkPreviews::Url)
+                            .text()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(LinkPreviews::Title).text().null())
+                    .col(ColumnDef::new(LinkPreviews::Description).text().null())
+                    .col(ColumnDef::new(LinkPreviews::SiteName).text().null())
+                    .col(ColumnDef::new(LinkPreviews::ImageId).uuid().null())
+                    .col(
+                        ColumnDef::new(LinkPreviews::Resolved)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(LinkPreviews::FetchedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_link_previews_image")
+                            .from(LinkPreviews::Table, LinkPreviews::ImageId)
+                            .to(Images::Table, Images::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LinkPreviews::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LinkPreviews {
+    Table,
+    Url,
+    Title,
+    Description,
+    SiteName,
+    ImageId,
+    Resolved,
+    FetchedAt,
+}
+
+#[derive(DeriveIden)]
+enum Images {
+    Table,
+    Id,
+}
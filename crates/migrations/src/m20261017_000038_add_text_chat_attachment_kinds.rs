@@ -0,0 +1,69 @@
+//! Adds typed attachments to text chat rooms.
+
+use sea_orm_migration::prelude::*;
+
+/// Adds `kind` to `text_chat_attachments` and makes image dimensions optional.
+///
+/// Existing rows are images, so they keep their dimensions and get the `image` kind.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TextChatAttachments::Table)
+                    .add_column(
+                        ColumnDef::new(TextChatAttachments::Kind)
+                            .string_len(16)
+                            .not_null()
+                            .default("image"),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE text_chat_attachments \
+                 ALTER COLUMN width DROP NOT NULL, \
+                 ALTER COLUMN height DROP NOT NULL",
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        // Rows without dimensions cannot satisfy the restored constraints.
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "DELETE FROM text_chat_attachments WHERE width IS NULL OR height IS NULL",
+            )
+            .await?;
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "ALTER TABLE text_chat_attachments \
+                 ALTER COLUMN width SET NOT NULL, \
+                 ALTER COLUMN height SET NOT NULL",
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(TextChatAttachments::Table)
+                    .drop_column(TextChatAttachments::Kind)
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum TextChatAttachments {
+    Table,
+    Kind,
+}
@@ -0,0 +1,98 @@
+//! Creates per-server voice and video quality limits.
+
+use sea_orm_migration::prelude::*;
+
+/// Creates the `server_media_settings` table.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ServerMediaSettings::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ServerMediaSettings::ServerId)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ServerMediaSettings::AllowedVideoPresets)
+                            .json_binary()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ServerMediaSettings::AudioBitrateBps)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ServerMediaSettings::MaxVideoPublishers)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ServerMediaSettings::UpdatedByUserId)
+                            .uuid()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(ServerMediaSettings::UpdatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_server_media_settings_server")
+                            .from(ServerMediaSettings::Table, ServerMediaSettings::ServerId)
+                            .to(Servers::Table, Servers::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_server_media_settings_updated_by_user")
+                            .from(
+                                ServerMediaSettings::Table,
+                                ServerMediaSettings::UpdatedByUserId,
+                            )
+                            .to(Users::Table, Users::Id)
+                            .on_delete(ForeignKeyAction::SetNull),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ServerMediaSettings::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum ServerMediaSettings {
+    Table,
+    ServerId,
+    AllowedVideoPresets,
+    AudioBitrateBps,
+    MaxVideoPublishers,
+    UpdatedByUserId,
+    UpdatedAt,
+}
+
+#[derive(DeriveIden)]
+enum Servers {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}
@@ -0,0 +1,47 @@
+//! Seeds read markers of existing server members at the current room tail.
+
+use sea_orm_migration::prelude::*;
+
+/// Marks the history that predates read markers as read for every active member.
+///
+/// Without a marker every message of a room counts as unread, so members who
+/// joined before markers existed would otherwise see the whole history unread.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .get_connection()
+            .execute_unprepared(
+                r#"
+                WITH latest AS (
+                    SELECT DISTINCT ON (room_id) room_id, server_id, id, created_at
+                    FROM text_messages
+                    ORDER BY room_id, created_at DESC, id DESC
+                )
+                INSERT INTO text_room_read_states (
+                    room_id,
+                    user_id,
+                    last_read_message_id,
+                    last_read_message_created_at,
+                    updated_at
+                )
+                SELECT latest.room_id, server_members.user_id, latest.id, latest.created_at, now()
+                FROM latest
+                JOIN server_members
+                    ON server_members.server_id = latest.server_id
+                    AND server_members.left_at IS NULL
+                ON CONFLICT (room_id, user_id) DO NOTHING
+                "#,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn down(&self, _manager: &SchemaManager) -> Result<(), DbErr> {
+        // Seeded markers are indistinguishable from real ones, so they stay.
+        Ok(())
+    }
+}
@@ -0,0 +1,202 @@
+//! Добавляет типизированные вложения к личным сообщениям.
+
+use sea_orm_migration::prelude::*;
+
+/// Создает `dm_attachments` и ссылку личного сообщения на вложение.
+///
+/// Старые изображения остаются в `images` и читаются через `dm_messages.image_id`.
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DmAttachments::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DmAttachments::Id)
+                            .uuid()
+                            .not_null()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::ConversationId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::UploaderUserId)
+                            .uuid()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::Bucket)
+                            .string_len(128)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::ObjectKey)
+                            .string_len(512)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::ContentType)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DmAttachments::ByteSize)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DmAttachments::Width).integer())
+                    .col(ColumnDef::new(DmAttachments::Height).integer())
+                    .col(
+                        ColumnDef::new(DmAttachments::Sha256)
+                            .string_len(64)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(DmAttachments::OriginalFilename).string_len(255))
+                    .col(
+                        ColumnDef::new(DmAttachments::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dm_attachments_conversation")
+                            .from(DmAttachments::Table, DmAttachments::ConversationId)
+                            .to(DmConversations::Table, DmConversations::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk_dm_attachments_uploader")
+                            .from(DmAttachments::Table, DmAttachments::UploaderUserId)
+                            .to(Users::Table, Users::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dm_attachments_object_key")
+                    .table(DmAttachments::Table)
+                    .col(DmAttachments::ObjectKey)
+                    .unique()
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DmMessages::Table)
+                    .add_column(ColumnDef::new(DmMessages::AttachmentId).uuid().null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_foreign_key(
+                ForeignKey::create()
+                    .name("fk_dm_messages_attachment_id")
+                    .from(DmMessages::Table, DmMessages::AttachmentId)
+                    .to(DmAttachments::Table, DmAttachments::Id)
+                    .on_delete(ForeignKeyAction::SetNull)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx_dm_messages_attachment_id_unique")
+                    .table(DmMessages::Table)
+                    .col(DmMessages::AttachmentId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_dm_messages_attachment_id_unique")
+                    .table(DmMessages::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_foreign_key(
+                ForeignKey::drop()
+                    .name("fk_dm_messages_attachment_id")
+                    .table(DmMessages::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(DmMessages::Table)
+                    .drop_column(DmMessages::AttachmentId)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx_dm_attachments_object_key")
+                    .table(DmAttachments::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .drop_table(Table::drop().table(DmAttachments::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum DmAttachments {
+    Table,
+    Id,
+    ConversationId,
+    UploaderUserId,
+    Bucket,
+    ObjectKey,
+    Kind,
+    ContentType,
+    ByteSize,
+    Width,
+    Height,
+    Sha256,
+    OriginalFilename,
+    CreatedAt,
+}
+
+#[derive(DeriveIden)]
+enum DmMessages {
+    Table,
+    AttachmentId,
+}
+
+#[derive(DeriveIden)]
+enum DmConversations {
+    Table,
+    Id,
+}
+
+#[derive(DeriveIden)]
+enum Users {
+    Table,
+    Id,
+}